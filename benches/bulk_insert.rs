@@ -0,0 +1,83 @@
+//! Bulk insert chunk sizes and end-to-end pipeline throughput
+//!
+//! `BENCH_PIPELINE_RUNS` sets the synthetic dataset size for the pipeline
+//! benchmark (default 100k runs).
+mod common;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use tokio::runtime::Runtime;
+
+use sd_its_benchmark::{
+    repositories::{GpuRepository, PerformanceResultRepository, RunsRepository},
+    services::{
+        data_processing::{
+            process_gpu_service::ProcessGpuService,
+            process_its_service::ProcessItsService,
+            save_data_service::{runs_from_data, SaveDataService, DEFAULT_CHUNK_SIZE},
+        },
+        ingest::parse_benchmark_export,
+    },
+};
+
+const CHUNK_SIZE_RUNS: usize = 10_000;
+const DEFAULT_PIPELINE_RUNS: usize = 100_000;
+
+fn bench_bulk_create_chunk_sizes(c: &mut Criterion) {
+    let runtime = Runtime::new().unwrap();
+    let (pool, _dir) = runtime.block_on(common::create_bench_pool());
+    let service = SaveDataService::new(RunsRepository::new(pool.clone()), pool.clone());
+
+    let (_, data) = parse_benchmark_export(&common::benchmark_export(CHUNK_SIZE_RUNS)).unwrap();
+    let runs = runs_from_data(data);
+
+    let mut group = c.benchmark_group("bulk_create_runs");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(runs.len() as u64));
+    for chunk_size in [50, 100, 250, DEFAULT_CHUNK_SIZE, 1_000, 2_500] {
+        group.bench_with_input(BenchmarkId::from_parameter(chunk_size), &chunk_size, |b, &chunk_size| {
+            // Roll back after every iteration so each one inserts into an empty table
+            b.to_async(&runtime).iter(|| async {
+                let mut tx = pool.begin().await.unwrap();
+                let result = service.insert_runs_in_chunks_tx(runs.clone(), chunk_size, &mut tx).await.unwrap();
+                assert_eq!(result.inserted_runs.len(), runs.len());
+                tx.rollback().await.unwrap();
+            })
+        });
+    }
+    group.finish();
+}
+
+fn bench_pipeline_throughput(c: &mut Criterion) {
+    let run_count = std::env::var("BENCH_PIPELINE_RUNS")
+        .ok()
+        .and_then(|count| count.parse().ok())
+        .unwrap_or(DEFAULT_PIPELINE_RUNS);
+
+    let runtime = Runtime::new().unwrap();
+    let (pool, _dir) = runtime.block_on(common::create_bench_pool());
+    let export = common::benchmark_export(run_count);
+
+    let save_data = SaveDataService::new(RunsRepository::new(pool.clone()), pool.clone());
+    let process_its = ProcessItsService::new(
+        RunsRepository::new(pool.clone()),
+        PerformanceResultRepository::new(pool.clone()),
+        pool.clone(),
+    );
+    let process_gpu = ProcessGpuService::new(RunsRepository::new(pool.clone()), GpuRepository::new(pool.clone()), pool.clone());
+
+    let mut group = c.benchmark_group("pipeline");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(run_count as u64));
+    group.bench_function(BenchmarkId::new("save_its_gpu", run_count), |b| {
+        b.to_async(&runtime).iter(|| async {
+            let saved = save_data.save_data_in_batches(export.clone(), DEFAULT_CHUNK_SIZE).await.unwrap();
+            assert_eq!(saved.inserted_rows, run_count);
+            process_its.process_its().await.unwrap();
+            process_gpu.process_gpu().await.unwrap();
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_bulk_create_chunk_sizes, bench_pipeline_throughput);
+criterion_main!(benches);
@@ -0,0 +1,66 @@
+//! Synthetic benchmark data shared by the bench targets
+#![allow(dead_code)]
+
+use serde_json::{json, Value};
+use sqlx::SqlitePool;
+use tempfile::TempDir;
+
+use sd_its_benchmark::config::database::{DatabaseConfig, create_pool, initialize_database};
+
+const DEVICES: &[&str] = &[
+    "device:NVIDIA driver:535.98 NVIDIA GeForce RTX 4090 24GB",
+    "device:NVIDIA driver:531.61 NVIDIA GeForce RTX 3060 12GB",
+    "device:NVIDIA driver:470.82.01 NVIDIA GeForce RTX 3080 Laptop GPU 16GB",
+    "device:AMD driver:23.5.2 AMD Radeon RX 7900 XTX 24GB",
+    "device:Intel driver:31.0.101 Intel Arc A770 16GB",
+];
+
+const MODELS: &[&str] = &["sd_xl_base_1.0", "v1-5-pruned-emaonly", "dreamshaper_8", "juggernautXL_v9"];
+
+/// A device_info string in the format submitted by the webui extension
+pub fn device_info(index: usize) -> &'static str {
+    DEVICES[index % DEVICES.len()]
+}
+
+/// An ITS string with `samples` slash-separated measurements
+pub fn its_string(index: usize, samples: usize) -> String {
+    (0..samples)
+        .map(|sample| format!("{:.2}", 1.0 + ((index * 7 + sample * 13) % 400) as f64 / 10.0))
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// A webui-format benchmark export with `count` runs
+pub fn benchmark_export(count: usize) -> Vec<u8> {
+    let runs: Vec<Value> = (0..count)
+        .map(|index| {
+            json!({
+                "timestamp": format!("2024-01-{:02}T10:00:00Z", index % 28 + 1),
+                "vram_usage": its_string(index, 3),
+                "info": format!("app:stable-diffusion-webui updated:2023-05-18 hash:{:08x} url:https://github.com/AUTOMATIC1111/stable-diffusion-webui/tree/master", index),
+                "system_info": "arch:x86_64 cpu:AMD64 system:Windows release:10 python:3.10.6",
+                "model_info": "torch:2.0.1+cu118 autocast half xformers:0.0.20 diffusers: transformers:4.25.1",
+                "device_info": device_info(index),
+                "xformers": "0.0.20",
+                "model_name": MODELS[index % MODELS.len()],
+                "user": format!("user{}", index % 500),
+                "notes": "",
+            })
+        })
+        .collect();
+
+    serde_json::to_vec(&runs).expect("synthetic export serializes")
+}
+
+/// A fresh file-backed database; pipeline services read through the pool while
+/// holding a transaction, so an in-memory database with one connection won't do
+pub async fn create_bench_pool() -> (SqlitePool, TempDir) {
+    let dir = tempfile::tempdir().expect("Failed to create bench directory");
+    let db_config = DatabaseConfig {
+        url: format!("sqlite:{}?mode=rwc", dir.path().join("bench.db").display()),
+        ..DatabaseConfig::default()
+    };
+    let pool = create_pool(&db_config).await.expect("Failed to create bench pool");
+    initialize_database(&pool).await.expect("Failed to initialize bench database");
+    (pool, dir)
+}
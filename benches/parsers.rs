@@ -0,0 +1,54 @@
+//! Parser hot paths: every run goes through these during processing
+mod common;
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+use sd_its_benchmark::services::parsers::{GpuInfoParser, PerformanceParser};
+
+fn bench_its_parsing(c: &mut Criterion) {
+    let mut group = c.benchmark_group("its_parsing");
+    for samples in [1, 3, 10, 50] {
+        let inputs: Vec<String> = (0..1_000).map(|index| common::its_string(index, samples)).collect();
+        group.throughput(Throughput::Elements(inputs.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(samples), &inputs, |b, inputs| {
+            b.iter(|| {
+                for input in inputs {
+                    black_box(PerformanceParser::parse(black_box(input)));
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+fn bench_device_info_parsing(c: &mut Criterion) {
+    let inputs: Vec<&str> = (0..1_000).map(common::device_info).collect();
+
+    let mut group = c.benchmark_group("device_info_parsing");
+    group.throughput(Throughput::Elements(inputs.len() as u64));
+    group.bench_function("parse", |b| {
+        b.iter(|| {
+            for input in &inputs {
+                black_box(GpuInfoParser::parse(black_box(input)));
+            }
+        })
+    });
+    group.bench_function("parse_vram_gb", |b| {
+        b.iter(|| {
+            for input in &inputs {
+                black_box(GpuInfoParser::parse_vram_gb(black_box(input)));
+            }
+        })
+    });
+    group.bench_function("is_laptop_gpu", |b| {
+        b.iter(|| {
+            for input in &inputs {
+                black_box(GpuInfoParser::is_laptop_gpu(black_box(input)));
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_its_parsing, bench_device_info_parsing);
+criterion_main!(benches);
@@ -0,0 +1,31 @@
+//! Feed arbitrary bytes through the shared tokenizer and every parser built on it
+//!
+//! Run with `cargo +nightly fuzz run tokenizer` from the repository root.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use sd_its_benchmark::services::parsers::{
+    tokenize, AppDetailsParser, GpuInfoParser, LibrariesParser, SystemInfoParser, Token,
+};
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(input) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    let tokens: Vec<&str> = tokenize(input).map(Token::as_str).collect();
+    assert_eq!(tokens.join(" "), input);
+
+    let app_details = AppDetailsParser::parse(input);
+    assert_eq!(AppDetailsParser::parse(&AppDetailsParser::get_summary(&app_details)), app_details);
+
+    let system_info = SystemInfoParser::parse(input);
+    assert_eq!(SystemInfoParser::parse(&SystemInfoParser::get_summary(&system_info)), system_info);
+
+    let libraries = LibrariesParser::parse(input);
+    assert_eq!(LibrariesParser::parse(&LibrariesParser::get_summary(&libraries)), libraries);
+
+    let gpu_info = GpuInfoParser::parse(input);
+    assert_eq!(GpuInfoParser::parse(&GpuInfoParser::get_summary(&gpu_info)), gpu_info);
+});
@@ -0,0 +1,13 @@
+-- Create RunScore table
+CREATE TABLE IF NOT EXISTS RunScore (
+    id INTEGER PRIMARY KEY,
+    run_id INTEGER,
+    score REAL,
+    normalized_its REAL,
+    model_factor REAL,
+    vram_gb REAL,
+    FOREIGN KEY (run_id) REFERENCES runs(id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_RunScore_run_id ON RunScore (run_id);
+CREATE INDEX IF NOT EXISTS idx_RunScore_score ON RunScore (score);
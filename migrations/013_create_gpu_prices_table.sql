@@ -0,0 +1,9 @@
+-- Create gpu_prices table (manually maintained reference prices per base GPU)
+CREATE TABLE IF NOT EXISTS gpu_prices (
+    id INTEGER PRIMARY KEY,
+    base_gpu_id INTEGER NOT NULL UNIQUE,
+    price_usd REAL NOT NULL,
+    source TEXT,
+    recorded_at TEXT,
+    FOREIGN KEY (base_gpu_id) REFERENCES GPUBase(id)
+);
@@ -0,0 +1,10 @@
+-- Create AppRelease table
+CREATE TABLE IF NOT EXISTS AppRelease (
+    id INTEGER PRIMARY KEY,
+    run_id INTEGER,
+    release_channel TEXT,
+    release_month TEXT,
+    FOREIGN KEY (run_id) REFERENCES runs(id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_AppRelease_run_id ON AppRelease (run_id);
@@ -0,0 +1,21 @@
+-- Create UploadSession table for resumable uploads
+CREATE TABLE IF NOT EXISTS UploadSession (
+    id TEXT PRIMARY KEY,
+    file_name TEXT NOT NULL,
+    status TEXT NOT NULL,
+    total_parts INTEGER,
+    total_size INTEGER,
+    message TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+-- Create UploadPart table tracking the parts received for each session
+CREATE TABLE IF NOT EXISTS UploadPart (
+    id INTEGER PRIMARY KEY,
+    upload_id TEXT NOT NULL,
+    part_number INTEGER NOT NULL,
+    size INTEGER NOT NULL,
+    UNIQUE (upload_id, part_number),
+    FOREIGN KEY (upload_id) REFERENCES UploadSession(id)
+);
@@ -0,0 +1,6 @@
+-- Create DatasetMeta key/value table describing the current dataset
+CREATE TABLE IF NOT EXISTS DatasetMeta (
+    key TEXT PRIMARY KEY,
+    value TEXT,
+    updated_at TEXT NOT NULL
+);
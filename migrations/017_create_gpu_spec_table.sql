@@ -0,0 +1,9 @@
+-- Create GPUSpec table (reference specs per base GPU, seeded from the bundled GPU catalog)
+CREATE TABLE IF NOT EXISTS GPUSpec (
+    id INTEGER PRIMARY KEY,
+    base_gpu_id INTEGER NOT NULL UNIQUE,
+    vram_gb REAL,
+    architecture TEXT,
+    launch_year INTEGER,
+    FOREIGN KEY (base_gpu_id) REFERENCES GPUBase(id)
+);
@@ -0,0 +1,7 @@
+-- Create ModelMapHash table (known checkpoint hashes per ModelMap entry)
+CREATE TABLE IF NOT EXISTS ModelMapHash (
+    id INTEGER PRIMARY KEY,
+    model_map_id INTEGER NOT NULL,
+    hash TEXT NOT NULL UNIQUE,
+    FOREIGN KEY (model_map_id) REFERENCES ModelMap(id)
+);
@@ -0,0 +1,8 @@
+-- Move architecture from GPUSpec onto GPUBase and add the product generation
+ALTER TABLE GPUBase ADD COLUMN architecture TEXT;
+ALTER TABLE GPUBase ADD COLUMN generation TEXT;
+
+UPDATE GPUBase
+SET architecture = (SELECT architecture FROM GPUSpec WHERE GPUSpec.base_gpu_id = GPUBase.id);
+
+ALTER TABLE GPUSpec DROP COLUMN architecture;
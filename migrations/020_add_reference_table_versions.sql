@@ -0,0 +1,4 @@
+-- Row versions for optimistic concurrency control on mutable reference tables
+ALTER TABLE ModelMap ADD COLUMN version INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE GPUMap ADD COLUMN version INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE GPUBase ADD COLUMN version INTEGER NOT NULL DEFAULT 1;
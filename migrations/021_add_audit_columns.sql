@@ -0,0 +1,31 @@
+-- Audit timestamps recording when each dataset row was created and last updated
+ALTER TABLE runs ADD COLUMN created_at TEXT;
+ALTER TABLE runs ADD COLUMN updated_at TEXT;
+ALTER TABLE performanceResult ADD COLUMN created_at TEXT;
+ALTER TABLE performanceResult ADD COLUMN updated_at TEXT;
+ALTER TABLE AppDetails ADD COLUMN created_at TEXT;
+ALTER TABLE AppDetails ADD COLUMN updated_at TEXT;
+ALTER TABLE AppRelease ADD COLUMN created_at TEXT;
+ALTER TABLE AppRelease ADD COLUMN updated_at TEXT;
+ALTER TABLE SystemInfo ADD COLUMN created_at TEXT;
+ALTER TABLE SystemInfo ADD COLUMN updated_at TEXT;
+ALTER TABLE Libraries ADD COLUMN created_at TEXT;
+ALTER TABLE Libraries ADD COLUMN updated_at TEXT;
+ALTER TABLE GPU ADD COLUMN created_at TEXT;
+ALTER TABLE GPU ADD COLUMN updated_at TEXT;
+ALTER TABLE RunMoreDetails ADD COLUMN created_at TEXT;
+ALTER TABLE RunMoreDetails ADD COLUMN updated_at TEXT;
+ALTER TABLE ModelMap ADD COLUMN created_at TEXT;
+ALTER TABLE ModelMap ADD COLUMN updated_at TEXT;
+ALTER TABLE ModelMapHash ADD COLUMN created_at TEXT;
+ALTER TABLE ModelMapHash ADD COLUMN updated_at TEXT;
+ALTER TABLE GPUMap ADD COLUMN created_at TEXT;
+ALTER TABLE GPUMap ADD COLUMN updated_at TEXT;
+ALTER TABLE GPUBase ADD COLUMN created_at TEXT;
+ALTER TABLE GPUBase ADD COLUMN updated_at TEXT;
+ALTER TABLE GPUSpec ADD COLUMN created_at TEXT;
+ALTER TABLE GPUSpec ADD COLUMN updated_at TEXT;
+ALTER TABLE RunScore ADD COLUMN created_at TEXT;
+ALTER TABLE RunScore ADD COLUMN updated_at TEXT;
+ALTER TABLE gpu_prices ADD COLUMN created_at TEXT;
+ALTER TABLE gpu_prices ADD COLUMN updated_at TEXT;
@@ -0,0 +1,22 @@
+-- Create tags table (free-form labels such as "overclocked" or "docker")
+CREATE TABLE IF NOT EXISTS tags (
+    id INTEGER PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE,
+    created_at TEXT,
+    updated_at TEXT
+);
+
+-- Create run_tags join table; source is 'manual' for user tags and 'auto' for tags derived during processing
+CREATE TABLE IF NOT EXISTS run_tags (
+    id INTEGER PRIMARY KEY,
+    run_id INTEGER NOT NULL,
+    tag_id INTEGER NOT NULL,
+    source TEXT NOT NULL DEFAULT 'manual',
+    created_at TEXT,
+    updated_at TEXT,
+    UNIQUE (run_id, tag_id),
+    FOREIGN KEY (run_id) REFERENCES runs(id),
+    FOREIGN KEY (tag_id) REFERENCES tags(id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_run_tags_tag_id ON run_tags (tag_id);
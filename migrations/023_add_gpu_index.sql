@@ -0,0 +1,4 @@
+-- Position of the GPU within its run; runs reporting several GPUs get one row per GPU
+ALTER TABLE GPU ADD COLUMN gpu_index INTEGER NOT NULL DEFAULT 0;
+
+CREATE INDEX IF NOT EXISTS idx_GPU_run_id_gpu_index ON GPU (run_id, gpu_index);
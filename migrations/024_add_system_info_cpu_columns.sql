@@ -0,0 +1,5 @@
+-- CPU fields normalized from SystemInfo.cpu, e.g. "Intel(R) Core(TM) i7-10700K" -> Intel / i7 / 10 / i7-10700K
+ALTER TABLE SystemInfo ADD COLUMN cpu_vendor TEXT;
+ALTER TABLE SystemInfo ADD COLUMN cpu_family TEXT;
+ALTER TABLE SystemInfo ADD COLUMN cpu_generation INTEGER;
+ALTER TABLE SystemInfo ADD COLUMN cpu_model TEXT;
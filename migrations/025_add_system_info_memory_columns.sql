@@ -0,0 +1,4 @@
+-- System memory and platform bitness, present in v2 WebUI uploads
+ALTER TABLE SystemInfo ADD COLUMN ram_gb REAL;
+ALTER TABLE SystemInfo ADD COLUMN swap_gb REAL;
+ALTER TABLE SystemInfo ADD COLUMN os_bits INTEGER;
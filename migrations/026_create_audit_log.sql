@@ -0,0 +1,9 @@
+-- Create audit_log table recording admin data fixes; details is a JSON description of the change
+CREATE TABLE IF NOT EXISTS audit_log (
+    id INTEGER PRIMARY KEY,
+    action TEXT NOT NULL,
+    details TEXT,
+    created_at TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_audit_log_action ON audit_log (action);
@@ -0,0 +1,20 @@
+-- One row per run in the one-to-one child tables (per run and GPU position in GPU), so
+-- re-running a processing step replaces rows instead of duplicating them. Existing
+-- duplicates are not deleted: the index creation fails until they are resolved, e.g.
+-- DELETE FROM <table> WHERE run_id IS NOT NULL AND id NOT IN
+--     (SELECT MAX(id) FROM <table> WHERE run_id IS NOT NULL GROUP BY run_id)
+-- keeps the newest row per run (GROUP BY run_id, gpu_index for GPU).
+
+DROP INDEX IF EXISTS idx_performanceResult_run_id;
+DROP INDEX IF EXISTS idx_AppDetails_run_id;
+DROP INDEX IF EXISTS idx_SystemInfo_run_id;
+DROP INDEX IF EXISTS idx_Libraries_run_id;
+DROP INDEX IF EXISTS idx_GPU_run_id_gpu_index;
+DROP INDEX IF EXISTS idx_RunMoreDetails_run_id;
+
+CREATE UNIQUE INDEX IF NOT EXISTS uq_performanceResult_run_id ON performanceResult (run_id);
+CREATE UNIQUE INDEX IF NOT EXISTS uq_AppDetails_run_id ON AppDetails (run_id);
+CREATE UNIQUE INDEX IF NOT EXISTS uq_SystemInfo_run_id ON SystemInfo (run_id);
+CREATE UNIQUE INDEX IF NOT EXISTS uq_Libraries_run_id ON Libraries (run_id);
+CREATE UNIQUE INDEX IF NOT EXISTS uq_GPU_run_id_gpu_index ON GPU (run_id, gpu_index);
+CREATE UNIQUE INDEX IF NOT EXISTS uq_RunMoreDetails_run_id ON RunMoreDetails (run_id);
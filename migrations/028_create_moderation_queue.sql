@@ -0,0 +1,13 @@
+-- Create moderation_queue table holding runs whose notes were flagged by the notes filter;
+-- reasons is a comma-separated list of the rules broken, status is pending, approved or rejected
+CREATE TABLE IF NOT EXISTS moderation_queue (
+    id INTEGER PRIMARY KEY,
+    run_id INTEGER NOT NULL UNIQUE,
+    reasons TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending',
+    created_at TEXT,
+    reviewed_at TEXT,
+    FOREIGN KEY (run_id) REFERENCES runs(id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_moderation_queue_status ON moderation_queue (status);
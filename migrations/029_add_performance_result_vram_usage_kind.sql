@@ -0,0 +1,5 @@
+-- What the run's vram_usage string held: "its" samples, "memory" values, or "unknown" when nothing parsed;
+-- only "its" rows carry an avg_its
+ALTER TABLE performanceResult ADD COLUMN vram_usage_kind TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_performanceResult_vram_usage_kind ON performanceResult (vram_usage_kind);
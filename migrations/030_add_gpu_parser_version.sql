@@ -0,0 +1,2 @@
+-- Version of the device_info parser that produced each GPU row; NULL for rows written before versions were recorded
+ALTER TABLE GPU ADD COLUMN parser_version INTEGER;
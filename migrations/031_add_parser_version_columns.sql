@@ -0,0 +1,4 @@
+-- Version of the parser that produced each derived row; NULL for rows written before versions were recorded
+ALTER TABLE AppDetails ADD COLUMN parser_version INTEGER;
+ALTER TABLE SystemInfo ADD COLUMN parser_version INTEGER;
+ALTER TABLE Libraries ADD COLUMN parser_version INTEGER;
@@ -0,0 +1,11 @@
+-- Create dataset_versions table recording, for each successful pipeline step, the
+-- revision hashes of the reference data and processing rules it ran against
+CREATE TABLE IF NOT EXISTS dataset_versions (
+    id INTEGER PRIMARY KEY,
+    pipeline_step TEXT NOT NULL,
+    model_map_hash TEXT NOT NULL,
+    gpu_map_hash TEXT NOT NULL,
+    gpu_base_hash TEXT NOT NULL,
+    rules_hash TEXT NOT NULL,
+    created_at TEXT
+);
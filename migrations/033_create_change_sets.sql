@@ -0,0 +1,19 @@
+-- Create change_sets table recording each application of a data fix, and change_set_items
+-- holding the value each touched row had before it so the fix can be reverted
+CREATE TABLE IF NOT EXISTS change_sets (
+    id INTEGER PRIMARY KEY,
+    action TEXT NOT NULL,
+    created_at TEXT,
+    reverted_at TEXT
+);
+
+CREATE TABLE IF NOT EXISTS change_set_items (
+    id INTEGER PRIMARY KEY,
+    change_set_id INTEGER NOT NULL,
+    row_id INTEGER NOT NULL,
+    old_value TEXT,
+    new_value TEXT,
+    FOREIGN KEY (change_set_id) REFERENCES change_sets(id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_change_set_items_change_set_id ON change_set_items (change_set_id);
@@ -0,0 +1,3 @@
+-- Integrated GPUs (Intel UHD/Iris, AMD Vega and Radeon Graphics APUs), flagged by the update-gpu-integrated-info step;
+-- NULL until the step has run
+ALTER TABLE GPU ADD COLUMN is_integrated BOOLEAN;
@@ -0,0 +1,3 @@
+-- GPUs that ran on cloud hardware (datacenter parts such as A100/H100/T4, or a cloud driver or kernel),
+-- flagged by the update-gpu-cloud-info step; NULL until the step has run
+ALTER TABLE GPU ADD COLUMN is_cloud BOOLEAN;
@@ -0,0 +1,13 @@
+-- Create pipeline_events table logging every stage the pipeline orchestrator executes
+CREATE TABLE IF NOT EXISTS pipeline_events (
+    id INTEGER PRIMARY KEY,
+    stage TEXT NOT NULL,
+    started_at TEXT NOT NULL,
+    finished_at TEXT NOT NULL,
+    rows_in INTEGER,
+    rows_out INTEGER,
+    status TEXT NOT NULL,
+    error TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_pipeline_events_stage ON pipeline_events (stage);
@@ -0,0 +1,9 @@
+-- Create UploadSchemaReport table recording how each upload's keys differ from its format's schema
+CREATE TABLE IF NOT EXISTS UploadSchemaReport (
+    upload_id TEXT PRIMARY KEY,
+    file_name TEXT NOT NULL,
+    format TEXT NOT NULL,
+    unknown_keys TEXT NOT NULL,
+    missing_keys TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
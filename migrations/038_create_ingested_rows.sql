@@ -0,0 +1,5 @@
+-- Create ingested_rows table holding a content hash of every ingested benchmark row, so syncs skip rows already imported
+CREATE TABLE IF NOT EXISTS ingested_rows (
+    content_hash TEXT PRIMARY KEY,
+    ingested_at TEXT NOT NULL
+);
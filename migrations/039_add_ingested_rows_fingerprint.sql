@@ -0,0 +1,6 @@
+-- Fingerprint of each ingested row: its content hash leaving out the ITS, so appended
+-- rows re-reporting a known benchmark with different ITS count as updates; NULL for rows
+-- recorded before fingerprints were kept
+ALTER TABLE ingested_rows ADD COLUMN fingerprint TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_ingested_rows_fingerprint ON ingested_rows (fingerprint);
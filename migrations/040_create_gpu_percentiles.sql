@@ -0,0 +1,14 @@
+-- Create gpu_percentiles table caching the avg_its distribution of each GPU device,
+-- rebuilt by POST /api/admin/refresh-percentiles
+CREATE TABLE IF NOT EXISTS gpu_percentiles (
+    gpu TEXT PRIMARY KEY,
+    run_count INTEGER NOT NULL,
+    min_its REAL NOT NULL,
+    p10_its REAL NOT NULL,
+    p25_its REAL NOT NULL,
+    median_its REAL NOT NULL,
+    p75_its REAL NOT NULL,
+    p90_its REAL NOT NULL,
+    max_its REAL NOT NULL,
+    computed_at TEXT NOT NULL
+);
@@ -0,0 +1,16 @@
+-- Create health_samples table holding the periodic database, pool and request rate samples
+-- served by GET /api/admin/health-history
+CREATE TABLE IF NOT EXISTS health_samples (
+    id INTEGER PRIMARY KEY,
+    sampled_at TEXT NOT NULL,
+    db_size_bytes INTEGER NOT NULL,
+    row_counts TEXT NOT NULL,
+    pool_connections INTEGER NOT NULL,
+    pool_idle_connections INTEGER NOT NULL,
+    pool_max_connections INTEGER NOT NULL,
+    requests INTEGER NOT NULL,
+    server_errors INTEGER NOT NULL,
+    requests_per_minute REAL NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_health_samples_sampled_at ON health_samples (sampled_at);
@@ -0,0 +1,4 @@
+-- Tenant whose dataset a run belongs to; runs ingested before tenants existed are the default tenant's
+ALTER TABLE runs ADD COLUMN tenant_id TEXT NOT NULL DEFAULT 'default';
+
+CREATE INDEX IF NOT EXISTS idx_runs_tenant_id ON runs (tenant_id);
@@ -0,0 +1,5 @@
+-- The url lowercased, trimmed and stripped of trailing slashes, NULL when nothing is left; matched by the
+-- fix-app-names rules. Generated, so it follows every write of url, process-app-details' included
+ALTER TABLE AppDetails ADD COLUMN url_normalized TEXT GENERATED ALWAYS AS (NULLIF(rtrim(lower(trim(url, char(32, 9, 10, 13))), '/'), '')) VIRTUAL;
+
+CREATE INDEX IF NOT EXISTS idx_AppDetails_url_normalized ON AppDetails (url_normalized);
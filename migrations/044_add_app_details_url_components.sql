@@ -0,0 +1,7 @@
+-- Host, owner and repository parsed from url, and the project a fork is credited to; set by canonicalize-app-urls
+ALTER TABLE AppDetails ADD COLUMN url_host TEXT;
+ALTER TABLE AppDetails ADD COLUMN url_owner TEXT;
+ALTER TABLE AppDetails ADD COLUMN url_repo TEXT;
+ALTER TABLE AppDetails ADD COLUMN project TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_AppDetails_project ON AppDetails (project);
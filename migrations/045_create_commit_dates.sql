@@ -0,0 +1,13 @@
+-- Create commit_dates table caching GitHub's commit date of each app hash, filled by
+-- POST /api/admin/enrich-commit-dates; a NULL commit_date marks a commit GitHub does not know
+CREATE TABLE IF NOT EXISTS commit_dates (
+    owner TEXT NOT NULL,
+    repo TEXT NOT NULL,
+    hash TEXT NOT NULL,
+    commit_date TEXT,
+    looked_up_at TEXT NOT NULL,
+    PRIMARY KEY (owner, repo, hash)
+);
+
+-- Commit date of the app hash, copied from commit_dates
+ALTER TABLE AppDetails ADD COLUMN commit_date TEXT;
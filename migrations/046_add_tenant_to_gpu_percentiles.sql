@@ -0,0 +1,18 @@
+-- Key the percentile cache by tenant as well; it is rebuilt by POST /api/admin/refresh-percentiles,
+-- so the old rows are dropped rather than copied
+DROP TABLE IF EXISTS gpu_percentiles;
+
+CREATE TABLE gpu_percentiles (
+    tenant_id TEXT NOT NULL DEFAULT 'default',
+    gpu TEXT NOT NULL,
+    run_count INTEGER NOT NULL,
+    min_its REAL NOT NULL,
+    p10_its REAL NOT NULL,
+    p25_its REAL NOT NULL,
+    median_its REAL NOT NULL,
+    p75_its REAL NOT NULL,
+    p90_its REAL NOT NULL,
+    max_its REAL NOT NULL,
+    computed_at TEXT NOT NULL,
+    PRIMARY KEY (tenant_id, gpu)
+);
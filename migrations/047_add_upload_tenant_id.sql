@@ -0,0 +1,3 @@
+-- Tenant an upload session and its schema report belong to; those created before tenants existed are the default tenant's
+ALTER TABLE UploadSession ADD COLUMN tenant_id TEXT NOT NULL DEFAULT 'default';
+ALTER TABLE UploadSchemaReport ADD COLUMN tenant_id TEXT NOT NULL DEFAULT 'default';
@@ -1,6 +1,7 @@
 // Configuration management module
 pub mod settings;
 pub mod database;
+pub mod schema;
 pub mod utils;
 
 pub use settings::Settings;
@@ -1,8 +1,19 @@
-use sqlx::{sqlite::SqlitePoolOptions, SqlitePool};
+use sqlx::{sqlite::{SqliteConnectOptions, SqlitePoolOptions}, SqlitePool};
+use std::str::FromStr;
 use std::time::Duration;
 use std::path::Path;
 use std::env;
 
+use crate::repositories::dataset_meta_repository::DATASET_TABLES;
+
+/// Database the server opens when `DATABASE_URL` is unset
+pub const DEFAULT_DATABASE_URL: &str = "sqlite:./my-database.db";
+
+/// The URL the server's pool opens: `DATABASE_URL`, or [`DEFAULT_DATABASE_URL`]
+pub fn database_url() -> String {
+    env::var("DATABASE_URL").unwrap_or_else(|_| DEFAULT_DATABASE_URL.to_string())
+}
+
 pub struct DatabaseConfig {
     pub url: String,
     pub max_connections: u32,
@@ -13,7 +24,7 @@ pub struct DatabaseConfig {
 
 impl Default for DatabaseConfig {
     fn default() -> Self {
-        let url = env::var("DATABASE_URL").unwrap_or_else(|_| "sqlite:./my-database.db".to_string());
+        let url = database_url();
         
         // Extract file path from SQLite URL and ensure it's absolute
         if let Some(file_path) = url.strip_prefix("sqlite:") {
@@ -46,13 +57,37 @@ impl Default for DatabaseConfig {
     }
 }
 
+impl DatabaseConfig {
+    /// A private in-memory database
+    ///
+    /// It lives only as long as its connection, so the pool holds exactly one
+    /// and never lets it expire. Code holding a transaction on it must not also
+    /// query the pool, or it waits for its own connection.
+    pub fn in_memory() -> Self {
+        let never = Duration::from_secs(365 * 24 * 3600);
+        Self {
+            url: "sqlite::memory:".to_string(),
+            max_connections: 1,
+            min_connections: 1,
+            idle_timeout: never,
+            max_lifetime: never,
+        }
+    }
+}
+
+/// Open a pool on `config.url`
+///
+/// Every connection gets a `REGEXP` function, so queries can filter with
+/// `column REGEXP ?`; SQLite has the operator but no implementation of its own.
 pub async fn create_pool(config: &DatabaseConfig) -> Result<SqlitePool, sqlx::Error> {
+    let options = SqliteConnectOptions::from_str(&config.url)?.with_regexp();
+
     SqlitePoolOptions::new()
         .max_connections(config.max_connections)
         .min_connections(config.min_connections)
         .idle_timeout(config.idle_timeout)
         .max_lifetime(config.max_lifetime)
-        .connect(&config.url)
+        .connect_with(options)
         .await
 }
 
@@ -76,7 +111,9 @@ pub async fn initialize_database(pool: &SqlitePool) -> Result<(), sqlx::Error> {
             xformers TEXT,
             model_name TEXT,
             user TEXT,
-            notes TEXT
+            notes TEXT,
+            created_at TEXT,
+            updated_at TEXT
         )
         "#
     ).execute(pool).await?;
@@ -89,6 +126,9 @@ pub async fn initialize_database(pool: &SqlitePool) -> Result<(), sqlx::Error> {
             run_id INTEGER,
             its TEXT,
             avg_its REAL,
+            vram_usage_kind TEXT,
+            created_at TEXT,
+            updated_at TEXT,
             FOREIGN KEY (run_id) REFERENCES runs(id)
         )
         "#
@@ -104,6 +144,14 @@ pub async fn initialize_database(pool: &SqlitePool) -> Result<(), sqlx::Error> {
             updated TEXT,
             hash TEXT,
             url TEXT,
+            url_host TEXT,
+            url_owner TEXT,
+            url_repo TEXT,
+            project TEXT,
+            commit_date TEXT,
+            parser_version INTEGER,
+            created_at TEXT,
+            updated_at TEXT,
             FOREIGN KEY (run_id) REFERENCES runs(id)
         )
         "#
@@ -120,6 +168,16 @@ pub async fn initialize_database(pool: &SqlitePool) -> Result<(), sqlx::Error> {
             system TEXT,
             release TEXT,
             python TEXT,
+            cpu_vendor TEXT,
+            cpu_family TEXT,
+            cpu_generation INTEGER,
+            cpu_model TEXT,
+            ram_gb REAL,
+            swap_gb REAL,
+            os_bits INTEGER,
+            parser_version INTEGER,
+            created_at TEXT,
+            updated_at TEXT,
             FOREIGN KEY (run_id) REFERENCES runs(id)
         )
         "#
@@ -136,6 +194,9 @@ pub async fn initialize_database(pool: &SqlitePool) -> Result<(), sqlx::Error> {
             xformers1 TEXT,
             diffusers TEXT,
             transformers TEXT,
+            parser_version INTEGER,
+            created_at TEXT,
+            updated_at TEXT,
             FOREIGN KEY (run_id) REFERENCES runs(id)
         )
         "#
@@ -152,6 +213,12 @@ pub async fn initialize_database(pool: &SqlitePool) -> Result<(), sqlx::Error> {
             gpu_chip TEXT,
             brand TEXT,
             isLaptop BOOLEAN,
+            is_integrated BOOLEAN,
+            is_cloud BOOLEAN,
+            gpu_index INTEGER NOT NULL DEFAULT 0,
+            parser_version INTEGER,
+            created_at TEXT,
+            updated_at TEXT,
             FOREIGN KEY (run_id) REFERENCES runs(id)
         )
         "#
@@ -168,6 +235,8 @@ pub async fn initialize_database(pool: &SqlitePool) -> Result<(), sqlx::Error> {
             user TEXT,
             notes TEXT,
             ModelMapId INTEGER,
+            created_at TEXT,
+            updated_at TEXT,
             FOREIGN KEY (run_id) REFERENCES runs(id)
         )
         "#
@@ -179,7 +248,10 @@ pub async fn initialize_database(pool: &SqlitePool) -> Result<(), sqlx::Error> {
         CREATE TABLE IF NOT EXISTS ModelMap (
             id INTEGER PRIMARY KEY,
             model_name TEXT,
-            base_model TEXT
+            base_model TEXT,
+            version INTEGER NOT NULL DEFAULT 1,
+            created_at TEXT,
+            updated_at TEXT
         )
         "#
     ).execute(pool).await?;
@@ -190,7 +262,10 @@ pub async fn initialize_database(pool: &SqlitePool) -> Result<(), sqlx::Error> {
         CREATE TABLE IF NOT EXISTS GPUMap (
             id INTEGER PRIMARY KEY,
             gpu_name TEXT,
-            base_gpu_id INTEGER REFERENCES GPUBase(id)
+            base_gpu_id INTEGER REFERENCES GPUBase(id),
+            version INTEGER NOT NULL DEFAULT 1,
+            created_at TEXT,
+            updated_at TEXT
         )
         "#
     ).execute(pool).await?;
@@ -201,24 +276,488 @@ pub async fn initialize_database(pool: &SqlitePool) -> Result<(), sqlx::Error> {
         CREATE TABLE IF NOT EXISTS GPUBase (
             id INTEGER PRIMARY KEY AUTOINCREMENT,
             name TEXT NOT NULL UNIQUE,
-            brand TEXT
+            brand TEXT,
+            architecture TEXT,
+            generation TEXT,
+            version INTEGER NOT NULL DEFAULT 1,
+            created_at TEXT,
+            updated_at TEXT
         )
         "#
     ).execute(pool).await?;
 
+    // Databases created before GPUBase carried architecture/generation need the columns added
+    add_column_if_missing(pool, "GPUBase", "architecture", "TEXT").await?;
+    add_column_if_missing(pool, "GPUBase", "generation", "TEXT").await?;
+
+    // Row versions used for optimistic concurrency on the mutable reference tables
+    add_column_if_missing(pool, "ModelMap", "version", "INTEGER NOT NULL DEFAULT 1").await?;
+    add_column_if_missing(pool, "GPUMap", "version", "INTEGER NOT NULL DEFAULT 1").await?;
+    add_column_if_missing(pool, "GPUBase", "version", "INTEGER NOT NULL DEFAULT 1").await?;
+
+    // Databases created before multi-GPU runs were split into one row per GPU
+    add_column_if_missing(pool, "GPU", "gpu_index", "INTEGER NOT NULL DEFAULT 0").await?;
+
+    // Integrated GPU flag set by the update-gpu-integrated-info step
+    add_column_if_missing(pool, "GPU", "is_integrated", "BOOLEAN").await?;
+
+    // Cloud hardware flag set by the update-gpu-cloud-info step
+    add_column_if_missing(pool, "GPU", "is_cloud", "BOOLEAN").await?;
+
+    // Parser version behind each derived row, so backfills can find rows from an older parser
+    add_column_if_missing(pool, "GPU", "parser_version", "INTEGER").await?;
+    add_column_if_missing(pool, "AppDetails", "parser_version", "INTEGER").await?;
+    add_column_if_missing(pool, "SystemInfo", "parser_version", "INTEGER").await?;
+    add_column_if_missing(pool, "Libraries", "parser_version", "INTEGER").await?;
+
+    // CPU fields normalized from SystemInfo.cpu by the normalize-cpu-info step
+    add_column_if_missing(pool, "SystemInfo", "cpu_vendor", "TEXT").await?;
+    add_column_if_missing(pool, "SystemInfo", "cpu_family", "TEXT").await?;
+    add_column_if_missing(pool, "SystemInfo", "cpu_generation", "INTEGER").await?;
+    add_column_if_missing(pool, "SystemInfo", "cpu_model", "TEXT").await?;
+
+    // Whether vram_usage held ITS samples or memory values, recorded by the process-its step
+    add_column_if_missing(pool, "performanceResult", "vram_usage_kind", "TEXT").await?;
+
+    // Memory and bitness from v2 WebUI uploads
+    add_column_if_missing(pool, "SystemInfo", "ram_gb", "REAL").await?;
+    add_column_if_missing(pool, "SystemInfo", "swap_gb", "REAL").await?;
+    add_column_if_missing(pool, "SystemInfo", "os_bits", "INTEGER").await?;
+
+    // Tenant whose dataset a run belongs to, see settings.tenancy
+    add_column_if_missing(pool, "runs", "tenant_id", "TEXT NOT NULL DEFAULT 'default'").await?;
+
+    // URL components and fork-attributed project set by the canonicalize-app-urls step
+    add_column_if_missing(pool, "AppDetails", "url_host", "TEXT").await?;
+    add_column_if_missing(pool, "AppDetails", "url_owner", "TEXT").await?;
+    add_column_if_missing(pool, "AppDetails", "url_repo", "TEXT").await?;
+    add_column_if_missing(pool, "AppDetails", "project", "TEXT").await?;
+
+    // Commit date of the app hash, copied from commit_dates by the enrich-commit-dates step
+    add_column_if_missing(pool, "AppDetails", "commit_date", "TEXT").await?;
+
+    // Normalized URL the fix-app-names rules match, kept in step with url by SQLite
+    add_column_if_missing(
+        pool,
+        "AppDetails",
+        "url_normalized",
+        "TEXT GENERATED ALWAYS AS (NULLIF(rtrim(lower(trim(url, char(32, 9, 10, 13))), '/'), '')) VIRTUAL",
+    )
+    .await?;
+
+    // Create RunScore table
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS RunScore (
+            id INTEGER PRIMARY KEY,
+            run_id INTEGER,
+            score REAL,
+            normalized_its REAL,
+            model_factor REAL,
+            vram_gb REAL,
+            created_at TEXT,
+            updated_at TEXT,
+            FOREIGN KEY (run_id) REFERENCES runs(id)
+        )
+        "#
+    ).execute(pool).await?;
+
+    // Create gpu_prices table
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS gpu_prices (
+            id INTEGER PRIMARY KEY,
+            base_gpu_id INTEGER NOT NULL UNIQUE,
+            price_usd REAL NOT NULL,
+            source TEXT,
+            recorded_at TEXT,
+            created_at TEXT,
+            updated_at TEXT,
+            FOREIGN KEY (base_gpu_id) REFERENCES GPUBase(id)
+        )
+        "#
+    ).execute(pool).await?;
+
+    // Create AppRelease table
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS AppRelease (
+            id INTEGER PRIMARY KEY,
+            run_id INTEGER,
+            release_channel TEXT,
+            release_month TEXT,
+            created_at TEXT,
+            updated_at TEXT,
+            FOREIGN KEY (run_id) REFERENCES runs(id)
+        )
+        "#
+    ).execute(pool).await?;
+
+    // Create UploadSession table
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS UploadSession (
+            id TEXT PRIMARY KEY,
+            file_name TEXT NOT NULL,
+            status TEXT NOT NULL,
+            total_parts INTEGER,
+            total_size INTEGER,
+            message TEXT,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        )
+        "#
+    ).execute(pool).await?;
+
+    // Create UploadPart table
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS UploadPart (
+            id INTEGER PRIMARY KEY,
+            upload_id TEXT NOT NULL,
+            part_number INTEGER NOT NULL,
+            size INTEGER NOT NULL,
+            UNIQUE (upload_id, part_number),
+            FOREIGN KEY (upload_id) REFERENCES UploadSession(id)
+        )
+        "#
+    ).execute(pool).await?;
+
+    // Create UploadSchemaReport table
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS UploadSchemaReport (
+            upload_id TEXT PRIMARY KEY,
+            file_name TEXT NOT NULL,
+            format TEXT NOT NULL,
+            unknown_keys TEXT NOT NULL,
+            missing_keys TEXT NOT NULL,
+            created_at TEXT NOT NULL
+        )
+        "#
+    ).execute(pool).await?;
+
+    // Tenant an upload session and its schema report belong to, see settings.tenancy
+    add_column_if_missing(pool, "UploadSession", "tenant_id", "TEXT NOT NULL DEFAULT 'default'").await?;
+    add_column_if_missing(pool, "UploadSchemaReport", "tenant_id", "TEXT NOT NULL DEFAULT 'default'").await?;
+
+    // Create DatasetMeta table
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS DatasetMeta (
+            key TEXT PRIMARY KEY,
+            value TEXT,
+            updated_at TEXT NOT NULL
+        )
+        "#
+    ).execute(pool).await?;
+
+    // Create GPUSpec table
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS GPUSpec (
+            id INTEGER PRIMARY KEY,
+            base_gpu_id INTEGER NOT NULL UNIQUE,
+            vram_gb REAL,
+            launch_year INTEGER,
+            created_at TEXT,
+            updated_at TEXT,
+            FOREIGN KEY (base_gpu_id) REFERENCES GPUBase(id)
+        )
+        "#
+    ).execute(pool).await?;
+
+    // Create ModelMapHash table
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS ModelMapHash (
+            id INTEGER PRIMARY KEY,
+            model_map_id INTEGER NOT NULL,
+            hash TEXT NOT NULL UNIQUE,
+            created_at TEXT,
+            updated_at TEXT,
+            FOREIGN KEY (model_map_id) REFERENCES ModelMap(id)
+        )
+        "#
+    ).execute(pool).await?;
+
+    // Create tags table
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS tags (
+            id INTEGER PRIMARY KEY,
+            name TEXT NOT NULL UNIQUE,
+            created_at TEXT,
+            updated_at TEXT
+        )
+        "#
+    ).execute(pool).await?;
+
+    // Create run_tags table
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS run_tags (
+            id INTEGER PRIMARY KEY,
+            run_id INTEGER NOT NULL,
+            tag_id INTEGER NOT NULL,
+            source TEXT NOT NULL DEFAULT 'manual',
+            created_at TEXT,
+            updated_at TEXT,
+            UNIQUE (run_id, tag_id),
+            FOREIGN KEY (run_id) REFERENCES runs(id),
+            FOREIGN KEY (tag_id) REFERENCES tags(id)
+        )
+        "#
+    ).execute(pool).await?;
+
+    // Create audit_log table
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS audit_log (
+            id INTEGER PRIMARY KEY,
+            action TEXT NOT NULL,
+            details TEXT,
+            created_at TEXT
+        )
+        "#
+    ).execute(pool).await?;
+
+    // Create moderation_queue table
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS moderation_queue (
+            id INTEGER PRIMARY KEY,
+            run_id INTEGER NOT NULL UNIQUE,
+            reasons TEXT NOT NULL,
+            status TEXT NOT NULL DEFAULT 'pending',
+            created_at TEXT,
+            reviewed_at TEXT,
+            FOREIGN KEY (run_id) REFERENCES runs(id)
+        )
+        "#
+    ).execute(pool).await?;
+
+    // Create dataset_versions table
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS dataset_versions (
+            id INTEGER PRIMARY KEY,
+            pipeline_step TEXT NOT NULL,
+            model_map_hash TEXT NOT NULL,
+            gpu_map_hash TEXT NOT NULL,
+            gpu_base_hash TEXT NOT NULL,
+            rules_hash TEXT NOT NULL,
+            created_at TEXT
+        )
+        "#
+    ).execute(pool).await?;
+
+    // Create change_sets and change_set_items tables
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS change_sets (
+            id INTEGER PRIMARY KEY,
+            action TEXT NOT NULL,
+            created_at TEXT,
+            reverted_at TEXT
+        )
+        "#
+    ).execute(pool).await?;
+
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS change_set_items (
+            id INTEGER PRIMARY KEY,
+            change_set_id INTEGER NOT NULL,
+            row_id INTEGER NOT NULL,
+            old_value TEXT,
+            new_value TEXT,
+            FOREIGN KEY (change_set_id) REFERENCES change_sets(id)
+        )
+        "#
+    ).execute(pool).await?;
+
+    // Create pipeline_events table
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS pipeline_events (
+            id INTEGER PRIMARY KEY,
+            stage TEXT NOT NULL,
+            started_at TEXT NOT NULL,
+            finished_at TEXT NOT NULL,
+            rows_in INTEGER,
+            rows_out INTEGER,
+            status TEXT NOT NULL,
+            error TEXT
+        )
+        "#
+    ).execute(pool).await?;
+
+    // Create ingested_rows table
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS ingested_rows (
+            content_hash TEXT PRIMARY KEY,
+            ingested_at TEXT NOT NULL,
+            fingerprint TEXT
+        )
+        "#
+    ).execute(pool).await?;
+
+    // Fingerprints used by the append delta report, added to tables that predate them
+    add_column_if_missing(pool, "ingested_rows", "fingerprint", "TEXT").await?;
+
+    // Drop a gpu_percentiles cache that predates tenants; it is rebuilt on refresh
+    let percentiles_have_tenant: bool =
+        sqlx::query_scalar("SELECT COUNT(*) > 0 FROM pragma_table_xinfo('gpu_percentiles') WHERE name = 'tenant_id'")
+            .fetch_one(pool)
+            .await?;
+    if !percentiles_have_tenant {
+        sqlx::query("DROP TABLE IF EXISTS gpu_percentiles").execute(pool).await?;
+    }
+
+    // Create gpu_percentiles table
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS gpu_percentiles (
+            tenant_id TEXT NOT NULL DEFAULT 'default',
+            gpu TEXT NOT NULL,
+            run_count INTEGER NOT NULL,
+            min_its REAL NOT NULL,
+            p10_its REAL NOT NULL,
+            p25_its REAL NOT NULL,
+            median_its REAL NOT NULL,
+            p75_its REAL NOT NULL,
+            p90_its REAL NOT NULL,
+            max_its REAL NOT NULL,
+            computed_at TEXT NOT NULL,
+            PRIMARY KEY (tenant_id, gpu)
+        )
+        "#
+    ).execute(pool).await?;
+
+    // Create health_samples table
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS health_samples (
+            id INTEGER PRIMARY KEY,
+            sampled_at TEXT NOT NULL,
+            db_size_bytes INTEGER NOT NULL,
+            row_counts TEXT NOT NULL,
+            pool_connections INTEGER NOT NULL,
+            pool_idle_connections INTEGER NOT NULL,
+            pool_max_connections INTEGER NOT NULL,
+            requests INTEGER NOT NULL,
+            server_errors INTEGER NOT NULL,
+            requests_per_minute REAL NOT NULL
+        )
+        "#
+    ).execute(pool).await?;
+
+    // Create commit_dates table
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS commit_dates (
+            owner TEXT NOT NULL,
+            repo TEXT NOT NULL,
+            hash TEXT NOT NULL,
+            commit_date TEXT,
+            looked_up_at TEXT NOT NULL,
+            PRIMARY KEY (owner, repo, hash)
+        )
+        "#
+    ).execute(pool).await?;
+
+    // Audit timestamps on every dataset table, added to databases that predate them
+    for table in DATASET_TABLES {
+        add_column_if_missing(pool, table, "created_at", "TEXT").await?;
+        add_column_if_missing(pool, table, "updated_at", "TEXT").await?;
+    }
+
     // Create indexes
-    sqlx::query("CREATE INDEX IF NOT EXISTS idx_performanceResult_run_id ON performanceResult (run_id)").execute(pool).await?;
-    sqlx::query("CREATE INDEX IF NOT EXISTS idx_AppDetails_run_id ON AppDetails (run_id)").execute(pool).await?;
-    sqlx::query("CREATE INDEX IF NOT EXISTS idx_SystemInfo_run_id ON SystemInfo (run_id)").execute(pool).await?;
-    sqlx::query("CREATE INDEX IF NOT EXISTS idx_Libraries_run_id ON Libraries (run_id)").execute(pool).await?;
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_runs_tenant_id ON runs (tenant_id)").execute(pool).await?;
     sqlx::query("CREATE INDEX IF NOT EXISTS idx_GPU_run_id ON GPU (run_id)").execute(pool).await?;
     sqlx::query("CREATE INDEX IF NOT EXISTS idx_GPU_device ON GPU (device)").execute(pool).await?;
-    sqlx::query("CREATE INDEX IF NOT EXISTS idx_RunMoreDetails_run_id ON RunMoreDetails (run_id)").execute(pool).await?;
     sqlx::query("CREATE INDEX IF NOT EXISTS idx_RunMoreDetails_model_name ON RunMoreDetails (model_name)").execute(pool).await?;
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_RunScore_run_id ON RunScore (run_id)").execute(pool).await?;
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_RunScore_score ON RunScore (score)").execute(pool).await?;
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_AppRelease_run_id ON AppRelease (run_id)").execute(pool).await?;
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_run_tags_tag_id ON run_tags (tag_id)").execute(pool).await?;
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_audit_log_action ON audit_log (action)").execute(pool).await?;
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_performanceResult_vram_usage_kind ON performanceResult (vram_usage_kind)").execute(pool).await?;
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_moderation_queue_status ON moderation_queue (status)").execute(pool).await?;
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_change_set_items_change_set_id ON change_set_items (change_set_id)").execute(pool).await?;
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_pipeline_events_stage ON pipeline_events (stage)").execute(pool).await?;
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_ingested_rows_fingerprint ON ingested_rows (fingerprint)").execute(pool).await?;
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_health_samples_sampled_at ON health_samples (sampled_at)").execute(pool).await?;
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_AppDetails_url_normalized ON AppDetails (url_normalized)").execute(pool).await?;
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_AppDetails_project ON AppDetails (project)").execute(pool).await?;
+
+    // One row per run in the one-to-one child tables (per run and GPU position in GPU)
+    create_unique_run_index(pool, "performanceResult", "run_id", "idx_performanceResult_run_id").await?;
+    create_unique_run_index(pool, "AppDetails", "run_id", "idx_AppDetails_run_id").await?;
+    create_unique_run_index(pool, "SystemInfo", "run_id", "idx_SystemInfo_run_id").await?;
+    create_unique_run_index(pool, "Libraries", "run_id", "idx_Libraries_run_id").await?;
+    create_unique_run_index(pool, "GPU", "run_id, gpu_index", "idx_GPU_run_id_gpu_index").await?;
+    create_unique_run_index(pool, "RunMoreDetails", "run_id", "idx_RunMoreDetails_run_id").await?;
     
     Ok(())
 }
 
+/// Create the unique index `uq_<table>_<columns>`, replacing the plain index it supersedes
+///
+/// Databases that predate the index may hold duplicate rows. Those are never
+/// deleted here: startup fails, naming the table and how many rows to resolve.
+async fn create_unique_run_index(pool: &SqlitePool, table: &str, columns: &str, superseded_index: &str) -> Result<(), sqlx::Error> {
+    let index = format!("uq_{}_{}", table, columns.replace(", ", "_"));
+    let exists: bool = sqlx::query_scalar("SELECT COUNT(*) > 0 FROM sqlite_master WHERE type = 'index' AND name = ?")
+        .bind(&index)
+        .fetch_one(pool)
+        .await?;
+
+    if !exists {
+        let duplicates: i64 = sqlx::query_scalar(&format!(
+            "SELECT COUNT(*) FROM {table} WHERE run_id IS NOT NULL AND id NOT IN (SELECT MAX(id) FROM {table} WHERE run_id IS NOT NULL GROUP BY {columns})"
+        ))
+        .fetch_one(pool)
+        .await?;
+        if duplicates > 0 {
+            return Err(sqlx::Error::Configuration(
+                format!(
+                    "Cannot create unique index {}: {} has {} row(s) duplicating another row with the same ({}). \
+                     Remove them before starting, e.g. keep the newest row per run with: \
+                     DELETE FROM {table} WHERE run_id IS NOT NULL AND id NOT IN (SELECT MAX(id) FROM {table} WHERE run_id IS NOT NULL GROUP BY {columns})",
+                    index, table, duplicates, columns
+                )
+                .into(),
+            ));
+        }
+        sqlx::query(&format!("DROP INDEX IF EXISTS {}", superseded_index)).execute(pool).await?;
+        sqlx::query(&format!("CREATE UNIQUE INDEX {} ON {} ({})", index, table, columns)).execute(pool).await?;
+    }
+
+    Ok(())
+}
+
+/// Add a column to an existing table unless it is already there
+///
+/// Looks in `pragma_table_xinfo`, as `pragma_table_info` leaves out generated columns.
+async fn add_column_if_missing(pool: &SqlitePool, table: &str, column: &str, definition: &str) -> Result<(), sqlx::Error> {
+    let exists: bool = sqlx::query_scalar("SELECT COUNT(*) > 0 FROM pragma_table_xinfo(?) WHERE name = ?")
+        .bind(table)
+        .bind(column)
+        .fetch_one(pool)
+        .await?;
+
+    if !exists {
+        sqlx::query(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition))
+            .execute(pool)
+            .await?;
+    }
+
+    Ok(())
+}
+
 // Add some sample queries for sqlx to analyze
 pub async fn get_all_runs(pool: &SqlitePool) -> Result<Vec<(i32, String)>, sqlx::Error> {
     sqlx::query_as::<_, (i32, String)>("SELECT id, model_name FROM runs LIMIT 10")
@@ -0,0 +1,111 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use sqlx::SqlitePool;
+
+use crate::config::database::{create_pool, DatabaseConfig};
+
+/// Table sqlx records applied migrations in, which the app itself never queries
+const MIGRATIONS_TABLE: &str = "_sqlx_migrations";
+
+/// The tables of a database and the columns of each, generated columns included
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SchemaManifest {
+    tables: BTreeMap<String, BTreeSet<String>>,
+}
+
+/// What a database lacks of the schema the app was compiled against
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SchemaReport {
+    pub missing_tables: Vec<String>,
+    /// As `table.column`, for tables that exist
+    pub missing_columns: Vec<String>,
+}
+
+impl SchemaManifest {
+    /// The schema the migrations build, the one the `query!` macros were checked against
+    ///
+    /// Generated by applying the migrations embedded at compile time to a
+    /// throwaway in-memory database, so it never drifts from them.
+    pub async fn expected() -> Result<Self, sqlx::Error> {
+        let pool = create_pool(&DatabaseConfig::in_memory()).await?;
+        sqlx::migrate!("./migrations").run(&pool).await?;
+        let manifest = Self::read(&pool).await;
+        pool.close().await;
+
+        manifest
+    }
+
+    /// Introspect the tables in `sqlite_master` and their columns
+    pub async fn read(pool: &SqlitePool) -> Result<Self, sqlx::Error> {
+        let columns: Vec<(String, String)> = sqlx::query_as(
+            r#"
+            SELECT m.name, c.name
+            FROM sqlite_master m, pragma_table_xinfo(m.name) c
+            WHERE m.type = 'table' AND m.name NOT LIKE 'sqlite_%' AND m.name <> ?
+            "#,
+        )
+        .bind(MIGRATIONS_TABLE)
+        .fetch_all(pool)
+        .await?;
+
+        let mut tables: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        for (table, column) in columns {
+            tables.entry(table).or_default().insert(column);
+        }
+
+        Ok(Self { tables })
+    }
+
+    /// The tables and columns of this manifest that `actual` lacks; extra ones in `actual` are fine
+    ///
+    /// Names are compared case-insensitively, as SQLite resolves them.
+    pub fn missing_from(&self, actual: &SchemaManifest) -> SchemaReport {
+        let mut report = SchemaReport::default();
+        for (table, columns) in &self.tables {
+            let actual_columns = actual
+                .tables
+                .iter()
+                .find(|(actual_table, _)| actual_table.eq_ignore_ascii_case(table))
+                .map(|(_, actual_columns)| actual_columns);
+            let Some(actual_columns) = actual_columns else {
+                report.missing_tables.push(table.clone());
+                continue;
+            };
+            report.missing_columns.extend(
+                columns
+                    .iter()
+                    .filter(|column| !actual_columns.iter().any(|actual| actual.eq_ignore_ascii_case(column)))
+                    .map(|column| format!("{}.{}", table, column)),
+            );
+        }
+
+        report
+    }
+}
+
+impl SchemaReport {
+    pub fn is_complete(&self) -> bool {
+        self.missing_tables.is_empty() && self.missing_columns.is_empty()
+    }
+
+    /// One line per missing table or column, for the startup log
+    pub fn problems(&self) -> Vec<String> {
+        self.missing_tables
+            .iter()
+            .map(|table| format!("Missing table {}", table))
+            .chain(self.missing_columns.iter().map(|column| format!("Missing column {}", column)))
+            .collect()
+    }
+}
+
+/// Check that the database holds every table and column the migrations define
+///
+/// Run after `initialize_database`: a table created by an older release is
+/// left as it was, and a column it lacks would otherwise only surface as a 500
+/// on the first query touching it.
+pub async fn verify_schema(pool: &SqlitePool) -> Result<SchemaReport, sqlx::Error> {
+    let expected = SchemaManifest::expected().await?;
+    let actual = SchemaManifest::read(pool).await?;
+
+    Ok(expected.missing_from(&actual))
+}
@@ -1,5 +1,7 @@
 use config::{Config, ConfigError, Environment as ConfigEnvironment, File};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::net::IpAddr;
 use std::path::PathBuf;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -10,6 +12,52 @@ pub struct Settings {
     pub application: ApplicationConfig,
     #[serde(default)]
     pub file_upload: FileUploadConfig,
+    #[serde(default)]
+    pub scoring: ScoringConfig,
+    #[serde(default)]
+    pub error_messages: ErrorMessagesConfig,
+    #[serde(default)]
+    pub body_limits: BodyLimitsConfig,
+    #[serde(default)]
+    pub sharing: SharingConfig,
+    #[serde(default)]
+    pub badges: BadgesConfig,
+    #[serde(default)]
+    pub feed: FeedConfig,
+    #[serde(default)]
+    pub parsing: ParsingConfig,
+    #[serde(default)]
+    pub anonymization: AnonymizationConfig,
+    #[serde(default)]
+    pub pii_scan: PiiScanConfig,
+    #[serde(default)]
+    pub notes_filter: NotesFilterConfig,
+    #[serde(default)]
+    pub laptop_detection: LaptopDetectionConfig,
+    #[serde(default)]
+    pub app_attribution: AppAttributionConfig,
+    #[serde(default)]
+    pub features: FeatureFlags,
+    #[serde(default)]
+    pub compression: CompressionConfig,
+    #[serde(default)]
+    pub pipeline: PipelineConfig,
+    #[serde(default)]
+    pub alerts: AlertsConfig,
+    #[serde(default)]
+    pub remote_ingest: RemoteIngestConfig,
+    #[serde(default)]
+    pub upstream_sync: UpstreamSyncConfig,
+    #[serde(default)]
+    pub commit_dates: CommitDatesConfig,
+    #[serde(default)]
+    pub health_history: HealthHistoryConfig,
+    #[serde(default)]
+    pub demo: DemoConfig,
+    #[serde(default)]
+    pub tenancy: TenancyConfig,
+    #[serde(default)]
+    pub retention: RetentionConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -30,6 +78,20 @@ pub struct DatabaseSettings {
     pub idle_timeout: u64,  // Duration in seconds
     pub max_lifetime: u64,  // Duration in seconds
     pub connection_timeout: u64,  // Duration in seconds
+    /// Repository queries running longer than this are abandoned with a timeout error
+    #[serde(default = "default_query_timeout_secs")]
+    pub query_timeout_secs: u64,
+    /// Repository queries running longer than this are logged as slow
+    #[serde(default = "default_slow_query_ms")]
+    pub slow_query_ms: u64,
+}
+
+fn default_query_timeout_secs() -> u64 {
+    30
+}
+
+fn default_slow_query_ms() -> u64 {
+    500
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -40,6 +102,18 @@ pub struct LoggingConfig {
     pub file_path: Option<PathBuf>,
     pub max_file_size: usize,
     pub max_files: usize,
+    #[serde(default)]
+    pub request_logging: RequestLoggingConfig,
+}
+
+/// Per-request debug logging of method, path, sampled JSON body, status and latency
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RequestLoggingConfig {
+    pub enabled: bool,
+    pub max_body_bytes: usize,
+    /// Headers whose values are replaced with `[REDACTED]`, matched case-insensitively
+    pub redacted_headers: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -50,6 +124,9 @@ pub struct ApplicationConfig {
     pub upload_dir: PathBuf,
     pub max_upload_size: usize,
     pub allowed_file_types: Vec<String>,
+    /// Built dashboard SPA served at `/`, e.g. "dist"; not served when unset
+    #[serde(default)]
+    pub dashboard_dir: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -58,6 +135,319 @@ pub struct FileUploadConfig {
     pub allowed_content_types: Vec<String>,
     pub temp_dir: PathBuf,
     pub cleanup_interval_seconds: u64,
+    #[serde(default = "default_max_resumable_size_mb")]
+    pub max_resumable_size_mb: usize,
+    /// Most rows one upload may hold, whichever route it comes in by
+    #[serde(default = "default_max_rows")]
+    pub max_rows: usize,
+    /// Most bytes any one field of an uploaded row may hold
+    #[serde(default = "default_max_field_bytes")]
+    pub max_field_bytes: usize,
+}
+
+fn default_max_resumable_size_mb() -> usize {
+    1024
+}
+
+fn default_max_rows() -> usize {
+    500_000
+}
+
+fn default_max_field_bytes() -> usize {
+    64 * 1024
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScoringConfig {
+    pub its_weight: f64,
+    pub vram_weight: f64,
+    pub default_model_factor: f64,
+    pub model_base_factors: HashMap<String, f64>,
+}
+
+/// Request body size limits for the upload and JSON route groups
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BodyLimitsConfig {
+    pub upload_max_mb: usize,
+    pub json_max_kb: usize,
+}
+
+impl BodyLimitsConfig {
+    pub fn upload_max_bytes(&self) -> usize {
+        self.upload_max_mb * 1024 * 1024
+    }
+
+    pub fn json_max_bytes(&self) -> usize {
+        self.json_max_kb * 1024
+    }
+}
+
+/// Placeholder signing secret; every environment but development and demo must override it (`APP_SHARING__SECRET`)
+pub const DEFAULT_SHARE_SECRET: &str = "insecure-development-share-secret";
+
+/// Signed, unauthenticated permalinks to a single run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SharingConfig {
+    /// HMAC key for share tokens; rotating it revokes every issued link
+    pub secret: String,
+    /// Days a share link stays valid, at most 36500; links never expire when unset
+    pub token_ttl_days: Option<u64>,
+}
+
+/// Placeholder username salt; every environment but development and demo must override it (`APP_ANONYMIZATION__SALT`)
+pub const DEFAULT_ANONYMIZATION_SALT: &str = "insecure-development-anonymization-salt";
+
+/// Pseudonymous usernames and stripped notes in published runs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AnonymizationConfig {
+    /// Anonymize every public export, not only requests with `anonymize=true`
+    pub enabled: bool,
+    /// Per-instance HMAC key for username hashes; rotating it changes every pseudonym
+    pub salt: String,
+}
+
+/// Scanning of uploaded notes and info for emails, IP addresses and home directory paths
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PiiScanConfig {
+    pub mode: PiiScanMode,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum PiiScanMode {
+    /// Store uploads as submitted
+    #[serde(rename = "off")]
+    Off,
+    /// Replace personal data with placeholders and report how much was replaced
+    #[serde(rename = "redact")]
+    Redact,
+    /// Refuse uploads containing personal data
+    #[serde(rename = "reject")]
+    Reject,
+}
+
+/// Content checks on uploaded notes; flagged runs wait in the moderation queue
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotesFilterConfig {
+    /// External links (`http://`, `https://`, `www.`)
+    pub links: NotesFilterAction,
+    /// HTML tags, markdown links/images and BBCode
+    pub markup: NotesFilterAction,
+    /// Words from `profanity_words`
+    pub profanity: NotesFilterAction,
+    /// Words matched whole and ignoring case by the profanity check
+    pub profanity_words: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum NotesFilterAction {
+    /// Let the notes through
+    #[serde(rename = "off")]
+    Off,
+    /// Store the run but hold it in the moderation queue until reviewed
+    #[serde(rename = "flag")]
+    Flag,
+    /// Refuse the upload
+    #[serde(rename = "block")]
+    Block,
+}
+
+/// Device name patterns the GPU laptop info step flags as laptop parts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LaptopDetectionConfig {
+    /// Matched anywhere in the device name, ignoring case; a GPU is credited to the first match
+    pub patterns: Vec<String>,
+}
+
+/// Forks the canonicalize-app-urls step credits to the project they were forked from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppAttributionConfig {
+    /// Fork `owner/repo` -> upstream `owner/repo`, compared ignoring case; unlisted repositories are their own project
+    pub fork_map: HashMap<String, String>,
+}
+
+/// Encodings response compression can use
+pub const COMPRESSION_ENCODINGS: &[&str] = &["gzip", "br"];
+
+/// Response compression, negotiated per request from Accept-Encoding
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CompressionConfig {
+    pub enabled: bool,
+    /// Encodings offered to clients, any of `COMPRESSION_ENCODINGS`
+    pub encodings: Vec<String>,
+    /// Responses with a known size below this are sent uncompressed
+    pub min_size_bytes: u16,
+}
+
+/// Stages `POST /api/run-pipeline` runs when the request names none
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PipelineConfig {
+    /// Stage names to run, e.g. without "update-gpu-laptop-info"; every stage when unset
+    pub enabled_stages: Option<Vec<String>>,
+}
+
+/// Thresholds checked after each pipeline run; unset thresholds are not checked
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AlertsConfig {
+    /// Alerts are POSTed here as JSON; without it they are only logged
+    pub webhook_url: Option<String>,
+    pub webhook_timeout_seconds: u64,
+    /// Alert when more than this share of the recent stage executions failed
+    pub max_error_rate_percent: Option<f64>,
+    /// Most recent stage executions the error rate is computed over
+    pub error_rate_window: i64,
+    /// Alert when a stage's rows_out falls below its minimum, by stage name
+    pub min_rows_out: HashMap<String, i64>,
+    /// Alert when a stage takes longer than this
+    pub max_stage_duration_ms: Option<f64>,
+}
+
+/// Downloads of benchmark files by `POST /api/ingest-url`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RemoteIngestConfig {
+    /// Hosts files may be downloaded from, matched exactly; redirects must stay on them too
+    pub allowed_hosts: Vec<String>,
+    pub timeout_seconds: u64,
+}
+
+/// Periodic import of new rows from the public upstream benchmark data
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UpstreamSyncConfig {
+    /// Run the sync on a schedule; `POST /api/admin/upstream-sync` works either way
+    pub enabled: bool,
+    /// Benchmark JSON to pull, on a host in `remote_ingest.allowed_hosts`
+    pub url: Option<String>,
+    pub interval_minutes: u64,
+    /// Run the pipeline's enabled stages after a sync appended rows
+    pub run_pipeline: bool,
+}
+
+/// Commit dates of app hashes, looked up on GitHub by `POST /api/admin/enrich-commit-dates`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CommitDatesConfig {
+    /// Allow lookups; dates already cached are applied either way
+    pub enabled: bool,
+    /// Root of the GitHub REST API
+    pub api_url: String,
+    /// Access token, raising GitHub's limit from 60 to 5000 requests an hour
+    pub token: Option<String>,
+    /// Lookups are spaced out to stay under this rate
+    pub requests_per_minute: u32,
+    /// Lookups per call; the rest are left for the next call
+    pub max_lookups: usize,
+    pub timeout_seconds: u64,
+}
+
+/// Periodic health samples served by `GET /api/admin/health-history`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HealthHistoryConfig {
+    /// Record samples in the background
+    pub enabled: bool,
+    pub interval_seconds: u64,
+    /// Samples older than this are deleted as new ones are recorded
+    pub retention_days: u64,
+}
+
+/// Scheduled pruning of staged uploads, backups and logs, previewed by `GET /api/admin/storage-cleanup`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetentionConfig {
+    /// Prune in the background; the preview endpoint works either way
+    pub enabled: bool,
+    pub interval_seconds: u64,
+    /// Files last modified longer ago than this are deleted
+    pub max_age_days: u64,
+    /// Quota over all three areas; the oldest backups and logs are deleted until they fit
+    pub max_total_bytes: Option<u64>,
+    /// Where the deployment's backup job writes database backups; the server itself never does
+    pub backup_dir: PathBuf,
+}
+
+/// Sample data of the `demo` environment, which runs on an in-memory database
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DemoConfig {
+    /// Synthetic runs ingested and processed at startup
+    pub sample_rows: usize,
+}
+
+/// Separate datasets per community, selected by API key
+///
+/// Runs and everything read from them are scoped to the tenant; reference data
+/// such as the maps is shared, and operator endpoints act on every tenant.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TenancyConfig {
+    /// Resolve tenants from the `X-API-Key` header; off, every run belongs to the default tenant
+    pub enabled: bool,
+    /// API key to tenant id; requests without a key use the default tenant
+    pub api_keys: HashMap<String, String>,
+}
+
+/// Optional route groups that can be switched off per environment
+///
+/// A disabled feature's routes are not mounted, and its handlers refuse
+/// requests with 404 as well. The export routes stay mounted, as their paths
+/// would otherwise match the `{id}` routes of the maps.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FeatureFlags {
+    /// Benchmark uploads from anyone: `/api/upload` and the resumable `/api/uploads` routes
+    pub enable_public_submissions: bool,
+    /// CSV exports of the model and GPU mappings
+    pub enable_export: bool,
+}
+
+/// Embeddable SVG badges, served to anonymous clients
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BadgesConfig {
+    /// Seconds a rendered badge is served from cache (and advertised in Cache-Control)
+    pub cache_ttl_seconds: u64,
+    /// Badge requests allowed per client per minute
+    pub rate_limit_per_minute: u32,
+    /// Proxies whose `X-Forwarded-For` names the client; everyone else is limited by peer address
+    pub trusted_proxies: Vec<IpAddr>,
+}
+
+/// Atom feed of newly ingested runs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FeedConfig {
+    /// Public origin the feed and its entry links are served from, without a trailing slash
+    pub base_url: String,
+    pub max_entries: i64,
+}
+
+/// Worker threads parsing run fields ahead of the database writes in processing steps
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ParsingConfig {
+    pub workers: usize,
+    /// Runs handed to a worker at a time
+    pub chunk_size: usize,
+    /// Parsed chunks allowed to wait for the writer before parsing pauses
+    pub queue_depth: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ErrorMessagesConfig {
+    pub default_locale: String,
+    /// JSON catalog merged over the bundled one, same shape: locale -> error code -> message
+    pub catalog_path: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -86,6 +476,9 @@ pub enum Environment {
     Staging,
     #[serde(rename = "production")]
     Production,
+    /// In-memory database seeded with sample data at startup, see `DemoConfig`
+    #[serde(rename = "demo")]
+    Demo,
 }
 
 impl Settings {
@@ -93,6 +486,11 @@ impl Settings {
         let environment = std::env::var("RUST_ENV")
             .unwrap_or_else(|_| "development".into());
 
+        Self::for_environment(&environment)
+    }
+
+    /// Settings layered from `config/default`, `config/<environment>`, `config/local` and `APP__` variables
+    pub fn for_environment(environment: &str) -> Result<Self, ConfigError> {
         let config = Config::builder()
             // Start with default settings
             .add_source(File::with_name("config/default"))
@@ -118,6 +516,10 @@ impl Settings {
     pub fn is_staging(&self) -> bool {
         matches!(self.application.environment, Environment::Staging)
     }
+
+    pub fn is_demo(&self) -> bool {
+        matches!(self.application.environment, Environment::Demo)
+    }
 }
 
 impl Default for Settings {
@@ -128,6 +530,29 @@ impl Default for Settings {
             logging: LoggingConfig::default(),
             application: ApplicationConfig::default(),
             file_upload: FileUploadConfig::default(),
+            scoring: ScoringConfig::default(),
+            error_messages: ErrorMessagesConfig::default(),
+            body_limits: BodyLimitsConfig::default(),
+            sharing: SharingConfig::default(),
+            badges: BadgesConfig::default(),
+            feed: FeedConfig::default(),
+            parsing: ParsingConfig::default(),
+            anonymization: AnonymizationConfig::default(),
+            pii_scan: PiiScanConfig::default(),
+            notes_filter: NotesFilterConfig::default(),
+            laptop_detection: LaptopDetectionConfig::default(),
+            app_attribution: AppAttributionConfig::default(),
+            features: FeatureFlags::default(),
+            compression: CompressionConfig::default(),
+            pipeline: PipelineConfig::default(),
+            alerts: AlertsConfig::default(),
+            remote_ingest: RemoteIngestConfig::default(),
+            upstream_sync: UpstreamSyncConfig::default(),
+            commit_dates: CommitDatesConfig::default(),
+            health_history: HealthHistoryConfig::default(),
+            demo: DemoConfig::default(),
+            tenancy: TenancyConfig::default(),
+            retention: RetentionConfig::default(),
         }
     }
 }
@@ -154,6 +579,8 @@ impl Default for DatabaseSettings {
             idle_timeout: 600,  // 600 seconds (10 minutes)
             max_lifetime: 1800,  // 1800 seconds (30 minutes)
             connection_timeout: 30,  // 30 seconds
+            query_timeout_secs: default_query_timeout_secs(),
+            slow_query_ms: default_slow_query_ms(),
         }
     }
 }
@@ -167,6 +594,23 @@ impl Default for LoggingConfig {
             file_path: Some(PathBuf::from("logs/app.log")),
             max_file_size: 10 * 1024 * 1024, // 10MB
             max_files: 5,
+            request_logging: RequestLoggingConfig::default(),
+        }
+    }
+}
+
+impl Default for RequestLoggingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_body_bytes: 2048,
+            redacted_headers: vec![
+                "authorization".to_string(),
+                "proxy-authorization".to_string(),
+                "cookie".to_string(),
+                "set-cookie".to_string(),
+                "x-api-key".to_string(),
+            ],
         }
     }
 }
@@ -184,6 +628,7 @@ impl Default for ApplicationConfig {
                 "txt".to_string(),
                 "csv".to_string(),
             ],
+            dashboard_dir: None,
         }
     }
 }
@@ -200,6 +645,230 @@ impl Default for FileUploadConfig {
             ],
             temp_dir: PathBuf::from("temp"),
             cleanup_interval_seconds: 3600, // 1 hour
+            max_resumable_size_mb: default_max_resumable_size_mb(),
+            max_rows: default_max_rows(),
+            max_field_bytes: default_max_field_bytes(),
+        }
+    }
+}
+
+impl Default for ScoringConfig {
+    fn default() -> Self {
+        Self {
+            its_weight: 0.8,
+            vram_weight: 0.2,
+            default_model_factor: 1.0,
+            model_base_factors: HashMap::from([
+                ("sd15".to_string(), 1.0),
+                ("sd21".to_string(), 1.2),
+                ("sdxl".to_string(), 2.5),
+            ]),
+        }
+    }
+}
+
+impl Default for BodyLimitsConfig {
+    fn default() -> Self {
+        Self {
+            upload_max_mb: 50,
+            json_max_kb: 256,
+        }
+    }
+}
+
+impl Default for AnonymizationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            salt: DEFAULT_ANONYMIZATION_SALT.to_string(),
+        }
+    }
+}
+
+impl Default for PiiScanConfig {
+    fn default() -> Self {
+        Self { mode: PiiScanMode::Off }
+    }
+}
+
+impl Default for NotesFilterConfig {
+    fn default() -> Self {
+        Self {
+            links: NotesFilterAction::Off,
+            markup: NotesFilterAction::Off,
+            profanity: NotesFilterAction::Off,
+            profanity_words: Vec::new(),
+        }
+    }
+}
+
+impl Default for LaptopDetectionConfig {
+    fn default() -> Self {
+        let patterns = [
+            "Laptop GPU", "Laptop", "Mobile", "Max-Q",
+            // Mobile-only chips whose names carry no laptop marker
+            "MX150", "MX250", "MX330", "MX350", "MX450", "MX550", "MX570",
+            "RX 5500M", "RX 5600M", "RX 6600M", "RX 6650M", "RX 6700M", "RX 6800M", "RX 6850M", "RX 7600M",
+        ];
+
+        Self {
+            patterns: patterns.iter().map(|pattern| pattern.to_string()).collect(),
+        }
+    }
+}
+
+impl Default for AppAttributionConfig {
+    fn default() -> Self {
+        let webui = "automatic1111/stable-diffusion-webui";
+        let forks = [
+            "lshqqytiger/stable-diffusion-webui-directml",
+            "lshqqytiger/stable-diffusion-webui-amdgpu",
+            "anapnoe/stable-diffusion-webui-ux",
+        ];
+
+        Self {
+            fork_map: forks.iter().map(|fork| (fork.to_string(), webui.to_string())).collect(),
+        }
+    }
+}
+
+impl Default for SharingConfig {
+    fn default() -> Self {
+        Self {
+            secret: DEFAULT_SHARE_SECRET.to_string(),
+            token_ttl_days: None,
+        }
+    }
+}
+
+impl Default for CompressionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            encodings: COMPRESSION_ENCODINGS.iter().map(|encoding| encoding.to_string()).collect(),
+            min_size_bytes: 1024,
+        }
+    }
+}
+
+impl Default for AlertsConfig {
+    fn default() -> Self {
+        Self {
+            webhook_url: None,
+            webhook_timeout_seconds: 10,
+            max_error_rate_percent: None,
+            error_rate_window: 20,
+            min_rows_out: HashMap::new(),
+            max_stage_duration_ms: None,
+        }
+    }
+}
+
+impl Default for RemoteIngestConfig {
+    fn default() -> Self {
+        Self {
+            allowed_hosts: vec!["raw.githubusercontent.com".to_string()],
+            timeout_seconds: 60,
+        }
+    }
+}
+
+impl Default for UpstreamSyncConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            url: None,
+            interval_minutes: 1440,
+            run_pipeline: true,
+        }
+    }
+}
+
+impl Default for CommitDatesConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            api_url: "https://api.github.com".to_string(),
+            token: None,
+            // GitHub allows 60 unauthenticated requests an hour
+            requests_per_minute: 1,
+            max_lookups: 50,
+            timeout_seconds: 10,
+        }
+    }
+}
+
+impl Default for HealthHistoryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_seconds: 300,
+            retention_days: 90,
+        }
+    }
+}
+
+impl Default for RetentionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_seconds: 86400,
+            max_age_days: 30,
+            max_total_bytes: None,
+            backup_dir: PathBuf::from("backups"),
+        }
+    }
+}
+
+impl Default for DemoConfig {
+    fn default() -> Self {
+        Self { sample_rows: 1000 }
+    }
+}
+
+impl Default for FeatureFlags {
+    fn default() -> Self {
+        Self {
+            enable_public_submissions: true,
+            enable_export: true,
+        }
+    }
+}
+
+impl Default for BadgesConfig {
+    fn default() -> Self {
+        Self {
+            cache_ttl_seconds: 300,
+            rate_limit_per_minute: 60,
+            trusted_proxies: Vec::new(),
+        }
+    }
+}
+
+impl Default for FeedConfig {
+    fn default() -> Self {
+        Self {
+            base_url: "http://localhost:4000".to_string(),
+            max_entries: 50,
+        }
+    }
+}
+
+impl Default for ParsingConfig {
+    fn default() -> Self {
+        Self {
+            workers: num_cpus::get(),
+            chunk_size: 1000,
+            queue_depth: 2,
+        }
+    }
+}
+
+impl Default for ErrorMessagesConfig {
+    fn default() -> Self {
+        Self {
+            default_locale: "en".to_string(),
+            catalog_path: None,
         }
     }
 }
@@ -210,6 +879,7 @@ impl std::fmt::Display for Environment {
             Environment::Development => write!(f, "development"),
             Environment::Staging => write!(f, "staging"),
             Environment::Production => write!(f, "production"),
+            Environment::Demo => write!(f, "demo"),
         }
     }
 }
@@ -241,6 +911,7 @@ impl std::str::FromStr for Environment {
             "development" | "dev" => Ok(Environment::Development),
             "staging" | "stage" => Ok(Environment::Staging),
             "production" | "prod" => Ok(Environment::Production),
+            "demo" => Ok(Environment::Demo),
             _ => Err(format!("Unknown environment: {}", s)),
         }
     }
@@ -280,6 +951,7 @@ mod tests {
         assert_eq!(Environment::Development.to_string(), "development");
         assert_eq!(Environment::Production.to_string(), "production");
         assert_eq!(Environment::Staging.to_string(), "staging");
+        assert_eq!(Environment::Demo.to_string(), "demo");
     }
 
     #[test]
@@ -288,6 +960,7 @@ mod tests {
         assert_eq!("dev".parse::<Environment>().unwrap(), Environment::Development);
         assert_eq!("production".parse::<Environment>().unwrap(), Environment::Production);
         assert_eq!("prod".parse::<Environment>().unwrap(), Environment::Production);
+        assert_eq!("demo".parse::<Environment>().unwrap(), Environment::Demo);
         assert!("unknown".parse::<Environment>().is_err());
     }
 
@@ -333,5 +1006,9 @@ mod tests {
         assert!(!settings.is_development());
         assert!(!settings.is_production());
         assert!(settings.is_staging());
+
+        settings.application.environment = Environment::Demo;
+        assert!(settings.is_demo());
+        assert!(!settings.is_development());
     }
 }
@@ -1,5 +1,7 @@
 use crate::config::Settings;
-use crate::config::settings::{ServerConfig, DatabaseSettings, LoggingConfig, ApplicationConfig};
+use crate::handlers::pipeline::pipeline_registry;
+use crate::services::retention::StorageRetention;
+use crate::config::settings::{ServerConfig, DatabaseSettings, LoggingConfig, ApplicationConfig, COMPRESSION_ENCODINGS, DEFAULT_ANONYMIZATION_SALT, DEFAULT_SHARE_SECRET};
 use std::path::PathBuf;
 use std::fs;
 use tracing::{info, warn};
@@ -44,6 +46,10 @@ pub fn validate_config(settings: &Settings) -> Result<(), Vec<String>> {
         errors.push("Database min_connections cannot be greater than max_connections".to_string());
     }
 
+    if settings.database.query_timeout_secs == 0 {
+        errors.push("Database query_timeout_secs cannot be 0".to_string());
+    }
+
     // Validate logging configuration
     if settings.logging.max_file_size == 0 {
         errors.push("Logging max_file_size cannot be 0".to_string());
@@ -62,6 +68,221 @@ pub fn validate_config(settings: &Settings) -> Result<(), Vec<String>> {
         errors.push("Application allowed_file_types cannot be empty".to_string());
     }
 
+    if let Some(dashboard_dir) = &settings.application.dashboard_dir
+        && !dashboard_dir.join("index.html").is_file()
+    {
+        errors.push(format!("Application dashboard_dir {:?} has no index.html", dashboard_dir));
+    }
+
+    // Validate body limits
+    if settings.body_limits.upload_max_mb == 0 {
+        errors.push("Body limits upload_max_mb cannot be 0".to_string());
+    }
+
+    if settings.body_limits.json_max_kb == 0 {
+        errors.push("Body limits json_max_kb cannot be 0".to_string());
+    }
+
+    // Validate upload row limits
+    if settings.file_upload.max_rows == 0 {
+        errors.push("File upload max_rows cannot be 0".to_string());
+    }
+
+    if settings.file_upload.max_field_bytes == 0 {
+        errors.push("File upload max_field_bytes cannot be 0".to_string());
+    }
+
+    // Validate error message configuration
+    if settings.error_messages.default_locale.trim().is_empty() {
+        errors.push("Error messages default_locale cannot be empty".to_string());
+    }
+
+    // The checked-in secrets are public, so only local environments may run with them; tests run as development
+    let allows_default_secrets = settings.is_development() || settings.is_demo();
+
+    // Validate share link configuration
+    if settings.sharing.secret.len() < 16 {
+        errors.push("Sharing secret must be at least 16 characters".to_string());
+    }
+
+    if !allows_default_secrets && settings.sharing.secret == DEFAULT_SHARE_SECRET {
+        errors.push(format!("Sharing secret must be changed from the default in {}", settings.application.environment));
+    }
+
+    if settings.sharing.token_ttl_days == Some(0) {
+        errors.push("Sharing token_ttl_days cannot be 0".to_string());
+    }
+
+    // Validate anonymization configuration
+    if settings.anonymization.salt.len() < 16 {
+        errors.push("Anonymization salt must be at least 16 characters".to_string());
+    }
+
+    if !allows_default_secrets && settings.anonymization.salt == DEFAULT_ANONYMIZATION_SALT {
+        errors.push(format!("Anonymization salt must be changed from the default in {}", settings.application.environment));
+    }
+
+    // Validate badge configuration
+    if settings.badges.rate_limit_per_minute == 0 {
+        errors.push("Badges rate_limit_per_minute cannot be 0".to_string());
+    }
+
+    // Validate feed configuration
+    if !(settings.feed.base_url.starts_with("http://") || settings.feed.base_url.starts_with("https://")) {
+        errors.push("Feed base_url must start with http:// or https://".to_string());
+    }
+
+    if !(1..=500).contains(&settings.feed.max_entries) {
+        errors.push("Feed max_entries must be between 1 and 500".to_string());
+    }
+
+    // Validate app attribution configuration
+    let fork_map = &settings.app_attribution.fork_map;
+    let mut forks: Vec<(&String, &String)> = fork_map.iter().collect();
+    forks.sort();
+    for (fork, upstream) in forks {
+        for repository in [fork, upstream] {
+            if !is_owner_repo(repository) {
+                errors.push(format!("App attribution fork_map entry '{}' must read owner/repo", repository));
+            }
+        }
+        if fork_map.keys().any(|other| other.eq_ignore_ascii_case(upstream)) {
+            errors.push(format!(
+                "App attribution fork_map maps '{}' to '{}', which is itself a fork; map it to the upstream directly",
+                fork, upstream
+            ));
+        }
+    }
+
+    // Validate compression configuration
+    for encoding in &settings.compression.encodings {
+        if !COMPRESSION_ENCODINGS.contains(&encoding.as_str()) {
+            errors.push(format!(
+                "Compression encoding '{}' is not supported; use one of {}",
+                encoding,
+                COMPRESSION_ENCODINGS.join(", ")
+            ));
+        }
+    }
+
+    // Validate pipeline configuration
+    if let Some(enabled_stages) = &settings.pipeline.enabled_stages {
+        if enabled_stages.is_empty() {
+            errors.push("Pipeline enabled_stages cannot be empty; leave it unset to run every stage".to_string());
+        }
+        for stage in pipeline_registry().unknown_stages(enabled_stages) {
+            errors.push(format!("Pipeline enabled_stages names unknown stage '{}'", stage));
+        }
+    }
+
+    // Validate alerts configuration
+    let alerts = &settings.alerts;
+    if let Some(webhook_url) = &alerts.webhook_url
+        && !(webhook_url.starts_with("http://") || webhook_url.starts_with("https://"))
+    {
+        errors.push("Alerts webhook_url must start with http:// or https://".to_string());
+    }
+
+    if alerts.webhook_timeout_seconds == 0 {
+        errors.push("Alerts webhook_timeout_seconds cannot be 0".to_string());
+    }
+
+    if alerts.max_error_rate_percent.is_some_and(|rate| !(0.0..=100.0).contains(&rate)) {
+        errors.push("Alerts max_error_rate_percent must be between 0 and 100".to_string());
+    }
+
+    if alerts.error_rate_window < 1 {
+        errors.push("Alerts error_rate_window must be at least 1".to_string());
+    }
+
+    let min_rows_stages: Vec<String> = alerts.min_rows_out.keys().cloned().collect();
+    for stage in pipeline_registry().unknown_stages(&min_rows_stages) {
+        errors.push(format!("Alerts min_rows_out names unknown stage '{}'", stage));
+    }
+
+    // Validate remote ingest configuration
+    let remote_ingest = &settings.remote_ingest;
+    if remote_ingest.allowed_hosts.iter().any(|host| host.trim().is_empty() || host.contains('/')) {
+        errors.push("Remote ingest allowed_hosts must be host names, without scheme or path".to_string());
+    }
+
+    if remote_ingest.timeout_seconds == 0 {
+        errors.push("Remote ingest timeout_seconds cannot be 0".to_string());
+    }
+
+    // Validate upstream sync configuration
+    let upstream_sync = &settings.upstream_sync;
+    match &upstream_sync.url {
+        Some(url) => {
+            let host = reqwest::Url::parse(url).ok().filter(|url| matches!(url.scheme(), "http" | "https")).and_then(|url| url.host_str().map(str::to_string));
+            match host {
+                Some(host) if !remote_ingest.allowed_hosts.iter().any(|allowed| allowed.trim().eq_ignore_ascii_case(&host)) => {
+                    errors.push(format!("Upstream sync url host '{}' must be listed in remote_ingest.allowed_hosts", host));
+                }
+                Some(_) => {}
+                None => errors.push("Upstream sync url must be an http:// or https:// URL".to_string()),
+            }
+        }
+        None if upstream_sync.enabled => errors.push("Upstream sync is enabled but has no url".to_string()),
+        None => {}
+    }
+
+    if upstream_sync.interval_minutes == 0 {
+        errors.push("Upstream sync interval_minutes cannot be 0".to_string());
+    }
+
+    // Validate commit dates configuration
+    let commit_dates = &settings.commit_dates;
+    if !reqwest::Url::parse(&commit_dates.api_url).is_ok_and(|url| matches!(url.scheme(), "http" | "https")) {
+        errors.push("Commit dates api_url must be an http or https URL".to_string());
+    }
+
+    if commit_dates.requests_per_minute == 0 {
+        errors.push("Commit dates requests_per_minute cannot be 0".to_string());
+    }
+
+    if commit_dates.max_lookups == 0 {
+        errors.push("Commit dates max_lookups cannot be 0".to_string());
+    }
+
+    if commit_dates.timeout_seconds == 0 {
+        errors.push("Commit dates timeout_seconds cannot be 0".to_string());
+    }
+
+    // Validate health history configuration
+    if settings.health_history.interval_seconds == 0 {
+        errors.push("Health history interval_seconds cannot be 0".to_string());
+    }
+
+    if settings.health_history.retention_days == 0 {
+        errors.push("Health history retention_days cannot be 0".to_string());
+    }
+
+    if settings.is_demo() && settings.demo.sample_rows == 0 {
+        errors.push("Demo sample_rows cannot be 0".to_string());
+    }
+
+    if settings.tenancy.enabled && settings.tenancy.api_keys.is_empty() {
+        errors.push("Tenancy api_keys cannot be empty when tenancy is enabled".to_string());
+    }
+
+    if settings.tenancy.api_keys.iter().any(|(key, tenant)| key.trim().is_empty() || tenant.trim().is_empty()) {
+        errors.push("Tenancy api_keys cannot contain blank keys or tenant ids".to_string());
+    }
+
+    // Validate retention configuration
+    if settings.retention.interval_seconds == 0 {
+        errors.push("Retention interval_seconds cannot be 0".to_string());
+    }
+
+    if settings.retention.max_age_days == 0 {
+        errors.push("Retention max_age_days cannot be 0".to_string());
+    }
+
+    if settings.retention.enabled {
+        errors.extend(StorageRetention::from_settings(settings).rejected().iter().cloned());
+    }
+
     if errors.is_empty() {
         Ok(())
     } else {
@@ -69,6 +290,11 @@ pub fn validate_config(settings: &Settings) -> Result<(), Vec<String>> {
     }
 }
 
+/// Whether a fork map entry is `owner/repo`, both parts non-empty
+fn is_owner_repo(repository: &str) -> bool {
+    matches!(repository.split_once('/'), Some((owner, repo)) if !owner.is_empty() && !repo.is_empty() && !repo.contains('/'))
+}
+
 /// Get configuration summary for logging
 pub fn get_config_summary(settings: &Settings) -> String {
     format!(
@@ -167,6 +393,7 @@ pub fn get_database_url(environment: &str) -> String {
         "development" => "sqlite:./dev-database.db".to_string(),
         "staging" => "sqlite:./staging-database.db".to_string(),
         "production" => "sqlite:./production-database.db".to_string(),
+        "demo" => "sqlite::memory:".to_string(),
         _ => "sqlite:./my-database.db".to_string(),
     }
 }
@@ -177,6 +404,7 @@ pub fn get_log_file_path(environment: &str) -> PathBuf {
         "development" => PathBuf::from("logs/dev.log"),
         "staging" => PathBuf::from("logs/staging.log"),
         "production" => PathBuf::from("logs/production.log"),
+        "demo" => PathBuf::from("logs/demo.log"),
         _ => PathBuf::from("logs/app.log"),
     }
 }
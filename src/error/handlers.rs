@@ -8,6 +8,8 @@ use tracing::{error, warn, info};
 
 use crate::error::AppError;
 use crate::error::AppResult;
+use crate::error::DatabaseErrorKind;
+use crate::repositories::traits::Repository;
 
 /// Global error handler for unhandled errors
 pub async fn handle_error(err: axum::Error) -> Response {
@@ -27,9 +29,14 @@ pub async fn handle_error(err: axum::Error) -> Response {
 /// Log error with context
 pub fn log_error(err: &AppError, context: &str) {
     match err {
-        AppError::Database(db_err) => {
-            error!("Database error in {}: {:?}", context, db_err);
-        }
+        AppError::Database(db_err) => match DatabaseErrorKind::of(db_err) {
+            DatabaseErrorKind::ConstraintViolation | DatabaseErrorKind::MissingReference | DatabaseErrorKind::NotFound => {
+                warn!("Database error in {}: {:?}", context, db_err);
+            }
+            DatabaseErrorKind::Busy | DatabaseErrorKind::Timeout | DatabaseErrorKind::Other => {
+                error!("Database error in {}: {:?}", context, db_err);
+            }
+        },
         AppError::Validation(msg) => {
             warn!("Validation error in {}: {}", context, msg);
         }
@@ -42,6 +49,9 @@ pub fn log_error(err: &AppError, context: &str) {
         AppError::BadRequest(msg) => {
             warn!("Bad request in {}: {}", context, msg);
         }
+        AppError::InvalidQuery(_) => {
+            warn!("Invalid query in {}: {}", context, err);
+        }
         AppError::Unauthorized(msg) => {
             warn!("Unauthorized access in {}: {}", context, msg);
         }
@@ -57,13 +67,64 @@ pub fn log_error(err: &AppError, context: &str) {
         AppError::Config(msg) => {
             error!("Configuration error in {}: {}", context, msg);
         }
+        AppError::Conflict { message, current_version } => {
+            warn!("Conflict in {}: {} (current version {})", context, message, current_version);
+        }
+        AppError::TooManyRequests { message, retry_after_secs } => {
+            warn!("Rate limited in {}: {} (retry after {}s)", context, message, retry_after_secs);
+        }
+        AppError::Maintenance(msg) => {
+            info!("Rejected during maintenance in {}: {}", context, msg);
+        }
+        AppError::MissingReference { field, reason } => {
+            warn!("Missing reference in {}: {}: {}", context, field, reason);
+        }
+        AppError::InvalidState(msg) => {
+            warn!("Invalid state in {}: {}", context, msg);
+        }
+        AppError::Duplicate(msg) => {
+            warn!("Duplicate in {}: {}", context, msg);
+        }
     }
 }
 
 /// Convert anyhow errors to AppError
+///
+/// Wrapped sqlx errors keep their database classification (409/503/404) instead
+/// of collapsing into a generic internal error.
 pub fn handle_anyhow_error(err: anyhow::Error, context: &str) -> AppError {
     error!("Anyhow error in {}: {:?}", context, err);
-    AppError::Internal(format!("Error in {}: {}", context, err))
+    match err.downcast::<sqlx::Error>() {
+        Ok(db_err) => AppError::Database(db_err),
+        Err(err) => AppError::Internal(format!("Error in {}: {}", context, err)),
+    }
+}
+
+/// Check that the row a write points at exists, before the write fails on its foreign key
+///
+/// A dangling id is reported as a 422 naming `field`, e.g. `run_id` with
+/// `run not found`, rather than as a generic database error.
+pub async fn ensure_reference_exists<R, T>(repository: &R, id: i64, field: &str, reason: &str) -> AppResult<()>
+where
+    R: Repository<T, i64> + Sync,
+{
+    if repository.exists(id).await? {
+        Ok(())
+    } else {
+        Err(AppError::missing_reference(field, reason))
+    }
+}
+
+/// Report a child-row write that failed on its foreign key the way `ensure_reference_exists` does
+///
+/// SQLite does not say which constraint failed, so the caller names the reference
+/// the rows carry; any other database error is passed through unchanged.
+pub fn reference_error(err: sqlx::Error, field: &str, reason: &str) -> AppError {
+    if DatabaseErrorKind::of(&err) == DatabaseErrorKind::MissingReference {
+        AppError::missing_reference(field, reason)
+    } else {
+        AppError::Database(err)
+    }
 }
 
 /// Validate required fields
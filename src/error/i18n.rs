@@ -0,0 +1,144 @@
+use std::collections::HashMap;
+
+use crate::config::settings::ErrorMessagesConfig;
+use crate::error::types::AppError;
+
+/// Bundled translations of the error codes, embedded in the binary
+const ERROR_MESSAGES_JSON: &str = include_str!("../../data/error_messages.json");
+
+/// Locale used when neither the request nor the configured default has a translation
+pub const FALLBACK_LOCALE: &str = "en";
+
+/// Translated, user-facing messages keyed by locale and then by error code
+#[derive(Debug, Clone)]
+pub struct ErrorCatalog {
+    messages: HashMap<String, HashMap<String, String>>,
+    default_locale: String,
+}
+
+impl ErrorCatalog {
+    /// The bundled catalog with English as the default locale
+    pub fn bundled() -> Result<Self, AppError> {
+        let messages = serde_json::from_str(ERROR_MESSAGES_JSON)
+            .map_err(|e| AppError::internal(format!("Failed to parse bundled error messages: {}", e)))?;
+
+        Ok(Self {
+            messages,
+            default_locale: FALLBACK_LOCALE.to_string(),
+        })
+    }
+
+    /// The bundled catalog with the configured default locale and overrides applied
+    pub fn load(config: &ErrorMessagesConfig) -> Result<Self, AppError> {
+        let mut catalog = Self::bundled()?;
+        catalog.default_locale = config.default_locale.to_lowercase();
+
+        if let Some(path) = &config.catalog_path {
+            let contents = std::fs::read_to_string(path).map_err(|e| {
+                AppError::config(format!("Failed to read error message catalog {}: {}", path.display(), e))
+            })?;
+            let overrides: HashMap<String, HashMap<String, String>> = serde_json::from_str(&contents).map_err(|e| {
+                AppError::config(format!("Failed to parse error message catalog {}: {}", path.display(), e))
+            })?;
+
+            for (locale, messages) in overrides {
+                catalog.messages.entry(locale.to_lowercase()).or_default().extend(messages);
+            }
+        }
+
+        Ok(catalog)
+    }
+
+    /// Pick the catalog locale with the highest quality value from an Accept-Language header
+    ///
+    /// Regional tags match their language (`de-AT` uses `de`). Falls back to the
+    /// configured default locale.
+    pub fn negotiate_locale(&self, accept_language: Option<&str>) -> &str {
+        let mut best: Option<(&str, f32)> = None;
+        for entry in accept_language.unwrap_or_default().split(',') {
+            let mut params = entry.split(';');
+            let tag = params.next().unwrap_or_default().trim().to_lowercase();
+            let quality = params
+                .filter_map(|param| param.trim().strip_prefix("q="))
+                .find_map(|q| q.parse::<f32>().ok())
+                .unwrap_or(1.0);
+            let language = tag.split('-').next().unwrap_or_default();
+
+            if let Some((locale, _)) = self.messages.get_key_value(language)
+                && quality > 0.0
+                && best.is_none_or(|(_, best_quality)| quality > best_quality)
+            {
+                best = Some((locale.as_str(), quality));
+            }
+        }
+
+        best.map(|(locale, _)| locale).unwrap_or(&self.default_locale)
+    }
+
+    /// The message for an error code in a locale, falling back to the default locale and then English
+    pub fn message(&self, locale: &str, code: &str) -> Option<&str> {
+        [locale, self.default_locale.as_str(), FALLBACK_LOCALE]
+            .into_iter()
+            .find_map(|locale| self.messages.get(locale).and_then(|messages| messages.get(code)))
+            .map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ERROR_CODES: &[&str] = &[
+        "DATABASE_ERROR",
+        "VALIDATION_ERROR",
+        "NOT_FOUND",
+        "INTERNAL_ERROR",
+        "BAD_REQUEST",
+        "INVALID_QUERY",
+        "UNAUTHORIZED",
+        "FILE_UPLOAD_ERROR",
+        "JSON_PARSING_ERROR",
+        "IO_ERROR",
+        "CONFIG_ERROR",
+        "VERSION_CONFLICT",
+        "CONSTRAINT_VIOLATION",
+        "DATABASE_BUSY",
+        "QUERY_TIMEOUT",
+        "RATE_LIMITED",
+        "MAINTENANCE_MODE",
+        "MISSING_REFERENCE",
+        "INVALID_STATE",
+    ];
+
+    #[test]
+    fn test_bundled_catalog_covers_every_error_code() {
+        let catalog = ErrorCatalog::bundled().expect("bundled error messages should parse");
+        for (locale, messages) in &catalog.messages {
+            for code in ERROR_CODES {
+                assert!(messages.contains_key(*code), "{} has no {} message", locale, code);
+            }
+        }
+    }
+
+    #[test]
+    fn test_negotiate_locale() {
+        let catalog = ErrorCatalog::bundled().unwrap();
+        assert_eq!(catalog.negotiate_locale(None), "en");
+        assert_eq!(catalog.negotiate_locale(Some("de-AT")), "de");
+        assert_eq!(catalog.negotiate_locale(Some("pt-BR, fr;q=0.8, de;q=0.5")), "fr");
+        assert_eq!(catalog.negotiate_locale(Some("de;q=0, es")), "es");
+        assert_eq!(catalog.negotiate_locale(Some("zh-CN")), "en");
+    }
+
+    #[test]
+    fn test_message_falls_back_to_english() {
+        let mut catalog = ErrorCatalog::bundled().unwrap();
+        catalog.messages.get_mut("de").unwrap().remove("IO_ERROR");
+
+        assert_eq!(
+            catalog.message("de", "IO_ERROR"),
+            catalog.message("en", "IO_ERROR"),
+        );
+        assert!(catalog.message("de", "NOT_A_CODE").is_none());
+    }
+}
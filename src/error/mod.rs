@@ -1,5 +1,6 @@
 pub mod types;
 pub mod handlers;
+pub mod i18n;
 
 pub use types::*;
  
\ No newline at end of file
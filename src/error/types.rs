@@ -1,8 +1,9 @@
 use axum::{
-    http::StatusCode,
+    http::{header, HeaderValue, StatusCode},
     response::{IntoResponse, Response},
     Json,
 };
+use serde::Serialize;
 use serde_json::json;
 use thiserror::Error;
 
@@ -23,6 +24,9 @@ pub enum AppError {
     #[error("Bad request: {0}")]
     BadRequest(String),
 
+    #[error("Invalid query parameters: {}", describe_query_params(.0))]
+    InvalidQuery(Vec<QueryParamError>),
+
     #[error("Unauthorized: {0}")]
     Unauthorized(String),
 
@@ -37,36 +41,174 @@ pub enum AppError {
 
     #[error("Configuration error: {0}")]
     Config(String),
+
+    #[error("Conflict: {message}")]
+    Conflict { message: String, current_version: i64 },
+
+    #[error("Too many requests: {message}")]
+    TooManyRequests { message: String, retry_after_secs: u64 },
+
+    #[error("Maintenance mode: {0}")]
+    Maintenance(String),
+
+    #[error("Missing reference: {field}: {reason}")]
+    MissingReference { field: String, reason: String },
+
+    #[error("Invalid state: {0}")]
+    InvalidState(String),
+
+    #[error("Duplicate: {0}")]
+    Duplicate(String),
+}
+
+/// One rejected parameter of an `AppError::InvalidQuery`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct QueryParamError {
+    pub field: String,
+    pub message: String,
+    /// Values the parameter accepts, when it takes one of a fixed set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allowed: Option<Vec<String>>,
+}
+
+impl QueryParamError {
+    pub fn new<T: Into<String>>(field: &str, message: T, allowed: Option<Vec<&str>>) -> Self {
+        Self {
+            field: field.to_string(),
+            message: message.into(),
+            allowed: allowed.map(|values| values.into_iter().map(str::to_string).collect()),
+        }
+    }
+}
+
+fn describe_query_params(params: &[QueryParamError]) -> String {
+    params
+        .iter()
+        .map(|param| format!("{}: {}", param.field, param.message))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Seconds a client should wait before retrying after the database reported busy/locked
+pub const DATABASE_BUSY_RETRY_AFTER_SECS: u64 = 1;
+
+/// What a sqlx error means for the client, used to pick an actionable status
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DatabaseErrorKind {
+    /// A UNIQUE, NOT NULL or CHECK constraint rejected the write
+    ConstraintViolation,
+    /// A FOREIGN KEY constraint rejected the write: the row it points at does not exist
+    MissingReference,
+    /// SQLITE_BUSY/SQLITE_LOCKED or no pooled connection was free; retrying may succeed
+    Busy,
+    /// A query that expected a row found none
+    NotFound,
+    /// A repository query ran past `database.query_timeout_secs` and was abandoned
+    Timeout,
+    Other,
+}
+
+// Primary SQLite result codes; extended codes carry these in their low byte
+const SQLITE_BUSY: i64 = 5;
+const SQLITE_LOCKED: i64 = 6;
+const SQLITE_CONSTRAINT: i64 = 19;
+// Extended code of SQLITE_CONSTRAINT for foreign keys
+const SQLITE_CONSTRAINT_FOREIGNKEY: i64 = 787;
+
+impl DatabaseErrorKind {
+    pub fn of(err: &sqlx::Error) -> Self {
+        match err {
+            sqlx::Error::RowNotFound => DatabaseErrorKind::NotFound,
+            sqlx::Error::PoolTimedOut => DatabaseErrorKind::Busy,
+            sqlx::Error::Io(io_err) if io_err.kind() == std::io::ErrorKind::TimedOut => DatabaseErrorKind::Timeout,
+            sqlx::Error::Database(db_err) => {
+                let code = db_err.code().and_then(|code| code.parse::<i64>().ok());
+                if matches!(db_err.kind(), sqlx::error::ErrorKind::ForeignKeyViolation)
+                    || code == Some(SQLITE_CONSTRAINT_FOREIGNKEY)
+                {
+                    return DatabaseErrorKind::MissingReference;
+                }
+                if matches!(
+                    db_err.kind(),
+                    sqlx::error::ErrorKind::UniqueViolation
+                        | sqlx::error::ErrorKind::NotNullViolation
+                        | sqlx::error::ErrorKind::CheckViolation
+                ) {
+                    return DatabaseErrorKind::ConstraintViolation;
+                }
+
+                match code.map(|code| code & 0xff) {
+                    Some(SQLITE_BUSY | SQLITE_LOCKED) => DatabaseErrorKind::Busy,
+                    Some(SQLITE_CONSTRAINT) => DatabaseErrorKind::ConstraintViolation,
+                    _ => DatabaseErrorKind::Other,
+                }
+            }
+            _ => DatabaseErrorKind::Other,
+        }
+    }
+
+    pub fn is_retryable(self) -> bool {
+        self == DatabaseErrorKind::Busy
+    }
 }
 
 impl AppError {
     pub fn status_code(&self) -> StatusCode {
         match self {
-            AppError::Database(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            AppError::Database(e) => match DatabaseErrorKind::of(e) {
+                DatabaseErrorKind::ConstraintViolation => StatusCode::CONFLICT,
+                DatabaseErrorKind::MissingReference => StatusCode::UNPROCESSABLE_ENTITY,
+                DatabaseErrorKind::Busy => StatusCode::SERVICE_UNAVAILABLE,
+                DatabaseErrorKind::NotFound => StatusCode::NOT_FOUND,
+                DatabaseErrorKind::Timeout => StatusCode::GATEWAY_TIMEOUT,
+                DatabaseErrorKind::Other => StatusCode::INTERNAL_SERVER_ERROR,
+            },
             AppError::Validation(_) => StatusCode::BAD_REQUEST,
             AppError::NotFound(_) => StatusCode::NOT_FOUND,
             AppError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
             AppError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            AppError::InvalidQuery(_) => StatusCode::BAD_REQUEST,
             AppError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
             AppError::FileUpload(_) => StatusCode::BAD_REQUEST,
             AppError::JsonParsing(_) => StatusCode::BAD_REQUEST,
             AppError::Io(_) => StatusCode::INTERNAL_SERVER_ERROR,
             AppError::Config(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            AppError::Conflict { .. } => StatusCode::CONFLICT,
+            AppError::TooManyRequests { .. } => StatusCode::TOO_MANY_REQUESTS,
+            AppError::Maintenance(_) => StatusCode::SERVICE_UNAVAILABLE,
+            AppError::MissingReference { .. } => StatusCode::UNPROCESSABLE_ENTITY,
+            AppError::InvalidState(_) => StatusCode::CONFLICT,
+            AppError::Duplicate(_) => StatusCode::CONFLICT,
         }
     }
 
     pub fn error_code(&self) -> &'static str {
         match self {
-            AppError::Database(_) => "DATABASE_ERROR",
+            AppError::Database(e) => match DatabaseErrorKind::of(e) {
+                DatabaseErrorKind::ConstraintViolation => "CONSTRAINT_VIOLATION",
+                DatabaseErrorKind::MissingReference => "MISSING_REFERENCE",
+                DatabaseErrorKind::Busy => "DATABASE_BUSY",
+                DatabaseErrorKind::NotFound => "NOT_FOUND",
+                DatabaseErrorKind::Timeout => "QUERY_TIMEOUT",
+                DatabaseErrorKind::Other => "DATABASE_ERROR",
+            },
             AppError::Validation(_) => "VALIDATION_ERROR",
             AppError::NotFound(_) => "NOT_FOUND",
             AppError::Internal(_) => "INTERNAL_ERROR",
             AppError::BadRequest(_) => "BAD_REQUEST",
+            AppError::InvalidQuery(_) => "INVALID_QUERY",
             AppError::Unauthorized(_) => "UNAUTHORIZED",
             AppError::FileUpload(_) => "FILE_UPLOAD_ERROR",
             AppError::JsonParsing(_) => "JSON_PARSING_ERROR",
             AppError::Io(_) => "IO_ERROR",
             AppError::Config(_) => "CONFIG_ERROR",
+            AppError::Conflict { .. } => "VERSION_CONFLICT",
+            AppError::TooManyRequests { .. } => "RATE_LIMITED",
+            AppError::Maintenance(_) => "MAINTENANCE_MODE",
+            AppError::MissingReference { .. } => "MISSING_REFERENCE",
+            AppError::InvalidState(_) => "INVALID_STATE",
+            // Reported like the unique constraint a racing write would hit
+            AppError::Duplicate(_) => "CONSTRAINT_VIOLATION",
         }
     }
 }
@@ -74,7 +216,7 @@ impl AppError {
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
         let status = self.status_code();
-        let error_response = json!({
+        let mut error_response = json!({
             "error": {
                 "code": self.error_code(),
                 "message": self.to_string(),
@@ -82,7 +224,35 @@ impl IntoResponse for AppError {
             }
         });
 
-        (status, Json(error_response)).into_response()
+        // Let the client retry against the row it lost the race to
+        if let AppError::Conflict { current_version, .. } = &self {
+            error_response["error"]["current_version"] = json!(current_version);
+        }
+        // Say which parameters to fix, and to what
+        if let AppError::InvalidQuery(params) = &self {
+            error_response["error"]["params"] = json!(params);
+        }
+        // Say which id points at nothing
+        if let AppError::MissingReference { field, reason } = &self {
+            error_response["error"]["field"] = json!(field);
+            error_response["error"]["reason"] = json!(reason);
+        }
+
+        let mut response = (status, Json(error_response)).into_response();
+        // Busy/locked is transient; tell the client when to try again
+        if let AppError::Database(e) = &self
+            && DatabaseErrorKind::of(e).is_retryable()
+        {
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, HeaderValue::from(DATABASE_BUSY_RETRY_AFTER_SECS));
+        }
+        if let AppError::TooManyRequests { retry_after_secs, .. } = &self {
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, HeaderValue::from(*retry_after_secs));
+        }
+        response
     }
 }
 
@@ -104,6 +274,10 @@ impl AppError {
         AppError::BadRequest(message.into())
     }
 
+    pub fn invalid_query(params: Vec<QueryParamError>) -> Self {
+        AppError::InvalidQuery(params)
+    }
+
     pub fn unauthorized<T: Into<String>>(message: T) -> Self {
         AppError::Unauthorized(message.into())
     }
@@ -115,6 +289,37 @@ impl AppError {
     pub fn config<T: Into<String>>(message: T) -> Self {
         AppError::Config(message.into())
     }
+
+    pub fn conflict<T: Into<String>>(message: T, current_version: i64) -> Self {
+        AppError::Conflict {
+            message: message.into(),
+            current_version,
+        }
+    }
+
+    pub fn too_many_requests<T: Into<String>>(message: T, retry_after_secs: u64) -> Self {
+        AppError::TooManyRequests {
+            message: message.into(),
+            retry_after_secs,
+        }
+    }
+
+    /// The item is not in a state that allows the action, e.g. an upload another request is already completing
+    pub fn invalid_state<T: Into<String>>(message: T) -> Self {
+        AppError::InvalidState(message.into())
+    }
+
+    /// The row would duplicate one a unique constraint allows only once, found before the write
+    pub fn duplicate<T: Into<String>>(message: T) -> Self {
+        AppError::Duplicate(message.into())
+    }
+
+    pub fn missing_reference<T: Into<String>>(field: &str, reason: T) -> Self {
+        AppError::MissingReference {
+            field: field.to_string(),
+            reason: reason.into(),
+        }
+    }
 }
 
 // Result type alias for convenience
@@ -1,28 +1,53 @@
 use axum::{
-    extract::State,
+    body::Bytes,
+    extract::{Query, State},
     response::Json,
 };
 use axum_extra::extract::Multipart;
+use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use sqlx::{Sqlite, Transaction};
 use tracing::{error, info, warn};
+use uuid::Uuid;
 // validator::Validate removed as it's no longer used
 
 use crate::{
-    error::types::AppError,
-    models::{runs::Run, performance_result::PerformanceResult, app_details::AppDetails, system_info::SystemInfo, libraries::Libraries, gpu::Gpu, run_more_details::RunMoreDetails},
+    config::settings::{NotesFilterAction, PiiScanMode},
+    error::{handlers::reference_error, types::AppError},
+    models::{performance_result::PerformanceResult, runs::{Run, RunDeviceInfo, RunItsInput, RunSystemInfo}, app_details::AppDetails, system_info::SystemInfo, libraries::Libraries, gpu::Gpu, run_more_details::RunMoreDetails, app_release::AppRelease, change_set::CHANGE_SET_ACTION_FIX_APP_NAMES, upload_session::UploadSchemaReport, dataset_meta::{META_SOURCE_FILE_NAME, META_SOURCE_FORMAT, META_SOURCE_FILE_SIZE, META_SOURCE_UPLOADED_AT}},
     repositories::{
         runs_repository::RunsRepository,
-        performance_result_repository::PerformanceResultRepository,
-        app_details_repository::AppDetailsRepository,
         system_info_repository::SystemInfoRepository,
-        libraries_repository::LibrariesRepository,
+        app_details_repository::AppDetailsRepository,
         gpu_repository::GpuRepository,
-        run_more_details_repository::RunMoreDetailsRepository,
+        run_score_repository::RunScoreRepository,
+        dataset_meta_repository::DatasetMetaRepository,
+        moderation_repository::ModerationRepository,
+        change_set_repository::ChangeSetRepository,
+        gpu_base_repository::GpuBaseRepository,
+        model_map_repository::ModelMapRepository,
+        shadow_table::ShadowTable,
+        tag_repository::TagRepository,
+        upload_session_repository::UploadSessionRepository,
+        ingested_row_repository::IngestedRowRepository,
+        commit_date_repository::CommitDateRepository,
         traits::{Repository, TransactionRepository},
+        audit_timestamp,
     },
-    handlers::{common::create_file_upload_response, validation::{RunData, FixAppNamesRequest, validate_json_content, validate_timestamp_format, validate_vram_usage_format, MAX_FILE_SIZE, ALLOWED_FILE_EXTENSIONS}},
-    middleware::validation::validate_file_upload,
+    services::{
+        data_processing::{compute_run_scores_service::ComputeRunScoresService, seed_gpu_base_service::SeedGpuBaseService, seed_model_map_service::SeedModelMapService, save_data_service::{runs_from_data, SaveDataService, DEFAULT_CHUNK_SIZE}},
+        ingest::{check_row_limits, decode_upload, detect_benchmark_format, detect_schema_drift, parse_benchmark_document, scan_runs_for_pii, EncodingReport, KnownRows, NotesFilter, PiiRedactionCounts, RowHashes},
+        parsers::{AppDetailsParser, CpuParser, GpuInfoParser, ModelNameParser, PerformanceParser, SystemInfoParser, VramUsageKind, GPU_INFO_PARSER_VERSION, SYSTEM_INFO_PARSER_VERSION},
+        parsing_pool::ParsingPool,
+        percentiles::PercentileService,
+        laptop_detection::LaptopDetector,
+        app_attribution::AppAttributor,
+        commit_dates::CommitDateClient,
+        cloud_detection::{cloud_hint, is_datacenter_gpu},
+        stage_timing::StageTimer,
+    },
+    handlers::{common::{combine_file_upload_responses, create_file_upload_response, DryRunQuery, FileUploadResponse, DRY_RUN_SAMPLE_SIZE}, validation::{FixAppNamesRequest, RunData, validate_json_content, validate_timestamp_format, validate_vram_usage_format, MAX_FILE_SIZE, ALLOWED_FILE_EXTENSIONS}},
+    middleware::{maintenance_mode::ensure_not_in_maintenance, request_transaction::RequestTransaction, tenant::Tenant, validation::validate_file_upload},
     AppState,
 };
 
@@ -63,66 +88,280 @@ pub struct ProcessLibrariesResponse {
 #[derive(Debug, Serialize)]
 pub struct ProcessGpuResponse {
     pub success: bool,
+    /// One row per GPU, so more than the number of runs when some report several GPUs
+    pub rows_inserted: usize,
+    pub multi_gpu_runs: usize,
+}
+
+/// Largest number of changed runs listed in a backfill response
+pub const BACKFILL_DIFF_LIMIT: usize = 100;
+
+#[derive(Debug, Deserialize)]
+pub struct BackfillQuery {
+    /// Processing stage whose derived table is regenerated
+    pub stage: String,
+    pub parser_version: i64,
+}
+
+/// The parsed fields of one GPU row, as compared between parses
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BackfillGpuRow {
+    pub device: Option<String>,
+    pub driver: Option<String>,
+    pub gpu_chip: Option<String>,
+}
+
+/// A run whose GPU rows came out differently from the previous parse
+#[derive(Debug, Serialize)]
+pub struct BackfillRunDiff {
+    pub run_id: i64,
+    pub before: Vec<BackfillGpuRow>,
+    pub after: Vec<BackfillGpuRow>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BackfillResponse {
+    pub success: bool,
+    pub stage: String,
+    pub parser_version: i64,
+    pub runs_processed: usize,
     pub rows_inserted: usize,
+    pub runs_changed: usize,
+    /// The first `BACKFILL_DIFF_LIMIT` changed runs
+    pub diffs: Vec<BackfillRunDiff>,
 }
 
-// RunData is now imported from validation module
+// RunData rows are produced by the ingest adapters in services::ingest
 
+/// Query parameters of `save_data`
+#[derive(Debug, Default, Deserialize)]
+pub struct SaveDataQuery {
+    /// Keep the existing runs and add the uploaded ones, ingesting each file in its own transaction
+    #[serde(default)]
+    pub append: bool,
+}
+
+/// Import every `file` field of a multipart body
+///
+/// By default the files' runs replace the runs table in one transaction, so a
+/// bad file leaves the data untouched. With `append=true` the existing runs are
+/// kept and each file is ingested in its own transaction; a file that fails is
+/// reported in its result without undoing the others. Appended runs are filed
+/// under the request's tenant; while tenancy is enabled only appending is allowed.
 pub async fn save_data(
     State(state): State<AppState>,
+    tenant: Tenant,
+    Query(query): Query<SaveDataQuery>,
     mut multipart: Multipart,
-) -> Result<Json<crate::handlers::common::FileUploadResponse>, AppError> {
+) -> Result<Json<FileUploadResponse>, AppError> {
     info!("Processing save-data request");
 
-    // Extract file from multipart
-    let mut file_content = None;
-    let mut file_name = None;
-    
+    if !query.append {
+        ensure_replace_allowed(&state)?;
+    }
+
+    // Extract files from multipart
+    let mut files = Vec::new();
     while let Some(field) = multipart.next_field().await.map_err(|e| {
         error!("Failed to read multipart field: {}", e);
         AppError::BadRequest("Invalid multipart data".to_string())
     })? {
         if field.name() == Some("file") {
-            file_name = field.file_name().map(|s| s.to_string());
+            let file_name = field.file_name().unwrap_or("unknown.json").to_string();
             let data = field.bytes().await.map_err(|e| {
                 error!("Failed to read file bytes: {}", e);
                 AppError::BadRequest("Failed to read uploaded file".to_string())
             })?;
-            file_content = Some(data);
-            break;
+            files.push((file_name, data));
         }
     }
 
-    let file_bytes = file_content.ok_or_else(|| {
+    if files.is_empty() {
         error!("No file provided in multipart data");
-        AppError::BadRequest("No file provided".to_string())
-    })?;
+        return Err(AppError::BadRequest("No file provided".to_string()));
+    }
+
+    let results = if query.append {
+        info!("Appending {} files", files.len());
+        append_benchmark_files(&state, &tenant, &files, MAX_FILE_SIZE).await
+    } else {
+        info!("Replacing the runs with {} files", files.len());
+        let mut uploads = Vec::with_capacity(files.len());
+        for (file_name, file_bytes) in &files {
+            let upload_id = Uuid::new_v4().to_string();
+            uploads.push(prepare_benchmark_file(&state, &tenant, &upload_id, file_name, file_bytes, MAX_FILE_SIZE).await?);
+        }
+        ingest_uploads(&state, uploads, true).await?
+    };
+
+    Ok(combine_file_upload_responses(results))
+}
+
+/// Refuse to replace the runs while tenancy is enabled, since it would clear every tenant's runs
+pub(crate) fn ensure_replace_allowed(state: &AppState) -> Result<(), AppError> {
+    if state.settings.tenancy.enabled {
+        return Err(AppError::bad_request(
+            "Replacing the runs would clear every tenant's dataset; use append=true while tenancy is enabled",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Validate and append each file in its own transaction, one result per file
+///
+/// A file that fails is reported in its result, with the error as its message,
+/// without undoing the others. Shared by `save_data?append=true` and the
+/// public `/api/upload`, so both accept and reject the same files.
+pub(crate) async fn append_benchmark_files(
+    state: &AppState,
+    tenant: &Tenant,
+    files: &[(String, Bytes)],
+    max_file_size: usize,
+) -> Vec<FileUploadResponse> {
+    let mut results = Vec::with_capacity(files.len());
+    for (file_name, file_bytes) in files {
+        let upload_id = Uuid::new_v4().to_string();
+        let result = match prepare_benchmark_file(state, tenant, &upload_id, file_name, file_bytes, max_file_size).await {
+            Ok(upload) => ingest_uploads(state, vec![upload], false)
+                .await
+                .map(|mut responses| responses.remove(0)),
+            Err(e) => Err(e),
+        };
+        results.push(result.unwrap_or_else(|e| {
+            error!("Failed to ingest {}: {}", file_name, e);
+            let mut failed = create_file_upload_response(&e.to_string(), file_name, file_bytes.len(), 0, 0, 0, e.status_code());
+            failed.0.success = false;
+            failed.0.upload_id = Some(upload_id);
+            failed.0
+        }));
+    }
+
+    results
+}
+
+/// Validate and parse a benchmark file, then append its rows under `tenant`
+///
+/// Used by the resumable upload flow, which is open to anonymous clients and
+/// so never replaces the runs; `save_data?append=true` ingests its files the same way.
+pub async fn ingest_benchmark_file(
+    state: &AppState,
+    tenant: &Tenant,
+    upload_id: &str,
+    final_file_name: &str,
+    file_bytes: &[u8],
+    max_file_size: usize,
+) -> Result<Json<FileUploadResponse>, AppError> {
+    let upload = prepare_benchmark_file(state, tenant, upload_id, final_file_name, file_bytes, max_file_size).await?;
+    let mut responses = ingest_uploads(state, vec![upload], false).await?;
+
+    Ok(Json(responses.remove(0)))
+}
+
+/// A benchmark file that passed validation, ready to insert
+pub(crate) struct PreparedUpload {
+    upload_id: String,
+    file_name: String,
+    file_size: usize,
+    format_name: &'static str,
+    run_data: Vec<RunData>,
+    /// Content hash and fingerprint of each row, as uploaded
+    row_hashes: Vec<RowHashes>,
+    pii_redactions: PiiRedactionCounts,
+    /// Set when the file was not clean UTF-8
+    encoding_repairs: Option<EncodingReport>,
+    /// Tenant the runs are filed under
+    tenant: Tenant,
+}
+
+impl PreparedUpload {
+    /// Keep only the rows `keep` accepts by content hash
+    pub(crate) fn retain_rows(&mut self, mut keep: impl FnMut(&str) -> bool) {
+        let rows = std::mem::take(&mut self.run_data).into_iter().zip(std::mem::take(&mut self.row_hashes));
+        let (run_data, row_hashes): (Vec<RunData>, Vec<RowHashes>) =
+            rows.filter(|(_, hashes)| keep(&hashes.content_hash)).unzip();
+        self.run_data = run_data;
+        self.row_hashes = row_hashes;
+    }
+
+    pub(crate) fn row_count(&self) -> usize {
+        self.run_data.len()
+    }
+}
 
-    let final_file_name = file_name.as_ref().unwrap_or(&"unknown.json".to_string()).to_string();
+/// Validate and parse a benchmark file without touching the runs
+///
+/// The schema report of the file is stored under `upload_id` once its format is
+/// detected, so it is kept even when the rows are then rejected. Both the report
+/// and the runs are filed under `tenant`.
+pub(crate) async fn prepare_benchmark_file(
+    state: &AppState,
+    tenant: &Tenant,
+    upload_id: &str,
+    final_file_name: &str,
+    file_bytes: &[u8],
+    max_file_size: usize,
+) -> Result<PreparedUpload, AppError> {
+    // Decode before any JSON check, which a byte order mark or a stray Windows-1252 byte would fail
+    let decoded = decode_upload(file_bytes);
 
     // Validate file upload
     validate_file_upload(
-        &file_bytes,
-        &final_file_name,
-        MAX_FILE_SIZE,
+        decoded.text.as_bytes(),
+        final_file_name,
+        max_file_size,
         ALLOWED_FILE_EXTENSIONS,
     )?;
 
     // Validate JSON content
-    validate_json_content(&file_bytes).map_err(|e| {
+    validate_json_content(decoded.text.as_bytes()).map_err(|e| {
         AppError::Validation(format!("Invalid JSON content: {}", e))
     })?;
 
-    // Parse JSON from file
-    let file_string = String::from_utf8(file_bytes.to_vec()).map_err(|e| {
-        error!("Failed to convert file to UTF-8: {}", e);
-        AppError::BadRequest("File is not valid UTF-8".to_string())
-    })?;
+    let document = parse_benchmark_document(decoded.text.as_bytes())?;
+    let format = detect_benchmark_format(&document)?;
+    let format_name = format.name();
+
+    // Record keys the format does not know or that the file lacks
+    let schema_report = UploadSchemaReport {
+        upload_id: upload_id.to_string(),
+        file_name: final_file_name.to_string(),
+        format: format_name.to_string(),
+        drift: detect_schema_drift(&format.schema(), &document),
+        created_at: Utc::now().to_rfc3339(),
+        tenant_id: tenant.id().to_string(),
+    };
+    if !schema_report.drift.is_empty() {
+        warn!(
+            "Upload {} drifts from the {} schema: {} unknown keys, {} missing keys",
+            upload_id,
+            format_name,
+            schema_report.drift.unknown_keys.len(),
+            schema_report.drift.missing_keys.len()
+        );
+    }
+    if let Err(e) = UploadSessionRepository::new(state.db.clone()).save_schema_report(&schema_report).await {
+        warn!("Failed to record schema report of upload {}: {}", upload_id, e);
+    }
 
-    let run_data: Vec<RunData> = serde_json::from_str(&file_string).map_err(|e| {
-        error!("Failed to parse JSON: {}", e);
-        AppError::BadRequest("Invalid JSON format".to_string())
+    let mut run_data = format.to_run_data(&document).map_err(|e| {
+        error!("Failed to parse benchmark export: {}", e);
+        e
     })?;
+    let encoding_repairs = decoded.restore(&mut run_data);
+    if let Some(report) = &encoding_repairs {
+        warn!(
+            "Upload {} decoded as {} (byte order mark: {}) with {} repaired characters in {} rows",
+            upload_id,
+            report.encoding,
+            report.byte_order_mark,
+            report.repaired_characters,
+            report.rows.len()
+        );
+    }
+
+    // Refuse pathological uploads before any of their rows reaches SQLite
+    check_row_limits(&run_data, &state.settings.file_upload)?;
 
     // Validate each run data entry
     for (index, data) in run_data.iter().enumerate() {
@@ -135,7 +374,42 @@ pub async fn save_data(
         })?;
     }
 
-    info!("Parsed {} rows from uploaded file", run_data.len());
+    let row_hashes = run_data.iter().map(RowHashes::of_row).collect();
+
+    // Reject or redact personal data in notes/info, as configured
+    let pii_redactions = scan_runs_for_pii(&mut run_data, state.settings.pii_scan.mode)?;
+    if pii_redactions.total() > 0 {
+        info!("Redacted {} personal data matches from uploaded notes/info", pii_redactions.total());
+    }
+
+    // Refuse notes breaking a blocking rule; flagged ones are queued once the runs have ids
+    NotesFilter::new(&state.settings.notes_filter).reject_blocked(&run_data)?;
+
+    info!("Parsed {} rows from uploaded {} file", run_data.len(), format_name);
+
+    Ok(PreparedUpload {
+        upload_id: upload_id.to_string(),
+        file_name: final_file_name.to_string(),
+        file_size: file_bytes.len(),
+        format_name,
+        run_data,
+        row_hashes,
+        pii_redactions,
+        encoding_repairs,
+        tenant: tenant.clone(),
+    })
+}
+
+/// Insert prepared uploads in one transaction, first clearing the runs table when `replace` is set
+///
+/// When appending, each response carries the upload's delta against the rows
+/// ingested before it, including those of the uploads ahead of it.
+pub(crate) async fn ingest_uploads(
+    state: &AppState,
+    uploads: Vec<PreparedUpload>,
+    replace: bool,
+) -> Result<Vec<FileUploadResponse>, AppError> {
+    let mut known = if replace { None } else { Some(known_rows(state).await?) };
 
     // Start transaction
     let mut tx = state.db.begin().await.map_err(|e| {
@@ -144,48 +418,23 @@ pub async fn save_data(
     })?;
 
     // Clear existing data
-    if let Err(e) = clear_runs_table(&mut tx).await {
-        error!("Failed to clear runs table: {}", e);
-        tx.rollback().await.map_err(|rollback_err| {
-            error!("Failed to rollback transaction: {}", rollback_err);
-            AppError::Database(rollback_err)
-        })?;
-        return Err(AppError::Database(e));
+    if replace {
+        if let Err(e) = clear_runs_table(&mut tx).await {
+            error!("Failed to clear runs table: {}", e);
+            return Err(rollback_with(tx, AppError::Database(e)).await);
+        }
+        info!("Cleared existing runs data");
     }
 
-    info!("Cleared existing runs data");
-
-    // Process data insertion
-    let runs_repo = RunsRepository::new(state.db.clone());
-    let mut inserted_rows = 0;
-    let mut error_rows = 0;
-    let mut error_data = Vec::new();
-
-    for (index, data) in run_data.iter().enumerate() {
-        let run = Run {
-            id: None,
-            timestamp: Some(data.timestamp.clone()),
-            vram_usage: Some(data.vram_usage.clone()),
-            info: Some(data.info.clone()),
-            system_info: Some(data.system_info.clone()),
-            model_info: Some(data.model_info.clone()),
-            device_info: Some(data.device_info.clone()),
-            xformers: Some(data.xformers.clone()),
-            model_name: Some(data.model_name.clone()),
-            user: Some(data.user.clone()),
-            notes: Some(data.notes.clone()),
-        };
-
-        match runs_repo.create_tx(run, &mut tx).await {
-            Ok(_) => {
-                inserted_rows += 1;
-            }
-            Err(e) => {
-                error_rows += 1;
-                let error_msg = format!("Row {}: {}", index + 1, e);
-                error_data.push(error_msg.clone());
-                warn!("Failed to insert row {}: {}", index + 1, e);
+    let mut responses = Vec::with_capacity(uploads.len());
+    for upload in uploads {
+        let delta = known.as_mut().map(|known| known.classify(&upload.row_hashes));
+        match insert_upload(state, upload, &mut tx).await {
+            Ok(mut response) => {
+                response.delta = delta;
+                responses.push(response);
             }
+            Err(e) => return Err(rollback_with(tx, e).await),
         }
     }
 
@@ -195,22 +444,181 @@ pub async fn save_data(
         return Err(AppError::Database(e));
     }
 
+    Ok(responses)
+}
+
+/// Content hashes and fingerprints of the ingested rows
+///
+/// Runs ingested before hashes were kept are hashed and recorded the first time
+/// this finds no rows recorded.
+pub(crate) async fn known_rows(state: &AppState) -> Result<KnownRows, AppError> {
+    let repository = IngestedRowRepository::new(state.db.clone());
+    let content_hashes = repository.find_all_hashes().await?;
+    if !content_hashes.is_empty() {
+        return Ok(KnownRows {
+            content_hashes,
+            fingerprints: repository.find_all_fingerprints().await?,
+        });
+    }
+
+    let runs = RunsRepository::new(state.db.clone()).find_all().await?;
+    if runs.is_empty() {
+        return Ok(KnownRows::default());
+    }
+    let row_hashes: Vec<RowHashes> = runs.iter().map(RowHashes::of_run).collect();
+    let mut tx = state.db.begin().await?;
+    repository.insert_hashes_tx(&row_hashes, &Utc::now().to_rfc3339(), &mut tx).await?;
+    tx.commit().await?;
+    info!("Recorded content hashes of {} runs ingested before hashes were kept", row_hashes.len());
+
+    Ok(KnownRows {
+        content_hashes: row_hashes.iter().map(|row| row.content_hash.clone()).collect(),
+        fingerprints: row_hashes.into_iter().map(|row| row.fingerprint).collect(),
+    })
+}
+
+/// Insert the runs of a prepared upload, queue its flagged notes and record it as the dataset's source
+async fn insert_upload(
+    state: &AppState,
+    upload: PreparedUpload,
+    tx: &mut Transaction<'_, Sqlite>,
+) -> Result<FileUploadResponse, AppError> {
+    let total_rows = upload.run_data.len();
+
+    // Process data insertion in bulk chunks
+    let save_data_service = SaveDataService::new(RunsRepository::new(state.db.clone()), state.db.clone());
+    let insert_result = save_data_service
+        .insert_runs_in_chunks_tx(runs_from_data(upload.run_data), DEFAULT_CHUNK_SIZE, tx)
+        .await
+        .map_err(|e| {
+            error!("Failed to insert runs: {}", e);
+            e
+        })?;
+    let inserted_rows = insert_result.inserted_runs.len();
+    let error_rows = insert_result.error_data.len();
+
+    // Runs go in as the default tenant's, the column default
+    if upload.tenant != Tenant::default() {
+        let run_ids: Vec<i64> = insert_result.inserted_runs.iter().filter_map(|run| run.id).collect();
+        RunsRepository::new(state.db.clone())
+            .assign_tenant_tx(&run_ids, upload.tenant.id(), tx)
+            .await
+            .map_err(|e| {
+                error!("Failed to file runs under tenant {}: {}", upload.tenant.id(), e);
+                AppError::Database(e)
+            })?;
+    }
+
+    // Remember the rows, so upstream syncs skip them
+    IngestedRowRepository::new(state.db.clone())
+        .insert_hashes_tx(&upload.row_hashes, &Utc::now().to_rfc3339(), tx)
+        .await
+        .map_err(|e| {
+            error!("Failed to record ingested row hashes: {}", e);
+            AppError::Database(e)
+        })?;
+
+    // Hold runs with flagged notes back from publication until reviewed
+    let notes_filter_config = &state.settings.notes_filter;
+    let notes_filter = NotesFilter::new(notes_filter_config);
+    let notes_flagged = queue_flagged_runs(state, &notes_filter, &insert_result.inserted_runs, tx)
+        .await
+        .map_err(|e| {
+            error!("Failed to queue flagged runs for moderation: {}", e);
+            reference_error(e, "run_id", "run not found")
+        })?;
+    if notes_flagged > 0 {
+        info!("Queued {} runs with flagged notes for moderation", notes_flagged);
+    }
+
+    // Record which upload the dataset was built from and bump the dataset version
+    let dataset_meta_repository = DatasetMetaRepository::new(state.db.clone());
+    record_source_upload(&dataset_meta_repository, &upload.file_name, upload.format_name, upload.file_size, tx)
+        .await
+        .map_err(|e| {
+            error!("Failed to record dataset metadata: {}", e);
+            AppError::Database(e)
+        })?;
+
     info!(
-        "Data processing complete: {} inserted, {} errors out of {} total",
-        inserted_rows, error_rows, run_data.len()
+        "Data processing of {} complete: {} inserted, {} errors out of {} total",
+        upload.file_name, inserted_rows, error_rows, total_rows
     );
 
-    let final_file_name = file_name.as_ref().unwrap_or(&"unknown.json".to_string()).to_string();
-    
-    Ok(create_file_upload_response(
+    let mut response = create_file_upload_response(
         "Data processed successfully",
-        &final_file_name,
-        file_bytes.len(),
-        run_data.len(),
+        &upload.file_name,
+        upload.file_size,
+        total_rows,
         inserted_rows,
         error_rows,
         axum::http::StatusCode::OK,
-    ))
+    );
+    response.0.upload_id = Some(upload.upload_id);
+    response.0.encoding_repairs = upload.encoding_repairs;
+    if state.settings.pii_scan.mode == PiiScanMode::Redact {
+        response.0.pii_redactions = Some(upload.pii_redactions);
+    }
+    let flags_notes = [notes_filter_config.links, notes_filter_config.markup, notes_filter_config.profanity]
+        .contains(&NotesFilterAction::Flag);
+    if flags_notes {
+        response.0.notes_flagged = Some(notes_flagged);
+    }
+
+    Ok(response.0)
+}
+
+/// Roll back `tx`, handing back `error`, or the rollback's own error if that fails too
+async fn rollback_with(tx: Transaction<'_, Sqlite>, error: AppError) -> AppError {
+    match tx.rollback().await {
+        Ok(()) => error,
+        Err(rollback_err) => {
+            error!("Failed to rollback transaction: {}", rollback_err);
+            AppError::Database(rollback_err)
+        }
+    }
+}
+
+/// Queue every inserted run whose notes break a flagging rule, returning how many were queued
+async fn queue_flagged_runs(
+    state: &AppState,
+    notes_filter: &NotesFilter<'_>,
+    runs: &[Run],
+    tx: &mut Transaction<'_, Sqlite>,
+) -> Result<usize, sqlx::Error> {
+    let moderation_repository = ModerationRepository::new(state.db.clone());
+    let mut queued = 0;
+
+    for run in runs {
+        let (Some(run_id), Some(notes)) = (run.id, run.notes.as_deref()) else {
+            continue;
+        };
+        let reasons = notes_filter.flag_reasons(notes);
+        if reasons.is_empty() {
+            continue;
+        }
+        moderation_repository.create_tx(run_id, &reasons.join(","), tx).await?;
+        queued += 1;
+    }
+
+    Ok(queued)
+}
+
+async fn record_source_upload(
+    dataset_meta_repository: &DatasetMetaRepository,
+    file_name: &str,
+    format_name: &str,
+    file_size: usize,
+    tx: &mut Transaction<'_, Sqlite>,
+) -> Result<i64, sqlx::Error> {
+    let now = Utc::now().to_rfc3339();
+    let file_size = file_size.to_string();
+
+    dataset_meta_repository.set_tx(META_SOURCE_FILE_NAME, Some(file_name), &now, tx).await?;
+    dataset_meta_repository.set_tx(META_SOURCE_FORMAT, Some(format_name), &now, tx).await?;
+    dataset_meta_repository.set_tx(META_SOURCE_FILE_SIZE, Some(&file_size), &now, tx).await?;
+    dataset_meta_repository.set_tx(META_SOURCE_UPLOADED_AT, Some(&now), &now, tx).await?;
+    dataset_meta_repository.increment_version_tx(&now, tx).await
 }
 
 async fn clear_runs_table(tx: &mut Transaction<'_, Sqlite>) -> Result<(), sqlx::Error> {
@@ -226,6 +634,9 @@ async fn clear_runs_table(tx: &mut Transaction<'_, Sqlite>) -> Result<(), sqlx::
     sqlx::query!("DELETE FROM AppDetails")
         .execute(&mut **tx)
         .await?;
+    sqlx::query!("DELETE FROM AppRelease")
+        .execute(&mut **tx)
+        .await?;
     sqlx::query!("DELETE FROM SystemInfo")
         .execute(&mut **tx)
         .await?;
@@ -238,6 +649,18 @@ async fn clear_runs_table(tx: &mut Transaction<'_, Sqlite>) -> Result<(), sqlx::
     sqlx::query!("DELETE FROM RunMoreDetails")
         .execute(&mut **tx)
         .await?;
+    sqlx::query!("DELETE FROM RunScore")
+        .execute(&mut **tx)
+        .await?;
+    sqlx::query!("DELETE FROM run_tags")
+        .execute(&mut **tx)
+        .await?;
+    sqlx::query!("DELETE FROM moderation_queue")
+        .execute(&mut **tx)
+        .await?;
+    sqlx::query!("DELETE FROM ingested_rows")
+        .execute(&mut **tx)
+        .await?;
     
     // Clear the runs table
     sqlx::query!("DELETE FROM runs")
@@ -252,144 +675,198 @@ async fn clear_runs_table(tx: &mut Transaction<'_, Sqlite>) -> Result<(), sqlx::
     Ok(())
 }
 
+/// Build the performance result of one run from its vram_usage string
+fn parse_its_row(run: RunItsInput) -> Result<PerformanceResult, AppError> {
+    let run_id = run.id;
+
+    let vram_usage = run.vram_usage.ok_or_else(|| {
+        error!("Run {} has no vram_usage", run_id);
+        AppError::BadRequest("Missing vram_usage data".to_string())
+    })?;
+
+    // Parse ITS values from vram_usage string
+    let its_values: Vec<f64> = vram_usage
+        .split('/')
+        .filter_map(|value| {
+            let trimmed = value.trim();
+            if trimmed.is_empty() {
+                None
+            } else {
+                // Parse the value and filter out NaN
+                trimmed.parse::<f64>().ok().filter(|&x| !x.is_nan())
+            }
+        })
+        .collect();
+
+    // Memory readings uploaded in place of ITS samples get no average, keeping them out of ITS statistics
+    let vram_usage_kind = PerformanceParser::classify(&vram_usage);
+    if vram_usage_kind == VramUsageKind::Memory {
+        warn!("Run {} has memory values in vram_usage, excluding it from ITS statistics: {}", run_id, vram_usage);
+    }
+
+    // Calculate average ITS
+    let avg_its = if its_values.is_empty() || vram_usage_kind != VramUsageKind::Its {
+        None
+    } else {
+        let sum: f64 = its_values.iter().sum();
+        Some(sum / its_values.len() as f64)
+    };
+
+    Ok(PerformanceResult {
+        id: None,
+        run_id: Some(run_id),
+        its: Some(vram_usage),
+        avg_its,
+        vram_usage_kind: Some(vram_usage_kind.as_str().to_string()),
+        created_at: None,
+        updated_at: None,
+    })
+}
+
 pub async fn process_its(
     State(state): State<AppState>,
 ) -> Result<Json<crate::handlers::common::ProcessingResponse>, AppError> {
-    info!("Processing ITS data from runs table");
-
     // Start transaction
     let mut tx = state.db.begin().await.map_err(|e| {
         error!("Failed to begin transaction: {}", e);
         AppError::Database(e)
     })?;
 
-    // Clear existing performance results
-    let perf_repo = PerformanceResultRepository::new(state.db.clone());
-    if let Err(e) = perf_repo.clear_all_tx(&mut tx).await {
-        error!("Failed to clear performance results: {}", e);
-        tx.rollback().await.map_err(|rollback_err| {
-            error!("Failed to rollback transaction: {}", rollback_err);
-            AppError::Database(rollback_err)
-        })?;
+    let mut response = process_its_tx(&state, &mut tx).await?;
+
+    // Commit transaction
+    let mut timer = StageTimer::start("commit");
+    if let Err(e) = timer.db(tx.commit()).await {
+        error!("Failed to commit transaction: {}", e);
         return Err(AppError::Database(e));
     }
+    response.stage_timings.push(timer.finish(response.rows_inserted));
 
-    info!("Cleared existing performance results");
+    Ok(Json(response))
+}
+
+/// The process-its step within a transaction, also run as a pipeline stage
+pub(crate) async fn process_its_tx(
+    state: &AppState,
+    tx: &mut Transaction<'_, Sqlite>,
+) -> Result<crate::handlers::common::ProcessingResponse, AppError> {
+    info!("Processing ITS data from runs table");
+    let mut stage_timings = Vec::new();
 
-    // Fetch all runs data
+    // Rebuild into a shadow table; readers keep the current performance results until the swap
+    let mut timer = StageTimer::start("create_shadow_table");
+    let shadow = timer.db(ShadowTable::<PerformanceResult>::create_tx(tx)).await.map_err(|e| {
+        error!("Failed to create performance result shadow table: {}", e);
+        AppError::Database(e)
+    })?;
+    stage_timings.push(timer.finish(0));
+
+    // Fetch all runs data through the transaction, which holds the shadow table's schema lock
+    let mut timer = StageTimer::start("fetch_runs");
     let runs_repo = RunsRepository::new(state.db.clone());
-    let runs = runs_repo.find_all().await.map_err(|e| {
+    let runs = timer.db(runs_repo.find_all_its_inputs_tx(tx)).await.map_err(|e| {
         error!("Failed to fetch runs data: {}", e);
         AppError::Database(e)
     })?;
+    stage_timings.push(timer.finish(runs.len()));
 
     info!("Found {} runs to process", runs.len());
 
+    let total_runs = runs.len();
+    let mut timer = StageTimer::start("insert_performance_results");
     let mut inserted_rows = 0;
+    let mut processed_runs = 0;
 
-    // Process each run
-    for (index, run) in runs.iter().enumerate() {
-        let run_id = run.id.ok_or_else(|| {
-            error!("Run at index {} has no ID", index);
-            AppError::BadRequest("Invalid run data".to_string())
-        })?;
+    // Parse on the worker pool while earlier chunks are being written
+    let mut parsed_chunks = ParsingPool::new(&state.settings.parsing).parse(runs, parse_its_row);
+    while let Some(chunk) = parsed_chunks.recv().await {
+        for performance_result in chunk? {
+            let performance_result = performance_result?;
+            let run_id = performance_result.run_id.unwrap_or_default();
+            let avg_its = performance_result.avg_its;
+            processed_runs += 1;
 
-        let vram_usage = run.vram_usage.as_ref().ok_or_else(|| {
-            error!("Run {} has no vram_usage", run_id);
-            AppError::BadRequest("Missing vram_usage data".to_string())
-        })?;
+            info!("Processing run {} of {} (ID: {})", processed_runs, total_runs, run_id);
 
-        info!("Processing run {} of {} (ID: {})", index + 1, runs.len(), run_id);
-
-        // Parse ITS values from vram_usage string
-        let its_values: Vec<f64> = vram_usage
-            .split('/')
-            .filter_map(|value| {
-                let trimmed = value.trim();
-                if trimmed.is_empty() {
-                    None
-                } else {
-                    // Parse the value and filter out NaN
-                    trimmed.parse::<f64>().ok().filter(|&x| !x.is_nan())
+            // Insert into database
+            match timer.db(shadow.insert_tx(performance_result, tx)).await {
+                Ok(_) => {
+                    inserted_rows += 1;
+                    info!("Processed run {} with average ITS: {}", processed_runs, avg_its.unwrap_or(0.0));
+                }
+                Err(e) => {
+                    error!("Failed to insert performance result for run {}: {}", run_id, e);
+                    // Continue processing other runs
                 }
-            })
-            .collect();
-
-        // Calculate average ITS
-        let avg_its = if its_values.is_empty() {
-            None
-        } else {
-            let sum: f64 = its_values.iter().sum();
-            Some(sum / its_values.len() as f64)
-        };
-
-        // Create performance result
-        let performance_result = PerformanceResult {
-            id: None,
-            run_id: Some(run_id),
-            its: Some(vram_usage.clone()),
-            avg_its,
-        };
-
-        // Insert into database
-        match perf_repo.create_tx(performance_result, &mut tx).await {
-            Ok(_) => {
-                inserted_rows += 1;
-                info!("Processed run {} with average ITS: {}", index + 1, avg_its.unwrap_or(0.0));
-            }
-            Err(e) => {
-                error!("Failed to insert performance result for run {}: {}", run_id, e);
-                // Continue processing other runs
             }
         }
     }
 
-    // Commit transaction
-    if let Err(e) = tx.commit().await {
-        error!("Failed to commit transaction: {}", e);
-        return Err(AppError::Database(e));
-    }
+    stage_timings.push(timer.finish(inserted_rows));
+
+    // Swap in the rebuilt table within the transaction, so readers switch over at commit
+    let mut timer = StageTimer::start("swap_shadow_table");
+    timer.db(shadow.swap_tx(tx)).await.map_err(|e| {
+        error!("Failed to swap in rebuilt performance results: {}", e);
+        AppError::Database(e)
+    })?;
+    stage_timings.push(timer.finish(inserted_rows));
 
     info!("ITS processing complete: {} rows inserted", inserted_rows);
 
-    Ok(crate::handlers::common::create_processing_response(
+    let Json(mut response) = crate::handlers::common::create_processing_response(
         "ITS processing completed successfully",
-        runs.len(),
+        total_runs,
         inserted_rows,
         0, // rows_updated
         0, // rows_deleted
         vec![], // errors
         axum::http::StatusCode::OK,
-    ))
+    );
+    response.stage_timings = stage_timings;
+    Ok(response)
 }
 
 pub async fn process_app_details(
     State(state): State<AppState>,
 ) -> Result<Json<ProcessAppDetailsResponse>, AppError> {
-    info!("Processing app details from runs table");
-
     // Start transaction
     let mut tx = state.db.begin().await.map_err(|e| {
         error!("Failed to begin transaction: {}", e);
         AppError::Database(e)
     })?;
 
-    // Clear existing app details
-    let app_details_repo = AppDetailsRepository::new(state.db.clone());
-    if let Err(e) = app_details_repo.clear_all_tx(&mut tx).await {
-        error!("Failed to clear app details: {}", e);
-        tx.rollback().await.map_err(|rollback_err| {
-            error!("Failed to rollback transaction: {}", rollback_err);
-            AppError::Database(rollback_err)
-        })?;
+    let response = process_app_details_tx(&state, &mut tx).await?;
+
+    // Commit transaction
+    if let Err(e) = tx.commit().await {
+        error!("Failed to commit transaction: {}", e);
         return Err(AppError::Database(e));
     }
 
-    info!("Cleared existing app details");
+    Ok(Json(response))
+}
+
+/// The process-app-details step within a transaction, also run as a pipeline stage
+pub(crate) async fn process_app_details_tx(
+    state: &AppState,
+    tx: &mut Transaction<'_, Sqlite>,
+) -> Result<ProcessAppDetailsResponse, AppError> {
+    info!("Processing app details from runs table");
+
+    // Rebuild into shadow tables; readers keep the current app details until the swap
+    let app_details_shadow = ShadowTable::<AppDetails>::create_tx(tx).await.map_err(|e| {
+        error!("Failed to create app details shadow table: {}", e);
+        AppError::Database(e)
+    })?;
+    let app_release_shadow = ShadowTable::<AppRelease>::create_tx(tx).await.map_err(|e| {
+        error!("Failed to create app release shadow table: {}", e);
+        AppError::Database(e)
+    })?;
 
-    // Fetch all runs data
+    // Fetch all runs data through the transaction, which holds the shadow table's schema lock
     let runs_repo = RunsRepository::new(state.db.clone());
-    let runs = runs_repo.find_all().await.map_err(|e| {
+    let runs = runs_repo.find_all_app_infos_tx(tx).await.map_err(|e| {
         error!("Failed to fetch runs data: {}", e);
         AppError::Database(e)
     })?;
@@ -400,10 +877,7 @@ pub async fn process_app_details(
 
     // Process each run
     for (index, run) in runs.iter().enumerate() {
-        let run_id = run.id.ok_or_else(|| {
-            error!("Run at index {} has no ID", index);
-            AppError::BadRequest("Invalid run data".to_string())
-        })?;
+        let run_id = run.id;
 
         let info = run.info.as_ref().ok_or_else(|| {
             error!("Run {} has no info", run_id);
@@ -418,6 +892,16 @@ pub async fn process_app_details(
         // Store app_name for logging
         let app_name_for_log = app_details.app_name.clone();
 
+        // Derive release channel and month from the url and updated fields
+        let app_release_record = AppRelease {
+            id: None,
+            run_id: Some(run_id),
+            release_channel: app_details.url.as_deref().and_then(AppDetailsParser::parse_release_channel),
+            release_month: app_details.updated.as_deref().and_then(AppDetailsParser::parse_release_month),
+            created_at: None,
+            updated_at: None,
+        };
+
         // Create app details record
         let app_details_record = AppDetails {
             id: None,
@@ -426,13 +910,20 @@ pub async fn process_app_details(
             updated: app_details.updated,
             hash: app_details.hash,
             url: app_details.url,
+            parser_version: Some(LEGACY_PARSER_VERSION),
+            created_at: None,
+            updated_at: None,
         };
 
         // Insert into database
-        match app_details_repo.create_tx(app_details_record, &mut tx).await {
+        match app_details_shadow.insert_tx(app_details_record, tx).await {
             Ok(_) => {
                 inserted_rows += 1;
                 info!("Processed app details for run {}: app={:?}", index + 1, app_name_for_log);
+
+                if let Err(e) = app_release_shadow.insert_tx(app_release_record, tx).await {
+                    error!("Failed to insert app release for run {}: {}", run_id, e);
+                }
             }
             Err(e) => {
                 error!("Failed to insert app details for run {}: {}", run_id, e);
@@ -441,11 +932,26 @@ pub async fn process_app_details(
         }
     }
 
-    // Commit transaction
-    if let Err(e) = tx.commit().await {
-        error!("Failed to commit transaction: {}", e);
-        return Err(AppError::Database(e));
-    }
+    // Swap in the rebuilt tables within the transaction, so readers switch over at commit
+    app_details_shadow.swap_tx(tx).await.map_err(|e| {
+        error!("Failed to swap in rebuilt app details: {}", e);
+        AppError::Database(e)
+    })?;
+    app_release_shadow.swap_tx(tx).await.map_err(|e| {
+        error!("Failed to swap in rebuilt app releases: {}", e);
+        AppError::Database(e)
+    })?;
+
+    // The rebuilt rows only hold what the info string says; restore the
+    // canonical URL columns and the cached commit dates derived from it
+    canonicalize_app_urls_tx(state, tx).await?;
+    CommitDateRepository::new(state.db.clone())
+        .apply_to_app_details_tx(tx)
+        .await
+        .map_err(|e| {
+            error!("Failed to restore commit dates of rebuilt app details: {}", e);
+            AppError::Database(e)
+        })?;
 
     info!("App details processing complete: {} rows inserted", inserted_rows);
 
@@ -454,7 +960,7 @@ pub async fn process_app_details(
         rows_inserted: inserted_rows,
     };
 
-    Ok(Json(response))
+    Ok(response)
 }
 
 #[derive(Debug)]
@@ -495,100 +1001,125 @@ fn parse_app_details(info_string: &str) -> ParsedAppDetails {
     app_details
 }
 
+/// Build the system info record of one run, `None` when required fields are missing
+fn parse_system_info_row(run: RunSystemInfo) -> Result<Option<SystemInfo>, AppError> {
+    let run_id = run.id;
+
+    let system_info = run.system_info.ok_or_else(|| {
+        error!("Run {} has no system_info", run_id);
+        AppError::BadRequest("Missing system_info data".to_string())
+    })?;
+
+    // Parse system info from system_info string
+    let parsed_system_info = SystemInfoParser::parse(&system_info);
+
+    // Only insert if all required fields are present
+    if parsed_system_info.arch.is_none() ||
+       parsed_system_info.cpu.is_none() ||
+       parsed_system_info.system.is_none() ||
+       parsed_system_info.release.is_none() ||
+       parsed_system_info.python.is_none() {
+        warn!("Skipping run {} due to missing required system info fields", run_id);
+        return Ok(None);
+    }
+
+    Ok(Some(SystemInfo {
+        id: None,
+        run_id: Some(run_id),
+        arch: parsed_system_info.arch,
+        cpu: parsed_system_info.cpu,
+        system: parsed_system_info.system,
+        release: parsed_system_info.release,
+        python: parsed_system_info.python,
+        ram_gb: parsed_system_info.ram_gb,
+        swap_gb: parsed_system_info.swap_gb,
+        os_bits: parsed_system_info.os_bits,
+        parser_version: Some(SYSTEM_INFO_PARSER_VERSION),
+        created_at: None,
+        updated_at: None,
+    }))
+}
+
 pub async fn process_system_info(
     State(state): State<AppState>,
 ) -> Result<Json<ProcessSystemInfoResponse>, AppError> {
-    info!("Processing system info from runs table");
-
     // Start transaction
     let mut tx = state.db.begin().await.map_err(|e| {
         error!("Failed to begin transaction: {}", e);
         AppError::Database(e)
     })?;
 
-    // Clear existing system info
-    let system_info_repo = SystemInfoRepository::new(state.db.clone());
-    if let Err(e) = system_info_repo.clear_all_tx(&mut tx).await {
-        error!("Failed to clear system info: {}", e);
-        tx.rollback().await.map_err(|rollback_err| {
-            error!("Failed to rollback transaction: {}", rollback_err);
-            AppError::Database(rollback_err)
-        })?;
+    let response = process_system_info_tx(&state, &mut tx).await?;
+
+    // Commit transaction
+    if let Err(e) = tx.commit().await {
+        error!("Failed to commit transaction: {}", e);
         return Err(AppError::Database(e));
     }
 
-    info!("Cleared existing system info");
+    Ok(Json(response))
+}
+
+/// The process-system-info step within a transaction, also run as a pipeline stage
+pub(crate) async fn process_system_info_tx(
+    state: &AppState,
+    tx: &mut Transaction<'_, Sqlite>,
+) -> Result<ProcessSystemInfoResponse, AppError> {
+    info!("Processing system info from runs table");
+
+    // Rebuild into a shadow table; readers keep the current system info until the swap
+    let shadow = ShadowTable::<SystemInfo>::create_tx(tx).await.map_err(|e| {
+        error!("Failed to create system info shadow table: {}", e);
+        AppError::Database(e)
+    })?;
 
-    // Fetch all runs data
+    // Fetch all runs data through the transaction, which holds the shadow table's schema lock
     let runs_repo = RunsRepository::new(state.db.clone());
-    let runs = runs_repo.find_all().await.map_err(|e| {
+    let runs = runs_repo.find_all_system_infos_tx(tx).await.map_err(|e| {
         error!("Failed to fetch runs data: {}", e);
         AppError::Database(e)
     })?;
 
     info!("Found {} runs to process", runs.len());
 
+    let total_runs = runs.len();
     let mut inserted_rows = 0;
+    let mut processed_runs = 0;
+
+    // Parse on the worker pool while earlier chunks are being written
+    let mut parsed_chunks = ParsingPool::new(&state.settings.parsing).parse(runs, parse_system_info_row);
+    while let Some(chunk) = parsed_chunks.recv().await {
+        for system_info_record in chunk? {
+            processed_runs += 1;
+            let Some(system_info_record) = system_info_record? else {
+                continue;
+            };
+            let run_id = system_info_record.run_id.unwrap_or_default();
 
-    // Process each run
-    for (index, run) in runs.iter().enumerate() {
-        let run_id = run.id.ok_or_else(|| {
-            error!("Run at index {} has no ID", index);
-            AppError::BadRequest("Invalid run data".to_string())
-        })?;
-
-        let system_info = run.system_info.as_ref().ok_or_else(|| {
-            error!("Run {} has no system_info", run_id);
-            AppError::BadRequest("Missing system_info data".to_string())
-        })?;
+            info!("Processing system info for run {} of {} (ID: {})", processed_runs, total_runs, run_id);
 
-        info!("Processing system info for run {} of {} (ID: {})", index + 1, runs.len(), run_id);
-
-        // Parse system info from system_info string
-        let parsed_system_info = parse_system_info(system_info);
-
-        // Store arch for logging
-        let arch_for_log = parsed_system_info.arch.clone();
-
-        // Only insert if all required fields are present
-        if parsed_system_info.arch.is_some() &&
-           parsed_system_info.cpu.is_some() &&
-           parsed_system_info.system.is_some() &&
-           parsed_system_info.release.is_some() &&
-           parsed_system_info.python.is_some() {
-            
-            // Create system info record
-            let system_info_record = SystemInfo {
-                id: None,
-                run_id: Some(run_id),
-                arch: parsed_system_info.arch,
-                cpu: parsed_system_info.cpu,
-                system: parsed_system_info.system,
-                release: parsed_system_info.release,
-                python: parsed_system_info.python,
-            };
+            // Store arch for logging
+            let arch_for_log = system_info_record.arch.clone();
 
             // Insert into database
-            match system_info_repo.create_tx(system_info_record, &mut tx).await {
+            match shadow.insert_tx(system_info_record, tx).await {
                 Ok(_) => {
                     inserted_rows += 1;
-                    info!("Processed system info for run {}: arch={:?}", index + 1, arch_for_log);
+                    info!("Processed system info for run {}: arch={:?}", processed_runs, arch_for_log);
                 }
                 Err(e) => {
                     error!("Failed to insert system info for run {}: {}", run_id, e);
                     // Continue processing other runs
                 }
             }
-        } else {
-            warn!("Skipping run {} due to missing required system info fields", run_id);
         }
     }
 
-    // Commit transaction
-    if let Err(e) = tx.commit().await {
-        error!("Failed to commit transaction: {}", e);
-        return Err(AppError::Database(e));
-    }
+    // Swap in the rebuilt table within the transaction, so readers switch over at commit
+    shadow.swap_tx(tx).await.map_err(|e| {
+        error!("Failed to swap in rebuilt system info: {}", e);
+        AppError::Database(e)
+    })?;
 
     info!("System info processing complete: {} rows inserted", inserted_rows);
 
@@ -597,75 +1128,7 @@ pub async fn process_system_info(
         rows_inserted: inserted_rows,
     };
 
-    Ok(Json(response))
-}
-
-#[derive(Debug)]
-struct ParsedSystemInfo {
-    arch: Option<String>,
-    cpu: Option<String>,
-    system: Option<String>,
-    release: Option<String>,
-    python: Option<String>,
-}
-
-fn parse_system_info(system_info_string: &str) -> ParsedSystemInfo {
-    let mut system_info = ParsedSystemInfo {
-        arch: None,
-        cpu: None,
-        system: None,
-        release: None,
-        python: None,
-    };
-
-    // Split by spaces and process each part
-    let parts: Vec<&str> = system_info_string.split(' ').collect();
-    let mut i = 0;
-    
-    while i < parts.len() {
-        let part = parts[i];
-        
-        if let Some(colon_index) = part.find(':') {
-            let key = &part[..colon_index];
-            let value = &part[colon_index + 1..];
-            
-            // Handle multi-word values
-            let mut full_value = value.to_string();
-            let mut next_i = i + 1;
-            
-            // Look ahead for multi-word values (especially for CPU)
-            while next_i < parts.len() && !parts[next_i].contains(':') {
-                full_value.push(' ');
-                full_value.push_str(parts[next_i]);
-                next_i += 1;
-            }
-            
-            match key {
-                "arch" => {
-                    system_info.arch = Some(full_value);
-                }
-                "cpu" => {
-                    system_info.cpu = Some(full_value);
-                }
-                "system" => {
-                    system_info.system = Some(full_value);
-                }
-                "release" => {
-                    system_info.release = Some(full_value);
-                }
-                "python" => {
-                    system_info.python = Some(full_value);
-                }
-                _ => {}
-            }
-            
-            i = next_i;
-        } else {
-            i += 1;
-        }
-    }
-
-    system_info
+    Ok(response)
 }
 
 #[derive(Debug)]
@@ -741,30 +1204,39 @@ fn parse_model_info(model_info_string: &str) -> ParsedLibraries {
 pub async fn process_libraries(
     State(state): State<AppState>,
 ) -> Result<Json<ProcessLibrariesResponse>, AppError> {
-    info!("Processing libraries from runs table");
-
     // Start transaction
     let mut tx = state.db.begin().await.map_err(|e| {
         error!("Failed to begin transaction: {}", e);
         AppError::Database(e)
     })?;
 
-    // Clear existing libraries
-    let libraries_repo = LibrariesRepository::new(state.db.clone());
-    if let Err(e) = libraries_repo.clear_all_tx(&mut tx).await {
-        error!("Failed to clear libraries: {}", e);
-        tx.rollback().await.map_err(|rollback_err| {
-            error!("Failed to rollback transaction: {}", rollback_err);
-            AppError::Database(rollback_err)
-        })?;
+    let response = process_libraries_tx(&state, &mut tx).await?;
+
+    // Commit transaction
+    if let Err(e) = tx.commit().await {
+        error!("Failed to commit transaction: {}", e);
         return Err(AppError::Database(e));
     }
 
-    info!("Cleared existing libraries");
+    Ok(Json(response))
+}
+
+/// The process-libraries step within a transaction, also run as a pipeline stage
+pub(crate) async fn process_libraries_tx(
+    state: &AppState,
+    tx: &mut Transaction<'_, Sqlite>,
+) -> Result<ProcessLibrariesResponse, AppError> {
+    info!("Processing libraries from runs table");
+
+    // Rebuild into a shadow table; readers keep the current libraries until the swap
+    let shadow = ShadowTable::<Libraries>::create_tx(tx).await.map_err(|e| {
+        error!("Failed to create libraries shadow table: {}", e);
+        AppError::Database(e)
+    })?;
 
-    // Fetch all runs data
+    // Fetch all runs data through the transaction, which holds the shadow table's schema lock
     let runs_repo = RunsRepository::new(state.db.clone());
-    let runs = runs_repo.find_all().await.map_err(|e| {
+    let runs = runs_repo.find_all_libraries_inputs_tx(tx).await.map_err(|e| {
         error!("Failed to fetch runs data: {}", e);
         AppError::Database(e)
     })?;
@@ -775,10 +1247,7 @@ pub async fn process_libraries(
 
     // Process each run
     for (index, run) in runs.iter().enumerate() {
-        let run_id = run.id.ok_or_else(|| {
-            error!("Run at index {} has no ID", index);
-            AppError::BadRequest("Invalid run data".to_string())
-        })?;
+        let run_id = run.id;
 
         let model_info = run.model_info.as_ref().ok_or_else(|| {
             error!("Run {} has no model_info", run_id);
@@ -808,10 +1277,13 @@ pub async fn process_libraries(
             xformers1: Some(xformers.clone()), // Copy xformers value from runs table
             diffusers: parsed_libraries.diffusers,
             transformers: parsed_libraries.transformers,
+            parser_version: Some(LEGACY_PARSER_VERSION),
+            created_at: None,
+            updated_at: None,
         };
 
         // Insert into database
-        match libraries_repo.create_tx(libraries_record, &mut tx).await {
+        match shadow.insert_tx(libraries_record, tx).await {
             Ok(_) => {
                 inserted_rows += 1;
                 info!("Processed libraries for run {}: torch={:?}, xformers={:?}", 
@@ -824,11 +1296,11 @@ pub async fn process_libraries(
         }
     }
 
-    // Commit transaction
-    if let Err(e) = tx.commit().await {
-        error!("Failed to commit transaction: {}", e);
-        return Err(AppError::Database(e));
-    }
+    // Swap in the rebuilt table within the transaction, so readers switch over at commit
+    shadow.swap_tx(tx).await.map_err(|e| {
+        error!("Failed to swap in rebuilt libraries: {}", e);
+        AppError::Database(e)
+    })?;
 
     info!("Libraries processing complete: {} rows inserted", inserted_rows);
 
@@ -837,17 +1309,52 @@ pub async fn process_libraries(
         rows_inserted: inserted_rows,
     };
 
-    Ok(Json(response))
+    Ok(response)
 }
 
-#[derive(Debug)]
+/// Version recorded on rows from the whitespace-splitting parsers in this module,
+/// which process-app-details, process-libraries and process-gpu use
+pub const LEGACY_PARSER_VERSION: i64 = 1;
+
+/// Parser versions a GPU backfill can re-parse with
+pub const GPU_BACKFILL_PARSER_VERSIONS: &[i64] = &[LEGACY_PARSER_VERSION, GPU_INFO_PARSER_VERSION];
+
+#[derive(Debug, Clone)]
 struct ParsedGpuInfo {
     device: Option<String>,
     driver: Option<String>,
     gpu_chip: Option<String>,
 }
 
-fn parse_device_info(device_info_string: &str) -> ParsedGpuInfo {
+/// Parse a device_info string into one entry per GPU it reports
+///
+/// Several `device:` keys give one entry each; a single device with a "(N)"
+/// device count gives N identical entries. A driver reported once applies to
+/// every GPU.
+fn parse_device_info(device_info_string: &str) -> Vec<ParsedGpuInfo> {
+    let mut gpus: Vec<ParsedGpuInfo> = GpuInfoParser::split_devices(device_info_string)
+        .into_iter()
+        .map(parse_gpu_segment)
+        .collect();
+
+    let shared_driver = gpus.iter().find_map(|gpu| gpu.driver.clone());
+    for gpu in &mut gpus {
+        if gpu.driver.is_none() {
+            gpu.driver = shared_driver.clone();
+        }
+    }
+
+    if let [gpu] = gpus.as_slice() {
+        let count = gpu.device.as_deref().map_or(1, GpuInfoParser::device_count);
+        if count > 1 {
+            gpus = vec![gpu.clone(); count];
+        }
+    }
+
+    gpus
+}
+
+fn parse_gpu_segment(device_info_string: &str) -> ParsedGpuInfo {
     let parts: Vec<&str> = device_info_string.split(' ').collect();
     let mut parsed_gpu_info = ParsedGpuInfo {
         device: None,
@@ -906,99 +1413,304 @@ fn parse_device_info(device_info_string: &str) -> ParsedGpuInfo {
     parsed_gpu_info
 }
 
+/// Build the GPU records of one run, one per GPU in its device_info
+fn parse_gpu_rows(run: RunDeviceInfo) -> Result<Vec<Gpu>, AppError> {
+    let run_id = run.id;
+
+    let device_info = run.device_info.ok_or_else(|| {
+        error!("Run {} has no device_info", run_id);
+        AppError::BadRequest("Missing device_info data".to_string())
+    })?;
+
+    Ok(gpu_rows_with_parser(run_id, &device_info, LEGACY_PARSER_VERSION))
+}
+
+/// Parse a device_info string into GPU records with one of `GPU_BACKFILL_PARSER_VERSIONS`
+fn gpu_rows_with_parser(run_id: i64, device_info: &str, parser_version: i64) -> Vec<Gpu> {
+    let parsed: Vec<(Option<String>, Option<String>, Option<String>)> = if parser_version == LEGACY_PARSER_VERSION {
+        parse_device_info(device_info)
+            .into_iter()
+            .map(|gpu| (gpu.device, gpu.driver, gpu.gpu_chip))
+            .collect()
+    } else {
+        GpuInfoParser::parse_all(device_info)
+            .into_iter()
+            .map(|gpu| (gpu.device, gpu.driver, gpu.gpu_chip))
+            .collect()
+    };
+
+    // One row per GPU
+    parsed
+        .into_iter()
+        .enumerate()
+        .map(|(gpu_index, (device, driver, gpu_chip))| Gpu {
+            id: None,
+            run_id: Some(run_id),
+            device,
+            driver,
+            gpu_chip,
+            brand: None, // Will be populated by separate update process
+            is_laptop: None, // Will be populated by separate update process
+            is_integrated: None, // Will be populated by separate update process
+            is_cloud: None, // Will be populated by separate update process
+            gpu_index: gpu_index as i64,
+            parser_version: Some(parser_version),
+            created_at: None,
+            updated_at: None,
+        })
+        .collect()
+}
+
 pub async fn process_gpu(
     State(state): State<AppState>,
 ) -> Result<Json<ProcessGpuResponse>, AppError> {
-    info!("Processing GPU info from runs table");
-
     // Start transaction
     let mut tx = state.db.begin().await.map_err(|e| {
         error!("Failed to begin transaction: {}", e);
         AppError::Database(e)
     })?;
 
-    // Clear existing GPU data
-    let gpu_repo = GpuRepository::new(state.db.clone());
-    if let Err(e) = gpu_repo.clear_all_tx(&mut tx).await {
-        error!("Failed to clear GPU data: {}", e);
-        tx.rollback().await.map_err(|rollback_err| {
-            error!("Failed to rollback transaction: {}", rollback_err);
-            AppError::Database(rollback_err)
-        })?;
+    let response = process_gpu_tx(&state, &mut tx).await?;
+
+    // Commit transaction
+    if let Err(e) = tx.commit().await {
+        error!("Failed to commit transaction: {}", e);
         return Err(AppError::Database(e));
     }
 
-    info!("Cleared existing GPU data");
+    Ok(Json(response))
+}
+
+/// The process-gpu step within a transaction, also run as a pipeline stage
+pub(crate) async fn process_gpu_tx(
+    state: &AppState,
+    tx: &mut Transaction<'_, Sqlite>,
+) -> Result<ProcessGpuResponse, AppError> {
+    info!("Processing GPU info from runs table");
+
+    // Rebuild into a shadow table; readers keep the current GPU data until the swap
+    let shadow = ShadowTable::<Gpu>::create_tx(tx).await.map_err(|e| {
+        error!("Failed to create GPU shadow table: {}", e);
+        AppError::Database(e)
+    })?;
 
-    // Fetch all runs data
+    // Fetch all runs data through the transaction, which holds the shadow table's schema lock
     let runs_repo = RunsRepository::new(state.db.clone());
-    let runs = runs_repo.find_all().await.map_err(|e| {
+    let runs = runs_repo.find_all_device_infos_tx(tx).await.map_err(|e| {
         error!("Failed to fetch runs data: {}", e);
         AppError::Database(e)
     })?;
 
     info!("Found {} runs to process", runs.len());
 
+    let total_runs = runs.len();
     let mut inserted_rows = 0;
+    let mut multi_gpu_runs = 0;
+    let mut processed_runs = 0;
+
+    // Parse on the worker pool while earlier chunks are being written
+    let mut parsed_chunks = ParsingPool::new(&state.settings.parsing).parse(runs, parse_gpu_rows);
+    while let Some(chunk) = parsed_chunks.recv().await {
+        for gpu_records in chunk? {
+            let gpu_records = gpu_records?;
+            processed_runs += 1;
+            let Some(run_id) = gpu_records.first().and_then(|gpu| gpu.run_id) else {
+                continue;
+            };
 
-    // Process each run
-    for (index, run) in runs.iter().enumerate() {
-        let run_id = run.id.ok_or_else(|| {
-            error!("Run at index {} has no ID", index);
-            AppError::BadRequest("Invalid run data".to_string())
-        })?;
+            info!("Processing GPU info for run {} of {} (ID: {})", processed_runs, total_runs, run_id);
+
+            if gpu_records.len() > 1 {
+                multi_gpu_runs += 1;
+                info!("Run {} reports {} GPUs", run_id, gpu_records.len());
+            }
+
+            for gpu_record in gpu_records {
+                // Store values for logging
+                let gpu_index = gpu_record.gpu_index;
+                let device_for_log = gpu_record.device.clone();
+
+                // Insert into database
+                match shadow.insert_tx(gpu_record, tx).await {
+                    Ok(_) => {
+                        inserted_rows += 1;
+                        info!("Processed GPU {} info for run {}: device={:?}", gpu_index, processed_runs, device_for_log);
+                    }
+                    Err(e) => {
+                        error!("Failed to insert GPU {} info for run {}: {}", gpu_index, run_id, e);
+                        // Continue processing other GPUs and runs
+                    }
+                }
+            }
+        }
+    }
 
-        let device_info = run.device_info.as_ref().ok_or_else(|| {
-            error!("Run {} has no device_info", run_id);
-            AppError::BadRequest("Missing device_info data".to_string())
+    // Swap in the rebuilt table within the transaction, so readers switch over at commit
+    shadow.swap_tx(tx).await.map_err(|e| {
+        error!("Failed to swap in rebuilt GPU data: {}", e);
+        AppError::Database(e)
+    })?;
+
+    // Flag multi-GPU runs right away rather than waiting for the laptop info step
+    TagRepository::new(state.db.clone())
+        .refresh_auto_tags_tx(tx)
+        .await
+        .map_err(|e| {
+            error!("Failed to refresh automatic run tags: {}", e);
+            AppError::Database(e)
         })?;
 
-        info!("Processing GPU info for run {} of {} (ID: {})", index + 1, runs.len(), run_id);
+    info!("GPU processing complete: {} rows inserted, {} multi-GPU runs", inserted_rows, multi_gpu_runs);
 
-        // Parse device info to extract GPU information
-        let parsed_gpu_info = parse_device_info(device_info);
+    let response = ProcessGpuResponse {
+        success: true,
+        rows_inserted: inserted_rows,
+        multi_gpu_runs,
+    };
 
-        // Store values for logging
-        let device_for_log = parsed_gpu_info.device.clone();
+    Ok(response)
+}
 
-        // Create GPU record
-        let gpu_record = Gpu {
-            id: None,
-            run_id: Some(run_id),
-            device: parsed_gpu_info.device,
-            driver: parsed_gpu_info.driver,
-            gpu_chip: parsed_gpu_info.gpu_chip,
-            brand: None, // Will be populated by separate update process
-            is_laptop: None, // Will be populated by separate update process
-        };
+/// Regenerate one stage's derived table from the runs with a chosen parser version
+///
+/// Every row written records the parser version, and the response lists how
+/// the new parse differs from the rows it replaces. Only the `gpu` stage can
+/// be backfilled so far. Brand, laptop, integrated and cloud flags are kept for
+/// GPUs whose device did not change; re-run update-gpu-brands, update-gpu-laptop-info,
+/// update-gpu-integrated-info and update-gpu-cloud-info for the rest.
+pub async fn backfill(
+    State(state): State<AppState>,
+    Query(query): Query<BackfillQuery>,
+) -> Result<Json<BackfillResponse>, AppError> {
+    match query.stage.as_str() {
+        "gpu" => backfill_gpu(&state, query.parser_version).await,
+        stage => Err(AppError::bad_request(format!("Unknown backfill stage '{}'; supported stages: gpu", stage))),
+    }
+}
 
-        // Insert into database
-        match gpu_repo.create_tx(gpu_record, &mut tx).await {
-            Ok(_) => {
-                inserted_rows += 1;
-                info!("Processed GPU info for run {}: device={:?}", index + 1, device_for_log);
+async fn backfill_gpu(state: &AppState, parser_version: i64) -> Result<Json<BackfillResponse>, AppError> {
+    if !GPU_BACKFILL_PARSER_VERSIONS.contains(&parser_version) {
+        return Err(AppError::bad_request(format!(
+            "Unknown GPU parser version {}; supported versions: {:?}",
+            parser_version, GPU_BACKFILL_PARSER_VERSIONS
+        )));
+    }
+    info!("Backfilling GPU info with parser version {}", parser_version);
+
+    let mut tx = state.db.begin().await.map_err(|e| {
+        error!("Failed to begin transaction: {}", e);
+        AppError::Database(e)
+    })?;
+
+    // The previous parse, per run in GPU order, to diff against and to carry brand/laptop/integrated flags over from;
+    // read in the rebuild's transaction, so the diff is against exactly the rows it replaces
+    let mut previous: std::collections::HashMap<i64, Vec<Gpu>> = std::collections::HashMap::new();
+    let previous_rows = GpuRepository::new(state.db.clone()).find_all_tx(&mut tx).await.map_err(|e| {
+        error!("Failed to fetch current GPU rows: {}", e);
+        AppError::Database(e)
+    })?;
+    for gpu in previous_rows {
+        if let Some(run_id) = gpu.run_id {
+            previous.entry(run_id).or_default().push(gpu);
+        }
+    }
+    for gpus in previous.values_mut() {
+        gpus.sort_by_key(|gpu| gpu.gpu_index);
+    }
+
+    let runs = RunsRepository::new(state.db.clone()).find_all_device_infos_tx(&mut tx).await.map_err(|e| {
+        error!("Failed to fetch runs data: {}", e);
+        AppError::Database(e)
+    })?;
+
+    // Rebuild into a shadow table like process-gpu, so readers switch over at commit
+    let shadow = ShadowTable::<Gpu>::create_tx(&mut tx).await.map_err(|e| {
+        error!("Failed to create GPU shadow table: {}", e);
+        AppError::Database(e)
+    })?;
+
+    let mut runs_processed = 0;
+    let mut rows_inserted = 0;
+    let mut runs_changed = 0;
+    let mut diffs = Vec::new();
+
+    for run in runs {
+        let Some(device_info) = run.device_info else {
+            warn!("Run {} has no device_info, skipping", run.id);
+            continue;
+        };
+        runs_processed += 1;
+
+        let before = previous.remove(&run.id).unwrap_or_default();
+        let mut after = gpu_rows_with_parser(run.id, &device_info, parser_version);
+        for gpu in &mut after {
+            if let Some(old) = before.iter().find(|old| old.gpu_index == gpu.gpu_index && old.device == gpu.device) {
+                gpu.brand = old.brand.clone();
+                gpu.is_laptop = old.is_laptop;
+                gpu.is_integrated = old.is_integrated;
+                gpu.is_cloud = old.is_cloud;
             }
-            Err(e) => {
-                error!("Failed to insert GPU info for run {}: {}", run_id, e);
-                // Continue processing other runs
+        }
+
+        let before_rows: Vec<BackfillGpuRow> = before.iter().map(backfill_gpu_row).collect();
+        let after_rows: Vec<BackfillGpuRow> = after.iter().map(backfill_gpu_row).collect();
+        if before_rows != after_rows {
+            runs_changed += 1;
+            if diffs.len() < BACKFILL_DIFF_LIMIT {
+                diffs.push(BackfillRunDiff { run_id: run.id, before: before_rows, after: after_rows });
             }
         }
+
+        for gpu in after {
+            shadow.insert_tx(gpu, &mut tx).await.map_err(|e| {
+                error!("Failed to insert backfilled GPU row for run {}: {}", run.id, e);
+                AppError::Database(e)
+            })?;
+            rows_inserted += 1;
+        }
     }
 
-    // Commit transaction
-    if let Err(e) = tx.commit().await {
+    shadow.swap_tx(&mut tx).await.map_err(|e| {
+        error!("Failed to swap in backfilled GPU data: {}", e);
+        AppError::Database(e)
+    })?;
+
+    // GPU counts may have changed, and with them the multi-GPU tags
+    TagRepository::new(state.db.clone())
+        .refresh_auto_tags_tx(&mut tx)
+        .await
+        .map_err(|e| {
+            error!("Failed to refresh automatic run tags: {}", e);
+            AppError::Database(e)
+        })?;
+
+    tx.commit().await.map_err(|e| {
         error!("Failed to commit transaction: {}", e);
-        return Err(AppError::Database(e));
-    }
+        AppError::Database(e)
+    })?;
 
-    info!("GPU processing complete: {} rows inserted", inserted_rows);
+    info!(
+        "GPU backfill with parser version {} complete: {} runs, {} rows, {} changed",
+        parser_version, runs_processed, rows_inserted, runs_changed
+    );
 
-    let response = ProcessGpuResponse {
+    Ok(Json(BackfillResponse {
         success: true,
-        rows_inserted: inserted_rows,
-    };
+        stage: "gpu".to_string(),
+        parser_version,
+        runs_processed,
+        rows_inserted,
+        runs_changed,
+        diffs,
+    }))
+}
 
-    Ok(Json(response))
+fn backfill_gpu_row(gpu: &Gpu) -> BackfillGpuRow {
+    BackfillGpuRow {
+        device: gpu.device.clone(),
+        driver: gpu.driver.clone(),
+        gpu_chip: gpu.gpu_chip.clone(),
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -1007,6 +1719,19 @@ pub struct UpdateGpuBrandsResponse {
     pub message: String,
     pub total_updates: usize,
     pub update_counts_by_brand: Vec<BrandCount>,
+    pub dry_run: bool,
+    /// GPUs whose brand would change, only on dry runs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub samples: Option<Vec<GpuBrandChange>>,
+}
+
+/// A GPU row whose brand update-gpu-brands would change
+#[derive(Debug, Serialize)]
+pub struct GpuBrandChange {
+    pub id: i64,
+    pub device: String,
+    pub brand: Option<String>,
+    pub new_brand: String,
 }
 
 #[derive(Debug, Serialize)]
@@ -1034,31 +1759,55 @@ fn get_brand_name(device_string: &str) -> String {
     }
 }
 
+/// Derive every GPU's brand from its device name
+///
+/// With `?dry_run=true` the updates are made and then rolled back, and the
+/// response samples the GPUs whose brand would change.
 pub async fn update_gpu_brands(
     State(state): State<AppState>,
+    Query(query): Query<DryRunQuery>,
 ) -> Result<Json<UpdateGpuBrandsResponse>, AppError> {
-    info!("Updating GPU brand information");
-
     // Start transaction
     let mut tx = state.db.begin().await.map_err(|e| {
         error!("Failed to begin transaction: {}", e);
         AppError::Database(e)
     })?;
 
+    let response = update_gpu_brands_tx(&state, query.dry_run, &mut tx).await?;
+
+    // A dry run discards the changes it just counted
+    if query.dry_run {
+        tx.rollback().await.map_err(|e| {
+            error!("Failed to roll back transaction: {}", e);
+            AppError::Database(e)
+        })?;
+    } else if let Err(e) = tx.commit().await {
+        error!("Failed to commit transaction: {}", e);
+        return Err(AppError::Database(e));
+    }
+
+    Ok(Json(response))
+}
+
+/// The update-gpu-brands step within a transaction, also run as a pipeline stage
+///
+/// `dry_run` only adds samples to the response; the caller rolls the transaction back.
+pub(crate) async fn update_gpu_brands_tx(
+    state: &AppState,
+    dry_run: bool,
+    tx: &mut Transaction<'_, Sqlite>,
+) -> Result<UpdateGpuBrandsResponse, AppError> {
+    info!("Updating GPU brand information (dry_run={})", dry_run);
+
     // Fetch all GPU data
     let gpu_repo = GpuRepository::new(state.db.clone());
-    let gpu_data = gpu_repo.find_all().await.map_err(|e| {
+    let gpu_data = gpu_repo.find_all_tx(tx).await.map_err(|e| {
         error!("Failed to fetch GPU data: {}", e);
         AppError::Database(e)
     })?;
 
     if gpu_data.is_empty() {
         info!("No GPU data found to update");
-        tx.commit().await.map_err(|e| {
-            error!("Failed to commit transaction: {}", e);
-            AppError::Database(e)
-        })?;
-
         // Return all brand categories with 0 counts
         let update_counts_by_brand = vec![
             BrandCount {
@@ -1084,9 +1833,11 @@ pub async fn update_gpu_brands(
             message: "No GPU data found to update".to_string(),
             total_updates: 0,
             update_counts_by_brand,
+            dry_run,
+            samples: dry_run.then(Vec::new),
         };
 
-        return Ok(Json(response));
+        return Ok(response);
     }
 
     info!("Found {} GPUs to update", gpu_data.len());
@@ -1097,6 +1848,7 @@ pub async fn update_gpu_brands(
     brand_counts.insert("amd".to_string(), 0);
     brand_counts.insert("intel".to_string(), 0);
     brand_counts.insert("unknown".to_string(), 0);
+    let mut samples = dry_run.then(Vec::new);
 
     // Process each GPU
     for gpu in &gpu_data {
@@ -1116,24 +1868,30 @@ pub async fn update_gpu_brands(
         total_updates += 1;
         *brand_counts.get_mut(&brand_name).unwrap() += 1;
 
+        if let Some(samples) = samples.as_mut()
+            && samples.len() < DRY_RUN_SAMPLE_SIZE
+            && gpu.brand.as_deref() != Some(brand_name.as_str())
+        {
+            samples.push(GpuBrandChange {
+                id: gpu_id,
+                device: device.clone(),
+                brand: gpu.brand.clone(),
+                new_brand: brand_name.clone(),
+            });
+        }
+
         info!("Updating brand for GPU ID {} to {}", gpu_id, brand_name);
 
         // Update the GPU record
         let mut updated_gpu = gpu.clone();
         updated_gpu.brand = Some(brand_name);
 
-        if let Err(e) = gpu_repo.update_tx(updated_gpu, &mut tx).await {
+        if let Err(e) = gpu_repo.update_tx(updated_gpu, tx).await {
             error!("Failed to update GPU {}: {}", gpu_id, e);
             // Continue processing other GPUs
         }
     }
 
-    // Commit transaction
-    if let Err(e) = tx.commit().await {
-        error!("Failed to commit transaction: {}", e);
-        return Err(AppError::Database(e));
-    }
-
     info!("GPU brand update complete: {} total updates", total_updates);
 
     // Convert brand counts to response format
@@ -1147,12 +1905,18 @@ pub async fn update_gpu_brands(
 
     let response = UpdateGpuBrandsResponse {
         status: true,
-        message: "GPU brand information updated successfully!".to_string(),
+        message: if dry_run {
+            "Dry run: GPU brand information that would be updated".to_string()
+        } else {
+            "GPU brand information updated successfully!".to_string()
+        },
         total_updates,
         update_counts_by_brand,
+        dry_run,
+        samples,
     };
 
-    Ok(Json(response))
+    Ok(response)
 }
 
 #[derive(Debug, Serialize)]
@@ -1161,51 +1925,109 @@ pub struct UpdateGpuLaptopInfoResponse {
     pub message: String,
     pub total_updates: usize,
     pub laptop_only_updates: usize,
+    /// Laptop GPUs credited to each configured pattern, in the order listed
+    pub pattern_matches: Vec<LaptopPatternCount>,
+    /// Automatic tags ("laptop", "multi-gpu") attached after the update
+    pub auto_tags: u64,
+    pub dry_run: bool,
+    /// GPUs whose laptop flag would change, only on dry runs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub samples: Option<Vec<GpuLaptopChange>>,
+}
+
+/// A GPU row whose laptop flag update-gpu-laptop-info would change
+#[derive(Debug, Serialize)]
+pub struct GpuLaptopChange {
+    pub id: i64,
+    pub device: String,
+    pub is_laptop: Option<bool>,
+    pub new_is_laptop: bool,
 }
 
-fn is_gpu_in_laptop(device_string: &str) -> bool {
-    device_string.contains("Laptop") || device_string.contains("Mobile")
+#[derive(Debug, Serialize)]
+pub struct LaptopPatternCount {
+    pub pattern: String,
+    pub count: usize,
 }
 
+/// Flag every GPU whose device name matches a `laptop_detection` pattern, then refresh the automatic tags
+///
+/// With `?dry_run=true` the updates are made and then rolled back, and the
+/// response samples the GPUs whose flag would change.
 pub async fn update_gpu_laptop_info(
     State(state): State<AppState>,
+    Query(query): Query<DryRunQuery>,
 ) -> Result<Json<UpdateGpuLaptopInfoResponse>, AppError> {
-    info!("Updating GPU laptop information");
-
     // Start transaction
     let mut tx = state.db.begin().await.map_err(|e| {
         error!("Failed to begin transaction: {}", e);
         AppError::Database(e)
     })?;
 
-    // Fetch all GPU data
-    let gpu_repo = GpuRepository::new(state.db.clone());
-    let gpu_data = gpu_repo.find_all().await.map_err(|e| {
-        error!("Failed to fetch GPU data: {}", e);
-        AppError::Database(e)
-    })?;
+    let response = update_gpu_laptop_info_tx(&state, query.dry_run, &mut tx).await?;
 
-    if gpu_data.is_empty() {
-        info!("No GPU data found to update");
-        tx.commit().await.map_err(|e| {
-            error!("Failed to commit transaction: {}", e);
+    // A dry run discards the changes it just counted
+    if query.dry_run {
+        tx.rollback().await.map_err(|e| {
+            error!("Failed to roll back transaction: {}", e);
             AppError::Database(e)
         })?;
+    } else if let Err(e) = tx.commit().await {
+        error!("Failed to commit transaction: {}", e);
+        return Err(AppError::Database(e));
+    }
 
-        let response = UpdateGpuLaptopInfoResponse {
-            status: true,
-            message: "No GPU data found to update".to_string(),
+    Ok(Json(response))
+}
+
+/// The update-gpu-laptop-info step within a transaction, also run as a pipeline stage
+///
+/// `dry_run` only adds samples to the response; the caller rolls the transaction back.
+pub(crate) async fn update_gpu_laptop_info_tx(
+    state: &AppState,
+    dry_run: bool,
+    tx: &mut Transaction<'_, Sqlite>,
+) -> Result<UpdateGpuLaptopInfoResponse, AppError> {
+    info!("Updating GPU laptop information (dry_run={})", dry_run);
+
+    let detector = LaptopDetector::new(&state.settings.laptop_detection);
+    let mut pattern_matches: Vec<LaptopPatternCount> = detector
+        .patterns()
+        .iter()
+        .map(|pattern| LaptopPatternCount {
+            pattern: pattern.clone(),
+            count: 0,
+        })
+        .collect();
+
+    // Fetch all GPU data through the transaction that refreshes the tags from it
+    let gpu_repo = GpuRepository::new(state.db.clone());
+    let gpu_data = gpu_repo.find_all_tx(tx).await.map_err(|e| {
+        error!("Failed to fetch GPU data: {}", e);
+        AppError::Database(e)
+    })?;
+
+    if gpu_data.is_empty() {
+        info!("No GPU data found to update");
+        let response = UpdateGpuLaptopInfoResponse {
+            status: true,
+            message: "No GPU data found to update".to_string(),
             total_updates: 0,
             laptop_only_updates: 0,
+            pattern_matches,
+            auto_tags: 0,
+            dry_run,
+            samples: dry_run.then(Vec::new),
         };
 
-        return Ok(Json(response));
+        return Ok(response);
     }
 
     info!("Found {} GPUs to update", gpu_data.len());
 
     let mut total_updates = 0;
     let mut laptop_only_updates = 0;
+    let mut samples = dry_run.then(Vec::new);
 
     // Process each GPU
     for gpu in &gpu_data {
@@ -1219,12 +2041,28 @@ pub async fn update_gpu_laptop_info(
             AppError::BadRequest("Missing device data".to_string())
         })?;
 
-        let is_laptop = is_gpu_in_laptop(device);
+        let pattern = detector.matching_pattern(device);
+        let is_laptop = pattern.is_some();
 
         // Update the count
         total_updates += 1;
-        if is_laptop {
+        if let Some(pattern) = pattern {
             laptop_only_updates += 1;
+            if let Some(pattern_count) = pattern_matches.iter_mut().find(|count| count.pattern == pattern) {
+                pattern_count.count += 1;
+            }
+        }
+
+        if let Some(samples) = samples.as_mut()
+            && samples.len() < DRY_RUN_SAMPLE_SIZE
+            && gpu.is_laptop != Some(is_laptop)
+        {
+            samples.push(GpuLaptopChange {
+                id: gpu_id,
+                device: device.clone(),
+                is_laptop: gpu.is_laptop,
+                new_is_laptop: is_laptop,
+            });
         }
 
         info!("Updating laptop info for GPU ID {} to {}", gpu_id, is_laptop);
@@ -1233,31 +2071,470 @@ pub async fn update_gpu_laptop_info(
         let mut updated_gpu = gpu.clone();
         updated_gpu.is_laptop = Some(is_laptop);
 
-        if let Err(e) = gpu_repo.update_tx(updated_gpu, &mut tx).await {
+        if let Err(e) = gpu_repo.update_tx(updated_gpu, tx).await {
             error!("Failed to update GPU {}: {}", gpu_id, e);
             // Continue processing other GPUs
         }
     }
 
-    // Commit transaction
-    if let Err(e) = tx.commit().await {
-        error!("Failed to commit transaction: {}", e);
-        return Err(AppError::Database(e));
-    }
+    // Re-derive the automatic run tags from the updated GPU data
+    let auto_tags = TagRepository::new(state.db.clone())
+        .refresh_auto_tags_tx(tx)
+        .await
+        .map_err(|e| {
+            error!("Failed to refresh automatic run tags: {}", e);
+            AppError::Database(e)
+        })?;
 
-    info!("GPU laptop info update complete: {} total updates, {} laptop updates", 
-          total_updates, laptop_only_updates);
+    info!("GPU laptop info update complete: {} total updates, {} laptop updates, {} automatic tags", 
+          total_updates, laptop_only_updates, auto_tags);
 
     let response = UpdateGpuLaptopInfoResponse {
         status: true,
-        message: "GPU laptop information updated successfully!".to_string(),
+        message: if dry_run {
+            "Dry run: GPU laptop information that would be updated".to_string()
+        } else {
+            "GPU laptop information updated successfully!".to_string()
+        },
         total_updates,
         laptop_only_updates,
+        pattern_matches,
+        auto_tags,
+        dry_run,
+        samples,
     };
 
+    Ok(response)
+}
+
+#[derive(Debug, Serialize)]
+pub struct UpdateGpuIntegratedInfoResponse {
+    pub success: bool,
+    pub total_rows: usize,
+    /// Rows whose device names an Intel or AMD integrated GPU
+    pub integrated_rows: usize,
+}
+
+/// Flag every GPU whose device names an integrated GPU, which stats and the leaderboard then leave out
+pub async fn update_gpu_integrated_info(
+    State(state): State<AppState>,
+) -> Result<Json<UpdateGpuIntegratedInfoResponse>, AppError> {
+    // Start transaction
+    let mut tx = state.db.begin().await.map_err(|e| {
+        error!("Failed to begin transaction: {}", e);
+        AppError::Database(e)
+    })?;
+
+    let response = update_gpu_integrated_info_tx(&state, &mut tx).await?;
+
+    // Commit transaction
+    if let Err(e) = tx.commit().await {
+        error!("Failed to commit transaction: {}", e);
+        return Err(AppError::Database(e));
+    }
+
     Ok(Json(response))
 }
 
+/// The update-gpu-integrated-info step within a transaction, also run as a pipeline stage
+pub(crate) async fn update_gpu_integrated_info_tx(
+    state: &AppState,
+    tx: &mut Transaction<'_, Sqlite>,
+) -> Result<UpdateGpuIntegratedInfoResponse, AppError> {
+    info!("Updating GPU integrated information");
+
+    let gpu_repo = GpuRepository::new(state.db.clone());
+    let gpus = gpu_repo.find_all_tx(tx).await.map_err(|e| {
+        error!("Failed to fetch GPU data: {}", e);
+        AppError::Database(e)
+    })?;
+
+    let mut integrated_rows = 0;
+    for gpu in &gpus {
+        let Some(gpu_id) = gpu.id else { continue };
+        let is_integrated = GpuInfoParser::is_integrated_gpu(gpu.device.as_deref().unwrap_or_default());
+        if is_integrated {
+            integrated_rows += 1;
+        }
+
+        gpu_repo
+            .set_integrated_tx(gpu_id, is_integrated, tx)
+            .await
+            .map_err(|e| {
+                error!("Failed to store integrated flag for GPU {}: {}", gpu_id, e);
+                AppError::Database(e)
+            })?;
+    }
+
+    info!("GPU integrated info update complete: {} of {} rows integrated", integrated_rows, gpus.len());
+
+    Ok(UpdateGpuIntegratedInfoResponse {
+        success: true,
+        total_rows: gpus.len(),
+        integrated_rows,
+    })
+}
+
+#[derive(Debug, Serialize)]
+pub struct UpdateGpuCloudInfoResponse {
+    pub success: bool,
+    pub total_rows: usize,
+    /// Rows on cloud hardware, by device name or by driver/kernel hint
+    pub cloud_rows: usize,
+    /// Cloud rows whose device names a datacenter GPU
+    pub datacenter_rows: usize,
+    /// Automatic tags attached by the refresh, "cloud" among them
+    pub auto_tags: u64,
+}
+
+/// Flag every GPU that ran on cloud hardware, then refresh the automatic tags so those runs carry "cloud"
+///
+/// A GPU counts as cloud hardware when its device names a datacenter GPU, or
+/// when its driver or its run's kernel release carries a cloud hint.
+pub async fn update_gpu_cloud_info(
+    State(state): State<AppState>,
+) -> Result<Json<UpdateGpuCloudInfoResponse>, AppError> {
+    // Start transaction
+    let mut tx = state.db.begin().await.map_err(|e| {
+        error!("Failed to begin transaction: {}", e);
+        AppError::Database(e)
+    })?;
+
+    let response = update_gpu_cloud_info_tx(&state, &mut tx).await?;
+
+    // Commit transaction
+    if let Err(e) = tx.commit().await {
+        error!("Failed to commit transaction: {}", e);
+        return Err(AppError::Database(e));
+    }
+
+    Ok(Json(response))
+}
+
+/// The update-gpu-cloud-info step within a transaction, also run as a pipeline stage
+pub(crate) async fn update_gpu_cloud_info_tx(
+    state: &AppState,
+    tx: &mut Transaction<'_, Sqlite>,
+) -> Result<UpdateGpuCloudInfoResponse, AppError> {
+    info!("Updating GPU cloud information");
+
+    let gpu_repo = GpuRepository::new(state.db.clone());
+    let gpus = gpu_repo.find_all_tx(tx).await.map_err(|e| {
+        error!("Failed to fetch GPU data: {}", e);
+        AppError::Database(e)
+    })?;
+
+    let system_infos = SystemInfoRepository::new(state.db.clone()).find_all_tx(tx).await.map_err(|e| {
+        error!("Failed to fetch system info: {}", e);
+        AppError::Database(e)
+    })?;
+    let kernel_releases: std::collections::HashMap<i64, String> = system_infos
+        .into_iter()
+        .filter_map(|system_info| Some((system_info.run_id?, system_info.release?)))
+        .collect();
+
+    let mut cloud_rows = 0;
+    let mut datacenter_rows = 0;
+    for gpu in &gpus {
+        let Some(gpu_id) = gpu.id else { continue };
+        let datacenter = is_datacenter_gpu(gpu.device.as_deref().unwrap_or_default());
+        let kernel_release = gpu.run_id.and_then(|run_id| kernel_releases.get(&run_id));
+        let is_cloud = datacenter || cloud_hint(gpu.driver.as_deref(), kernel_release.map(String::as_str)).is_some();
+        if is_cloud {
+            cloud_rows += 1;
+        }
+        if datacenter {
+            datacenter_rows += 1;
+        }
+
+        gpu_repo
+            .set_cloud_tx(gpu_id, is_cloud, tx)
+            .await
+            .map_err(|e| {
+                error!("Failed to store cloud flag for GPU {}: {}", gpu_id, e);
+                AppError::Database(e)
+            })?;
+    }
+
+    let auto_tags = TagRepository::new(state.db.clone())
+        .refresh_auto_tags_tx(tx)
+        .await
+        .map_err(|e| {
+            error!("Failed to refresh automatic run tags: {}", e);
+            AppError::Database(e)
+        })?;
+
+    info!(
+        "GPU cloud info update complete: {} of {} rows on cloud hardware ({} datacenter GPUs)",
+        cloud_rows,
+        gpus.len(),
+        datacenter_rows
+    );
+
+    Ok(UpdateGpuCloudInfoResponse {
+        success: true,
+        total_rows: gpus.len(),
+        cloud_rows,
+        datacenter_rows,
+        auto_tags,
+    })
+}
+
+#[derive(Debug, Serialize)]
+pub struct NormalizeCpuInfoResponse {
+    pub success: bool,
+    pub total_rows: usize,
+    /// Rows whose CPU vendor could be recognized
+    pub normalized_rows: usize,
+}
+
+/// Split every SystemInfo.cpu string into vendor, family, generation and model columns
+pub async fn normalize_cpu_info(
+    State(state): State<AppState>,
+) -> Result<Json<NormalizeCpuInfoResponse>, AppError> {
+    // Start transaction
+    let mut tx = state.db.begin().await.map_err(|e| {
+        error!("Failed to begin transaction: {}", e);
+        AppError::Database(e)
+    })?;
+
+    let response = normalize_cpu_info_tx(&state, &mut tx).await?;
+
+    // Commit transaction
+    if let Err(e) = tx.commit().await {
+        error!("Failed to commit transaction: {}", e);
+        return Err(AppError::Database(e));
+    }
+
+    Ok(Json(response))
+}
+
+/// The normalize-cpu-info step within a transaction, also run as a pipeline stage
+pub(crate) async fn normalize_cpu_info_tx(
+    state: &AppState,
+    tx: &mut Transaction<'_, Sqlite>,
+) -> Result<NormalizeCpuInfoResponse, AppError> {
+    info!("Normalizing CPU info");
+
+    let system_info_repo = SystemInfoRepository::new(state.db.clone());
+    let cpus = system_info_repo.find_all_cpus_tx(tx).await.map_err(|e| {
+        error!("Failed to fetch system info CPUs: {}", e);
+        AppError::Database(e)
+    })?;
+
+    let mut normalized_rows = 0;
+    for row in &cpus {
+        let parsed = CpuParser::parse(row.cpu.as_deref().unwrap_or_default());
+        if parsed.vendor.is_some() {
+            normalized_rows += 1;
+        }
+
+        system_info_repo
+            .update_cpu_fields_tx(
+                row.id,
+                parsed.vendor.as_deref(),
+                parsed.family.as_deref(),
+                parsed.generation,
+                parsed.model.as_deref(),
+                tx,
+            )
+            .await
+            .map_err(|e| {
+                error!("Failed to store normalized CPU for system info {}: {}", row.id, e);
+                AppError::Database(e)
+            })?;
+    }
+
+    info!("CPU normalization complete: {} of {} rows recognized", normalized_rows, cpus.len());
+
+    Ok(NormalizeCpuInfoResponse {
+        success: true,
+        total_rows: cpus.len(),
+        normalized_rows,
+    })
+}
+
+#[derive(Debug, Serialize)]
+pub struct CanonicalizeAppUrlsResponse {
+    pub success: bool,
+    pub total_rows: usize,
+    /// Rows whose URL named a host, owner and repository
+    pub parsed_rows: usize,
+    /// Parsed rows credited to an upstream project through the fork map
+    pub fork_rows: usize,
+}
+
+/// Split every AppDetails.url into host, owner and repository, and record the project each run counts toward
+///
+/// Forks listed in `app_attribution.fork_map` are credited to their upstream.
+/// Rows whose URL names no repository get no project.
+pub async fn canonicalize_app_urls(
+    State(state): State<AppState>,
+) -> Result<Json<CanonicalizeAppUrlsResponse>, AppError> {
+    info!("Canonicalizing app URLs");
+
+    let mut tx = state.db.begin().await.map_err(|e| {
+        error!("Failed to begin transaction: {}", e);
+        AppError::Database(e)
+    })?;
+
+    let canonicalized = canonicalize_app_urls_tx(&state, &mut tx).await?;
+
+    if let Err(e) = tx.commit().await {
+        error!("Failed to commit transaction: {}", e);
+        return Err(AppError::Database(e));
+    }
+
+    info!(
+        "App URL canonicalization complete: {} of {} rows parsed, {} credited to an upstream",
+        canonicalized.parsed_rows, canonicalized.total_rows, canonicalized.fork_rows
+    );
+
+    Ok(Json(canonicalized))
+}
+
+/// The canonicalize-app-urls step within a transaction, also run as a pipeline stage and when process-app-details rebuilds AppDetails
+pub(crate) async fn canonicalize_app_urls_tx(
+    state: &AppState,
+    tx: &mut Transaction<'_, Sqlite>,
+) -> Result<CanonicalizeAppUrlsResponse, AppError> {
+    let app_details_repo = AppDetailsRepository::new(state.db.clone());
+    let urls = app_details_repo.find_all_urls_tx(tx).await.map_err(|e| {
+        error!("Failed to fetch app details URLs: {}", e);
+        AppError::Database(e)
+    })?;
+
+    let attributor = AppAttributor::new(&state.settings.app_attribution);
+    let mut parsed_rows = 0;
+    let mut fork_rows = 0;
+    for row in &urls {
+        let parsed = row.url.as_deref().and_then(AppDetailsParser::parse_url_components);
+        let project = parsed.as_ref().map(|url| {
+            parsed_rows += 1;
+            let upstream = attributor.upstream(url);
+            if upstream.is_some() {
+                fork_rows += 1;
+            }
+            upstream.unwrap_or_else(|| url.repository())
+        });
+
+        app_details_repo
+            .update_url_components_tx(
+                row.id,
+                parsed.as_ref().map(|url| url.host.as_str()),
+                parsed.as_ref().map(|url| url.owner.as_str()),
+                parsed.as_ref().map(|url| url.repo.as_str()),
+                project.as_deref(),
+                tx,
+            )
+            .await
+            .map_err(|e| {
+                error!("Failed to store canonical URL for app details {}: {}", row.id, e);
+                AppError::Database(e)
+            })?;
+    }
+
+    Ok(CanonicalizeAppUrlsResponse {
+        success: true,
+        total_rows: urls.len(),
+        parsed_rows,
+        fork_rows,
+    })
+}
+
+#[derive(Debug, Serialize)]
+pub struct EnrichCommitDatesResponse {
+    pub success: bool,
+    /// Whether `commit_dates.enabled` allowed looking up commits on GitHub
+    pub lookups_enabled: bool,
+    /// Commits of GitHub repositories named by app details that were not cached yet
+    pub pending_commits: usize,
+    pub looked_up: usize,
+    /// Looked-up commits GitHub returned a date for
+    pub resolved: usize,
+    /// Looked-up commits GitHub does not know, cached so they are not asked for again
+    pub not_found: usize,
+    /// Commits left for a later call
+    pub remaining: usize,
+    /// AppDetails rows given a commit date from the cache
+    pub rows_updated: u64,
+    /// Why lookups stopped before `max_lookups`, e.g. GitHub's rate limit
+    pub stopped: Option<String>,
+}
+
+/// Look up the commit dates of app hashes on GitHub, caching them, and copy the cached dates to AppDetails
+///
+/// Needs the URL components from canonicalize-app-urls; only `github.com`
+/// repositories are looked up, at most `commit_dates.max_lookups` commits per
+/// call. Each answer is cached as soon as it arrives, so a failed lookup keeps
+/// the ones before it. With lookups disabled only the cache is applied, which
+/// restores the dates cleared by a process-app-details rebuild.
+pub async fn enrich_commit_dates(
+    State(state): State<AppState>,
+) -> Result<Json<EnrichCommitDatesResponse>, AppError> {
+    // Like the scheduled jobs, checked here as well as by the middleware, so no caller skips it
+    ensure_not_in_maintenance(&state.db).await?;
+
+    let config = &state.settings.commit_dates;
+    info!("Enriching app details with commit dates (lookups enabled: {})", config.enabled);
+
+    let commit_date_repo = CommitDateRepository::new(state.db.clone());
+    let pending = commit_date_repo.find_unresolved().await.map_err(|e| {
+        error!("Failed to fetch commits without a date: {}", e);
+        AppError::Database(e)
+    })?;
+
+    let mut looked_up = 0;
+    let mut resolved = 0;
+    let mut stopped = None;
+    if config.enabled && !pending.is_empty() {
+        let mut client = CommitDateClient::new(config)?;
+        for commit in pending.iter().take(config.max_lookups) {
+            let commit_date = match client.commit_date(commit).await {
+                Ok(commit_date) => commit_date,
+                Err(e) => {
+                    warn!("Stopping commit date lookups: {}", e);
+                    stopped = Some(e.to_string());
+                    break;
+                }
+            };
+            commit_date_repo.record(commit, commit_date.as_deref()).await.map_err(|e| {
+                error!("Failed to cache the date of {}/{}@{}: {}", commit.owner, commit.repo, commit.hash, e);
+                AppError::Database(e)
+            })?;
+            looked_up += 1;
+            if commit_date.is_some() {
+                resolved += 1;
+            }
+        }
+    }
+
+    let rows_updated = commit_date_repo.apply_to_app_details().await.map_err(|e| {
+        error!("Failed to copy commit dates to app details: {}", e);
+        AppError::Database(e)
+    })?;
+
+    info!(
+        "Commit date enrichment complete: {} of {} pending commits looked up, {} resolved, {} app details rows updated",
+        looked_up,
+        pending.len(),
+        resolved,
+        rows_updated
+    );
+
+    Ok(Json(EnrichCommitDatesResponse {
+        success: true,
+        lookups_enabled: config.enabled,
+        pending_commits: pending.len(),
+        looked_up,
+        resolved,
+        not_found: looked_up - resolved,
+        remaining: pending.len() - looked_up,
+        rows_updated,
+        stopped,
+    }))
+}
+
 #[derive(Debug, Serialize)]
 pub struct ProcessRunDetailsResponse {
     pub success: bool,
@@ -1267,26 +2544,39 @@ pub struct ProcessRunDetailsResponse {
 pub async fn process_run_details(
     State(state): State<AppState>,
 ) -> Result<Json<ProcessRunDetailsResponse>, AppError> {
-    info!("Processing run details");
-
     // Start transaction
     let mut tx = state.db.begin().await.map_err(|e| {
         error!("Failed to begin transaction: {}", e);
         AppError::Database(e)
     })?;
 
-    // Clear all existing data from RunMoreDetails table
-    let run_more_details_repo = RunMoreDetailsRepository::new(state.db.clone());
-    run_more_details_repo.clear_all_tx(&mut tx).await.map_err(|e| {
-        error!("Failed to clear RunMoreDetails table: {}", e);
+    let response = process_run_details_tx(&state, &mut tx).await?;
+
+    // Commit transaction
+    if let Err(e) = tx.commit().await {
+        error!("Failed to commit transaction: {}", e);
+        return Err(AppError::Database(e));
+    }
+
+    Ok(Json(response))
+}
+
+/// The process-run-details step within a transaction, also run as a pipeline stage
+pub(crate) async fn process_run_details_tx(
+    state: &AppState,
+    tx: &mut Transaction<'_, Sqlite>,
+) -> Result<ProcessRunDetailsResponse, AppError> {
+    info!("Processing run details");
+
+    // Rebuild into a shadow table; readers keep the current run details until the swap
+    let shadow = ShadowTable::<RunMoreDetails>::create_tx(tx).await.map_err(|e| {
+        error!("Failed to create run details shadow table: {}", e);
         AppError::Database(e)
     })?;
 
-    info!("Cleared existing RunMoreDetails data");
-
-    // Fetch data from runs table
+    // Fetch data from runs table through the transaction, which holds the shadow table's schema lock
     let runs_repo = RunsRepository::new(state.db.clone());
-    let runs_data = runs_repo.find_all().await.map_err(|e| {
+    let runs_data = runs_repo.find_all_details_inputs_tx(tx).await.map_err(|e| {
         error!("Failed to fetch runs data: {}", e);
         AppError::Database(e)
     })?;
@@ -1297,10 +2587,7 @@ pub async fn process_run_details(
 
     // Process each run and insert into RunMoreDetails
     for run in &runs_data {
-        let run_id = run.id.ok_or_else(|| {
-            error!("Run has no ID");
-            AppError::BadRequest("Invalid run data".to_string())
-        })?;
+        let run_id = run.id;
 
         info!("Processing run details for run ID: {}", run_id);
 
@@ -1312,9 +2599,11 @@ pub async fn process_run_details(
             user: run.user.clone(),
             notes: run.notes.clone(),
             model_map_id: None,
+            created_at: None,
+            updated_at: None,
         };
 
-        if let Err(e) = run_more_details_repo.create_tx(run_more_details, &mut tx).await {
+        if let Err(e) = shadow.insert_tx(run_more_details, tx).await {
             error!("Failed to insert run details for run {}: {}", run_id, e);
             // Continue processing other runs
         } else {
@@ -1322,11 +2611,11 @@ pub async fn process_run_details(
         }
     }
 
-    // Commit transaction
-    if let Err(e) = tx.commit().await {
-        error!("Failed to commit transaction: {}", e);
-        return Err(AppError::Database(e));
-    }
+    // Swap in the rebuilt table within the transaction, so readers switch over at commit
+    shadow.swap_tx(tx).await.map_err(|e| {
+        error!("Failed to swap in rebuilt run details: {}", e);
+        AppError::Database(e)
+    })?;
 
     info!("Run details processing complete: {} total inserts", insert_count);
 
@@ -1335,7 +2624,7 @@ pub async fn process_run_details(
         total_inserts: insert_count,
     };
 
-    Ok(Json(response))
+    Ok(response)
 }
 
 #[derive(Debug, Serialize)]
@@ -1385,7 +2674,16 @@ pub async fn app_details_analysis(
 #[derive(Debug, Serialize)]
 pub struct FixAppNamesResponse {
     pub message: String,
+    pub dry_run: bool,
     pub updated_counts: UpdatedCounts,
+    /// Of `updated_counts`, the rows matched only once the URL was lowercased, trimmed and stripped of trailing slashes
+    pub additional_matches: UpdatedCounts,
+    /// Change set holding the replaced app names, revertible through the change-sets endpoint
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub change_set_id: Option<i64>,
+    /// Rows each rule would rename, only on dry runs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub samples: Option<AppNameChangeSamples>,
 }
 
 #[derive(Debug, Serialize)]
@@ -1396,14 +2694,162 @@ pub struct UpdatedCounts {
     pub null_app_name_null_url: i64,
 }
 
+/// An AppDetails row a fix-app-names rule would rename
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct AppNameChange {
+    pub id: i64,
+    pub run_id: Option<i64>,
+    pub url: Option<String>,
+    pub app_name: Option<String>,
+    pub new_app_name: String,
+}
+
+/// The first `DRY_RUN_SAMPLE_SIZE` rows per rule, keyed like `UpdatedCounts`
+#[derive(Debug, Default, Serialize)]
+pub struct AppNameChangeSamples {
+    pub automatic1111: Vec<AppNameChange>,
+    pub vladmandic: Vec<AppNameChange>,
+    pub stable_diffusion: Vec<AppNameChange>,
+    pub null_app_name_null_url: Vec<AppNameChange>,
+}
+
+/// A fix-app-names rule, matched on the normalized URL
+///
+/// `raw_condition` is the rule as it read against the raw `url`; rows it misses
+/// are the matches normalizing gained. Both must be literal WHERE clauses.
+struct AppNameRule {
+    name: &'static str,
+    condition: &'static str,
+    raw_condition: &'static str,
+}
+
+const AUTOMATIC1111_RULE: AppNameRule = AppNameRule {
+    name: "AUTOMATIC1111",
+    condition: "url_normalized LIKE '%automatic1111%'",
+    raw_condition: "url LIKE '%AUTOMATIC1111%'",
+};
+
+const VLADMANDIC_RULE: AppNameRule = AppNameRule {
+    name: "Vladmandic",
+    condition: "url_normalized LIKE '%vladmandic%' AND (app_name IS NULL OR app_name = '')",
+    raw_condition: "url LIKE '%vladmandic%' AND (app_name IS NULL OR app_name = '')",
+};
+
+const STABLE_DIFFUSION_RULE: AppNameRule = AppNameRule {
+    name: "Stable Diffusion",
+    condition: "url_normalized LIKE '%stable-diffusion-webui%' AND app_name IS NULL",
+    raw_condition: "url LIKE '%stable-diffusion-webui%' AND app_name IS NULL",
+};
+
+// A blank or whitespace-only URL normalizes to NULL
+const NULL_APP_NAME_NULL_URL_RULE: AppNameRule = AppNameRule {
+    name: "NULL app_name NULL url",
+    condition: "app_name IS NULL AND url_normalized IS NULL",
+    raw_condition: "app_name IS NULL AND url IS NULL",
+};
+
+/// Rows matching a fix-app-names rule condition, as they are before the rule runs
+async fn app_name_change_samples(
+    rule: &AppNameRule,
+    new_app_name: &str,
+    tx: &mut Transaction<'static, Sqlite>,
+) -> Result<Vec<AppNameChange>, AppError> {
+    let query = format!(
+        "SELECT id, run_id, url, app_name, ? AS new_app_name FROM AppDetails WHERE {} ORDER BY id LIMIT ?",
+        rule.condition
+    );
+
+    sqlx::query_as::<_, AppNameChange>(&query)
+        .bind(new_app_name)
+        .bind(DRY_RUN_SAMPLE_SIZE as i64)
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(|e| {
+            error!("Failed to sample app name changes: {}", e);
+            AppError::Database(e)
+        })
+}
+
+/// Record the current app name of the rows a fix-app-names rule is about to rename
+async fn record_app_name_changes(
+    change_set_id: i64,
+    rule: &AppNameRule,
+    new_app_name: &str,
+    tx: &mut Transaction<'static, Sqlite>,
+) -> Result<(), AppError> {
+    let query = format!(
+        "INSERT INTO change_set_items (change_set_id, row_id, old_value, new_value) SELECT ?, id, app_name, ? FROM AppDetails WHERE {}",
+        rule.condition
+    );
+
+    sqlx::query(&query)
+        .bind(change_set_id)
+        .bind(new_app_name)
+        .execute(&mut **tx)
+        .await
+        .map_err(|e| {
+            error!("Failed to record app name changes: {}", e);
+            AppError::Database(e)
+        })?;
+
+    Ok(())
+}
+
+/// Rename the rows a fix-app-names rule matches, returning how many it renamed and how many of those the raw URL missed
+///
+/// The raw condition matches a subset of the normalized one, so its count taken
+/// just before the update is what the rule would have renamed without normalizing.
+async fn apply_app_name_rule(
+    rule: &AppNameRule,
+    new_app_name: &str,
+    now: &str,
+    tx: &mut Transaction<'static, Sqlite>,
+) -> Result<(i64, i64), AppError> {
+    let raw_matches: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM AppDetails WHERE {}", rule.raw_condition))
+        .fetch_one(&mut **tx)
+        .await
+        .map_err(|e| {
+            error!("Failed to count {} app names on the raw URL: {}", rule.name, e);
+            AppError::Database(e)
+        })?;
+
+    let updated = sqlx::query(&format!("UPDATE AppDetails SET app_name = ?, updated_at = ? WHERE {}", rule.condition))
+        .bind(new_app_name)
+        .bind(now)
+        .execute(&mut **tx)
+        .await
+        .map_err(|e| {
+            error!("Failed to update {} app names: {}", rule.name, e);
+            AppError::Database(e)
+        })?
+        .rows_affected() as i64;
+
+    let additional = (updated - raw_matches).max(0);
+    info!("Updated {} {} app names, {} matched only on the normalized URL", updated, rule.name, additional);
+
+    Ok((updated, additional))
+}
+
 // FixAppNamesRequest is now imported from validation module
 
+/// Apply the four app name rules in order
+///
+/// The rules match `AppDetails.url_normalized`, so case, padding and trailing
+/// slashes do not hide a URL, and a blank URL counts as a missing one; the
+/// response reports how many renames each rule gained from that.
+///
+/// With `?dry_run=true` the rules run in the request transaction as usual,
+/// sampling the rows each one renames, and the transaction is then rolled back.
+/// Otherwise the replaced app names are kept in a change set, so a wrong rule
+/// can be undone with `POST /api/admin/change-sets/{id}/revert`.
 pub async fn fix_app_names(
     State(state): State<AppState>,
+    request_tx: RequestTransaction,
+    Query(query): Query<DryRunQuery>,
     Json(request): Json<FixAppNamesRequest>,
 ) -> Result<Json<crate::handlers::common::ApiResponse<FixAppNamesResponse>>, AppError> {
-    info!("Fixing app names with parameters: automatic1111={}, vladmandic={}, stable_diffusion={}, null_app_name_null_url={}", 
-          request.automatic1111, request.vladmandic, request.stable_diffusion, request.null_app_name_null_url);
+    info!("Fixing app names with parameters: automatic1111={}, vladmandic={}, stable_diffusion={}, null_app_name_null_url={}, dry_run={}", 
+          request.automatic1111, request.vladmandic, request.stable_diffusion, request.null_app_name_null_url, query.dry_run);
 
     // Basic validation for request fields
     if request.automatic1111.is_empty() || request.vladmandic.is_empty() || 
@@ -1411,101 +2857,85 @@ pub async fn fix_app_names(
         return Err(AppError::Validation("All fields must be non-empty".to_string()));
     }
 
-    // Start a transaction
-    let mut tx = state.db.begin().await.map_err(|e| {
-        error!("Failed to begin transaction: {}", e);
-        AppError::Database(e)
-    })?;
+    // All four updates share the request transaction, committed only if every one succeeds
+    let mut tx = request_tx.begin().await?;
 
-    // Update AUTOMATIC1111 app names
-    let count_automatic1111 = sqlx::query!(
-        r#"
-        UPDATE AppDetails
-        SET app_name = ?
-        WHERE url LIKE '%AUTOMATIC1111%'
-        "#,
-        request.automatic1111
-    )
-    .execute(&mut *tx)
-    .await
-    .map_err(|e| {
-        error!("Failed to update AUTOMATIC1111 app names: {}", e);
-        AppError::Database(e)
-    })?
-    .rows_affected();
-
-    info!("Updated {} AUTOMATIC1111 app names", count_automatic1111);
-
-    // Update Vladmandic app names
-    let count_vladmandic = sqlx::query!(
-        r#"
-        UPDATE AppDetails
-        SET app_name = ?
-        WHERE url LIKE '%vladmandic%' AND (app_name IS NULL OR app_name = '')
-        "#,
-        request.vladmandic
-    )
-    .execute(&mut *tx)
-    .await
-    .map_err(|e| {
-        error!("Failed to update Vladmandic app names: {}", e);
-        AppError::Database(e)
-    })?
-    .rows_affected();
-
-    info!("Updated {} Vladmandic app names", count_vladmandic);
+    let now = audit_timestamp();
+    let mut samples = query.dry_run.then(AppNameChangeSamples::default);
+    let change_set_id = if query.dry_run {
+        None
+    } else {
+        let id = ChangeSetRepository::new(state.db.clone())
+            .create_tx(CHANGE_SET_ACTION_FIX_APP_NAMES, &mut tx)
+            .await
+            .map_err(|e| {
+                error!("Failed to open app names change set: {}", e);
+                AppError::Database(e)
+            })?;
+        Some(id)
+    };
 
-    // Update Stable Diffusion app names
-    let count_stable_diffusion = sqlx::query!(
-        r#"
-        UPDATE AppDetails
-        SET app_name = ?
-        WHERE url LIKE '%stable-diffusion-webui%' AND app_name IS NULL
-        "#,
-        request.stable_diffusion
-    )
-    .execute(&mut *tx)
-    .await
-    .map_err(|e| {
-        error!("Failed to update Stable Diffusion app names: {}", e);
-        AppError::Database(e)
-    })?
-    .rows_affected();
+    if let Some(samples) = samples.as_mut() {
+        samples.automatic1111 = app_name_change_samples(&AUTOMATIC1111_RULE, &request.automatic1111, &mut tx).await?;
+    }
+    if let Some(change_set_id) = change_set_id {
+        record_app_name_changes(change_set_id, &AUTOMATIC1111_RULE, &request.automatic1111, &mut tx).await?;
+    }
+    let (count_automatic1111, gained_automatic1111) =
+        apply_app_name_rule(&AUTOMATIC1111_RULE, &request.automatic1111, &now, &mut tx).await?;
 
-    info!("Updated {} Stable Diffusion app names", count_stable_diffusion);
+    if let Some(samples) = samples.as_mut() {
+        samples.vladmandic = app_name_change_samples(&VLADMANDIC_RULE, &request.vladmandic, &mut tx).await?;
+    }
+    if let Some(change_set_id) = change_set_id {
+        record_app_name_changes(change_set_id, &VLADMANDIC_RULE, &request.vladmandic, &mut tx).await?;
+    }
+    let (count_vladmandic, gained_vladmandic) =
+        apply_app_name_rule(&VLADMANDIC_RULE, &request.vladmandic, &now, &mut tx).await?;
 
-    // Update NULL app_name and NULL url records
-    let count_null_app_name_null_url = sqlx::query!(
-        r#"
-        UPDATE AppDetails
-        SET app_name = ?
-        WHERE app_name IS NULL AND url IS NULL
-        "#,
-        request.null_app_name_null_url
-    )
-    .execute(&mut *tx)
-    .await
-    .map_err(|e| {
-        error!("Failed to update NULL app_name NULL url records: {}", e);
-        AppError::Database(e)
-    })?
-    .rows_affected();
+    if let Some(samples) = samples.as_mut() {
+        samples.stable_diffusion = app_name_change_samples(&STABLE_DIFFUSION_RULE, &request.stable_diffusion, &mut tx).await?;
+    }
+    if let Some(change_set_id) = change_set_id {
+        record_app_name_changes(change_set_id, &STABLE_DIFFUSION_RULE, &request.stable_diffusion, &mut tx).await?;
+    }
+    let (count_stable_diffusion, gained_stable_diffusion) =
+        apply_app_name_rule(&STABLE_DIFFUSION_RULE, &request.stable_diffusion, &now, &mut tx).await?;
 
-    info!("Updated {} NULL app_name NULL url records", count_null_app_name_null_url);
+    if let Some(samples) = samples.as_mut() {
+        samples.null_app_name_null_url =
+            app_name_change_samples(&NULL_APP_NAME_NULL_URL_RULE, &request.null_app_name_null_url, &mut tx).await?;
+    }
+    if let Some(change_set_id) = change_set_id {
+        record_app_name_changes(change_set_id, &NULL_APP_NAME_NULL_URL_RULE, &request.null_app_name_null_url, &mut tx).await?;
+    }
+    let (count_null_app_name_null_url, gained_null_app_name_null_url) =
+        apply_app_name_rule(&NULL_APP_NAME_NULL_URL_RULE, &request.null_app_name_null_url, &now, &mut tx).await?;
 
-    // Commit transaction
-    tx.commit().await.map_err(|e| {
-        error!("Failed to commit transaction: {}", e);
-        AppError::Database(e)
-    })?;
+    // A dry run discards the updates it just measured
+    let message = if query.dry_run {
+        tx.rollback().await?;
+        "Dry run: app names that would be updated"
+    } else {
+        "App names updated successfully"
+    };
 
     let response = FixAppNamesResponse {
-        message: "App names updated successfully".to_string(),
+        message: message.to_string(),
+        dry_run: query.dry_run,
+        samples,
+        change_set_id,
         updated_counts: UpdatedCounts {
-            automatic1111: count_automatic1111 as i64,
-            vladmandic: count_vladmandic as i64,
-            stable_diffusion: count_stable_diffusion as i64,
-            null_app_name_null_url: count_null_app_name_null_url as i64,
+            automatic1111: count_automatic1111,
+            vladmandic: count_vladmandic,
+            stable_diffusion: count_stable_diffusion,
+            null_app_name_null_url: count_null_app_name_null_url,
+        },
+        additional_matches: UpdatedCounts {
+            automatic1111: gained_automatic1111,
+            vladmandic: gained_vladmandic,
+            stable_diffusion: gained_stable_diffusion,
+            null_app_name_null_url: gained_null_app_name_null_url,
         },
     };
 
@@ -1514,7 +2944,7 @@ pub async fn fix_app_names(
 
     Ok(crate::handlers::common::create_success_response(
         response,
-        "App names updated successfully",
+        message,
         axum::http::StatusCode::OK,
     ))
 }
@@ -1523,26 +2953,74 @@ pub async fn fix_app_names(
 pub struct UpdateRunMoreDetailsWithModelMapIdResponse {
     pub success: bool,
     pub message: String,
+    pub dry_run: bool,
+    /// Details linked to a ModelMap entry, by name or by checkpoint hash
+    pub updated: usize,
+    pub hash_matched: usize,
+    pub not_found: usize,
+    /// Details that would be linked, only on dry runs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub samples: Option<Vec<ModelMapLink>>,
 }
 
+/// A RunMoreDetails row the model-map linking step would point at a ModelMap entry
+#[derive(Debug, Serialize)]
+pub struct ModelMapLink {
+    pub id: i64,
+    pub model_name: String,
+    pub model_map_id: i64,
+    pub matched_by_hash: bool,
+}
+
+/// Link every RunMoreDetails row without a ModelMapId to ModelMap, by model name or checkpoint hash
+///
+/// With `?dry_run=true` the links are made and then rolled back, and the
+/// response samples the rows that would be linked.
 pub async fn update_run_more_details_with_modelmapid(
     State(state): State<AppState>,
+    Query(query): Query<DryRunQuery>,
 ) -> Result<Json<UpdateRunMoreDetailsWithModelMapIdResponse>, AppError> {
-    info!("Updating RunMoreDetails with ModelMapId");
-
-    // Start a transaction
+    // Start transaction
     let mut tx = state.db.begin().await.map_err(|e| {
         error!("Failed to begin transaction: {}", e);
         AppError::Database(e)
     })?;
 
+    let response = update_run_more_details_with_modelmapid_tx(&state, query.dry_run, &mut tx).await?;
+
+    // A dry run discards the changes it just counted
+    if query.dry_run {
+        tx.rollback().await.map_err(|e| {
+            error!("Failed to roll back transaction: {}", e);
+            AppError::Database(e)
+        })?;
+    } else if let Err(e) = tx.commit().await {
+        error!("Failed to commit transaction: {}", e);
+        return Err(AppError::Database(e));
+    }
+
+    Ok(Json(response))
+}
+
+/// The update-run-more-details-with-modelmapid step within a transaction, also run as a pipeline stage
+///
+/// `dry_run` only adds samples to the response; the caller rolls the transaction back.
+pub(crate) async fn update_run_more_details_with_modelmapid_tx(
+    state: &AppState,
+    dry_run: bool,
+    tx: &mut Transaction<'_, Sqlite>,
+) -> Result<UpdateRunMoreDetailsWithModelMapIdResponse, AppError> {
+    info!("Updating RunMoreDetails with ModelMapId (dry_run={})", dry_run);
+
+    let now = audit_timestamp();
+
     // Get all runs from RunMoreDetails that don't have ModelMapId filled
     let runs_without_modelmapid = sqlx::query!(
         r#"
-        SELECT id, model_name FROM RunMoreDetails WHERE ModelMapId IS NULL
+        SELECT id AS "id!", model_name FROM RunMoreDetails WHERE ModelMapId IS NULL
         "#
     )
-    .fetch_all(&mut *tx)
+    .fetch_all(&mut **tx)
     .await
     .map_err(|e| {
         error!("Failed to fetch RunMoreDetails without ModelMapId: {}", e);
@@ -1551,23 +3029,26 @@ pub async fn update_run_more_details_with_modelmapid(
 
     if runs_without_modelmapid.is_empty() {
         info!("All RunMoreDetails entries already have ModelMapId");
-        tx.commit().await.map_err(|e| {
-            error!("Failed to commit transaction: {}", e);
-            AppError::Database(e)
-        })?;
-
         let response = UpdateRunMoreDetailsWithModelMapIdResponse {
             success: true,
             message: "All RunMoreDetails entries already have ModelMapId.".to_string(),
+            dry_run,
+            updated: 0,
+            hash_matched: 0,
+            not_found: 0,
+            samples: dry_run.then(Vec::new),
         };
 
-        return Ok(Json(response));
+        return Ok(response);
     }
 
     info!("Found {} RunMoreDetails entries without ModelMapId", runs_without_modelmapid.len());
 
     let mut updated_count = 0;
     let mut not_found_count = 0;
+    let mut hash_matched_count = 0;
+    let mut samples = dry_run.then(Vec::new);
+    let model_map_repository = ModelMapRepository::new(state.db.clone());
 
     // For each run, find the corresponding ModelMapId from ModelMap based on model_name
     for run in &runs_without_modelmapid {
@@ -1580,58 +3061,204 @@ pub async fn update_run_more_details_with_modelmapid(
             }
         };
 
-        let model_map_entry = sqlx::query!(
+        let mut model_map_id = sqlx::query!(
             r#"
             SELECT id FROM ModelMap WHERE model_name = ?
             "#,
             model_name
         )
-        .fetch_optional(&mut *tx)
+        .fetch_optional(&mut **tx)
         .await
         .map_err(|e| {
             error!("Failed to query ModelMap for model_name '{}': {}", model_name, e);
             AppError::Database(e)
-        })?;
+        })?
+        .map(|entry| entry.id);
+        let mut matched_by_hash = false;
+
+        // Fall back to the checkpoint hash embedded in the model name (e.g. "name [6ce0161689]")
+        if model_map_id.is_none()
+            && let Some(hash) = ModelNameParser::parse_checkpoint_hash(model_name)
+        {
+            for key in ModelNameParser::hash_lookup_keys(&hash) {
+                model_map_id = model_map_repository.find_id_by_hash_tx(&key, tx).await.map_err(|e| {
+                    error!("Failed to query ModelMap for checkpoint hash '{}': {}", key, e);
+                    AppError::Database(e)
+                })?;
+                if model_map_id.is_some() {
+                    hash_matched_count += 1;
+                    matched_by_hash = true;
+                    break;
+                }
+            }
+        }
 
-        if let Some(model_map_entry) = model_map_entry {
+        if let Some(model_map_id) = model_map_id {
             // Update RunMoreDetails with the found ModelMapId
             sqlx::query!(
                 r#"
-                UPDATE RunMoreDetails SET ModelMapId = ? WHERE id = ?
+                UPDATE RunMoreDetails SET ModelMapId = ?, updated_at = ? WHERE id = ?
                 "#,
-                model_map_entry.id,
+                model_map_id,
+                now,
                 run.id
             )
-            .execute(&mut *tx)
+            .execute(&mut **tx)
             .await
             .map_err(|e| {
                 error!("Failed to update RunMoreDetails ID {} with ModelMapId {}: {}", 
-                       run.id, model_map_entry.id, e);
+                       run.id, model_map_id, e);
                 AppError::Database(e)
             })?;
 
             updated_count += 1;
+            if let Some(samples) = samples.as_mut()
+                && samples.len() < DRY_RUN_SAMPLE_SIZE
+            {
+                samples.push(ModelMapLink {
+                    id: run.id,
+                    model_name: model_name.clone(),
+                    model_map_id,
+                    matched_by_hash,
+                });
+            }
             info!("Updated RunMoreDetails ID {} with ModelMapId {} for model_name '{}'", 
-                  run.id, model_map_entry.id, model_name);
+                  run.id, model_map_id, model_name);
         } else {
             info!("No matching entry in ModelMap for model_name: {}", model_name);
             not_found_count += 1;
         }
     }
 
-    // Commit transaction
-    tx.commit().await.map_err(|e| {
-        error!("Failed to commit transaction: {}", e);
-        AppError::Database(e)
-    })?;
-
+    let message = if dry_run {
+        format!("Dry run: RunMoreDetails that would be updated with ModelMapId. Updated: {} ({} by checkpoint hash), Not found: {}",
+                updated_count, hash_matched_count, not_found_count)
+    } else {
+        format!("RunMoreDetails updated with ModelMapId successfully. Updated: {} ({} by checkpoint hash), Not found: {}", 
+                updated_count, hash_matched_count, not_found_count)
+    };
     let response = UpdateRunMoreDetailsWithModelMapIdResponse {
         success: true,
-        message: format!("RunMoreDetails updated with ModelMapId successfully. Updated: {}, Not found: {}", 
-                        updated_count, not_found_count),
+        message,
+        dry_run,
+        updated: updated_count,
+        hash_matched: hash_matched_count,
+        not_found: not_found_count,
+        samples,
     };
 
-    info!("RunMoreDetails update complete: {} updated, {} not found", updated_count, not_found_count);
+    info!("RunMoreDetails update complete: {} updated ({} by checkpoint hash), {} not found",
+          updated_count, hash_matched_count, not_found_count);
+
+    Ok(response)
+}
+
+pub async fn compute_run_scores(
+    State(state): State<AppState>,
+) -> Result<Json<crate::handlers::common::ProcessingResponse>, AppError> {
+    // Start transaction
+    let mut tx = state.db.begin().await.map_err(|e| {
+        error!("Failed to begin transaction: {}", e);
+        AppError::Database(e)
+    })?;
+
+    let mut response = compute_run_scores_tx(&state, &mut tx).await?;
+
+    // Commit transaction
+    let mut timer = StageTimer::start("commit");
+    if let Err(e) = timer.db(tx.commit()).await {
+        error!("Failed to commit transaction: {}", e);
+        return Err(AppError::Database(e));
+    }
+    response.stage_timings.push(timer.finish(response.rows_inserted));
 
     Ok(Json(response))
-}
\ No newline at end of file
+}
+
+/// The compute-run-scores step within a transaction, also run as a pipeline stage
+pub(crate) async fn compute_run_scores_tx(
+    state: &AppState,
+    tx: &mut Transaction<'_, Sqlite>,
+) -> Result<crate::handlers::common::ProcessingResponse, AppError> {
+    info!("Computing run scores");
+
+    let service = ComputeRunScoresService::new(
+        RunScoreRepository::new(state.db.clone()),
+        state.db.clone(),
+        state.settings.scoring.clone(),
+    );
+    let output = service.compute_scores_tx(tx).await?;
+
+    info!("Run score computation complete: {} scored, {} skipped", output.scored_rows, output.skipped_rows);
+
+    let Json(mut response) = crate::handlers::common::create_processing_response(
+        &output.message,
+        output.total_runs,
+        output.scored_rows,
+        0, // rows_updated
+        0, // rows_deleted
+        vec![], // errors
+        axum::http::StatusCode::OK,
+    );
+    response.stage_timings = output.stage_timings;
+    Ok(response)
+}
+
+/// Upsert the bundled GPU catalog into GPUBase (and its specs into GPUSpec)
+pub async fn seed_gpu_base(
+    State(state): State<AppState>,
+) -> Result<Json<crate::handlers::common::ProcessingResponse>, AppError> {
+    info!("Seeding GPUBase from the bundled GPU catalog");
+
+    let service = SeedGpuBaseService::new(GpuBaseRepository::new(state.db.clone()), state.db.clone());
+    let output = service.seed_gpu_base().await?;
+
+    Ok(crate::handlers::common::create_processing_response(
+        &output.message,
+        output.total_entries,
+        output.inserted_rows,
+        output.updated_rows,
+        0, // rows_deleted
+        vec![], // errors
+        axum::http::StatusCode::OK,
+    ))
+}
+
+/// Rebuild the per-GPU percentile cache served by `/api/stats/gpu-percentiles`
+pub async fn refresh_percentiles(
+    State(state): State<AppState>,
+) -> Result<Json<crate::handlers::common::ProcessingResponse>, AppError> {
+    info!("Refreshing the GPU percentile cache");
+
+    let output = PercentileService::new(state.db.clone()).refresh().await?;
+
+    Ok(crate::handlers::common::create_processing_response(
+        &output.message,
+        output.total_runs,
+        output.gpu_rows,
+        0, // rows_updated
+        0, // rows_deleted
+        vec![], // errors
+        axum::http::StatusCode::OK,
+    ))
+}
+
+/// Upsert the bundled checkpoint catalog into ModelMap along with its known hashes
+pub async fn seed_model_map(
+    State(state): State<AppState>,
+) -> Result<Json<crate::handlers::common::ProcessingResponse>, AppError> {
+    info!("Seeding ModelMap from the bundled model catalog");
+
+    let service = SeedModelMapService::new(ModelMapRepository::new(state.db.clone()), state.db.clone());
+    let output = service.seed_model_map().await?;
+
+    Ok(crate::handlers::common::create_processing_response(
+        &output.message,
+        output.total_entries,
+        output.inserted_rows,
+        output.updated_rows,
+        0, // rows_deleted
+        vec![], // errors
+        axum::http::StatusCode::OK,
+    ))
+}
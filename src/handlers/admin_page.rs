@@ -0,0 +1,25 @@
+use axum::{extract::State, response::Html};
+use tracing::info;
+
+use crate::{
+    error::types::AppError,
+    handlers::{meta::load_dataset_metadata, pipeline::pipeline_registry},
+    middleware::tenant::Tenant,
+    services::admin_page::render_admin_page,
+    AppState,
+};
+
+/// Server-rendered operator page for instances without the dashboard SPA
+pub async fn admin_page(State(state): State<AppState>, tenant: Tenant) -> Result<Html<String>, AppError> {
+    info!("Rendering admin page");
+
+    let metadata = load_dataset_metadata(&state, &tenant).await?;
+    let title = format!("{} admin", state.settings.application.name);
+
+    // The whole pipeline first, then its stages in the order it runs them
+    let registry = pipeline_registry();
+    let mut steps = vec!["run-pipeline"];
+    steps.extend(registry.execution_order()?.iter().map(|stage| stage.name()));
+
+    Ok(Html(render_admin_page(&title, &metadata, &steps)))
+}
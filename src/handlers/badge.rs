@@ -0,0 +1,51 @@
+use axum::{
+    extract::{Path, State},
+    http::header,
+    response::{IntoResponse, Response},
+};
+use tracing::{error, info};
+
+use crate::{
+    error::types::AppError,
+    middleware::tenant::Tenant,
+    repositories::gpu_repository::GpuRepository,
+    services::badges::{its_color, median, render_badge, COLOR_NO_DATA},
+    AppState,
+};
+
+/// Render an embeddable SVG badge with a GPU's median it/s in the tenant's dataset
+///
+/// The GPU is matched by reported device name or base GPU name. Unknown GPUs
+/// still get a "no data" badge so embeds never show a broken image.
+pub async fn gpu_badge(
+    State(state): State<AppState>,
+    tenant: Tenant,
+    Path(file_name): Path<String>,
+) -> Result<Response, AppError> {
+    let gpu_name = file_name
+        .strip_suffix(".svg")
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .ok_or_else(|| AppError::not_found(format!("Badge {}", file_name)))?;
+    info!("Rendering ITS badge for GPU {}", gpu_name);
+
+    let repository = GpuRepository::new(state.db.clone());
+    let samples = repository.find_avg_its_by_gpu_name(tenant.id(), gpu_name).await.map_err(|e| {
+        error!("Failed to fetch ITS samples for GPU {}: {}", gpu_name, e);
+        AppError::Database(e)
+    })?;
+
+    let svg = match median(&samples) {
+        Some(its) => render_badge(gpu_name, &format!("{:.2} it/s", its), its_color(its)),
+        None => render_badge(gpu_name, "no data", COLOR_NO_DATA),
+    };
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "image/svg+xml; charset=utf-8".to_string()),
+            (header::CACHE_CONTROL, format!("public, max-age={}", state.settings.badges.cache_ttl_seconds)),
+        ],
+        svg,
+    )
+        .into_response())
+}
@@ -0,0 +1,99 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+};
+use tracing::{error, info};
+
+use crate::{
+    error::types::AppError,
+    handlers::common::{create_success_response, ApiResponse},
+    middleware::request_transaction::RequestTransaction,
+    models::change_set::{ChangeSet, ChangeSetRevert, CHANGE_SET_ACTION_FIX_APP_NAMES},
+    repositories::{app_details_repository::AppDetailsRepository, change_set_repository::ChangeSetRepository},
+    AppState,
+};
+
+/// Applied data fixes, newest first
+pub async fn list_change_sets(State(state): State<AppState>) -> Result<Json<ApiResponse<Vec<ChangeSet>>>, AppError> {
+    let change_sets = ChangeSetRepository::new(state.db.clone())
+        .find_all()
+        .await
+        .map_err(|e| {
+            error!("Failed to fetch change sets: {}", e);
+            AppError::Database(e)
+        })?;
+
+    Ok(create_success_response(change_sets, "Change sets retrieved successfully", StatusCode::OK))
+}
+
+/// Put back the values a change set replaced, in the request transaction
+///
+/// Items are undone newest first, and a row is only restored while it still
+/// holds the value the fix wrote; rows edited since are skipped and counted.
+pub async fn revert_change_set(
+    State(state): State<AppState>,
+    request_tx: RequestTransaction,
+    Path(id): Path<i64>,
+) -> Result<Json<ApiResponse<ChangeSetRevert>>, AppError> {
+    let mut tx = request_tx.begin().await?;
+    let change_set_repository = ChangeSetRepository::new(state.db.clone());
+
+    let change_set = change_set_repository
+        .find_by_id_tx(id, &mut tx)
+        .await
+        .map_err(|e| {
+            error!("Failed to fetch change set {}: {}", id, e);
+            AppError::Database(e)
+        })?
+        .ok_or_else(|| AppError::not_found(format!("Change set with id {}", id)))?;
+    if let Some(reverted_at) = &change_set.reverted_at {
+        return Err(AppError::bad_request(format!("Change set {} was already reverted at {}", id, reverted_at)));
+    }
+    if change_set.action != CHANGE_SET_ACTION_FIX_APP_NAMES {
+        return Err(AppError::bad_request(format!(
+            "Change sets of action '{}' cannot be reverted",
+            change_set.action
+        )));
+    }
+
+    let items = change_set_repository
+        .find_items_tx(id, &mut tx)
+        .await
+        .map_err(|e| {
+            error!("Failed to fetch items of change set {}: {}", id, e);
+            AppError::Database(e)
+        })?;
+
+    let app_details_repository = AppDetailsRepository::new(state.db.clone());
+    let mut restored = 0;
+    for item in &items {
+        restored += app_details_repository
+            .restore_app_name_tx(item.row_id, item.old_value.as_deref(), item.new_value.as_deref(), &mut tx)
+            .await
+            .map_err(|e| {
+                error!("Failed to restore app name of AppDetails {}: {}", item.row_id, e);
+                AppError::Database(e)
+            })?;
+    }
+    let skipped = items.len() as u64 - restored;
+
+    change_set_repository
+        .mark_reverted_tx(id, &mut tx)
+        .await
+        .map_err(|e| {
+            error!("Failed to mark change set {} reverted: {}", id, e);
+            AppError::Database(e)
+        })?;
+
+    info!("Reverted change set {}: {} rows restored, {} skipped", id, restored, skipped);
+
+    let revert = ChangeSetRevert {
+        change_set_id: id,
+        action: change_set.action,
+        restored,
+        skipped,
+    };
+
+    Ok(create_success_response(revert, "Change set reverted successfully", StatusCode::OK))
+}
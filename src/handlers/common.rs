@@ -1,11 +1,17 @@
 use axum::{
-    http::StatusCode,
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
     Json,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::collections::HashMap;
 use time::OffsetDateTime;
+use validator::Validate;
+
+use crate::error::types::AppError;
+use crate::handlers::query_params::QueryParams;
+use crate::services::{ingest::{ingest_delta::IngestDelta, pii_scan::PiiRedactionCounts, text_encoding::EncodingReport}, stage_timing::StageTiming};
 
 // ============================================================================
 // Standardized Response Structures
@@ -50,10 +56,66 @@ pub struct ListResponse<T> {
     pub message: String,
     pub data: Vec<T>,
     pub pagination: Option<PaginationMeta>,
+    /// Total matching rows, on endpoints taking `count_mode`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub meta: Option<ListMeta>,
     pub timestamp: String,
     pub status_code: u16,
 }
 
+/// How a list endpoint counts the rows matching its filters
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CountMode {
+    /// COUNT(*) over the filtered rows
+    #[default]
+    Exact,
+    /// The listed table's row count from the cached table statistics, ignoring filters
+    Approximate,
+    /// No total
+    None,
+}
+
+impl CountMode {
+    pub const ALL: [CountMode; 3] = [CountMode::Exact, CountMode::Approximate, CountMode::None];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            CountMode::Exact => "exact",
+            CountMode::Approximate => "approximate",
+            CountMode::None => "none",
+        }
+    }
+}
+
+/// The `meta` block of a list response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListMeta {
+    /// How `total` was counted; `approximate` falls back to `exact` before the tables were first analyzed
+    pub count_mode: CountMode,
+    /// Rows matching the request before `limit`; `None` with `count_mode=none`
+    pub total: Option<i64>,
+    pub limit: i64,
+}
+
+/// `?anonymize=true` on endpoints that publish runs
+#[derive(Debug, Default, Serialize, Deserialize, Validate)]
+pub struct AnonymizeQuery {
+    pub anonymize: Option<bool>,
+}
+
+impl QueryParams for AnonymizeQuery {}
+
+/// `?dry_run=true` on data fix endpoints: report the changes without writing them
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct DryRunQuery {
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Affected rows listed per rule in a dry-run response
+pub const DRY_RUN_SAMPLE_SIZE: usize = 10;
+
 /// Processing operation response
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ProcessingResponse {
@@ -64,6 +126,9 @@ pub struct ProcessingResponse {
     pub rows_updated: usize,
     pub rows_deleted: usize,
     pub errors: Vec<String>,
+    /// Per-stage wall-clock, throughput and DB time, for handlers that time their stages
+    #[serde(default)]
+    pub stage_timings: Vec<StageTiming>,
     pub timestamp: String,
     pub status_code: u16,
 }
@@ -78,6 +143,24 @@ pub struct FileUploadResponse {
     pub rows_processed: usize,
     pub rows_inserted: usize,
     pub rows_failed: usize,
+    /// Id of the ingested upload, for fetching its schema report
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upload_id: Option<String>,
+    /// Personal data replaced in notes/info, when PII scanning redacts uploads
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pii_redactions: Option<PiiRedactionCounts>,
+    /// Byte order mark dropped and characters repaired while decoding, when the file was not clean UTF-8
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encoding_repairs: Option<EncodingReport>,
+    /// Runs held in the moderation queue because the notes filter flagged their notes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes_flagged: Option<usize>,
+    /// New, duplicate and updated rows compared with the data already ingested, when appending
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delta: Option<IngestDelta>,
+    /// Result of each file, when one request uploads several
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub files: Option<Vec<FileUploadResponse>>,
     pub timestamp: String,
     pub status_code: u16,
 }
@@ -144,6 +227,7 @@ pub fn create_list_response<T: Serialize>(
         message: message.to_string(),
         data,
         pagination,
+        meta: None,
         timestamp: OffsetDateTime::now_utc().to_string(),
         status_code: status_code.as_u16(),
     })
@@ -167,6 +251,7 @@ pub fn create_processing_response(
         rows_updated,
         rows_deleted,
         errors,
+        stage_timings: Vec::new(),
         timestamp: OffsetDateTime::now_utc().to_string(),
         status_code: status_code.as_u16(),
     })
@@ -190,11 +275,104 @@ pub fn create_file_upload_response(
         rows_processed,
         rows_inserted,
         rows_failed,
+        upload_id: None,
+        pii_redactions: None,
+        encoding_repairs: None,
+        notes_flagged: None,
+        delta: None,
+        files: None,
         timestamp: OffsetDateTime::now_utc().to_string(),
         status_code: status_code.as_u16(),
     })
 }
 
+/// Total the results of the files of one upload request, listing each under `files`
+///
+/// A single file keeps its own message and upload id, so one-file requests
+/// answer as before apart from the `files` list.
+pub fn combine_file_upload_responses(files: Vec<FileUploadResponse>) -> Json<FileUploadResponse> {
+    let succeeded = files.iter().filter(|file| file.success).count();
+    let message = match files.as_slice() {
+        [file] => file.message.clone(),
+        _ => format!("{} of {} files processed successfully", succeeded, files.len()),
+    };
+    let file_names: Vec<&str> = files.iter().map(|file| file.file_name.as_str()).collect();
+    let mut combined = create_file_upload_response(
+        &message,
+        &file_names.join(", "),
+        files.iter().map(|file| file.file_size).sum(),
+        files.iter().map(|file| file.rows_processed).sum(),
+        files.iter().map(|file| file.rows_inserted).sum(),
+        files.iter().map(|file| file.rows_failed).sum(),
+        StatusCode::OK,
+    );
+
+    combined.0.success = succeeded == files.len();
+    if let Some(failed) = files.iter().find(|file| !file.success) {
+        combined.0.status_code = failed.status_code;
+    }
+    // Row indices count from each file's start, so repairs stay with their file beyond one
+    if let [file] = files.as_slice() {
+        combined.0.upload_id = file.upload_id.clone();
+        combined.0.encoding_repairs = file.encoding_repairs.clone();
+    }
+    combined.0.pii_redactions = files.iter().filter_map(|file| file.pii_redactions).reduce(|mut total, counts| {
+        total.add(counts);
+        total
+    });
+    combined.0.notes_flagged = files.iter().filter_map(|file| file.notes_flagged).reduce(|total, flagged| total + flagged);
+    combined.0.delta = files.iter().filter_map(|file| file.delta).reduce(|mut total, delta| {
+        total.add(delta);
+        total
+    });
+    combined.0.files = Some(files);
+
+    combined
+}
+
+/// Error for a versioned update that matched no row: 409 with the row's
+/// current version if it still exists, 404 otherwise
+pub fn stale_update_error(resource: &str, current_version: Option<i64>) -> AppError {
+    match current_version {
+        Some(current_version) => AppError::conflict(
+            format!("{} was modified by another request; reload it and retry", resource),
+            current_version,
+        ),
+        None => AppError::not_found(resource),
+    }
+}
+
+/// Refuse a request for a feature switched off under `[features]`, answering as an unmounted route would
+pub fn require_feature(enabled: bool, feature: &str) -> Result<(), AppError> {
+    if enabled {
+        Ok(())
+    } else {
+        Err(AppError::not_found(format!("Feature '{}'", feature)))
+    }
+}
+
+/// Serialize rows to a CSV download named `file_name`
+pub fn create_csv_download<T: Serialize>(rows: &[T], file_name: &str) -> Result<Response, AppError> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    for row in rows {
+        writer
+            .serialize(row)
+            .map_err(|e| AppError::internal(format!("Failed to write CSV: {}", e)))?;
+    }
+    let body = writer
+        .into_inner()
+        .map_err(|e| AppError::internal(format!("Failed to write CSV: {}", e)))?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+            (header::CONTENT_DISPOSITION, format!("attachment; filename=\"{}\"", file_name)),
+        ],
+        body,
+    )
+        .into_response())
+}
+
 // ============================================================================
 // Pagination Helper Functions
 // ============================================================================
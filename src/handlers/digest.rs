@@ -0,0 +1,65 @@
+use axum::{extract::State, http::StatusCode, response::Json};
+use chrono::Utc;
+use tracing::{error, info};
+
+use crate::{
+    error::types::AppError,
+    handlers::{
+        common::{create_success_response, ApiResponse},
+        query_params::{QueryParams, ValidatedQuery},
+    },
+    middleware::tenant::Tenant,
+    models::digest::{Digest, DigestQuery, DIGEST_SECTION_LIMIT},
+    repositories::digest_repository::DigestRepository,
+    services::{anonymization::AnonymizationService, digest::render_digest_summary},
+    AppState,
+};
+
+impl QueryParams for DigestQuery {}
+
+/// Summary of what the last `period` (a week by default) added to the tenant's dataset
+///
+/// Counts the runs ingested, and lists the GPUs seen for the first time, the
+/// largest gains in a GPU's best avg_its over its best before the period and the
+/// most active submitters. `summary` renders them as text to post as is.
+/// Submitters are shown by pseudonym with `anonymize=true` or anonymization
+/// enabled in settings.
+pub async fn digest(
+    State(state): State<AppState>,
+    tenant: Tenant,
+    ValidatedQuery(query): ValidatedQuery<DigestQuery>,
+) -> Result<Json<ApiResponse<Digest>>, AppError> {
+    let period = query.period.unwrap_or_default();
+    let until = Utc::now();
+    let since = (until - period.duration()).to_rfc3339();
+    info!("Building the {:?} digest since {}", period, since);
+
+    let repository = DigestRepository::new(state.db.clone());
+    let new_runs = repository.count_new_runs(tenant.id(), &since).await.map_err(database_error)?;
+    let new_gpus = repository.find_new_gpus(tenant.id(), &since, DIGEST_SECTION_LIMIT).await.map_err(database_error)?;
+    let its_improvements = repository
+        .find_its_improvements(tenant.id(), &since, DIGEST_SECTION_LIMIT)
+        .await
+        .map_err(database_error)?;
+    let mut active_users = repository.find_active_users(tenant.id(), &since, DIGEST_SECTION_LIMIT).await.map_err(database_error)?;
+    AnonymizationService::new(&state.settings.anonymization).apply(query.anonymize, &mut active_users);
+
+    let mut digest = Digest {
+        period,
+        since,
+        until: until.to_rfc3339(),
+        new_runs,
+        new_gpus,
+        its_improvements,
+        active_users,
+        summary: String::new(),
+    };
+    digest.summary = render_digest_summary(&digest);
+
+    Ok(create_success_response(digest, "Digest generated successfully", StatusCode::OK))
+}
+
+fn database_error(e: sqlx::Error) -> AppError {
+    error!("Failed to build the digest: {}", e);
+    AppError::Database(e)
+}
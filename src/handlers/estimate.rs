@@ -0,0 +1,24 @@
+use axum::{extract::State, http::StatusCode, response::Json};
+
+use crate::{
+    error::types::AppError,
+    handlers::common::{create_success_response, ApiResponse},
+    middleware::tenant::Tenant,
+    models::estimate::{EstimateRequest, ItsEstimate},
+    services::estimate::EstimateService,
+    AppState,
+};
+
+/// Expected ITS range for a GPU, torch version and model, from the closest comparable runs
+pub async fn estimate_its(
+    State(state): State<AppState>,
+    tenant: Tenant,
+    Json(request): Json<EstimateRequest>,
+) -> Result<Json<ApiResponse<ItsEstimate>>, AppError> {
+    let estimate = EstimateService::new(state.db.clone())
+        .estimate(tenant.id(), &request)
+        .await?
+        .ok_or_else(|| AppError::not_found(format!("Comparable runs for GPU {}", request.gpu.trim())))?;
+
+    Ok(create_success_response(estimate, "ITS estimate computed successfully", StatusCode::OK))
+}
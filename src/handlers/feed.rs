@@ -0,0 +1,102 @@
+use axum::{
+    extract::State,
+    http::header,
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, Utc};
+use tracing::{error, info};
+
+use crate::{
+    error::types::AppError,
+    handlers::{common::AnonymizeQuery, query_params::ValidatedQuery},
+    middleware::tenant::Tenant,
+    models::runs::RunFeedEntry,
+    repositories::runs_repository::RunsRepository,
+    services::{
+        anonymization::AnonymizationService,
+        feed::{render_atom_feed, AtomEntry},
+        sharing::ShareTokenService,
+    },
+    AppState,
+};
+
+pub const FEED_TITLE: &str = "SD-ITS-Benchmark: new submissions";
+
+/// Atom feed of the tenant's newest ingested runs
+///
+/// Each entry links to the run's public share permalink, so subscribers can
+/// open it even when the rest of the API requires authentication. Submitters are
+/// shown by pseudonym with `anonymize=true` or anonymization enabled in settings.
+pub async fn submissions_feed(
+    State(state): State<AppState>,
+    tenant: Tenant,
+    ValidatedQuery(query): ValidatedQuery<AnonymizeQuery>,
+) -> Result<Response, AppError> {
+    let feed_config = &state.settings.feed;
+    info!("Rendering submissions feed ({} entries max)", feed_config.max_entries);
+
+    let repository = RunsRepository::new(state.db.clone());
+    let mut runs = repository
+        .find_latest_feed_entries(tenant.id(), feed_config.max_entries)
+        .await
+        .map_err(|e| {
+            error!("Failed to fetch runs for feed: {}", e);
+            AppError::Database(e)
+        })?;
+    AnonymizationService::new(&state.settings.anonymization).apply(query.anonymize, &mut runs);
+
+    let now = Utc::now().to_rfc3339();
+    let share_tokens = ShareTokenService::new(&state.settings.sharing);
+    let entries: Vec<AtomEntry> = runs
+        .iter()
+        .map(|run| {
+            let (token, _) = share_tokens.issue(run.run_id);
+            AtomEntry {
+                id: format!("urn:sd-its-benchmark:run:{}", run.run_id),
+                title: entry_title(run),
+                link: format!("{}/api/share/{}", feed_config.base_url, token),
+                updated: entry_updated(run).unwrap_or_else(|| now.clone()),
+                summary: entry_summary(run),
+            }
+        })
+        .collect();
+
+    // Entries are newest first, so the first one dates the feed
+    let updated = entries.first().map(|entry| entry.updated.clone()).unwrap_or(now);
+    let feed_url = format!("{}/api/feed.atom", feed_config.base_url);
+    let xml = render_atom_feed(FEED_TITLE, &feed_url, &updated, &entries);
+
+    Ok(([(header::CONTENT_TYPE, "application/atom+xml; charset=utf-8")], xml).into_response())
+}
+
+fn entry_title(run: &RunFeedEntry) -> String {
+    let gpu = run.device.as_deref().unwrap_or("Unknown GPU");
+    let model = run.model_name.as_deref().unwrap_or("unknown model");
+    match run.avg_its {
+        Some(avg_its) => format!("{} on {}: {:.2} it/s", gpu, model, avg_its),
+        None => format!("{} on {}", gpu, model),
+    }
+}
+
+fn entry_summary(run: &RunFeedEntry) -> String {
+    let mut parts = vec![
+        format!("GPU: {}", run.device.as_deref().unwrap_or("unknown")),
+        format!("Model: {}", run.model_name.as_deref().unwrap_or("unknown")),
+    ];
+    if let Some(avg_its) = run.avg_its {
+        parts.push(format!("Average: {:.2} it/s", avg_its));
+    }
+    if let Some(user) = run.user.as_deref().filter(|user| !user.trim().is_empty()) {
+        parts.push(format!("Submitted by: {}", user));
+    }
+    parts.join(", ")
+}
+
+/// When the run was ingested, falling back to its benchmark timestamp when that is RFC 3339
+fn entry_updated(run: &RunFeedEntry) -> Option<String> {
+    [run.created_at.as_deref(), run.timestamp.as_deref()]
+        .into_iter()
+        .flatten()
+        .find_map(|value| DateTime::parse_from_rfc3339(value).ok())
+        .map(|at| at.to_rfc3339())
+}
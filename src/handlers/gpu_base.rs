@@ -0,0 +1,67 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+};
+use tracing::{error, info};
+
+use crate::{
+    error::types::AppError,
+    handlers::common::{create_success_response, stale_update_error, ApiResponse},
+    models::gpu_base::{GpuBase, UpdateGpuBase},
+    repositories::{gpu_base_repository::GpuBaseRepository, traits::Repository},
+    AppState,
+};
+
+pub async fn get_gpu_base(
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+) -> Result<Json<ApiResponse<GpuBase>>, AppError> {
+    let gpu_base = GpuBaseRepository::new(state.db.clone())
+        .find_by_id(id)
+        .await
+        .map_err(AppError::Database)?
+        .ok_or_else(|| AppError::not_found(format!("GPUBase {}", id)))?;
+
+    Ok(create_success_response(gpu_base, "GPUBase retrieved successfully", StatusCode::OK))
+}
+
+/// Update a GPUBase row if it is still at the version the client read
+pub async fn update_gpu_base(
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+    Json(request): Json<UpdateGpuBase>,
+) -> Result<Json<ApiResponse<GpuBase>>, AppError> {
+    if request.name.trim().is_empty() {
+        return Err(AppError::validation("name must not be blank"));
+    }
+
+    let repository = GpuBaseRepository::new(state.db.clone());
+    let updated = match repository
+        .update(GpuBase {
+            id: Some(id),
+            name: request.name,
+            brand: request.brand,
+            architecture: request.architecture,
+            generation: request.generation,
+            version: request.version,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+    {
+        Ok(updated) => updated,
+        Err(sqlx::Error::RowNotFound) => {
+            let current = repository.find_by_id(id).await.map_err(AppError::Database)?;
+            return Err(stale_update_error(&format!("GPUBase {}", id), current.map(|gpu_base| gpu_base.version)));
+        }
+        Err(e) => {
+            error!("Failed to update GPUBase {}: {}", id, e);
+            return Err(AppError::Database(e));
+        }
+    };
+
+    info!("Updated GPUBase {} to version {}", id, updated.version);
+
+    Ok(create_success_response(updated, "GPUBase updated successfully", StatusCode::OK))
+}
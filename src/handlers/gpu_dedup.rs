@@ -0,0 +1,121 @@
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::Json,
+};
+use tracing::{error, info};
+
+use crate::{
+    error::types::AppError,
+    handlers::common::{create_list_response, create_success_response, ApiResponse, ListResponse},
+    models::{
+        audit_log::AUDIT_ACTION_GPU_DEDUP,
+        gpu_dedup::{GpuDedupCluster, GpuDedupResult},
+    },
+    repositories::{
+        audit_log_repository::AuditLogRepository,
+        gpu_map_repository::GpuMapRepository,
+        gpu_repository::GpuRepository,
+    },
+    services::gpu_dedup::find_gpu_dedup_clusters,
+    AppState,
+};
+
+async fn current_clusters(state: &AppState) -> Result<Vec<GpuDedupCluster>, AppError> {
+    let devices = GpuRepository::new(state.db.clone()).find_device_counts().await.map_err(|e| {
+        error!("Failed to fetch GPU device counts: {}", e);
+        AppError::Database(e)
+    })?;
+
+    Ok(find_gpu_dedup_clusters(&devices))
+}
+
+/// GPU device strings that would be merged into a canonical spelling
+pub async fn preview_gpu_dedup(
+    State(state): State<AppState>,
+) -> Result<Json<ListResponse<GpuDedupCluster>>, AppError> {
+    let clusters = current_clusters(&state).await?;
+
+    Ok(create_list_response(clusters, "GPU dedup preview retrieved successfully", StatusCode::OK, None))
+}
+
+/// Rewrite near-duplicate GPU device strings to their canonical spelling
+///
+/// The clusters are recomputed from the current data, so the result matches a
+/// preview taken right before. GPUMap entries follow the rename unless the
+/// canonical name is already mapped. The rewrite and its audit entry are
+/// committed together. Re-running process-gpu restores the raw spellings.
+pub async fn apply_gpu_dedup(
+    State(state): State<AppState>,
+) -> Result<Json<ApiResponse<GpuDedupResult>>, AppError> {
+    let clusters = current_clusters(&state).await?;
+    if clusters.is_empty() {
+        return Ok(create_success_response(
+            GpuDedupResult { clusters: 0, gpu_rows_updated: 0, gpu_map_rows_updated: 0, audit_id: None },
+            "No near-duplicate GPU devices found",
+            StatusCode::OK,
+        ));
+    }
+
+    let gpu_repo = GpuRepository::new(state.db.clone());
+    let gpu_map_repo = GpuMapRepository::new(state.db.clone());
+    let mut tx = state.db.begin().await.map_err(|e| {
+        error!("Failed to begin transaction: {}", e);
+        AppError::Database(e)
+    })?;
+
+    let mut gpu_rows_updated = 0;
+    let mut gpu_map_rows_updated = 0;
+    for cluster in &clusters {
+        for variant in &cluster.variants {
+            gpu_rows_updated += gpu_repo
+                .rename_device_tx(&variant.device, &cluster.canonical, &mut tx)
+                .await
+                .map_err(|e| {
+                    error!("Failed to rename GPU device '{}': {}", variant.device, e);
+                    AppError::Database(e)
+                })?;
+            gpu_map_rows_updated += gpu_map_repo
+                .rename_gpu_name_tx(&variant.device, &cluster.canonical, &mut tx)
+                .await
+                .map_err(|e| {
+                    error!("Failed to rename GPUMap entry '{}': {}", variant.device, e);
+                    AppError::Database(e)
+                })?;
+        }
+    }
+
+    let details = serde_json::to_string(&clusters)
+        .map_err(|e| AppError::internal(format!("Failed to serialize GPU dedup audit details: {}", e)))?;
+    let audit_id = AuditLogRepository::new(state.db.clone())
+        .create_tx(AUDIT_ACTION_GPU_DEDUP, &details, &mut tx)
+        .await
+        .map_err(|e| {
+            error!("Failed to record GPU dedup audit entry: {}", e);
+            AppError::Database(e)
+        })?;
+
+    if let Err(e) = tx.commit().await {
+        error!("Failed to commit transaction: {}", e);
+        return Err(AppError::Database(e));
+    }
+
+    info!(
+        "Merged {} GPU device clusters: {} GPU rows, {} GPUMap rows (audit {})",
+        clusters.len(),
+        gpu_rows_updated,
+        gpu_map_rows_updated,
+        audit_id
+    );
+
+    Ok(create_success_response(
+        GpuDedupResult {
+            clusters: clusters.len(),
+            gpu_rows_updated,
+            gpu_map_rows_updated,
+            audit_id: Some(audit_id),
+        },
+        "GPU devices merged successfully",
+        StatusCode::OK,
+    ))
+}
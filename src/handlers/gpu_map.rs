@@ -0,0 +1,94 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{Json, Response},
+};
+use tracing::{error, info};
+
+use crate::{
+    error::{handlers::ensure_reference_exists, types::AppError},
+    handlers::common::{create_csv_download, create_success_response, require_feature, stale_update_error, ApiResponse},
+    models::gpu_map::{GpuMap, UpdateGpuMap},
+    repositories::{
+        gpu_base_repository::GpuBaseRepository,
+        gpu_map_repository::GpuMapRepository,
+        traits::Repository,
+    },
+    AppState,
+};
+
+pub async fn get_gpu_map(
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+) -> Result<Json<ApiResponse<GpuMap>>, AppError> {
+    let gpu_map = GpuMapRepository::new(state.db.clone())
+        .find_by_id(id)
+        .await
+        .map_err(AppError::Database)?
+        .ok_or_else(|| AppError::not_found(format!("GPUMap {}", id)))?;
+
+    Ok(create_success_response(gpu_map, "GPUMap retrieved successfully", StatusCode::OK))
+}
+
+/// Download every GPUMap row with its usage count as CSV for offline curation
+pub async fn export_gpu_map(State(state): State<AppState>) -> Result<Response, AppError> {
+    require_feature(state.settings.features.enable_export, "export")?;
+
+    let rows = GpuMapRepository::new(state.db.clone())
+        .export_with_usage()
+        .await
+        .map_err(|e| {
+            error!("Failed to export GPUMap: {}", e);
+            AppError::Database(e)
+        })?;
+
+    info!("Exporting {} GPUMap rows", rows.len());
+
+    create_csv_download(&rows, "gpu_map.csv")
+}
+
+/// Update a GPUMap row if it is still at the version the client read
+pub async fn update_gpu_map(
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+    Json(request): Json<UpdateGpuMap>,
+) -> Result<Json<ApiResponse<GpuMap>>, AppError> {
+    if request.gpu_name.trim().is_empty() {
+        return Err(AppError::validation("gpu_name must not be blank"));
+    }
+
+    ensure_reference_exists(
+        &GpuBaseRepository::new(state.db.clone()),
+        request.base_gpu_id,
+        "base_gpu_id",
+        "base GPU not found",
+    )
+    .await?;
+
+    let repository = GpuMapRepository::new(state.db.clone());
+    let updated = match repository
+        .update(GpuMap {
+            id: Some(id),
+            gpu_name: Some(request.gpu_name),
+            base_gpu_id: Some(request.base_gpu_id),
+            version: request.version,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+    {
+        Ok(updated) => updated,
+        Err(sqlx::Error::RowNotFound) => {
+            let current = repository.find_by_id(id).await.map_err(AppError::Database)?;
+            return Err(stale_update_error(&format!("GPUMap {}", id), current.map(|gpu_map| gpu_map.version)));
+        }
+        Err(e) => {
+            error!("Failed to update GPUMap {}: {}", id, e);
+            return Err(AppError::Database(e));
+        }
+    };
+
+    info!("Updated GPUMap {} to version {}", id, updated.version);
+
+    Ok(create_success_response(updated, "GPUMap updated successfully", StatusCode::OK))
+}
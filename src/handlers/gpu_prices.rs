@@ -0,0 +1,147 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+};
+use tracing::{error, info};
+
+use crate::{
+    error::{handlers::ensure_reference_exists, types::AppError},
+    handlers::common::{create_list_response, create_success_message, create_success_response, ApiResponse, ListResponse},
+    models::gpu_price::{CreateGpuPrice, GpuPrice},
+    repositories::{
+        gpu_base_repository::GpuBaseRepository,
+        gpu_price_repository::GpuPriceRepository,
+        query_builder::Filter,
+        traits::Repository,
+    },
+    AppState,
+};
+
+/// Validate a price payload and make sure the referenced base GPU exists
+async fn validate_gpu_price(state: &AppState, request: &CreateGpuPrice) -> Result<(), AppError> {
+    if !request.price_usd.is_finite() || request.price_usd <= 0.0 {
+        return Err(AppError::validation("price_usd must be a positive number"));
+    }
+
+    ensure_reference_exists(
+        &GpuBaseRepository::new(state.db.clone()),
+        request.base_gpu_id,
+        "base_gpu_id",
+        "base GPU not found",
+    )
+    .await?;
+
+    Ok(())
+}
+
+pub async fn list_gpu_prices(
+    State(state): State<AppState>,
+) -> Result<Json<ListResponse<GpuPrice>>, AppError> {
+    let prices = GpuPriceRepository::new(state.db.clone()).find_all().await.map_err(|e| {
+        error!("Failed to fetch GPU prices: {}", e);
+        AppError::Database(e)
+    })?;
+
+    Ok(create_list_response(prices, "GPU prices retrieved successfully", StatusCode::OK, None))
+}
+
+pub async fn get_gpu_price(
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+) -> Result<Json<ApiResponse<GpuPrice>>, AppError> {
+    let price = GpuPriceRepository::new(state.db.clone())
+        .find_by_id(id)
+        .await
+        .map_err(AppError::Database)?
+        .ok_or_else(|| AppError::not_found(format!("GPU price {}", id)))?;
+
+    Ok(create_success_response(price, "GPU price retrieved successfully", StatusCode::OK))
+}
+
+pub async fn create_gpu_price(
+    State(state): State<AppState>,
+    Json(request): Json<CreateGpuPrice>,
+) -> Result<Json<ApiResponse<GpuPrice>>, AppError> {
+    validate_gpu_price(&state, &request).await?;
+
+    let repository = GpuPriceRepository::new(state.db.clone());
+    if repository.count_where(&Filter::new("base_gpu_id", request.base_gpu_id)).await.map_err(AppError::Database)? > 0 {
+        return Err(AppError::duplicate(format!(
+            "A price for GPUBase {} already exists; update it instead",
+            request.base_gpu_id
+        )));
+    }
+
+    let created = repository
+        .create(GpuPrice {
+            id: None,
+            base_gpu_id: Some(request.base_gpu_id),
+            price_usd: Some(request.price_usd),
+            source: request.source,
+            recorded_at: request.recorded_at,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .map_err(|e| {
+            error!("Failed to create GPU price: {}", e);
+            AppError::Database(e)
+        })?;
+
+    info!("Created GPU price {:?} for GPUBase {}", created.id, request.base_gpu_id);
+
+    Ok(create_success_response(created, "GPU price created successfully", StatusCode::CREATED))
+}
+
+pub async fn update_gpu_price(
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+    Json(request): Json<CreateGpuPrice>,
+) -> Result<Json<ApiResponse<GpuPrice>>, AppError> {
+    validate_gpu_price(&state, &request).await?;
+
+    let repository = GpuPriceRepository::new(state.db.clone());
+    if !repository.exists(id).await.map_err(AppError::Database)? {
+        return Err(AppError::not_found(format!("GPU price {}", id)));
+    }
+
+    let updated = repository
+        .update(GpuPrice {
+            id: Some(id),
+            base_gpu_id: Some(request.base_gpu_id),
+            price_usd: Some(request.price_usd),
+            source: request.source,
+            recorded_at: request.recorded_at,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .map_err(|e| {
+            error!("Failed to update GPU price {}: {}", id, e);
+            AppError::Database(e)
+        })?;
+
+    info!("Updated GPU price {}", id);
+
+    Ok(create_success_response(updated, "GPU price updated successfully", StatusCode::OK))
+}
+
+pub async fn delete_gpu_price(
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+) -> Result<Json<ApiResponse<()>>, AppError> {
+    let repository = GpuPriceRepository::new(state.db.clone());
+    if !repository.exists(id).await.map_err(AppError::Database)? {
+        return Err(AppError::not_found(format!("GPU price {}", id)));
+    }
+
+    repository.delete(id).await.map_err(|e| {
+        error!("Failed to delete GPU price {}: {}", id, e);
+        AppError::Database(e)
+    })?;
+
+    info!("Deleted GPU price {}", id);
+
+    Ok(create_success_message("GPU price deleted successfully", StatusCode::OK))
+}
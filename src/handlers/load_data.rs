@@ -0,0 +1,105 @@
+use axum::{
+    extract::State,
+    http::header,
+    response::{IntoResponse, Json, Response},
+};
+use serde::Deserialize;
+use tracing::{error, info};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    error::types::AppError,
+    handlers::{
+        admin::{ingest_uploads, prepare_benchmark_file},
+        common::FileUploadResponse,
+        pipeline::execute_pipeline,
+        query_params::{QueryParams, ValidatedQuery},
+    },
+    middleware::tenant::Tenant,
+    services::load_data::{SyntheticDataGenerator, DEFAULT_GENERATOR_SEED},
+    AppState,
+};
+
+/// Largest dataset one request generates; the CLI has no limit
+pub const MAX_GENERATED_ROWS: usize = 500_000;
+
+const GENERATED_FILE_NAME: &str = "synthetic_runs.json";
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct GenerateDataQuery {
+    #[validate(required, range(min = 1, max = MAX_GENERATED_ROWS))]
+    pub rows: Option<usize>,
+    /// Defaults to `DEFAULT_GENERATOR_SEED`, so repeated requests give the same dataset
+    pub seed: Option<u64>,
+    /// Ingest the dataset as an appended upload instead of returning it
+    pub ingest: Option<bool>,
+}
+
+impl QueryParams for GenerateDataQuery {}
+
+/// Generate a reproducible synthetic benchmark export of `rows` runs (development only)
+///
+/// Returns the export as a JSON download, or with `ingest=true` appends it to
+/// the dataset through the upload path and returns the upload response.
+pub async fn generate_data(
+    State(state): State<AppState>,
+    ValidatedQuery(query): ValidatedQuery<GenerateDataQuery>,
+) -> Result<Response, AppError> {
+    let rows = query.rows.unwrap_or_default();
+    let seed = query.seed.unwrap_or(DEFAULT_GENERATOR_SEED);
+
+    if query.ingest.unwrap_or(false) {
+        let response = ingest_generated(&state, rows, seed).await?;
+        return Ok(Json(response).into_response());
+    }
+
+    info!("Generating {} synthetic runs (seed {})", rows, seed);
+    let export = SyntheticDataGenerator::new(seed).export(rows);
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/json".to_string()),
+            (header::CONTENT_DISPOSITION, format!("attachment; filename=\"synthetic_{}_{}.json\"", rows, seed)),
+        ],
+        export,
+    )
+        .into_response())
+}
+
+/// Generate `rows` synthetic runs and append them to the dataset like an uploaded file
+///
+/// As for uploads, the response's `delta` counts the rows ingested before,
+/// e.g. by an earlier call with the same seed.
+pub async fn ingest_generated(state: &AppState, rows: usize, seed: u64) -> Result<FileUploadResponse, AppError> {
+    info!("Generating and ingesting {} synthetic runs (seed {})", rows, seed);
+    let export = SyntheticDataGenerator::new(seed).export(rows);
+
+    let upload_id = Uuid::new_v4().to_string();
+    // Generated files are trusted, so the upload size limit does not apply
+    let upload = prepare_benchmark_file(state, &Tenant::default(), &upload_id, GENERATED_FILE_NAME, &export, usize::MAX).await?;
+    let mut responses = ingest_uploads(state, vec![upload], false).await.map_err(|e| {
+        error!("Failed to ingest synthetic runs: {}", e);
+        e
+    })?;
+
+    Ok(responses.remove(0))
+}
+
+/// Seed the demo environment's database with `settings.demo.sample_rows` processed runs
+///
+/// The runs are ingested and run through the enabled pipeline stages, so the read
+/// endpoints have statistics to serve from the start. The demo database is a
+/// single in-memory connection, which the pipeline's transaction holds while
+/// its stages run.
+pub async fn seed_demo_data(state: &AppState) -> Result<(), AppError> {
+    let response = ingest_generated(state, state.settings.demo.sample_rows, DEFAULT_GENERATOR_SEED).await?;
+    let pipeline = execute_pipeline(state, state.settings.pipeline.enabled_stages.as_deref()).await?;
+    info!(
+        "Seeded demo data: {} runs ingested, {} pipeline stages run",
+        response.rows_inserted,
+        pipeline.stages.len()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,83 @@
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::Json,
+};
+use chrono::Utc;
+use serde_json::json;
+use tracing::{error, info};
+
+use crate::{
+    error::types::AppError,
+    handlers::common::{create_success_response, ApiResponse},
+    models::{
+        audit_log::AUDIT_ACTION_MAINTENANCE_MODE,
+        dataset_meta::{MaintenanceMode, SetMaintenanceModeRequest, META_MAINTENANCE_MODE},
+    },
+    repositories::{audit_log_repository::AuditLogRepository, dataset_meta_repository::DatasetMetaRepository},
+    AppState,
+};
+
+/// Report whether maintenance mode is on
+pub async fn get_maintenance_mode(
+    State(state): State<AppState>,
+) -> Result<Json<ApiResponse<MaintenanceMode>>, AppError> {
+    let entry = DatasetMetaRepository::new(state.db.clone())
+        .find_by_key(META_MAINTENANCE_MODE)
+        .await
+        .map_err(|e| {
+            error!("Failed to read maintenance mode: {}", e);
+            AppError::Database(e)
+        })?;
+
+    let mode = MaintenanceMode {
+        enabled: entry.as_ref().is_some_and(|entry| entry.value.as_deref() == Some("true")),
+        updated_at: entry.map(|entry| entry.updated_at),
+    };
+
+    Ok(create_success_response(mode, "Maintenance mode retrieved successfully", StatusCode::OK))
+}
+
+/// Turn maintenance mode on or off
+///
+/// While it is on, `reject_writes_during_maintenance` answers mutating requests
+/// with 503 so migrations and backfills see a stable dataset. Every switch is
+/// audit-logged.
+pub async fn set_maintenance_mode(
+    State(state): State<AppState>,
+    Json(request): Json<SetMaintenanceModeRequest>,
+) -> Result<Json<ApiResponse<MaintenanceMode>>, AppError> {
+    let now = Utc::now().to_rfc3339();
+    let value = if request.enabled { "true" } else { "false" };
+
+    let mut tx = state.db.begin().await.map_err(|e| {
+        error!("Failed to begin transaction: {}", e);
+        AppError::Database(e)
+    })?;
+    DatasetMetaRepository::new(state.db.clone())
+        .set_tx(META_MAINTENANCE_MODE, Some(value), &now, &mut tx)
+        .await
+        .map_err(|e| {
+            error!("Failed to store maintenance mode: {}", e);
+            AppError::Database(e)
+        })?;
+    AuditLogRepository::new(state.db.clone())
+        .create_tx(AUDIT_ACTION_MAINTENANCE_MODE, &json!({ "enabled": request.enabled }).to_string(), &mut tx)
+        .await
+        .map_err(|e| {
+            error!("Failed to record maintenance mode audit entry: {}", e);
+            AppError::Database(e)
+        })?;
+    tx.commit().await.map_err(|e| {
+        error!("Failed to commit transaction: {}", e);
+        AppError::Database(e)
+    })?;
+
+    info!("Maintenance mode {}", if request.enabled { "enabled" } else { "disabled" });
+
+    let mode = MaintenanceMode {
+        enabled: request.enabled,
+        updated_at: Some(now),
+    };
+    Ok(create_success_response(mode, "Maintenance mode updated successfully", StatusCode::OK))
+}
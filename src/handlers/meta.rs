@@ -0,0 +1,245 @@
+use std::{collections::HashMap, time::Duration};
+
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::Json,
+};
+use serde::{de, Deserialize, Deserializer};
+use tokio::time::{sleep, Instant};
+use tracing::{error, info};
+use validator::Validate;
+
+use crate::{
+    error::types::AppError,
+    handlers::{
+        common::{create_list_response, create_success_response, ApiResponse, ListResponse},
+        query_params::{QueryParams, ValidatedQuery},
+    },
+    middleware::tenant::Tenant,
+    models::{
+        aggregates::{Facet, DEFAULT_FACET_LIMIT, MAX_FACET_LIMIT},
+        dataset_meta::{
+            DatasetMetadata, DatasetUpdate, SourceUpload, META_DATASET_VERSION, META_LAST_PIPELINE_DURATION_MS, META_LAST_PIPELINE_RUN_AT,
+            META_LAST_PIPELINE_STAGE_TIMINGS, META_LAST_PIPELINE_STEP, META_SOURCE_FILE_NAME, META_SOURCE_FILE_SIZE, META_SOURCE_FORMAT, META_SOURCE_UPLOADED_AT,
+        },
+        dataset_version::{DatasetVersionQuery, DatasetVersionSummary, DEFAULT_DATASET_VERSION_LIMIT},
+    },
+    repositories::{
+        aggregates::{AggregateColumn, AggregatesRepository},
+        dataset_meta_repository::DatasetMetaRepository,
+        dataset_version_repository::DatasetVersionRepository,
+    },
+    AppState,
+};
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct FacetQuery {
+    /// Comma-separated fields such as `gpu.brand,libraries.torch`
+    #[serde(default, deserialize_with = "facet_fields")]
+    #[validate(length(min = 1, message = "is required"))]
+    pub fields: Vec<AggregateColumn>,
+    /// Values listed per field; the rest are totalled as other
+    #[validate(range(min = 1, max = MAX_FACET_LIMIT))]
+    pub limit: Option<usize>,
+}
+
+impl QueryParams for FacetQuery {
+    fn allowed_values(field: &str) -> Option<Vec<&'static str>> {
+        (field == "fields").then(|| AggregateColumn::ALL.iter().map(|column| column.field()).collect())
+    }
+}
+
+impl QueryParams for DatasetVersionQuery {}
+
+pub const DEFAULT_WAIT_TIMEOUT_SECONDS: u64 = 25;
+pub const MAX_WAIT_TIMEOUT_SECONDS: u64 = 120;
+/// How often a waiting request re-reads the dataset version
+const WAIT_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct WaitForUpdateQuery {
+    /// The dataset version the client already has
+    #[validate(required, range(min = 0))]
+    pub since_version: Option<i64>,
+    #[validate(range(min = 1, max = MAX_WAIT_TIMEOUT_SECONDS))]
+    pub timeout_seconds: Option<u64>,
+}
+
+impl QueryParams for WaitForUpdateQuery {}
+
+/// Parse comma-separated facet fields, dropping repeats
+fn facet_fields<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<AggregateColumn>, D::Error> {
+    let mut columns = Vec::new();
+    for field in Option::<String>::deserialize(deserializer)?
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|field| !field.is_empty())
+    {
+        let column = AggregateColumn::from_field(field).ok_or_else(|| {
+            let known: Vec<&str> = AggregateColumn::ALL.iter().map(|column| column.field()).collect();
+            de::Error::custom(format!("unknown field `{}`, expected one of: {}", field, known.join(", ")))
+        })?;
+        if !columns.contains(&column) {
+            columns.push(column);
+        }
+    }
+
+    Ok(columns)
+}
+
+/// Report the tenant's row counts per table and how fresh the dataset is
+pub async fn dataset_metadata(
+    State(state): State<AppState>,
+    tenant: Tenant,
+) -> Result<Json<ApiResponse<DatasetMetadata>>, AppError> {
+    info!("Fetching dataset metadata");
+
+    let metadata = load_dataset_metadata(&state, &tenant).await?;
+
+    Ok(create_success_response(metadata, "Dataset metadata retrieved successfully", StatusCode::OK))
+}
+
+/// A tenant's row counts per table and the recorded pipeline and source upload details
+pub(crate) async fn load_dataset_metadata(state: &AppState, tenant: &Tenant) -> Result<DatasetMetadata, AppError> {
+    let repository = DatasetMetaRepository::new(state.db.clone());
+    let table_counts = repository.count_tenant_table_rows(tenant.id()).await.map_err(|e| {
+        error!("Failed to count table rows: {}", e);
+        AppError::Database(e)
+    })?;
+    let mut entries: HashMap<String, Option<String>> = repository
+        .find_all()
+        .await
+        .map_err(|e| {
+            error!("Failed to fetch dataset metadata: {}", e);
+            AppError::Database(e)
+        })?
+        .into_iter()
+        .map(|entry| (entry.key, entry.value))
+        .collect();
+    let mut take = |key: &str| entries.remove(key).flatten();
+
+    let dataset_version = take(META_DATASET_VERSION)
+        .and_then(|version| version.parse().ok())
+        .unwrap_or(0);
+    let last_pipeline_step = take(META_LAST_PIPELINE_STEP);
+    let last_pipeline_run_at = take(META_LAST_PIPELINE_RUN_AT);
+    let last_pipeline_duration_ms = take(META_LAST_PIPELINE_DURATION_MS).and_then(|duration| duration.parse().ok());
+    let last_pipeline_stage_timings = take(META_LAST_PIPELINE_STAGE_TIMINGS)
+        .and_then(|timings| serde_json::from_str(&timings).ok())
+        .unwrap_or_default();
+    let source_upload = take(META_SOURCE_FILE_NAME).map(|file_name| SourceUpload {
+        file_name,
+        format: take(META_SOURCE_FORMAT),
+        file_size: take(META_SOURCE_FILE_SIZE).and_then(|size| size.parse().ok()),
+        uploaded_at: take(META_SOURCE_UPLOADED_AT),
+    });
+
+    Ok(DatasetMetadata {
+        dataset_version,
+        table_counts,
+        last_pipeline_step,
+        last_pipeline_run_at,
+        last_pipeline_duration_ms,
+        last_pipeline_stage_timings,
+        source_upload,
+    })
+}
+
+/// Wait until the dataset version passes `since_version`, or the timeout elapses
+///
+/// Answers at once when the version is already newer, so dashboards can call
+/// it again right after each response instead of polling on a timer.
+pub async fn wait_for_update(
+    State(state): State<AppState>,
+    ValidatedQuery(query): ValidatedQuery<WaitForUpdateQuery>,
+) -> Result<Json<ApiResponse<DatasetUpdate>>, AppError> {
+    let since_version = query.since_version.unwrap_or_default();
+    let timeout = Duration::from_secs(query.timeout_seconds.unwrap_or(DEFAULT_WAIT_TIMEOUT_SECONDS));
+    info!("Waiting up to {:?} for a dataset version after {}", timeout, since_version);
+
+    let repository = DatasetMetaRepository::new(state.db.clone());
+    let deadline = Instant::now() + timeout;
+    loop {
+        let dataset_version = repository.current_version().await.map_err(|e| {
+            error!("Failed to fetch the dataset version: {}", e);
+            AppError::Database(e)
+        })?;
+
+        let updated = dataset_version > since_version;
+        if updated || Instant::now() >= deadline {
+            let message = if updated { "Dataset updated" } else { "No dataset update before the timeout" };
+            return Ok(create_success_response(DatasetUpdate { dataset_version, updated }, message, StatusCode::OK));
+        }
+
+        sleep(WAIT_POLL_INTERVAL.min(deadline.saturating_duration_since(Instant::now()))).await;
+    }
+}
+
+/// Pipeline steps with the reference data revisions they ran against, newest first
+///
+/// Each version lists the reference sets that changed since the version before
+/// it, which explains result differences between two pipeline runs.
+pub async fn list_dataset_versions(
+    State(state): State<AppState>,
+    ValidatedQuery(query): ValidatedQuery<DatasetVersionQuery>,
+) -> Result<Json<ApiResponse<Vec<DatasetVersionSummary>>>, AppError> {
+    let limit = query.limit.unwrap_or(DEFAULT_DATASET_VERSION_LIMIT);
+
+    // One extra version, so the oldest listed one can be compared with its predecessor
+    let versions = DatasetVersionRepository::new(state.db.clone())
+        .find_recent(limit + 1)
+        .await
+        .map_err(|e| {
+            error!("Failed to fetch dataset versions: {}", e);
+            AppError::Database(e)
+        })?;
+
+    let summaries = versions
+        .iter()
+        .enumerate()
+        .take(limit as usize)
+        .map(|(index, version)| DatasetVersionSummary {
+            version: version.clone(),
+            changed_since_previous: versions
+                .get(index + 1)
+                .map(|previous| version.revisions().changed_since(&previous.revisions()))
+                .unwrap_or_default()
+                .into_iter()
+                .map(str::to_string)
+                .collect(),
+        })
+        .collect();
+
+    Ok(create_success_response(summaries, "Dataset versions retrieved successfully", StatusCode::OK))
+}
+
+/// Distinct values and run counts of the requested columns over the tenant's runs, for building faceted filters
+///
+/// Each field lists its `limit` most common values; the remaining ones are
+/// totalled in `other_values` and `other_run_count`.
+pub async fn facets(
+    State(state): State<AppState>,
+    tenant: Tenant,
+    ValidatedQuery(query): ValidatedQuery<FacetQuery>,
+) -> Result<Json<ListResponse<Facet>>, AppError> {
+    let limit = query.limit.unwrap_or(DEFAULT_FACET_LIMIT);
+    info!(
+        "Fetching facets of {} (limit {})",
+        query.fields.iter().map(|column| column.field()).collect::<Vec<_>>().join(", "),
+        limit
+    );
+
+    let repository = AggregatesRepository::new(state.db.clone());
+    let mut facets = Vec::with_capacity(query.fields.len());
+    for column in query.fields {
+        let counts = repository.count_grouped_by(tenant.id(), column, None).await.map_err(|e| {
+            error!("Failed to fetch facet {}: {}", column.field(), e);
+            AppError::Database(e)
+        })?;
+        facets.push(Facet::from_counts(column.field(), counts, limit));
+    }
+
+    Ok(create_list_response(facets, "Facets retrieved successfully", StatusCode::OK, None))
+}
@@ -0,0 +1,55 @@
+use axum::{extract::State, http::StatusCode, response::Json};
+use serde::Serialize;
+use tracing::error;
+
+use crate::{
+    error::types::AppError,
+    handlers::{
+        common::{create_success_response, ApiResponse},
+        query_params::{QueryParams, ValidatedQuery},
+    },
+    middleware::request_metrics::{request_metrics, RequestMetrics},
+    models::health_sample::{HealthHistoryQuery, HealthSample, DEFAULT_HEALTH_HISTORY_LIMIT},
+    repositories::{connection::{query_metrics, QueryMetrics}, health_sample_repository::HealthSampleRepository},
+    AppState,
+};
+
+/// Operational counters since startup
+#[derive(Debug, Clone, Serialize)]
+pub struct Metrics {
+    pub database: QueryMetrics,
+    pub requests: RequestMetrics,
+}
+
+/// Report repository query counts, including slow and timed-out queries, and request counts
+pub async fn metrics() -> Json<ApiResponse<Metrics>> {
+    let metrics = Metrics {
+        database: query_metrics(),
+        requests: request_metrics(),
+    };
+
+    create_success_response(metrics, "Metrics retrieved successfully", StatusCode::OK)
+}
+
+impl QueryParams for HealthHistoryQuery {}
+
+/// Recorded health samples as a time series, oldest first
+///
+/// Lists the latest `limit` samples, only those taken at or after `since`
+/// when given; samples are recorded every `health_history.interval_seconds`.
+pub async fn health_history(
+    State(state): State<AppState>,
+    ValidatedQuery(query): ValidatedQuery<HealthHistoryQuery>,
+) -> Result<Json<ApiResponse<Vec<HealthSample>>>, AppError> {
+    let limit = query.limit.unwrap_or(DEFAULT_HEALTH_HISTORY_LIMIT);
+
+    let samples = HealthSampleRepository::new(state.db.clone())
+        .find_recent(query.since.as_deref(), limit)
+        .await
+        .map_err(|e| {
+            error!("Failed to fetch health history: {}", e);
+            AppError::Database(e)
+        })?;
+
+    Ok(create_success_response(samples, "Health history retrieved successfully", StatusCode::OK))
+}
@@ -1,4 +1,35 @@
 pub mod upload;
 pub mod common;
 pub mod admin;
-pub mod validation; 
\ No newline at end of file
+pub mod admin_page;
+pub mod validation;
+pub mod query_params;
+pub mod stats;
+pub mod runs;
+pub mod gpu_prices;
+pub mod upload_formats;
+pub mod uploads;
+pub mod remote_ingest;
+pub mod upstream_sync;
+pub mod meta;
+pub mod model_map;
+pub mod gpu_map;
+pub mod gpu_base;
+pub mod share;
+pub mod badge;
+pub mod feed;
+pub mod estimate;
+pub mod tags;
+pub mod gpu_dedup;
+pub mod metrics;
+pub mod users;
+pub mod maintenance;
+pub mod moderation;
+pub mod change_sets;
+pub mod quality;
+pub mod pipeline;
+pub mod self_test;
+pub mod load_data;
+pub mod retention;
+pub mod digest;
+pub mod reparse;
@@ -0,0 +1,114 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{Json, Response},
+};
+use serde::{Deserialize, Serialize};
+use tracing::{error, info};
+
+use crate::{
+    error::types::AppError,
+    handlers::common::{create_csv_download, create_success_response, require_feature, stale_update_error, ApiResponse},
+    models::model_map::{ModelMap, ModelMapImportReport, UpdateModelMap},
+    repositories::{model_map_repository::ModelMapRepository, traits::Repository},
+    services::data_processing::import_model_map_service::ImportModelMapService,
+    AppState,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImportQuery {
+    #[serde(default)]
+    pub apply: bool,
+}
+
+pub async fn get_model_map(
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+) -> Result<Json<ApiResponse<ModelMap>>, AppError> {
+    let model_map = ModelMapRepository::new(state.db.clone())
+        .find_by_id(id)
+        .await
+        .map_err(AppError::Database)?
+        .ok_or_else(|| AppError::not_found(format!("ModelMap {}", id)))?;
+
+    Ok(create_success_response(model_map, "ModelMap retrieved successfully", StatusCode::OK))
+}
+
+/// Download every ModelMap row with its usage count as CSV for offline curation
+pub async fn export_model_map(State(state): State<AppState>) -> Result<Response, AppError> {
+    require_feature(state.settings.features.enable_export, "export")?;
+
+    let rows = ModelMapRepository::new(state.db.clone())
+        .export_with_usage()
+        .await
+        .map_err(|e| {
+            error!("Failed to export ModelMap: {}", e);
+            AppError::Database(e)
+        })?;
+
+    info!("Exporting {} ModelMap rows", rows.len());
+
+    create_csv_download(&rows, "model_map.csv")
+}
+
+/// Update a ModelMap row if it is still at the version the client read
+pub async fn update_model_map(
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+    Json(request): Json<UpdateModelMap>,
+) -> Result<Json<ApiResponse<ModelMap>>, AppError> {
+    if request.model_name.trim().is_empty() || request.base_model.trim().is_empty() {
+        return Err(AppError::validation("model_name and base_model must not be blank"));
+    }
+
+    let repository = ModelMapRepository::new(state.db.clone());
+    let updated = match repository
+        .update(ModelMap {
+            id: Some(id),
+            model_name: Some(request.model_name),
+            base_model: Some(request.base_model),
+            version: request.version,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+    {
+        Ok(updated) => updated,
+        Err(sqlx::Error::RowNotFound) => {
+            let current = repository.find_by_id(id).await.map_err(AppError::Database)?;
+            return Err(stale_update_error(&format!("ModelMap {}", id), current.map(|model_map| model_map.version)));
+        }
+        Err(e) => {
+            error!("Failed to update ModelMap {}: {}", id, e);
+            return Err(AppError::Database(e));
+        }
+    };
+
+    info!("Updated ModelMap {} to version {}", id, updated.version);
+
+    Ok(create_success_response(updated, "ModelMap updated successfully", StatusCode::OK))
+}
+
+/// Preview a model_name,base_model CSV against ModelMap, writing it only with `?apply=true`
+pub async fn import_model_map(
+    State(state): State<AppState>,
+    Query(query): Query<ImportQuery>,
+    body: String,
+) -> Result<Json<ApiResponse<ModelMapImportReport>>, AppError> {
+    if body.trim().is_empty() {
+        return Err(AppError::validation("CSV body must not be empty"));
+    }
+
+    let service = ImportModelMapService::new(ModelMapRepository::new(state.db.clone()), state.db.clone());
+    let report = service.import_model_map(&body, query.apply).await?;
+
+    let message = if report.applied {
+        "ModelMap import applied"
+    } else if query.apply {
+        "ModelMap import not applied: fix the rows listed in errors and retry"
+    } else {
+        "ModelMap import preview; resend with ?apply=true to apply"
+    };
+
+    Ok(create_success_response(report, message, StatusCode::OK))
+}
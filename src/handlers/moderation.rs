@@ -0,0 +1,134 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+};
+use serde_json::json;
+use tracing::{error, info};
+
+use crate::{
+    error::types::AppError,
+    handlers::{
+        common::{create_success_response, ApiResponse},
+        query_params::{QueryParams, ValidatedQuery},
+    },
+    middleware::{request_transaction::RequestTransaction, tenant::Tenant},
+    models::{
+        audit_log::AUDIT_ACTION_MODERATION_REVIEW,
+        moderation::{
+            ModerationDecision, ModerationEntry, ModerationQueueQuery, ModerationStatus, ReviewModerationRequest,
+            MODERATION_STATUS_APPROVED, MODERATION_STATUS_PENDING, MODERATION_STATUS_REJECTED,
+        },
+    },
+    repositories::{
+        audit_log_repository::AuditLogRepository,
+        moderation_repository::ModerationRepository,
+        run_more_details_repository::RunMoreDetailsRepository,
+        runs_repository::RunsRepository,
+    },
+    AppState,
+};
+
+impl QueryParams for ModerationQueueQuery {
+    fn allowed_values(field: &str) -> Option<Vec<&'static str>> {
+        (field == "status").then(|| ModerationStatus::ALL.iter().map(|status| status.name()).collect())
+    }
+}
+
+/// The tenant's runs whose notes the notes filter flagged, pending review unless `status` says otherwise
+pub async fn list_moderation_queue(
+    State(state): State<AppState>,
+    tenant: Tenant,
+    ValidatedQuery(query): ValidatedQuery<ModerationQueueQuery>,
+) -> Result<Json<ApiResponse<Vec<ModerationEntry>>>, AppError> {
+    let status = query.status.unwrap_or_default().name();
+
+    let entries = ModerationRepository::new(state.db.clone())
+        .find_by_status(tenant.id(), status)
+        .await
+        .map_err(|e| {
+            error!("Failed to fetch moderation queue: {}", e);
+            AppError::Database(e)
+        })?;
+
+    Ok(create_success_response(entries, "Moderation queue retrieved successfully", StatusCode::OK))
+}
+
+/// Approve or reject a run of the tenant held for moderation
+///
+/// Approving publishes the run as submitted. Rejecting publishes it with its
+/// notes cleared on the run and its processed details. Either way the run
+/// leaves the queue, and the review is audit-logged in the request transaction.
+pub async fn review_moderation_entry(
+    State(state): State<AppState>,
+    tenant: Tenant,
+    request_tx: RequestTransaction,
+    Path(id): Path<i64>,
+    Json(request): Json<ReviewModerationRequest>,
+) -> Result<Json<ApiResponse<ModerationEntry>>, AppError> {
+    let mut tx = request_tx.begin().await?;
+    let moderation_repository = ModerationRepository::new(state.db.clone());
+
+    let entry = moderation_repository
+        .find_by_id_tx(id, tenant.id(), &mut tx)
+        .await
+        .map_err(|e| {
+            error!("Failed to fetch moderation entry {}: {}", id, e);
+            AppError::Database(e)
+        })?
+        .ok_or_else(|| AppError::not_found(format!("Moderation entry with id {}", id)))?;
+    if entry.status != MODERATION_STATUS_PENDING {
+        return Err(AppError::bad_request(format!("Moderation entry {} was already {}", id, entry.status)));
+    }
+
+    let status = match request.decision {
+        ModerationDecision::Approve => MODERATION_STATUS_APPROVED,
+        ModerationDecision::Reject => {
+            RunsRepository::new(state.db.clone())
+                .clear_notes_tx(entry.run_id, &mut tx)
+                .await
+                .map_err(|e| {
+                    error!("Failed to clear notes of run {}: {}", entry.run_id, e);
+                    AppError::Database(e)
+                })?;
+            RunMoreDetailsRepository::new(state.db.clone())
+                .clear_notes_tx(entry.run_id, &mut tx)
+                .await
+                .map_err(|e| {
+                    error!("Failed to clear notes of run details {}: {}", entry.run_id, e);
+                    AppError::Database(e)
+                })?;
+            MODERATION_STATUS_REJECTED
+        }
+    };
+
+    moderation_repository
+        .set_status_tx(id, status, &mut tx)
+        .await
+        .map_err(|e| {
+            error!("Failed to update moderation entry {}: {}", id, e);
+            AppError::Database(e)
+        })?;
+
+    let details = json!({ "run_id": entry.run_id, "reasons": entry.reasons, "status": status }).to_string();
+    AuditLogRepository::new(state.db.clone())
+        .create_tx(AUDIT_ACTION_MODERATION_REVIEW, &details, &mut tx)
+        .await
+        .map_err(|e| {
+            error!("Failed to record moderation audit entry: {}", e);
+            AppError::Database(e)
+        })?;
+
+    let reviewed = moderation_repository
+        .find_by_id_tx(id, tenant.id(), &mut tx)
+        .await
+        .map_err(|e| {
+            error!("Failed to fetch moderation entry {}: {}", id, e);
+            AppError::Database(e)
+        })?
+        .ok_or_else(|| AppError::internal(format!("Moderation entry {} disappeared during review", id)))?;
+
+    info!("Moderation entry {} for run {} {}", id, entry.run_id, status);
+
+    Ok(create_success_response(reviewed, "Moderation entry reviewed successfully", StatusCode::OK))
+}
@@ -0,0 +1,381 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{Sqlite, Transaction};
+use tracing::{error, info, warn};
+
+use crate::{
+    error::types::AppError,
+    handlers::{
+        admin,
+        common::{create_success_response, ApiResponse},
+        query_params::{QueryParams, ValidatedQuery},
+    },
+    models::pipeline_event::{
+        PipelineEvent, PipelineHistoryQuery, DEFAULT_PIPELINE_HISTORY_LIMIT, PIPELINE_EVENT_FAILED,
+        PIPELINE_EVENT_ROLLED_BACK, PIPELINE_EVENT_SUCCEEDED,
+    },
+    repositories::{dataset_meta_repository::DatasetMetaRepository, pipeline_event_repository::PipelineEventRepository},
+    services::{
+        alerts::{evaluate_alerts, PipelineAlert},
+        notifications::{AlertNotification, WebhookNotifier},
+        pipeline::{PipelineRegistry, PipelineStage, StageOutput},
+    },
+    AppState,
+};
+
+/// A running stage, borrowing the state and transaction it was given
+type StageFuture<'a> = Pin<Box<dyn Future<Output = Result<StageOutput, AppError>> + Send + 'a>>;
+
+/// A pipeline stage backed by the transactional body of its `/api/{name}` handler
+struct HandlerStage {
+    name: &'static str,
+    dependencies: &'static [&'static str],
+    run: for<'a, 'c> fn(&'a AppState, &'a mut Transaction<'c, Sqlite>) -> StageFuture<'a>,
+}
+
+#[async_trait]
+impl PipelineStage for HandlerStage {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn dependencies(&self) -> &'static [&'static str] {
+        self.dependencies
+    }
+
+    async fn execute(&self, state: &AppState, tx: &mut Transaction<'_, Sqlite>) -> Result<StageOutput, AppError> {
+        (self.run)(state, tx).await
+    }
+}
+
+fn stage(
+    name: &'static str,
+    dependencies: &'static [&'static str],
+    run: for<'a, 'c> fn(&'a AppState, &'a mut Transaction<'c, Sqlite>) -> StageFuture<'a>,
+) -> HandlerStage {
+    HandlerStage { name, dependencies, run }
+}
+
+/// A stage's response as the stage output, with `rows` picking its (in, out) counts
+fn output<T: Serialize>(
+    response: Result<T, AppError>,
+    rows: impl FnOnce(&T) -> (Option<usize>, usize),
+) -> Result<StageOutput, AppError> {
+    let response = response?;
+    let (rows_in, rows_out) = rows(&response);
+    let summary = serde_json::to_value(response)
+        .map_err(|e| AppError::internal(format!("Failed to serialize stage summary: {}", e)))?;
+
+    Ok(StageOutput {
+        rows_in,
+        rows_out: Some(rows_out),
+        summary,
+    })
+}
+
+/// The derivation stages of the pipeline
+///
+/// Add new derived tables here; `POST /api/run-pipeline` picks them up in
+/// dependency order. Fixes that need request input (e.g. fix-app-names) are
+/// not stages.
+pub fn pipeline_registry() -> PipelineRegistry {
+    PipelineRegistry::new()
+        .register(stage("process-its", &[], |state, tx| {
+            Box::pin(async move {
+                output(admin::process_its_tx(state, tx).await, |r| (Some(r.rows_processed), r.rows_inserted))
+            })
+        }))
+        .register(stage("process-app-details", &[], |state, tx| {
+            Box::pin(async move { output(admin::process_app_details_tx(state, tx).await, |r| (None, r.rows_inserted)) })
+        }))
+        .register(stage("canonicalize-app-urls", &["process-app-details"], |state, tx| {
+            Box::pin(async move {
+                output(admin::canonicalize_app_urls_tx(state, tx).await, |r| (Some(r.total_rows), r.parsed_rows))
+            })
+        }))
+        .register(stage("process-system-info", &[], |state, tx| {
+            Box::pin(async move { output(admin::process_system_info_tx(state, tx).await, |r| (None, r.rows_inserted)) })
+        }))
+        .register(stage("process-libraries", &[], |state, tx| {
+            Box::pin(async move { output(admin::process_libraries_tx(state, tx).await, |r| (None, r.rows_inserted)) })
+        }))
+        .register(stage("process-gpu", &[], |state, tx| {
+            Box::pin(async move { output(admin::process_gpu_tx(state, tx).await, |r| (None, r.rows_inserted)) })
+        }))
+        .register(stage("update-gpu-brands", &["process-gpu"], |state, tx| {
+            Box::pin(async move { output(admin::update_gpu_brands_tx(state, false, tx).await, |r| (None, r.total_updates)) })
+        }))
+        .register(stage("update-gpu-laptop-info", &["process-gpu"], |state, tx| {
+            Box::pin(async move {
+                output(admin::update_gpu_laptop_info_tx(state, false, tx).await, |r| (None, r.total_updates))
+            })
+        }))
+        .register(stage("update-gpu-integrated-info", &["process-gpu"], |state, tx| {
+            Box::pin(async move {
+                output(admin::update_gpu_integrated_info_tx(state, tx).await, |r| (Some(r.total_rows), r.integrated_rows))
+            })
+        }))
+        .register(stage("update-gpu-cloud-info", &["process-gpu", "process-system-info"], |state, tx| {
+            Box::pin(async move {
+                output(admin::update_gpu_cloud_info_tx(state, tx).await, |r| (Some(r.total_rows), r.cloud_rows))
+            })
+        }))
+        .register(stage("normalize-cpu-info", &["process-system-info"], |state, tx| {
+            Box::pin(async move {
+                output(admin::normalize_cpu_info_tx(state, tx).await, |r| (Some(r.total_rows), r.normalized_rows))
+            })
+        }))
+        .register(stage("process-run-details", &[], |state, tx| {
+            Box::pin(async move { output(admin::process_run_details_tx(state, tx).await, |r| (None, r.total_inserts)) })
+        }))
+        .register(stage("update-run-more-details-with-modelmapid", &["process-run-details"], |state, tx| {
+            Box::pin(async move {
+                output(admin::update_run_more_details_with_modelmapid_tx(state, false, tx).await, |r| (None, r.updated))
+            })
+        }))
+        .register(stage(
+            "compute-run-scores",
+            &["process-its", "process-gpu", "update-run-more-details-with-modelmapid"],
+            |state, tx| {
+                Box::pin(async move {
+                    output(admin::compute_run_scores_tx(state, tx).await, |r| (Some(r.rows_processed), r.rows_inserted))
+                })
+            },
+        ))
+}
+
+#[derive(Debug, Serialize)]
+pub struct PipelineStageResult {
+    pub stage: String,
+    pub duration_ms: f64,
+    pub rows_in: Option<usize>,
+    pub rows_out: Option<usize>,
+    /// The stage endpoint's response
+    pub summary: Value,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RunPipelineResponse {
+    pub success: bool,
+    pub stages: Vec<PipelineStageResult>,
+    /// Stages left out by the request or `settings.pipeline.enabled_stages`
+    pub skipped: Vec<String>,
+    /// Thresholds from `settings.alerts` the run tripped
+    pub alerts: Vec<PipelineAlert>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RunPipelineQuery {
+    /// Comma-separated stage names to run; `settings.pipeline.enabled_stages` when absent
+    pub stages: Option<String>,
+}
+
+/// Run the enabled pipeline stages in dependency order
+///
+/// The stages come from `?stages=`, else from `settings.pipeline.enabled_stages`,
+/// else all of them. A selected stage's dependencies are not added, they are
+/// taken to be up to date. The stages run in one transaction, so the first
+/// failing stage rolls back the stages before it too. Either way the run is checked against
+/// `settings.alerts` afterwards.
+pub async fn run_pipeline(
+    State(state): State<AppState>,
+    Query(query): Query<RunPipelineQuery>,
+) -> Result<Json<RunPipelineResponse>, AppError> {
+    let registry = pipeline_registry();
+    let enabled: Option<Vec<String>> = match &query.stages {
+        Some(stages) => Some(
+            stages
+                .split(',')
+                .map(str::trim)
+                .filter(|stage| !stage.is_empty())
+                .map(str::to_string)
+                .collect(),
+        ),
+        None => state.settings.pipeline.enabled_stages.clone(),
+    };
+    if let Some(enabled) = &enabled {
+        if enabled.is_empty() {
+            return Err(AppError::bad_request("stages cannot be empty"));
+        }
+        let unknown = registry.unknown_stages(enabled);
+        if !unknown.is_empty() {
+            return Err(AppError::bad_request(format!("Unknown pipeline stages: {}", unknown.join(", "))));
+        }
+    }
+
+    execute_pipeline(&state, enabled.as_deref()).await.map(Json)
+}
+
+/// Run the stages in `enabled`, or all of them, in dependency order
+///
+/// The names must be registered stages; `run_pipeline` checks them.
+pub(crate) async fn execute_pipeline(state: &AppState, enabled: Option<&[String]>) -> Result<RunPipelineResponse, AppError> {
+    let registry = pipeline_registry();
+    let (order, skipped): (Vec<_>, Vec<_>) = registry
+        .execution_order()?
+        .into_iter()
+        .partition(|stage| enabled.is_none_or(|enabled| enabled.iter().any(|name| name == stage.name())));
+    let skipped: Vec<String> = skipped.iter().map(|stage| stage.name().to_string()).collect();
+    info!(
+        "Running pipeline: {} (skipping {})",
+        order.iter().map(|stage| stage.name()).collect::<Vec<_>>().join(", "),
+        if skipped.is_empty() { "none".to_string() } else { skipped.join(", ") }
+    );
+
+    let event_repository = PipelineEventRepository::new(state.db.clone());
+    let mut stages = Vec::with_capacity(order.len());
+    let mut run_events = Vec::with_capacity(order.len());
+    let mut tx = state.db.begin().await?;
+    for stage in order {
+        let started_at = Utc::now().to_rfc3339();
+        let started = Instant::now();
+        let result = stage.execute(state, &mut tx).await;
+        let mut event = PipelineEvent {
+            id: None,
+            stage: stage.name().to_string(),
+            started_at,
+            finished_at: Utc::now().to_rfc3339(),
+            rows_in: None,
+            rows_out: None,
+            status: PIPELINE_EVENT_SUCCEEDED.to_string(),
+            error: None,
+        };
+
+        match result {
+            Ok(output) => {
+                event.rows_in = output.rows_in.map(|rows| rows as i64);
+                event.rows_out = output.rows_out.map(|rows| rows as i64);
+                run_events.push(event);
+                stages.push(PipelineStageResult {
+                    stage: stage.name().to_string(),
+                    duration_ms: started.elapsed().as_secs_f64() * 1000.0,
+                    rows_in: output.rows_in,
+                    rows_out: output.rows_out,
+                    summary: output.summary,
+                });
+            }
+            Err(e) => {
+                error!("Pipeline stage {} failed: {}", stage.name(), e);
+                if let Err(rollback_error) = tx.rollback().await {
+                    warn!("Failed to roll back the pipeline: {}", rollback_error);
+                }
+                // The stages before it went with the rollback
+                for earlier in &mut run_events {
+                    earlier.status = PIPELINE_EVENT_ROLLED_BACK.to_string();
+                }
+                event.status = PIPELINE_EVENT_FAILED.to_string();
+                event.error = Some(e.to_string());
+                run_events.push(event);
+                record_events(&event_repository, &run_events).await;
+                raise_alerts(state, &event_repository, &run_events).await;
+                return Err(e);
+            }
+        }
+    }
+    tx.commit().await?;
+    // Recorded once the transaction is closed, through the pool
+    record_events(&event_repository, &run_events).await;
+
+    info!("Pipeline complete: {} stages run, {} skipped", stages.len(), skipped.len());
+    // Keep the row counts behind `count_mode=approximate` current
+    if let Err(e) = DatasetMetaRepository::new(state.db.clone()).analyze().await {
+        warn!("Failed to refresh table statistics: {}", e);
+    }
+    let alerts = raise_alerts(state, &event_repository, &run_events).await;
+
+    Ok(RunPipelineResponse {
+        success: true,
+        stages,
+        skipped,
+        alerts,
+    })
+}
+
+/// Check a run against `settings.alerts`, logging tripped thresholds and sending them to the webhook
+async fn raise_alerts(
+    state: &AppState,
+    repository: &PipelineEventRepository,
+    run_events: &[PipelineEvent],
+) -> Vec<PipelineAlert> {
+    let config = &state.settings.alerts;
+    let recent_events = if config.max_error_rate_percent.is_some() {
+        repository
+            .find_recent(None, config.error_rate_window)
+            .await
+            .unwrap_or_else(|e| {
+                warn!("Failed to fetch recent pipeline events for alerting: {}", e);
+                Vec::new()
+            })
+    } else {
+        Vec::new()
+    };
+
+    let alerts = evaluate_alerts(config, run_events, &recent_events);
+    for alert in &alerts {
+        warn!("Pipeline alert: {}", alert.message);
+    }
+
+    if let Some(webhook_url) = config.webhook_url.as_deref().filter(|_| !alerts.is_empty()) {
+        match WebhookNotifier::new(webhook_url, Duration::from_secs(config.webhook_timeout_seconds)) {
+            Ok(notifier) => {
+                let source = state.settings.application.name.clone();
+                let alerts = alerts.clone();
+                // In the background, so a slow webhook does not hold up the response
+                tokio::spawn(async move {
+                    let notification = AlertNotification {
+                        source: &source,
+                        alerts: &alerts,
+                    };
+                    if let Err(e) = notifier.send(&notification).await {
+                        warn!("Failed to deliver pipeline alerts: {}", e);
+                    }
+                });
+            }
+            Err(e) => warn!("Failed to deliver pipeline alerts: {}", e),
+        }
+    }
+
+    alerts
+}
+
+/// Log a run's stage executions; failing to do so never fails the pipeline
+async fn record_events(repository: &PipelineEventRepository, events: &[PipelineEvent]) {
+    for event in events {
+        if let Err(e) = repository.create(event).await {
+            warn!("Failed to record pipeline event for {}: {}", event.stage, e);
+        }
+    }
+}
+
+impl QueryParams for PipelineHistoryQuery {}
+
+/// Past stage executions by the pipeline orchestrator, newest first
+pub async fn pipeline_history(
+    State(state): State<AppState>,
+    ValidatedQuery(query): ValidatedQuery<PipelineHistoryQuery>,
+) -> Result<Json<ApiResponse<Vec<PipelineEvent>>>, AppError> {
+    let limit = query.limit.unwrap_or(DEFAULT_PIPELINE_HISTORY_LIMIT);
+
+    let events = PipelineEventRepository::new(state.db.clone())
+        .find_recent(query.stage.as_deref(), limit)
+        .await
+        .map_err(|e| {
+            error!("Failed to fetch pipeline history: {}", e);
+            AppError::Database(e)
+        })?;
+
+    Ok(create_success_response(events, "Pipeline history retrieved successfully", StatusCode::OK))
+}
@@ -0,0 +1,70 @@
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::Json,
+};
+use tracing::error;
+
+use crate::{
+    error::types::AppError,
+    handlers::common::{create_success_response, ApiResponse},
+    middleware::tenant::Tenant,
+    models::{
+        performance_result::{VRAM_USAGE_KIND_MEMORY, VRAM_USAGE_KIND_UNKNOWN},
+        quality_report::{
+            QualityReport, QualityReportEntry, QUALITY_CHECK_VRAM_USAGE_MEMORY, QUALITY_CHECK_VRAM_USAGE_UNPARSEABLE,
+            QUALITY_REPORT_SAMPLE_SIZE,
+        },
+    },
+    repositories::performance_result_repository::PerformanceResultRepository,
+    AppState,
+};
+
+/// Data quality problems the processing steps found in the tenant's runs
+///
+/// Only checks that found something are listed, so an empty report means the
+/// processed data passed every check. Runs reported here are excluded from ITS
+/// statistics.
+pub async fn quality_report(
+    State(state): State<AppState>,
+    tenant: Tenant,
+) -> Result<Json<ApiResponse<QualityReport>>, AppError> {
+    let repository = PerformanceResultRepository::new(state.db.clone());
+
+    let checks = [
+        (
+            QUALITY_CHECK_VRAM_USAGE_MEMORY,
+            VRAM_USAGE_KIND_MEMORY,
+            "vram_usage holds memory values instead of ITS samples",
+        ),
+        (
+            QUALITY_CHECK_VRAM_USAGE_UNPARSEABLE,
+            VRAM_USAGE_KIND_UNKNOWN,
+            "vram_usage holds no numeric ITS samples",
+        ),
+    ];
+
+    let mut entries = Vec::new();
+    for (check, kind, description) in checks {
+        let run_ids = repository.find_run_ids_by_vram_usage_kind(tenant.id(), kind).await.map_err(|e| {
+            error!("Failed to run quality check {}: {}", check, e);
+            AppError::Database(e)
+        })?;
+        if run_ids.is_empty() {
+            continue;
+        }
+
+        entries.push(QualityReportEntry {
+            check: check.to_string(),
+            description: description.to_string(),
+            affected_rows: run_ids.len(),
+            sample_run_ids: run_ids.into_iter().take(QUALITY_REPORT_SAMPLE_SIZE).collect(),
+        });
+    }
+
+    Ok(create_success_response(
+        QualityReport { entries },
+        "Quality report retrieved successfully",
+        StatusCode::OK,
+    ))
+}
@@ -0,0 +1,219 @@
+use axum::{
+    extract::{FromRequestParts, Query},
+    http::{request::Parts, Uri},
+};
+use serde::{
+    de::{value::StringDeserializer, DeserializeOwned, IntoDeserializer},
+    Deserialize, Deserializer,
+};
+use validator::{Validate, ValidationError, ValidationErrors};
+
+use crate::error::types::{AppError, QueryParamError};
+
+/// Query parameters of a read endpoint, parsed and checked by `ValidatedQuery`
+///
+/// Every field should be optional, with `#[validate(required)]` on the ones
+/// that are not, so a parameter that fails to parse can be told apart from
+/// the others.
+pub trait QueryParams: DeserializeOwned + Validate {
+    /// Values a parameter accepts, listed in its error; `None` for free-form parameters
+    fn allowed_values(_field: &str) -> Option<Vec<&'static str>> {
+        None
+    }
+}
+
+/// Query string extractor rejecting bad parameters with a structured 400
+///
+/// Parameters that fail to parse or validate are listed in the error's
+/// `params`, each with its message and, for enumerated ones, the allowed values.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ValidatedQuery<T>(pub T);
+
+impl<S: Send + Sync, T: QueryParams> FromRequestParts<S> for ValidatedQuery<T> {
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let params = deserialize_query::<T>(parts.uri.query().unwrap_or_default()).map_err(AppError::invalid_query)?;
+        params
+            .validate()
+            .map_err(|errors| AppError::invalid_query(validation_errors::<T>(&errors)))?;
+
+        Ok(Self(params))
+    }
+}
+
+/// Deserialize a query string, naming each parameter that does not parse
+///
+/// Deserialization stops at the first bad parameter, so on failure the pairs
+/// are parsed one at a time to find all of them.
+fn deserialize_query<T: QueryParams>(query: &str) -> Result<T, Vec<QueryParamError>> {
+    let message = match parse_query::<T>(query) {
+        Ok(params) => return Ok(params),
+        Err(message) => message,
+    };
+
+    let errors: Vec<QueryParamError> = query
+        .split('&')
+        .filter_map(|pair| {
+            let message = parse_query::<T>(pair).err()?;
+            let (field, _) = parse_query::<Vec<(String, String)>>(pair).ok()?.pop()?;
+            let message = message.strip_prefix(&format!("{}: ", field)).unwrap_or(&message).to_string();
+            Some(QueryParamError::new(&field, message, T::allowed_values(&field)))
+        })
+        .collect();
+
+    // Only the combination is wrong, e.g. a repeated parameter
+    if errors.is_empty() {
+        return Err(vec![QueryParamError::new("query", message, None)]);
+    }
+
+    Err(errors)
+}
+
+fn parse_query<T: DeserializeOwned>(query: &str) -> Result<T, String> {
+    let uri: Uri = format!("/?{}", query).parse().map_err(|e| format!("Malformed query string: {}", e))?;
+    Query::<T>::try_from_uri(&uri).map(|Query(params)| params).map_err(|rejection| {
+        let message = rejection.body_text();
+        message
+            .strip_prefix("Failed to deserialize query string: ")
+            .map(str::to_string)
+            .unwrap_or(message)
+    })
+}
+
+/// One error per failed check, ordered by parameter
+///
+/// Struct-level (`schema`) checks report the parameter they reject as their
+/// error code.
+fn validation_errors<T: QueryParams>(errors: &ValidationErrors) -> Vec<QueryParamError> {
+    let mut params = Vec::new();
+    for (field, field_errors) in errors.field_errors() {
+        for error in field_errors.iter() {
+            let field = if field == "__all__" { error.code.as_ref() } else { field.as_ref() };
+            params.push(QueryParamError::new(field, describe(error), T::allowed_values(field)));
+        }
+    }
+    params.sort_by(|a, b| a.field.cmp(&b.field));
+
+    params
+}
+
+fn describe(error: &ValidationError) -> String {
+    if let Some(message) = &error.message {
+        return message.to_string();
+    }
+
+    let param = |name: &str| error.params.get(name).map(|value| value.to_string());
+    match (error.code.as_ref(), param("min"), param("max")) {
+        ("required", _, _) => "is required".to_string(),
+        ("range", Some(min), Some(max)) => format!("must be between {} and {}", min, max),
+        ("range", Some(min), None) => format!("must be at least {}", min),
+        ("range", None, Some(max)) => format!("must be at most {}", max),
+        (code, _, _) => format!("is invalid ({})", code),
+    }
+}
+
+/// Deserialize an optional parameter, treating a blank value as absent
+///
+/// For use with `#[serde(default, deserialize_with = "blank_as_none")]` on
+/// enumerated parameters, which are parsed from the trimmed value.
+pub fn blank_as_none<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    match Option::<String>::deserialize(deserializer)? {
+        Some(value) if !value.trim().is_empty() => {
+            let value: StringDeserializer<D::Error> = value.trim().to_string().into_deserializer();
+            T::deserialize(value).map(Some)
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Deserialize a comma-separated parameter into its distinct values
+///
+/// Blank items are skipped and an absent parameter is empty; use with
+/// `#[serde(default, deserialize_with = "comma_separated")]`.
+pub fn comma_separated<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de> + PartialEq,
+{
+    let mut values = Vec::new();
+    for item in Option::<String>::deserialize(deserializer)?
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+    {
+        let item: StringDeserializer<D::Error> = item.to_string().into_deserializer();
+        let value = T::deserialize(item)?;
+        if !values.contains(&value) {
+            values.push(value);
+        }
+    }
+
+    Ok(values)
+}
+
+/// Reject values that are empty once trimmed, for required free-form parameters
+pub fn not_blank(value: &str) -> Result<(), ValidationError> {
+    if value.trim().is_empty() {
+        return Err(ValidationError::new("required").with_message("must not be blank".into()));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Deserialize, Validate)]
+    struct TestQuery {
+        #[validate(range(min = 1, max = 10))]
+        limit: Option<i64>,
+        #[serde(default, deserialize_with = "blank_as_none")]
+        mode: Option<TestMode>,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(rename_all = "snake_case")]
+    enum TestMode {
+        Fast,
+        Slow,
+    }
+
+    impl QueryParams for TestQuery {
+        fn allowed_values(field: &str) -> Option<Vec<&'static str>> {
+            (field == "mode").then(|| vec!["fast", "slow"])
+        }
+    }
+
+    #[test]
+    fn test_deserialize_query_names_every_bad_parameter() {
+        let query = deserialize_query::<TestQuery>("limit=5&mode=%20slow").unwrap();
+        assert_eq!(query.limit, Some(5));
+        assert_eq!(query.mode, Some(TestMode::Slow));
+        assert!(deserialize_query::<TestQuery>("mode=").unwrap().mode.is_none());
+
+        let errors = deserialize_query::<TestQuery>("limit=many&mode=quick").unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].field, "limit");
+        assert_eq!(errors[0].allowed, None);
+        assert_eq!(errors[1].field, "mode");
+        assert!(errors[1].message.contains("quick"), "{}", errors[1].message);
+        assert_eq!(errors[1].allowed, Some(vec!["fast".to_string(), "slow".to_string()]));
+    }
+
+    #[test]
+    fn test_validation_errors_describe_ranges() {
+        let query = deserialize_query::<TestQuery>("limit=0").unwrap();
+        let errors = validation_errors::<TestQuery>(&query.validate().unwrap_err());
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].field, "limit");
+        assert!(errors[0].message.starts_with("must be between 1"), "{}", errors[0].message);
+    }
+}
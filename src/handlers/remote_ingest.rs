@@ -0,0 +1,52 @@
+use std::time::Duration;
+
+use axum::{extract::State, response::Json};
+use serde::Deserialize;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::{
+    error::types::AppError,
+    handlers::{
+        admin::{ensure_replace_allowed, ingest_uploads, prepare_benchmark_file},
+        common::FileUploadResponse,
+        validation::MAX_FILE_SIZE,
+    },
+    middleware::tenant::Tenant,
+    services::remote_ingest::RemoteFileFetcher,
+    AppState,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct IngestUrlRequest {
+    pub url: String,
+    /// Keep the existing runs and add the downloaded ones, as `save-data?append=true` does
+    #[serde(default)]
+    pub append: bool,
+}
+
+/// Download a benchmark file from an allowed host and ingest it like a `save-data` upload
+///
+/// Appended runs are filed under the request's tenant; while tenancy is
+/// enabled only appending is allowed.
+pub async fn ingest_url(
+    State(state): State<AppState>,
+    tenant: Tenant,
+    Json(request): Json<IngestUrlRequest>,
+) -> Result<Json<FileUploadResponse>, AppError> {
+    if !request.append {
+        ensure_replace_allowed(&state)?;
+    }
+
+    let config = &state.settings.remote_ingest;
+    let fetcher = RemoteFileFetcher::new(&config.allowed_hosts, Duration::from_secs(config.timeout_seconds), MAX_FILE_SIZE)?;
+
+    let file = fetcher.fetch(&request.url).await?;
+    info!("Downloaded {} ({} bytes) from {}", file.file_name, file.content.len(), file.url);
+
+    let upload_id = Uuid::new_v4().to_string();
+    let upload = prepare_benchmark_file(&state, &tenant, &upload_id, &file.file_name, &file.content, MAX_FILE_SIZE).await?;
+    let mut responses = ingest_uploads(&state, vec![upload], !request.append).await?;
+
+    Ok(Json(responses.remove(0)))
+}
@@ -0,0 +1,83 @@
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::Json,
+};
+
+use crate::{
+    error::types::AppError,
+    handlers::{
+        common::{create_success_response, ApiResponse, DryRunQuery},
+        validation::{compile_regex_filter, get_validation_error_message},
+    },
+    middleware::request_transaction::RequestTransaction,
+    models::reparse::{RawStringFilter, ReparsePreview, ReparsePreviewRequest, ReparseRequest, ReparseResult},
+    services::data_processing::reparse_service::ReparseService,
+    AppState,
+};
+
+/// Run the current parsers on one run's raw strings and return the rows each step would write
+///
+/// Nothing is written; each step's rows are shown beside the ones it holds for
+/// the run now, so parser rules can be tried on problem rows before rerunning
+/// the pipeline.
+pub async fn reparse_preview(
+    State(state): State<AppState>,
+    Json(request): Json<ReparsePreviewRequest>,
+) -> Result<Json<ApiResponse<ReparsePreview>>, AppError> {
+    let preview = ReparseService::new(state.db.clone()).preview(request.run_id).await?;
+
+    Ok(create_success_response(preview, "Reparse preview generated successfully", StatusCode::OK))
+}
+
+/// Rebuild one step's rows for the runs whose raw string matches `where_like` or `where_regex`
+///
+/// E.g. `{"stage": "gpu", "where_like": "%Radeon(TM)%"}` reparses only the
+/// device strings a GPU parser fix was made for. The rows are replaced in the
+/// request transaction; with `?dry_run=true` it is rolled back afterwards and
+/// only the counts are returned.
+pub async fn reparse(
+    State(state): State<AppState>,
+    request_tx: RequestTransaction,
+    Query(query): Query<DryRunQuery>,
+    Json(request): Json<ReparseRequest>,
+) -> Result<Json<ApiResponse<ReparseResult>>, AppError> {
+    let filter = raw_string_filter(request.where_like, request.where_regex)?;
+
+    let mut tx = request_tx.begin().await?;
+    let result = ReparseService::new(state.db.clone())
+        .reparse(request.stage, &filter, query.dry_run, &mut tx)
+        .await?;
+
+    let message = if query.dry_run {
+        tx.rollback().await?;
+        "Dry run: runs that would be reparsed"
+    } else {
+        "Runs reparsed successfully"
+    };
+
+    Ok(create_success_response(result, message, StatusCode::OK))
+}
+
+/// The filter of a reparse request, refusing patterns that match every run or none
+///
+/// Reparsing every run is a full rebuild, which the pipeline steps do.
+fn raw_string_filter(where_like: Option<String>, where_regex: Option<String>) -> Result<RawStringFilter, AppError> {
+    match (where_like, where_regex) {
+        (Some(pattern), None) => {
+            if pattern.trim_matches(|c: char| c == '%' || c.is_whitespace()).is_empty() {
+                return Err(AppError::bad_request("where_like must match part of a raw string"));
+            }
+            Ok(RawStringFilter::Like(pattern))
+        }
+        (None, Some(pattern)) => {
+            let regex = compile_regex_filter(&pattern)
+                .map_err(|e| AppError::bad_request(get_validation_error_message("where_regex", &e.code)))?;
+            if regex.is_match("") {
+                return Err(AppError::bad_request("where_regex must not match an empty string"));
+            }
+            Ok(RawStringFilter::Regex(pattern))
+        }
+        _ => Err(AppError::bad_request("Exactly one of where_like and where_regex is required")),
+    }
+}
@@ -0,0 +1,25 @@
+use axum::{extract::State, http::StatusCode, response::Json};
+use tracing::info;
+
+use crate::{
+    error::types::AppError,
+    handlers::common::{create_success_response, ApiResponse},
+    services::retention::{preview_storage_cleanup, CleanupReport},
+    AppState,
+};
+
+/// Dry run of the storage cleanup: the staged uploads, backups and logs it would delete now
+///
+/// Applies `settings.retention` whether or not the scheduled cleanup is enabled,
+/// and deletes nothing.
+pub async fn storage_cleanup_preview(State(state): State<AppState>) -> Result<Json<ApiResponse<CleanupReport>>, AppError> {
+    let report = preview_storage_cleanup(&state.settings).await?;
+    info!(
+        "Storage cleanup would delete {} of {} files ({} bytes)",
+        report.files.len(),
+        report.scanned_files,
+        report.freed_bytes
+    );
+
+    Ok(create_success_response(report, "Storage cleanup preview generated successfully", StatusCode::OK))
+}
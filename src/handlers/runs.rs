@@ -0,0 +1,181 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+};
+use serde::Deserialize;
+use tracing::{error, info};
+use validator::Validate;
+
+use crate::{
+    error::types::AppError,
+    handlers::{
+        common::{create_list_response, create_success_response, ApiResponse, ListResponse},
+        query_params::{comma_separated, QueryParams, ValidatedQuery},
+    },
+    middleware::tenant::Tenant,
+    models::runs::{ParsedSource, RunInclude, RunListEntry, RunRawSources},
+    repositories::{
+        app_details_repository::AppDetailsRepository, gpu_repository::GpuRepository,
+        libraries_repository::LibrariesRepository, performance_result_repository::PerformanceResultRepository,
+        run_more_details_repository::RunMoreDetailsRepository, runs_repository::RunsRepository,
+        system_info_repository::SystemInfoRepository, traits::Repository,
+    },
+    services::anonymization::AnonymizationService,
+    AppState,
+};
+
+pub const DEFAULT_RUN_LIST_LIMIT: i64 = 50;
+pub const MAX_RUN_LIST_LIMIT: i64 = 500;
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct RunListQuery {
+    #[validate(range(min = 1, max = MAX_RUN_LIST_LIMIT))]
+    pub limit: Option<i64>,
+    #[validate(range(min = 0))]
+    pub offset: Option<i64>,
+    /// Comma-separated child rows to embed, e.g. `gpu,performance,app`
+    #[serde(default, deserialize_with = "comma_separated")]
+    pub include: Vec<RunInclude>,
+    pub anonymize: Option<bool>,
+}
+
+impl QueryParams for RunListQuery {
+    fn allowed_values(field: &str) -> Option<Vec<&'static str>> {
+        (field == "include").then(|| RunInclude::ALL.iter().map(|include| include.name()).collect())
+    }
+}
+
+/// The tenant's runs newest first, as lightweight rows or with the child rows named in `include`
+///
+/// Each included table is read with one query for the whole page, so the
+/// number of queries does not grow with `limit`.
+pub async fn list_runs(
+    State(state): State<AppState>,
+    tenant: Tenant,
+    ValidatedQuery(query): ValidatedQuery<RunListQuery>,
+) -> Result<Json<ListResponse<RunListEntry>>, AppError> {
+    let limit = query.limit.unwrap_or(DEFAULT_RUN_LIST_LIMIT);
+    let offset = query.offset.unwrap_or(0);
+    info!("Listing runs (limit {}, offset {}, include: {:?})", limit, offset, query.include);
+
+    let summaries = RunsRepository::new(state.db.clone())
+        .find_summaries(tenant.id(), limit, offset)
+        .await
+        .map_err(|e| {
+            error!("Failed to list runs: {}", e);
+            AppError::Database(e)
+        })?;
+    let run_ids: Vec<i64> = summaries.iter().map(|run| run.run_id).collect();
+    let included = |include| query.include.contains(&include);
+
+    let mut gpus = None;
+    if included(RunInclude::Gpu) {
+        gpus = Some(GpuRepository::new(state.db.clone()).find_by_run_ids(&run_ids).await.map_err(|e| {
+            error!("Failed to fetch GPUs of listed runs: {}", e);
+            AppError::Database(e)
+        })?);
+    }
+
+    let mut performance = None;
+    if included(RunInclude::Performance) {
+        performance = Some(
+            PerformanceResultRepository::new(state.db.clone())
+                .find_by_run_ids(&run_ids)
+                .await
+                .map_err(|e| {
+                    error!("Failed to fetch performance results of listed runs: {}", e);
+                    AppError::Database(e)
+                })?,
+        );
+    }
+
+    let mut system_info = None;
+    if included(RunInclude::SystemInfo) {
+        system_info = Some(SystemInfoRepository::new(state.db.clone()).find_by_run_ids(&run_ids).await.map_err(|e| {
+            error!("Failed to fetch system info of listed runs: {}", e);
+            AppError::Database(e)
+        })?);
+    }
+
+    let mut libraries = None;
+    if included(RunInclude::Libraries) {
+        libraries = Some(LibrariesRepository::new(state.db.clone()).find_by_run_ids(&run_ids).await.map_err(|e| {
+            error!("Failed to fetch libraries of listed runs: {}", e);
+            AppError::Database(e)
+        })?);
+    }
+
+    let mut app = None;
+    if included(RunInclude::App) {
+        app = Some(AppDetailsRepository::new(state.db.clone()).find_by_run_ids(&run_ids).await.map_err(|e| {
+            error!("Failed to fetch app details of listed runs: {}", e);
+            AppError::Database(e)
+        })?);
+    }
+
+    let mut entries: Vec<RunListEntry> = summaries
+        .into_iter()
+        .map(|run| RunListEntry {
+            gpus: gpus.as_mut().map(|gpus| gpus.remove(&run.run_id).unwrap_or_default()),
+            performance: performance.as_mut().map(|performance| performance.remove(&run.run_id)),
+            system_info: system_info.as_mut().map(|system_info| system_info.remove(&run.run_id)),
+            libraries: libraries.as_mut().map(|libraries| libraries.remove(&run.run_id)),
+            app: app.as_mut().map(|app| app.remove(&run.run_id)),
+            run,
+        })
+        .collect();
+    AnonymizationService::new(&state.settings.anonymization).apply(query.anonymize, &mut entries);
+
+    Ok(create_list_response(entries, "Runs retrieved successfully", StatusCode::OK, None))
+}
+
+/// A run's raw benchmark strings next to the rows parsed from each, for curators diagnosing a bad parse
+///
+/// A derived row is `null`, or the GPU list empty, when its pipeline step has
+/// not processed the run yet or could not parse the string. The strings are
+/// returned as submitted, so this is only mounted with the admin routes.
+pub async fn get_run_raw_sources(
+    State(state): State<AppState>,
+    tenant: Tenant,
+    Path(run_id): Path<i64>,
+) -> Result<Json<ApiResponse<RunRawSources>>, AppError> {
+    let repository = RunsRepository::new(state.db.clone());
+    let lookup_error = |e: sqlx::Error| {
+        error!("Failed to fetch run {}: {}", run_id, e);
+        AppError::Database(e)
+    };
+    let not_found = || AppError::not_found(format!("Run with id {}", run_id));
+    if !repository.exists_for_tenant(run_id, tenant.id()).await.map_err(lookup_error)? {
+        return Err(not_found());
+    }
+    let run = repository.find_by_id(run_id).await.map_err(lookup_error)?.ok_or_else(not_found)?;
+
+    let run_ids = [run_id];
+    let derived_error = |e: sqlx::Error| {
+        error!("Failed to fetch the derived rows of run {}: {}", run_id, e);
+        AppError::Database(e)
+    };
+    let mut app = AppDetailsRepository::new(state.db.clone()).find_by_run_ids(&run_ids).await.map_err(derived_error)?;
+    let mut system_info =
+        SystemInfoRepository::new(state.db.clone()).find_by_run_ids(&run_ids).await.map_err(derived_error)?;
+    let mut libraries = LibrariesRepository::new(state.db.clone()).find_by_run_ids(&run_ids).await.map_err(derived_error)?;
+    let mut gpus = GpuRepository::new(state.db.clone()).find_by_run_ids(&run_ids).await.map_err(derived_error)?;
+    let mut performance =
+        PerformanceResultRepository::new(state.db.clone()).find_by_run_ids(&run_ids).await.map_err(derived_error)?;
+    let mut run_details =
+        RunMoreDetailsRepository::new(state.db.clone()).find_by_run_ids(&run_ids).await.map_err(derived_error)?;
+
+    let sources = RunRawSources {
+        run_id,
+        info: ParsedSource { raw: run.info, parsed: app.remove(&run_id) },
+        system_info: ParsedSource { raw: run.system_info, parsed: system_info.remove(&run_id) },
+        model_info: ParsedSource { raw: run.model_info, parsed: libraries.remove(&run_id) },
+        device_info: ParsedSource { raw: run.device_info, parsed: gpus.remove(&run_id).unwrap_or_default() },
+        vram_usage: ParsedSource { raw: run.vram_usage, parsed: performance.remove(&run_id) },
+        model_name: ParsedSource { raw: run.model_name, parsed: run_details.remove(&run_id) },
+    };
+    info!("Retrieved raw sources of run {}", run_id);
+
+    Ok(create_success_response(sources, "Run sources retrieved successfully", StatusCode::OK))
+}
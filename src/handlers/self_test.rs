@@ -0,0 +1,203 @@
+use std::time::Instant;
+
+use axum::{extract::State, response::Json};
+use serde::Serialize;
+use tracing::{error, info};
+use uuid::Uuid;
+
+use crate::{
+    config::{
+        database::{create_pool, initialize_database, DatabaseConfig},
+        settings::Settings,
+    },
+    error::types::AppError,
+    handlers::{
+        admin::{ingest_uploads, prepare_benchmark_file},
+        pipeline::pipeline_registry,
+        validation::MAX_FILE_SIZE,
+    },
+    middleware::tenant::Tenant,
+    services::pipeline::PipelineStage,
+    AppState,
+};
+
+/// The bundled mini-dataset the self-test ingests, in the webui export format
+const SELF_TEST_FIXTURE: &[u8] = include_bytes!("../../data/self_test_runs.json");
+const SELF_TEST_FIXTURE_NAME: &str = "self_test_runs.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SelfTestStatus {
+    Passed,
+    Failed,
+    /// Not run because a step it depends on failed
+    Skipped,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SelfTestStep {
+    /// `migrate`, `ingest` or a pipeline stage name
+    pub step: String,
+    pub status: SelfTestStatus,
+    pub duration_ms: f64,
+    /// Rows the step inserted or updated, where it reports them
+    pub rows_out: Option<usize>,
+    pub error: Option<String>,
+}
+
+impl SelfTestStep {
+    fn finished(step: &str, started: Instant, result: Result<Option<usize>, AppError>) -> Self {
+        let (status, rows_out, error) = match result {
+            Ok(rows_out) => (SelfTestStatus::Passed, rows_out, None),
+            Err(e) => {
+                error!("Self-test step {} failed: {}", step, e);
+                (SelfTestStatus::Failed, None, Some(e.to_string()))
+            }
+        };
+
+        Self {
+            step: step.to_string(),
+            status,
+            duration_ms: started.elapsed().as_secs_f64() * 1000.0,
+            rows_out,
+            error,
+        }
+    }
+
+    fn skipped(step: &str) -> Self {
+        Self {
+            step: step.to_string(),
+            status: SelfTestStatus::Skipped,
+            duration_ms: 0.0,
+            rows_out: None,
+            error: None,
+        }
+    }
+
+    fn passed(&self) -> bool {
+        self.status == SelfTestStatus::Passed
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct SelfTestResponse {
+    /// Whether every step passed
+    pub success: bool,
+    pub steps: Vec<SelfTestStep>,
+}
+
+/// Smoke-check a deployment by running the whole pipeline on a bundled mini-dataset
+///
+/// Everything runs against a throwaway in-memory database with this
+/// deployment's settings, so the dataset is left untouched. Answers 200 either
+/// way; `success` and each step's status tell whether it passed.
+pub async fn self_test(State(state): State<AppState>) -> Json<SelfTestResponse> {
+    info!("Running self-test on an in-memory database");
+    let response = run_self_test(&state.settings).await;
+    info!(
+        "Self-test {}: {} of {} steps passed",
+        if response.success { "passed" } else { "failed" },
+        response.steps.iter().filter(|step| step.passed()).count(),
+        response.steps.len()
+    );
+
+    Json(response)
+}
+
+/// Migrate a fresh in-memory database, ingest the fixture into it and run every pipeline stage
+///
+/// A failing stage does not stop the run; only the stages depending on it are skipped.
+/// The scratch database has a single connection, so each stage gets its own
+/// transaction and must do all its reads and writes through it.
+pub async fn run_self_test(settings: &Settings) -> SelfTestResponse {
+    let registry = pipeline_registry();
+    let order = match registry.execution_order() {
+        Ok(order) => order,
+        Err(e) => {
+            let step = SelfTestStep::finished("pipeline", Instant::now(), Err(e));
+            return SelfTestResponse { success: false, steps: vec![step] };
+        }
+    };
+    let mut steps = Vec::with_capacity(order.len() + 2);
+
+    let started = Instant::now();
+    let state = match scratch_state(settings).await {
+        Ok(state) => {
+            steps.push(SelfTestStep::finished("migrate", started, Ok(None)));
+            let started = Instant::now();
+            let result = ingest_fixture(&state).await.map(Some);
+            let ingested = result.is_ok();
+            steps.push(SelfTestStep::finished("ingest", started, result));
+            ingested.then_some(state)
+        }
+        Err(e) => {
+            steps.push(SelfTestStep::finished("migrate", started, Err(e)));
+            steps.push(SelfTestStep::skipped("ingest"));
+            None
+        }
+    };
+
+    let mut failed: Vec<&str> = Vec::new();
+    for stage in order {
+        let state = match &state {
+            Some(state) if !stage.dependencies().iter().any(|dependency| failed.contains(dependency)) => state,
+            _ => {
+                failed.push(stage.name());
+                steps.push(SelfTestStep::skipped(stage.name()));
+                continue;
+            }
+        };
+
+        let started = Instant::now();
+        let result = run_stage(stage, state).await;
+        if result.is_err() {
+            failed.push(stage.name());
+        }
+        steps.push(SelfTestStep::finished(stage.name(), started, result));
+    }
+
+    if let Some(state) = state {
+        state.db.close().await;
+    }
+
+    SelfTestResponse {
+        success: steps.iter().all(SelfTestStep::passed),
+        steps,
+    }
+}
+
+/// Run one stage in its own transaction, rolled back if it fails
+async fn run_stage(stage: &dyn PipelineStage, state: &AppState) -> Result<Option<usize>, AppError> {
+    let mut tx = state.db.begin().await?;
+    let output = stage.execute(state, &mut tx).await?;
+    tx.commit().await?;
+
+    Ok(output.rows_out)
+}
+
+/// App state backed by a fresh, migrated in-memory database
+async fn scratch_state(settings: &Settings) -> Result<AppState, AppError> {
+    let db = create_pool(&DatabaseConfig::in_memory()).await?;
+    initialize_database(&db).await?;
+
+    Ok(AppState {
+        db,
+        settings: settings.clone(),
+    })
+}
+
+/// Ingest the bundled fixture the way an upload is, returning the runs inserted
+///
+/// Every fixture row is valid, so a row failing to insert fails the step.
+async fn ingest_fixture(state: &AppState) -> Result<usize, AppError> {
+    let upload_id = Uuid::new_v4().to_string();
+    let upload = prepare_benchmark_file(state, &Tenant::default(), &upload_id, SELF_TEST_FIXTURE_NAME, SELF_TEST_FIXTURE, MAX_FILE_SIZE).await?;
+    let responses = ingest_uploads(state, vec![upload], true).await?;
+
+    let rows_failed: usize = responses.iter().map(|response| response.rows_failed).sum();
+    if rows_failed > 0 {
+        return Err(AppError::internal(format!("{} fixture rows failed to insert", rows_failed)));
+    }
+
+    Ok(responses.iter().map(|response| response.rows_inserted).sum())
+}
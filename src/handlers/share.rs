@@ -0,0 +1,87 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+};
+use tracing::{error, info};
+
+use crate::{
+    error::types::AppError,
+    handlers::common::{create_success_response, ApiResponse},
+    middleware::tenant::Tenant,
+    models::runs::{RunDetail, ShareLink},
+    repositories::{moderation_repository::ModerationRepository, runs_repository::RunsRepository},
+    services::{
+        anonymization::{Anonymize, AnonymizationService},
+        sharing::ShareTokenService,
+    },
+    AppState,
+};
+
+/// Create a signed permalink to the detail of a run in the tenant's dataset
+///
+/// The token itself grants access, so anyone holding the link can read the run.
+pub async fn create_share_link(
+    State(state): State<AppState>,
+    tenant: Tenant,
+    Path(run_id): Path<i64>,
+) -> Result<(StatusCode, Json<ApiResponse<ShareLink>>), AppError> {
+    let repository = RunsRepository::new(state.db.clone());
+    if !repository
+        .exists_for_tenant(run_id, tenant.id())
+        .await
+        .map_err(|e| {
+            error!("Failed to fetch run {}: {}", run_id, e);
+            AppError::Database(e)
+        })?
+    {
+        return Err(AppError::not_found(format!("Run with id {}", run_id)));
+    }
+
+    let (token, expires_at) = ShareTokenService::new(&state.settings.sharing).issue(run_id);
+    info!("Issued share link for run {}", run_id);
+
+    let link = ShareLink {
+        run_id,
+        url: format!("/api/share/{}", token),
+        token,
+        expires_at: expires_at.map(|at| at.to_rfc3339()),
+    };
+
+    Ok((StatusCode::CREATED, create_success_response(link, "Share link created", StatusCode::CREATED)))
+}
+
+/// Resolve a share token to the run detail it was issued for; needs no authentication
+///
+/// A share link publishes the run to whoever holds it, so the username is always
+/// replaced with its pseudonym and the notes are left out. Runs held for
+/// moderation are not found until an admin has reviewed them.
+pub async fn get_shared_run(
+    State(state): State<AppState>,
+    Path(token): Path<String>,
+) -> Result<Json<ApiResponse<RunDetail>>, AppError> {
+    let run_id = ShareTokenService::new(&state.settings.sharing).verify(&token)?;
+
+    let held = ModerationRepository::new(state.db.clone())
+        .is_pending(run_id)
+        .await
+        .map_err(|e| {
+            error!("Failed to check moderation status of run {}: {}", run_id, e);
+            AppError::Database(e)
+        })?;
+    if held {
+        return Err(AppError::not_found("Share link not found or expired"));
+    }
+
+    let mut detail = RunsRepository::new(state.db.clone())
+        .find_detail(run_id)
+        .await
+        .map_err(|e| {
+            error!("Failed to fetch run detail {}: {}", run_id, e);
+            AppError::Database(e)
+        })?
+        .ok_or_else(|| AppError::not_found("Share link not found or expired"))?;
+    detail.anonymize(&AnonymizationService::new(&state.settings.anonymization));
+
+    Ok(create_success_response(detail, "Shared run retrieved successfully", StatusCode::OK))
+}
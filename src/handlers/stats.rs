@@ -0,0 +1,577 @@
+use std::future::Future;
+
+use axum::{extract::State, response::Json};
+use serde::{Deserialize, Serialize};
+use tracing::{error, info};
+use validator::{Validate, ValidationError};
+
+use crate::{
+    error::types::AppError,
+    handlers::{
+        common::{create_list_response, create_success_response, ApiResponse, CountMode, ListMeta, ListResponse},
+        query_params::{blank_as_none, not_blank, QueryParams, ValidatedQuery},
+    },
+    middleware::tenant::Tenant,
+    models::{
+        aggregates::{GroupedAverage, GroupedCount},
+        app_details::AppProjectStats, app_release::AppVersionStats, gpu_base::ArchitectureStats, gpu_percentile::GpuPercentilesReport, gpu_price::ItsPerDollar,
+        run_score::{LeaderboardEntry, TopConfiguration, TopConfigurationFilter},
+        system_info::CpuImpact,
+        tag::normalize_tag_name,
+    },
+    repositories::{
+        aggregates::{AggregateColumn, AggregateFilter, AggregateMetric, AggregatesRepository},
+        app_details_repository::AppDetailsRepository,
+        app_release_repository::AppReleaseRepository,
+        dataset_meta_repository::DatasetMetaRepository,
+        gpu_base_repository::GpuBaseRepository,
+        gpu_price_repository::GpuPriceRepository,
+        run_score_repository::RunScoreRepository,
+        system_info_repository::SystemInfoRepository,
+    },
+    services::{anonymization::AnonymizationService, cpu_impact::summarize_cpu_impact, percentiles::PercentileService},
+    AppState,
+};
+
+pub const DEFAULT_LEADERBOARD_LIMIT: i64 = 50;
+pub const MAX_LEADERBOARD_LIMIT: i64 = 500;
+pub const DEFAULT_TOP_LIMIT: i64 = 10;
+pub const MAX_TOP_LIMIT: i64 = 100;
+
+/// Leaderboard ranking, by `run_scores` column
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LeaderboardMetric {
+    #[default]
+    Score,
+    AvgIts,
+}
+
+impl LeaderboardMetric {
+    pub const ALL: [LeaderboardMetric; 2] = [LeaderboardMetric::Score, LeaderboardMetric::AvgIts];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            LeaderboardMetric::Score => "score",
+            LeaderboardMetric::AvgIts => "avg_its",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct LeaderboardQuery {
+    pub metric: Option<LeaderboardMetric>,
+    #[validate(range(min = 1, max = MAX_LEADERBOARD_LIMIT))]
+    pub limit: Option<i64>,
+    #[validate(custom(function = "valid_tag"))]
+    pub tag: Option<String>,
+    /// Leave out runs carrying this tag, e.g. "cloud"
+    #[validate(custom(function = "valid_tag"))]
+    pub exclude_tag: Option<String>,
+    pub anonymize: Option<bool>,
+    /// Keep runs on integrated GPUs, which are left out unless true
+    pub include_integrated: Option<bool>,
+    /// `exact` (default), `approximate` or `none`, see `CountMode`
+    #[serde(default, deserialize_with = "blank_as_none")]
+    pub count_mode: Option<CountMode>,
+}
+
+impl QueryParams for LeaderboardQuery {
+    fn allowed_values(field: &str) -> Option<Vec<&'static str>> {
+        match field {
+            "metric" => Some(LeaderboardMetric::ALL.iter().map(|metric| metric.name()).collect()),
+            "count_mode" => Some(CountMode::ALL.iter().map(|mode| mode.name()).collect()),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct TopConfigurationsQuery {
+    pub model_base: Option<String>,
+    /// Largest GPU VRAM to consider, in GB
+    #[validate(custom(function = "positive_gb"))]
+    pub max_vram: Option<f64>,
+    #[validate(range(min = 1, max = MAX_TOP_LIMIT))]
+    pub limit: Option<i64>,
+    #[validate(custom(function = "valid_tag"))]
+    pub tag: Option<String>,
+    /// Leave out runs carrying this tag, e.g. "cloud"
+    #[validate(custom(function = "valid_tag"))]
+    pub exclude_tag: Option<String>,
+    /// Keep runs on integrated GPUs, which are left out unless true
+    pub include_integrated: Option<bool>,
+}
+
+impl QueryParams for TopConfigurationsQuery {}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct AppProjectQuery {
+    #[validate(custom(function = "valid_tag"))]
+    pub tag: Option<String>,
+    /// Leave out runs carrying this tag, e.g. "cloud"
+    #[validate(custom(function = "valid_tag"))]
+    pub exclude_tag: Option<String>,
+    /// Keep runs on integrated GPUs, which are left out unless true
+    pub include_integrated: Option<bool>,
+}
+
+impl QueryParams for AppProjectQuery {}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct AppVersionQuery {
+    pub app: Option<String>,
+    #[validate(custom(function = "valid_tag"))]
+    pub tag: Option<String>,
+    /// Leave out runs carrying this tag, e.g. "cloud"
+    #[validate(custom(function = "valid_tag"))]
+    pub exclude_tag: Option<String>,
+    /// Keep runs on integrated GPUs, which are left out unless true
+    pub include_integrated: Option<bool>,
+}
+
+impl QueryParams for AppVersionQuery {}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CpuImpactQuery {
+    #[validate(required, custom(function = "not_blank"))]
+    pub gpu: Option<String>,
+}
+
+impl QueryParams for CpuImpactQuery {}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct ArchitectureQuery {
+    pub brand: Option<String>,
+    #[validate(custom(function = "valid_tag"))]
+    pub tag: Option<String>,
+    /// Leave out runs carrying this tag, e.g. "cloud"
+    #[validate(custom(function = "valid_tag"))]
+    pub exclude_tag: Option<String>,
+    /// Keep runs on integrated GPUs, which are left out unless true
+    pub include_integrated: Option<bool>,
+}
+
+impl QueryParams for ArchitectureQuery {}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct GpuPercentilesQuery {
+    /// Reported device name; every GPU when absent
+    pub gpu: Option<String>,
+}
+
+impl QueryParams for GpuPercentilesQuery {}
+
+#[derive(Debug, Deserialize, Validate)]
+#[validate(schema(function = "filter_given_together"))]
+pub struct GroupedStatsQuery {
+    /// Column to group runs by
+    #[serde(default, deserialize_with = "blank_as_none")]
+    #[validate(required)]
+    pub by: Option<AggregateColumn>,
+    /// Metric to average, `avg_its` unless given
+    #[serde(default, deserialize_with = "blank_as_none")]
+    pub metric: Option<AggregateMetric>,
+    /// Column restricting the runs to those where it equals `filter_value`
+    pub filter_by: Option<AggregateColumn>,
+    pub filter_value: Option<String>,
+}
+
+impl QueryParams for GroupedStatsQuery {
+    fn allowed_values(field: &str) -> Option<Vec<&'static str>> {
+        match field {
+            "by" | "filter_by" => Some(AggregateColumn::ALL.iter().map(|column| column.name()).collect()),
+            "metric" => Some(AggregateMetric::ALL.iter().map(|metric| metric.name()).collect()),
+            _ => None,
+        }
+    }
+}
+
+fn filter_given_together(query: &GroupedStatsQuery) -> Result<(), ValidationError> {
+    match (&query.filter_by, &query.filter_value) {
+        (Some(_), None) => Err(ValidationError::new("filter_value").with_message("is required with filter_by".into())),
+        (None, Some(_)) => Err(ValidationError::new("filter_by").with_message("is required with filter_value".into())),
+        _ => Ok(()),
+    }
+}
+
+/// Accept blank tags, which mean no filter, and valid tag names
+fn valid_tag(tag: &str) -> Result<(), ValidationError> {
+    if tag.trim().is_empty() || normalize_tag_name(tag).is_some() {
+        return Ok(());
+    }
+
+    Err(ValidationError::new("tag").with_message(format!("'{}' is not a valid tag", tag).into()))
+}
+
+fn positive_gb(max_vram: f64) -> Result<(), ValidationError> {
+    if max_vram.is_finite() && max_vram > 0.0 {
+        return Ok(());
+    }
+
+    Err(ValidationError::new("range").with_message("must be a positive number of GB".into()))
+}
+
+/// Group column and optional filter of a validated grouped stats request
+fn grouped_stats_target(query: &GroupedStatsQuery) -> Result<(AggregateColumn, Option<AggregateFilter>), AppError> {
+    let column = query.by.ok_or_else(|| AppError::bad_request("by is required"))?;
+    let filter = query
+        .filter_by
+        .zip(query.filter_value.clone())
+        .map(|(column, value)| AggregateFilter { column, value });
+
+    Ok((column, filter))
+}
+
+/// The `meta` block of a list over `table`, counting its rows per `count_mode`
+///
+/// `exact` runs `count`; `approximate` reads the table's cached row count and
+/// falls back to `count` when the tables were never analyzed, or while tenancy
+/// is enabled since the statistics span every tenant.
+async fn list_meta<F, Fut>(state: &AppState, count_mode: CountMode, table: &str, limit: i64, count: F) -> Result<ListMeta, AppError>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<i64, sqlx::Error>>,
+{
+    let estimated = match count_mode {
+        CountMode::None => {
+            return Ok(ListMeta {
+                count_mode,
+                total: None,
+                limit,
+            });
+        }
+        CountMode::Approximate if state.settings.tenancy.enabled => None,
+        CountMode::Approximate => DatasetMetaRepository::new(state.db.clone())
+            .estimated_row_count(table)
+            .await
+            .map_err(|e| {
+                error!("Failed to read table statistics of {}: {}", table, e);
+                AppError::Database(e)
+            })?,
+        CountMode::Exact => None,
+    };
+
+    let (count_mode, total) = match estimated {
+        Some(total) => (CountMode::Approximate, total),
+        None => {
+            let total = count().await.map_err(|e| {
+                error!("Failed to count rows of {}: {}", table, e);
+                AppError::Database(e)
+            })?;
+            (CountMode::Exact, total)
+        }
+    };
+
+    Ok(ListMeta {
+        count_mode,
+        total: Some(total),
+        limit,
+    })
+}
+
+/// Normalize a validated `tag` filter; blank means no filter
+fn tag_filter(tag: Option<String>) -> Option<String> {
+    tag.as_deref().and_then(normalize_tag_name)
+}
+
+pub async fn leaderboard(
+    State(state): State<AppState>,
+    tenant: Tenant,
+    ValidatedQuery(query): ValidatedQuery<LeaderboardQuery>,
+) -> Result<Json<ListResponse<LeaderboardEntry>>, AppError> {
+    let metric = query.metric.unwrap_or_default().name();
+    let limit = query.limit.unwrap_or(DEFAULT_LEADERBOARD_LIMIT);
+    let tag = tag_filter(query.tag);
+    let exclude_tag = tag_filter(query.exclude_tag);
+    let include_integrated = query.include_integrated.unwrap_or(false);
+    let count_mode = query.count_mode.unwrap_or_default();
+
+    info!(
+        "Fetching leaderboard by {} (limit {}, tag: {:?}, exclude_tag: {:?}, include_integrated: {}, count_mode: {})",
+        metric, limit, tag, exclude_tag, include_integrated, count_mode.name()
+    );
+
+    let repository = RunScoreRepository::new(state.db.clone());
+    let mut entries = repository
+        .find_leaderboard(tenant.id(), metric, tag.as_deref(), exclude_tag.as_deref(), include_integrated, limit)
+        .await
+        .map_err(|e| {
+            error!("Failed to fetch leaderboard: {}", e);
+            AppError::Database(e)
+        })?;
+    AnonymizationService::new(&state.settings.anonymization).apply(query.anonymize, &mut entries);
+
+    let meta = list_meta(&state, count_mode, "runs", limit, || {
+        repository.count_leaderboard(tenant.id(), metric, tag.as_deref(), exclude_tag.as_deref(), include_integrated)
+    })
+    .await?;
+
+    let mut response = create_list_response(
+        entries,
+        "Leaderboard retrieved successfully",
+        axum::http::StatusCode::OK,
+        None,
+    );
+    response.0.meta = Some(meta);
+    Ok(response)
+}
+
+/// Fastest GPU + torch + xformers configurations meeting the base model and VRAM constraints
+pub async fn top_configurations(
+    State(state): State<AppState>,
+    tenant: Tenant,
+    ValidatedQuery(query): ValidatedQuery<TopConfigurationsQuery>,
+) -> Result<Json<ListResponse<TopConfiguration>>, AppError> {
+    let model_base = query.model_base.filter(|model_base| !model_base.trim().is_empty());
+    let limit = query.limit.unwrap_or(DEFAULT_TOP_LIMIT);
+    let tag = tag_filter(query.tag);
+    let exclude_tag = tag_filter(query.exclude_tag);
+    let include_integrated = query.include_integrated.unwrap_or(false);
+
+    info!(
+        "Fetching top {} configurations (model_base: {:?}, max_vram: {:?}, tag: {:?}, exclude_tag: {:?}, include_integrated: {})",
+        limit, model_base, query.max_vram, tag, exclude_tag, include_integrated
+    );
+
+    let repository = RunScoreRepository::new(state.db.clone());
+    let entries = repository
+        .find_top_configurations(
+            tenant.id(),
+            &TopConfigurationFilter {
+                base_model: model_base.as_deref(),
+                max_vram_gb: query.max_vram,
+                tag: tag.as_deref(),
+                exclude_tag: exclude_tag.as_deref(),
+                include_integrated,
+            },
+            limit,
+        )
+        .await
+        .map_err(|e| {
+            error!("Failed to fetch top configurations: {}", e);
+            AppError::Database(e)
+        })?;
+
+    Ok(create_list_response(
+        entries,
+        "Top configurations retrieved successfully",
+        axum::http::StatusCode::OK,
+        None,
+    ))
+}
+
+pub async fn its_per_dollar(
+    State(state): State<AppState>,
+    tenant: Tenant,
+) -> Result<Json<ListResponse<ItsPerDollar>>, AppError> {
+    info!("Fetching ITS per dollar by base GPU");
+
+    let repository = GpuPriceRepository::new(state.db.clone());
+    let entries = repository.its_per_dollar(tenant.id()).await.map_err(|e| {
+        error!("Failed to fetch ITS per dollar: {}", e);
+        AppError::Database(e)
+    })?;
+
+    Ok(create_list_response(
+        entries,
+        "ITS per dollar retrieved successfully",
+        axum::http::StatusCode::OK,
+        None,
+    ))
+}
+
+/// Average ITS per app project, forks counted toward their upstream
+///
+/// Projects come from the canonicalize-app-urls step, so runs on the many
+/// spellings of one clone URL, and on forks in `app_attribution.fork_map`, land together.
+pub async fn its_by_app(
+    State(state): State<AppState>,
+    tenant: Tenant,
+    ValidatedQuery(query): ValidatedQuery<AppProjectQuery>,
+) -> Result<Json<ListResponse<AppProjectStats>>, AppError> {
+    let tag = tag_filter(query.tag);
+    let exclude_tag = tag_filter(query.exclude_tag);
+    let include_integrated = query.include_integrated.unwrap_or(false);
+    info!(
+        "Fetching ITS by app project (tag: {:?}, exclude_tag: {:?}, include_integrated: {})",
+        tag, exclude_tag, include_integrated
+    );
+
+    let repository = AppDetailsRepository::new(state.db.clone());
+    let entries = repository
+        .its_by_app(tenant.id(), tag.as_deref(), exclude_tag.as_deref(), include_integrated)
+        .await
+        .map_err(|e| {
+            error!("Failed to fetch ITS by app project: {}", e);
+            AppError::Database(e)
+        })?;
+
+    Ok(create_list_response(
+        entries,
+        "ITS by app project retrieved successfully",
+        axum::http::StatusCode::OK,
+        None,
+    ))
+}
+
+pub async fn its_by_app_version(
+    State(state): State<AppState>,
+    tenant: Tenant,
+    ValidatedQuery(query): ValidatedQuery<AppVersionQuery>,
+) -> Result<Json<ListResponse<AppVersionStats>>, AppError> {
+    let app = query.app.filter(|app| !app.trim().is_empty());
+    let tag = tag_filter(query.tag);
+    let exclude_tag = tag_filter(query.exclude_tag);
+    let include_integrated = query.include_integrated.unwrap_or(false);
+    info!(
+        "Fetching ITS by app version (app filter: {:?}, tag: {:?}, exclude_tag: {:?}, include_integrated: {})",
+        app, tag, exclude_tag, include_integrated
+    );
+
+    let repository = AppReleaseRepository::new(state.db.clone());
+    let entries = repository
+        .its_by_app_version(tenant.id(), app.as_deref(), tag.as_deref(), exclude_tag.as_deref(), include_integrated)
+        .await
+        .map_err(|e| {
+            error!("Failed to fetch ITS by app version: {}", e);
+            AppError::Database(e)
+        })?;
+
+    Ok(create_list_response(
+        entries,
+        "ITS by app version retrieved successfully",
+        axum::http::StatusCode::OK,
+        None,
+    ))
+}
+
+pub async fn its_by_architecture(
+    State(state): State<AppState>,
+    tenant: Tenant,
+    ValidatedQuery(query): ValidatedQuery<ArchitectureQuery>,
+) -> Result<Json<ListResponse<ArchitectureStats>>, AppError> {
+    let brand = query.brand.filter(|brand| !brand.trim().is_empty());
+    let tag = tag_filter(query.tag);
+    let exclude_tag = tag_filter(query.exclude_tag);
+    let include_integrated = query.include_integrated.unwrap_or(false);
+    info!(
+        "Fetching ITS by GPU architecture (brand filter: {:?}, tag: {:?}, exclude_tag: {:?}, include_integrated: {})",
+        brand, tag, exclude_tag, include_integrated
+    );
+
+    let repository = GpuBaseRepository::new(state.db.clone());
+    let entries = repository
+        .its_by_architecture(tenant.id(), brand.as_deref(), tag.as_deref(), exclude_tag.as_deref(), include_integrated)
+        .await
+        .map_err(|e| {
+            error!("Failed to fetch ITS by GPU architecture: {}", e);
+            AppError::Database(e)
+        })?;
+
+    Ok(create_list_response(
+        entries,
+        "ITS by GPU architecture retrieved successfully",
+        axum::http::StatusCode::OK,
+        None,
+    ))
+}
+
+/// Whether CPU choice measurably affects ITS on one GPU (device or base GPU name)
+pub async fn cpu_impact(
+    State(state): State<AppState>,
+    tenant: Tenant,
+    ValidatedQuery(query): ValidatedQuery<CpuImpactQuery>,
+) -> Result<Json<ApiResponse<CpuImpact>>, AppError> {
+    let gpu = query
+        .gpu
+        .map(|gpu| gpu.trim().to_string())
+        .ok_or_else(|| AppError::bad_request("gpu is required"))?;
+    info!("Fetching CPU impact on ITS for {}", gpu);
+
+    let repository = SystemInfoRepository::new(state.db.clone());
+    let samples = repository.find_cpu_its_samples_by_gpu_name(tenant.id(), &gpu).await.map_err(|e| {
+        error!("Failed to fetch CPU samples for {}: {}", gpu, e);
+        AppError::Database(e)
+    })?;
+
+    if samples.is_empty() {
+        return Err(AppError::not_found(format!("Runs on GPU '{}'", gpu)));
+    }
+
+    Ok(create_success_response(
+        summarize_cpu_impact(&gpu, &samples),
+        "CPU impact retrieved successfully",
+        axum::http::StatusCode::OK,
+    ))
+}
+
+/// avg_its percentiles per GPU device, from the percentile cache when it has been refreshed
+///
+/// `stale` tells whether the data changed since; `POST /api/admin/refresh-percentiles` rebuilds the cache.
+pub async fn gpu_percentiles(
+    State(state): State<AppState>,
+    tenant: Tenant,
+    ValidatedQuery(query): ValidatedQuery<GpuPercentilesQuery>,
+) -> Result<Json<ApiResponse<GpuPercentilesReport>>, AppError> {
+    let gpu = query.gpu.map(|gpu| gpu.trim().to_string()).filter(|gpu| !gpu.is_empty());
+    info!("Fetching ITS percentiles (gpu filter: {:?})", gpu);
+
+    let report = PercentileService::new(state.db.clone()).report(tenant.id(), gpu.as_deref()).await?;
+    if let Some(gpu) = gpu.filter(|_| report.gpus.is_empty()) {
+        return Err(AppError::not_found(format!("Runs on GPU '{}'", gpu)));
+    }
+
+    Ok(create_success_response(
+        report,
+        "ITS percentiles retrieved successfully",
+        axum::http::StatusCode::OK,
+    ))
+}
+
+/// Number of runs per value of a column
+pub async fn grouped_counts(
+    State(state): State<AppState>,
+    tenant: Tenant,
+    ValidatedQuery(query): ValidatedQuery<GroupedStatsQuery>,
+) -> Result<Json<ListResponse<GroupedCount>>, AppError> {
+    let (column, filter) = grouped_stats_target(&query)?;
+    info!("Fetching run counts grouped by {} (filter: {:?})", column.name(), filter);
+
+    let repository = AggregatesRepository::new(state.db.clone());
+    let entries = repository.count_grouped_by(tenant.id(), column, filter.as_ref()).await.map_err(|e| {
+        error!("Failed to fetch run counts grouped by {}: {}", column.name(), e);
+        AppError::Database(e)
+    })?;
+
+    Ok(create_list_response(
+        entries,
+        "Grouped run counts retrieved successfully",
+        axum::http::StatusCode::OK,
+        None,
+    ))
+}
+
+/// Average of a run metric per value of a column
+pub async fn grouped_averages(
+    State(state): State<AppState>,
+    tenant: Tenant,
+    ValidatedQuery(query): ValidatedQuery<GroupedStatsQuery>,
+) -> Result<Json<ListResponse<GroupedAverage>>, AppError> {
+    let (column, filter) = grouped_stats_target(&query)?;
+    let metric = query.metric.unwrap_or(AggregateMetric::AvgIts);
+    info!("Fetching average {} grouped by {} (filter: {:?})", metric.name(), column.name(), filter);
+
+    let repository = AggregatesRepository::new(state.db.clone());
+    let entries = repository.avg_grouped_by(tenant.id(), column, metric, filter.as_ref()).await.map_err(|e| {
+        error!("Failed to fetch average {} grouped by {}: {}", metric.name(), column.name(), e);
+        AppError::Database(e)
+    })?;
+
+    Ok(create_list_response(
+        entries,
+        "Grouped averages retrieved successfully",
+        axum::http::StatusCode::OK,
+        None,
+    ))
+}
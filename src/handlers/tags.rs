@@ -0,0 +1,133 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+};
+use tracing::{error, info};
+
+use crate::{
+    error::{handlers::reference_error, types::AppError},
+    handlers::common::{create_list_response, create_success_message, ApiResponse, ListResponse},
+    middleware::tenant::Tenant,
+    models::tag::{normalize_tag_name, AddRunTags, RunTag, TagSummary, MAX_TAG_LENGTH},
+    repositories::{runs_repository::RunsRepository, tag_repository::TagRepository},
+    AppState,
+};
+
+/// Make sure the run exists in the tenant's dataset before touching its tags
+async fn ensure_run_exists(state: &AppState, tenant: &Tenant, run_id: i64) -> Result<(), AppError> {
+    let exists = RunsRepository::new(state.db.clone())
+        .exists_for_tenant(run_id, tenant.id())
+        .await
+        .map_err(|e| {
+            error!("Failed to look up run {}: {}", run_id, e);
+            AppError::Database(e)
+        })?;
+
+    if exists {
+        Ok(())
+    } else {
+        Err(AppError::not_found(format!("Run {}", run_id)))
+    }
+}
+
+fn invalid_tag(name: &str) -> AppError {
+    AppError::validation(format!(
+        "Invalid tag '{}': use 1 to {} letters, digits, '-' or '_'",
+        name, MAX_TAG_LENGTH
+    ))
+}
+
+async fn run_tags(state: &AppState, run_id: i64) -> Result<Vec<RunTag>, AppError> {
+    TagRepository::new(state.db.clone())
+        .find_by_run_id(run_id)
+        .await
+        .map_err(|e| {
+            error!("Failed to fetch tags of run {}: {}", run_id, e);
+            AppError::Database(e)
+        })
+}
+
+/// Every tag in use in the tenant's dataset with its run count
+pub async fn list_tags(
+    State(state): State<AppState>,
+    tenant: Tenant,
+) -> Result<Json<ListResponse<TagSummary>>, AppError> {
+    let tags = TagRepository::new(state.db.clone()).find_all_with_counts(tenant.id()).await.map_err(|e| {
+        error!("Failed to fetch tags: {}", e);
+        AppError::Database(e)
+    })?;
+
+    Ok(create_list_response(tags, "Tags retrieved successfully", StatusCode::OK, None))
+}
+
+pub async fn get_run_tags(
+    State(state): State<AppState>,
+    tenant: Tenant,
+    Path(run_id): Path<i64>,
+) -> Result<Json<ListResponse<RunTag>>, AppError> {
+    ensure_run_exists(&state, &tenant, run_id).await?;
+    let tags = run_tags(&state, run_id).await?;
+
+    Ok(create_list_response(tags, "Run tags retrieved successfully", StatusCode::OK, None))
+}
+
+/// Attach manual tags to a run; returns the run's tags afterwards
+pub async fn add_run_tags(
+    State(state): State<AppState>,
+    tenant: Tenant,
+    Path(run_id): Path<i64>,
+    Json(request): Json<AddRunTags>,
+) -> Result<Json<ListResponse<RunTag>>, AppError> {
+    if request.tags.is_empty() {
+        return Err(AppError::validation("tags must not be empty"));
+    }
+
+    let mut names = Vec::with_capacity(request.tags.len());
+    for tag in &request.tags {
+        let name = normalize_tag_name(tag).ok_or_else(|| invalid_tag(tag))?;
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+
+    ensure_run_exists(&state, &tenant, run_id).await?;
+
+    TagRepository::new(state.db.clone())
+        .add_to_run(run_id, &names)
+        .await
+        .map_err(|e| {
+            error!("Failed to tag run {}: {}", run_id, e);
+            reference_error(e, "run_id", "run not found")
+        })?;
+
+    info!("Tagged run {} with {:?}", run_id, names);
+
+    let tags = run_tags(&state, run_id).await?;
+    Ok(create_list_response(tags, "Run tagged successfully", StatusCode::OK, None))
+}
+
+pub async fn remove_run_tag(
+    State(state): State<AppState>,
+    tenant: Tenant,
+    Path((run_id, tag)): Path<(i64, String)>,
+) -> Result<Json<ApiResponse<()>>, AppError> {
+    let name = normalize_tag_name(&tag).ok_or_else(|| invalid_tag(&tag))?;
+    ensure_run_exists(&state, &tenant, run_id).await?;
+
+    let removed = TagRepository::new(state.db.clone())
+        .remove_from_run(run_id, &name)
+        .await
+        .map_err(|e| {
+            error!("Failed to remove tag '{}' from run {}: {}", name, run_id, e);
+            AppError::Database(e)
+        })?;
+
+    if !removed {
+        return Err(AppError::not_found(format!("Tag '{}' on run {}", name, run_id)));
+    }
+
+    info!("Removed tag '{}' from run {}", name, run_id);
+
+    Ok(create_success_message("Run tag removed successfully", StatusCode::OK))
+}
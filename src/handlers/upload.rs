@@ -1,155 +1,87 @@
 use axum::{
     extract::State,
-    response::IntoResponse,
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
 };
 use axum_extra::extract::Multipart;
+use serde::Serialize;
 use serde_json::Value;
 use tempfile::NamedTempFile;
 use tokio::fs;
 use tracing::{error, info, warn};
 
 use crate::{
-    config::Settings,
-    handlers::common::{
-        create_error_response, create_file_upload_response, validate_content_type, validate_file_size,
-        validate_json_content, FileUploadResponse,
+    handlers::{
+        admin::append_benchmark_files,
+        common::{combine_file_upload_responses, create_error_response, require_feature, FileUploadResponse},
     },
+    middleware::tenant::Tenant,
     AppState,
 };
 use crate::error::AppError;
 
-/// File upload handler for processing multipart form data
-pub async fn upload_file(
-    State(config): State<Settings>,
+/// `FileUploadResponse` as `/api/upload` answers it
+///
+/// Clients written against the endpoint before it ingested check `error` for
+/// a code when an upload fails, so failures keep carrying one.
+#[derive(Debug, Serialize)]
+pub struct CompatUploadResponse {
+    #[serde(flatten)]
+    pub upload: FileUploadResponse,
+    /// `FILE_UPLOAD_ERROR` when any file failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<&'static str>,
+}
+
+/// Public benchmark upload: every file of the multipart body is appended to the runs
+///
+/// Files are validated and ingested exactly as by `save-data?append=true`,
+/// each in its own transaction, and filed under the request's tenant. Answers the standard `FileUploadResponse`,
+/// with 400 and the legacy error code when a file failed.
+pub async fn upload_file_compat(
+    State(state): State<AppState>,
+    tenant: Tenant,
     mut multipart: Multipart,
-) -> Result<axum::response::Response, AppError> {
-    let mut uploaded_files = Vec::new();
-    let mut errors = Vec::new();
+) -> Result<Response, AppError> {
+    require_feature(state.settings.features.enable_public_submissions, "public submissions")?;
 
-    // Process each field in the multipart form
+    let mut files = Vec::new();
     while let Some(field) = multipart.next_field().await.map_err(|e| {
         error!("Failed to read multipart field: {}", e);
         AppError::FileUpload("Failed to read multipart form data".to_string())
     })? {
-        let field_name = field.name().unwrap_or("unknown").to_string();
-        let filename = field.file_name().unwrap_or("unknown").to_string();
-        let content_type = field.content_type().unwrap_or("application/octet-stream").to_string();
-
-        info!("Processing file upload: {} ({}), type: {}", filename, field_name, content_type);
-
-        // Validate content type
-        if let Err(e) = validate_content_type(&content_type) {
-            errors.push(format!("File '{}': {}", filename, e));
-            continue;
-        }
-
-        // Read file content
-        let file_data = field.bytes().await.map_err(|e| {
-            error!("Failed to read file data for {}: {}", filename, e);
+        // Older clients name the file field freely, so every field is a file
+        let file_name = field.file_name().unwrap_or("unknown.json").to_string();
+        let data = field.bytes().await.map_err(|e| {
+            error!("Failed to read file data for {}: {}", file_name, e);
             AppError::FileUpload(format!("Failed to read file data: {}", e))
         })?;
-
-        let file_size = file_data.len();
-
-        // Validate file size
-        if let Err(e) = validate_file_size(file_size, config.file_upload.max_size_mb * 1024 * 1024) {
-            errors.push(format!("File '{}': {}", filename, e));
-            continue;
-        }
-
-        // Validate JSON content
-        if let Err(e) = validate_json_content(&file_data) {
-            errors.push(format!("File '{}': {}", filename, e));
-            continue;
-        }
-
-        // Convert bytes to string for processing
-        let content = String::from_utf8(file_data.to_vec()).map_err(|e| {
-            error!("Invalid UTF-8 in file {}: {}", filename, e);
-            AppError::FileUpload(format!("Invalid UTF-8 encoding: {}", e))
-        })?;
-
-        // Parse JSON data
-        let json_data: Value = serde_json::from_str(&content).map_err(|e| {
-            error!("Invalid JSON in file {}: {}", filename, e);
-            AppError::FileUpload(format!("Invalid JSON format: {}", e))
-        })?;
-
-        // Save to temporary file
-        let temp_file = match save_to_temp_file(&content, &filename).await {
-            Ok(temp_file) => temp_file,
-            Err(e) => {
-                errors.push(format!("File '{}': Failed to save temporary file: {}", filename, e));
-                continue;
-            }
-        };
-
-        // Create upload response using the new format
-        let upload_response = create_file_upload_response(
-            "File processed successfully",
-            &filename,
-            file_size,
-            1, // rows_processed
-            1, // rows_inserted
-            0, // rows_failed
-            axum::http::StatusCode::OK,
-        );
-
-        uploaded_files.push((upload_response, json_data, temp_file));
-
-        info!("Successfully processed file: {} ({} bytes)", filename, file_size);
+        files.push((file_name, data));
     }
 
-    // If there were errors but also successful uploads, return partial success
-    if !errors.is_empty() && !uploaded_files.is_empty() {
-        warn!("File upload completed with {} errors", errors.len());
-        return Ok(create_error_response(
-            "FILE_UPLOAD_ERROR",
-            "Some files failed to upload",
-            axum::http::StatusCode::BAD_REQUEST,
-            None,
-        ).into_response());
+    if files.is_empty() {
+        let response = create_error_response("NO_FILES_UPLOADED", "No files were uploaded", StatusCode::BAD_REQUEST, None);
+        return Ok((StatusCode::BAD_REQUEST, response).into_response());
     }
 
-    // If there were only errors, return error response
-    if !errors.is_empty() {
-        error!("File upload failed with {} errors", errors.len());
-        return Ok(create_error_response(
-            "FILE_UPLOAD_ERROR",
-            "All files failed to upload",
-            axum::http::StatusCode::BAD_REQUEST,
-            None,
-        ).into_response());
-    }
+    info!("Processing public upload of {} files", files.len());
+    let max_file_size = state.settings.file_upload.max_size_mb * 1024 * 1024;
+    let Json(upload) = combine_file_upload_responses(append_benchmark_files(&state, &tenant, &files, max_file_size).await);
 
-    // If no files were uploaded, return error
-    if uploaded_files.is_empty() {
-        return Ok(create_error_response(
-            "NO_FILES_UPLOADED",
-            "No files were uploaded",
-            axum::http::StatusCode::BAD_REQUEST,
-            None,
-        ).into_response());
+    if upload.success {
+        info!("Public upload ingested {} rows", upload.rows_inserted);
+        return Ok(Json(CompatUploadResponse { upload, error: None }).into_response());
     }
 
-    // Return success response with the first uploaded file
-    let (response, _, _) = uploaded_files.remove(0);
-    Ok(response.into_response())
-}
-
-/// Compatible file upload handler that works with AppState
-pub async fn upload_file_compat(
-    State(app_state): State<AppState>,
-    multipart: Multipart,
-) -> Result<impl IntoResponse, AppError> {
-    upload_file(State(app_state.settings), multipart).await
-}
-
-/// Save content to a temporary file
-async fn save_to_temp_file(content: &str, _filename: &str) -> Result<NamedTempFile, std::io::Error> {
-    let temp_file = NamedTempFile::new()?;
-    fs::write(&temp_file, content).await?;
-    Ok(temp_file)
+    warn!("Public upload failed: {}", upload.message);
+    Ok((
+        StatusCode::BAD_REQUEST,
+        Json(CompatUploadResponse {
+            upload,
+            error: Some("FILE_UPLOAD_ERROR"),
+        }),
+    )
+        .into_response())
 }
 
 /// Clean up temporary files
@@ -196,6 +128,8 @@ mod tests {
     use super::*;
     use serde_json::json;
 
+    use crate::handlers::common::create_file_upload_response;
+
     #[test]
     fn test_validate_file_structure_object() {
         let data = json!({"key": "value"});
@@ -240,15 +174,4 @@ mod tests {
         assert_eq!(extracted.len(), 1);
         assert_eq!(extracted[0], json_data);
     }
-
-    #[tokio::test]
-    async fn test_save_to_temp_file() {
-        let content = r#"{"test": "data"}"#;
-        let filename = "test.json";
-        
-        let temp_file = save_to_temp_file(content, filename).await.unwrap();
-        let saved_content = fs::read_to_string(temp_file.path()).await.unwrap();
-        
-        assert_eq!(saved_content, content);
-    }
 } 
\ No newline at end of file
@@ -0,0 +1,19 @@
+use axum::{http::StatusCode, response::Json};
+use tracing::info;
+
+use crate::{
+    handlers::common::{create_list_response, ListResponse},
+    services::ingest::{upload_formats, UploadFormat},
+};
+
+/// Describe every accepted upload format with its JSON Schema
+pub async fn list_upload_formats() -> Json<ListResponse<UploadFormat>> {
+    info!("Listing accepted upload formats");
+
+    create_list_response(
+        upload_formats(),
+        "Upload formats retrieved successfully",
+        StatusCode::OK,
+        None,
+    )
+}
@@ -0,0 +1,109 @@
+use axum::{
+    body::Bytes,
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+};
+use tracing::{error, info};
+
+use crate::{
+    error::types::AppError,
+    handlers::{
+        admin::ingest_benchmark_file,
+        common::{create_success_response, require_feature, ApiResponse, FileUploadResponse},
+    },
+    middleware::tenant::Tenant,
+    models::upload_session::{CreateUploadSession, UploadProgress, UploadSchemaReport, UploadSession},
+    repositories::upload_session_repository::UploadSessionRepository,
+    services::uploads::{upload_staging_dir, ResumableUploadService},
+    AppState,
+};
+
+fn create_service(state: &AppState) -> ResumableUploadService {
+    let upload_config = &state.settings.file_upload;
+    ResumableUploadService::new(
+        UploadSessionRepository::new(state.db.clone()),
+        upload_staging_dir(upload_config),
+        upload_config.max_resumable_size_mb * 1024 * 1024,
+    )
+}
+
+/// Start an upload owned by the request's tenant; the other upload routes treat another tenant's upload as not found
+pub async fn init_upload(
+    State(state): State<AppState>,
+    tenant: Tenant,
+    Json(request): Json<CreateUploadSession>,
+) -> Result<Json<ApiResponse<UploadSession>>, AppError> {
+    require_feature(state.settings.features.enable_public_submissions, "public submissions")?;
+    let session = create_service(&state).init(tenant.id(), request).await?;
+
+    Ok(create_success_response(session, "Upload started", StatusCode::CREATED))
+}
+
+pub async fn upload_part(
+    State(state): State<AppState>,
+    tenant: Tenant,
+    Path((id, part_number)): Path<(String, i64)>,
+    body: Bytes,
+) -> Result<Json<ApiResponse<UploadProgress>>, AppError> {
+    let progress = create_service(&state).store_part(tenant.id(), &id, part_number, &body).await?;
+
+    info!(
+        "Stored part {} of upload {} ({} parts, {} bytes received)",
+        part_number, id, progress.received_parts, progress.bytes_received
+    );
+
+    Ok(create_success_response(progress, "Upload part stored", StatusCode::OK))
+}
+
+pub async fn get_upload(
+    State(state): State<AppState>,
+    tenant: Tenant,
+    Path(id): Path<String>,
+) -> Result<Json<ApiResponse<UploadProgress>>, AppError> {
+    let progress = create_service(&state).progress(tenant.id(), &id).await?;
+
+    Ok(create_success_response(progress, "Upload progress retrieved successfully", StatusCode::OK))
+}
+
+/// Assemble the parts of an upload and append its runs under the request's tenant
+pub async fn complete_upload(
+    State(state): State<AppState>,
+    tenant: Tenant,
+    Path(id): Path<String>,
+) -> Result<Json<FileUploadResponse>, AppError> {
+    let service = create_service(&state);
+    // Held until `finish`; dropped early, it fails the upload rather than leaving it claimed
+    let (session, file, claim) = service.assemble(tenant.id(), &id).await?;
+    let max_size = state.settings.file_upload.max_resumable_size_mb * 1024 * 1024;
+
+    let result = ingest_benchmark_file(&state, &tenant, &id, &session.file_name, &file, max_size).await;
+
+    let outcome = match &result {
+        Ok(response) => Ok(format!(
+            "{} rows inserted, {} rows failed",
+            response.rows_inserted, response.rows_failed
+        )),
+        Err(e) => {
+            error!("Failed to ingest upload {}: {}", id, e);
+            Err(e.to_string())
+        }
+    };
+    service.finish(claim, outcome).await?;
+
+    result
+}
+
+/// Unknown and missing keys of an ingested upload, compared with its format's JSON Schema
+pub async fn get_schema_report(
+    State(state): State<AppState>,
+    tenant: Tenant,
+    Path(id): Path<String>,
+) -> Result<Json<ApiResponse<UploadSchemaReport>>, AppError> {
+    let report = UploadSessionRepository::new(state.db.clone())
+        .find_schema_report(&id, tenant.id())
+        .await?
+        .ok_or_else(|| AppError::not_found(format!("Schema report for upload {}", id)))?;
+
+    Ok(create_success_response(report, "Schema report retrieved successfully", StatusCode::OK))
+}
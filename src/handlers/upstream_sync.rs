@@ -0,0 +1,123 @@
+use std::time::Duration;
+
+use axum::{extract::State, response::Json};
+use serde::Serialize;
+use tokio::{task::JoinHandle, time::MissedTickBehavior};
+use tracing::{error, info};
+use uuid::Uuid;
+
+use crate::{
+    error::types::AppError,
+    handlers::{
+        admin::{ingest_uploads, known_rows, prepare_benchmark_file},
+        common::FileUploadResponse,
+        pipeline::{execute_pipeline, RunPipelineResponse},
+        validation::MAX_FILE_SIZE,
+    },
+    middleware::{maintenance_mode::ensure_not_in_maintenance, tenant::Tenant},
+    services::remote_ingest::RemoteFileFetcher,
+    AppState,
+};
+
+#[derive(Debug, Serialize)]
+pub struct UpstreamSyncResponse {
+    pub success: bool,
+    pub url: String,
+    /// Rows in the downloaded file
+    pub rows_downloaded: usize,
+    /// Rows skipped because the same row was ingested before
+    pub rows_already_ingested: usize,
+    /// Result of appending the new rows; absent when there were none
+    pub upload: Option<FileUploadResponse>,
+    /// Pipeline run after appending, when `upstream_sync.run_pipeline` is set
+    pub pipeline: Option<RunPipelineResponse>,
+}
+
+/// Pull the upstream benchmark file now and append its new rows
+pub async fn upstream_sync(State(state): State<AppState>) -> Result<Json<UpstreamSyncResponse>, AppError> {
+    sync_upstream(&state).await.map(Json)
+}
+
+/// Download `settings.upstream_sync.url`, append the rows not ingested before and process them
+///
+/// Rows are told apart by content hash, so a row edited upstream is appended
+/// as a new run, and a row any tenant uploaded already counts as ingested.
+/// New rows are public data and are filed under the default tenant, whoever
+/// triggered the sync. The pipeline only runs when rows were appended. Refused
+/// with 503 while maintenance mode is on.
+pub async fn sync_upstream(state: &AppState) -> Result<UpstreamSyncResponse, AppError> {
+    ensure_not_in_maintenance(&state.db).await?;
+
+    let config = &state.settings.upstream_sync;
+    let url = config
+        .url
+        .as_deref()
+        .ok_or_else(|| AppError::bad_request("No upstream_sync url is configured"))?;
+    let remote_ingest = &state.settings.remote_ingest;
+    let fetcher = RemoteFileFetcher::new(
+        &remote_ingest.allowed_hosts,
+        Duration::from_secs(remote_ingest.timeout_seconds),
+        MAX_FILE_SIZE,
+    )?;
+    let file = fetcher.fetch(url).await?;
+
+    let upload_id = Uuid::new_v4().to_string();
+    let mut upload = prepare_benchmark_file(state, &Tenant::default(), &upload_id, &file.file_name, &file.content, MAX_FILE_SIZE).await?;
+    let rows_downloaded = upload.row_count();
+
+    // Inserting as we go also drops rows repeated within the file
+    let mut known = known_rows(state).await?.content_hashes;
+    upload.retain_rows(|hash| known.insert(hash.to_string()));
+    let rows_new = upload.row_count();
+    info!("Upstream sync of {}: {} rows, {} new", url, rows_downloaded, rows_new);
+
+    let mut response = UpstreamSyncResponse {
+        success: true,
+        url: url.to_string(),
+        rows_downloaded,
+        rows_already_ingested: rows_downloaded - rows_new,
+        upload: None,
+        pipeline: None,
+    };
+    if rows_new == 0 {
+        return Ok(response);
+    }
+
+    response.upload = ingest_uploads(state, vec![upload], false).await?.pop();
+    if config.run_pipeline {
+        response.pipeline = Some(execute_pipeline(state, state.settings.pipeline.enabled_stages.as_deref()).await?);
+    }
+
+    Ok(response)
+}
+
+/// Start the scheduled upstream sync when `settings.upstream_sync.enabled` is set
+///
+/// The first sync runs one interval after startup; a failed sync is logged and
+/// tried again at the next interval, and ticks during maintenance are skipped.
+pub fn spawn_upstream_sync(state: AppState) -> Option<JoinHandle<()>> {
+    let config = &state.settings.upstream_sync;
+    if !config.enabled {
+        return None;
+    }
+
+    let period = Duration::from_secs(config.interval_minutes * 60);
+    info!("Syncing upstream benchmark data every {} minutes", config.interval_minutes);
+
+    Some(tokio::spawn(async move {
+        let mut interval = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            match sync_upstream(&state).await {
+                Ok(outcome) => info!(
+                    "Upstream sync appended {} of {} rows",
+                    outcome.rows_downloaded - outcome.rows_already_ingested,
+                    outcome.rows_downloaded
+                ),
+                Err(AppError::Maintenance(_)) => info!("Skipped upstream sync during maintenance"),
+                Err(e) => error!("Upstream sync failed: {}", e),
+            }
+        }
+    }))
+}
@@ -0,0 +1,173 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+};
+use serde_json::json;
+use tracing::{error, info};
+
+use crate::{
+    error::types::AppError,
+    handlers::common::{create_success_response, ApiResponse},
+    middleware::{request_transaction::RequestTransaction, tenant::Tenant},
+    models::{
+        audit_log::{AUDIT_ACTION_FORGET_USER, AUDIT_ACTION_MERGE_USERS},
+        runs::{ForgetUserResult, MergeUsersRequest, MergeUsersResult},
+    },
+    repositories::{
+        audit_log_repository::AuditLogRepository,
+        audit_timestamp,
+        dataset_meta_repository::DatasetMetaRepository,
+        run_more_details_repository::RunMoreDetailsRepository,
+        runs_repository::{RunsRepository, RUN_CHILD_TABLES},
+    },
+    services::anonymization::AnonymizationService,
+    AppState,
+};
+
+/// Delete every run the tenant's `user` submitted together with the rows derived from it
+///
+/// Runs are matched on the raw `runs.user` and on the processed
+/// `RunMoreDetails.user`; a user of the same name in another tenant is untouched. The deletions and their audit entry share the request
+/// transaction, so either all of the user's data is gone or none of it is. The
+/// audit entry names the user by pseudonym, never by the raw username.
+pub async fn forget_user(
+    State(state): State<AppState>,
+    tenant: Tenant,
+    request_tx: RequestTransaction,
+    Path(user): Path<String>,
+) -> Result<Json<ApiResponse<ForgetUserResult>>, AppError> {
+    if user.trim().is_empty() {
+        return Err(AppError::bad_request("User must not be blank"));
+    }
+
+    let runs_repo = RunsRepository::new(state.db.clone());
+    let mut tx = request_tx.begin().await?;
+
+    let run_ids = runs_repo.find_ids_by_user_tx(&user, tenant.id(), &mut tx).await.map_err(|e| {
+        error!("Failed to find runs to forget: {}", e);
+        AppError::Database(e)
+    })?;
+    if run_ids.is_empty() {
+        let deleted = RUN_CHILD_TABLES
+            .iter()
+            .chain(["runs"].iter())
+            .map(|table| (table.to_string(), 0))
+            .collect();
+        return Ok(create_success_response(
+            ForgetUserResult { runs_deleted: 0, deleted, audit_id: None },
+            "No runs found for user",
+            StatusCode::OK,
+        ));
+    }
+
+    let deleted = runs_repo.delete_with_children_tx(&run_ids, &mut tx).await.map_err(|e| {
+        error!("Failed to delete runs of forgotten user: {}", e);
+        AppError::Database(e)
+    })?;
+
+    let pseudonym = AnonymizationService::new(&state.settings.anonymization).pseudonym(&user);
+    let details = json!({ "user": pseudonym, "run_ids": run_ids, "deleted": deleted }).to_string();
+    let audit_id = AuditLogRepository::new(state.db.clone())
+        .create_tx(AUDIT_ACTION_FORGET_USER, &details, &mut tx)
+        .await
+        .map_err(|e| {
+            error!("Failed to record user deletion audit entry: {}", e);
+            AppError::Database(e)
+        })?;
+
+    let runs_deleted = deleted.get("runs").copied().unwrap_or(0);
+    info!("Forgot user {}: deleted {} runs (audit {})", pseudonym, runs_deleted, audit_id);
+
+    Ok(create_success_response(
+        ForgetUserResult { runs_deleted, deleted, audit_id: Some(audit_id) },
+        "User data deleted successfully",
+        StatusCode::OK,
+    ))
+}
+
+/// File every run the tenant's `from` submitted under `to`
+///
+/// Contributors sometimes submit under several spellings of one name. Both the
+/// raw `runs.user` and the processed `RunMoreDetails.user` are rewritten in the
+/// request transaction together with the audit entry, and the dataset version
+/// is bumped so anything cached per dataset version is refreshed. Leaderboards
+/// and feeds read the user from these rows, so they pick up the merge directly.
+pub async fn merge_users(
+    State(state): State<AppState>,
+    tenant: Tenant,
+    request_tx: RequestTransaction,
+    Json(request): Json<MergeUsersRequest>,
+) -> Result<Json<ApiResponse<MergeUsersResult>>, AppError> {
+    let to = request.to.trim();
+    if request.from.trim().is_empty() || to.is_empty() {
+        return Err(AppError::bad_request("Both from and to users are required"));
+    }
+    if request.from == to {
+        return Err(AppError::bad_request("Cannot merge a user into itself"));
+    }
+
+    let mut tx = request_tx.begin().await?;
+
+    let runs_updated = RunsRepository::new(state.db.clone())
+        .rename_user_tx(&request.from, to, tenant.id(), &mut tx)
+        .await
+        .map_err(|e| {
+            error!("Failed to merge user on runs: {}", e);
+            AppError::Database(e)
+        })?;
+    let run_details_updated = RunMoreDetailsRepository::new(state.db.clone())
+        .rename_user_tx(&request.from, to, tenant.id(), &mut tx)
+        .await
+        .map_err(|e| {
+            error!("Failed to merge user on run details: {}", e);
+            AppError::Database(e)
+        })?;
+
+    if runs_updated == 0 && run_details_updated == 0 {
+        return Ok(create_success_response(
+            MergeUsersResult { runs_updated, run_details_updated, dataset_version: None, audit_id: None },
+            "No runs found for user",
+            StatusCode::OK,
+        ));
+    }
+
+    let dataset_version = DatasetMetaRepository::new(state.db.clone())
+        .increment_version_tx(&audit_timestamp(), &mut tx)
+        .await
+        .map_err(|e| {
+            error!("Failed to bump dataset version: {}", e);
+            AppError::Database(e)
+        })?;
+
+    let details = json!({
+        "from": request.from,
+        "to": to,
+        "runs_updated": runs_updated,
+        "run_details_updated": run_details_updated,
+    })
+    .to_string();
+    let audit_id = AuditLogRepository::new(state.db.clone())
+        .create_tx(AUDIT_ACTION_MERGE_USERS, &details, &mut tx)
+        .await
+        .map_err(|e| {
+            error!("Failed to record user merge audit entry: {}", e);
+            AppError::Database(e)
+        })?;
+
+    info!(
+        "Merged user {:?} into {:?}: {} runs, {} run details (audit {})",
+        request.from, to, runs_updated, run_details_updated, audit_id
+    );
+
+    Ok(create_success_response(
+        MergeUsersResult {
+            runs_updated,
+            run_details_updated,
+            dataset_version: Some(dataset_version),
+            audit_id: Some(audit_id),
+        },
+        "Users merged successfully",
+        StatusCode::OK,
+    ))
+}
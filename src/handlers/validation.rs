@@ -1,3 +1,5 @@
+use regex::{Regex, RegexBuilder};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use validator::ValidationError;
 
@@ -27,7 +29,7 @@ pub struct FixAppNamesRequest {
 // Data Processing Validation
 // ============================================================================
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct RunData {
     pub timestamp: String,
     pub vram_usage: String,
@@ -41,6 +43,24 @@ pub struct RunData {
     pub notes: String,
 }
 
+impl RunData {
+    /// Every field with its name, in column order
+    pub fn fields(&self) -> [(&'static str, &str); 10] {
+        [
+            ("timestamp", &self.timestamp),
+            ("vram_usage", &self.vram_usage),
+            ("info", &self.info),
+            ("system_info", &self.system_info),
+            ("model_info", &self.model_info),
+            ("device_info", &self.device_info),
+            ("xformers", &self.xformers),
+            ("model_name", &self.model_name),
+            ("user", &self.user),
+            ("notes", &self.notes),
+        ]
+    }
+}
+
 // ============================================================================
 // Query Parameter Validation
 // ============================================================================
@@ -105,6 +125,25 @@ pub fn validate_vram_usage_format(vram_usage: &str) -> Result<(), ValidationErro
     Ok(())
 }
 
+/// Compile a regular expression filter taken from a request
+///
+/// The regex engine runs in linear time, so the only cost left to bound is the
+/// pattern's size; the limits here are well inside those of SQLite's `REGEXP`,
+/// which compiles the same pattern again per query.
+pub fn compile_regex_filter(pattern: &str) -> Result<Regex, ValidationError> {
+    if pattern.is_empty() {
+        return Err(ValidationError::new("empty_regex"));
+    }
+    if pattern.len() > MAX_REGEX_FILTER_LEN {
+        return Err(ValidationError::new("regex_too_long"));
+    }
+
+    RegexBuilder::new(pattern)
+        .size_limit(REGEX_FILTER_SIZE_LIMIT)
+        .build()
+        .map_err(|_| ValidationError::new("invalid_regex"))
+}
+
 // ============================================================================
 // Validation Helpers
 // ============================================================================
@@ -136,6 +175,8 @@ pub fn validate_file_extension(filename: &str, allowed_extensions: &[&str]) -> R
 
 pub const MAX_FILE_SIZE: usize = 50 * 1024 * 1024; // 50MB
 pub const ALLOWED_FILE_EXTENSIONS: &[&str] = &["json"];
+pub const MAX_REGEX_FILTER_LEN: usize = 256;
+pub const REGEX_FILTER_SIZE_LIMIT: usize = 1024 * 1024; // compiled program, 1MB
 
 // ============================================================================
 // Validation Error Messages
@@ -152,6 +193,9 @@ pub fn get_validation_error_message(field: &str, error_type: &str) -> String {
         ("timestamp", "invalid_timestamp_format") => "Invalid timestamp format".to_string(),
         ("vram_usage", "empty_vram_usage") => "VRAM usage cannot be empty".to_string(),
         ("vram_usage", "invalid_vram_format") => "Invalid VRAM usage format".to_string(),
+        (field, "empty_regex") => format!("{} cannot be empty", field),
+        (field, "regex_too_long") => format!("{} must be at most {} bytes", field, MAX_REGEX_FILTER_LEN),
+        (field, "invalid_regex") => format!("{} is not a valid regular expression", field),
         _ => format!("Validation error for field '{}': {}", field, error_type),
     }
 } 
\ No newline at end of file
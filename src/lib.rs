@@ -5,6 +5,8 @@ pub mod handlers;
 pub mod repositories;
 pub mod services;
 pub mod middleware;
+pub mod router;
+pub mod routes;
 
 use sqlx::SqlitePool;
 
@@ -1,7 +1,3 @@
-use axum::{
-    routing::{get, post},
-    Router,
-};
 use std::net::SocketAddr;
 use tracing::{info, error, warn};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
@@ -11,8 +7,17 @@ use sd_its_benchmark::{
     load_config_with_fallback, 
     validate_config, 
     initialize_config_directories,
-    handlers,
-    config::database::{DatabaseConfig, create_pool, initialize_database, health_check},
+    error::i18n::ErrorCatalog,
+    middleware::catch_panic::install_panic_hook,
+    router::create_router,
+    handlers::{load_data::{ingest_generated, seed_demo_data}, upstream_sync::spawn_upstream_sync},
+    services::{
+        health_history::spawn_health_sampling,
+        load_data::{GenerateCommand, SyntheticDataGenerator},
+        retention::spawn_storage_cleanup,
+    },
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database, health_check}, schema::verify_schema},
+    repositories::connection::configure_query_limits,
 };
 
 #[tokio::main]
@@ -30,8 +35,16 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Load environment variables from .env file
     dotenvy::dotenv().ok();
     
+    // `demo` runs the demo profile whatever RUST_ENV says: in-memory database with sample data
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let demo = args.first().map(String::as_str) == Some("demo");
+
     // Log the current RUST_ENV value and which config TOML files exist
-    let rust_env = std::env::var("RUST_ENV").unwrap_or_else(|_| "development".to_string());
+    let rust_env = if demo {
+        "demo".to_string()
+    } else {
+        std::env::var("RUST_ENV").unwrap_or_else(|_| "development".to_string())
+    };
     let config_files = [
         "config/default.toml",
         &format!("config/{}.toml", rust_env),
@@ -39,7 +52,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     ];
     let used_files: Vec<&str> = config_files.iter()
         .filter(|file| std::path::Path::new(file).exists())
-        .map(|s| *s)
+        .copied()
         .collect();
     info!("RUST_ENV is set to: {} | Using config files: {}", rust_env, used_files.join(", "));
 
@@ -54,7 +67,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Load and validate configuration
     info!("Loading configuration...");
-    let settings = load_config_with_fallback()?;
+    let settings = if demo { Settings::for_environment(&rust_env)? } else { load_config_with_fallback()? };
     info!("Configuration loaded - Port: {}", settings.server.port);
     
     // Validate configuration
@@ -71,16 +84,53 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Initialize database
     info!("Initializing database...");
-    let db_config = DatabaseConfig::default();
+    configure_query_limits(&settings.database);
+    let db_config = if settings.is_demo() { DatabaseConfig::in_memory() } else { DatabaseConfig::default() };
     let db_pool = create_pool(&db_config).await?;
     
     // Run database migrations/initialization
     initialize_database(&db_pool).await?;
+
+    // Fail fast on tables or columns the queries need but the database lacks
+    let schema_report = verify_schema(&db_pool).await?;
+    if !schema_report.is_complete() {
+        error!("Database schema verification failed:");
+        for problem in schema_report.problems() {
+            error!("  - {}", problem);
+        }
+        std::process::exit(1);
+    }
     
     // Health check database
     health_check(&db_pool).await?;
     info!("Database initialized successfully");
 
+    // `generate --rows N [--seed N] [--output PATH] [--ingest]` builds a synthetic dataset instead of serving
+    if args.first().map(String::as_str) == Some("generate") {
+        let command = GenerateCommand::parse(&args[1..]).unwrap_or_else(|e| {
+            error!("{}", e);
+            std::process::exit(2);
+        });
+        if command.ingest {
+            let app_state = AppState { db: db_pool, settings };
+            let response = ingest_generated(&app_state, command.rows, command.seed).await?;
+            info!("Ingested {} synthetic runs ({} failed)", response.rows_inserted, response.rows_failed);
+        } else {
+            let path = command.output_path();
+            std::fs::write(&path, SyntheticDataGenerator::new(command.seed).export(command.rows))?;
+            info!("Wrote {} synthetic runs to {}", command.rows, path);
+        }
+        return Ok(());
+    }
+
+    if settings.is_demo() {
+        info!("Seeding the in-memory demo database...");
+        seed_demo_data(&AppState { db: db_pool.clone(), settings: settings.clone() }).await?;
+    }
+
+    // Load the translated error messages served to clients via Accept-Language
+    let error_catalog = std::sync::Arc::new(ErrorCatalog::load(&settings.error_messages)?);
+
     // Create application state
     let app_state = AppState {
         db: db_pool,
@@ -92,39 +142,24 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let port = settings.server.port;
     let addr = SocketAddr::from((host.parse::<std::net::IpAddr>()?, port));
 
-    // Create application router
-    let app = Router::new()
-        .route("/health", get(health_check_endpoint))
-        .route("/env", get(show_environment))
-        .route("/api/upload", post(handlers::upload::upload_file_compat))
-        // Admin routes
-        .route("/api/save-data", post(handlers::admin::save_data))
-        .route("/api/process-its", post(handlers::admin::process_its))
-        .route("/api/process-app-details", post(handlers::admin::process_app_details))
-        .route("/api/process-system-info", post(handlers::admin::process_system_info))
-        .route("/api/process-libraries", post(handlers::admin::process_libraries))
-        .route("/api/process-gpu", post(handlers::admin::process_gpu))
-        .route("/api/update-gpu-brands", post(handlers::admin::update_gpu_brands))
-        .route("/api/update-gpu-laptop-info", post(handlers::admin::update_gpu_laptop_info))
-        .route("/api/process-run-details", post(handlers::admin::process_run_details))
-        .route("/api/app-details-analysis", get(handlers::admin::app_details_analysis))
-        .route("/api/fix-app-names", post(handlers::admin::fix_app_names))
-        .route("/api/update-run-more-details-with-modelmapid", post(handlers::admin::update_run_more_details_with_modelmapid))
-        .with_state(app_state);
+    // Scheduled pull of new rows from the upstream benchmark data, when enabled
+    spawn_upstream_sync(app_state.clone());
+
+    // Database, pool and request rate samples for capacity planning, when enabled
+    spawn_health_sampling(app_state.db.clone(), &app_state.settings.health_history);
+
+    // Abandoned upload sessions, old backups and logs pruned to the retention limits, when enabled
+    spawn_storage_cleanup(app_state.db.clone(), &app_state.settings);
+
+    // Panics are logged with a backtrace here and answered by the router's catch-panic layer
+    install_panic_hook();
+    let app = create_router(app_state, error_catalog);
     info!("Server starting on {}", addr);
 
     // Start server
     let listener = tokio::net::TcpListener::bind(addr).await?;
-    axum::serve(listener, app).await?;
+    // Peer addresses identify clients for rate limiting
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>()).await?;
 
     Ok(())
 }
-
-async fn health_check_endpoint() -> &'static str {
-    "OK"
-}
-
-async fn show_environment() -> String {
-    let rust_env = std::env::var("RUST_ENV").unwrap_or_else(|_| "development".to_string());
-    format!("Current RUST_ENV: {}", rust_env)
-}
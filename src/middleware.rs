@@ -1,7 +1,19 @@
+pub mod allowed_methods;
+pub mod catch_panic;
+pub mod compression;
+pub mod content_negotiation;
 pub mod cors;
+pub mod error_localization;
 pub mod logging;
+pub mod maintenance_mode;
+pub mod pipeline_tracking;
+pub mod rate_limit;
+pub mod request_metrics;
+pub mod request_transaction;
+pub mod response_cache;
 pub mod security_headers;
 pub mod size_limit;
+pub mod tenant;
 pub mod timeout;
 pub mod validation;
 
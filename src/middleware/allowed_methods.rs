@@ -0,0 +1,85 @@
+use axum::{
+    body::Body,
+    extract::{Request, State},
+    http::{header, HeaderValue, Method, StatusCode, Uri},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Router,
+};
+use tower::ServiceExt;
+
+/// Answer OPTIONS on every route and list the route's methods in the Allow header of 405 responses
+///
+/// `routes` are the bare route groups, which answer a method a route does not
+/// take with 405 and the route's methods in Allow (GET routes also take HEAD).
+/// An OPTIONS request that is not a CORS preflight goes straight to them, since
+/// the CORS layer would answer it as one, and gets that Allow header on a 204.
+/// Preflights go on to the CORS layer.
+pub async fn answer_options(State(routes): State<Router>, request: Request, next: Next) -> Response {
+    let is_options = request.method() == Method::OPTIONS;
+    if is_options && !request.headers().contains_key(header::ACCESS_CONTROL_REQUEST_METHOD) {
+        let response = call(routes, request).await;
+        if response.status() != StatusCode::METHOD_NOT_ALLOWED {
+            return response;
+        }
+
+        let mut answer = StatusCode::NO_CONTENT.into_response();
+        answer.headers_mut().insert(header::ALLOW, with_options(response.headers().get(header::ALLOW)));
+        return answer;
+    }
+
+    let uri = request.uri().clone();
+    let mut response = next.run(request).await;
+    if response.status() != StatusCode::METHOD_NOT_ALLOWED {
+        return response;
+    }
+
+    // The layers around the routes drop the router's Allow header, so it is taken from the bare routes
+    let allow = route_allow_header(routes, uri).await;
+    response.headers_mut().insert(header::ALLOW, with_options(allow.as_ref()));
+
+    response
+}
+
+/// The Allow header the bare routes answer an OPTIONS request to `uri` with
+async fn route_allow_header(routes: Router, uri: Uri) -> Option<HeaderValue> {
+    let mut request = Request::new(Body::empty());
+    *request.method_mut() = Method::OPTIONS;
+    *request.uri_mut() = uri;
+
+    call(routes, request).await.headers().get(header::ALLOW).cloned()
+}
+
+async fn call(routes: Router, request: Request) -> Response {
+    match routes.oneshot(request).await {
+        Ok(response) => response,
+        Err(infallible) => match infallible {},
+    }
+}
+
+/// The Allow header value with OPTIONS added
+fn with_options(allow: Option<&HeaderValue>) -> HeaderValue {
+    let methods = allow.and_then(|value| value.to_str().ok()).unwrap_or_default();
+    if methods.split(',').any(|method| method.trim() == "OPTIONS") {
+        return HeaderValue::from_str(methods).unwrap_or(HeaderValue::from_static("OPTIONS"));
+    }
+
+    let methods = if methods.trim().is_empty() {
+        "OPTIONS".to_string()
+    } else {
+        format!("{},OPTIONS", methods)
+    };
+    HeaderValue::from_str(&methods).unwrap_or(HeaderValue::from_static("OPTIONS"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_options() {
+        assert_eq!(with_options(Some(&HeaderValue::from_static("GET,HEAD"))), "GET,HEAD,OPTIONS");
+        assert_eq!(with_options(Some(&HeaderValue::from_static("POST,OPTIONS"))), "POST,OPTIONS");
+        assert_eq!(with_options(None), "OPTIONS");
+    }
+}
@@ -0,0 +1,70 @@
+use std::{any::Any, backtrace::Backtrace, cell::RefCell};
+
+use axum::{
+    http::{HeaderName, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde_json::json;
+use tower_http::catch_panic::CatchPanicLayer;
+use tracing::error;
+use uuid::Uuid;
+
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+thread_local! {
+    // Set by the panic hook so the response reports the same id as the logged backtrace
+    static PANIC_REQUEST_ID: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Log every panic with a full backtrace and a request id for the error response
+///
+/// The backtrace has to be captured here, at the panic site; by the time the
+/// catch-panic layer sees the panic the stack has already unwound.
+pub fn install_panic_hook() {
+    std::panic::set_hook(Box::new(|info| {
+        let request_id = Uuid::new_v4().to_string();
+        error!(
+            request_id = %request_id,
+            "Panic: {}\n{}",
+            info,
+            Backtrace::force_capture()
+        );
+        PANIC_REQUEST_ID.with(|id| *id.borrow_mut() = Some(request_id));
+    }));
+}
+
+/// Turn a handler panic into the standard JSON error envelope
+pub fn panic_response(payload: Box<dyn Any + Send + 'static>) -> Response {
+    let request_id = PANIC_REQUEST_ID
+        .with(|id| id.borrow_mut().take())
+        .unwrap_or_else(|| Uuid::new_v4().to_string());
+
+    let detail = payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic payload".to_string());
+    error!(request_id = %request_id, "Handler panicked: {}", detail);
+
+    let status = StatusCode::INTERNAL_SERVER_ERROR;
+    let body = json!({
+        "error": {
+            "code": "INTERNAL_ERROR",
+            "message": "An unexpected error occurred",
+            "status": status.as_u16(),
+            "request_id": request_id
+        }
+    });
+
+    let mut response = (status, Json(body)).into_response();
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        response.headers_mut().insert(HeaderName::from_static(REQUEST_ID_HEADER), value);
+    }
+    response
+}
+
+/// Layer that answers panicking handlers with `panic_response` instead of dropping the connection
+pub fn catch_panic_layer() -> CatchPanicLayer<fn(Box<dyn Any + Send + 'static>) -> Response> {
+    CatchPanicLayer::custom(panic_response as fn(Box<dyn Any + Send + 'static>) -> Response)
+}
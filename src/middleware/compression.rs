@@ -0,0 +1,23 @@
+use tower_http::compression::{
+    predicate::{NotForContentType, Predicate, SizeAbove},
+    CompressionLayer,
+};
+
+use crate::config::settings::CompressionConfig;
+
+/// Compress responses with the configured encodings the client accepts
+///
+/// Responses of a known size below `min_size_bytes` are left alone, and so
+/// are images, event streams and gRPC, which gain nothing from it.
+pub fn compression_layer(config: &CompressionConfig) -> CompressionLayer<impl Predicate + use<>> {
+    let offers = |encoding: &str| config.encodings.iter().any(|offered| offered == encoding);
+    let predicate = SizeAbove::new(config.min_size_bytes)
+        .and(NotForContentType::IMAGES)
+        .and(NotForContentType::SSE)
+        .and(NotForContentType::GRPC);
+
+    CompressionLayer::new()
+        .gzip(offers("gzip"))
+        .br(offers("br"))
+        .compress_when(predicate)
+}
@@ -0,0 +1,203 @@
+use axum::{
+    body::{to_bytes, Body},
+    extract::Request,
+    http::{header, HeaderValue, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use serde_json::Value;
+use tracing::error;
+
+/// Response formats that read endpoints can be serialized to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResponseFormat {
+    Json,
+    Csv,
+    MsgPack,
+}
+
+impl ResponseFormat {
+    fn from_media_type(media_type: &str) -> Option<Self> {
+        match media_type {
+            "application/json" | "application/*" | "*/*" => Some(Self::Json),
+            "text/csv" | "text/*" => Some(Self::Csv),
+            "application/msgpack" | "application/x-msgpack" | "application/vnd.msgpack" => Some(Self::MsgPack),
+            _ => None,
+        }
+    }
+
+    fn content_type(self) -> &'static str {
+        match self {
+            Self::Json => "application/json",
+            Self::Csv => "text/csv; charset=utf-8",
+            Self::MsgPack => "application/msgpack",
+        }
+    }
+}
+
+/// Pick the supported format with the highest quality value from an Accept header
+///
+/// Falls back to JSON when the header is missing or names no supported type.
+pub fn negotiate_format(accept: Option<&str>) -> ResponseFormat {
+    let Some(accept) = accept else {
+        return ResponseFormat::Json;
+    };
+
+    let mut best: Option<(ResponseFormat, f32)> = None;
+    for entry in accept.split(',') {
+        let mut params = entry.split(';');
+        let media_type = params.next().unwrap_or_default().trim().to_lowercase();
+        let quality = params
+            .filter_map(|param| param.trim().strip_prefix("q="))
+            .find_map(|q| q.parse::<f32>().ok())
+            .unwrap_or(1.0);
+
+        if let Some(format) = ResponseFormat::from_media_type(&media_type)
+            && quality > 0.0
+            && best.is_none_or(|(_, best_quality)| quality > best_quality)
+        {
+            best = Some((format, quality));
+        }
+    }
+
+    best.map(|(format, _)| format).unwrap_or(ResponseFormat::Json)
+}
+
+/// Serve JSON handler output as CSV or MessagePack when the client asks for it
+///
+/// Only successful GET responses with a JSON body are converted; everything else
+/// passes through untouched.
+pub async fn negotiate_content_type(request: Request, next: Next) -> Response {
+    let is_read = matches!(*request.method(), Method::GET | Method::HEAD);
+    let format = negotiate_format(
+        request
+            .headers()
+            .get(header::ACCEPT)
+            .and_then(|value| value.to_str().ok()),
+    );
+
+    let mut response = next.run(request).await;
+    response.headers_mut().append(header::VARY, HeaderValue::from_static("accept"));
+
+    let is_json = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/json"));
+
+    if !is_read || format == ResponseFormat::Json || !is_json || !response.status().is_success() {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let value: Value = match to_bytes(body, usize::MAX).await {
+        Ok(bytes) => match serde_json::from_slice(&bytes) {
+            Ok(value) => value,
+            Err(_) => return Response::from_parts(parts, Body::from(bytes)),
+        },
+        Err(e) => {
+            error!("Failed to buffer response body: {}", e);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let encoded = match format {
+        ResponseFormat::Csv => to_csv(&value),
+        ResponseFormat::MsgPack => rmp_serde::to_vec_named(&value).map_err(|e| e.to_string()),
+        ResponseFormat::Json => unreachable!("JSON responses are returned unchanged"),
+    };
+
+    match encoded {
+        Ok(bytes) => {
+            parts.headers.insert(header::CONTENT_TYPE, HeaderValue::from_static(format.content_type()));
+            parts.headers.remove(header::CONTENT_LENGTH);
+            Response::from_parts(parts, Body::from(bytes))
+        }
+        Err(e) => {
+            error!("Failed to encode response as {:?}: {}", format, e);
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+/// Flatten a JSON response into CSV
+///
+/// List responses are written one row per `data` item; any other object becomes
+/// a single row. Columns follow the key order of the JSON objects (alphabetical)
+/// and nested values are written as JSON text.
+pub fn to_csv(value: &Value) -> Result<Vec<u8>, String> {
+    let rows: Vec<&Value> = match value.get("data").unwrap_or(value) {
+        Value::Array(items) => items.iter().collect(),
+        other => vec![other],
+    };
+
+    let mut columns: Vec<String> = Vec::new();
+    for row in &rows {
+        if let Value::Object(fields) = row {
+            for key in fields.keys() {
+                if !columns.contains(key) {
+                    columns.push(key.clone());
+                }
+            }
+        }
+    }
+    if columns.is_empty() && !rows.is_empty() {
+        columns.push("value".to_string());
+    }
+
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(&columns).map_err(|e| e.to_string())?;
+
+    for row in rows {
+        let record: Vec<String> = match row {
+            Value::Object(fields) => columns
+                .iter()
+                .map(|column| csv_cell(fields.get(column)))
+                .collect(),
+            other => vec![csv_cell(Some(other))],
+        };
+        writer.write_record(&record).map_err(|e| e.to_string())?;
+    }
+
+    writer.into_inner().map_err(|e| e.to_string())
+}
+
+fn csv_cell(value: Option<&Value>) -> String {
+    match value {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::String(text)) => text.clone(),
+        Some(other) => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_negotiate_format() {
+        assert_eq!(negotiate_format(None), ResponseFormat::Json);
+        assert_eq!(negotiate_format(Some("text/csv")), ResponseFormat::Csv);
+        assert_eq!(negotiate_format(Some("application/msgpack")), ResponseFormat::MsgPack);
+        assert_eq!(negotiate_format(Some("text/csv;q=0.5, application/json")), ResponseFormat::Json);
+        assert_eq!(negotiate_format(Some("image/png")), ResponseFormat::Json);
+    }
+
+    #[test]
+    fn test_to_csv_list_response() {
+        let value = json!({
+            "success": true,
+            "data": [
+                { "device": "RTX 4090", "avg_its": 30.5, "user": null },
+                { "device": "RTX, 3060", "avg_its": 10.0, "tags": ["a"] }
+            ]
+        });
+
+        let csv = String::from_utf8(to_csv(&value).unwrap()).unwrap();
+        assert_eq!(
+            csv,
+            "avg_its,device,user,tags\n30.5,RTX 4090,,\n10.0,\"RTX, 3060\",,\"[\"\"a\"\"]\"\n"
+        );
+    }
+}
@@ -1 +1,24 @@
-// CORS middleware stub. Uses tower_http::cors::CorsLayer in apply_middleware. 
\ No newline at end of file
+use axum::http::{HeaderValue, Method};
+use tower_http::cors::{AllowOrigin, Any, CorsLayer};
+use tracing::warn;
+
+/// CORS for the origins in `server.cors_origins`; "*" allows any origin
+///
+/// Preflight requests are answered by the layer itself, so they succeed on
+/// every route whatever methods the route takes.
+pub fn cors_layer(origins: &[String]) -> CorsLayer {
+    let allow_origin = if origins.iter().any(|origin| origin == "*") {
+        AllowOrigin::any()
+    } else {
+        AllowOrigin::list(origins.iter().filter_map(|origin| {
+            HeaderValue::from_str(origin)
+                .inspect_err(|_| warn!("Ignoring invalid CORS origin '{}'", origin))
+                .ok()
+        }))
+    };
+
+    CorsLayer::new()
+        .allow_origin(allow_origin)
+        .allow_methods([Method::GET, Method::HEAD, Method::POST, Method::PUT, Method::DELETE, Method::OPTIONS])
+        .allow_headers(Any)
+}
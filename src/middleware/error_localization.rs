@@ -0,0 +1,72 @@
+use std::sync::Arc;
+
+use axum::{
+    body::{to_bytes, Body},
+    extract::{Request, State},
+    http::{header, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use serde_json::{json, Value};
+use tracing::error;
+
+use crate::error::i18n::ErrorCatalog;
+
+/// Add a translated `localized_message` to JSON error responses
+///
+/// The locale comes from the Accept-Language header. The error `code` and the
+/// original `message` are left untouched so clients can keep matching on them.
+pub async fn localize_errors(
+    State(catalog): State<Arc<ErrorCatalog>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let locale = catalog
+        .negotiate_locale(
+            request
+                .headers()
+                .get(header::ACCEPT_LANGUAGE)
+                .and_then(|value| value.to_str().ok()),
+        )
+        .to_string();
+
+    let mut response = next.run(request).await;
+    response.headers_mut().append(header::VARY, HeaderValue::from_static("accept-language"));
+
+    let is_json = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/json"));
+
+    if response.status().is_success() || !is_json {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let bytes = match to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            error!("Failed to buffer error response body: {}", e);
+            return Response::from_parts(parts, Body::empty());
+        }
+    };
+
+    let mut value: Value = match serde_json::from_slice(&bytes) {
+        Ok(value) => value,
+        Err(_) => return Response::from_parts(parts, Body::from(bytes)),
+    };
+    let Some(message) = value["error"]["code"]
+        .as_str()
+        .and_then(|code| catalog.message(&locale, code))
+    else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+
+    value["error"]["localized_message"] = json!(message);
+    value["error"]["locale"] = json!(locale);
+    parts.headers.insert(header::CONTENT_LANGUAGE, HeaderValue::from_str(&locale).unwrap_or(HeaderValue::from_static("en")));
+    parts.headers.remove(header::CONTENT_LENGTH);
+
+    Response::from_parts(parts, Body::from(value.to_string()))
+}
@@ -1 +1,207 @@
-// Logging middleware stub. Uses tower_http::trace::TraceLayer in apply_middleware. 
\ No newline at end of file
+// Request/response logging. Access logs come from tower_http::trace::TraceLayer in
+// apply_middleware; this adds opt-in debug logging with body samples.
+use std::{
+    future::poll_fn,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Instant,
+};
+
+use axum::{
+    body::{Body, Bytes, HttpBody},
+    extract::{Request, State},
+    http::{header, HeaderMap},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use hyper::body::{Frame, SizeHint};
+use tracing::{info, warn};
+
+use crate::config::settings::RequestLoggingConfig;
+use crate::error::types::AppError;
+
+const REDACTED: &str = "[REDACTED]";
+
+/// Log method, path, headers, a truncated JSON body, response status and latency
+///
+/// Only JSON request bodies are sampled, and only their first `max_body_bytes`
+/// are held in memory: the rest streams on to the handler, so the route's body
+/// size limit still applies. Uploads and other bodies are streamed through
+/// untouched. Headers listed in `redacted_headers` are masked.
+pub async fn log_requests(
+    State(config): State<Arc<RequestLoggingConfig>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let started = Instant::now();
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
+    let headers = format_headers(request.headers(), &config.redacted_headers);
+
+    let is_json = request
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/json"));
+
+    let request = if is_json {
+        let (parts, body) = request.into_parts();
+        let body = match SampledBody::read(body, config.max_body_bytes).await {
+            Ok(body) => body,
+            Err(e) => {
+                warn!("{} {} failed to read request body: {}", method, path, e);
+                return AppError::bad_request(format!("Failed to read request body: {}", e)).into_response();
+            }
+        };
+        let sample = truncate_body(&body.sample, config.max_body_bytes, body.complete);
+        info!("--> {} {} headers=[{}] body={}", method, path, headers, sample);
+        Request::from_parts(parts, Body::new(body))
+    } else {
+        info!("--> {} {} headers=[{}]", method, path, headers);
+        request
+    };
+
+    let response = next.run(request).await;
+    info!(
+        "<-- {} {} status={} latency={}ms",
+        method,
+        path,
+        response.status().as_u16(),
+        started.elapsed().as_millis()
+    );
+
+    response
+}
+
+/// Render headers as `name: value` pairs, masking the sensitive ones
+pub fn format_headers(headers: &HeaderMap, redacted_headers: &[String]) -> String {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let value = if redacted_headers.iter().any(|redacted| redacted.eq_ignore_ascii_case(name.as_str())) {
+                REDACTED
+            } else {
+                value.to_str().unwrap_or("<binary>")
+            };
+            format!("{}: {}", name, value)
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// A request body whose first bytes were read for the log line, then the unread rest
+struct SampledBody {
+    sample: Bytes,
+    /// Whether `sample` holds the whole body
+    complete: bool,
+    trailers: Option<Frame<Bytes>>,
+    rest: Body,
+}
+
+impl SampledBody {
+    /// Read frames until more than `max_bytes` are in hand or the body ends
+    async fn read(mut body: Body, max_bytes: usize) -> Result<Self, axum::Error> {
+        let mut sample = Vec::new();
+        let mut trailers = None;
+        let mut complete = false;
+        while sample.len() <= max_bytes {
+            let Some(frame) = poll_fn(|cx| Pin::new(&mut body).poll_frame(cx)).await.transpose()? else {
+                complete = true;
+                break;
+            };
+            match frame.into_data() {
+                Ok(data) => sample.extend_from_slice(&data),
+                // Trailers end the body
+                Err(frame) => {
+                    trailers = Some(frame);
+                    complete = true;
+                    break;
+                }
+            }
+        }
+
+        Ok(Self {
+            sample: Bytes::from(sample),
+            complete,
+            trailers,
+            rest: body,
+        })
+    }
+}
+
+impl HttpBody for SampledBody {
+    type Data = Bytes;
+    type Error = axum::Error;
+
+    fn poll_frame(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Result<Frame<Bytes>, axum::Error>>> {
+        if !self.sample.is_empty() {
+            return Poll::Ready(Some(Ok(Frame::data(std::mem::take(&mut self.sample)))));
+        }
+        if let Some(trailers) = self.trailers.take() {
+            return Poll::Ready(Some(Ok(trailers)));
+        }
+        Pin::new(&mut self.rest).poll_frame(cx)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.sample.is_empty() && self.trailers.is_none() && self.rest.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        let mut hint = self.rest.size_hint();
+        let sampled = self.sample.len() as u64;
+        hint.set_lower(hint.lower() + sampled);
+        if let Some(upper) = hint.upper() {
+            hint.set_upper(upper + sampled);
+        }
+        hint
+    }
+}
+
+/// The body as text, cut to at most `max_bytes` on a character boundary
+///
+/// `complete` says whether `bytes` is the whole body or only the part read for the sample.
+pub fn truncate_body(bytes: &[u8], max_bytes: usize, complete: bool) -> String {
+    let text = String::from_utf8_lossy(bytes);
+    if text.len() <= max_bytes {
+        return text.into_owned();
+    }
+
+    let mut end = max_bytes;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    if complete {
+        format!("{}... ({} bytes total)", &text[..end], bytes.len())
+    } else {
+        format!("{}... (more than {} bytes)", &text[..end], bytes.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    #[test]
+    fn test_format_headers_redacts_sensitive_values() {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::AUTHORIZATION, HeaderValue::from_static("Bearer secret"));
+        headers.insert(header::CONTENT_TYPE, HeaderValue::from_static("application/json"));
+
+        let formatted = format_headers(&headers, &["Authorization".to_string()]);
+        assert!(formatted.contains("authorization: [REDACTED]"));
+        assert!(formatted.contains("content-type: application/json"));
+        assert!(!formatted.contains("secret"));
+    }
+
+    #[test]
+    fn test_truncate_body() {
+        assert_eq!(truncate_body(br#"{"a":1}"#, 100, true), r#"{"a":1}"#);
+        assert_eq!(truncate_body(b"abcdef", 3, true), "abc... (6 bytes total)");
+        assert_eq!(truncate_body(b"abcdef", 3, false), "abc... (more than 6 bytes)");
+        // Never splits a multi-byte character
+        assert_eq!(truncate_body("ééé".as_bytes(), 3, true), "é... (6 bytes total)");
+    }
+}
@@ -0,0 +1,94 @@
+use axum::{
+    extract::{Request, State},
+    http::Method,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use tracing::{error, info};
+
+use sqlx::SqlitePool;
+
+use crate::{error::types::AppError, repositories::dataset_meta_repository::DatasetMetaRepository, AppState};
+
+/// The switch itself, which must stay reachable to turn maintenance off again
+pub const MAINTENANCE_MODE_PATH: &str = "/api/admin/maintenance-mode";
+
+/// POST endpoints that only compute or read, so they stay available during maintenance
+const READ_ONLY_POSTS: &[&str] = &["/api/estimate"];
+
+/// Whether a request may change stored data
+fn is_mutating(method: &Method, path: &str) -> bool {
+    if matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS) || path == MAINTENANCE_MODE_PATH {
+        return false;
+    }
+    if *method == Method::POST {
+        // Share links are signed tokens; issuing one writes nothing
+        let is_share_link = path.starts_with("/api/runs/") && path.ends_with("/share");
+        return !(is_share_link || READ_ONLY_POSTS.contains(&path));
+    }
+    true
+}
+
+/// Refuse requests that would change data with 503 while maintenance mode is on
+///
+/// The flag lives in the dataset metadata so every instance sees the same state
+/// and it survives restarts. Reads are never blocked; if the flag cannot be read
+/// the request is let through rather than taking the service down with it.
+pub async fn reject_writes_during_maintenance(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if !is_mutating(request.method(), request.uri().path()) {
+        return next.run(request).await;
+    }
+
+    match DatasetMetaRepository::new(state.db.clone()).maintenance_mode().await {
+        Ok(true) => {
+            info!("Refused {} {} during maintenance", request.method(), request.uri().path());
+            maintenance_error().into_response()
+        }
+        Ok(false) => next.run(request).await,
+        Err(e) => {
+            error!("Failed to read maintenance mode: {}", e);
+            next.run(request).await
+        }
+    }
+}
+
+/// Fail with 503 while maintenance mode is on, for work started outside a request
+///
+/// Scheduled jobs call this before each run, as the middleware only sees HTTP
+/// requests. Unlike the middleware, a flag that cannot be read fails the job.
+pub async fn ensure_not_in_maintenance(pool: &SqlitePool) -> Result<(), AppError> {
+    let maintenance_mode = DatasetMetaRepository::new(pool.clone()).maintenance_mode().await.map_err(|e| {
+        error!("Failed to read maintenance mode: {}", e);
+        AppError::Database(e)
+    })?;
+
+    if maintenance_mode {
+        return Err(maintenance_error());
+    }
+
+    Ok(())
+}
+
+fn maintenance_error() -> AppError {
+    AppError::Maintenance("Changes are disabled while maintenance is in progress".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_mutating() {
+        assert!(!is_mutating(&Method::GET, "/api/leaderboard"));
+        assert!(!is_mutating(&Method::POST, "/api/estimate"));
+        assert!(!is_mutating(&Method::POST, "/api/runs/7/share"));
+        assert!(!is_mutating(&Method::POST, MAINTENANCE_MODE_PATH));
+        assert!(is_mutating(&Method::POST, "/api/process-gpu"));
+        assert!(is_mutating(&Method::PUT, "/api/model-map/3"));
+        assert!(is_mutating(&Method::DELETE, "/api/runs/7/tags/fast"));
+    }
+}
@@ -0,0 +1,77 @@
+use axum::{
+    extract::{Query, Request, State},
+    middleware::Next,
+    response::Response,
+};
+use std::time::Instant;
+
+use chrono::Utc;
+use tracing::warn;
+
+use crate::{
+    handlers::common::DryRunQuery,
+    models::dataset_meta::{
+        META_LAST_PIPELINE_DURATION_MS, META_LAST_PIPELINE_RUN_AT, META_LAST_PIPELINE_STAGE_TIMINGS,
+        META_LAST_PIPELINE_STEP,
+    },
+    repositories::{dataset_meta_repository::DatasetMetaRepository, dataset_version_repository::DatasetVersionRepository},
+    services::{reference_revisions::capture_reference_revisions, stage_timing::collect_stage_timings},
+    AppState,
+};
+
+/// Record the last successful pipeline step, when it ran and how long its stages took
+///
+/// The step name is the request path without its `/api/` prefix, e.g. `process-its`.
+/// Each step also gets a dataset version pinning the revisions of the reference
+/// data and processing rules in effect when it finished.
+/// Dry runs (`?dry_run=true`) are not recorded. Failing to record the step is
+/// logged and never fails the request itself.
+pub async fn track_pipeline_run(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    // A dry run writes nothing, so it is not a pipeline run either
+    let dry_run = Query::<DryRunQuery>::try_from_uri(request.uri()).is_ok_and(|Query(query)| query.dry_run);
+    if dry_run {
+        return next.run(request).await;
+    }
+
+    let path = request.uri().path();
+    let step = path.strip_prefix("/api/").unwrap_or(path).to_string();
+
+    let started = Instant::now();
+    let (response, stage_timings) = collect_stage_timings(next.run(request)).await;
+    if !response.status().is_success() {
+        return response;
+    }
+    let duration_ms = (started.elapsed().as_secs_f64() * 1000.0).to_string();
+
+    let now = Utc::now().to_rfc3339();
+    let dataset_meta_repository = DatasetMetaRepository::new(state.db.clone());
+    if let Err(e) = dataset_meta_repository.set(META_LAST_PIPELINE_STEP, Some(&step), &now).await {
+        warn!("Failed to record pipeline step {}: {}", step, e);
+    }
+    if let Err(e) = dataset_meta_repository.set(META_LAST_PIPELINE_RUN_AT, Some(&now), &now).await {
+        warn!("Failed to record pipeline run time: {}", e);
+    }
+    if let Err(e) = dataset_meta_repository.set(META_LAST_PIPELINE_DURATION_MS, Some(&duration_ms), &now).await {
+        warn!("Failed to record pipeline duration: {}", e);
+    }
+    match serde_json::to_string(&stage_timings) {
+        Ok(timings) => {
+            if let Err(e) = dataset_meta_repository.set(META_LAST_PIPELINE_STAGE_TIMINGS, Some(&timings), &now).await {
+                warn!("Failed to record pipeline stage timings: {}", e);
+            }
+        }
+        Err(e) => warn!("Failed to serialize pipeline stage timings: {}", e),
+    }
+
+    // Pin the reference data the step ran against, so later runs can be compared with it
+    match capture_reference_revisions(&state.db, &state.settings.scoring).await {
+        Ok(revisions) => {
+            if let Err(e) = DatasetVersionRepository::new(state.db.clone()).create(&step, &revisions).await {
+                warn!("Failed to record dataset version for {}: {}", step, e);
+            }
+        }
+        Err(e) => warn!("Failed to capture reference data revisions for {}: {}", step, e),
+    }
+
+    response
+}
@@ -0,0 +1,165 @@
+use std::{
+    collections::HashMap,
+    net::{IpAddr, SocketAddr},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+use crate::error::types::AppError;
+
+/// Clients tracked before expired windows are swept from the map
+const SWEEP_THRESHOLD: usize = 1024;
+
+/// Clients tracked at most; once full, clients not already tracked are refused until windows expire
+const MAX_TRACKED_CLIENTS: usize = 65_536;
+
+/// Fixed-window request limiter keyed by client
+pub struct RateLimiter {
+    limit: u32,
+    window: Duration,
+    max_clients: usize,
+    trusted_proxies: Vec<IpAddr>,
+    clients: Mutex<HashMap<String, (Instant, u32)>>,
+}
+
+impl RateLimiter {
+    pub fn new(limit: u32, window: Duration) -> Self {
+        Self {
+            limit,
+            window,
+            max_clients: MAX_TRACKED_CLIENTS,
+            trusted_proxies: Vec::new(),
+            clients: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Peers whose `X-Forwarded-For` header is believed; requests from anyone else are keyed by peer address
+    pub fn with_trusted_proxies(mut self, trusted_proxies: Vec<IpAddr>) -> Self {
+        self.trusted_proxies = trusted_proxies;
+        self
+    }
+
+    pub fn per_minute(limit: u32) -> Self {
+        Self::new(limit, Duration::from_secs(60))
+    }
+
+    /// Count a request from `client`, returning how long to wait when it is over the limit
+    pub fn check(&self, client: &str) -> Result<(), Duration> {
+        self.check_at(client, Instant::now())
+    }
+
+    fn check_at(&self, client: &str, now: Instant) -> Result<(), Duration> {
+        let mut clients = self.clients.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if clients.len() >= SWEEP_THRESHOLD.min(self.max_clients) {
+            clients.retain(|_, (started, _)| now.duration_since(*started) < self.window);
+        }
+        if clients.len() >= self.max_clients && !clients.contains_key(client) {
+            // Every tracked window is still open, so the earliest one to close frees a slot
+            let oldest = clients.values().map(|(started, _)| *started).min().unwrap_or(now);
+            return Err(self.window.saturating_sub(now.duration_since(oldest)));
+        }
+
+        let (started, count) = clients.entry(client.to_string()).or_insert((now, 0));
+        if now.duration_since(*started) >= self.window {
+            *started = now;
+            *count = 0;
+        }
+
+        if *count >= self.limit {
+            return Err(self.window - now.duration_since(*started));
+        }
+        *count += 1;
+        Ok(())
+    }
+}
+
+/// Reject clients that exceed the limiter's budget with 429 and a Retry-After header
+///
+/// Clients are identified by their peer address. When the peer is a trusted
+/// proxy, the nearest `X-Forwarded-For` address that is not itself a trusted
+/// proxy is used instead; the header is ignored from anyone else, since a
+/// client can put any address in it.
+pub async fn rate_limit(State(limiter): State<Arc<RateLimiter>>, request: Request, next: Next) -> Response {
+    let client = client_key(&request, &limiter.trusted_proxies);
+    if let Err(retry_after) = limiter.check(&client) {
+        let retry_after_secs = retry_after.as_secs().max(1);
+        return AppError::too_many_requests(
+            format!("Rate limit exceeded, retry in {} seconds", retry_after_secs),
+            retry_after_secs,
+        )
+        .into_response();
+    }
+
+    next.run(request).await
+}
+
+fn client_key(request: &Request, trusted_proxies: &[IpAddr]) -> String {
+    let Some(ConnectInfo(peer)) = request.extensions().get::<ConnectInfo<SocketAddr>>() else {
+        return "unknown".to_string();
+    };
+    let peer = peer.ip();
+    if !trusted_proxies.contains(&peer) {
+        return peer.to_string();
+    }
+
+    // Each proxy appends the address it received from, so walk back from the
+    // right past our own proxies to the first address we did not add ourselves
+    let forwarded = request
+        .headers()
+        .get_all("x-forwarded-for")
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .filter_map(|address| address.trim().parse::<IpAddr>().ok())
+        .collect::<Vec<_>>();
+
+    forwarded
+        .into_iter()
+        .rev()
+        .find(|address| !trusted_proxies.contains(address))
+        .unwrap_or(peer)
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limit_resets_after_window() {
+        let limiter = RateLimiter::new(2, Duration::from_secs(60));
+        let start = Instant::now();
+
+        assert!(limiter.check_at("a", start).is_ok());
+        assert!(limiter.check_at("a", start).is_ok());
+        assert_eq!(
+            limiter.check_at("a", start + Duration::from_secs(20)),
+            Err(Duration::from_secs(40))
+        );
+        assert!(limiter.check_at("b", start).is_ok());
+        assert!(limiter.check_at("a", start + Duration::from_secs(60)).is_ok());
+    }
+
+    #[test]
+    fn test_new_clients_are_refused_once_the_map_is_full() {
+        let limiter = RateLimiter {
+            max_clients: 2,
+            ..RateLimiter::new(5, Duration::from_secs(60))
+        };
+        let start = Instant::now();
+
+        assert!(limiter.check_at("a", start).is_ok());
+        assert!(limiter.check_at("b", start + Duration::from_secs(10)).is_ok());
+        assert_eq!(
+            limiter.check_at("c", start + Duration::from_secs(20)),
+            Err(Duration::from_secs(40))
+        );
+        assert!(limiter.check_at("a", start + Duration::from_secs(20)).is_ok());
+    }
+}
@@ -0,0 +1,34 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use axum::{extract::Request, middleware::Next, response::Response};
+use serde::Serialize;
+
+static REQUESTS: AtomicU64 = AtomicU64::new(0);
+static SERVER_ERRORS: AtomicU64 = AtomicU64::new(0);
+
+/// Counts of answered requests since startup
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct RequestMetrics {
+    pub requests: u64,
+    /// Requests answered with a 5xx status
+    pub server_errors: u64,
+}
+
+pub fn request_metrics() -> RequestMetrics {
+    RequestMetrics {
+        requests: REQUESTS.load(Ordering::Relaxed),
+        server_errors: SERVER_ERRORS.load(Ordering::Relaxed),
+    }
+}
+
+/// Count every request and the ones failing with a server error, for the health samples
+pub async fn count_requests(request: Request, next: Next) -> Response {
+    let response = next.run(request).await;
+
+    REQUESTS.fetch_add(1, Ordering::Relaxed);
+    if response.status().is_server_error() {
+        SERVER_ERRORS.fetch_add(1, Ordering::Relaxed);
+    }
+
+    response
+}
@@ -0,0 +1,129 @@
+use std::{
+    ops::{Deref, DerefMut},
+    sync::Arc,
+};
+
+use axum::{
+    extract::{FromRequestParts, Request, State},
+    http::{request::Parts, Method},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use sqlx::{Sqlite, SqlitePool, Transaction};
+use tokio::sync::{Mutex, MutexGuard};
+use tracing::{error, warn};
+
+use crate::{error::types::AppError, AppState};
+
+type SharedTransaction = Arc<Mutex<Option<Transaction<'static, Sqlite>>>>;
+
+/// The transaction of one request, begun by the first `RequestTransaction::begin`
+#[derive(Clone)]
+struct TransactionSlot {
+    pool: SqlitePool,
+    tx: SharedTransaction,
+}
+
+/// Give every mutating request (POST, PUT, PATCH, DELETE) a database transaction
+///
+/// Handlers opt in through the `RequestTransaction` extractor; the transaction
+/// only begins when a handler asks for it, so handlers writing through the pool
+/// are unaffected. It is committed when the handler responds with a success
+/// status and rolled back otherwise, so a handler failing half-way leaves no
+/// partial writes behind. Install it innermost, below any middleware that
+/// writes to the database after the handler, since SQLite allows one writer.
+pub async fn transaction_per_request(State(state): State<AppState>, mut request: Request, next: Next) -> Response {
+    if !matches!(*request.method(), Method::POST | Method::PUT | Method::PATCH | Method::DELETE) {
+        return next.run(request).await;
+    }
+
+    let slot = TransactionSlot {
+        pool: state.db.clone(),
+        tx: SharedTransaction::default(),
+    };
+    request.extensions_mut().insert(slot.clone());
+
+    let response = next.run(request).await;
+
+    let Some(tx) = slot.tx.lock().await.take() else {
+        return response;
+    };
+    if response.status().is_success() {
+        if let Err(e) = tx.commit().await {
+            error!("Failed to commit request transaction: {}", e);
+            return AppError::Database(e).into_response();
+        }
+    } else if let Err(e) = tx.rollback().await {
+        warn!("Failed to roll back request transaction: {}", e);
+    }
+
+    response
+}
+
+/// Extractor for the request's database transaction
+///
+/// Requires the `transaction_per_request` middleware; the handler must not
+/// commit itself, and only rolls back through `RequestTransactionGuard::rollback`.
+pub struct RequestTransaction {
+    slot: TransactionSlot,
+}
+
+impl RequestTransaction {
+    /// Lock the request transaction, beginning it on first use
+    pub async fn begin(&self) -> Result<RequestTransactionGuard<'_>, AppError> {
+        let mut tx = self.slot.tx.lock().await;
+        if tx.is_none() {
+            *tx = Some(self.slot.pool.begin().await.map_err(|e| {
+                error!("Failed to begin request transaction: {}", e);
+                AppError::Database(e)
+            })?);
+        }
+
+        Ok(RequestTransactionGuard(tx))
+    }
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for RequestTransaction {
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let slot = parts.extensions.get::<TransactionSlot>().cloned().ok_or_else(|| {
+            AppError::internal("Request transaction requested outside the transaction_per_request middleware")
+        })?;
+
+        Ok(Self { slot })
+    }
+}
+
+/// Locked access to a begun request transaction
+pub struct RequestTransactionGuard<'a>(MutexGuard<'a, Option<Transaction<'static, Sqlite>>>);
+
+impl RequestTransactionGuard<'_> {
+    /// Roll back everything written so far, leaving nothing for the middleware to commit
+    ///
+    /// For dry runs, which make their writes to measure them and then discard them.
+    pub async fn rollback(mut self) -> Result<(), AppError> {
+        if let Some(tx) = self.0.take() {
+            tx.rollback().await.map_err(|e| {
+                error!("Failed to roll back request transaction: {}", e);
+                AppError::Database(e)
+            })?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Deref for RequestTransactionGuard<'_> {
+    type Target = Transaction<'static, Sqlite>;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.as_ref().expect("request transaction is begun before the guard is handed out")
+    }
+}
+
+impl DerefMut for RequestTransactionGuard<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.0.as_mut().expect("request transaction is begun before the guard is handed out")
+    }
+}
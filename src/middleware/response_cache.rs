@@ -0,0 +1,84 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use axum::{
+    body::{to_bytes, Body, Bytes},
+    extract::{Request, State},
+    http::{HeaderMap, Method, StatusCode},
+    middleware::Next,
+    response::Response,
+};
+use tracing::error;
+
+use crate::middleware::tenant::Tenant;
+
+struct CachedResponse {
+    stored_at: Instant,
+    headers: HeaderMap,
+    body: Bytes,
+}
+
+/// In-memory cache of successful GET responses, keyed by tenant, path and query
+pub struct ResponseCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<String, CachedResponse>>,
+}
+
+impl ResponseCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn get(&self, key: &str) -> Option<Response> {
+        let entries = self.entries.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let cached = entries.get(key).filter(|cached| cached.stored_at.elapsed() < self.ttl)?;
+
+        let mut response = Response::new(Body::from(cached.body.clone()));
+        *response.headers_mut() = cached.headers.clone();
+        Some(response)
+    }
+
+    fn insert(&self, key: String, headers: HeaderMap, body: Bytes) {
+        let mut entries = self.entries.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        entries.retain(|_, cached| cached.stored_at.elapsed() < self.ttl);
+        entries.insert(key, CachedResponse { stored_at: Instant::now(), headers, body });
+    }
+}
+
+/// Serve repeated GET requests from the cache until the entry expires
+///
+/// Only 200 responses are stored; a zero TTL turns caching off.
+pub async fn cache_responses(State(cache): State<Arc<ResponseCache>>, request: Request, next: Next) -> Response {
+    if request.method() != Method::GET || cache.ttl.is_zero() {
+        return next.run(request).await;
+    }
+
+    let tenant = request.extensions().get::<Tenant>().cloned().unwrap_or_default();
+    let key = format!("{}\n{}", tenant.id(), request.uri());
+    if let Some(response) = cache.get(&key) {
+        return response;
+    }
+
+    let response = next.run(request).await;
+    if response.status() != StatusCode::OK {
+        return response;
+    }
+
+    let (parts, body) = response.into_parts();
+    let bytes = match to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            error!("Failed to buffer response body for caching: {}", e);
+            return Response::from_parts(parts, Body::empty());
+        }
+    };
+    cache.insert(key, parts.headers.clone(), bytes.clone());
+
+    Response::from_parts(parts, Body::from(bytes))
+}
@@ -0,0 +1,66 @@
+use axum::{
+    extract::{FromRequestParts, Request, State},
+    http::request::Parts,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use tracing::info;
+
+use crate::{error::types::AppError, AppState};
+
+/// Header carrying the API key a tenant is resolved from
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// Tenant of runs ingested without one, and of every run while tenancy is off
+pub const DEFAULT_TENANT: &str = "default";
+
+/// The tenant a request files its runs under, resolved by `resolve_tenant`
+///
+/// Extracting it outside the middleware, e.g. in handlers mounted directly,
+/// gives the default tenant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tenant(pub String);
+
+impl Tenant {
+    pub fn id(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Default for Tenant {
+    fn default() -> Self {
+        Self(DEFAULT_TENANT.to_string())
+    }
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for Tenant {
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(parts.extensions.get::<Tenant>().cloned().unwrap_or_default())
+    }
+}
+
+/// Resolve the request's tenant from its `X-API-Key` header, per `settings.tenancy.api_keys`
+///
+/// Requests without a key, and every request while tenancy is off, belong to
+/// the default tenant. A key that is not configured is refused with 401 rather
+/// than falling back, so a mistyped key never writes into another dataset.
+pub async fn resolve_tenant(State(state): State<AppState>, mut request: Request, next: Next) -> Response {
+    let tenancy = &state.settings.tenancy;
+    let api_key = request.headers().get(API_KEY_HEADER).map(|key| key.to_str().unwrap_or_default().to_string());
+
+    let tenant = match api_key {
+        Some(api_key) if tenancy.enabled => match tenancy.api_keys.get(&api_key) {
+            Some(tenant) => Tenant(tenant.clone()),
+            None => {
+                info!("Refused {} {} with an unknown API key", request.method(), request.uri().path());
+                return AppError::unauthorized("Unknown API key").into_response();
+            }
+        },
+        _ => Tenant::default(),
+    };
+    request.extensions_mut().insert(tenant);
+
+    next.run(request).await
+}
@@ -8,3 +8,23 @@ pub mod run_more_details;
 pub mod model_map;
 pub mod gpu_map;
 pub mod gpu_base;
+pub mod run_score;
+pub mod gpu_price;
+pub mod app_release;
+pub mod upload_session;
+pub mod dataset_meta;
+pub mod gpu_spec;
+pub mod estimate;
+pub mod tag;
+pub mod audit_log;
+pub mod moderation;
+pub mod change_set;
+pub mod quality_report;
+pub mod dataset_version;
+pub mod pipeline_event;
+pub mod gpu_dedup;
+pub mod aggregates;
+pub mod gpu_percentile;
+pub mod health_sample;
+pub mod digest;
+pub mod reparse;
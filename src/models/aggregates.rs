@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// Number of runs sharing one value of the grouped column
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow)]
+pub struct GroupedCount {
+    /// `None` groups the runs without a value
+    pub value: Option<String>,
+    pub run_count: i64,
+}
+
+/// Average of a metric over the runs sharing one value of the grouped column
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow)]
+pub struct GroupedAverage {
+    /// `None` groups the runs without a value
+    pub value: Option<String>,
+    /// Runs in the group that have the metric
+    pub run_count: i64,
+    pub average: Option<f64>,
+}
+
+/// Values listed per facet unless `limit` says otherwise
+pub const DEFAULT_FACET_LIMIT: usize = 20;
+pub const MAX_FACET_LIMIT: usize = 200;
+
+/// Distinct values of one column with their run counts, for a faceted filter
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Facet {
+    pub field: String,
+    pub distinct_values: usize,
+    /// The most common values, largest first; `None` counts the runs without a value
+    pub values: Vec<GroupedCount>,
+    /// Values beyond the listed ones, and the runs having them
+    pub other_values: usize,
+    pub other_run_count: i64,
+}
+
+impl Facet {
+    /// List the first `limit` of `counts`, ordered largest first, totalling the rest as other
+    pub fn from_counts(field: &str, mut counts: Vec<GroupedCount>, limit: usize) -> Self {
+        let distinct_values = counts.len();
+        let other = counts.split_off(limit.min(counts.len()));
+
+        Facet {
+            field: field.to_string(),
+            distinct_values,
+            values: counts,
+            other_values: other.len(),
+            other_run_count: other.iter().map(|count| count.run_count).sum(),
+        }
+    }
+}
@@ -9,6 +9,10 @@ pub struct AppDetails {
     pub updated: Option<String>,
     pub hash: Option<String>,
     pub url: Option<String>,
+    /// Version of the info parser that produced the row, `None` for rows from before versions were recorded
+    pub parser_version: Option<i64>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -19,3 +23,30 @@ pub struct CreateAppDetails {
     pub hash: String,
     pub url: String,
 }
+
+/// The URL of an AppDetails row, as read for canonicalization
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AppDetailsUrl {
+    pub id: i64,
+    pub url: Option<String>,
+}
+
+/// Average ITS of the runs credited to one app project, forks included
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AppProjectStats {
+    /// Upstream `owner/repo`; `None` for runs without a recognizable URL
+    pub project: Option<String>,
+    pub run_count: i64,
+    /// Runs on a fork counted toward the project through the fork map
+    pub fork_run_count: i64,
+    pub avg_its: Option<f64>,
+}
+
+/// A commit of a GitHub repository an app hash points at
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, FromRow)]
+pub struct AppCommit {
+    pub owner: String,
+    pub repo: String,
+    /// Full or abbreviated hash, lowercase
+    pub hash: String,
+}
@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AppRelease {
+    pub id: Option<i64>,
+    pub run_id: Option<i64>,
+    pub release_channel: Option<String>,
+    pub release_month: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateAppRelease {
+    pub run_id: i64,
+    pub release_channel: Option<String>,
+    pub release_month: Option<String>,
+}
+
+/// Average ITS for one app / release channel / release month bucket
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AppVersionStats {
+    pub app_name: Option<String>,
+    pub release_channel: Option<String>,
+    pub release_month: Option<String>,
+    pub run_count: i64,
+    pub avg_its: Option<f64>,
+}
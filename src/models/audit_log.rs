@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// Audit action of a GPU device dedup
+pub const AUDIT_ACTION_GPU_DEDUP: &str = "gpu-dedup";
+
+/// Audit action of a user data deletion; the entry names the user by pseudonym only
+pub const AUDIT_ACTION_FORGET_USER: &str = "forget-user";
+
+/// Audit action of filing one submitter spelling under another
+pub const AUDIT_ACTION_MERGE_USERS: &str = "merge-users";
+
+/// Audit action of switching maintenance mode on or off
+pub const AUDIT_ACTION_MAINTENANCE_MODE: &str = "maintenance-mode";
+
+/// Audit action of approving or rejecting a run held for moderation
+pub const AUDIT_ACTION_MODERATION_REVIEW: &str = "moderation-review";
+
+/// One admin data fix, with a JSON description of what changed
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AuditLogEntry {
+    pub id: i64,
+    pub action: String,
+    pub details: Option<String>,
+    pub created_at: Option<String>,
+}
@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// Change set of one fix-app-names run; items hold AppDetails ids and their app_name before and after
+pub const CHANGE_SET_ACTION_FIX_APP_NAMES: &str = "fix-app-names";
+
+/// One application of a data fix, revertible until `reverted_at` is set
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ChangeSet {
+    pub id: i64,
+    pub action: String,
+    pub created_at: Option<String>,
+    pub reverted_at: Option<String>,
+}
+
+/// One row a data fix updated, with the value it replaced
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ChangeSetItem {
+    pub id: i64,
+    pub change_set_id: i64,
+    pub row_id: i64,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+}
+
+/// Outcome of reverting a change set
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangeSetRevert {
+    pub change_set_id: i64,
+    pub action: String,
+    /// Rows put back to their previous value
+    pub restored: u64,
+    /// Rows left alone because they changed again after the fix
+    pub skipped: u64,
+}
@@ -0,0 +1,66 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+use crate::services::stage_timing::StageTiming;
+
+pub const META_DATASET_VERSION: &str = "dataset_version";
+pub const META_SOURCE_FILE_NAME: &str = "source_file_name";
+pub const META_SOURCE_FORMAT: &str = "source_format";
+pub const META_SOURCE_FILE_SIZE: &str = "source_file_size";
+pub const META_SOURCE_UPLOADED_AT: &str = "source_uploaded_at";
+pub const META_LAST_PIPELINE_STEP: &str = "last_pipeline_step";
+pub const META_LAST_PIPELINE_RUN_AT: &str = "last_pipeline_run_at";
+pub const META_LAST_PIPELINE_DURATION_MS: &str = "last_pipeline_duration_ms";
+pub const META_LAST_PIPELINE_STAGE_TIMINGS: &str = "last_pipeline_stage_timings";
+pub const META_MAINTENANCE_MODE: &str = "maintenance_mode";
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DatasetMetaEntry {
+    pub key: String,
+    pub value: Option<String>,
+    pub updated_at: String,
+}
+
+/// The upload the current dataset was built from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SourceUpload {
+    pub file_name: String,
+    pub format: Option<String>,
+    pub file_size: Option<i64>,
+    pub uploaded_at: Option<String>,
+}
+
+/// Freshness information about the dataset
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatasetMetadata {
+    pub dataset_version: i64,
+    pub table_counts: BTreeMap<String, i64>,
+    pub last_pipeline_step: Option<String>,
+    pub last_pipeline_run_at: Option<String>,
+    pub last_pipeline_duration_ms: Option<f64>,
+    /// Stages timed by the last pipeline step, empty if it does not time its stages
+    pub last_pipeline_stage_timings: Vec<StageTiming>,
+    pub source_upload: Option<SourceUpload>,
+}
+
+/// Outcome of waiting for the dataset version to pass a known one
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatasetUpdate {
+    pub dataset_version: i64,
+    /// False when the wait timed out without a new version
+    pub updated: bool,
+}
+
+/// Whether mutating endpoints are refused while migrations or backfills run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MaintenanceMode {
+    pub enabled: bool,
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SetMaintenanceModeRequest {
+    pub enabled: bool,
+}
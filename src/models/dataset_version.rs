@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use validator::Validate;
+
+pub const REFERENCE_MODEL_MAP: &str = "model_map";
+pub const REFERENCE_GPU_MAP: &str = "gpu_map";
+pub const REFERENCE_GPU_BASE: &str = "gpu_base";
+pub const REFERENCE_RULES: &str = "rules";
+
+/// Dataset versions listed when no limit is given
+pub const DEFAULT_DATASET_VERSION_LIMIT: i64 = 50;
+
+/// Revision hashes of the reference data and processing rules a pipeline step ran against
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReferenceRevisions {
+    pub model_map_hash: String,
+    pub gpu_map_hash: String,
+    pub gpu_base_hash: String,
+    /// Parser versions and scoring settings
+    pub rules_hash: String,
+}
+
+impl ReferenceRevisions {
+    /// Reference sets whose revision differs from `previous`
+    pub fn changed_since(&self, previous: &ReferenceRevisions) -> Vec<&'static str> {
+        [
+            (REFERENCE_MODEL_MAP, &self.model_map_hash, &previous.model_map_hash),
+            (REFERENCE_GPU_MAP, &self.gpu_map_hash, &previous.gpu_map_hash),
+            (REFERENCE_GPU_BASE, &self.gpu_base_hash, &previous.gpu_base_hash),
+            (REFERENCE_RULES, &self.rules_hash, &previous.rules_hash),
+        ]
+        .into_iter()
+        .filter(|(_, current, previous)| current != previous)
+        .map(|(reference, _, _)| reference)
+        .collect()
+    }
+}
+
+/// One successful pipeline step with the reference revisions it used
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DatasetVersion {
+    pub id: i64,
+    pub pipeline_step: String,
+    pub model_map_hash: String,
+    pub gpu_map_hash: String,
+    pub gpu_base_hash: String,
+    pub rules_hash: String,
+    pub created_at: Option<String>,
+}
+
+impl DatasetVersion {
+    pub fn revisions(&self) -> ReferenceRevisions {
+        ReferenceRevisions {
+            model_map_hash: self.model_map_hash.clone(),
+            gpu_map_hash: self.gpu_map_hash.clone(),
+            gpu_base_hash: self.gpu_base_hash.clone(),
+            rules_hash: self.rules_hash.clone(),
+        }
+    }
+}
+
+/// A dataset version as listed, with what changed since the version before it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatasetVersionSummary {
+    #[serde(flatten)]
+    pub version: DatasetVersion,
+    /// Reference sets that changed since the previous version; empty for the first
+    pub changed_since_previous: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct DatasetVersionQuery {
+    #[validate(range(min = 1))]
+    pub limit: Option<i64>,
+}
@@ -0,0 +1,90 @@
+use chrono::Duration;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use validator::Validate;
+
+/// GPUs, improvements and submitters listed per digest section
+pub const DIGEST_SECTION_LIMIT: i64 = 5;
+
+/// Time a digest looks back over, ending now
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DigestPeriod {
+    Day,
+    #[default]
+    Week,
+    Month,
+}
+
+impl DigestPeriod {
+    pub fn duration(self) -> Duration {
+        match self {
+            DigestPeriod::Day => Duration::days(1),
+            DigestPeriod::Week => Duration::weeks(1),
+            DigestPeriod::Month => Duration::days(30),
+        }
+    }
+
+    /// "this week" and the like, as written in the summary
+    pub fn label(self) -> &'static str {
+        match self {
+            DigestPeriod::Day => "today",
+            DigestPeriod::Week => "this week",
+            DigestPeriod::Month => "this month",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Validate)]
+pub struct DigestQuery {
+    /// Defaults to a week
+    pub period: Option<DigestPeriod>,
+    pub anonymize: Option<bool>,
+}
+
+/// A GPU whose first run was ingested during the period
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow)]
+pub struct NewGpu {
+    pub device: String,
+    pub runs: i64,
+}
+
+/// A GPU whose best avg_its during the period beats its best before
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow)]
+pub struct ItsImprovement {
+    pub device: String,
+    pub previous_best_its: f64,
+    pub best_its: f64,
+}
+
+impl ItsImprovement {
+    pub fn improvement_percent(&self) -> f64 {
+        (self.best_its / self.previous_best_its - 1.0) * 100.0
+    }
+}
+
+/// A submitter and the runs they had ingested during the period
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow)]
+pub struct UserActivity {
+    pub user: String,
+    pub runs: i64,
+}
+
+/// What changed in the dataset over a period, ready to post to a forum
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Digest {
+    pub period: DigestPeriod,
+    /// RFC 3339 start of the period; it ends at `until`
+    pub since: String,
+    pub until: String,
+    /// Runs ingested during the period
+    pub new_runs: i64,
+    /// With the most runs first
+    pub new_gpus: Vec<NewGpu>,
+    /// Largest relative improvement first
+    pub its_improvements: Vec<ItsImprovement>,
+    /// Most runs first
+    pub active_users: Vec<UserActivity>,
+    /// The sections above as plain text
+    pub summary: String,
+}
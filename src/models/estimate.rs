@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// The hardware and software a user wants an ITS estimate for
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EstimateRequest {
+    /// Reported device name (e.g. "NVIDIA GeForce RTX 3060") or base GPU name
+    pub gpu: String,
+    pub torch: Option<String>,
+    /// Model name or base model (e.g. "sdxl")
+    pub model: Option<String>,
+}
+
+/// How closely the runs behind an estimate match the requested GPU, closest first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchLevel {
+    ExactGpu,
+    GpuBase,
+    Architecture,
+}
+
+/// One run's avg_its with the context used to narrow an estimate
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ItsSample {
+    pub avg_its: f64,
+    pub torch: Option<String>,
+    pub base_model: Option<String>,
+}
+
+/// Expected ITS range derived from the closest comparable runs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ItsEstimate {
+    pub match_level: MatchLevel,
+    /// The device, base GPU or architecture the samples were matched on
+    pub matched_on: String,
+    /// Whether the samples were narrowed to the requested torch version
+    pub torch_matched: bool,
+    /// Base model the samples were restricted to, if one was requested
+    pub base_model: Option<String>,
+    pub sample_count: usize,
+    pub min_its: f64,
+    pub p25_its: f64,
+    pub median_its: f64,
+    pub p75_its: f64,
+    pub max_its: f64,
+}
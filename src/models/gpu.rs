@@ -10,6 +10,16 @@ pub struct Gpu {
     pub gpu_chip: Option<String>,
     pub brand: Option<String>,
     pub is_laptop: Option<bool>,
+    /// Intel/AMD integrated GPU, `None` until the update-gpu-integrated-info step has run
+    pub is_integrated: Option<bool>,
+    /// Ran on cloud hardware, `None` until the update-gpu-cloud-info step has run
+    pub is_cloud: Option<bool>,
+    /// Position of the GPU within its run; 0 for the first (or only) GPU
+    pub gpu_index: i64,
+    /// Version of the device_info parser that produced the row, `None` for rows from before versions were recorded
+    pub parser_version: Option<i64>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -6,10 +6,39 @@ pub struct GpuBase {
     pub id: Option<i64>,
     pub name: String,
     pub brand: Option<String>,
+    pub architecture: Option<String>,
+    pub generation: Option<String>,
+    pub version: i64,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CreateGpuBase {
     pub name: String,
     pub brand: Option<String>,
+    pub architecture: Option<String>,
+    pub generation: Option<String>,
+}
+
+/// Replacement values for a GPUBase row, checked against the version the client last read
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateGpuBase {
+    pub name: String,
+    pub brand: Option<String>,
+    pub architecture: Option<String>,
+    pub generation: Option<String>,
+    pub version: i64,
+}
+
+/// Average ITS for one brand / architecture / generation bucket
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ArchitectureStats {
+    pub brand: Option<String>,
+    pub architecture: Option<String>,
+    pub generation: Option<String>,
+    pub first_launch_year: Option<i64>,
+    pub gpu_count: i64,
+    pub run_count: i64,
+    pub avg_its: Option<f64>,
 }
@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// Number of GPU rows reporting a device string
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow)]
+pub struct GpuDeviceCount {
+    pub device: String,
+    pub row_count: i64,
+}
+
+/// GPU device strings that normalize to the same name
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GpuDedupCluster {
+    /// The spelling every variant is rewritten to
+    pub canonical: String,
+    /// Device strings that differ from the canonical spelling
+    pub variants: Vec<GpuDeviceCount>,
+    pub rows_to_update: i64,
+}
+
+/// Outcome of rewriting near-duplicate GPU device strings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GpuDedupResult {
+    pub clusters: usize,
+    pub gpu_rows_updated: u64,
+    pub gpu_map_rows_updated: u64,
+    /// Audit log entry of the rewrite; absent when there was nothing to merge
+    pub audit_id: Option<i64>,
+}
@@ -6,6 +6,9 @@ pub struct GpuMap {
     pub id: Option<i64>,
     pub gpu_name: Option<String>,
     pub base_gpu_id: Option<i64>,
+    pub version: i64,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -13,3 +16,22 @@ pub struct CreateGpuMap {
     pub gpu_name: String,
     pub base_gpu_id: i64,
 }
+
+/// Replacement values for a GPUMap row, checked against the version the client last read
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateGpuMap {
+    pub gpu_name: String,
+    pub base_gpu_id: i64,
+    pub version: i64,
+}
+
+/// A GPUMap row as exported for curation, with the number of GPU rows reporting that device
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct GpuMapExportRow {
+    pub gpu_name: Option<String>,
+    pub base_gpu_id: Option<i64>,
+    pub base_gpu_name: Option<String>,
+    pub usage_count: i64,
+    pub id: i64,
+    pub version: i64,
+}
@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// One single-GPU run's avg_its with the device it ran on
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DeviceItsSample {
+    pub device: String,
+    pub avg_its: f64,
+}
+
+/// Distribution of avg_its over the single-GPU runs on one device
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow)]
+pub struct GpuPercentiles {
+    pub gpu: String,
+    pub run_count: i64,
+    pub min_its: f64,
+    pub p10_its: f64,
+    pub p25_its: f64,
+    pub median_its: f64,
+    pub p75_its: f64,
+    pub p90_its: f64,
+    pub max_its: f64,
+    pub computed_at: String,
+}
+
+/// Per-GPU percentiles, from the `gpu_percentiles` cache when it has been refreshed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GpuPercentilesReport {
+    /// Whether the percentiles came from the cache rather than the runs
+    pub cached: bool,
+    /// When the cache was last refreshed; `None` when computed from the runs
+    pub computed_at: Option<String>,
+    /// Whether data was uploaded or the pipeline ran since the cache was refreshed
+    pub stale: bool,
+    pub gpus: Vec<GpuPercentiles>,
+}
@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct GpuPrice {
+    pub id: Option<i64>,
+    pub base_gpu_id: Option<i64>,
+    pub price_usd: Option<f64>,
+    pub source: Option<String>,
+    pub recorded_at: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateGpuPrice {
+    pub base_gpu_id: i64,
+    pub price_usd: f64,
+    pub source: Option<String>,
+    pub recorded_at: Option<String>,
+}
+
+/// Average ITS per dollar for a base GPU model
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ItsPerDollar {
+    pub base_gpu_id: i64,
+    pub gpu_name: String,
+    pub brand: Option<String>,
+    pub price_usd: f64,
+    pub avg_its: Option<f64>,
+    pub sample_count: i64,
+    pub its_per_dollar: Option<f64>,
+}
@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct GpuSpec {
+    pub id: Option<i64>,
+    pub base_gpu_id: Option<i64>,
+    pub vram_gb: Option<f64>,
+    pub launch_year: Option<i64>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+/// A known GPU from the bundled reference catalog
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GpuCatalogEntry {
+    pub name: String,
+    pub brand: String,
+    pub vram_gb: f64,
+    pub architecture: String,
+    pub generation: String,
+    pub launch_year: i64,
+}
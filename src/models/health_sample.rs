@@ -0,0 +1,45 @@
+use std::collections::BTreeMap;
+
+use chrono::DateTime;
+use serde::{Deserialize, Serialize};
+use validator::{Validate, ValidationError};
+
+/// Health samples listed when no limit is given, a day at the default interval
+pub const DEFAULT_HEALTH_HISTORY_LIMIT: i64 = 288;
+pub const MAX_HEALTH_HISTORY_LIMIT: i64 = 10_000;
+
+/// Database, connection pool and request rate figures at one point in time
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HealthSample {
+    pub id: Option<i64>,
+    /// RFC 3339
+    pub sampled_at: String,
+    /// Size of the database file, free pages included
+    pub db_size_bytes: i64,
+    /// Rows per dataset table
+    pub row_counts: BTreeMap<String, i64>,
+    /// Open pool connections, idle ones included
+    pub pool_connections: i64,
+    pub pool_idle_connections: i64,
+    pub pool_max_connections: i64,
+    /// Requests answered since the previous sample
+    pub requests: i64,
+    /// Of those, the ones answered with a 5xx status
+    pub server_errors: i64,
+    pub requests_per_minute: f64,
+}
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct HealthHistoryQuery {
+    /// Only samples taken at or after this RFC 3339 time
+    #[validate(custom(function = "rfc3339"))]
+    pub since: Option<String>,
+    #[validate(range(min = 1, max = MAX_HEALTH_HISTORY_LIMIT))]
+    pub limit: Option<i64>,
+}
+
+fn rfc3339(value: &str) -> Result<(), ValidationError> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|_| ())
+        .map_err(|_| ValidationError::new("rfc3339").with_message("must be an RFC 3339 time".into()))
+}
@@ -10,6 +10,10 @@ pub struct Libraries {
     pub xformers1: Option<String>,
     pub diffusers: Option<String>,
     pub transformers: Option<String>,
+    /// Version of the model_info parser that produced the row, `None` for rows from before versions were recorded
+    pub parser_version: Option<i64>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -6,6 +6,9 @@ pub struct ModelMap {
     pub id: Option<i64>,
     pub model_name: Option<String>,
     pub base_model: Option<String>,
+    pub version: i64,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -13,3 +16,77 @@ pub struct CreateModelMap {
     pub model_name: String,
     pub base_model: String,
 }
+
+/// Replacement values for a ModelMap row, checked against the version the client last read
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateModelMap {
+    pub model_name: String,
+    pub base_model: String,
+    pub version: i64,
+}
+
+/// A ModelMap row as exported for curation, with the number of runs mapped to it
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ModelMapExportRow {
+    pub model_name: Option<String>,
+    pub base_model: Option<String>,
+    pub usage_count: i64,
+    pub id: i64,
+    pub version: i64,
+}
+
+/// A known checkpoint hash pointing at a ModelMap entry
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ModelMapHash {
+    pub id: Option<i64>,
+    pub model_map_id: Option<i64>,
+    pub hash: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+/// A well-known checkpoint from the bundled model catalog
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelCatalogEntry {
+    pub model_name: String,
+    pub base_model: String,
+    pub hashes: Vec<String>,
+}
+
+/// What importing a CSV row would do to ModelMap
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModelMapImportAction {
+    New,
+    Updated,
+    Unchanged,
+}
+
+/// A valid CSV row and how it compares to the current ModelMap
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelMapImportChange {
+    pub line: u64,
+    pub action: ModelMapImportAction,
+    pub model_name: String,
+    pub base_model: String,
+    pub previous_base_model: Option<String>,
+}
+
+/// A CSV row rejected by validation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelMapImportError {
+    pub line: u64,
+    pub message: String,
+}
+
+/// Dry-run diff (or applied result) of a ModelMap CSV import
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelMapImportReport {
+    pub applied: bool,
+    pub total_rows: usize,
+    pub new_rows: usize,
+    pub updated_rows: usize,
+    pub unchanged_rows: usize,
+    pub changes: Vec<ModelMapImportChange>,
+    pub errors: Vec<ModelMapImportError>,
+}
@@ -0,0 +1,63 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use validator::Validate;
+
+/// Waiting for review; the run is kept out of the feed and share links
+pub const MODERATION_STATUS_PENDING: &str = "pending";
+/// Published as submitted
+pub const MODERATION_STATUS_APPROVED: &str = "approved";
+/// Published with its notes cleared
+pub const MODERATION_STATUS_REJECTED: &str = "rejected";
+
+/// A run whose notes the notes filter flagged, with the notes under review
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ModerationEntry {
+    pub id: i64,
+    pub run_id: i64,
+    /// Comma-separated notes filter rules the notes broke
+    pub reasons: String,
+    pub status: String,
+    pub notes: Option<String>,
+    pub created_at: Option<String>,
+    pub reviewed_at: Option<String>,
+}
+
+/// A `MODERATION_STATUS_*` value, as taken by the moderation queue
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModerationStatus {
+    #[default]
+    Pending,
+    Approved,
+    Rejected,
+}
+
+impl ModerationStatus {
+    pub const ALL: [ModerationStatus; 3] = [ModerationStatus::Pending, ModerationStatus::Approved, ModerationStatus::Rejected];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ModerationStatus::Pending => MODERATION_STATUS_PENDING,
+            ModerationStatus::Approved => MODERATION_STATUS_APPROVED,
+            ModerationStatus::Rejected => MODERATION_STATUS_REJECTED,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct ModerationQueueQuery {
+    /// Entries in this status, pending when omitted
+    pub status: Option<ModerationStatus>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ModerationDecision {
+    Approve,
+    Reject,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReviewModerationRequest {
+    pub decision: ModerationDecision,
+}
@@ -1,12 +1,23 @@
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 
+/// vram_usage held ITS samples, as the pipeline expects
+pub const VRAM_USAGE_KIND_ITS: &str = "its";
+/// vram_usage held memory values; the row is kept out of ITS statistics
+pub const VRAM_USAGE_KIND_MEMORY: &str = "memory";
+/// vram_usage held nothing that parsed as a number
+pub const VRAM_USAGE_KIND_UNKNOWN: &str = "unknown";
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct PerformanceResult {
     pub id: Option<i64>,
     pub run_id: Option<i64>,
     pub its: Option<String>,
     pub avg_its: Option<f64>,
+    /// One of the `VRAM_USAGE_KIND_*` values, `None` for rows processed before classification
+    pub vram_usage_kind: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
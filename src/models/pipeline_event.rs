@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use validator::Validate;
+
+pub const PIPELINE_EVENT_SUCCEEDED: &str = "succeeded";
+pub const PIPELINE_EVENT_FAILED: &str = "failed";
+/// A stage that succeeded, undone when a later stage of its run failed
+pub const PIPELINE_EVENT_ROLLED_BACK: &str = "rolled_back";
+
+/// Pipeline events listed when no limit is given
+pub const DEFAULT_PIPELINE_HISTORY_LIMIT: i64 = 100;
+
+/// One stage execution by the pipeline orchestrator
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct PipelineEvent {
+    pub id: Option<i64>,
+    pub stage: String,
+    /// RFC 3339
+    pub started_at: String,
+    /// RFC 3339
+    pub finished_at: String,
+    /// Rows the stage read, where it reports them
+    pub rows_in: Option<i64>,
+    /// Rows the stage inserted or updated
+    pub rows_out: Option<i64>,
+    /// `PIPELINE_EVENT_SUCCEEDED`, `PIPELINE_EVENT_FAILED` or `PIPELINE_EVENT_ROLLED_BACK`
+    pub status: String,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct PipelineHistoryQuery {
+    /// Only executions of this stage
+    pub stage: Option<String>,
+    #[validate(range(min = 1))]
+    pub limit: Option<i64>,
+}
@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+/// Runs whose vram_usage held memory values instead of ITS samples
+pub const QUALITY_CHECK_VRAM_USAGE_MEMORY: &str = "vram_usage_memory_values";
+/// Runs whose vram_usage held nothing that parsed as a number
+pub const QUALITY_CHECK_VRAM_USAGE_UNPARSEABLE: &str = "vram_usage_unparseable";
+
+/// Run ids listed per quality report entry; `affected_rows` counts them all
+pub const QUALITY_REPORT_SAMPLE_SIZE: usize = 20;
+
+/// One data quality problem found in the processed tables
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QualityReportEntry {
+    pub check: String,
+    pub description: String,
+    pub affected_rows: usize,
+    pub sample_run_ids: Vec<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QualityReport {
+    pub entries: Vec<QualityReportEntry>,
+}
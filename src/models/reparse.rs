@@ -0,0 +1,128 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::{
+    app_details::AppDetails, app_release::AppRelease, gpu::Gpu, libraries::Libraries,
+    performance_result::PerformanceResult, run_more_details::RunMoreDetails, system_info::SystemInfo,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReparsePreviewRequest {
+    pub run_id: i64,
+}
+
+/// The rows one pipeline step holds for a run, beside those its current parser would write
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StepPreview<T> {
+    pub current: T,
+    pub reparsed: T,
+    /// Why the step would write nothing for the run, e.g. a missing raw string
+    pub skipped: Option<String>,
+}
+
+/// What reprocessing one run with the current parsers would write, step by step
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReparsePreview {
+    pub run_id: i64,
+    /// From `vram_usage`, by the ITS step
+    pub performance: StepPreview<Option<PerformanceResult>>,
+    /// From `info`, by the app details step
+    pub app_details: StepPreview<Option<AppDetails>>,
+    /// Derived from the app details by the same step
+    pub app_release: StepPreview<Option<AppRelease>>,
+    /// From `system_info`
+    pub system_info: StepPreview<Option<SystemInfo>>,
+    /// From `model_info` and `xformers`
+    pub libraries: StepPreview<Option<Libraries>>,
+    /// From `device_info`, one row per GPU; brand and laptop flags are set by later update steps
+    pub gpus: StepPreview<Vec<Gpu>>,
+    /// Copied from the run by the run details step; the model map id is set by a later step
+    pub run_details: StepPreview<Option<RunMoreDetails>>,
+}
+
+/// A pipeline step that `POST /api/admin/reparse` can rerun on a subset of runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReparseStage {
+    Its,
+    AppDetails,
+    SystemInfo,
+    Libraries,
+    Gpu,
+}
+
+impl ReparseStage {
+    /// The raw `runs` column the step parses, matched against the request pattern
+    pub fn source_column(self) -> &'static str {
+        match self {
+            Self::Its => "vram_usage",
+            Self::AppDetails => "info",
+            Self::SystemInfo => "system_info",
+            Self::Libraries => "model_info",
+            Self::Gpu => "device_info",
+        }
+    }
+
+    /// The tables the step writes, cleared for the matched runs before they are rewritten
+    pub fn derived_tables(self) -> &'static [&'static str] {
+        match self {
+            Self::Its => &["performanceResult"],
+            Self::AppDetails => &["AppDetails", "AppRelease"],
+            Self::SystemInfo => &["SystemInfo"],
+            Self::Libraries => &["Libraries"],
+            Self::Gpu => &["GPU"],
+        }
+    }
+}
+
+/// Picks the runs to reparse by the stage's raw column; exactly one pattern is given
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReparseRequest {
+    pub stage: ReparseStage,
+    /// SQL LIKE pattern, e.g. `%Radeon(TM)%`
+    pub where_like: Option<String>,
+    /// Regular expression, unanchored, e.g. `Radeon\(TM\) RX \d{4}`
+    pub where_regex: Option<String>,
+}
+
+/// How the runs to reparse are matched on their raw string
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RawStringFilter {
+    Like(String),
+    Regex(String),
+}
+
+impl RawStringFilter {
+    /// The SQL operator applying the pattern, as in `WHERE <column> <operator> ?`
+    pub fn operator(&self) -> &'static str {
+        match self {
+            Self::Like(_) => "LIKE",
+            Self::Regex(_) => "REGEXP",
+        }
+    }
+
+    pub fn pattern(&self) -> &str {
+        match self {
+            Self::Like(pattern) | Self::Regex(pattern) => pattern,
+        }
+    }
+}
+
+/// What rerunning one step on the runs matching a pattern wrote, or would write on a dry run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReparseResult {
+    pub stage: ReparseStage,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub where_like: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub where_regex: Option<String>,
+    pub dry_run: bool,
+    pub matched_runs: usize,
+    /// Derived rows cleared for the matched runs across the stage's tables
+    pub rows_deleted: u64,
+    /// Rows written by the current parser across the same tables
+    pub rows_inserted: usize,
+    /// Matched runs the step's parser rejected, left without rows
+    pub skipped_runs: usize,
+    /// The first `DRY_RUN_SAMPLE_SIZE` matched run ids
+    pub sample_run_ids: Vec<i64>,
+}
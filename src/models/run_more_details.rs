@@ -10,6 +10,8 @@ pub struct RunMoreDetails {
     pub user: Option<String>,
     pub notes: Option<String>,
     pub model_map_id: Option<i64>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
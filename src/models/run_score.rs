@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RunScore {
+    pub id: Option<i64>,
+    pub run_id: Option<i64>,
+    pub score: Option<f64>,
+    pub normalized_its: Option<f64>,
+    pub model_factor: Option<f64>,
+    pub vram_gb: Option<f64>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateRunScore {
+    pub run_id: i64,
+    pub score: f64,
+    pub normalized_its: f64,
+    pub model_factor: f64,
+    pub vram_gb: Option<f64>,
+}
+
+/// Per-run inputs needed to compute a score
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ScoringInput {
+    pub run_id: i64,
+    pub tenant_id: String,
+    pub avg_its: Option<f64>,
+    pub base_model: Option<String>,
+    pub device: Option<String>,
+}
+
+/// A single leaderboard row joining the score with its run context
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct LeaderboardEntry {
+    pub run_id: i64,
+    pub score: Option<f64>,
+    pub avg_its: Option<f64>,
+    pub device: Option<String>,
+    pub model_name: Option<String>,
+    pub base_model: Option<String>,
+    pub user: Option<String>,
+    /// The run reported more than one GPU; `device` is the first
+    pub multi_gpu: bool,
+}
+
+/// A GPU + torch + xformers combination ranked by its average ITS
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TopConfiguration {
+    pub device: String,
+    pub torch: Option<String>,
+    pub xformers: Option<String>,
+    pub vram_gb: Option<f64>,
+    pub avg_its: f64,
+    pub max_its: f64,
+    pub sample_count: i64,
+}
+
+/// Which runs count toward the top configurations
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TopConfigurationFilter<'a> {
+    pub base_model: Option<&'a str>,
+    pub max_vram_gb: Option<f64>,
+    pub tag: Option<&'a str>,
+    pub exclude_tag: Option<&'a str>,
+    pub include_integrated: bool,
+}
@@ -1,6 +1,13 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 
+use crate::models::{
+    app_details::AppDetails, gpu::Gpu, libraries::Libraries, performance_result::PerformanceResult,
+    run_more_details::RunMoreDetails, system_info::SystemInfo,
+};
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Run {
     pub id: Option<i64>,
@@ -14,6 +21,8 @@ pub struct Run {
     pub model_name: Option<String>,
     pub user: Option<String>,
     pub notes: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -29,3 +38,224 @@ pub struct CreateRun {
     pub user: String,
     pub notes: String,
 }
+
+/// The columns of a run read by the process-its stage
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RunItsInput {
+    pub id: i64,
+    pub vram_usage: Option<String>,
+}
+
+/// The columns of a run read by the process-app-details stage
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RunAppInfo {
+    pub id: i64,
+    pub info: Option<String>,
+}
+
+/// The columns of a run read by the process-system-info stage
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RunSystemInfo {
+    pub id: i64,
+    pub system_info: Option<String>,
+}
+
+/// The columns of a run read by the process-libraries stage
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RunLibrariesInput {
+    pub id: i64,
+    pub model_info: Option<String>,
+    pub xformers: Option<String>,
+}
+
+/// The columns of a run read by the process-gpu stage
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RunDeviceInfo {
+    pub id: i64,
+    pub device_info: Option<String>,
+}
+
+/// The columns of a run read by the process-run-details stage
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RunDetailsInput {
+    pub id: i64,
+    pub timestamp: Option<String>,
+    pub model_name: Option<String>,
+    pub user: Option<String>,
+    pub notes: Option<String>,
+}
+
+/// A run joined with everything the processing pipeline derived from it
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RunDetail {
+    pub run_id: i64,
+    pub timestamp: Option<String>,
+    pub model_name: Option<String>,
+    pub user: Option<String>,
+    pub notes: Option<String>,
+    pub its: Option<String>,
+    pub avg_its: Option<f64>,
+    pub score: Option<f64>,
+    pub app_name: Option<String>,
+    pub app_updated: Option<String>,
+    pub app_hash: Option<String>,
+    pub arch: Option<String>,
+    pub cpu: Option<String>,
+    pub system: Option<String>,
+    pub release: Option<String>,
+    pub python: Option<String>,
+    pub torch: Option<String>,
+    pub xformers: Option<String>,
+    pub diffusers: Option<String>,
+    pub transformers: Option<String>,
+    pub device: Option<String>,
+    pub driver: Option<String>,
+    pub gpu_chip: Option<String>,
+    pub brand: Option<String>,
+    pub is_laptop: Option<bool>,
+    /// Number of GPUs the run reported; the GPU fields describe the first one
+    pub gpu_count: i64,
+    pub base_model: Option<String>,
+}
+
+/// A shareable, unauthenticated link to one run's detail
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShareLink {
+    pub run_id: i64,
+    pub token: String,
+    pub url: String,
+    /// RFC 3339 expiry, `None` for links that never expire
+    pub expires_at: Option<String>,
+}
+
+/// The newest runs with the headline numbers shown in the submissions feed
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RunFeedEntry {
+    pub run_id: i64,
+    pub timestamp: Option<String>,
+    pub created_at: Option<String>,
+    pub model_name: Option<String>,
+    pub user: Option<String>,
+    pub device: Option<String>,
+    pub avg_its: Option<f64>,
+}
+
+/// A run as listed by `GET /api/runs`, without its raw benchmark strings
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RunSummary {
+    pub run_id: i64,
+    pub timestamp: Option<String>,
+    pub created_at: Option<String>,
+    pub model_name: Option<String>,
+    pub user: Option<String>,
+    pub notes: Option<String>,
+}
+
+/// Child rows a run list can embed, named in its `include` parameter
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunInclude {
+    Gpu,
+    Performance,
+    SystemInfo,
+    Libraries,
+    App,
+}
+
+impl RunInclude {
+    pub const ALL: [RunInclude; 5] = [
+        RunInclude::Gpu,
+        RunInclude::Performance,
+        RunInclude::SystemInfo,
+        RunInclude::Libraries,
+        RunInclude::App,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            RunInclude::Gpu => "gpu",
+            RunInclude::Performance => "performance",
+            RunInclude::SystemInfo => "system_info",
+            RunInclude::Libraries => "libraries",
+            RunInclude::App => "app",
+        }
+    }
+}
+
+/// A listed run with the child rows it was asked to embed
+///
+/// Single-row children are `null` when included but missing for the run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunListEntry {
+    #[serde(flatten)]
+    pub run: RunSummary,
+    /// The run's GPUs by `gpu_index`, with `include=gpu`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gpus: Option<Vec<Gpu>>,
+    /// The run's newest performance result, with `include=performance`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub performance: Option<Option<PerformanceResult>>,
+    /// With `include=system_info`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_info: Option<Option<SystemInfo>>,
+    /// With `include=libraries`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub libraries: Option<Option<Libraries>>,
+    /// The run's app details, with `include=app`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub app: Option<Option<AppDetails>>,
+}
+
+/// One raw benchmark string of a run and the rows the pipeline parsed from it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParsedSource<T> {
+    pub raw: Option<String>,
+    /// Empty or `null` when the parsing step has not run or rejected the string
+    pub parsed: T,
+}
+
+/// A run's raw benchmark strings side by side with their derived rows, for diagnosing parses
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunRawSources {
+    pub run_id: i64,
+    /// Parsed by the app details step
+    pub info: ParsedSource<Option<AppDetails>>,
+    /// Parsed by the system info step
+    pub system_info: ParsedSource<Option<SystemInfo>>,
+    /// Parsed by the libraries step
+    pub model_info: ParsedSource<Option<Libraries>>,
+    /// Parsed by the GPU step, one row per GPU
+    pub device_info: ParsedSource<Vec<Gpu>>,
+    /// Parsed by the ITS step
+    pub vram_usage: ParsedSource<Option<PerformanceResult>>,
+    /// Mapped by the run details step
+    pub model_name: ParsedSource<Option<RunMoreDetails>>,
+}
+
+/// Request to file every run of one submitter spelling under another
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergeUsersRequest {
+    pub from: String,
+    pub to: String,
+}
+
+/// Outcome of merging one submitter spelling into another
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergeUsersResult {
+    pub runs_updated: u64,
+    pub run_details_updated: u64,
+    /// Dataset version after the merge, bumped so cached summaries are refreshed; absent when nothing changed
+    pub dataset_version: Option<i64>,
+    /// Audit log entry of the merge; absent when `from` had no runs
+    pub audit_id: Option<i64>,
+}
+
+/// Outcome of deleting every run of one submitter
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForgetUserResult {
+    pub runs_deleted: u64,
+    /// Rows deleted per table, `runs` included
+    pub deleted: BTreeMap<String, u64>,
+    /// Audit log entry of the deletion; absent when the user had no runs
+    pub audit_id: Option<i64>,
+}
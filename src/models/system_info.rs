@@ -10,6 +10,16 @@ pub struct SystemInfo {
     pub system: Option<String>,
     pub release: Option<String>,
     pub python: Option<String>,
+    /// Installed system memory in GB
+    pub ram_gb: Option<f64>,
+    /// Swap size in GB
+    pub swap_gb: Option<f64>,
+    /// Platform bitness, 32 or 64
+    pub os_bits: Option<i64>,
+    /// Version of the system_info parser that produced the row, `None` for rows from before versions were recorded
+    pub parser_version: Option<i64>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -21,3 +31,44 @@ pub struct CreateSystemInfo {
     pub release: String,
     pub python: String,
 }
+
+/// The raw CPU string of a SystemInfo row, as read for normalization
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct SystemInfoCpu {
+    pub id: i64,
+    pub cpu: Option<String>,
+}
+
+/// avg_its of one run with the run's normalized CPU
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct CpuItsSample {
+    pub cpu_vendor: Option<String>,
+    pub cpu_family: Option<String>,
+    pub cpu_generation: Option<i64>,
+    pub avg_its: f64,
+}
+
+/// ITS of the runs sharing a CPU vendor, family and generation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CpuGroupStats {
+    pub cpu_vendor: Option<String>,
+    pub cpu_family: Option<String>,
+    pub cpu_generation: Option<i64>,
+    pub run_count: usize,
+    pub avg_its: f64,
+    pub stddev_its: Option<f64>,
+}
+
+/// Whether the CPU measurably affects ITS on one GPU
+///
+/// The fastest and slowest CPU groups with enough runs are compared with
+/// Welch's t-test; `spread_pct` is how much faster the fastest group is.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CpuImpact {
+    pub gpu: String,
+    pub run_count: usize,
+    pub groups: Vec<CpuGroupStats>,
+    pub spread_pct: Option<f64>,
+    pub t_statistic: Option<f64>,
+    pub significant: bool,
+}
@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// Tag attached by a user through the API
+pub const TAG_SOURCE_MANUAL: &str = "manual";
+/// Tag derived from the processed data; recomputed on every refresh
+pub const TAG_SOURCE_AUTO: &str = "auto";
+
+/// Automatic tag for runs on a laptop GPU
+pub const AUTO_TAG_LAPTOP: &str = "laptop";
+/// Automatic tag for runs reporting more than one GPU
+pub const AUTO_TAG_MULTI_GPU: &str = "multi-gpu";
+/// Automatic tag for runs on cloud hardware
+pub const AUTO_TAG_CLOUD: &str = "cloud";
+
+pub const MAX_TAG_LENGTH: usize = 32;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Tag {
+    pub id: Option<i64>,
+    pub name: String,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+/// A tag attached to a run, with where it came from
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RunTag {
+    pub name: String,
+    pub source: String,
+}
+
+/// A tag and the number of runs carrying it
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TagSummary {
+    pub name: String,
+    pub run_count: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AddRunTags {
+    pub tags: Vec<String>,
+}
+
+/// Trim and lowercase a tag name, rejecting anything but letters, digits, '-' and '_'
+pub fn normalize_tag_name(name: &str) -> Option<String> {
+    let name = name.trim().to_ascii_lowercase();
+    let valid = !name.is_empty()
+        && name.len() <= MAX_TAG_LENGTH
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    valid.then_some(name)
+}
+
@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+use crate::services::ingest::schema_drift::SchemaDrift;
+
+pub const UPLOAD_STATUS_PENDING: &str = "pending";
+pub const UPLOAD_STATUS_INGESTING: &str = "ingesting";
+pub const UPLOAD_STATUS_COMPLETED: &str = "completed";
+pub const UPLOAD_STATUS_FAILED: &str = "failed";
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct UploadSession {
+    pub id: String,
+    pub file_name: String,
+    pub status: String,
+    pub total_parts: Option<i64>,
+    pub total_size: Option<i64>,
+    pub message: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+    /// Tenant that started the upload; another tenant's requests see it as not found
+    #[serde(skip)]
+    pub tenant_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateUploadSession {
+    pub file_name: String,
+    pub total_parts: Option<i64>,
+    pub total_size: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct UploadPart {
+    pub id: Option<i64>,
+    pub upload_id: String,
+    pub part_number: i64,
+    pub size: i64,
+}
+
+/// An upload session together with how much of it has been received
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UploadProgress {
+    #[serde(flatten)]
+    pub session: UploadSession,
+    pub received_parts: i64,
+    pub bytes_received: i64,
+    pub percent_complete: Option<f64>,
+}
+
+/// How an upload's keys differed from the JSON Schema of its detected format
+///
+/// Recorded for both `save-data` uploads and resumable upload sessions, keyed
+/// by the id returned when the upload was ingested.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UploadSchemaReport {
+    pub upload_id: String,
+    pub file_name: String,
+    pub format: String,
+    #[serde(flatten)]
+    pub drift: SchemaDrift,
+    pub created_at: String,
+    /// Tenant the upload was filed under
+    #[serde(skip)]
+    pub tenant_id: String,
+}
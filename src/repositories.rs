@@ -2,6 +2,9 @@ pub mod traits;
 pub mod transaction;
 pub mod connection;
 pub mod query_builder;
+pub mod aggregates;
+pub mod shadow_table;
+pub mod batch;
 
 // Repository implementations
 pub mod runs_repository;
@@ -14,6 +17,23 @@ pub mod run_more_details_repository;
 pub mod model_map_repository;
 pub mod gpu_map_repository;
 pub mod gpu_base_repository;
+pub mod run_score_repository;
+pub mod gpu_price_repository;
+pub mod app_release_repository;
+pub mod upload_session_repository;
+pub mod dataset_meta_repository;
+pub mod gpu_spec_repository;
+pub mod tag_repository;
+pub mod audit_log_repository;
+pub mod moderation_repository;
+pub mod change_set_repository;
+pub mod dataset_version_repository;
+pub mod pipeline_event_repository;
+pub mod ingested_row_repository;
+pub mod gpu_percentile_repository;
+pub mod health_sample_repository;
+pub mod digest_repository;
+pub mod commit_date_repository;
 
 // Re-export repository structs for easier access
 pub use runs_repository::RunsRepository;
@@ -26,3 +46,26 @@ pub use run_more_details_repository::RunMoreDetailsRepository;
 pub use model_map_repository::ModelMapRepository;
 pub use gpu_map_repository::GpuMapRepository;
 pub use gpu_base_repository::GpuBaseRepository;
+pub use run_score_repository::RunScoreRepository;
+pub use gpu_price_repository::GpuPriceRepository;
+pub use app_release_repository::AppReleaseRepository;
+pub use upload_session_repository::UploadSessionRepository;
+pub use dataset_meta_repository::DatasetMetaRepository;
+pub use gpu_spec_repository::GpuSpecRepository;
+pub use tag_repository::TagRepository;
+pub use audit_log_repository::AuditLogRepository;
+pub use moderation_repository::ModerationRepository;
+pub use change_set_repository::ChangeSetRepository;
+pub use dataset_version_repository::DatasetVersionRepository;
+pub use pipeline_event_repository::PipelineEventRepository;
+pub use ingested_row_repository::IngestedRowRepository;
+pub use gpu_percentile_repository::GpuPercentileRepository;
+pub use health_sample_repository::HealthSampleRepository;
+pub use digest_repository::DigestRepository;
+pub use commit_date_repository::CommitDateRepository;
+pub use aggregates::AggregatesRepository;
+
+/// Timestamp written to the created_at/updated_at audit columns
+pub fn audit_timestamp() -> String {
+    chrono::Utc::now().to_rfc3339()
+}
@@ -0,0 +1,206 @@
+use serde::{Deserialize, Serialize};
+use sqlx::{Error, SqlitePool};
+
+use crate::models::aggregates::{GroupedAverage, GroupedCount};
+use crate::repositories::connection::TimedQuery;
+
+/// Run columns that grouped statistics may group or filter by
+///
+/// Only these expressions are ever interpolated into the aggregate SQL; request
+/// input is deserialized by `name` and never reaches the query text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AggregateColumn {
+    GpuBrand,
+    GpuDevice,
+    AppName,
+    Os,
+    CpuVendor,
+    Python,
+    Torch,
+    Xformers,
+    ModelName,
+}
+
+impl AggregateColumn {
+    pub const ALL: &'static [AggregateColumn] = &[
+        AggregateColumn::GpuBrand,
+        AggregateColumn::GpuDevice,
+        AggregateColumn::AppName,
+        AggregateColumn::Os,
+        AggregateColumn::CpuVendor,
+        AggregateColumn::Python,
+        AggregateColumn::Torch,
+        AggregateColumn::Xformers,
+        AggregateColumn::ModelName,
+    ];
+
+    /// Name used in query strings
+    pub fn name(self) -> &'static str {
+        match self {
+            AggregateColumn::GpuBrand => "gpu_brand",
+            AggregateColumn::GpuDevice => "gpu_device",
+            AggregateColumn::AppName => "app_name",
+            AggregateColumn::Os => "os",
+            AggregateColumn::CpuVendor => "cpu_vendor",
+            AggregateColumn::Python => "python",
+            AggregateColumn::Torch => "torch",
+            AggregateColumn::Xformers => "xformers",
+            AggregateColumn::ModelName => "model_name",
+        }
+    }
+
+    /// Table and column the values come from, as named in facet requests
+    pub fn field(self) -> &'static str {
+        match self {
+            AggregateColumn::GpuBrand => "gpu.brand",
+            AggregateColumn::GpuDevice => "gpu.device",
+            AggregateColumn::AppName => "app_details.app_name",
+            AggregateColumn::Os => "system_info.system",
+            AggregateColumn::CpuVendor => "system_info.cpu_vendor",
+            AggregateColumn::Python => "system_info.python",
+            AggregateColumn::Torch => "libraries.torch",
+            AggregateColumn::Xformers => "libraries.xformers",
+            AggregateColumn::ModelName => "run_more_details.model_name",
+        }
+    }
+
+    pub fn from_field(field: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|column| column.field().eq_ignore_ascii_case(field))
+    }
+
+    fn sql(self) -> &'static str {
+        match self {
+            AggregateColumn::GpuBrand => "g.brand",
+            AggregateColumn::GpuDevice => "g.device",
+            AggregateColumn::AppName => "ad.app_name",
+            AggregateColumn::Os => "si.system",
+            AggregateColumn::CpuVendor => "si.cpu_vendor",
+            AggregateColumn::Python => "si.python",
+            AggregateColumn::Torch => "l.torch",
+            AggregateColumn::Xformers => "l.xformers",
+            AggregateColumn::ModelName => "rmd.model_name",
+        }
+    }
+}
+
+/// Per-run metrics that grouped statistics may average
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AggregateMetric {
+    AvgIts,
+    Score,
+}
+
+impl AggregateMetric {
+    pub const ALL: &'static [AggregateMetric] = &[AggregateMetric::AvgIts, AggregateMetric::Score];
+
+    /// Name used in query strings
+    pub fn name(self) -> &'static str {
+        match self {
+            AggregateMetric::AvgIts => "avg_its",
+            AggregateMetric::Score => "score",
+        }
+    }
+
+    fn sql(self) -> &'static str {
+        match self {
+            AggregateMetric::AvgIts => "pr.avg_its",
+            AggregateMetric::Score => "rs.score",
+        }
+    }
+}
+
+/// Restrict grouped statistics to runs whose column equals a value
+#[derive(Debug, Clone)]
+pub struct AggregateFilter {
+    pub column: AggregateColumn,
+    pub value: String,
+}
+
+/// One row per run; multi-GPU runs are represented by their first GPU
+const RUN_JOINS: &str = "
+    FROM runs r
+    LEFT JOIN performanceResult pr ON pr.run_id = r.id
+    LEFT JOIN AppDetails ad ON ad.run_id = r.id
+    LEFT JOIN SystemInfo si ON si.run_id = r.id
+    LEFT JOIN Libraries l ON l.run_id = r.id
+    LEFT JOIN GPU g ON g.run_id = r.id AND g.gpu_index = 0
+    LEFT JOIN RunMoreDetails rmd ON rmd.run_id = r.id
+    LEFT JOIN RunScore rs ON rs.run_id = r.id";
+
+/// Grouped counts and averages over runs and their processed child rows
+pub struct AggregatesRepository {
+    pool: SqlitePool,
+}
+
+impl AggregatesRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Count a tenant's runs per value of `column`, largest groups first
+    pub async fn count_grouped_by(
+        &self,
+        tenant_id: &str,
+        column: AggregateColumn,
+        filter: Option<&AggregateFilter>,
+    ) -> Result<Vec<GroupedCount>, Error> {
+        let sql = format!(
+            "SELECT CAST({column} AS TEXT) AS value, COUNT(DISTINCT r.id) AS run_count
+            {RUN_JOINS}
+            WHERE r.tenant_id = ? {filter_clause}
+            GROUP BY 1
+            ORDER BY run_count DESC, value",
+            column = column.sql(),
+            filter_clause = filter_condition(filter).map(|condition| format!("AND {condition}")).unwrap_or_default(),
+        );
+
+        let mut query = sqlx::query_as::<_, GroupedCount>(&sql).bind(tenant_id);
+        if let Some(filter) = filter {
+            query = query.bind(&filter.value);
+        }
+
+        query
+            .fetch_all(&self.pool)
+            .timed("aggregates.count_grouped_by", 1 + usize::from(filter.is_some()))
+            .await
+    }
+
+    /// Average `metric` over a tenant's runs per value of `column`, highest average first
+    ///
+    /// Runs without the metric are left out of the average and the run count.
+    pub async fn avg_grouped_by(
+        &self,
+        tenant_id: &str,
+        column: AggregateColumn,
+        metric: AggregateMetric,
+        filter: Option<&AggregateFilter>,
+    ) -> Result<Vec<GroupedAverage>, Error> {
+        let sql = format!(
+            "SELECT CAST({column} AS TEXT) AS value, COUNT(DISTINCT r.id) AS run_count, AVG({metric}) AS average
+            {RUN_JOINS}
+            WHERE r.tenant_id = ? AND {metric} IS NOT NULL {filter_clause}
+            GROUP BY 1
+            ORDER BY average DESC, value",
+            column = column.sql(),
+            metric = metric.sql(),
+            filter_clause = filter_condition(filter).map(|condition| format!("AND {condition}")).unwrap_or_default(),
+        );
+
+        let mut query = sqlx::query_as::<_, GroupedAverage>(&sql).bind(tenant_id);
+        if let Some(filter) = filter {
+            query = query.bind(&filter.value);
+        }
+
+        query
+            .fetch_all(&self.pool)
+            .timed("aggregates.avg_grouped_by", 1 + usize::from(filter.is_some()))
+            .await
+    }
+}
+
+/// SQL condition of a filter, binding its value after the tenant
+fn filter_condition(filter: Option<&AggregateFilter>) -> Option<String> {
+    filter.map(|filter| format!("CAST({} AS TEXT) = ?", filter.column.sql()))
+}
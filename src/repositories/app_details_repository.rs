@@ -1,8 +1,15 @@
+use std::collections::HashMap;
+
 use async_trait::async_trait;
-use sqlx::{Error, SqlitePool, Transaction, Sqlite};
+use sqlx::{query::Query, sqlite::SqliteArguments, Error, SqlitePool, Transaction, Sqlite};
 
-use crate::models::app_details::AppDetails;
+use crate::models::app_details::{AppDetails, AppDetailsUrl, AppProjectStats};
 use crate::repositories::traits::{Repository, TransactionRepository, BulkRepository, BulkTransactionRepository};
+use crate::repositories::audit_timestamp;
+use crate::repositories::batch::{first_by_run, json_ids};
+use crate::repositories::connection::{retry_on_busy, TimedQuery};
+use crate::repositories::shadow_table::ShadowRow;
+use crate::repositories::query_builder::Filter;
 
 pub struct AppDetailsRepository {
     pool: SqlitePool,
@@ -18,7 +25,7 @@ impl AppDetailsRepository {
         let results = sqlx::query_as!(
             AppDetails,
             r#"
-            SELECT id, run_id, app_name, updated, hash, url
+            SELECT id, run_id, app_name, updated, hash, url, parser_version, created_at, updated_at
             FROM AppDetails
             WHERE run_id = ?
             ORDER BY id DESC
@@ -26,17 +33,38 @@ impl AppDetailsRepository {
             run_id
         )
         .fetch_all(&self.pool)
+        .timed("app_details.find_by_run_id", 1)
         .await?;
 
         Ok(results)
     }
 
+    /// The newest app details row of each of the given runs, in one query
+    pub async fn find_by_run_ids(&self, run_ids: &[i64]) -> Result<HashMap<i64, AppDetails>, Error> {
+        let ids = json_ids(run_ids)?;
+        let results = sqlx::query_as!(
+            AppDetails,
+            r#"
+            SELECT id, run_id, app_name, updated, hash, url, parser_version, created_at, updated_at
+            FROM AppDetails
+            WHERE run_id IN (SELECT value FROM json_each(?))
+            ORDER BY run_id, id DESC
+            "#,
+            ids
+        )
+        .fetch_all(&self.pool)
+        .timed("app_details.find_by_run_ids", 1)
+        .await?;
+
+        Ok(first_by_run(results, |row| row.run_id))
+    }
+
     /// Find app details by app_name
     pub async fn find_by_app_name(&self, app_name: &str) -> Result<Vec<AppDetails>, Error> {
         let results = sqlx::query_as!(
             AppDetails,
             r#"
-            SELECT id, run_id, app_name, updated, hash, url
+            SELECT id, run_id, app_name, updated, hash, url, parser_version, created_at, updated_at
             FROM AppDetails
             WHERE app_name = ?
             ORDER BY id DESC
@@ -44,6 +72,7 @@ impl AppDetailsRepository {
             app_name
         )
         .fetch_all(&self.pool)
+        .timed("app_details.find_by_app_name", 1)
         .await?;
 
         Ok(results)
@@ -51,8 +80,9 @@ impl AppDetailsRepository {
 
     /// Clear all app details
     pub async fn clear_all(&self) -> Result<(), Error> {
-        sqlx::query!("DELETE FROM AppDetails")
+        retry_on_busy(|| sqlx::query!("DELETE FROM AppDetails")
             .execute(&self.pool)
+            .timed("app_details.clear_all", 0))
             .await?;
         Ok(())
     }
@@ -75,6 +105,7 @@ impl AppDetailsRepository {
             "#
         )
         .fetch_one(&self.pool)
+        .timed("app_details.count_null_app_name_null_url", 0)
         .await?
         .count;
 
@@ -91,23 +122,126 @@ impl AppDetailsRepository {
             "#
         )
         .fetch_one(&self.pool)
+        .timed("app_details.count_null_app_name_non_null_url", 0)
         .await?
         .count;
 
         Ok(count)
     }
 
+    /// The URL of every app details row, for the canonicalize-app-urls step
+    pub async fn find_all_urls(&self) -> Result<Vec<AppDetailsUrl>, Error> {
+        let results = sqlx::query_as!(
+            AppDetailsUrl,
+            r#"SELECT id AS "id!", url FROM AppDetails ORDER BY id"#
+        )
+        .fetch_all(&self.pool)
+        .timed("app_details.find_all_urls", 0)
+        .await?;
+
+        Ok(results)
+    }
+
+    /// `find_all_urls` within a transaction, seeing rows it has rebuilt
+    pub async fn find_all_urls_tx(&self, tx: &mut Transaction<'_, Sqlite>) -> Result<Vec<AppDetailsUrl>, Error> {
+        let results = sqlx::query_as!(
+            AppDetailsUrl,
+            r#"SELECT id AS "id!", url FROM AppDetails ORDER BY id"#
+        )
+        .fetch_all(&mut **tx)
+        .timed("app_details.find_all_urls", 0)
+        .await?;
+
+        Ok(results)
+    }
+
+    /// Store the URL components and project of an app details row within a transaction
+    pub async fn update_url_components_tx(
+        &self,
+        id: i64,
+        host: Option<&str>,
+        owner: Option<&str>,
+        repo: Option<&str>,
+        project: Option<&str>,
+        tx: &mut Transaction<'_, Sqlite>,
+    ) -> Result<(), Error> {
+        let now = audit_timestamp();
+
+        sqlx::query!(
+            r#"
+            UPDATE AppDetails
+            SET url_host = ?, url_owner = ?, url_repo = ?, project = ?, updated_at = ?
+            WHERE id = ?
+            "#,
+            host,
+            owner,
+            repo,
+            project,
+            now,
+            id
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Average ITS of a tenant's runs grouped by the project set by the canonicalize-app-urls step
+    ///
+    /// When `tag` is given only runs carrying that tag are counted, and runs
+    /// carrying `exclude_tag` are not. Runs on an integrated GPU are left out
+    /// unless `include_integrated` is set.
+    pub async fn its_by_app(
+        &self,
+        tenant_id: &str,
+        tag: Option<&str>,
+        exclude_tag: Option<&str>,
+        include_integrated: bool,
+    ) -> Result<Vec<AppProjectStats>, Error> {
+        let results = sqlx::query_as!(
+            AppProjectStats,
+            r#"
+            SELECT
+                ad.project AS "project?",
+                COUNT(DISTINCT ad.run_id) AS "run_count!: i64",
+                COUNT(DISTINCT CASE WHEN ad.url_owner || '/' || ad.url_repo <> ad.project THEN ad.run_id END) AS "fork_run_count!: i64",
+                AVG(pr.avg_its) AS "avg_its?: f64"
+            FROM AppDetails ad
+            LEFT JOIN performanceResult pr ON pr.run_id = ad.run_id
+            WHERE (?1 IS NULL OR ad.run_id IN (SELECT rt.run_id FROM run_tags rt JOIN tags t ON t.id = rt.tag_id WHERE t.name = ?1))
+              AND (?3 IS NULL OR ad.run_id NOT IN (SELECT rt.run_id FROM run_tags rt JOIN tags t ON t.id = rt.tag_id WHERE t.name = ?3))
+              AND (?2 OR ad.run_id NOT IN (SELECT ig.run_id FROM GPU ig WHERE ig.gpu_index = 0 AND ig.is_integrated = 1))
+              AND ad.run_id IN (SELECT r.id FROM runs r WHERE r.tenant_id = ?4)
+            GROUP BY ad.project
+            ORDER BY COUNT(DISTINCT ad.run_id) DESC, ad.project
+            "#,
+            tag,
+            include_integrated,
+            exclude_tag,
+            tenant_id
+        )
+        .fetch_all(&self.pool)
+        .timed("app_details.its_by_app", 4)
+        .await?;
+
+        Ok(results)
+    }
+
     /// Update app names for AUTOMATIC1111 URLs
     pub async fn update_automatic1111_names(&self, app_name: &str) -> Result<i64, Error> {
-        let result = sqlx::query!(
+        let now = audit_timestamp();
+
+        let result = retry_on_busy(|| sqlx::query!(
             r#"
             UPDATE AppDetails
-            SET app_name = ?
+            SET app_name = ?, updated_at = ?
             WHERE url LIKE '%AUTOMATIC1111%'
             "#,
-            app_name
+            app_name,
+            now
         )
         .execute(&self.pool)
+        .timed("app_details.update_automatic1111_names", 2))
         .await?;
 
         Ok(result.rows_affected() as i64)
@@ -115,15 +249,19 @@ impl AppDetailsRepository {
 
     /// Update app names for vladmandic URLs (only if app_name is NULL or empty)
     pub async fn update_vladmandic_names(&self, app_name: &str) -> Result<i64, Error> {
-        let result = sqlx::query!(
+        let now = audit_timestamp();
+
+        let result = retry_on_busy(|| sqlx::query!(
             r#"
             UPDATE AppDetails
-            SET app_name = ?
+            SET app_name = ?, updated_at = ?
             WHERE url LIKE '%vladmandic%' AND (app_name IS NULL OR app_name = '')
             "#,
-            app_name
+            app_name,
+            now
         )
         .execute(&self.pool)
+        .timed("app_details.update_vladmandic_names", 2))
         .await?;
 
         Ok(result.rows_affected() as i64)
@@ -131,15 +269,19 @@ impl AppDetailsRepository {
 
     /// Update app names for stable-diffusion-webui URLs (only if app_name is NULL)
     pub async fn update_stable_diffusion_names(&self, app_name: &str) -> Result<i64, Error> {
-        let result = sqlx::query!(
+        let now = audit_timestamp();
+
+        let result = retry_on_busy(|| sqlx::query!(
             r#"
             UPDATE AppDetails
-            SET app_name = ?
+            SET app_name = ?, updated_at = ?
             WHERE url LIKE '%stable-diffusion-webui%' AND app_name IS NULL
             "#,
-            app_name
+            app_name,
+            now
         )
         .execute(&self.pool)
+        .timed("app_details.update_stable_diffusion_names", 2))
         .await?;
 
         Ok(result.rows_affected() as i64)
@@ -147,41 +289,99 @@ impl AppDetailsRepository {
 
     /// Update app names for records with both app_name and url as NULL
     pub async fn update_null_app_name_null_url_names(&self, app_name: &str) -> Result<i64, Error> {
-        let result = sqlx::query!(
+        let now = audit_timestamp();
+
+        let result = retry_on_busy(|| sqlx::query!(
             r#"
             UPDATE AppDetails
-            SET app_name = ?
+            SET app_name = ?, updated_at = ?
             WHERE app_name IS NULL AND url IS NULL
             "#,
-            app_name
+            app_name,
+            now
         )
         .execute(&self.pool)
+        .timed("app_details.update_null_app_name_null_url_names", 2))
         .await?;
 
         Ok(result.rows_affected() as i64)
     }
+
+    /// Put back a row's previous app name, unless it no longer holds `expected`
+    pub async fn restore_app_name_tx(
+        &self,
+        id: i64,
+        previous: Option<&str>,
+        expected: Option<&str>,
+        tx: &mut Transaction<'_, Sqlite>,
+    ) -> Result<u64, Error> {
+        let now = audit_timestamp();
+
+        let result = sqlx::query!(
+            "UPDATE AppDetails SET app_name = ?, updated_at = ? WHERE id = ? AND app_name IS ?",
+            previous,
+            now,
+            id,
+            expected
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+impl ShadowRow for AppDetails {
+    const TABLE: &'static str = "AppDetails";
+    const COLUMNS: &'static [&'static str] = &["run_id", "app_name", "updated", "hash", "url", "parser_version"];
+
+    fn bind_columns<'q>(self, query: Query<'q, Sqlite, SqliteArguments<'q>>) -> Query<'q, Sqlite, SqliteArguments<'q>> {
+        query
+            .bind(self.run_id)
+            .bind(self.app_name)
+            .bind(self.updated)
+            .bind(self.hash)
+            .bind(self.url)
+            .bind(self.parser_version)
+    }
 }
 
 #[async_trait]
 impl Repository<AppDetails, i64> for AppDetailsRepository {
     async fn create(&self, entity: AppDetails) -> Result<AppDetails, Error> {
-        let id = sqlx::query!(
+        let now = audit_timestamp();
+
+        let row = retry_on_busy(|| sqlx::query!(
             r#"
-            INSERT INTO AppDetails (run_id, app_name, updated, hash, url)
-            VALUES (?, ?, ?, ?, ?)
+            INSERT INTO AppDetails (run_id, app_name, updated, hash, url, parser_version, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT (run_id) DO UPDATE SET
+                app_name = excluded.app_name,
+                updated = excluded.updated,
+                hash = excluded.hash,
+                url = excluded.url,
+                parser_version = excluded.parser_version,
+                updated_at = excluded.updated_at
+            RETURNING id AS "id!: i64", created_at
             "#,
             entity.run_id,
             entity.app_name,
             entity.updated,
             entity.hash,
-            entity.url
+            entity.url,
+            entity.parser_version,
+            now,
+            now
         )
-        .execute(&self.pool)
-        .await?
-        .last_insert_rowid() as i64;
+        .fetch_one(&self.pool)
+        .timed("app_details.create", 8))
+        .await?;
 
         Ok(AppDetails {
-            id: Some(id),
+            id: Some(row.id),
+            // On conflict the row keeps its original creation time
+            created_at: row.created_at,
+            updated_at: Some(now),
             ..entity
         })
     }
@@ -190,13 +390,14 @@ impl Repository<AppDetails, i64> for AppDetailsRepository {
         let result = sqlx::query_as!(
             AppDetails,
             r#"
-            SELECT id, run_id, app_name, updated, hash, url
+            SELECT id, run_id, app_name, updated, hash, url, parser_version, created_at, updated_at
             FROM AppDetails
             WHERE id = ?
             "#,
             id
         )
         .fetch_optional(&self.pool)
+        .timed("app_details.find_by_id", 1)
         .await?;
 
         Ok(result)
@@ -206,12 +407,13 @@ impl Repository<AppDetails, i64> for AppDetailsRepository {
         let results = sqlx::query_as!(
             AppDetails,
             r#"
-            SELECT id, run_id, app_name, updated, hash, url
+            SELECT id, run_id, app_name, updated, hash, url, parser_version, created_at, updated_at
             FROM AppDetails
             ORDER BY id DESC
             "#
         )
         .fetch_all(&self.pool)
+        .timed("app_details.find_all", 0)
         .await?;
 
         Ok(results)
@@ -219,11 +421,12 @@ impl Repository<AppDetails, i64> for AppDetailsRepository {
 
     async fn update(&self, entity: AppDetails) -> Result<AppDetails, Error> {
         let id = entity.id.ok_or(Error::RowNotFound)?;
+        let now = audit_timestamp();
         
-        sqlx::query!(
+        retry_on_busy(|| sqlx::query!(
             r#"
             UPDATE AppDetails
-            SET run_id = ?, app_name = ?, updated = ?, hash = ?, url = ?
+            SET run_id = ?, app_name = ?, updated = ?, hash = ?, url = ?, parser_version = ?, updated_at = ?
             WHERE id = ?
             "#,
             entity.run_id,
@@ -231,17 +434,24 @@ impl Repository<AppDetails, i64> for AppDetailsRepository {
             entity.updated,
             entity.hash,
             entity.url,
+            entity.parser_version,
+            now,
             id
         )
         .execute(&self.pool)
+        .timed("app_details.update", 8))
         .await?;
 
-        Ok(entity)
+        Ok(AppDetails {
+            updated_at: Some(now),
+            ..entity
+        })
     }
 
     async fn delete(&self, id: i64) -> Result<(), Error> {
-        sqlx::query!("DELETE FROM AppDetails WHERE id = ?", id)
+        retry_on_busy(|| sqlx::query!("DELETE FROM AppDetails WHERE id = ?", id)
             .execute(&self.pool)
+            .timed("app_details.delete", 1))
             .await?;
         Ok(())
     }
@@ -249,43 +459,77 @@ impl Repository<AppDetails, i64> for AppDetailsRepository {
     async fn count(&self) -> Result<i64, Error> {
         let count = sqlx::query!("SELECT COUNT(*) as count FROM AppDetails")
             .fetch_one(&self.pool)
+            .timed("app_details.count", 0)
             .await?
             .count;
         Ok(count)
     }
+
+    async fn exists(&self, id: i64) -> Result<bool, Error> {
+        let exists = sqlx::query_scalar!(r#"SELECT EXISTS(SELECT 1 FROM AppDetails WHERE id = ?) AS "exists!: bool""#, id)
+            .fetch_one(&self.pool)
+            .timed("app_details.exists", 1)
+            .await?;
+        Ok(exists)
+    }
+
+    async fn count_where(&self, filter: &Filter) -> Result<i64, Error> {
+        let count: i64 = sqlx::query_scalar(&filter.count_sql("AppDetails")?)
+            .bind(&filter.value)
+            .fetch_one(&self.pool)
+            .timed("app_details.count_where", 1)
+            .await?;
+        Ok(count)
+    }
 }
 
 #[async_trait]
 impl<'a> TransactionRepository<'a, AppDetails, i64> for AppDetailsRepository {
     async fn create_tx(&self, entity: AppDetails, tx: &mut Transaction<'a, Sqlite>) -> Result<AppDetails, Error> {
-        let id = sqlx::query!(
+        let now = audit_timestamp();
+
+        let row = sqlx::query!(
             r#"
-            INSERT INTO AppDetails (run_id, app_name, updated, hash, url)
-            VALUES (?, ?, ?, ?, ?)
+            INSERT INTO AppDetails (run_id, app_name, updated, hash, url, parser_version, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT (run_id) DO UPDATE SET
+                app_name = excluded.app_name,
+                updated = excluded.updated,
+                hash = excluded.hash,
+                url = excluded.url,
+                parser_version = excluded.parser_version,
+                updated_at = excluded.updated_at
+            RETURNING id AS "id!: i64", created_at
             "#,
             entity.run_id,
             entity.app_name,
             entity.updated,
             entity.hash,
-            entity.url
+            entity.url,
+            entity.parser_version,
+            now,
+            now
         )
-        .execute(&mut **tx)
-        .await?
-        .last_insert_rowid() as i64;
+        .fetch_one(&mut **tx)
+        .await?;
 
         Ok(AppDetails {
-            id: Some(id),
+            id: Some(row.id),
+            // On conflict the row keeps its original creation time
+            created_at: row.created_at,
+            updated_at: Some(now),
             ..entity
         })
     }
 
     async fn update_tx(&self, entity: AppDetails, tx: &mut Transaction<'a, Sqlite>) -> Result<AppDetails, Error> {
         let id = entity.id.ok_or(Error::RowNotFound)?;
+        let now = audit_timestamp();
         
         sqlx::query!(
             r#"
             UPDATE AppDetails
-            SET run_id = ?, app_name = ?, updated = ?, hash = ?, url = ?
+            SET run_id = ?, app_name = ?, updated = ?, hash = ?, url = ?, parser_version = ?, updated_at = ?
             WHERE id = ?
             "#,
             entity.run_id,
@@ -293,12 +537,17 @@ impl<'a> TransactionRepository<'a, AppDetails, i64> for AppDetailsRepository {
             entity.updated,
             entity.hash,
             entity.url,
+            entity.parser_version,
+            now,
             id
         )
         .execute(&mut **tx)
         .await?;
 
-        Ok(entity)
+        Ok(AppDetails {
+            updated_at: Some(now),
+            ..entity
+        })
     }
 
     async fn delete_tx(&self, id: i64, tx: &mut Transaction<'a, Sqlite>) -> Result<(), Error> {
@@ -0,0 +1,406 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use sqlx::{query::Query, sqlite::SqliteArguments, Error, SqlitePool, Transaction, Sqlite};
+
+use crate::models::app_release::{AppRelease, AppVersionStats};
+use crate::repositories::traits::{Repository, TransactionRepository, BulkRepository, BulkTransactionRepository};
+use crate::repositories::audit_timestamp;
+use crate::repositories::batch::{first_by_run, json_ids};
+use crate::repositories::connection::{retry_on_busy, TimedQuery};
+use crate::repositories::shadow_table::ShadowRow;
+use crate::repositories::query_builder::Filter;
+
+pub struct AppReleaseRepository {
+    pool: SqlitePool,
+}
+
+impl AppReleaseRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Find app release info by run_id
+    pub async fn find_by_run_id(&self, run_id: i64) -> Result<Vec<AppRelease>, Error> {
+        let results = sqlx::query_as!(
+            AppRelease,
+            r#"
+            SELECT id, run_id, release_channel, release_month, created_at, updated_at
+            FROM AppRelease
+            WHERE run_id = ?
+            ORDER BY id DESC
+            "#,
+            run_id
+        )
+        .fetch_all(&self.pool)
+        .timed("app_release.find_by_run_id", 1)
+        .await?;
+
+        Ok(results)
+    }
+
+    /// The newest app releases row of each of the given runs, in one query
+    pub async fn find_by_run_ids(&self, run_ids: &[i64]) -> Result<HashMap<i64, AppRelease>, Error> {
+        let ids = json_ids(run_ids)?;
+        let results = sqlx::query_as!(
+            AppRelease,
+            r#"
+            SELECT id, run_id, release_channel, release_month, created_at, updated_at
+            FROM AppRelease
+            WHERE run_id IN (SELECT value FROM json_each(?))
+            ORDER BY run_id, id DESC
+            "#,
+            ids
+        )
+        .fetch_all(&self.pool)
+        .timed("app_release.find_by_run_ids", 1)
+        .await?;
+
+        Ok(first_by_run(results, |row| row.run_id))
+    }
+
+    /// Average ITS of a tenant's runs grouped by app name, release channel and release month
+    ///
+    /// When `app_name` is given only that app is included; when `tag` is given
+    /// only runs carrying that tag are counted, and runs carrying `exclude_tag`
+    /// are not. Runs on an integrated GPU are left out unless `include_integrated` is set.
+    pub async fn its_by_app_version(
+        &self,
+        tenant_id: &str,
+        app_name: Option<&str>,
+        tag: Option<&str>,
+        exclude_tag: Option<&str>,
+        include_integrated: bool,
+    ) -> Result<Vec<AppVersionStats>, Error> {
+        let results = sqlx::query_as!(
+            AppVersionStats,
+            r#"
+            SELECT
+                ad.app_name AS "app_name?",
+                ar.release_channel AS "release_channel?",
+                ar.release_month AS "release_month?",
+                COUNT(DISTINCT ar.run_id) AS "run_count!: i64",
+                AVG(pr.avg_its) AS "avg_its?: f64"
+            FROM AppRelease ar
+            JOIN AppDetails ad ON ad.run_id = ar.run_id
+            LEFT JOIN performanceResult pr ON pr.run_id = ar.run_id
+            WHERE (?1 IS NULL OR ad.app_name = ?1)
+              AND (?2 IS NULL OR ar.run_id IN (SELECT rt.run_id FROM run_tags rt JOIN tags t ON t.id = rt.tag_id WHERE t.name = ?2))
+              AND (?4 IS NULL OR ar.run_id NOT IN (SELECT rt.run_id FROM run_tags rt JOIN tags t ON t.id = rt.tag_id WHERE t.name = ?4))
+              AND (?3 OR ar.run_id NOT IN (SELECT ig.run_id FROM GPU ig WHERE ig.gpu_index = 0 AND ig.is_integrated = 1))
+              AND ar.run_id IN (SELECT r.id FROM runs r WHERE r.tenant_id = ?5)
+            GROUP BY ad.app_name, ar.release_channel, ar.release_month
+            ORDER BY ad.app_name, ar.release_month, ar.release_channel
+            "#,
+            app_name,
+            tag,
+            include_integrated,
+            exclude_tag,
+            tenant_id
+        )
+        .fetch_all(&self.pool)
+        .timed("app_release.its_by_app_version", 5)
+        .await?;
+
+        Ok(results)
+    }
+}
+
+impl ShadowRow for AppRelease {
+    const TABLE: &'static str = "AppRelease";
+    const COLUMNS: &'static [&'static str] = &["run_id", "release_channel", "release_month"];
+
+    fn bind_columns<'q>(self, query: Query<'q, Sqlite, SqliteArguments<'q>>) -> Query<'q, Sqlite, SqliteArguments<'q>> {
+        query
+            .bind(self.run_id)
+            .bind(self.release_channel)
+            .bind(self.release_month)
+    }
+}
+
+#[async_trait]
+impl Repository<AppRelease, i64> for AppReleaseRepository {
+    async fn create(&self, entity: AppRelease) -> Result<AppRelease, Error> {
+        let now = audit_timestamp();
+
+        let id = retry_on_busy(|| sqlx::query!(
+            r#"
+            INSERT INTO AppRelease (run_id, release_channel, release_month, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?)
+            "#,
+            entity.run_id,
+            entity.release_channel,
+            entity.release_month,
+            now,
+            now
+        )
+        .execute(&self.pool)
+        .timed("app_release.create", 5))
+        .await?
+        .last_insert_rowid();
+
+        Ok(AppRelease {
+            id: Some(id),
+            created_at: Some(now.clone()),
+            updated_at: Some(now),
+            ..entity
+        })
+    }
+
+    async fn find_by_id(&self, id: i64) -> Result<Option<AppRelease>, Error> {
+        let result = sqlx::query_as!(
+            AppRelease,
+            r#"
+            SELECT id, run_id, release_channel, release_month, created_at, updated_at
+            FROM AppRelease
+            WHERE id = ?
+            "#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .timed("app_release.find_by_id", 1)
+        .await?;
+
+        Ok(result)
+    }
+
+    async fn find_all(&self) -> Result<Vec<AppRelease>, Error> {
+        let results = sqlx::query_as!(
+            AppRelease,
+            r#"
+            SELECT id, run_id, release_channel, release_month, created_at, updated_at
+            FROM AppRelease
+            ORDER BY id DESC
+            "#
+        )
+        .fetch_all(&self.pool)
+        .timed("app_release.find_all", 0)
+        .await?;
+
+        Ok(results)
+    }
+
+    async fn update(&self, entity: AppRelease) -> Result<AppRelease, Error> {
+        let id = entity.id.ok_or(Error::RowNotFound)?;
+        let now = audit_timestamp();
+
+        retry_on_busy(|| sqlx::query!(
+            r#"
+            UPDATE AppRelease
+            SET run_id = ?, release_channel = ?, release_month = ?, updated_at = ?
+            WHERE id = ?
+            "#,
+            entity.run_id,
+            entity.release_channel,
+            entity.release_month,
+            now,
+            id
+        )
+        .execute(&self.pool)
+        .timed("app_release.update", 5))
+        .await?;
+
+        Ok(AppRelease {
+            updated_at: Some(now),
+            ..entity
+        })
+    }
+
+    async fn delete(&self, id: i64) -> Result<(), Error> {
+        retry_on_busy(|| sqlx::query!("DELETE FROM AppRelease WHERE id = ?", id)
+            .execute(&self.pool)
+            .timed("app_release.delete", 1))
+            .await?;
+        Ok(())
+    }
+
+    async fn count(&self) -> Result<i64, Error> {
+        let count = sqlx::query!("SELECT COUNT(*) as count FROM AppRelease")
+            .fetch_one(&self.pool)
+            .timed("app_release.count", 0)
+            .await?
+            .count;
+        Ok(count)
+    }
+
+    async fn exists(&self, id: i64) -> Result<bool, Error> {
+        let exists = sqlx::query_scalar!(r#"SELECT EXISTS(SELECT 1 FROM AppRelease WHERE id = ?) AS "exists!: bool""#, id)
+            .fetch_one(&self.pool)
+            .timed("app_release.exists", 1)
+            .await?;
+        Ok(exists)
+    }
+
+    async fn count_where(&self, filter: &Filter) -> Result<i64, Error> {
+        let count: i64 = sqlx::query_scalar(&filter.count_sql("AppRelease")?)
+            .bind(&filter.value)
+            .fetch_one(&self.pool)
+            .timed("app_release.count_where", 1)
+            .await?;
+        Ok(count)
+    }
+}
+
+#[async_trait]
+impl BulkRepository<AppRelease, i64> for AppReleaseRepository {
+    async fn bulk_create(&self, entities: Vec<AppRelease>) -> Result<Vec<AppRelease>, Error> {
+        if entities.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut tx = self.pool.begin().await?;
+
+        let result = self.bulk_create_tx(entities, &mut tx).await;
+
+        match result {
+            Ok(results) => {
+                tx.commit().await?;
+                Ok(results)
+            }
+            Err(e) => {
+                tx.rollback().await?;
+                Err(e)
+            }
+        }
+    }
+
+    async fn bulk_update(&self, entities: Vec<AppRelease>) -> Result<Vec<AppRelease>, Error> {
+        if entities.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut tx = self.pool.begin().await?;
+
+        let result = self.bulk_update_tx(entities, &mut tx).await;
+
+        match result {
+            Ok(results) => {
+                tx.commit().await?;
+                Ok(results)
+            }
+            Err(e) => {
+                tx.rollback().await?;
+                Err(e)
+            }
+        }
+    }
+
+    async fn delete_all(&self) -> Result<usize, Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let result = self.delete_all_tx(&mut tx).await;
+
+        match result {
+            Ok(count) => {
+                tx.commit().await?;
+                Ok(count)
+            }
+            Err(e) => {
+                tx.rollback().await?;
+                Err(e)
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<'a> TransactionRepository<'a, AppRelease, i64> for AppReleaseRepository {
+    async fn create_tx(&self, entity: AppRelease, tx: &mut Transaction<'a, Sqlite>) -> Result<AppRelease, Error> {
+        let now = audit_timestamp();
+
+        let id = sqlx::query!(
+            r#"
+            INSERT INTO AppRelease (run_id, release_channel, release_month, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?)
+            "#,
+            entity.run_id,
+            entity.release_channel,
+            entity.release_month,
+            now,
+            now
+        )
+        .execute(&mut **tx)
+        .await?
+        .last_insert_rowid();
+
+        Ok(AppRelease {
+            id: Some(id),
+            created_at: Some(now.clone()),
+            updated_at: Some(now),
+            ..entity
+        })
+    }
+
+    async fn update_tx(&self, entity: AppRelease, tx: &mut Transaction<'a, Sqlite>) -> Result<AppRelease, Error> {
+        let id = entity.id.ok_or(Error::RowNotFound)?;
+        let now = audit_timestamp();
+
+        sqlx::query!(
+            r#"
+            UPDATE AppRelease
+            SET run_id = ?, release_channel = ?, release_month = ?, updated_at = ?
+            WHERE id = ?
+            "#,
+            entity.run_id,
+            entity.release_channel,
+            entity.release_month,
+            now,
+            id
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(AppRelease {
+            updated_at: Some(now),
+            ..entity
+        })
+    }
+
+    async fn delete_tx(&self, id: i64, tx: &mut Transaction<'a, Sqlite>) -> Result<(), Error> {
+        sqlx::query!("DELETE FROM AppRelease WHERE id = ?", id)
+            .execute(&mut **tx)
+            .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<'a> BulkTransactionRepository<'a, AppRelease, i64> for AppReleaseRepository {
+    async fn bulk_create_tx(&self, entities: Vec<AppRelease>, tx: &mut Transaction<'a, Sqlite>) -> Result<Vec<AppRelease>, Error> {
+        if entities.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut created_results = Vec::with_capacity(entities.len());
+
+        for entity in entities {
+            let created_result = self.create_tx(entity, tx).await?;
+            created_results.push(created_result);
+        }
+
+        Ok(created_results)
+    }
+
+    async fn bulk_update_tx(&self, entities: Vec<AppRelease>, tx: &mut Transaction<'a, Sqlite>) -> Result<Vec<AppRelease>, Error> {
+        if entities.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut updated_results = Vec::with_capacity(entities.len());
+
+        for entity in entities {
+            let updated_result = self.update_tx(entity, tx).await?;
+            updated_results.push(updated_result);
+        }
+
+        Ok(updated_results)
+    }
+
+    async fn delete_all_tx(&self, tx: &mut Transaction<'a, Sqlite>) -> Result<usize, Error> {
+        let result = sqlx::query!("DELETE FROM AppRelease")
+            .execute(&mut **tx)
+            .await?;
+
+        Ok(result.rows_affected() as usize)
+    }
+}
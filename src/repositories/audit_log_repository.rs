@@ -0,0 +1,51 @@
+use sqlx::{Error, Sqlite, SqlitePool, Transaction};
+
+use crate::models::audit_log::AuditLogEntry;
+use crate::repositories::audit_timestamp;
+use crate::repositories::connection::TimedQuery;
+
+pub struct AuditLogRepository {
+    pool: SqlitePool,
+}
+
+impl AuditLogRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Entries of one action, newest first
+    pub async fn find_by_action(&self, action: &str) -> Result<Vec<AuditLogEntry>, Error> {
+        let results = sqlx::query_as!(
+            AuditLogEntry,
+            r#"
+            SELECT id AS "id!", action, details, created_at
+            FROM audit_log
+            WHERE action = ?
+            ORDER BY id DESC
+            "#,
+            action
+        )
+        .fetch_all(&self.pool)
+        .timed("audit_log.find_by_action", 1)
+        .await?;
+
+        Ok(results)
+    }
+
+    /// Record an admin data fix within the transaction making it, returning the entry id
+    pub async fn create_tx(&self, action: &str, details: &str, tx: &mut Transaction<'_, Sqlite>) -> Result<i64, Error> {
+        let now = audit_timestamp();
+
+        let id = sqlx::query!(
+            "INSERT INTO audit_log (action, details, created_at) VALUES (?, ?, ?)",
+            action,
+            details,
+            now
+        )
+        .execute(&mut **tx)
+        .await?
+        .last_insert_rowid();
+
+        Ok(id)
+    }
+}
@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+
+use sqlx::Error;
+
+/// Bind value for `run_id IN (SELECT value FROM json_each(?))`
+///
+/// Binding the ids as one JSON array keeps the statement the same whatever
+/// the number of runs.
+pub fn json_ids(ids: &[i64]) -> Result<String, Error> {
+    serde_json::to_string(ids).map_err(|e| Error::Encode(Box::new(e)))
+}
+
+/// Bind parameters one statement may carry on every SQLite build
+///
+/// 999 was the default of `SQLITE_MAX_VARIABLE_NUMBER` before 3.32, so
+/// multi-row statements stay under it whichever library they are linked to.
+pub const MAX_BIND_PARAMETERS: usize = 999;
+
+/// `(?, ?), (?, ?)` placeholders for a multi-row `INSERT ... VALUES` of `rows` rows of `columns` values
+pub fn values_placeholders(columns: usize, rows: usize) -> String {
+    let row = format!("({})", vec!["?"; columns].join(", "));
+    vec![row; rows].join(", ")
+}
+
+/// Rows keyed by their run, keeping the first row of each run
+pub fn first_by_run<T>(rows: Vec<T>, run_id: impl Fn(&T) -> Option<i64>) -> HashMap<i64, T> {
+    let mut keyed = HashMap::new();
+    for row in rows {
+        if let Some(id) = run_id(&row) {
+            keyed.entry(id).or_insert(row);
+        }
+    }
+
+    keyed
+}
+
+/// Rows grouped by their run, each group in query order
+pub fn group_by_run<T>(rows: Vec<T>, run_id: impl Fn(&T) -> Option<i64>) -> HashMap<i64, Vec<T>> {
+    let mut grouped: HashMap<i64, Vec<T>> = HashMap::new();
+    for row in rows {
+        if let Some(id) = run_id(&row) {
+            grouped.entry(id).or_default().push(row);
+        }
+    }
+
+    grouped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rows_are_keyed_by_run() {
+        let rows = vec![(Some(1), "a"), (Some(2), "b"), (None, "c"), (Some(1), "d")];
+
+        let first = first_by_run(rows.clone(), |row| row.0);
+        assert_eq!(first.len(), 2);
+        assert_eq!(first[&1].1, "a");
+
+        let grouped = group_by_run(rows, |row| row.0);
+        assert_eq!(grouped[&1].iter().map(|row| row.1).collect::<Vec<_>>(), ["a", "d"]);
+        assert_eq!(grouped[&2].len(), 1);
+        assert_eq!(json_ids(&[3, 1]).unwrap(), "[3,1]");
+        assert_eq!(values_placeholders(2, 3), "(?, ?), (?, ?), (?, ?)");
+    }
+}
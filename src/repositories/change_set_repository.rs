@@ -0,0 +1,84 @@
+use sqlx::{Error, Sqlite, SqlitePool, Transaction};
+
+use crate::models::change_set::{ChangeSet, ChangeSetItem};
+use crate::repositories::audit_timestamp;
+use crate::repositories::connection::TimedQuery;
+
+pub struct ChangeSetRepository {
+    pool: SqlitePool,
+}
+
+impl ChangeSetRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// All change sets, newest first
+    pub async fn find_all(&self) -> Result<Vec<ChangeSet>, Error> {
+        let results = sqlx::query_as!(
+            ChangeSet,
+            r#"SELECT id AS "id!", action, created_at, reverted_at FROM change_sets ORDER BY id DESC"#
+        )
+        .fetch_all(&self.pool)
+        .timed("change_sets.find_all", 0)
+        .await?;
+
+        Ok(results)
+    }
+
+    /// Open a change set within the transaction applying the fix, returning its id
+    pub async fn create_tx(&self, action: &str, tx: &mut Transaction<'_, Sqlite>) -> Result<i64, Error> {
+        let now = audit_timestamp();
+
+        let id = sqlx::query!(
+            "INSERT INTO change_sets (action, created_at) VALUES (?, ?)",
+            action,
+            now
+        )
+        .execute(&mut **tx)
+        .await?
+        .last_insert_rowid();
+
+        Ok(id)
+    }
+
+    pub async fn find_by_id_tx(&self, id: i64, tx: &mut Transaction<'_, Sqlite>) -> Result<Option<ChangeSet>, Error> {
+        let result = sqlx::query_as!(
+            ChangeSet,
+            r#"SELECT id AS "id!", action, created_at, reverted_at FROM change_sets WHERE id = ?"#,
+            id
+        )
+        .fetch_optional(&mut **tx)
+        .await?;
+
+        Ok(result)
+    }
+
+    /// Items of a change set, newest first, the order to undo them in
+    pub async fn find_items_tx(&self, change_set_id: i64, tx: &mut Transaction<'_, Sqlite>) -> Result<Vec<ChangeSetItem>, Error> {
+        let results = sqlx::query_as!(
+            ChangeSetItem,
+            r#"
+            SELECT id AS "id!", change_set_id, row_id, old_value, new_value
+            FROM change_set_items
+            WHERE change_set_id = ?
+            ORDER BY id DESC
+            "#,
+            change_set_id
+        )
+        .fetch_all(&mut **tx)
+        .await?;
+
+        Ok(results)
+    }
+
+    pub async fn mark_reverted_tx(&self, id: i64, tx: &mut Transaction<'_, Sqlite>) -> Result<u64, Error> {
+        let now = audit_timestamp();
+
+        let result = sqlx::query!("UPDATE change_sets SET reverted_at = ? WHERE id = ?", now, id)
+            .execute(&mut **tx)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,101 @@
+use sqlx::{Error, Sqlite, SqlitePool, Transaction};
+
+use crate::models::app_details::AppCommit;
+use crate::repositories::{audit_timestamp, connection::TimedQuery};
+
+/// The cached commit dates of app hashes, and their copies in AppDetails
+pub struct CommitDateRepository {
+    pool: SqlitePool,
+}
+
+impl CommitDateRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Commits of GitHub repositories named by app details that have not been looked up yet
+    ///
+    /// Only hashes of 7 to 40 hex digits qualify, the forms git prints; the
+    /// commits used by the most runs come first.
+    pub async fn find_unresolved(&self) -> Result<Vec<AppCommit>, Error> {
+        let results = sqlx::query_as!(
+            AppCommit,
+            r#"
+            SELECT ad.url_owner AS "owner!", ad.url_repo AS "repo!", lower(trim(ad.hash)) AS "hash!: String"
+            FROM AppDetails ad
+            WHERE ad.url_host = 'github.com'
+              AND ad.url_owner IS NOT NULL
+              AND ad.url_repo IS NOT NULL
+              AND length(trim(ad.hash)) BETWEEN 7 AND 40
+              AND lower(trim(ad.hash)) NOT GLOB '*[^0-9a-f]*'
+              AND NOT EXISTS (
+                  SELECT 1 FROM commit_dates cd
+                  WHERE cd.owner = ad.url_owner AND cd.repo = ad.url_repo AND cd.hash = lower(trim(ad.hash))
+              )
+            GROUP BY ad.url_owner, ad.url_repo, lower(trim(ad.hash))
+            ORDER BY COUNT(*) DESC, ad.url_owner, ad.url_repo, lower(trim(ad.hash))
+            "#
+        )
+        .fetch_all(&self.pool)
+        .timed("commit_dates.find_unresolved", 0)
+        .await?;
+
+        Ok(results)
+    }
+
+    /// Cache the date of a commit, `None` when GitHub does not know it
+    pub async fn record(&self, commit: &AppCommit, commit_date: Option<&str>) -> Result<(), Error> {
+        let now = audit_timestamp();
+
+        sqlx::query!(
+            r#"
+            INSERT OR REPLACE INTO commit_dates (owner, repo, hash, commit_date, looked_up_at)
+            VALUES (?, ?, ?, ?, ?)
+            "#,
+            commit.owner,
+            commit.repo,
+            commit.hash,
+            commit_date,
+            now
+        )
+        .execute(&self.pool)
+        .timed("commit_dates.record", 5)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Copy the cached dates to the app details rows of their commits, returning the rows changed
+    pub async fn apply_to_app_details(&self) -> Result<u64, Error> {
+        let mut tx = self.pool.begin().await?;
+        let rows_updated = self.apply_to_app_details_tx(&mut tx).await?;
+        tx.commit().await?;
+
+        Ok(rows_updated)
+    }
+
+    /// `apply_to_app_details` within a transaction, e.g. the one rebuilding AppDetails
+    pub async fn apply_to_app_details_tx(&self, tx: &mut Transaction<'_, Sqlite>) -> Result<u64, Error> {
+        let now = audit_timestamp();
+
+        let result = sqlx::query!(
+            r#"
+            UPDATE AppDetails
+            SET commit_date = cd.commit_date, updated_at = ?
+            FROM commit_dates cd
+            WHERE cd.owner = AppDetails.url_owner
+              AND cd.repo = AppDetails.url_repo
+              AND cd.hash = lower(trim(AppDetails.hash))
+              AND AppDetails.url_host = 'github.com'
+              AND cd.commit_date IS NOT NULL
+              AND AppDetails.commit_date IS NOT cd.commit_date
+            "#,
+            now
+        )
+        .execute(&mut **tx)
+        .timed("commit_dates.apply_to_app_details", 1)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
@@ -1,4 +1,24 @@
+use std::{
+    future::Future,
+    io,
+    pin::Pin,
+    sync::atomic::{AtomicU64, Ordering},
+    task::{ready, Context, Poll},
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
 use sqlx::{SqlitePool, Error};
+use tokio::time::Timeout;
+use tracing::warn;
+
+use crate::{config::settings::DatabaseSettings, error::DatabaseErrorKind};
+
+/// Attempts made by `retry_on_busy` before the busy error is returned to the caller
+pub const BUSY_RETRY_ATTEMPTS: u32 = 3;
+
+/// Delay before the first retry; doubled on each further attempt
+pub const BUSY_RETRY_BASE_DELAY: Duration = Duration::from_millis(50);
 
 /// Get a connection from the pool (for explicit connection management, if needed)
 pub async fn get_connection(pool: &SqlitePool) -> Result<sqlx::pool::PoolConnection<sqlx::Sqlite>, Error> {
@@ -9,4 +29,121 @@ pub async fn get_connection(pool: &SqlitePool) -> Result<sqlx::pool::PoolConnect
 pub async fn health_check(pool: &SqlitePool) -> Result<(), Error> {
     sqlx::query("SELECT 1;").execute(pool).await?;
     Ok(())
-} 
\ No newline at end of file
+}
+
+/// Run a single pool-level statement, retrying with backoff while SQLite reports busy/locked
+///
+/// Only use this for statements that run outside a transaction: a busy error inside
+/// a transaction means the whole transaction has to be restarted.
+pub async fn retry_on_busy<T, F, Fut>(mut operation: F) -> Result<T, Error>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, Error>>,
+{
+    let mut attempt = 1;
+    loop {
+        match operation().await {
+            Err(e) if attempt < BUSY_RETRY_ATTEMPTS && DatabaseErrorKind::of(&e).is_retryable() => {
+                let delay = BUSY_RETRY_BASE_DELAY * 2u32.pow(attempt - 1);
+                warn!("Database busy (attempt {}/{}), retrying in {:?}: {}", attempt, BUSY_RETRY_ATTEMPTS, delay, e);
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+// Limits applied by `TimedQuery::timed`, replaced from settings at startup
+static QUERY_TIMEOUT_MS: AtomicU64 = AtomicU64::new(30_000);
+static SLOW_QUERY_MS: AtomicU64 = AtomicU64::new(500);
+
+static QUERIES: AtomicU64 = AtomicU64::new(0);
+static SLOW_QUERIES: AtomicU64 = AtomicU64::new(0);
+static TIMED_OUT_QUERIES: AtomicU64 = AtomicU64::new(0);
+
+/// Apply `query_timeout_secs` and `slow_query_ms` to every timed repository query
+pub fn configure_query_limits(settings: &DatabaseSettings) {
+    QUERY_TIMEOUT_MS.store(settings.query_timeout_secs.saturating_mul(1000), Ordering::Relaxed);
+    SLOW_QUERY_MS.store(settings.slow_query_ms, Ordering::Relaxed);
+}
+
+/// Counts of timed repository queries since startup
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct QueryMetrics {
+    pub queries: u64,
+    /// Queries that finished but took at least `slow_query_ms`
+    pub slow_queries: u64,
+    pub timed_out_queries: u64,
+}
+
+pub fn query_metrics() -> QueryMetrics {
+    QueryMetrics {
+        queries: QUERIES.load(Ordering::Relaxed),
+        slow_queries: SLOW_QUERIES.load(Ordering::Relaxed),
+        timed_out_queries: TIMED_OUT_QUERIES.load(Ordering::Relaxed),
+    }
+}
+
+/// Statement timeout and slow-query logging for repository queries
+///
+/// SQLite has no statement timeout of its own, so the query future is dropped
+/// once `query_timeout_secs` passes and the caller gets an `io::ErrorKind::TimedOut`
+/// error (`DatabaseErrorKind::Timeout`). Slow and timed-out queries are logged with
+/// their label and the number of bound parameters; parameter values never are.
+pub trait TimedQuery: Future + Sized {
+    fn timed(self, label: &'static str, bound_params: usize) -> Timed<Self>;
+}
+
+impl<T, F> TimedQuery for F
+where
+    F: Future<Output = Result<T, Error>>,
+{
+    fn timed(self, label: &'static str, bound_params: usize) -> Timed<Self> {
+        let timeout = Duration::from_millis(QUERY_TIMEOUT_MS.load(Ordering::Relaxed));
+        Timed {
+            query: Box::pin(tokio::time::timeout(timeout, self)),
+            timeout,
+            label,
+            bound_params,
+            started: Instant::now(),
+        }
+    }
+}
+
+/// Future returned by `TimedQuery::timed`
+pub struct Timed<F> {
+    query: Pin<Box<Timeout<F>>>,
+    timeout: Duration,
+    label: &'static str,
+    bound_params: usize,
+    started: Instant,
+}
+
+impl<T, F> Future for Timed<F>
+where
+    F: Future<Output = Result<T, Error>>,
+{
+    type Output = Result<T, Error>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let result = ready!(self.query.as_mut().poll(cx));
+        let elapsed = self.started.elapsed();
+        QUERIES.fetch_add(1, Ordering::Relaxed);
+
+        let Ok(result) = result else {
+            TIMED_OUT_QUERIES.fetch_add(1, Ordering::Relaxed);
+            warn!("Query {} timed out after {:?} ({} bound parameters)", self.label, self.timeout, self.bound_params);
+            return Poll::Ready(Err(Error::Io(io::Error::new(
+                io::ErrorKind::TimedOut,
+                format!("query {} timed out after {:?}", self.label, self.timeout),
+            ))));
+        };
+
+        if elapsed >= Duration::from_millis(SLOW_QUERY_MS.load(Ordering::Relaxed)) {
+            SLOW_QUERIES.fetch_add(1, Ordering::Relaxed);
+            warn!("Slow query {} took {:?} ({} bound parameters)", self.label, elapsed, self.bound_params);
+        }
+        Poll::Ready(result)
+    }
+}
@@ -0,0 +1,238 @@
+use std::collections::BTreeMap;
+
+use sqlx::{Error, Sqlite, SqlitePool, Transaction};
+
+use crate::models::dataset_meta::{DatasetMetaEntry, META_DATASET_VERSION, META_MAINTENANCE_MODE};
+use crate::repositories::connection::{retry_on_busy, TimedQuery};
+
+/// Tables reported by the dataset metadata endpoint
+pub const DATASET_TABLES: &[&str] = &[
+    "runs",
+    "performanceResult",
+    "AppDetails",
+    "AppRelease",
+    "SystemInfo",
+    "Libraries",
+    "GPU",
+    "RunMoreDetails",
+    "ModelMap",
+    "ModelMapHash",
+    "GPUMap",
+    "GPUBase",
+    "GPUSpec",
+    "RunScore",
+    "gpu_prices",
+    "tags",
+    "run_tags",
+];
+
+/// Tables in `DATASET_TABLES` whose rows belong to a run, and so to its tenant
+const RUN_CHILD_TABLES: &[&str] = &[
+    "performanceResult",
+    "AppDetails",
+    "AppRelease",
+    "SystemInfo",
+    "Libraries",
+    "GPU",
+    "RunMoreDetails",
+    "RunScore",
+    "run_tags",
+];
+
+pub struct DatasetMetaRepository {
+    pool: SqlitePool,
+}
+
+impl DatasetMetaRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Fetch every metadata entry
+    pub async fn find_all(&self) -> Result<Vec<DatasetMetaEntry>, Error> {
+        let results = sqlx::query_as!(
+            DatasetMetaEntry,
+            r#"
+            SELECT key AS "key!", value, updated_at
+            FROM DatasetMeta
+            ORDER BY key
+            "#
+        )
+        .fetch_all(&self.pool)
+        .timed("dataset_meta.find_all", 0)
+        .await?;
+
+        Ok(results)
+    }
+
+    /// Fetch one metadata entry
+    pub async fn find_by_key(&self, key: &str) -> Result<Option<DatasetMetaEntry>, Error> {
+        let result = sqlx::query_as!(
+            DatasetMetaEntry,
+            r#"
+            SELECT key AS "key!", value, updated_at
+            FROM DatasetMeta
+            WHERE key = ?
+            "#,
+            key
+        )
+        .fetch_optional(&self.pool)
+        .timed("dataset_meta.find_by_key", 1)
+        .await?;
+
+        Ok(result)
+    }
+
+    /// Whether maintenance mode is on, which HTTP writes and scheduled jobs both respect
+    pub async fn maintenance_mode(&self) -> Result<bool, Error> {
+        let entry = self.find_by_key(META_MAINTENANCE_MODE).await?;
+
+        Ok(entry.is_some_and(|entry| entry.value.as_deref() == Some("true")))
+    }
+
+    /// The current dataset version, 0 before the first upload or pipeline run
+    pub async fn current_version(&self) -> Result<i64, Error> {
+        let version = self
+            .find_by_key(META_DATASET_VERSION)
+            .await?
+            .and_then(|entry| entry.value)
+            .and_then(|version| version.parse().ok())
+            .unwrap_or(0);
+
+        Ok(version)
+    }
+
+    /// Set a metadata value
+    pub async fn set(&self, key: &str, value: Option<&str>, updated_at: &str) -> Result<(), Error> {
+        retry_on_busy(|| sqlx::query!(
+            r#"
+            INSERT INTO DatasetMeta (key, value, updated_at)
+            VALUES (?, ?, ?)
+            ON CONFLICT (key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at
+            "#,
+            key,
+            value,
+            updated_at
+        )
+        .execute(&self.pool)
+        .timed("dataset_meta.set", 3))
+        .await?;
+
+        Ok(())
+    }
+
+    /// Set a metadata value within a transaction
+    pub async fn set_tx(&self, key: &str, value: Option<&str>, updated_at: &str, tx: &mut Transaction<'_, Sqlite>) -> Result<(), Error> {
+        sqlx::query!(
+            r#"
+            INSERT INTO DatasetMeta (key, value, updated_at)
+            VALUES (?, ?, ?)
+            ON CONFLICT (key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at
+            "#,
+            key,
+            value,
+            updated_at
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Increment the dataset version within a transaction and return the new version
+    pub async fn increment_version_tx(&self, updated_at: &str, tx: &mut Transaction<'_, Sqlite>) -> Result<i64, Error> {
+        let version = sqlx::query_scalar!(
+            r#"
+            INSERT INTO DatasetMeta (key, value, updated_at)
+            VALUES (?1, '1', ?2)
+            ON CONFLICT (key) DO UPDATE
+            SET value = CAST(COALESCE(CAST(value AS INTEGER), 0) + 1 AS TEXT), updated_at = excluded.updated_at
+            RETURNING CAST(value AS INTEGER) AS "version!: i64"
+            "#,
+            META_DATASET_VERSION,
+            updated_at
+        )
+        .fetch_one(&mut **tx)
+        .await?;
+
+        Ok(version)
+    }
+
+    /// Count the rows of every table in `DATASET_TABLES`
+    pub async fn count_table_rows(&self) -> Result<BTreeMap<String, i64>, Error> {
+        let mut counts = BTreeMap::new();
+
+        for table in DATASET_TABLES {
+            // Table names come from the constant list above, never from user input
+            let count: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM {}", table))
+                .fetch_one(&self.pool)
+                .timed("dataset_meta.count_table_rows", 0)
+                .await?;
+            counts.insert(table.to_string(), count);
+        }
+
+        Ok(counts)
+    }
+
+    /// Count the rows of every table in `DATASET_TABLES` as a tenant sees them
+    ///
+    /// Runs and their child rows are counted for the tenant only; reference
+    /// tables such as the maps and tags are shared, so they are counted whole.
+    pub async fn count_tenant_table_rows(&self, tenant_id: &str) -> Result<BTreeMap<String, i64>, Error> {
+        let mut counts = BTreeMap::new();
+
+        for table in DATASET_TABLES {
+            // Table names come from the constant lists above, never from user input
+            let count: i64 = if *table == "runs" {
+                sqlx::query_scalar("SELECT COUNT(*) FROM runs WHERE tenant_id = ?")
+                    .bind(tenant_id)
+                    .fetch_one(&self.pool)
+                    .timed("dataset_meta.count_tenant_table_rows", 1)
+                    .await?
+            } else if RUN_CHILD_TABLES.contains(table) {
+                sqlx::query_scalar(&format!(
+                    "SELECT COUNT(*) FROM {} WHERE run_id IN (SELECT id FROM runs WHERE tenant_id = ?)",
+                    table
+                ))
+                .bind(tenant_id)
+                .fetch_one(&self.pool)
+                .timed("dataset_meta.count_tenant_table_rows", 1)
+                .await?
+            } else {
+                sqlx::query_scalar(&format!("SELECT COUNT(*) FROM {}", table))
+                    .fetch_one(&self.pool)
+                    .timed("dataset_meta.count_tenant_table_rows", 0)
+                    .await?
+            };
+            counts.insert(table.to_string(), count);
+        }
+
+        Ok(counts)
+    }
+
+    /// Row count of a table as of the last `analyze`, `None` if it was never analyzed
+    pub async fn estimated_row_count(&self, table: &str) -> Result<Option<i64>, Error> {
+        let analyzed: bool = sqlx::query_scalar("SELECT COUNT(*) > 0 FROM sqlite_master WHERE name = 'sqlite_stat1'")
+            .fetch_one(&self.pool)
+            .timed("dataset_meta.estimated_row_count", 0)
+            .await?;
+        if !analyzed {
+            return Ok(None);
+        }
+
+        // The first number of every stat of a table is its row count
+        let stat: Option<String> = sqlx::query_scalar("SELECT stat FROM sqlite_stat1 WHERE tbl = ? LIMIT 1")
+            .bind(table)
+            .fetch_optional(&self.pool)
+            .timed("dataset_meta.estimated_row_count", 1)
+            .await?;
+
+        Ok(stat.and_then(|stat| stat.split_whitespace().next()?.parse().ok()))
+    }
+
+    /// Refresh the table statistics behind `estimated_row_count` and the query planner
+    pub async fn analyze(&self) -> Result<(), Error> {
+        retry_on_busy(|| sqlx::query("ANALYZE").execute(&self.pool)).await?;
+        Ok(())
+    }
+}
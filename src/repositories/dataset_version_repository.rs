@@ -0,0 +1,58 @@
+use sqlx::{Error, SqlitePool};
+
+use crate::models::dataset_version::{DatasetVersion, ReferenceRevisions};
+use crate::repositories::audit_timestamp;
+use crate::repositories::connection::{retry_on_busy, TimedQuery};
+
+pub struct DatasetVersionRepository {
+    pool: SqlitePool,
+}
+
+impl DatasetVersionRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Record a pipeline step and the reference revisions it ran against, returning the version id
+    pub async fn create(&self, pipeline_step: &str, revisions: &ReferenceRevisions) -> Result<i64, Error> {
+        let now = audit_timestamp();
+
+        let id = retry_on_busy(|| sqlx::query!(
+            r#"
+            INSERT INTO dataset_versions (pipeline_step, model_map_hash, gpu_map_hash, gpu_base_hash, rules_hash, created_at)
+            VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+            pipeline_step,
+            revisions.model_map_hash,
+            revisions.gpu_map_hash,
+            revisions.gpu_base_hash,
+            revisions.rules_hash,
+            now
+        )
+        .execute(&self.pool)
+        .timed("dataset_versions.create", 6))
+        .await?
+        .last_insert_rowid();
+
+        Ok(id)
+    }
+
+    /// The most recent versions, newest first
+    pub async fn find_recent(&self, limit: i64) -> Result<Vec<DatasetVersion>, Error> {
+        let results = sqlx::query_as!(
+            DatasetVersion,
+            r#"
+            SELECT id AS "id!", pipeline_step, model_map_hash, gpu_map_hash, gpu_base_hash, rules_hash, created_at
+            FROM dataset_versions
+            ORDER BY id DESC
+            LIMIT ?
+            "#,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .timed("dataset_versions.find_recent", 1)
+        .await?;
+
+        Ok(results)
+    }
+}
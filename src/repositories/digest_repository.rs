@@ -0,0 +1,124 @@
+use sqlx::{Error, SqlitePool};
+
+use crate::models::digest::{ItsImprovement, NewGpu, UserActivity};
+use crate::repositories::connection::TimedQuery;
+
+/// Changes to a tenant's dataset since a point in time, by ingest time (`runs.created_at`)
+///
+/// Runs held for moderation are left out, as in the feed.
+pub struct DigestRepository {
+    pool: SqlitePool,
+}
+
+impl DigestRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Runs ingested at or after `since`
+    pub async fn count_new_runs(&self, tenant_id: &str, since: &str) -> Result<i64, Error> {
+        let count = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) AS "count!: i64"
+            FROM runs r
+            WHERE r.tenant_id = ?
+              AND r.created_at >= ?
+              AND r.id NOT IN (SELECT run_id FROM moderation_queue WHERE status = 'pending')
+            "#,
+            tenant_id,
+            since
+        )
+        .fetch_one(&self.pool)
+        .timed("digest.count_new_runs", 2)
+        .await?;
+
+        Ok(count)
+    }
+
+    /// GPUs with no run ingested before `since`, most runs first
+    pub async fn find_new_gpus(&self, tenant_id: &str, since: &str, limit: i64) -> Result<Vec<NewGpu>, Error> {
+        let results = sqlx::query_as!(
+            NewGpu,
+            r#"
+            SELECT g.device AS "device!", COUNT(DISTINCT r.id) AS "runs!: i64"
+            FROM GPU g
+            JOIN runs r ON r.id = g.run_id
+            WHERE r.tenant_id = ?
+              AND g.device IS NOT NULL AND TRIM(g.device) <> ''
+              AND r.id NOT IN (SELECT run_id FROM moderation_queue WHERE status = 'pending')
+            GROUP BY g.device
+            HAVING MIN(r.created_at) >= ?
+            ORDER BY COUNT(DISTINCT r.id) DESC, g.device
+            LIMIT ?
+            "#,
+            tenant_id,
+            since,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .timed("digest.find_new_gpus", 3)
+        .await?;
+
+        Ok(results)
+    }
+
+    /// GPUs whose best avg_its since `since` beats their best before, largest relative gain first
+    pub async fn find_its_improvements(&self, tenant_id: &str, since: &str, limit: i64) -> Result<Vec<ItsImprovement>, Error> {
+        let results = sqlx::query_as!(
+            ItsImprovement,
+            r#"
+            SELECT device AS "device!", previous_best_its AS "previous_best_its!: f64", best_its AS "best_its!: f64"
+            FROM (
+                SELECT
+                    g.device,
+                    MAX(CASE WHEN r.created_at < ?1 THEN pr.avg_its END) AS previous_best_its,
+                    MAX(CASE WHEN r.created_at >= ?1 THEN pr.avg_its END) AS best_its
+                FROM GPU g
+                JOIN runs r ON r.id = g.run_id
+                JOIN performanceResult pr ON pr.run_id = r.id
+                WHERE r.tenant_id = ?3
+                  AND g.device IS NOT NULL AND pr.avg_its IS NOT NULL
+                  AND r.id NOT IN (SELECT run_id FROM moderation_queue WHERE status = 'pending')
+                GROUP BY g.device
+            )
+            WHERE previous_best_its > 0 AND best_its > previous_best_its
+            ORDER BY best_its / previous_best_its DESC, device
+            LIMIT ?2
+            "#,
+            since,
+            limit,
+            tenant_id
+        )
+        .fetch_all(&self.pool)
+        .timed("digest.find_its_improvements", 3)
+        .await?;
+
+        Ok(results)
+    }
+
+    /// Submitters by the runs they had ingested since `since`, most first
+    pub async fn find_active_users(&self, tenant_id: &str, since: &str, limit: i64) -> Result<Vec<UserActivity>, Error> {
+        let results = sqlx::query_as!(
+            UserActivity,
+            r#"
+            SELECT r.user AS "user!", COUNT(*) AS "runs!: i64"
+            FROM runs r
+            WHERE r.tenant_id = ?
+              AND r.created_at >= ?
+              AND r.user IS NOT NULL AND TRIM(r.user) <> ''
+              AND r.id NOT IN (SELECT run_id FROM moderation_queue WHERE status = 'pending')
+            GROUP BY r.user
+            ORDER BY COUNT(*) DESC, r.user
+            LIMIT ?
+            "#,
+            tenant_id,
+            since,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .timed("digest.find_active_users", 3)
+        .await?;
+
+        Ok(results)
+    }
+}
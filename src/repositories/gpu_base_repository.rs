@@ -1,8 +1,11 @@
 use async_trait::async_trait;
 use sqlx::{Error, SqlitePool, Transaction, Sqlite};
 
-use crate::models::gpu_base::GpuBase;
+use crate::models::gpu_base::{ArchitectureStats, GpuBase};
 use crate::repositories::traits::{Repository, TransactionRepository, BulkRepository, BulkTransactionRepository};
+use crate::repositories::audit_timestamp;
+use crate::repositories::connection::{retry_on_busy, TimedQuery};
+use crate::repositories::query_builder::Filter;
 
 pub struct GpuBaseRepository {
     pool: SqlitePool,
@@ -18,7 +21,7 @@ impl GpuBaseRepository {
         let results = sqlx::query_as!(
             GpuBase,
             r#"
-            SELECT id, name, brand
+            SELECT id, name, brand, architecture, generation, version, created_at, updated_at
             FROM GPUBase
             WHERE name = ?
             ORDER BY id DESC
@@ -26,17 +29,35 @@ impl GpuBaseRepository {
             name
         )
         .fetch_all(&self.pool)
+        .timed("gpu_base.find_by_name", 1)
         .await?;
 
         Ok(results)
     }
 
+    /// Find a GPU base by its exact name within a transaction
+    pub async fn find_by_name_tx(&self, name: &str, tx: &mut Transaction<'_, Sqlite>) -> Result<Option<GpuBase>, Error> {
+        let result = sqlx::query_as!(
+            GpuBase,
+            r#"
+            SELECT id, name, brand, architecture, generation, version, created_at, updated_at
+            FROM GPUBase
+            WHERE name = ?
+            "#,
+            name
+        )
+        .fetch_optional(&mut **tx)
+        .await?;
+
+        Ok(result)
+    }
+
     /// Find GPU base by brand
     pub async fn find_by_brand(&self, brand: &str) -> Result<Vec<GpuBase>, Error> {
         let results = sqlx::query_as!(
             GpuBase,
             r#"
-            SELECT id, name, brand
+            SELECT id, name, brand, architecture, generation, version, created_at, updated_at
             FROM GPUBase
             WHERE brand = ?
             ORDER BY id DESC
@@ -44,6 +65,59 @@ impl GpuBaseRepository {
             brand
         )
         .fetch_all(&self.pool)
+        .timed("gpu_base.find_by_brand", 1)
+        .await?;
+
+        Ok(results)
+    }
+
+    /// Average ITS of a tenant's runs per brand, architecture and generation, oldest generation first
+    ///
+    /// Runs are attributed to a base GPU through GPU.device -> GPUMap.gpu_name.
+    /// Multi-GPU runs and base GPUs without a known architecture are left out. When `tag` is given
+    /// only runs carrying that tag are counted, and runs carrying `exclude_tag` are not. Runs on
+    /// integrated GPUs are left out unless `include_integrated` is set.
+    pub async fn its_by_architecture(
+        &self,
+        tenant_id: &str,
+        brand: Option<&str>,
+        tag: Option<&str>,
+        exclude_tag: Option<&str>,
+        include_integrated: bool,
+    ) -> Result<Vec<ArchitectureStats>, Error> {
+        let results = sqlx::query_as!(
+            ArchitectureStats,
+            r#"
+            SELECT
+                gb.brand AS "brand?",
+                gb.architecture AS "architecture?",
+                gb.generation AS "generation?",
+                MIN(gs.launch_year) AS "first_launch_year?: i64",
+                COUNT(DISTINCT gb.id) AS "gpu_count!: i64",
+                COUNT(DISTINCT pr.run_id) AS "run_count!: i64",
+                AVG(pr.avg_its) AS "avg_its?: f64"
+            FROM GPUBase gb
+            LEFT JOIN GPUSpec gs ON gs.base_gpu_id = gb.id
+            LEFT JOIN GPUMap gm ON gm.base_gpu_id = gb.id
+            LEFT JOIN GPU g ON g.device = gm.gpu_name
+                AND NOT EXISTS (SELECT 1 FROM GPU mg WHERE mg.run_id = g.run_id AND mg.gpu_index > 0)
+                AND (?3 OR g.is_integrated IS NOT 1)
+                AND g.run_id IN (SELECT r.id FROM runs r WHERE r.tenant_id = ?5)
+            LEFT JOIN performanceResult pr ON pr.run_id = g.run_id
+                AND (?2 IS NULL OR pr.run_id IN (SELECT rt.run_id FROM run_tags rt JOIN tags t ON t.id = rt.tag_id WHERE t.name = ?2))
+                AND (?4 IS NULL OR pr.run_id NOT IN (SELECT rt.run_id FROM run_tags rt JOIN tags t ON t.id = rt.tag_id WHERE t.name = ?4))
+            WHERE gb.architecture IS NOT NULL AND (?1 IS NULL OR gb.brand = ?1)
+            GROUP BY gb.brand, gb.architecture, gb.generation
+            ORDER BY gb.brand, MIN(gs.launch_year), gb.architecture, gb.generation
+            "#,
+            brand,
+            tag,
+            include_integrated,
+            exclude_tag,
+            tenant_id
+        )
+        .fetch_all(&self.pool)
+        .timed("gpu_base.its_by_architecture", 5)
         .await?;
 
         Ok(results)
@@ -53,20 +127,30 @@ impl GpuBaseRepository {
 #[async_trait]
 impl Repository<GpuBase, i64> for GpuBaseRepository {
     async fn create(&self, entity: GpuBase) -> Result<GpuBase, Error> {
-        let id = sqlx::query!(
+        let now = audit_timestamp();
+
+        let id = retry_on_busy(|| sqlx::query!(
             r#"
-            INSERT INTO GPUBase (name, brand)
-            VALUES (?, ?)
+            INSERT INTO GPUBase (name, brand, architecture, generation, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?)
             "#,
             entity.name,
-            entity.brand
+            entity.brand,
+            entity.architecture,
+            entity.generation,
+            now,
+            now
         )
         .execute(&self.pool)
+        .timed("gpu_base.create", 6))
         .await?
         .last_insert_rowid() as i64;
 
         Ok(GpuBase {
             id: Some(id),
+            created_at: Some(now.clone()),
+            updated_at: Some(now),
+            version: 1,
             ..entity
         })
     }
@@ -75,13 +159,14 @@ impl Repository<GpuBase, i64> for GpuBaseRepository {
         let result = sqlx::query_as!(
             GpuBase,
             r#"
-            SELECT id, name, brand
+            SELECT id, name, brand, architecture, generation, version, created_at, updated_at
             FROM GPUBase
             WHERE id = ?
             "#,
             id
         )
         .fetch_optional(&self.pool)
+        .timed("gpu_base.find_by_id", 1)
         .await?;
 
         Ok(result)
@@ -91,12 +176,13 @@ impl Repository<GpuBase, i64> for GpuBaseRepository {
         let results = sqlx::query_as!(
             GpuBase,
             r#"
-            SELECT id, name, brand
+            SELECT id, name, brand, architecture, generation, version, created_at, updated_at
             FROM GPUBase
             ORDER BY id DESC
             "#
         )
         .fetch_all(&self.pool)
+        .timed("gpu_base.find_all", 0)
         .await?;
 
         Ok(results)
@@ -104,26 +190,43 @@ impl Repository<GpuBase, i64> for GpuBaseRepository {
 
     async fn update(&self, entity: GpuBase) -> Result<GpuBase, Error> {
         let id = entity.id.ok_or(Error::RowNotFound)?;
-        
-        sqlx::query!(
+        let now = audit_timestamp();
+
+        let rows_affected = retry_on_busy(|| sqlx::query!(
             r#"
             UPDATE GPUBase
-            SET name = ?, brand = ?
-            WHERE id = ?
+            SET name = ?, brand = ?, architecture = ?, generation = ?, updated_at = ?, version = version + 1
+            WHERE id = ? AND version = ?
             "#,
             entity.name,
             entity.brand,
-            id
+            entity.architecture,
+            entity.generation,
+            now,
+            id,
+            entity.version
         )
         .execute(&self.pool)
-        .await?;
+        .timed("gpu_base.update", 7))
+        .await?
+        .rows_affected();
+
+        // Either the row is gone or someone else updated it since it was read
+        if rows_affected == 0 {
+            return Err(Error::RowNotFound);
+        }
 
-        Ok(entity)
+        Ok(GpuBase {
+            version: entity.version + 1,
+            updated_at: Some(now),
+            ..entity
+        })
     }
 
     async fn delete(&self, id: i64) -> Result<(), Error> {
-        sqlx::query!("DELETE FROM GPUBase WHERE id = ?", id)
+        retry_on_busy(|| sqlx::query!("DELETE FROM GPUBase WHERE id = ?", id)
             .execute(&self.pool)
+            .timed("gpu_base.delete", 1))
             .await?;
         Ok(())
     }
@@ -131,22 +234,46 @@ impl Repository<GpuBase, i64> for GpuBaseRepository {
     async fn count(&self) -> Result<i64, Error> {
         let count = sqlx::query!("SELECT COUNT(*) as count FROM GPUBase")
             .fetch_one(&self.pool)
+            .timed("gpu_base.count", 0)
             .await?
             .count;
         Ok(count)
     }
+
+    async fn exists(&self, id: i64) -> Result<bool, Error> {
+        let exists = sqlx::query_scalar!(r#"SELECT EXISTS(SELECT 1 FROM GPUBase WHERE id = ?) AS "exists!: bool""#, id)
+            .fetch_one(&self.pool)
+            .timed("gpu_base.exists", 1)
+            .await?;
+        Ok(exists)
+    }
+
+    async fn count_where(&self, filter: &Filter) -> Result<i64, Error> {
+        let count: i64 = sqlx::query_scalar(&filter.count_sql("GPUBase")?)
+            .bind(&filter.value)
+            .fetch_one(&self.pool)
+            .timed("gpu_base.count_where", 1)
+            .await?;
+        Ok(count)
+    }
 }
 
 #[async_trait]
 impl<'a> TransactionRepository<'a, GpuBase, i64> for GpuBaseRepository {
     async fn create_tx(&self, entity: GpuBase, tx: &mut Transaction<'a, Sqlite>) -> Result<GpuBase, Error> {
+        let now = audit_timestamp();
+
         let id = sqlx::query!(
             r#"
-            INSERT INTO GPUBase (name, brand)
-            VALUES (?, ?)
+            INSERT INTO GPUBase (name, brand, architecture, generation, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?)
             "#,
             entity.name,
-            entity.brand
+            entity.brand,
+            entity.architecture,
+            entity.generation,
+            now,
+            now
         )
         .execute(&mut **tx)
         .await?
@@ -154,27 +281,45 @@ impl<'a> TransactionRepository<'a, GpuBase, i64> for GpuBaseRepository {
 
         Ok(GpuBase {
             id: Some(id),
+            created_at: Some(now.clone()),
+            updated_at: Some(now),
+            version: 1,
             ..entity
         })
     }
 
     async fn update_tx(&self, entity: GpuBase, tx: &mut Transaction<'a, Sqlite>) -> Result<GpuBase, Error> {
         let id = entity.id.ok_or(Error::RowNotFound)?;
-        
-        sqlx::query!(
+        let now = audit_timestamp();
+
+        let rows_affected = sqlx::query!(
             r#"
             UPDATE GPUBase
-            SET name = ?, brand = ?
-            WHERE id = ?
+            SET name = ?, brand = ?, architecture = ?, generation = ?, updated_at = ?, version = version + 1
+            WHERE id = ? AND version = ?
             "#,
             entity.name,
             entity.brand,
-            id
+            entity.architecture,
+            entity.generation,
+            now,
+            id,
+            entity.version
         )
         .execute(&mut **tx)
-        .await?;
+        .await?
+        .rows_affected();
+
+        // Either the row is gone or someone else updated it since it was read
+        if rows_affected == 0 {
+            return Err(Error::RowNotFound);
+        }
 
-        Ok(entity)
+        Ok(GpuBase {
+            version: entity.version + 1,
+            updated_at: Some(now),
+            ..entity
+        })
     }
 
     async fn delete_tx(&self, id: i64, tx: &mut Transaction<'a, Sqlite>) -> Result<(), Error> {
@@ -1,8 +1,11 @@
 use async_trait::async_trait;
 use sqlx::{Error, SqlitePool, Transaction, Sqlite};
 
-use crate::models::gpu_map::GpuMap;
+use crate::models::gpu_map::{GpuMap, GpuMapExportRow};
 use crate::repositories::traits::{Repository, TransactionRepository, BulkRepository, BulkTransactionRepository};
+use crate::repositories::audit_timestamp;
+use crate::repositories::connection::{retry_on_busy, TimedQuery};
+use crate::repositories::query_builder::Filter;
 
 pub struct GpuMapRepository {
     pool: SqlitePool,
@@ -18,7 +21,7 @@ impl GpuMapRepository {
         let results = sqlx::query_as!(
             GpuMap,
             r#"
-            SELECT id, gpu_name, base_gpu_id
+            SELECT id, gpu_name, base_gpu_id, version, created_at, updated_at
             FROM GPUMap
             WHERE gpu_name = ?
             ORDER BY id DESC
@@ -26,6 +29,7 @@ impl GpuMapRepository {
             gpu_name
         )
         .fetch_all(&self.pool)
+        .timed("gpu_map.find_by_gpu_name", 1)
         .await?;
 
         Ok(results)
@@ -36,7 +40,7 @@ impl GpuMapRepository {
         let results = sqlx::query_as!(
             GpuMap,
             r#"
-            SELECT id, gpu_name, base_gpu_id
+            SELECT id, gpu_name, base_gpu_id, version, created_at, updated_at
             FROM GPUMap
             WHERE base_gpu_id = ?
             ORDER BY id DESC
@@ -44,29 +48,86 @@ impl GpuMapRepository {
             base_gpu_id
         )
         .fetch_all(&self.pool)
+        .timed("gpu_map.find_by_base_gpu_id", 1)
         .await?;
 
         Ok(results)
     }
+
+    /// Every GPU mapping with its base GPU name and how many GPU rows report that device
+    pub async fn export_with_usage(&self) -> Result<Vec<GpuMapExportRow>, Error> {
+        let results = sqlx::query_as!(
+            GpuMapExportRow,
+            r#"
+            SELECT
+                gm.gpu_name,
+                gm.base_gpu_id,
+                gb.name AS "base_gpu_name?",
+                (SELECT COUNT(*) FROM GPU g WHERE g.device = gm.gpu_name) AS "usage_count!: i64",
+                gm.id AS "id!: i64",
+                gm.version
+            FROM GPUMap gm
+            LEFT JOIN GPUBase gb ON gb.id = gm.base_gpu_id
+            ORDER BY gm.gpu_name
+            "#
+        )
+        .fetch_all(&self.pool)
+        .timed("gpu_map.export_with_usage", 0)
+        .await?;
+
+        Ok(results)
+    }
+
+    /// Rename the mapping of a GPU name within a transaction, returning the rows changed
+    ///
+    /// Left alone when the new name is already mapped, so merging spellings
+    /// never produces two mappings for one name.
+    pub async fn rename_gpu_name_tx(&self, from: &str, to: &str, tx: &mut Transaction<'_, Sqlite>) -> Result<u64, Error> {
+        let now = audit_timestamp();
+
+        let result = sqlx::query!(
+            r#"
+            UPDATE GPUMap
+            SET gpu_name = ?1, version = version + 1, updated_at = ?2
+            WHERE gpu_name = ?3
+              AND NOT EXISTS (SELECT 1 FROM GPUMap WHERE gpu_name = ?1)
+            "#,
+            to,
+            now,
+            from
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
 }
 
 #[async_trait]
 impl Repository<GpuMap, i64> for GpuMapRepository {
     async fn create(&self, entity: GpuMap) -> Result<GpuMap, Error> {
-        let id = sqlx::query!(
+        let now = audit_timestamp();
+
+        let id = retry_on_busy(|| sqlx::query!(
             r#"
-            INSERT INTO GPUMap (gpu_name, base_gpu_id)
-            VALUES (?, ?)
+            INSERT INTO GPUMap (gpu_name, base_gpu_id, created_at, updated_at)
+            VALUES (?, ?, ?, ?)
             "#,
             entity.gpu_name,
-            entity.base_gpu_id
+            entity.base_gpu_id,
+            now,
+            now
         )
         .execute(&self.pool)
+        .timed("gpu_map.create", 4))
         .await?
         .last_insert_rowid() as i64;
 
         Ok(GpuMap {
             id: Some(id),
+            created_at: Some(now.clone()),
+            updated_at: Some(now),
+            version: 1,
             ..entity
         })
     }
@@ -75,13 +136,14 @@ impl Repository<GpuMap, i64> for GpuMapRepository {
         let result = sqlx::query_as!(
             GpuMap,
             r#"
-            SELECT id, gpu_name, base_gpu_id
+            SELECT id, gpu_name, base_gpu_id, version, created_at, updated_at
             FROM GPUMap
             WHERE id = ?
             "#,
             id
         )
         .fetch_optional(&self.pool)
+        .timed("gpu_map.find_by_id", 1)
         .await?;
 
         Ok(result)
@@ -91,12 +153,13 @@ impl Repository<GpuMap, i64> for GpuMapRepository {
         let results = sqlx::query_as!(
             GpuMap,
             r#"
-            SELECT id, gpu_name, base_gpu_id
+            SELECT id, gpu_name, base_gpu_id, version, created_at, updated_at
             FROM GPUMap
             ORDER BY id DESC
             "#
         )
         .fetch_all(&self.pool)
+        .timed("gpu_map.find_all", 0)
         .await?;
 
         Ok(results)
@@ -104,26 +167,41 @@ impl Repository<GpuMap, i64> for GpuMapRepository {
 
     async fn update(&self, entity: GpuMap) -> Result<GpuMap, Error> {
         let id = entity.id.ok_or(Error::RowNotFound)?;
-        
-        sqlx::query!(
+        let now = audit_timestamp();
+
+        let rows_affected = retry_on_busy(|| sqlx::query!(
             r#"
             UPDATE GPUMap
-            SET gpu_name = ?, base_gpu_id = ?
-            WHERE id = ?
+            SET gpu_name = ?, base_gpu_id = ?, updated_at = ?, version = version + 1
+            WHERE id = ? AND version = ?
             "#,
             entity.gpu_name,
             entity.base_gpu_id,
-            id
+            now,
+            id,
+            entity.version
         )
         .execute(&self.pool)
-        .await?;
+        .timed("gpu_map.update", 5))
+        .await?
+        .rows_affected();
 
-        Ok(entity)
+        // Either the row is gone or someone else updated it since it was read
+        if rows_affected == 0 {
+            return Err(Error::RowNotFound);
+        }
+
+        Ok(GpuMap {
+            version: entity.version + 1,
+            updated_at: Some(now),
+            ..entity
+        })
     }
 
     async fn delete(&self, id: i64) -> Result<(), Error> {
-        sqlx::query!("DELETE FROM GPUMap WHERE id = ?", id)
+        retry_on_busy(|| sqlx::query!("DELETE FROM GPUMap WHERE id = ?", id)
             .execute(&self.pool)
+            .timed("gpu_map.delete", 1))
             .await?;
         Ok(())
     }
@@ -131,22 +209,44 @@ impl Repository<GpuMap, i64> for GpuMapRepository {
     async fn count(&self) -> Result<i64, Error> {
         let count = sqlx::query!("SELECT COUNT(*) as count FROM GPUMap")
             .fetch_one(&self.pool)
+            .timed("gpu_map.count", 0)
             .await?
             .count;
         Ok(count)
     }
+
+    async fn exists(&self, id: i64) -> Result<bool, Error> {
+        let exists = sqlx::query_scalar!(r#"SELECT EXISTS(SELECT 1 FROM GPUMap WHERE id = ?) AS "exists!: bool""#, id)
+            .fetch_one(&self.pool)
+            .timed("gpu_map.exists", 1)
+            .await?;
+        Ok(exists)
+    }
+
+    async fn count_where(&self, filter: &Filter) -> Result<i64, Error> {
+        let count: i64 = sqlx::query_scalar(&filter.count_sql("GPUMap")?)
+            .bind(&filter.value)
+            .fetch_one(&self.pool)
+            .timed("gpu_map.count_where", 1)
+            .await?;
+        Ok(count)
+    }
 }
 
 #[async_trait]
 impl<'a> TransactionRepository<'a, GpuMap, i64> for GpuMapRepository {
     async fn create_tx(&self, entity: GpuMap, tx: &mut Transaction<'a, Sqlite>) -> Result<GpuMap, Error> {
+        let now = audit_timestamp();
+
         let id = sqlx::query!(
             r#"
-            INSERT INTO GPUMap (gpu_name, base_gpu_id)
-            VALUES (?, ?)
+            INSERT INTO GPUMap (gpu_name, base_gpu_id, created_at, updated_at)
+            VALUES (?, ?, ?, ?)
             "#,
             entity.gpu_name,
-            entity.base_gpu_id
+            entity.base_gpu_id,
+            now,
+            now
         )
         .execute(&mut **tx)
         .await?
@@ -154,27 +254,43 @@ impl<'a> TransactionRepository<'a, GpuMap, i64> for GpuMapRepository {
 
         Ok(GpuMap {
             id: Some(id),
+            created_at: Some(now.clone()),
+            updated_at: Some(now),
+            version: 1,
             ..entity
         })
     }
 
     async fn update_tx(&self, entity: GpuMap, tx: &mut Transaction<'a, Sqlite>) -> Result<GpuMap, Error> {
         let id = entity.id.ok_or(Error::RowNotFound)?;
-        
-        sqlx::query!(
+        let now = audit_timestamp();
+
+        let rows_affected = sqlx::query!(
             r#"
             UPDATE GPUMap
-            SET gpu_name = ?, base_gpu_id = ?
-            WHERE id = ?
+            SET gpu_name = ?, base_gpu_id = ?, updated_at = ?, version = version + 1
+            WHERE id = ? AND version = ?
             "#,
             entity.gpu_name,
             entity.base_gpu_id,
-            id
+            now,
+            id,
+            entity.version
         )
         .execute(&mut **tx)
-        .await?;
+        .await?
+        .rows_affected();
+
+        // Either the row is gone or someone else updated it since it was read
+        if rows_affected == 0 {
+            return Err(Error::RowNotFound);
+        }
 
-        Ok(entity)
+        Ok(GpuMap {
+            version: entity.version + 1,
+            updated_at: Some(now),
+            ..entity
+        })
     }
 
     async fn delete_tx(&self, id: i64, tx: &mut Transaction<'a, Sqlite>) -> Result<(), Error> {
@@ -0,0 +1,87 @@
+use sqlx::{Error, Sqlite, SqlitePool, Transaction};
+
+use crate::models::gpu_percentile::GpuPercentiles;
+use crate::repositories::connection::TimedQuery;
+
+/// The cached per-GPU avg_its percentiles of each tenant
+pub struct GpuPercentileRepository {
+    pool: SqlitePool,
+}
+
+impl GpuPercentileRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// A tenant's cached percentiles, optionally only of one GPU, fastest median first
+    pub async fn find_all(&self, tenant_id: &str, gpu: Option<&str>) -> Result<Vec<GpuPercentiles>, Error> {
+        let results = sqlx::query_as!(
+            GpuPercentiles,
+            r#"
+            SELECT gpu AS "gpu!", run_count, min_its, p10_its, p25_its, median_its, p75_its, p90_its, max_its, computed_at
+            FROM gpu_percentiles
+            WHERE tenant_id = ?1 AND (?2 IS NULL OR gpu = ?2 COLLATE NOCASE)
+            ORDER BY median_its DESC, gpu
+            "#,
+            tenant_id,
+            gpu
+        )
+        .fetch_all(&self.pool)
+        .timed("gpu_percentiles.find_all", 2)
+        .await?;
+
+        Ok(results)
+    }
+
+    /// When a tenant's cache was last refreshed, `None` if it never was
+    pub async fn last_computed_at(&self, tenant_id: &str) -> Result<Option<String>, Error> {
+        let computed_at = sqlx::query_scalar!(
+            r#"SELECT MAX(computed_at) AS "computed_at?: String" FROM gpu_percentiles WHERE tenant_id = ?"#,
+            tenant_id
+        )
+        .fetch_one(&self.pool)
+        .timed("gpu_percentiles.last_computed_at", 1)
+        .await?;
+
+        Ok(computed_at)
+    }
+
+    /// Empty the cache of every tenant within a transaction
+    pub async fn delete_all_tx(&self, tx: &mut Transaction<'_, Sqlite>) -> Result<(), Error> {
+        sqlx::query!("DELETE FROM gpu_percentiles").execute(&mut **tx).await?;
+
+        Ok(())
+    }
+
+    /// Cache a tenant's percentiles within a transaction
+    pub async fn insert_all_tx(
+        &self,
+        tenant_id: &str,
+        entries: &[GpuPercentiles],
+        tx: &mut Transaction<'_, Sqlite>,
+    ) -> Result<(), Error> {
+        for entry in entries {
+            sqlx::query!(
+                r#"
+                INSERT INTO gpu_percentiles (tenant_id, gpu, run_count, min_its, p10_its, p25_its, median_its, p75_its, p90_its, max_its, computed_at)
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                "#,
+                tenant_id,
+                entry.gpu,
+                entry.run_count,
+                entry.min_its,
+                entry.p10_its,
+                entry.p25_its,
+                entry.median_its,
+                entry.p75_its,
+                entry.p90_its,
+                entry.max_its,
+                entry.computed_at
+            )
+            .execute(&mut **tx)
+            .await?;
+        }
+
+        Ok(())
+    }
+}
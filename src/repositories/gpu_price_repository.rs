@@ -0,0 +1,259 @@
+use async_trait::async_trait;
+use sqlx::{Error, SqlitePool, Transaction, Sqlite};
+
+use crate::models::gpu_price::{GpuPrice, ItsPerDollar};
+use crate::repositories::traits::{Repository, TransactionRepository};
+use crate::repositories::audit_timestamp;
+use crate::repositories::connection::{retry_on_busy, TimedQuery};
+use crate::repositories::query_builder::Filter;
+
+pub struct GpuPriceRepository {
+    pool: SqlitePool,
+}
+
+impl GpuPriceRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Find the reference price for a base GPU
+    pub async fn find_by_base_gpu_id(&self, base_gpu_id: i64) -> Result<Option<GpuPrice>, Error> {
+        let result = sqlx::query_as!(
+            GpuPrice,
+            r#"
+            SELECT id, base_gpu_id, price_usd, source, recorded_at, created_at, updated_at
+            FROM gpu_prices
+            WHERE base_gpu_id = ?
+            "#,
+            base_gpu_id
+        )
+        .fetch_optional(&self.pool)
+        .timed("gpu_price.find_by_base_gpu_id", 1)
+        .await?;
+
+        Ok(result)
+    }
+
+    /// Average ITS per dollar for every priced base GPU over a tenant's runs, best value first
+    ///
+    /// Runs are attributed to a base GPU through GPU.device -> GPUMap.gpu_name;
+    /// multi-GPU runs are left out.
+    pub async fn its_per_dollar(&self, tenant_id: &str) -> Result<Vec<ItsPerDollar>, Error> {
+        let results = sqlx::query_as!(
+            ItsPerDollar,
+            r#"
+            SELECT
+                gb.id AS "base_gpu_id!",
+                gb.name AS "gpu_name!",
+                gb.brand AS "brand?",
+                p.price_usd AS "price_usd!: f64",
+                AVG(pr.avg_its) AS "avg_its?: f64",
+                COUNT(pr.avg_its) AS "sample_count!: i64",
+                AVG(pr.avg_its) / p.price_usd AS "its_per_dollar?: f64"
+            FROM gpu_prices p
+            JOIN GPUBase gb ON gb.id = p.base_gpu_id
+            LEFT JOIN GPUMap gm ON gm.base_gpu_id = gb.id
+            LEFT JOIN GPU g ON g.device = gm.gpu_name
+                AND NOT EXISTS (SELECT 1 FROM GPU mg WHERE mg.run_id = g.run_id AND mg.gpu_index > 0)
+                AND g.run_id IN (SELECT r.id FROM runs r WHERE r.tenant_id = ?)
+            LEFT JOIN performanceResult pr ON pr.run_id = g.run_id
+            GROUP BY gb.id, gb.name, gb.brand, p.price_usd
+            ORDER BY AVG(pr.avg_its) / p.price_usd DESC
+            "#,
+            tenant_id
+        )
+        .fetch_all(&self.pool)
+        .timed("gpu_price.its_per_dollar", 1)
+        .await?;
+
+        Ok(results)
+    }
+}
+
+#[async_trait]
+impl Repository<GpuPrice, i64> for GpuPriceRepository {
+    async fn create(&self, entity: GpuPrice) -> Result<GpuPrice, Error> {
+        let now = audit_timestamp();
+
+        let id = retry_on_busy(|| sqlx::query!(
+            r#"
+            INSERT INTO gpu_prices (base_gpu_id, price_usd, source, recorded_at, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+            entity.base_gpu_id,
+            entity.price_usd,
+            entity.source,
+            entity.recorded_at,
+            now,
+            now
+        )
+        .execute(&self.pool)
+        .timed("gpu_price.create", 6))
+        .await?
+        .last_insert_rowid();
+
+        Ok(GpuPrice {
+            id: Some(id),
+            created_at: Some(now.clone()),
+            updated_at: Some(now),
+            ..entity
+        })
+    }
+
+    async fn find_by_id(&self, id: i64) -> Result<Option<GpuPrice>, Error> {
+        let result = sqlx::query_as!(
+            GpuPrice,
+            r#"
+            SELECT id, base_gpu_id, price_usd, source, recorded_at, created_at, updated_at
+            FROM gpu_prices
+            WHERE id = ?
+            "#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .timed("gpu_price.find_by_id", 1)
+        .await?;
+
+        Ok(result)
+    }
+
+    async fn find_all(&self) -> Result<Vec<GpuPrice>, Error> {
+        let results = sqlx::query_as!(
+            GpuPrice,
+            r#"
+            SELECT id, base_gpu_id, price_usd, source, recorded_at, created_at, updated_at
+            FROM gpu_prices
+            ORDER BY id DESC
+            "#
+        )
+        .fetch_all(&self.pool)
+        .timed("gpu_price.find_all", 0)
+        .await?;
+
+        Ok(results)
+    }
+
+    async fn update(&self, entity: GpuPrice) -> Result<GpuPrice, Error> {
+        let id = entity.id.ok_or(Error::RowNotFound)?;
+        let now = audit_timestamp();
+
+        retry_on_busy(|| sqlx::query!(
+            r#"
+            UPDATE gpu_prices
+            SET base_gpu_id = ?, price_usd = ?, source = ?, recorded_at = ?, updated_at = ?
+            WHERE id = ?
+            "#,
+            entity.base_gpu_id,
+            entity.price_usd,
+            entity.source,
+            entity.recorded_at,
+            now,
+            id
+        )
+        .execute(&self.pool)
+        .timed("gpu_price.update", 6))
+        .await?;
+
+        Ok(GpuPrice {
+            updated_at: Some(now),
+            ..entity
+        })
+    }
+
+    async fn delete(&self, id: i64) -> Result<(), Error> {
+        retry_on_busy(|| sqlx::query!("DELETE FROM gpu_prices WHERE id = ?", id)
+            .execute(&self.pool)
+            .timed("gpu_price.delete", 1))
+            .await?;
+        Ok(())
+    }
+
+    async fn count(&self) -> Result<i64, Error> {
+        let count = sqlx::query!("SELECT COUNT(*) as count FROM gpu_prices")
+            .fetch_one(&self.pool)
+            .timed("gpu_price.count", 0)
+            .await?
+            .count;
+        Ok(count)
+    }
+
+    async fn exists(&self, id: i64) -> Result<bool, Error> {
+        let exists = sqlx::query_scalar!(r#"SELECT EXISTS(SELECT 1 FROM gpu_prices WHERE id = ?) AS "exists!: bool""#, id)
+            .fetch_one(&self.pool)
+            .timed("gpu_price.exists", 1)
+            .await?;
+        Ok(exists)
+    }
+
+    async fn count_where(&self, filter: &Filter) -> Result<i64, Error> {
+        let count: i64 = sqlx::query_scalar(&filter.count_sql("gpu_prices")?)
+            .bind(&filter.value)
+            .fetch_one(&self.pool)
+            .timed("gpu_price.count_where", 1)
+            .await?;
+        Ok(count)
+    }
+}
+
+#[async_trait]
+impl<'a> TransactionRepository<'a, GpuPrice, i64> for GpuPriceRepository {
+    async fn create_tx(&self, entity: GpuPrice, tx: &mut Transaction<'a, Sqlite>) -> Result<GpuPrice, Error> {
+        let now = audit_timestamp();
+
+        let id = sqlx::query!(
+            r#"
+            INSERT INTO gpu_prices (base_gpu_id, price_usd, source, recorded_at, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+            entity.base_gpu_id,
+            entity.price_usd,
+            entity.source,
+            entity.recorded_at,
+            now,
+            now
+        )
+        .execute(&mut **tx)
+        .await?
+        .last_insert_rowid();
+
+        Ok(GpuPrice {
+            id: Some(id),
+            created_at: Some(now.clone()),
+            updated_at: Some(now),
+            ..entity
+        })
+    }
+
+    async fn update_tx(&self, entity: GpuPrice, tx: &mut Transaction<'a, Sqlite>) -> Result<GpuPrice, Error> {
+        let id = entity.id.ok_or(Error::RowNotFound)?;
+        let now = audit_timestamp();
+
+        sqlx::query!(
+            r#"
+            UPDATE gpu_prices
+            SET base_gpu_id = ?, price_usd = ?, source = ?, recorded_at = ?, updated_at = ?
+            WHERE id = ?
+            "#,
+            entity.base_gpu_id,
+            entity.price_usd,
+            entity.source,
+            entity.recorded_at,
+            now,
+            id
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(GpuPrice {
+            updated_at: Some(now),
+            ..entity
+        })
+    }
+
+    async fn delete_tx(&self, id: i64, tx: &mut Transaction<'a, Sqlite>) -> Result<(), Error> {
+        sqlx::query!("DELETE FROM gpu_prices WHERE id = ?", id)
+            .execute(&mut **tx)
+            .await?;
+        Ok(())
+    }
+}
@@ -1,8 +1,15 @@
+use std::collections::HashMap;
+
 use async_trait::async_trait;
-use sqlx::{Error, SqlitePool, Transaction, Sqlite};
+use sqlx::{query::Query, sqlite::SqliteArguments, Error, SqlitePool, Transaction, Sqlite};
 
-use crate::models::gpu::Gpu;
+use crate::models::{estimate::ItsSample, gpu::Gpu, gpu_dedup::GpuDeviceCount, gpu_percentile::DeviceItsSample};
 use crate::repositories::traits::{Repository, TransactionRepository, BulkRepository, BulkTransactionRepository};
+use crate::repositories::audit_timestamp;
+use crate::repositories::batch::{group_by_run, json_ids};
+use crate::repositories::connection::{retry_on_busy, TimedQuery};
+use crate::repositories::shadow_table::ShadowRow;
+use crate::repositories::query_builder::Filter;
 
 pub struct GpuRepository {
     pool: SqlitePool,
@@ -18,7 +25,7 @@ impl GpuRepository {
         let results = sqlx::query_as!(
             Gpu,
             r#"
-            SELECT id, run_id, device, driver, gpu_chip, brand, isLaptop as "is_laptop"
+            SELECT id, run_id, device, driver, gpu_chip, brand, isLaptop as "is_laptop", is_integrated, is_cloud, gpu_index, parser_version, created_at, updated_at
             FROM GPU
             WHERE run_id = ?
             ORDER BY id DESC
@@ -26,17 +33,38 @@ impl GpuRepository {
             run_id
         )
         .fetch_all(&self.pool)
+        .timed("gpu.find_by_run_id", 1)
         .await?;
 
         Ok(results)
     }
 
+    /// The GPUs of each of the given runs by `gpu_index`, in one query
+    pub async fn find_by_run_ids(&self, run_ids: &[i64]) -> Result<HashMap<i64, Vec<Gpu>>, Error> {
+        let ids = json_ids(run_ids)?;
+        let results = sqlx::query_as!(
+            Gpu,
+            r#"
+            SELECT id, run_id, device, driver, gpu_chip, brand, isLaptop as "is_laptop", is_integrated, is_cloud, gpu_index, parser_version, created_at, updated_at
+            FROM GPU
+            WHERE run_id IN (SELECT value FROM json_each(?))
+            ORDER BY run_id, gpu_index, id
+            "#,
+            ids
+        )
+        .fetch_all(&self.pool)
+        .timed("gpu.find_by_run_ids", 1)
+        .await?;
+
+        Ok(group_by_run(results, |gpu| gpu.run_id))
+    }
+
     /// Find GPUs by brand
     pub async fn find_by_brand(&self, brand: &str) -> Result<Vec<Gpu>, Error> {
         let results = sqlx::query_as!(
             Gpu,
             r#"
-            SELECT id, run_id, device, driver, gpu_chip, brand, isLaptop as "is_laptop"
+            SELECT id, run_id, device, driver, gpu_chip, brand, isLaptop as "is_laptop", is_integrated, is_cloud, gpu_index, parser_version, created_at, updated_at
             FROM GPU
             WHERE brand = ?
             ORDER BY id DESC
@@ -44,6 +72,7 @@ impl GpuRepository {
             brand
         )
         .fetch_all(&self.pool)
+        .timed("gpu.find_by_brand", 1)
         .await?;
 
         Ok(results)
@@ -54,7 +83,7 @@ impl GpuRepository {
         let results = sqlx::query_as!(
             Gpu,
             r#"
-            SELECT id, run_id, device, driver, gpu_chip, brand, isLaptop as "is_laptop"
+            SELECT id, run_id, device, driver, gpu_chip, brand, isLaptop as "is_laptop", is_integrated, is_cloud, gpu_index, parser_version, created_at, updated_at
             FROM GPU
             WHERE isLaptop = ?
             ORDER BY id DESC
@@ -62,6 +91,235 @@ impl GpuRepository {
             is_laptop
         )
         .fetch_all(&self.pool)
+        .timed("gpu.find_by_laptop_status", 1)
+        .await?;
+
+        Ok(results)
+    }
+
+    /// avg_its of every single-GPU run of a tenant on a GPU, matched by reported device name or by mapped base GPU name
+    pub async fn find_avg_its_by_gpu_name(&self, tenant_id: &str, gpu_name: &str) -> Result<Vec<f64>, Error> {
+        let results = sqlx::query_scalar!(
+            r#"
+            SELECT pr.avg_its AS "avg_its!: f64"
+            FROM GPU g
+            JOIN runs r ON r.id = g.run_id
+            JOIN performanceResult pr ON pr.run_id = g.run_id
+            WHERE r.tenant_id = ?
+              AND pr.avg_its IS NOT NULL
+              AND NOT EXISTS (SELECT 1 FROM GPU mg WHERE mg.run_id = g.run_id AND mg.gpu_index > 0)
+              AND (
+                g.device = ? COLLATE NOCASE
+                OR g.device IN (
+                    SELECT gm.gpu_name
+                    FROM GPUMap gm
+                    JOIN GPUBase gb ON gb.id = gm.base_gpu_id
+                    WHERE gb.name = ? COLLATE NOCASE
+                )
+              )
+            ORDER BY pr.avg_its
+            "#,
+            tenant_id,
+            gpu_name,
+            gpu_name
+        )
+        .fetch_all(&self.pool)
+        .timed("gpu.find_avg_its_by_gpu_name", 3)
+        .await?;
+
+        Ok(results)
+    }
+
+    /// ITS samples of a tenant's single-GPU runs on a reported device name
+    pub async fn find_its_samples_by_device(&self, tenant_id: &str, device: &str) -> Result<Vec<ItsSample>, Error> {
+        let results = sqlx::query_as!(
+            ItsSample,
+            r#"
+            SELECT pr.avg_its AS "avg_its!: f64", l.torch AS "torch?", mm.base_model AS "base_model?"
+            FROM GPU g
+            JOIN runs r ON r.id = g.run_id
+            JOIN performanceResult pr ON pr.run_id = g.run_id
+            LEFT JOIN Libraries l ON l.run_id = g.run_id
+            LEFT JOIN RunMoreDetails rmd ON rmd.run_id = g.run_id
+            LEFT JOIN ModelMap mm ON mm.id = rmd.ModelMapId
+            WHERE pr.avg_its IS NOT NULL
+              AND NOT EXISTS (SELECT 1 FROM GPU mg WHERE mg.run_id = g.run_id AND mg.gpu_index > 0)
+              AND g.device = ? COLLATE NOCASE
+              AND r.tenant_id = ?
+            "#,
+            device,
+            tenant_id
+        )
+        .fetch_all(&self.pool)
+        .timed("gpu.find_its_samples_by_device", 2)
+        .await?;
+
+        Ok(results)
+    }
+
+    /// avg_its of every single-GPU run of a tenant with its device, optionally only on one device
+    pub async fn find_device_its_samples(&self, tenant_id: &str, device: Option<&str>) -> Result<Vec<DeviceItsSample>, Error> {
+        let results = sqlx::query_as!(
+            DeviceItsSample,
+            r#"
+            SELECT g.device AS "device!", pr.avg_its AS "avg_its!: f64"
+            FROM GPU g
+            JOIN runs r ON r.id = g.run_id
+            JOIN performanceResult pr ON pr.run_id = g.run_id
+            WHERE pr.avg_its IS NOT NULL
+              AND g.device IS NOT NULL
+              AND NOT EXISTS (SELECT 1 FROM GPU mg WHERE mg.run_id = g.run_id AND mg.gpu_index > 0)
+              AND (?1 IS NULL OR g.device = ?1 COLLATE NOCASE)
+              AND r.tenant_id = ?2
+            "#,
+            device,
+            tenant_id
+        )
+        .fetch_all(&self.pool)
+        .timed("gpu.find_device_its_samples", 2)
+        .await?;
+
+        Ok(results)
+    }
+
+    /// ITS samples of a tenant's single-GPU runs on any device mapped to a base GPU
+    pub async fn find_its_samples_by_base_gpu_id(&self, tenant_id: &str, base_gpu_id: i64) -> Result<Vec<ItsSample>, Error> {
+        let results = sqlx::query_as!(
+            ItsSample,
+            r#"
+            SELECT pr.avg_its AS "avg_its!: f64", l.torch AS "torch?", mm.base_model AS "base_model?"
+            FROM GPU g
+            JOIN runs r ON r.id = g.run_id
+            JOIN performanceResult pr ON pr.run_id = g.run_id
+            LEFT JOIN Libraries l ON l.run_id = g.run_id
+            LEFT JOIN RunMoreDetails rmd ON rmd.run_id = g.run_id
+            LEFT JOIN ModelMap mm ON mm.id = rmd.ModelMapId
+            WHERE pr.avg_its IS NOT NULL
+              AND NOT EXISTS (SELECT 1 FROM GPU mg WHERE mg.run_id = g.run_id AND mg.gpu_index > 0)
+              AND g.device IN (SELECT gm.gpu_name FROM GPUMap gm WHERE gm.base_gpu_id = ?)
+              AND r.tenant_id = ?
+            "#,
+            base_gpu_id,
+            tenant_id
+        )
+        .fetch_all(&self.pool)
+        .timed("gpu.find_its_samples_by_base_gpu_id", 2)
+        .await?;
+
+        Ok(results)
+    }
+
+    /// ITS samples of a tenant's single-GPU runs on any device whose base GPU has the given architecture
+    pub async fn find_its_samples_by_architecture(&self, tenant_id: &str, architecture: &str) -> Result<Vec<ItsSample>, Error> {
+        let results = sqlx::query_as!(
+            ItsSample,
+            r#"
+            SELECT pr.avg_its AS "avg_its!: f64", l.torch AS "torch?", mm.base_model AS "base_model?"
+            FROM GPU g
+            JOIN runs r ON r.id = g.run_id
+            JOIN performanceResult pr ON pr.run_id = g.run_id
+            LEFT JOIN Libraries l ON l.run_id = g.run_id
+            LEFT JOIN RunMoreDetails rmd ON rmd.run_id = g.run_id
+            LEFT JOIN ModelMap mm ON mm.id = rmd.ModelMapId
+            WHERE pr.avg_its IS NOT NULL
+              AND NOT EXISTS (SELECT 1 FROM GPU mg WHERE mg.run_id = g.run_id AND mg.gpu_index > 0)
+              AND g.device IN (
+                SELECT gm.gpu_name
+                FROM GPUMap gm
+                JOIN GPUBase gb ON gb.id = gm.base_gpu_id
+                WHERE gb.architecture = ?
+              )
+              AND r.tenant_id = ?
+            "#,
+            architecture,
+            tenant_id
+        )
+        .fetch_all(&self.pool)
+        .timed("gpu.find_its_samples_by_architecture", 2)
+        .await?;
+
+        Ok(results)
+    }
+
+    /// Every distinct device string with the number of GPU rows reporting it
+    pub async fn find_device_counts(&self) -> Result<Vec<GpuDeviceCount>, Error> {
+        let results = sqlx::query_as!(
+            GpuDeviceCount,
+            r#"
+            SELECT device AS "device!", COUNT(*) AS "row_count!: i64"
+            FROM GPU
+            WHERE device IS NOT NULL
+            GROUP BY device
+            ORDER BY device
+            "#
+        )
+        .fetch_all(&self.pool)
+        .timed("gpu.find_device_counts", 0)
+        .await?;
+
+        Ok(results)
+    }
+
+    /// Rewrite one device string to another within a transaction, returning the rows changed
+    pub async fn rename_device_tx(&self, from: &str, to: &str, tx: &mut Transaction<'_, Sqlite>) -> Result<u64, Error> {
+        let now = audit_timestamp();
+
+        let result = sqlx::query!(
+            "UPDATE GPU SET device = ?, updated_at = ? WHERE device = ?",
+            to,
+            now,
+            from
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Store the integrated GPU flag of one row within a transaction
+    pub async fn set_integrated_tx(&self, id: i64, is_integrated: bool, tx: &mut Transaction<'_, Sqlite>) -> Result<(), Error> {
+        let now = audit_timestamp();
+
+        sqlx::query!(
+            "UPDATE GPU SET is_integrated = ?, updated_at = ? WHERE id = ?",
+            is_integrated,
+            now,
+            id
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Store the cloud hardware flag of one row within a transaction
+    pub async fn set_cloud_tx(&self, id: i64, is_cloud: bool, tx: &mut Transaction<'_, Sqlite>) -> Result<(), Error> {
+        let now = audit_timestamp();
+
+        sqlx::query!(
+            "UPDATE GPU SET is_cloud = ?, updated_at = ? WHERE id = ?",
+            is_cloud,
+            now,
+            id
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Every GPU record within a transaction, newest first
+    pub async fn find_all_tx(&self, tx: &mut Transaction<'_, Sqlite>) -> Result<Vec<Gpu>, Error> {
+        let results = sqlx::query_as!(
+            Gpu,
+            r#"
+            SELECT id, run_id, device, driver, gpu_chip, brand, isLaptop as "is_laptop", is_integrated, is_cloud, gpu_index, parser_version, created_at, updated_at
+            FROM GPU
+            ORDER BY id DESC
+            "#
+        )
+        .fetch_all(&mut **tx)
+        .timed("gpu.find_all", 0)
         .await?;
 
         Ok(results)
@@ -69,8 +327,9 @@ impl GpuRepository {
 
     /// Clear all GPU records
     pub async fn clear_all(&self) -> Result<(), Error> {
-        sqlx::query!("DELETE FROM GPU")
+        retry_on_busy(|| sqlx::query!("DELETE FROM GPU")
             .execute(&self.pool)
+            .timed("gpu.clear_all", 0))
             .await?;
         Ok(())
     }
@@ -84,27 +343,68 @@ impl GpuRepository {
     }
 }
 
+impl ShadowRow for Gpu {
+    const TABLE: &'static str = "GPU";
+    const COLUMNS: &'static [&'static str] = &["run_id", "device", "driver", "gpu_chip", "brand", "isLaptop", "is_integrated", "is_cloud", "gpu_index", "parser_version"];
+
+    fn bind_columns<'q>(self, query: Query<'q, Sqlite, SqliteArguments<'q>>) -> Query<'q, Sqlite, SqliteArguments<'q>> {
+        query
+            .bind(self.run_id)
+            .bind(self.device)
+            .bind(self.driver)
+            .bind(self.gpu_chip)
+            .bind(self.brand)
+            .bind(self.is_laptop)
+            .bind(self.is_integrated)
+            .bind(self.is_cloud)
+            .bind(self.gpu_index)
+            .bind(self.parser_version)
+    }
+}
+
 #[async_trait]
 impl Repository<Gpu, i64> for GpuRepository {
     async fn create(&self, entity: Gpu) -> Result<Gpu, Error> {
-        let id = sqlx::query!(
+        let now = audit_timestamp();
+
+        let row = retry_on_busy(|| sqlx::query!(
             r#"
-            INSERT INTO GPU (run_id, device, driver, gpu_chip, brand, isLaptop)
-            VALUES (?, ?, ?, ?, ?, ?)
+            INSERT INTO GPU (run_id, device, driver, gpu_chip, brand, isLaptop, is_integrated, is_cloud, gpu_index, parser_version, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT (run_id, gpu_index) DO UPDATE SET
+                device = excluded.device,
+                driver = excluded.driver,
+                gpu_chip = excluded.gpu_chip,
+                brand = excluded.brand,
+                isLaptop = excluded.isLaptop,
+                is_integrated = excluded.is_integrated,
+                is_cloud = excluded.is_cloud,
+                parser_version = excluded.parser_version,
+                updated_at = excluded.updated_at
+            RETURNING id AS "id!: i64", created_at
             "#,
             entity.run_id,
             entity.device,
             entity.driver,
             entity.gpu_chip,
             entity.brand,
-            entity.is_laptop
+            entity.is_laptop,
+            entity.is_integrated,
+            entity.is_cloud,
+            entity.gpu_index,
+            entity.parser_version,
+            now,
+            now
         )
-        .execute(&self.pool)
-        .await?
-        .last_insert_rowid() as i64;
+        .fetch_one(&self.pool)
+        .timed("gpu.create", 12))
+        .await?;
 
         Ok(Gpu {
-            id: Some(id),
+            id: Some(row.id),
+            // On conflict the row keeps its original creation time
+            created_at: row.created_at,
+            updated_at: Some(now),
             ..entity
         })
     }
@@ -113,13 +413,14 @@ impl Repository<Gpu, i64> for GpuRepository {
         let result = sqlx::query_as!(
             Gpu,
             r#"
-            SELECT id, run_id, device, driver, gpu_chip, brand, isLaptop as "is_laptop"
+            SELECT id, run_id, device, driver, gpu_chip, brand, isLaptop as "is_laptop", is_integrated, is_cloud, gpu_index, parser_version, created_at, updated_at
             FROM GPU
             WHERE id = ?
             "#,
             id
         )
         .fetch_optional(&self.pool)
+        .timed("gpu.find_by_id", 1)
         .await?;
 
         Ok(result)
@@ -129,12 +430,13 @@ impl Repository<Gpu, i64> for GpuRepository {
         let results = sqlx::query_as!(
             Gpu,
             r#"
-            SELECT id, run_id, device, driver, gpu_chip, brand, isLaptop as "is_laptop"
+            SELECT id, run_id, device, driver, gpu_chip, brand, isLaptop as "is_laptop", is_integrated, is_cloud, gpu_index, parser_version, created_at, updated_at
             FROM GPU
             ORDER BY id DESC
             "#
         )
         .fetch_all(&self.pool)
+        .timed("gpu.find_all", 0)
         .await?;
 
         Ok(results)
@@ -142,11 +444,12 @@ impl Repository<Gpu, i64> for GpuRepository {
 
     async fn update(&self, entity: Gpu) -> Result<Gpu, Error> {
         let id = entity.id.ok_or(Error::RowNotFound)?;
+        let now = audit_timestamp();
         
-        sqlx::query!(
+        retry_on_busy(|| sqlx::query!(
             r#"
             UPDATE GPU
-            SET run_id = ?, device = ?, driver = ?, gpu_chip = ?, brand = ?, isLaptop = ?
+            SET run_id = ?, device = ?, driver = ?, gpu_chip = ?, brand = ?, isLaptop = ?, is_integrated = ?, is_cloud = ?, gpu_index = ?, parser_version = ?, updated_at = ?
             WHERE id = ?
             "#,
             entity.run_id,
@@ -155,17 +458,27 @@ impl Repository<Gpu, i64> for GpuRepository {
             entity.gpu_chip,
             entity.brand,
             entity.is_laptop,
+            entity.is_integrated,
+            entity.is_cloud,
+            entity.gpu_index,
+            entity.parser_version,
+            now,
             id
         )
         .execute(&self.pool)
+        .timed("gpu.update", 12))
         .await?;
 
-        Ok(entity)
+        Ok(Gpu {
+            updated_at: Some(now),
+            ..entity
+        })
     }
 
     async fn delete(&self, id: i64) -> Result<(), Error> {
-        sqlx::query!("DELETE FROM GPU WHERE id = ?", id)
+        retry_on_busy(|| sqlx::query!("DELETE FROM GPU WHERE id = ?", id)
             .execute(&self.pool)
+            .timed("gpu.delete", 1))
             .await?;
         Ok(())
     }
@@ -173,44 +486,84 @@ impl Repository<Gpu, i64> for GpuRepository {
     async fn count(&self) -> Result<i64, Error> {
         let count = sqlx::query!("SELECT COUNT(*) as count FROM GPU")
             .fetch_one(&self.pool)
+            .timed("gpu.count", 0)
             .await?
             .count;
         Ok(count)
     }
+
+    async fn exists(&self, id: i64) -> Result<bool, Error> {
+        let exists = sqlx::query_scalar!(r#"SELECT EXISTS(SELECT 1 FROM GPU WHERE id = ?) AS "exists!: bool""#, id)
+            .fetch_one(&self.pool)
+            .timed("gpu.exists", 1)
+            .await?;
+        Ok(exists)
+    }
+
+    async fn count_where(&self, filter: &Filter) -> Result<i64, Error> {
+        let count: i64 = sqlx::query_scalar(&filter.count_sql("GPU")?)
+            .bind(&filter.value)
+            .fetch_one(&self.pool)
+            .timed("gpu.count_where", 1)
+            .await?;
+        Ok(count)
+    }
 }
 
 #[async_trait]
 impl<'a> TransactionRepository<'a, Gpu, i64> for GpuRepository {
     async fn create_tx(&self, entity: Gpu, tx: &mut Transaction<'a, Sqlite>) -> Result<Gpu, Error> {
-        let id = sqlx::query!(
+        let now = audit_timestamp();
+
+        let row = sqlx::query!(
             r#"
-            INSERT INTO GPU (run_id, device, driver, gpu_chip, brand, isLaptop)
-            VALUES (?, ?, ?, ?, ?, ?)
+            INSERT INTO GPU (run_id, device, driver, gpu_chip, brand, isLaptop, is_integrated, is_cloud, gpu_index, parser_version, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT (run_id, gpu_index) DO UPDATE SET
+                device = excluded.device,
+                driver = excluded.driver,
+                gpu_chip = excluded.gpu_chip,
+                brand = excluded.brand,
+                isLaptop = excluded.isLaptop,
+                is_integrated = excluded.is_integrated,
+                is_cloud = excluded.is_cloud,
+                parser_version = excluded.parser_version,
+                updated_at = excluded.updated_at
+            RETURNING id AS "id!: i64", created_at
             "#,
             entity.run_id,
             entity.device,
             entity.driver,
             entity.gpu_chip,
             entity.brand,
-            entity.is_laptop
+            entity.is_laptop,
+            entity.is_integrated,
+            entity.is_cloud,
+            entity.gpu_index,
+            entity.parser_version,
+            now,
+            now
         )
-        .execute(&mut **tx)
-        .await?
-        .last_insert_rowid() as i64;
+        .fetch_one(&mut **tx)
+        .await?;
 
         Ok(Gpu {
-            id: Some(id),
+            id: Some(row.id),
+            // On conflict the row keeps its original creation time
+            created_at: row.created_at,
+            updated_at: Some(now),
             ..entity
         })
     }
 
     async fn update_tx(&self, entity: Gpu, tx: &mut Transaction<'a, Sqlite>) -> Result<Gpu, Error> {
         let id = entity.id.ok_or(Error::RowNotFound)?;
+        let now = audit_timestamp();
         
         sqlx::query!(
             r#"
             UPDATE GPU
-            SET run_id = ?, device = ?, driver = ?, gpu_chip = ?, brand = ?, isLaptop = ?
+            SET run_id = ?, device = ?, driver = ?, gpu_chip = ?, brand = ?, isLaptop = ?, is_integrated = ?, is_cloud = ?, gpu_index = ?, parser_version = ?, updated_at = ?
             WHERE id = ?
             "#,
             entity.run_id,
@@ -219,12 +572,20 @@ impl<'a> TransactionRepository<'a, Gpu, i64> for GpuRepository {
             entity.gpu_chip,
             entity.brand,
             entity.is_laptop,
+            entity.is_integrated,
+            entity.is_cloud,
+            entity.gpu_index,
+            entity.parser_version,
+            now,
             id
         )
         .execute(&mut **tx)
         .await?;
 
-        Ok(entity)
+        Ok(Gpu {
+            updated_at: Some(now),
+            ..entity
+        })
     }
 
     async fn delete_tx(&self, id: i64, tx: &mut Transaction<'a, Sqlite>) -> Result<(), Error> {
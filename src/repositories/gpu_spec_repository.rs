@@ -0,0 +1,250 @@
+use async_trait::async_trait;
+use sqlx::{Error, SqlitePool, Transaction, Sqlite};
+
+use crate::models::gpu_spec::GpuSpec;
+use crate::repositories::traits::{Repository, TransactionRepository};
+use crate::repositories::audit_timestamp;
+use crate::repositories::connection::{retry_on_busy, TimedQuery};
+use crate::repositories::query_builder::Filter;
+
+pub struct GpuSpecRepository {
+    pool: SqlitePool,
+}
+
+impl GpuSpecRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Find the reference specs for a base GPU
+    pub async fn find_by_base_gpu_id(&self, base_gpu_id: i64) -> Result<Option<GpuSpec>, Error> {
+        let result = sqlx::query_as!(
+            GpuSpec,
+            r#"
+            SELECT id, base_gpu_id, vram_gb, launch_year, created_at, updated_at
+            FROM GPUSpec
+            WHERE base_gpu_id = ?
+            "#,
+            base_gpu_id
+        )
+        .fetch_optional(&self.pool)
+        .timed("gpu_spec.find_by_base_gpu_id", 1)
+        .await?;
+
+        Ok(result)
+    }
+
+    /// Insert or replace the reference specs for a base GPU within a transaction
+    pub async fn upsert_tx(&self, entity: GpuSpec, tx: &mut Transaction<'_, Sqlite>) -> Result<GpuSpec, Error> {
+        let now = audit_timestamp();
+
+        let row = sqlx::query!(
+            r#"
+            INSERT INTO GPUSpec (base_gpu_id, vram_gb, launch_year, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?)
+            ON CONFLICT (base_gpu_id) DO UPDATE
+            SET vram_gb = excluded.vram_gb, launch_year = excluded.launch_year, updated_at = excluded.updated_at
+            RETURNING id AS "id!: i64", created_at
+            "#,
+            entity.base_gpu_id,
+            entity.vram_gb,
+            entity.launch_year,
+            now,
+            now
+        )
+        .fetch_one(&mut **tx)
+        .await?;
+
+        Ok(GpuSpec {
+            id: Some(row.id),
+            // On conflict the row keeps its original creation time
+            created_at: row.created_at,
+            updated_at: Some(now),
+            ..entity
+        })
+    }
+}
+
+#[async_trait]
+impl Repository<GpuSpec, i64> for GpuSpecRepository {
+    async fn create(&self, entity: GpuSpec) -> Result<GpuSpec, Error> {
+        let now = audit_timestamp();
+
+        let id = retry_on_busy(|| sqlx::query!(
+            r#"
+            INSERT INTO GPUSpec (base_gpu_id, vram_gb, launch_year, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?)
+            "#,
+            entity.base_gpu_id,
+            entity.vram_gb,
+            entity.launch_year,
+            now,
+            now
+        )
+        .execute(&self.pool)
+        .timed("gpu_spec.create", 5))
+        .await?
+        .last_insert_rowid();
+
+        Ok(GpuSpec {
+            id: Some(id),
+            created_at: Some(now.clone()),
+            updated_at: Some(now),
+            ..entity
+        })
+    }
+
+    async fn find_by_id(&self, id: i64) -> Result<Option<GpuSpec>, Error> {
+        let result = sqlx::query_as!(
+            GpuSpec,
+            r#"
+            SELECT id, base_gpu_id, vram_gb, launch_year, created_at, updated_at
+            FROM GPUSpec
+            WHERE id = ?
+            "#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .timed("gpu_spec.find_by_id", 1)
+        .await?;
+
+        Ok(result)
+    }
+
+    async fn find_all(&self) -> Result<Vec<GpuSpec>, Error> {
+        let results = sqlx::query_as!(
+            GpuSpec,
+            r#"
+            SELECT id, base_gpu_id, vram_gb, launch_year, created_at, updated_at
+            FROM GPUSpec
+            ORDER BY id DESC
+            "#
+        )
+        .fetch_all(&self.pool)
+        .timed("gpu_spec.find_all", 0)
+        .await?;
+
+        Ok(results)
+    }
+
+    async fn update(&self, entity: GpuSpec) -> Result<GpuSpec, Error> {
+        let id = entity.id.ok_or(Error::RowNotFound)?;
+        let now = audit_timestamp();
+
+        retry_on_busy(|| sqlx::query!(
+            r#"
+            UPDATE GPUSpec
+            SET base_gpu_id = ?, vram_gb = ?, launch_year = ?, updated_at = ?
+            WHERE id = ?
+            "#,
+            entity.base_gpu_id,
+            entity.vram_gb,
+            entity.launch_year,
+            now,
+            id
+        )
+        .execute(&self.pool)
+        .timed("gpu_spec.update", 5))
+        .await?;
+
+        Ok(GpuSpec {
+            updated_at: Some(now),
+            ..entity
+        })
+    }
+
+    async fn delete(&self, id: i64) -> Result<(), Error> {
+        retry_on_busy(|| sqlx::query!("DELETE FROM GPUSpec WHERE id = ?", id)
+            .execute(&self.pool)
+            .timed("gpu_spec.delete", 1))
+            .await?;
+        Ok(())
+    }
+
+    async fn count(&self) -> Result<i64, Error> {
+        let count = sqlx::query!("SELECT COUNT(*) as count FROM GPUSpec")
+            .fetch_one(&self.pool)
+            .timed("gpu_spec.count", 0)
+            .await?
+            .count;
+        Ok(count)
+    }
+
+    async fn exists(&self, id: i64) -> Result<bool, Error> {
+        let exists = sqlx::query_scalar!(r#"SELECT EXISTS(SELECT 1 FROM GPUSpec WHERE id = ?) AS "exists!: bool""#, id)
+            .fetch_one(&self.pool)
+            .timed("gpu_spec.exists", 1)
+            .await?;
+        Ok(exists)
+    }
+
+    async fn count_where(&self, filter: &Filter) -> Result<i64, Error> {
+        let count: i64 = sqlx::query_scalar(&filter.count_sql("GPUSpec")?)
+            .bind(&filter.value)
+            .fetch_one(&self.pool)
+            .timed("gpu_spec.count_where", 1)
+            .await?;
+        Ok(count)
+    }
+}
+
+#[async_trait]
+impl<'a> TransactionRepository<'a, GpuSpec, i64> for GpuSpecRepository {
+    async fn create_tx(&self, entity: GpuSpec, tx: &mut Transaction<'a, Sqlite>) -> Result<GpuSpec, Error> {
+        let now = audit_timestamp();
+
+        let id = sqlx::query!(
+            r#"
+            INSERT INTO GPUSpec (base_gpu_id, vram_gb, launch_year, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?)
+            "#,
+            entity.base_gpu_id,
+            entity.vram_gb,
+            entity.launch_year,
+            now,
+            now
+        )
+        .execute(&mut **tx)
+        .await?
+        .last_insert_rowid();
+
+        Ok(GpuSpec {
+            id: Some(id),
+            created_at: Some(now.clone()),
+            updated_at: Some(now),
+            ..entity
+        })
+    }
+
+    async fn update_tx(&self, entity: GpuSpec, tx: &mut Transaction<'a, Sqlite>) -> Result<GpuSpec, Error> {
+        let id = entity.id.ok_or(Error::RowNotFound)?;
+        let now = audit_timestamp();
+
+        sqlx::query!(
+            r#"
+            UPDATE GPUSpec
+            SET base_gpu_id = ?, vram_gb = ?, launch_year = ?, updated_at = ?
+            WHERE id = ?
+            "#,
+            entity.base_gpu_id,
+            entity.vram_gb,
+            entity.launch_year,
+            now,
+            id
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(GpuSpec {
+            updated_at: Some(now),
+            ..entity
+        })
+    }
+
+    async fn delete_tx(&self, id: i64, tx: &mut Transaction<'a, Sqlite>) -> Result<(), Error> {
+        sqlx::query!("DELETE FROM GPUSpec WHERE id = ?", id)
+            .execute(&mut **tx)
+            .await?;
+        Ok(())
+    }
+}
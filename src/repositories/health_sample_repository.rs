@@ -0,0 +1,105 @@
+use sqlx::{Error, SqlitePool};
+
+use crate::models::health_sample::HealthSample;
+use crate::repositories::connection::{retry_on_busy, TimedQuery};
+
+/// Periodic health samples, kept for capacity planning
+pub struct HealthSampleRepository {
+    pool: SqlitePool,
+}
+
+impl HealthSampleRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Record a sample, returning its id
+    pub async fn create(&self, sample: &HealthSample) -> Result<i64, Error> {
+        let row_counts = serde_json::to_string(&sample.row_counts).map_err(|e| Error::Encode(Box::new(e)))?;
+
+        let id = retry_on_busy(|| sqlx::query!(
+            r#"
+            INSERT INTO health_samples (sampled_at, db_size_bytes, row_counts, pool_connections, pool_idle_connections, pool_max_connections, requests, server_errors, requests_per_minute)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+            sample.sampled_at,
+            sample.db_size_bytes,
+            row_counts,
+            sample.pool_connections,
+            sample.pool_idle_connections,
+            sample.pool_max_connections,
+            sample.requests,
+            sample.server_errors,
+            sample.requests_per_minute
+        )
+        .execute(&self.pool)
+        .timed("health_samples.create", 9))
+        .await?
+        .last_insert_rowid();
+
+        Ok(id)
+    }
+
+    /// The latest `limit` samples, optionally taken at or after `since`, oldest first
+    pub async fn find_recent(&self, since: Option<&str>, limit: i64) -> Result<Vec<HealthSample>, Error> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT id AS "id!", sampled_at AS "sampled_at!", db_size_bytes AS "db_size_bytes!", row_counts AS "row_counts!",
+                pool_connections AS "pool_connections!", pool_idle_connections AS "pool_idle_connections!",
+                pool_max_connections AS "pool_max_connections!", requests AS "requests!", server_errors AS "server_errors!",
+                requests_per_minute AS "requests_per_minute!"
+            FROM (
+                SELECT * FROM health_samples
+                WHERE ?1 IS NULL OR sampled_at >= ?1
+                ORDER BY id DESC
+                LIMIT ?2
+            )
+            ORDER BY id
+            "#,
+            since,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .timed("health_samples.find_recent", 2)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| HealthSample {
+                id: Some(row.id),
+                sampled_at: row.sampled_at,
+                db_size_bytes: row.db_size_bytes,
+                // Written by `create`, so only a hand-edited row fails to parse
+                row_counts: serde_json::from_str(&row.row_counts).unwrap_or_default(),
+                pool_connections: row.pool_connections,
+                pool_idle_connections: row.pool_idle_connections,
+                pool_max_connections: row.pool_max_connections,
+                requests: row.requests,
+                server_errors: row.server_errors,
+                requests_per_minute: row.requests_per_minute,
+            })
+            .collect())
+    }
+
+    /// Delete samples taken before `cutoff`, returning how many were deleted
+    pub async fn delete_before(&self, cutoff: &str) -> Result<u64, Error> {
+        let result = retry_on_busy(|| sqlx::query!("DELETE FROM health_samples WHERE sampled_at < ?", cutoff)
+            .execute(&self.pool)
+            .timed("health_samples.delete_before", 1))
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Size of the database file in bytes
+    pub async fn database_size(&self) -> Result<i64, Error> {
+        let size = sqlx::query_scalar!(
+            r#"SELECT page_count * page_size AS "size!: i64" FROM pragma_page_count(), pragma_page_size()"#
+        )
+        .fetch_one(&self.pool)
+        .timed("health_samples.database_size", 0)
+        .await?;
+
+        Ok(size)
+    }
+}
@@ -0,0 +1,68 @@
+use std::collections::HashSet;
+
+use sqlx::{Error, Sqlite, SqlitePool, Transaction};
+
+use crate::{repositories::connection::TimedQuery, services::ingest::content_hash::RowHashes};
+
+/// Content hashes and fingerprints of the benchmark rows ingested into the current runs
+pub struct IngestedRowRepository {
+    pool: SqlitePool,
+}
+
+impl IngestedRowRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Every recorded content hash
+    pub async fn find_all_hashes(&self) -> Result<HashSet<String>, Error> {
+        let rows = sqlx::query!(r#"SELECT content_hash AS "content_hash!" FROM ingested_rows"#)
+            .fetch_all(&self.pool)
+            .timed("ingested_rows.find_all_hashes", 0)
+            .await?;
+
+        Ok(rows.into_iter().map(|row| row.content_hash).collect())
+    }
+
+    /// Every recorded fingerprint
+    pub async fn find_all_fingerprints(&self) -> Result<HashSet<String>, Error> {
+        let rows = sqlx::query!(
+            r#"SELECT DISTINCT fingerprint AS "fingerprint!" FROM ingested_rows WHERE fingerprint IS NOT NULL"#
+        )
+        .fetch_all(&self.pool)
+        .timed("ingested_rows.find_all_fingerprints", 0)
+        .await?;
+
+        Ok(rows.into_iter().map(|row| row.fingerprint).collect())
+    }
+
+    /// Record rows within a transaction, ignoring ones already recorded
+    pub async fn insert_hashes_tx(
+        &self,
+        rows: &[RowHashes],
+        ingested_at: &str,
+        tx: &mut Transaction<'_, Sqlite>,
+    ) -> Result<(), Error> {
+        for row in rows {
+            sqlx::query!(
+                "INSERT OR IGNORE INTO ingested_rows (content_hash, ingested_at, fingerprint) VALUES (?, ?, ?)",
+                row.content_hash,
+                ingested_at,
+                row.fingerprint
+            )
+            .execute(&mut **tx)
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    pub async fn count(&self) -> Result<i64, Error> {
+        let count = sqlx::query!("SELECT COUNT(*) as count FROM ingested_rows")
+            .fetch_one(&self.pool)
+            .timed("ingested_rows.count", 0)
+            .await?
+            .count;
+        Ok(count)
+    }
+}
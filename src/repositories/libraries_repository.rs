@@ -1,8 +1,15 @@
+use std::collections::HashMap;
+
 use async_trait::async_trait;
-use sqlx::{Error, SqlitePool, Transaction, Sqlite};
+use sqlx::{query::Query, sqlite::SqliteArguments, Error, SqlitePool, Transaction, Sqlite};
 
 use crate::models::libraries::Libraries;
 use crate::repositories::traits::{Repository, TransactionRepository, BulkRepository, BulkTransactionRepository};
+use crate::repositories::audit_timestamp;
+use crate::repositories::batch::{first_by_run, json_ids};
+use crate::repositories::connection::{retry_on_busy, TimedQuery};
+use crate::repositories::shadow_table::ShadowRow;
+use crate::repositories::query_builder::Filter;
 
 pub struct LibrariesRepository {
     pool: SqlitePool,
@@ -18,7 +25,7 @@ impl LibrariesRepository {
         let results = sqlx::query_as!(
             Libraries,
             r#"
-            SELECT id, run_id, torch, xformers, xformers1, diffusers, transformers
+            SELECT id, run_id, torch, xformers, xformers1, diffusers, transformers, parser_version, created_at, updated_at
             FROM Libraries
             WHERE run_id = ?
             ORDER BY id DESC
@@ -26,15 +33,37 @@ impl LibrariesRepository {
             run_id
         )
         .fetch_all(&self.pool)
+        .timed("libraries.find_by_run_id", 1)
         .await?;
 
         Ok(results)
     }
 
+    /// The newest libraries row of each of the given runs, in one query
+    pub async fn find_by_run_ids(&self, run_ids: &[i64]) -> Result<HashMap<i64, Libraries>, Error> {
+        let ids = json_ids(run_ids)?;
+        let results = sqlx::query_as!(
+            Libraries,
+            r#"
+            SELECT id, run_id, torch, xformers, xformers1, diffusers, transformers, parser_version, created_at, updated_at
+            FROM Libraries
+            WHERE run_id IN (SELECT value FROM json_each(?))
+            ORDER BY run_id, id DESC
+            "#,
+            ids
+        )
+        .fetch_all(&self.pool)
+        .timed("libraries.find_by_run_ids", 1)
+        .await?;
+
+        Ok(first_by_run(results, |row| row.run_id))
+    }
+
     /// Clear all libraries records
     pub async fn clear_all(&self) -> Result<(), Error> {
-        sqlx::query!("DELETE FROM Libraries")
+        retry_on_busy(|| sqlx::query!("DELETE FROM Libraries")
             .execute(&self.pool)
+            .timed("libraries.clear_all", 0))
             .await?;
         Ok(())
     }
@@ -48,27 +77,60 @@ impl LibrariesRepository {
     }
 }
 
+impl ShadowRow for Libraries {
+    const TABLE: &'static str = "Libraries";
+    const COLUMNS: &'static [&'static str] = &["run_id", "torch", "xformers", "xformers1", "diffusers", "transformers", "parser_version"];
+
+    fn bind_columns<'q>(self, query: Query<'q, Sqlite, SqliteArguments<'q>>) -> Query<'q, Sqlite, SqliteArguments<'q>> {
+        query
+            .bind(self.run_id)
+            .bind(self.torch)
+            .bind(self.xformers)
+            .bind(self.xformers1)
+            .bind(self.diffusers)
+            .bind(self.transformers)
+            .bind(self.parser_version)
+    }
+}
+
 #[async_trait]
 impl Repository<Libraries, i64> for LibrariesRepository {
     async fn create(&self, entity: Libraries) -> Result<Libraries, Error> {
-        let id = sqlx::query!(
+        let now = audit_timestamp();
+
+        let row = retry_on_busy(|| sqlx::query!(
             r#"
-            INSERT INTO Libraries (run_id, torch, xformers, xformers1, diffusers, transformers)
-            VALUES (?, ?, ?, ?, ?, ?)
+            INSERT INTO Libraries (run_id, torch, xformers, xformers1, diffusers, transformers, parser_version, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT (run_id) DO UPDATE SET
+                torch = excluded.torch,
+                xformers = excluded.xformers,
+                xformers1 = excluded.xformers1,
+                diffusers = excluded.diffusers,
+                transformers = excluded.transformers,
+                parser_version = excluded.parser_version,
+                updated_at = excluded.updated_at
+            RETURNING id AS "id!: i64", created_at
             "#,
             entity.run_id,
             entity.torch,
             entity.xformers,
             entity.xformers1,
             entity.diffusers,
-            entity.transformers
+            entity.transformers,
+            entity.parser_version,
+            now,
+            now
         )
-        .execute(&self.pool)
-        .await?
-        .last_insert_rowid() as i64;
+        .fetch_one(&self.pool)
+        .timed("libraries.create", 9))
+        .await?;
 
         Ok(Libraries {
-            id: Some(id),
+            id: Some(row.id),
+            // On conflict the row keeps its original creation time
+            created_at: row.created_at,
+            updated_at: Some(now),
             ..entity
         })
     }
@@ -77,13 +139,14 @@ impl Repository<Libraries, i64> for LibrariesRepository {
         let result = sqlx::query_as!(
             Libraries,
             r#"
-            SELECT id, run_id, torch, xformers, xformers1, diffusers, transformers
+            SELECT id, run_id, torch, xformers, xformers1, diffusers, transformers, parser_version, created_at, updated_at
             FROM Libraries
             WHERE id = ?
             "#,
             id
         )
         .fetch_optional(&self.pool)
+        .timed("libraries.find_by_id", 1)
         .await?;
 
         Ok(result)
@@ -93,12 +156,13 @@ impl Repository<Libraries, i64> for LibrariesRepository {
         let results = sqlx::query_as!(
             Libraries,
             r#"
-            SELECT id, run_id, torch, xformers, xformers1, diffusers, transformers
+            SELECT id, run_id, torch, xformers, xformers1, diffusers, transformers, parser_version, created_at, updated_at
             FROM Libraries
             ORDER BY id DESC
             "#
         )
         .fetch_all(&self.pool)
+        .timed("libraries.find_all", 0)
         .await?;
 
         Ok(results)
@@ -106,11 +170,12 @@ impl Repository<Libraries, i64> for LibrariesRepository {
 
     async fn update(&self, entity: Libraries) -> Result<Libraries, Error> {
         let id = entity.id.ok_or(Error::RowNotFound)?;
+        let now = audit_timestamp();
         
-        sqlx::query!(
+        retry_on_busy(|| sqlx::query!(
             r#"
             UPDATE Libraries
-            SET run_id = ?, torch = ?, xformers = ?, xformers1 = ?, diffusers = ?, transformers = ?
+            SET run_id = ?, torch = ?, xformers = ?, xformers1 = ?, diffusers = ?, transformers = ?, parser_version = ?, updated_at = ?
             WHERE id = ?
             "#,
             entity.run_id,
@@ -119,17 +184,24 @@ impl Repository<Libraries, i64> for LibrariesRepository {
             entity.xformers1,
             entity.diffusers,
             entity.transformers,
+            entity.parser_version,
+            now,
             id
         )
         .execute(&self.pool)
+        .timed("libraries.update", 9))
         .await?;
 
-        Ok(entity)
+        Ok(Libraries {
+            updated_at: Some(now),
+            ..entity
+        })
     }
 
     async fn delete(&self, id: i64) -> Result<(), Error> {
-        sqlx::query!("DELETE FROM Libraries WHERE id = ?", id)
+        retry_on_busy(|| sqlx::query!("DELETE FROM Libraries WHERE id = ?", id)
             .execute(&self.pool)
+            .timed("libraries.delete", 1))
             .await?;
         Ok(())
     }
@@ -137,44 +209,79 @@ impl Repository<Libraries, i64> for LibrariesRepository {
     async fn count(&self) -> Result<i64, Error> {
         let count = sqlx::query!("SELECT COUNT(*) as count FROM Libraries")
             .fetch_one(&self.pool)
+            .timed("libraries.count", 0)
             .await?
             .count;
         Ok(count)
     }
+
+    async fn exists(&self, id: i64) -> Result<bool, Error> {
+        let exists = sqlx::query_scalar!(r#"SELECT EXISTS(SELECT 1 FROM Libraries WHERE id = ?) AS "exists!: bool""#, id)
+            .fetch_one(&self.pool)
+            .timed("libraries.exists", 1)
+            .await?;
+        Ok(exists)
+    }
+
+    async fn count_where(&self, filter: &Filter) -> Result<i64, Error> {
+        let count: i64 = sqlx::query_scalar(&filter.count_sql("Libraries")?)
+            .bind(&filter.value)
+            .fetch_one(&self.pool)
+            .timed("libraries.count_where", 1)
+            .await?;
+        Ok(count)
+    }
 }
 
 #[async_trait]
 impl<'a> TransactionRepository<'a, Libraries, i64> for LibrariesRepository {
     async fn create_tx(&self, entity: Libraries, tx: &mut Transaction<'a, Sqlite>) -> Result<Libraries, Error> {
-        let id = sqlx::query!(
+        let now = audit_timestamp();
+
+        let row = sqlx::query!(
             r#"
-            INSERT INTO Libraries (run_id, torch, xformers, xformers1, diffusers, transformers)
-            VALUES (?, ?, ?, ?, ?, ?)
+            INSERT INTO Libraries (run_id, torch, xformers, xformers1, diffusers, transformers, parser_version, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT (run_id) DO UPDATE SET
+                torch = excluded.torch,
+                xformers = excluded.xformers,
+                xformers1 = excluded.xformers1,
+                diffusers = excluded.diffusers,
+                transformers = excluded.transformers,
+                parser_version = excluded.parser_version,
+                updated_at = excluded.updated_at
+            RETURNING id AS "id!: i64", created_at
             "#,
             entity.run_id,
             entity.torch,
             entity.xformers,
             entity.xformers1,
             entity.diffusers,
-            entity.transformers
+            entity.transformers,
+            entity.parser_version,
+            now,
+            now
         )
-        .execute(&mut **tx)
-        .await?
-        .last_insert_rowid() as i64;
+        .fetch_one(&mut **tx)
+        .await?;
 
         Ok(Libraries {
-            id: Some(id),
+            id: Some(row.id),
+            // On conflict the row keeps its original creation time
+            created_at: row.created_at,
+            updated_at: Some(now),
             ..entity
         })
     }
 
     async fn update_tx(&self, entity: Libraries, tx: &mut Transaction<'a, Sqlite>) -> Result<Libraries, Error> {
         let id = entity.id.ok_or(Error::RowNotFound)?;
+        let now = audit_timestamp();
         
         sqlx::query!(
             r#"
             UPDATE Libraries
-            SET run_id = ?, torch = ?, xformers = ?, xformers1 = ?, diffusers = ?, transformers = ?
+            SET run_id = ?, torch = ?, xformers = ?, xformers1 = ?, diffusers = ?, transformers = ?, parser_version = ?, updated_at = ?
             WHERE id = ?
             "#,
             entity.run_id,
@@ -183,12 +290,17 @@ impl<'a> TransactionRepository<'a, Libraries, i64> for LibrariesRepository {
             entity.xformers1,
             entity.diffusers,
             entity.transformers,
+            entity.parser_version,
+            now,
             id
         )
         .execute(&mut **tx)
         .await?;
 
-        Ok(entity)
+        Ok(Libraries {
+            updated_at: Some(now),
+            ..entity
+        })
     }
 
     async fn delete_tx(&self, id: i64, tx: &mut Transaction<'a, Sqlite>) -> Result<(), Error> {
@@ -1,8 +1,11 @@
 use async_trait::async_trait;
 use sqlx::{Error, SqlitePool, Transaction, Sqlite};
 
-use crate::models::model_map::ModelMap;
+use crate::models::model_map::{ModelMap, ModelMapExportRow, ModelMapHash};
 use crate::repositories::traits::{Repository, TransactionRepository, BulkRepository, BulkTransactionRepository};
+use crate::repositories::audit_timestamp;
+use crate::repositories::connection::{retry_on_busy, TimedQuery};
+use crate::repositories::query_builder::Filter;
 
 pub struct ModelMapRepository {
     pool: SqlitePool,
@@ -18,7 +21,7 @@ impl ModelMapRepository {
         let results = sqlx::query_as!(
             ModelMap,
             r#"
-            SELECT id, model_name, base_model
+            SELECT id, model_name, base_model, version, created_at, updated_at
             FROM ModelMap
             WHERE model_name = ?
             ORDER BY id DESC
@@ -26,6 +29,7 @@ impl ModelMapRepository {
             model_name
         )
         .fetch_all(&self.pool)
+        .timed("model_map.find_by_model_name", 1)
         .await?;
 
         Ok(results)
@@ -36,7 +40,7 @@ impl ModelMapRepository {
         let results = sqlx::query_as!(
             ModelMap,
             r#"
-            SELECT id, model_name, base_model
+            SELECT id, model_name, base_model, version, created_at, updated_at
             FROM ModelMap
             WHERE base_model = ?
             ORDER BY id DESC
@@ -44,6 +48,7 @@ impl ModelMapRepository {
             base_model
         )
         .fetch_all(&self.pool)
+        .timed("model_map.find_by_base_model", 1)
         .await?;
 
         Ok(results)
@@ -54,7 +59,7 @@ impl ModelMapRepository {
         let result = sqlx::query_as!(
             ModelMap,
             r#"
-            SELECT id, model_name, base_model
+            SELECT id, model_name, base_model, version, created_at, updated_at
             FROM ModelMap
             WHERE model_name = ?
             ORDER BY id DESC
@@ -63,29 +68,141 @@ impl ModelMapRepository {
             model_name
         )
         .fetch_optional(&self.pool)
+        .timed("model_map.find_single_by_model_name", 1)
         .await?;
 
         Ok(result)
     }
+
+    /// Find a single model map by model_name within a transaction (returns first match)
+    pub async fn find_single_by_model_name_tx(&self, model_name: &str, tx: &mut Transaction<'_, Sqlite>) -> Result<Option<ModelMap>, Error> {
+        let result = sqlx::query_as!(
+            ModelMap,
+            r#"
+            SELECT id, model_name, base_model, version, created_at, updated_at
+            FROM ModelMap
+            WHERE model_name = ?
+            ORDER BY id DESC
+            LIMIT 1
+            "#,
+            model_name
+        )
+        .fetch_optional(&mut **tx)
+        .await?;
+
+        Ok(result)
+    }
+
+    /// Find the model map a known checkpoint hash points at
+    pub async fn find_by_hash(&self, hash: &str) -> Result<Option<ModelMap>, Error> {
+        let result = sqlx::query_as!(
+            ModelMap,
+            r#"
+            SELECT mm.id, mm.model_name, mm.base_model, mm.version, mm.created_at, mm.updated_at
+            FROM ModelMapHash h
+            JOIN ModelMap mm ON mm.id = h.model_map_id
+            WHERE h.hash = ?
+            "#,
+            hash
+        )
+        .fetch_optional(&self.pool)
+        .timed("model_map.find_by_hash", 1)
+        .await?;
+
+        Ok(result)
+    }
+
+    /// Find the id of the model map a known checkpoint hash points at, within a transaction
+    pub async fn find_id_by_hash_tx(&self, hash: &str, tx: &mut Transaction<'_, Sqlite>) -> Result<Option<i64>, Error> {
+        let result = sqlx::query_scalar!(
+            r#"
+            SELECT model_map_id FROM ModelMapHash WHERE hash = ?
+            "#,
+            hash
+        )
+        .fetch_optional(&mut **tx)
+        .await?;
+
+        Ok(result)
+    }
+
+    /// Every model mapping with how many RunMoreDetails rows point at it
+    pub async fn export_with_usage(&self) -> Result<Vec<ModelMapExportRow>, Error> {
+        let results = sqlx::query_as!(
+            ModelMapExportRow,
+            r#"
+            SELECT
+                mm.model_name,
+                mm.base_model,
+                (SELECT COUNT(*) FROM RunMoreDetails rmd WHERE rmd.ModelMapId = mm.id) AS "usage_count!: i64",
+                mm.id AS "id!: i64",
+                mm.version
+            FROM ModelMap mm
+            ORDER BY mm.model_name
+            "#
+        )
+        .fetch_all(&self.pool)
+        .timed("model_map.export_with_usage", 0)
+        .await?;
+
+        Ok(results)
+    }
+
+    /// Point a checkpoint hash at a model map within a transaction, replacing any previous mapping
+    pub async fn upsert_hash_tx(&self, model_map_id: i64, hash: &str, tx: &mut Transaction<'_, Sqlite>) -> Result<ModelMapHash, Error> {
+        let now = audit_timestamp();
+
+        let row = sqlx::query!(
+            r#"
+            INSERT INTO ModelMapHash (model_map_id, hash, created_at, updated_at)
+            VALUES (?, ?, ?, ?)
+            ON CONFLICT (hash) DO UPDATE SET model_map_id = excluded.model_map_id, updated_at = excluded.updated_at
+            RETURNING id AS "id!: i64", created_at
+            "#,
+            model_map_id,
+            hash,
+            now,
+            now
+        )
+        .fetch_one(&mut **tx)
+        .await?;
+
+        Ok(ModelMapHash {
+            id: Some(row.id),
+            // On conflict the row keeps its original creation time
+            created_at: row.created_at,
+            updated_at: Some(now),
+            model_map_id: Some(model_map_id),
+            hash: Some(hash.to_string()),
+        })
+    }
 }
 
 #[async_trait]
 impl Repository<ModelMap, i64> for ModelMapRepository {
     async fn create(&self, entity: ModelMap) -> Result<ModelMap, Error> {
-        let id = sqlx::query!(
+        let now = audit_timestamp();
+
+        let id = retry_on_busy(|| sqlx::query!(
             r#"
-            INSERT INTO ModelMap (model_name, base_model)
-            VALUES (?, ?)
+            INSERT INTO ModelMap (model_name, base_model, created_at, updated_at)
+            VALUES (?, ?, ?, ?)
             "#,
             entity.model_name,
-            entity.base_model
+            entity.base_model,
+            now,
+            now
         )
         .execute(&self.pool)
+        .timed("model_map.create", 4))
         .await?
         .last_insert_rowid() as i64;
 
         Ok(ModelMap {
             id: Some(id),
+            created_at: Some(now.clone()),
+            updated_at: Some(now),
+            version: 1,
             ..entity
         })
     }
@@ -94,13 +211,14 @@ impl Repository<ModelMap, i64> for ModelMapRepository {
         let result = sqlx::query_as!(
             ModelMap,
             r#"
-            SELECT id, model_name, base_model
+            SELECT id, model_name, base_model, version, created_at, updated_at
             FROM ModelMap
             WHERE id = ?
             "#,
             id
         )
         .fetch_optional(&self.pool)
+        .timed("model_map.find_by_id", 1)
         .await?;
 
         Ok(result)
@@ -110,12 +228,13 @@ impl Repository<ModelMap, i64> for ModelMapRepository {
         let results = sqlx::query_as!(
             ModelMap,
             r#"
-            SELECT id, model_name, base_model
+            SELECT id, model_name, base_model, version, created_at, updated_at
             FROM ModelMap
             ORDER BY id DESC
             "#
         )
         .fetch_all(&self.pool)
+        .timed("model_map.find_all", 0)
         .await?;
 
         Ok(results)
@@ -123,26 +242,41 @@ impl Repository<ModelMap, i64> for ModelMapRepository {
 
     async fn update(&self, entity: ModelMap) -> Result<ModelMap, Error> {
         let id = entity.id.ok_or(Error::RowNotFound)?;
-        
-        sqlx::query!(
+        let now = audit_timestamp();
+
+        let rows_affected = retry_on_busy(|| sqlx::query!(
             r#"
             UPDATE ModelMap
-            SET model_name = ?, base_model = ?
-            WHERE id = ?
+            SET model_name = ?, base_model = ?, updated_at = ?, version = version + 1
+            WHERE id = ? AND version = ?
             "#,
             entity.model_name,
             entity.base_model,
-            id
+            now,
+            id,
+            entity.version
         )
         .execute(&self.pool)
-        .await?;
+        .timed("model_map.update", 5))
+        .await?
+        .rows_affected();
 
-        Ok(entity)
+        // Either the row is gone or someone else updated it since it was read
+        if rows_affected == 0 {
+            return Err(Error::RowNotFound);
+        }
+
+        Ok(ModelMap {
+            version: entity.version + 1,
+            updated_at: Some(now),
+            ..entity
+        })
     }
 
     async fn delete(&self, id: i64) -> Result<(), Error> {
-        sqlx::query!("DELETE FROM ModelMap WHERE id = ?", id)
+        retry_on_busy(|| sqlx::query!("DELETE FROM ModelMap WHERE id = ?", id)
             .execute(&self.pool)
+            .timed("model_map.delete", 1))
             .await?;
         Ok(())
     }
@@ -150,22 +284,44 @@ impl Repository<ModelMap, i64> for ModelMapRepository {
     async fn count(&self) -> Result<i64, Error> {
         let count = sqlx::query!("SELECT COUNT(*) as count FROM ModelMap")
             .fetch_one(&self.pool)
+            .timed("model_map.count", 0)
             .await?
             .count;
         Ok(count)
     }
+
+    async fn exists(&self, id: i64) -> Result<bool, Error> {
+        let exists = sqlx::query_scalar!(r#"SELECT EXISTS(SELECT 1 FROM ModelMap WHERE id = ?) AS "exists!: bool""#, id)
+            .fetch_one(&self.pool)
+            .timed("model_map.exists", 1)
+            .await?;
+        Ok(exists)
+    }
+
+    async fn count_where(&self, filter: &Filter) -> Result<i64, Error> {
+        let count: i64 = sqlx::query_scalar(&filter.count_sql("ModelMap")?)
+            .bind(&filter.value)
+            .fetch_one(&self.pool)
+            .timed("model_map.count_where", 1)
+            .await?;
+        Ok(count)
+    }
 }
 
 #[async_trait]
 impl<'a> TransactionRepository<'a, ModelMap, i64> for ModelMapRepository {
     async fn create_tx(&self, entity: ModelMap, tx: &mut Transaction<'a, Sqlite>) -> Result<ModelMap, Error> {
+        let now = audit_timestamp();
+
         let id = sqlx::query!(
             r#"
-            INSERT INTO ModelMap (model_name, base_model)
-            VALUES (?, ?)
+            INSERT INTO ModelMap (model_name, base_model, created_at, updated_at)
+            VALUES (?, ?, ?, ?)
             "#,
             entity.model_name,
-            entity.base_model
+            entity.base_model,
+            now,
+            now
         )
         .execute(&mut **tx)
         .await?
@@ -173,27 +329,43 @@ impl<'a> TransactionRepository<'a, ModelMap, i64> for ModelMapRepository {
 
         Ok(ModelMap {
             id: Some(id),
+            created_at: Some(now.clone()),
+            updated_at: Some(now),
+            version: 1,
             ..entity
         })
     }
 
     async fn update_tx(&self, entity: ModelMap, tx: &mut Transaction<'a, Sqlite>) -> Result<ModelMap, Error> {
         let id = entity.id.ok_or(Error::RowNotFound)?;
-        
-        sqlx::query!(
+        let now = audit_timestamp();
+
+        let rows_affected = sqlx::query!(
             r#"
             UPDATE ModelMap
-            SET model_name = ?, base_model = ?
-            WHERE id = ?
+            SET model_name = ?, base_model = ?, updated_at = ?, version = version + 1
+            WHERE id = ? AND version = ?
             "#,
             entity.model_name,
             entity.base_model,
-            id
+            now,
+            id,
+            entity.version
         )
         .execute(&mut **tx)
-        .await?;
+        .await?
+        .rows_affected();
 
-        Ok(entity)
+        // Either the row is gone or someone else updated it since it was read
+        if rows_affected == 0 {
+            return Err(Error::RowNotFound);
+        }
+
+        Ok(ModelMap {
+            version: entity.version + 1,
+            updated_at: Some(now),
+            ..entity
+        })
     }
 
     async fn delete_tx(&self, id: i64, tx: &mut Transaction<'a, Sqlite>) -> Result<(), Error> {
@@ -0,0 +1,108 @@
+use sqlx::{Error, Sqlite, SqlitePool, Transaction};
+
+use crate::models::moderation::{ModerationEntry, MODERATION_STATUS_PENDING};
+use crate::repositories::audit_timestamp;
+use crate::repositories::connection::TimedQuery;
+
+pub struct ModerationRepository {
+    pool: SqlitePool,
+}
+
+impl ModerationRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Entries in one status for a tenant's runs with the run's current notes, oldest first
+    pub async fn find_by_status(&self, tenant_id: &str, status: &str) -> Result<Vec<ModerationEntry>, Error> {
+        let results = sqlx::query_as!(
+            ModerationEntry,
+            r#"
+            SELECT mq.id AS "id!", mq.run_id, mq.reasons, mq.status, r.notes, mq.created_at, mq.reviewed_at
+            FROM moderation_queue mq
+            LEFT JOIN runs r ON r.id = mq.run_id
+            WHERE mq.status = ? AND r.tenant_id = ?
+            ORDER BY mq.id
+            "#,
+            status,
+            tenant_id
+        )
+        .fetch_all(&self.pool)
+        .timed("moderation.find_by_status", 2)
+        .await?;
+
+        Ok(results)
+    }
+
+    /// An entry for one of a tenant's runs within a transaction
+    pub async fn find_by_id_tx(
+        &self,
+        id: i64,
+        tenant_id: &str,
+        tx: &mut Transaction<'_, Sqlite>,
+    ) -> Result<Option<ModerationEntry>, Error> {
+        let result = sqlx::query_as!(
+            ModerationEntry,
+            r#"
+            SELECT mq.id AS "id!", mq.run_id, mq.reasons, mq.status, r.notes, mq.created_at, mq.reviewed_at
+            FROM moderation_queue mq
+            LEFT JOIN runs r ON r.id = mq.run_id
+            WHERE mq.id = ? AND r.tenant_id = ?
+            "#,
+            id,
+            tenant_id
+        )
+        .fetch_optional(&mut **tx)
+        .await?;
+
+        Ok(result)
+    }
+
+    /// Whether the run is still waiting for review, and so must not be published
+    pub async fn is_pending(&self, run_id: i64) -> Result<bool, Error> {
+        let pending = sqlx::query_scalar!(
+            r#"SELECT EXISTS(SELECT 1 FROM moderation_queue WHERE run_id = ? AND status = ?) AS "pending!: bool""#,
+            run_id,
+            MODERATION_STATUS_PENDING
+        )
+        .fetch_one(&self.pool)
+        .timed("moderation.is_pending", 2)
+        .await?;
+
+        Ok(pending)
+    }
+
+    /// Queue a run for review, returning the entry id
+    pub async fn create_tx(&self, run_id: i64, reasons: &str, tx: &mut Transaction<'_, Sqlite>) -> Result<i64, Error> {
+        let now = audit_timestamp();
+
+        let id = sqlx::query!(
+            "INSERT INTO moderation_queue (run_id, reasons, status, created_at) VALUES (?, ?, ?, ?)",
+            run_id,
+            reasons,
+            MODERATION_STATUS_PENDING,
+            now
+        )
+        .execute(&mut **tx)
+        .await?
+        .last_insert_rowid();
+
+        Ok(id)
+    }
+
+    /// Record the review of an entry, returning the rows updated
+    pub async fn set_status_tx(&self, id: i64, status: &str, tx: &mut Transaction<'_, Sqlite>) -> Result<u64, Error> {
+        let now = audit_timestamp();
+
+        let result = sqlx::query!(
+            "UPDATE moderation_queue SET status = ?, reviewed_at = ? WHERE id = ?",
+            status,
+            now,
+            id
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
@@ -1,8 +1,15 @@
+use std::collections::HashMap;
+
 use async_trait::async_trait;
-use sqlx::{Error, SqlitePool, Transaction, Sqlite};
+use sqlx::{query::Query, sqlite::SqliteArguments, Error, SqlitePool, Transaction, Sqlite};
 
 use crate::models::performance_result::PerformanceResult;
 use crate::repositories::traits::{Repository, TransactionRepository, BulkRepository, BulkTransactionRepository};
+use crate::repositories::audit_timestamp;
+use crate::repositories::batch::{first_by_run, json_ids};
+use crate::repositories::connection::{retry_on_busy, TimedQuery};
+use crate::repositories::shadow_table::ShadowRow;
+use crate::repositories::query_builder::Filter;
 
 pub struct PerformanceResultRepository {
     pool: SqlitePool,
@@ -18,7 +25,7 @@ impl PerformanceResultRepository {
         let results = sqlx::query_as!(
             PerformanceResult,
             r#"
-            SELECT id, run_id, its, avg_its
+            SELECT id, run_id, its, avg_its, vram_usage_kind, created_at, updated_at
             FROM performanceResult
             WHERE run_id = ?
             ORDER BY id DESC
@@ -26,15 +33,56 @@ impl PerformanceResultRepository {
             run_id
         )
         .fetch_all(&self.pool)
+        .timed("performance_result.find_by_run_id", 1)
         .await?;
 
         Ok(results)
     }
 
+    /// The newest performance results row of each of the given runs, in one query
+    pub async fn find_by_run_ids(&self, run_ids: &[i64]) -> Result<HashMap<i64, PerformanceResult>, Error> {
+        let ids = json_ids(run_ids)?;
+        let results = sqlx::query_as!(
+            PerformanceResult,
+            r#"
+            SELECT id, run_id, its, avg_its, vram_usage_kind, created_at, updated_at
+            FROM performanceResult
+            WHERE run_id IN (SELECT value FROM json_each(?))
+            ORDER BY run_id, id DESC
+            "#,
+            ids
+        )
+        .fetch_all(&self.pool)
+        .timed("performance_result.find_by_run_ids", 1)
+        .await?;
+
+        Ok(first_by_run(results, |row| row.run_id))
+    }
+
+    /// A tenant's runs whose vram_usage the process-its step classified as `kind`, oldest first
+    pub async fn find_run_ids_by_vram_usage_kind(&self, tenant_id: &str, kind: &str) -> Result<Vec<i64>, Error> {
+        let run_ids = sqlx::query_scalar!(
+            r#"
+            SELECT run_id AS "run_id!: i64"
+            FROM performanceResult
+            WHERE vram_usage_kind = ? AND run_id IN (SELECT id FROM runs WHERE tenant_id = ?)
+            ORDER BY run_id
+            "#,
+            kind,
+            tenant_id
+        )
+        .fetch_all(&self.pool)
+        .timed("performance_result.find_run_ids_by_vram_usage_kind", 2)
+        .await?;
+
+        Ok(run_ids)
+    }
+
     /// Clear all performance results
     pub async fn clear_all(&self) -> Result<(), Error> {
-        sqlx::query!("DELETE FROM performanceResult")
+        retry_on_busy(|| sqlx::query!("DELETE FROM performanceResult")
             .execute(&self.pool)
+            .timed("performance_result.clear_all", 0))
             .await?;
         Ok(())
     }
@@ -48,24 +96,51 @@ impl PerformanceResultRepository {
     }
 }
 
+impl ShadowRow for PerformanceResult {
+    const TABLE: &'static str = "performanceResult";
+    const COLUMNS: &'static [&'static str] = &["run_id", "its", "avg_its", "vram_usage_kind"];
+
+    fn bind_columns<'q>(self, query: Query<'q, Sqlite, SqliteArguments<'q>>) -> Query<'q, Sqlite, SqliteArguments<'q>> {
+        query
+            .bind(self.run_id)
+            .bind(self.its)
+            .bind(self.avg_its)
+            .bind(self.vram_usage_kind)
+    }
+}
+
 #[async_trait]
 impl Repository<PerformanceResult, i64> for PerformanceResultRepository {
     async fn create(&self, entity: PerformanceResult) -> Result<PerformanceResult, Error> {
-        let id = sqlx::query!(
+        let now = audit_timestamp();
+
+        let row = retry_on_busy(|| sqlx::query!(
             r#"
-            INSERT INTO performanceResult (run_id, its, avg_its)
-            VALUES (?, ?, ?)
+            INSERT INTO performanceResult (run_id, its, avg_its, vram_usage_kind, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?)
+            ON CONFLICT (run_id) DO UPDATE SET
+                its = excluded.its,
+                avg_its = excluded.avg_its,
+                vram_usage_kind = excluded.vram_usage_kind,
+                updated_at = excluded.updated_at
+            RETURNING id AS "id!: i64", created_at
             "#,
             entity.run_id,
             entity.its,
-            entity.avg_its
+            entity.avg_its,
+            entity.vram_usage_kind,
+            now,
+            now
         )
-        .execute(&self.pool)
-        .await?
-        .last_insert_rowid() as i64;
+        .fetch_one(&self.pool)
+        .timed("performance_result.create", 6))
+        .await?;
 
         Ok(PerformanceResult {
-            id: Some(id),
+            id: Some(row.id),
+            // On conflict the row keeps its original creation time
+            created_at: row.created_at,
+            updated_at: Some(now),
             ..entity
         })
     }
@@ -74,13 +149,14 @@ impl Repository<PerformanceResult, i64> for PerformanceResultRepository {
         let result = sqlx::query_as!(
             PerformanceResult,
             r#"
-            SELECT id, run_id, its, avg_its
+            SELECT id, run_id, its, avg_its, vram_usage_kind, created_at, updated_at
             FROM performanceResult
             WHERE id = ?
             "#,
             id
         )
         .fetch_optional(&self.pool)
+        .timed("performance_result.find_by_id", 1)
         .await?;
 
         Ok(result)
@@ -90,12 +166,13 @@ impl Repository<PerformanceResult, i64> for PerformanceResultRepository {
         let results = sqlx::query_as!(
             PerformanceResult,
             r#"
-            SELECT id, run_id, its, avg_its
+            SELECT id, run_id, its, avg_its, vram_usage_kind, created_at, updated_at
             FROM performanceResult
             ORDER BY id DESC
             "#
         )
         .fetch_all(&self.pool)
+        .timed("performance_result.find_all", 0)
         .await?;
 
         Ok(results)
@@ -103,27 +180,35 @@ impl Repository<PerformanceResult, i64> for PerformanceResultRepository {
 
     async fn update(&self, entity: PerformanceResult) -> Result<PerformanceResult, Error> {
         let id = entity.id.ok_or(Error::RowNotFound)?;
+        let now = audit_timestamp();
         
-        sqlx::query!(
+        retry_on_busy(|| sqlx::query!(
             r#"
             UPDATE performanceResult
-            SET run_id = ?, its = ?, avg_its = ?
+            SET run_id = ?, its = ?, avg_its = ?, vram_usage_kind = ?, updated_at = ?
             WHERE id = ?
             "#,
             entity.run_id,
             entity.its,
             entity.avg_its,
+            entity.vram_usage_kind,
+            now,
             id
         )
         .execute(&self.pool)
+        .timed("performance_result.update", 6))
         .await?;
 
-        Ok(entity)
+        Ok(PerformanceResult {
+            updated_at: Some(now),
+            ..entity
+        })
     }
 
     async fn delete(&self, id: i64) -> Result<(), Error> {
-        sqlx::query!("DELETE FROM performanceResult WHERE id = ?", id)
+        retry_on_busy(|| sqlx::query!("DELETE FROM performanceResult WHERE id = ?", id)
             .execute(&self.pool)
+            .timed("performance_result.delete", 1))
             .await?;
         Ok(())
     }
@@ -131,10 +216,28 @@ impl Repository<PerformanceResult, i64> for PerformanceResultRepository {
     async fn count(&self) -> Result<i64, Error> {
         let count = sqlx::query!("SELECT COUNT(*) as count FROM performanceResult")
             .fetch_one(&self.pool)
+            .timed("performance_result.count", 0)
             .await?
             .count;
         Ok(count)
     }
+
+    async fn exists(&self, id: i64) -> Result<bool, Error> {
+        let exists = sqlx::query_scalar!(r#"SELECT EXISTS(SELECT 1 FROM performanceResult WHERE id = ?) AS "exists!: bool""#, id)
+            .fetch_one(&self.pool)
+            .timed("performance_result.exists", 1)
+            .await?;
+        Ok(exists)
+    }
+
+    async fn count_where(&self, filter: &Filter) -> Result<i64, Error> {
+        let count: i64 = sqlx::query_scalar(&filter.count_sql("performanceResult")?)
+            .bind(&filter.value)
+            .fetch_one(&self.pool)
+            .timed("performance_result.count_where", 1)
+            .await?;
+        Ok(count)
+    }
 }
 
 #[async_trait]
@@ -202,43 +305,62 @@ impl BulkRepository<PerformanceResult, i64> for PerformanceResultRepository {
 #[async_trait]
 impl<'a> TransactionRepository<'a, PerformanceResult, i64> for PerformanceResultRepository {
     async fn create_tx(&self, entity: PerformanceResult, tx: &mut Transaction<'a, Sqlite>) -> Result<PerformanceResult, Error> {
-        let id = sqlx::query!(
+        let now = audit_timestamp();
+
+        let row = sqlx::query!(
             r#"
-            INSERT INTO performanceResult (run_id, its, avg_its)
-            VALUES (?, ?, ?)
+            INSERT INTO performanceResult (run_id, its, avg_its, vram_usage_kind, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?)
+            ON CONFLICT (run_id) DO UPDATE SET
+                its = excluded.its,
+                avg_its = excluded.avg_its,
+                vram_usage_kind = excluded.vram_usage_kind,
+                updated_at = excluded.updated_at
+            RETURNING id AS "id!: i64", created_at
             "#,
             entity.run_id,
             entity.its,
-            entity.avg_its
+            entity.avg_its,
+            entity.vram_usage_kind,
+            now,
+            now
         )
-        .execute(&mut **tx)
-        .await?
-        .last_insert_rowid() as i64;
+        .fetch_one(&mut **tx)
+        .await?;
 
         Ok(PerformanceResult {
-            id: Some(id),
+            id: Some(row.id),
+            // On conflict the row keeps its original creation time
+            created_at: row.created_at,
+            updated_at: Some(now),
             ..entity
         })
     }
 
     async fn update_tx(&self, entity: PerformanceResult, tx: &mut Transaction<'a, Sqlite>) -> Result<PerformanceResult, Error> {
         let id = entity.id.ok_or(Error::RowNotFound)?;
+        let now = audit_timestamp();
         
         sqlx::query!(
             r#"
             UPDATE performanceResult
-            SET run_id = ?, its = ?, avg_its = ?
+            SET run_id = ?, its = ?, avg_its = ?, vram_usage_kind = ?, updated_at = ?
             WHERE id = ?
             "#,
             entity.run_id,
             entity.its,
             entity.avg_its,
+            entity.vram_usage_kind,
+            now,
             id
         )
         .execute(&mut **tx)
         .await?;
 
-        Ok(entity)
+        Ok(PerformanceResult {
+            updated_at: Some(now),
+            ..entity
+        })
     }
 
     async fn delete_tx(&self, id: i64, tx: &mut Transaction<'a, Sqlite>) -> Result<(), Error> {
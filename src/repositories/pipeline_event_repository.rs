@@ -0,0 +1,58 @@
+use sqlx::{Error, SqlitePool};
+
+use crate::models::pipeline_event::PipelineEvent;
+use crate::repositories::connection::{retry_on_busy, TimedQuery};
+
+pub struct PipelineEventRepository {
+    pool: SqlitePool,
+}
+
+impl PipelineEventRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Record a stage execution, returning the event id
+    pub async fn create(&self, event: &PipelineEvent) -> Result<i64, Error> {
+        let id = retry_on_busy(|| sqlx::query!(
+            r#"
+            INSERT INTO pipeline_events (stage, started_at, finished_at, rows_in, rows_out, status, error)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            "#,
+            event.stage,
+            event.started_at,
+            event.finished_at,
+            event.rows_in,
+            event.rows_out,
+            event.status,
+            event.error
+        )
+        .execute(&self.pool)
+        .timed("pipeline_events.create", 7))
+        .await?
+        .last_insert_rowid();
+
+        Ok(id)
+    }
+
+    /// The most recent executions, optionally of one stage only, newest first
+    pub async fn find_recent(&self, stage: Option<&str>, limit: i64) -> Result<Vec<PipelineEvent>, Error> {
+        let results = sqlx::query_as!(
+            PipelineEvent,
+            r#"
+            SELECT id, stage, started_at, finished_at, rows_in, rows_out, status, error
+            FROM pipeline_events
+            WHERE (?1 IS NULL OR stage = ?1)
+            ORDER BY id DESC
+            LIMIT ?2
+            "#,
+            stage,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .timed("pipeline_events.find_recent", 2)
+        .await?;
+
+        Ok(results)
+    }
+}
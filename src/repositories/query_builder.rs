@@ -1,3 +1,5 @@
+use sqlx::Error;
+
 /// Pagination parameters
 pub struct Pagination {
     pub limit: Option<u32>,
@@ -35,9 +37,32 @@ pub struct Filter {
 }
 
 impl Filter {
+    pub fn new(field: impl Into<String>, value: impl ToString) -> Self {
+        Self {
+            field: field.into(),
+            value: value.to_string(),
+        }
+    }
+
     pub fn to_sql(&self) -> String {
         format!(" WHERE {} = ?", self.field)
     }
+
+    /// `SELECT COUNT(*)` of the rows of `table` matching the filter, its value bound as the only parameter
+    ///
+    /// The field is written into the SQL, so anything but a plain column name
+    /// is refused as an unknown column. SQLite converts the bound text to the
+    /// column's affinity, so `Filter::new("run_id", 5)` matches an INTEGER column.
+    pub fn count_sql(&self, table: &str) -> Result<String, Error> {
+        let mut chars = self.field.chars();
+        let is_identifier = chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !is_identifier {
+            return Err(Error::ColumnNotFound(self.field.clone()));
+        }
+
+        Ok(format!("SELECT COUNT(*) FROM {}{}", table, self.to_sql()))
+    }
 }
 
 /// Combine query parts for SELECT statements
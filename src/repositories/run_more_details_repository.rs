@@ -1,8 +1,15 @@
+use std::collections::HashMap;
+
 use async_trait::async_trait;
-use sqlx::{Error, SqlitePool, Transaction, Sqlite};
+use sqlx::{query::Query, sqlite::SqliteArguments, Error, SqlitePool, Transaction, Sqlite};
 
 use crate::models::run_more_details::RunMoreDetails;
 use crate::repositories::traits::{Repository, TransactionRepository, BulkRepository, BulkTransactionRepository};
+use crate::repositories::audit_timestamp;
+use crate::repositories::batch::{first_by_run, json_ids};
+use crate::repositories::connection::{retry_on_busy, TimedQuery};
+use crate::repositories::shadow_table::ShadowRow;
+use crate::repositories::query_builder::Filter;
 
 pub struct RunMoreDetailsRepository {
     pool: SqlitePool,
@@ -18,7 +25,7 @@ impl RunMoreDetailsRepository {
         let results = sqlx::query_as!(
             RunMoreDetails,
             r#"
-            SELECT id, run_id, timestamp, model_name, user, notes, ModelMapId as "model_map_id"
+            SELECT id, run_id, timestamp, model_name, user, notes, ModelMapId as "model_map_id", created_at, updated_at
             FROM RunMoreDetails
             WHERE run_id = ?
             ORDER BY id DESC
@@ -26,17 +33,38 @@ impl RunMoreDetailsRepository {
             run_id
         )
         .fetch_all(&self.pool)
+        .timed("run_more_details.find_by_run_id", 1)
         .await?;
 
         Ok(results)
     }
 
+    /// The newest run details row of each of the given runs, in one query
+    pub async fn find_by_run_ids(&self, run_ids: &[i64]) -> Result<HashMap<i64, RunMoreDetails>, Error> {
+        let ids = json_ids(run_ids)?;
+        let results = sqlx::query_as!(
+            RunMoreDetails,
+            r#"
+            SELECT id, run_id, timestamp, model_name, user, notes, ModelMapId as "model_map_id", created_at, updated_at
+            FROM RunMoreDetails
+            WHERE run_id IN (SELECT value FROM json_each(?))
+            ORDER BY run_id, id DESC
+            "#,
+            ids
+        )
+        .fetch_all(&self.pool)
+        .timed("run_more_details.find_by_run_ids", 1)
+        .await?;
+
+        Ok(first_by_run(results, |row| row.run_id))
+    }
+
     /// Find run more details by model_name
     pub async fn find_by_model_name(&self, model_name: &str) -> Result<Vec<RunMoreDetails>, Error> {
         let results = sqlx::query_as!(
             RunMoreDetails,
             r#"
-            SELECT id, run_id, timestamp, model_name, user, notes, ModelMapId as "model_map_id"
+            SELECT id, run_id, timestamp, model_name, user, notes, ModelMapId as "model_map_id", created_at, updated_at
             FROM RunMoreDetails
             WHERE model_name = ?
             ORDER BY id DESC
@@ -44,6 +72,7 @@ impl RunMoreDetailsRepository {
             model_name
         )
         .fetch_all(&self.pool)
+        .timed("run_more_details.find_by_model_name", 1)
         .await?;
 
         Ok(results)
@@ -54,7 +83,7 @@ impl RunMoreDetailsRepository {
         let results = sqlx::query_as!(
             RunMoreDetails,
             r#"
-            SELECT id, run_id, timestamp, model_name, user, notes, ModelMapId as "model_map_id"
+            SELECT id, run_id, timestamp, model_name, user, notes, ModelMapId as "model_map_id", created_at, updated_at
             FROM RunMoreDetails
             WHERE user = ?
             ORDER BY id DESC
@@ -62,6 +91,7 @@ impl RunMoreDetailsRepository {
             user
         )
         .fetch_all(&self.pool)
+        .timed("run_more_details.find_by_user", 1)
         .await?;
 
         Ok(results)
@@ -69,8 +99,9 @@ impl RunMoreDetailsRepository {
 
     /// Clear all records from the RunMoreDetails table
     pub async fn clear_all(&self) -> Result<(), Error> {
-        sqlx::query!("DELETE FROM RunMoreDetails")
+        retry_on_busy(|| sqlx::query!("DELETE FROM RunMoreDetails")
             .execute(&self.pool)
+            .timed("run_more_details.clear_all", 0))
             .await?;
         Ok(())
     }
@@ -83,45 +114,108 @@ impl RunMoreDetailsRepository {
         Ok(())
     }
 
+    /// Move every processed run detail of the tenant's `from` to `to`, returning the number of rows changed
+    pub async fn rename_user_tx(&self, from: &str, to: &str, tenant_id: &str, tx: &mut Transaction<'_, Sqlite>) -> Result<u64, Error> {
+        let now = audit_timestamp();
+
+        let result = sqlx::query!(
+            "UPDATE RunMoreDetails SET user = ?, updated_at = ? WHERE user = ? AND run_id IN (SELECT id FROM runs WHERE tenant_id = ?)",
+            to,
+            now,
+            from,
+            tenant_id
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Clear the notes copied from a run, returning the rows updated
+    pub async fn clear_notes_tx(&self, run_id: i64, tx: &mut Transaction<'_, Sqlite>) -> Result<u64, Error> {
+        let now = audit_timestamp();
+
+        let result = sqlx::query!(
+            "UPDATE RunMoreDetails SET notes = NULL, updated_at = ? WHERE run_id = ?",
+            now,
+            run_id
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
     /// Find all RunMoreDetails records that don't have ModelMapId filled
     pub async fn find_without_modelmapid(&self) -> Result<Vec<RunMoreDetails>, Error> {
         let results = sqlx::query_as!(
             RunMoreDetails,
             r#"
-            SELECT id, run_id, timestamp, model_name, user, notes, ModelMapId as "model_map_id"
+            SELECT id, run_id, timestamp, model_name, user, notes, ModelMapId as "model_map_id", created_at, updated_at
             FROM RunMoreDetails
             WHERE ModelMapId IS NULL
             ORDER BY id DESC
             "#
         )
         .fetch_all(&self.pool)
+        .timed("run_more_details.find_without_modelmapid", 0)
         .await?;
 
         Ok(results)
     }
 }
 
+impl ShadowRow for RunMoreDetails {
+    const TABLE: &'static str = "RunMoreDetails";
+    const COLUMNS: &'static [&'static str] = &["run_id", "timestamp", "model_name", "user", "notes", "ModelMapId"];
+
+    fn bind_columns<'q>(self, query: Query<'q, Sqlite, SqliteArguments<'q>>) -> Query<'q, Sqlite, SqliteArguments<'q>> {
+        query
+            .bind(self.run_id)
+            .bind(self.timestamp)
+            .bind(self.model_name)
+            .bind(self.user)
+            .bind(self.notes)
+            .bind(self.model_map_id)
+    }
+}
+
 #[async_trait]
 impl Repository<RunMoreDetails, i64> for RunMoreDetailsRepository {
     async fn create(&self, entity: RunMoreDetails) -> Result<RunMoreDetails, Error> {
-        let id = sqlx::query!(
+        let now = audit_timestamp();
+
+        let row = retry_on_busy(|| sqlx::query!(
             r#"
-            INSERT INTO RunMoreDetails (run_id, timestamp, model_name, user, notes, ModelMapId)
-            VALUES (?, ?, ?, ?, ?, ?)
+            INSERT INTO RunMoreDetails (run_id, timestamp, model_name, user, notes, ModelMapId, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT (run_id) DO UPDATE SET
+                timestamp = excluded.timestamp,
+                model_name = excluded.model_name,
+                user = excluded.user,
+                notes = excluded.notes,
+                ModelMapId = excluded.ModelMapId,
+                updated_at = excluded.updated_at
+            RETURNING id AS "id!: i64", created_at
             "#,
             entity.run_id,
             entity.timestamp,
             entity.model_name,
             entity.user,
             entity.notes,
-            entity.model_map_id
+            entity.model_map_id,
+            now,
+            now
         )
-        .execute(&self.pool)
-        .await?
-        .last_insert_rowid() as i64;
+        .fetch_one(&self.pool)
+        .timed("run_more_details.create", 8))
+        .await?;
 
         Ok(RunMoreDetails {
-            id: Some(id),
+            id: Some(row.id),
+            // On conflict the row keeps its original creation time
+            created_at: row.created_at,
+            updated_at: Some(now),
             ..entity
         })
     }
@@ -130,13 +224,14 @@ impl Repository<RunMoreDetails, i64> for RunMoreDetailsRepository {
         let result = sqlx::query_as!(
             RunMoreDetails,
             r#"
-            SELECT id, run_id, timestamp, model_name, user, notes, ModelMapId as "model_map_id"
+            SELECT id, run_id, timestamp, model_name, user, notes, ModelMapId as "model_map_id", created_at, updated_at
             FROM RunMoreDetails
             WHERE id = ?
             "#,
             id
         )
         .fetch_optional(&self.pool)
+        .timed("run_more_details.find_by_id", 1)
         .await?;
 
         Ok(result)
@@ -146,12 +241,13 @@ impl Repository<RunMoreDetails, i64> for RunMoreDetailsRepository {
         let results = sqlx::query_as!(
             RunMoreDetails,
             r#"
-            SELECT id, run_id, timestamp, model_name, user, notes, ModelMapId as "model_map_id"
+            SELECT id, run_id, timestamp, model_name, user, notes, ModelMapId as "model_map_id", created_at, updated_at
             FROM RunMoreDetails
             ORDER BY id DESC
             "#
         )
         .fetch_all(&self.pool)
+        .timed("run_more_details.find_all", 0)
         .await?;
 
         Ok(results)
@@ -159,11 +255,12 @@ impl Repository<RunMoreDetails, i64> for RunMoreDetailsRepository {
 
     async fn update(&self, entity: RunMoreDetails) -> Result<RunMoreDetails, Error> {
         let id = entity.id.ok_or(Error::RowNotFound)?;
+        let now = audit_timestamp();
         
-        sqlx::query!(
+        retry_on_busy(|| sqlx::query!(
             r#"
             UPDATE RunMoreDetails
-            SET run_id = ?, timestamp = ?, model_name = ?, user = ?, notes = ?, ModelMapId = ?
+            SET run_id = ?, timestamp = ?, model_name = ?, user = ?, notes = ?, ModelMapId = ?, updated_at = ?
             WHERE id = ?
             "#,
             entity.run_id,
@@ -172,17 +269,23 @@ impl Repository<RunMoreDetails, i64> for RunMoreDetailsRepository {
             entity.user,
             entity.notes,
             entity.model_map_id,
+            now,
             id
         )
         .execute(&self.pool)
+        .timed("run_more_details.update", 8))
         .await?;
 
-        Ok(entity)
+        Ok(RunMoreDetails {
+            updated_at: Some(now),
+            ..entity
+        })
     }
 
     async fn delete(&self, id: i64) -> Result<(), Error> {
-        sqlx::query!("DELETE FROM RunMoreDetails WHERE id = ?", id)
+        retry_on_busy(|| sqlx::query!("DELETE FROM RunMoreDetails WHERE id = ?", id)
             .execute(&self.pool)
+            .timed("run_more_details.delete", 1))
             .await?;
         Ok(())
     }
@@ -190,44 +293,77 @@ impl Repository<RunMoreDetails, i64> for RunMoreDetailsRepository {
     async fn count(&self) -> Result<i64, Error> {
         let count = sqlx::query!("SELECT COUNT(*) as count FROM RunMoreDetails")
             .fetch_one(&self.pool)
+            .timed("run_more_details.count", 0)
             .await?
             .count;
         Ok(count)
     }
+
+    async fn exists(&self, id: i64) -> Result<bool, Error> {
+        let exists = sqlx::query_scalar!(r#"SELECT EXISTS(SELECT 1 FROM RunMoreDetails WHERE id = ?) AS "exists!: bool""#, id)
+            .fetch_one(&self.pool)
+            .timed("run_more_details.exists", 1)
+            .await?;
+        Ok(exists)
+    }
+
+    async fn count_where(&self, filter: &Filter) -> Result<i64, Error> {
+        let count: i64 = sqlx::query_scalar(&filter.count_sql("RunMoreDetails")?)
+            .bind(&filter.value)
+            .fetch_one(&self.pool)
+            .timed("run_more_details.count_where", 1)
+            .await?;
+        Ok(count)
+    }
 }
 
 #[async_trait]
 impl<'a> TransactionRepository<'a, RunMoreDetails, i64> for RunMoreDetailsRepository {
     async fn create_tx(&self, entity: RunMoreDetails, tx: &mut Transaction<'a, Sqlite>) -> Result<RunMoreDetails, Error> {
-        let id = sqlx::query!(
+        let now = audit_timestamp();
+
+        let row = sqlx::query!(
             r#"
-            INSERT INTO RunMoreDetails (run_id, timestamp, model_name, user, notes, ModelMapId)
-            VALUES (?, ?, ?, ?, ?, ?)
+            INSERT INTO RunMoreDetails (run_id, timestamp, model_name, user, notes, ModelMapId, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT (run_id) DO UPDATE SET
+                timestamp = excluded.timestamp,
+                model_name = excluded.model_name,
+                user = excluded.user,
+                notes = excluded.notes,
+                ModelMapId = excluded.ModelMapId,
+                updated_at = excluded.updated_at
+            RETURNING id AS "id!: i64", created_at
             "#,
             entity.run_id,
             entity.timestamp,
             entity.model_name,
             entity.user,
             entity.notes,
-            entity.model_map_id
+            entity.model_map_id,
+            now,
+            now
         )
-        .execute(&mut **tx)
-        .await?
-        .last_insert_rowid() as i64;
+        .fetch_one(&mut **tx)
+        .await?;
 
         Ok(RunMoreDetails {
-            id: Some(id),
+            id: Some(row.id),
+            // On conflict the row keeps its original creation time
+            created_at: row.created_at,
+            updated_at: Some(now),
             ..entity
         })
     }
 
     async fn update_tx(&self, entity: RunMoreDetails, tx: &mut Transaction<'a, Sqlite>) -> Result<RunMoreDetails, Error> {
         let id = entity.id.ok_or(Error::RowNotFound)?;
+        let now = audit_timestamp();
         
         sqlx::query!(
             r#"
             UPDATE RunMoreDetails
-            SET run_id = ?, timestamp = ?, model_name = ?, user = ?, notes = ?, ModelMapId = ?
+            SET run_id = ?, timestamp = ?, model_name = ?, user = ?, notes = ?, ModelMapId = ?, updated_at = ?
             WHERE id = ?
             "#,
             entity.run_id,
@@ -236,12 +372,16 @@ impl<'a> TransactionRepository<'a, RunMoreDetails, i64> for RunMoreDetailsReposi
             entity.user,
             entity.notes,
             entity.model_map_id,
+            now,
             id
         )
         .execute(&mut **tx)
         .await?;
 
-        Ok(entity)
+        Ok(RunMoreDetails {
+            updated_at: Some(now),
+            ..entity
+        })
     }
 
     async fn delete_tx(&self, id: i64, tx: &mut Transaction<'a, Sqlite>) -> Result<(), Error> {
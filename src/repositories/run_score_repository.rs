@@ -0,0 +1,541 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use sqlx::{Error, SqlitePool, Transaction, Sqlite};
+
+use crate::models::run_score::{RunScore, ScoringInput, LeaderboardEntry, TopConfiguration, TopConfigurationFilter};
+use crate::repositories::traits::{Repository, TransactionRepository, BulkRepository, BulkTransactionRepository};
+use crate::repositories::audit_timestamp;
+use crate::repositories::batch::{first_by_run, json_ids};
+use crate::repositories::connection::{retry_on_busy, TimedQuery};
+use crate::repositories::query_builder::Filter;
+
+pub struct RunScoreRepository {
+    pool: SqlitePool,
+}
+
+impl RunScoreRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Find run scores by run_id
+    pub async fn find_by_run_id(&self, run_id: i64) -> Result<Vec<RunScore>, Error> {
+        let results = sqlx::query_as!(
+            RunScore,
+            r#"
+            SELECT id, run_id, score, normalized_its, model_factor, vram_gb, created_at, updated_at
+            FROM RunScore
+            WHERE run_id = ?
+            ORDER BY id DESC
+            "#,
+            run_id
+        )
+        .fetch_all(&self.pool)
+        .timed("run_score.find_by_run_id", 1)
+        .await?;
+
+        Ok(results)
+    }
+
+    /// The newest scores row of each of the given runs, in one query
+    pub async fn find_by_run_ids(&self, run_ids: &[i64]) -> Result<HashMap<i64, RunScore>, Error> {
+        let ids = json_ids(run_ids)?;
+        let results = sqlx::query_as!(
+            RunScore,
+            r#"
+            SELECT id, run_id, score, normalized_its, model_factor, vram_gb, created_at, updated_at
+            FROM RunScore
+            WHERE run_id IN (SELECT value FROM json_each(?))
+            ORDER BY run_id, id DESC
+            "#,
+            ids
+        )
+        .fetch_all(&self.pool)
+        .timed("run_score.find_by_run_ids", 1)
+        .await?;
+
+        Ok(first_by_run(results, |row| row.run_id))
+    }
+
+    /// Fetch the tenant, avg_its, base model and GPU device of every run for scoring within a transaction
+    pub async fn find_scoring_inputs_tx(&self, tx: &mut Transaction<'_, Sqlite>) -> Result<Vec<ScoringInput>, Error> {
+        let results = sqlx::query_as!(
+            ScoringInput,
+            r#"
+            SELECT
+                r.id AS "run_id!",
+                r.tenant_id,
+                pr.avg_its AS "avg_its?",
+                mm.base_model AS "base_model?",
+                g.device AS "device?"
+            FROM runs r
+            LEFT JOIN performanceResult pr ON pr.run_id = r.id
+            LEFT JOIN RunMoreDetails rmd ON rmd.run_id = r.id
+            LEFT JOIN ModelMap mm ON mm.id = rmd.ModelMapId
+            LEFT JOIN GPU g ON g.run_id = r.id AND g.gpu_index = 0
+            GROUP BY r.id
+            ORDER BY r.tenant_id, r.id
+            "#
+        )
+        .fetch_all(&mut **tx)
+        .timed("run_score.find_scoring_inputs", 0)
+        .await?;
+
+        Ok(results)
+    }
+
+    /// Fetch a tenant's top runs ordered by `score` or `avg_its`, optionally only runs carrying `tag`
+    ///
+    /// Runs carrying `exclude_tag` are left out, and so are runs whose first GPU
+    /// is integrated unless `include_integrated` is set.
+    pub async fn find_leaderboard(
+        &self,
+        tenant_id: &str,
+        metric: &str,
+        tag: Option<&str>,
+        exclude_tag: Option<&str>,
+        include_integrated: bool,
+        limit: i64,
+    ) -> Result<Vec<LeaderboardEntry>, Error> {
+        let results = sqlx::query_as!(
+            LeaderboardEntry,
+            r#"
+            SELECT
+                r.id AS "run_id!",
+                rs.score AS "score?",
+                pr.avg_its AS "avg_its?",
+                g.device AS "device?",
+                r.model_name AS "model_name?",
+                mm.base_model AS "base_model?",
+                r.user AS "user?",
+                EXISTS (SELECT 1 FROM GPU mg WHERE mg.run_id = r.id AND mg.gpu_index > 0) AS "multi_gpu!: bool"
+            FROM runs r
+            LEFT JOIN RunScore rs ON rs.run_id = r.id
+            LEFT JOIN performanceResult pr ON pr.run_id = r.id
+            LEFT JOIN RunMoreDetails rmd ON rmd.run_id = r.id
+            LEFT JOIN ModelMap mm ON mm.id = rmd.ModelMapId
+            LEFT JOIN GPU g ON g.run_id = r.id AND g.gpu_index = 0
+            WHERE (CASE WHEN ?1 = 'score' THEN rs.score ELSE pr.avg_its END) IS NOT NULL
+              AND (?3 IS NULL OR r.id IN (SELECT rt.run_id FROM run_tags rt JOIN tags t ON t.id = rt.tag_id WHERE t.name = ?3))
+              AND (?5 IS NULL OR r.id NOT IN (SELECT rt.run_id FROM run_tags rt JOIN tags t ON t.id = rt.tag_id WHERE t.name = ?5))
+              AND (?4 OR g.is_integrated IS NOT 1)
+              AND r.tenant_id = ?6
+            GROUP BY r.id
+            ORDER BY (CASE WHEN ?1 = 'score' THEN rs.score ELSE pr.avg_its END) DESC
+            LIMIT ?2
+            "#,
+            metric,
+            limit,
+            tag,
+            include_integrated,
+            exclude_tag,
+            tenant_id
+        )
+        .fetch_all(&self.pool)
+        .timed("run_score.find_leaderboard", 6)
+        .await?;
+
+        Ok(results)
+    }
+
+    /// Number of runs `find_leaderboard` would rank without its limit
+    pub async fn count_leaderboard(
+        &self,
+        tenant_id: &str,
+        metric: &str,
+        tag: Option<&str>,
+        exclude_tag: Option<&str>,
+        include_integrated: bool,
+    ) -> Result<i64, Error> {
+        let count = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(DISTINCT r.id) AS "count!: i64"
+            FROM runs r
+            LEFT JOIN RunScore rs ON rs.run_id = r.id
+            LEFT JOIN performanceResult pr ON pr.run_id = r.id
+            LEFT JOIN GPU g ON g.run_id = r.id AND g.gpu_index = 0
+            WHERE (CASE WHEN ?1 = 'score' THEN rs.score ELSE pr.avg_its END) IS NOT NULL
+              AND (?2 IS NULL OR r.id IN (SELECT rt.run_id FROM run_tags rt JOIN tags t ON t.id = rt.tag_id WHERE t.name = ?2))
+              AND (?4 IS NULL OR r.id NOT IN (SELECT rt.run_id FROM run_tags rt JOIN tags t ON t.id = rt.tag_id WHERE t.name = ?4))
+              AND (?3 OR g.is_integrated IS NOT 1)
+              AND r.tenant_id = ?5
+            "#,
+            metric,
+            tag,
+            include_integrated,
+            exclude_tag,
+            tenant_id
+        )
+        .fetch_one(&self.pool)
+        .timed("run_score.count_leaderboard", 5)
+        .await?;
+
+        Ok(count)
+    }
+
+    /// Fastest GPU + torch + xformers combinations by average ITS in a tenant's dataset
+    ///
+    /// The filter's `base_model` keeps runs on that base model only; `max_vram_gb` keeps runs
+    /// on GPUs with at most that much VRAM (per the run's score, else the GPU spec),
+    /// dropping runs whose VRAM is unknown; `tag` keeps runs carrying that tag
+    /// and `exclude_tag` drops them. Multi-GPU runs are left out as their ITS
+    /// cannot be put on a single GPU, and runs on integrated GPUs unless
+    /// `include_integrated` is set.
+    pub async fn find_top_configurations(
+        &self,
+        tenant_id: &str,
+        filter: &TopConfigurationFilter<'_>,
+        limit: i64,
+    ) -> Result<Vec<TopConfiguration>, Error> {
+        let results = sqlx::query_as!(
+            TopConfiguration,
+            r#"
+            SELECT
+                g.device AS "device!",
+                l.torch AS "torch?",
+                l.xformers AS "xformers?",
+                MAX(COALESCE(rs.vram_gb, gs.vram_gb)) AS "vram_gb?: f64",
+                AVG(pr.avg_its) AS "avg_its!: f64",
+                MAX(pr.avg_its) AS "max_its!: f64",
+                COUNT(DISTINCT r.id) AS "sample_count!: i64"
+            FROM runs r
+            JOIN performanceResult pr ON pr.run_id = r.id
+            JOIN GPU g ON g.run_id = r.id
+            LEFT JOIN Libraries l ON l.run_id = r.id
+            LEFT JOIN RunScore rs ON rs.run_id = r.id
+            LEFT JOIN RunMoreDetails rmd ON rmd.run_id = r.id
+            LEFT JOIN ModelMap mm ON mm.id = rmd.ModelMapId
+            LEFT JOIN GPUMap gm ON gm.gpu_name = g.device
+            LEFT JOIN GPUSpec gs ON gs.base_gpu_id = gm.base_gpu_id
+            WHERE pr.avg_its IS NOT NULL
+              AND g.device IS NOT NULL
+              AND NOT EXISTS (SELECT 1 FROM GPU mg WHERE mg.run_id = g.run_id AND mg.gpu_index > 0)
+              AND (?1 IS NULL OR mm.base_model = ?1 COLLATE NOCASE)
+              AND (?2 IS NULL OR COALESCE(rs.vram_gb, gs.vram_gb) <= ?2)
+              AND (?4 IS NULL OR r.id IN (SELECT rt.run_id FROM run_tags rt JOIN tags t ON t.id = rt.tag_id WHERE t.name = ?4))
+              AND (?6 IS NULL OR r.id NOT IN (SELECT rt.run_id FROM run_tags rt JOIN tags t ON t.id = rt.tag_id WHERE t.name = ?6))
+              AND (?5 OR g.is_integrated IS NOT 1)
+              AND r.tenant_id = ?7
+            GROUP BY g.device, l.torch, l.xformers
+            ORDER BY AVG(pr.avg_its) DESC
+            LIMIT ?3
+            "#,
+            filter.base_model,
+            filter.max_vram_gb,
+            limit,
+            filter.tag,
+            filter.include_integrated,
+            filter.exclude_tag,
+            tenant_id
+        )
+        .fetch_all(&self.pool)
+        .timed("run_score.find_top_configurations", 7)
+        .await?;
+
+        Ok(results)
+    }
+
+    /// Clear all run scores within a transaction
+    pub async fn clear_all_tx(&self, tx: &mut Transaction<'_, Sqlite>) -> Result<(), Error> {
+        sqlx::query!("DELETE FROM RunScore")
+            .execute(&mut **tx)
+            .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Repository<RunScore, i64> for RunScoreRepository {
+    async fn create(&self, entity: RunScore) -> Result<RunScore, Error> {
+        let now = audit_timestamp();
+
+        let id = retry_on_busy(|| sqlx::query!(
+            r#"
+            INSERT INTO RunScore (run_id, score, normalized_its, model_factor, vram_gb, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            "#,
+            entity.run_id,
+            entity.score,
+            entity.normalized_its,
+            entity.model_factor,
+            entity.vram_gb,
+            now,
+            now
+        )
+        .execute(&self.pool)
+        .timed("run_score.create", 7))
+        .await?
+        .last_insert_rowid();
+
+        Ok(RunScore {
+            id: Some(id),
+            created_at: Some(now.clone()),
+            updated_at: Some(now),
+            ..entity
+        })
+    }
+
+    async fn find_by_id(&self, id: i64) -> Result<Option<RunScore>, Error> {
+        let result = sqlx::query_as!(
+            RunScore,
+            r#"
+            SELECT id, run_id, score, normalized_its, model_factor, vram_gb, created_at, updated_at
+            FROM RunScore
+            WHERE id = ?
+            "#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .timed("run_score.find_by_id", 1)
+        .await?;
+
+        Ok(result)
+    }
+
+    async fn find_all(&self) -> Result<Vec<RunScore>, Error> {
+        let results = sqlx::query_as!(
+            RunScore,
+            r#"
+            SELECT id, run_id, score, normalized_its, model_factor, vram_gb, created_at, updated_at
+            FROM RunScore
+            ORDER BY id DESC
+            "#
+        )
+        .fetch_all(&self.pool)
+        .timed("run_score.find_all", 0)
+        .await?;
+
+        Ok(results)
+    }
+
+    async fn update(&self, entity: RunScore) -> Result<RunScore, Error> {
+        let id = entity.id.ok_or(Error::RowNotFound)?;
+        let now = audit_timestamp();
+
+        retry_on_busy(|| sqlx::query!(
+            r#"
+            UPDATE RunScore
+            SET run_id = ?, score = ?, normalized_its = ?, model_factor = ?, vram_gb = ?, updated_at = ?
+            WHERE id = ?
+            "#,
+            entity.run_id,
+            entity.score,
+            entity.normalized_its,
+            entity.model_factor,
+            entity.vram_gb,
+            now,
+            id
+        )
+        .execute(&self.pool)
+        .timed("run_score.update", 7))
+        .await?;
+
+        Ok(RunScore {
+            updated_at: Some(now),
+            ..entity
+        })
+    }
+
+    async fn delete(&self, id: i64) -> Result<(), Error> {
+        retry_on_busy(|| sqlx::query!("DELETE FROM RunScore WHERE id = ?", id)
+            .execute(&self.pool)
+            .timed("run_score.delete", 1))
+            .await?;
+        Ok(())
+    }
+
+    async fn count(&self) -> Result<i64, Error> {
+        let count = sqlx::query!("SELECT COUNT(*) as count FROM RunScore")
+            .fetch_one(&self.pool)
+            .timed("run_score.count", 0)
+            .await?
+            .count;
+        Ok(count)
+    }
+
+    async fn exists(&self, id: i64) -> Result<bool, Error> {
+        let exists = sqlx::query_scalar!(r#"SELECT EXISTS(SELECT 1 FROM RunScore WHERE id = ?) AS "exists!: bool""#, id)
+            .fetch_one(&self.pool)
+            .timed("run_score.exists", 1)
+            .await?;
+        Ok(exists)
+    }
+
+    async fn count_where(&self, filter: &Filter) -> Result<i64, Error> {
+        let count: i64 = sqlx::query_scalar(&filter.count_sql("RunScore")?)
+            .bind(&filter.value)
+            .fetch_one(&self.pool)
+            .timed("run_score.count_where", 1)
+            .await?;
+        Ok(count)
+    }
+}
+
+#[async_trait]
+impl BulkRepository<RunScore, i64> for RunScoreRepository {
+    async fn bulk_create(&self, entities: Vec<RunScore>) -> Result<Vec<RunScore>, Error> {
+        if entities.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut tx = self.pool.begin().await?;
+
+        let result = self.bulk_create_tx(entities, &mut tx).await;
+
+        match result {
+            Ok(results) => {
+                tx.commit().await?;
+                Ok(results)
+            }
+            Err(e) => {
+                tx.rollback().await?;
+                Err(e)
+            }
+        }
+    }
+
+    async fn bulk_update(&self, entities: Vec<RunScore>) -> Result<Vec<RunScore>, Error> {
+        if entities.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut tx = self.pool.begin().await?;
+
+        let result = self.bulk_update_tx(entities, &mut tx).await;
+
+        match result {
+            Ok(results) => {
+                tx.commit().await?;
+                Ok(results)
+            }
+            Err(e) => {
+                tx.rollback().await?;
+                Err(e)
+            }
+        }
+    }
+
+    async fn delete_all(&self) -> Result<usize, Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let result = self.delete_all_tx(&mut tx).await;
+
+        match result {
+            Ok(count) => {
+                tx.commit().await?;
+                Ok(count)
+            }
+            Err(e) => {
+                tx.rollback().await?;
+                Err(e)
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<'a> TransactionRepository<'a, RunScore, i64> for RunScoreRepository {
+    async fn create_tx(&self, entity: RunScore, tx: &mut Transaction<'a, Sqlite>) -> Result<RunScore, Error> {
+        let now = audit_timestamp();
+
+        let id = sqlx::query!(
+            r#"
+            INSERT INTO RunScore (run_id, score, normalized_its, model_factor, vram_gb, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            "#,
+            entity.run_id,
+            entity.score,
+            entity.normalized_its,
+            entity.model_factor,
+            entity.vram_gb,
+            now,
+            now
+        )
+        .execute(&mut **tx)
+        .await?
+        .last_insert_rowid();
+
+        Ok(RunScore {
+            id: Some(id),
+            created_at: Some(now.clone()),
+            updated_at: Some(now),
+            ..entity
+        })
+    }
+
+    async fn update_tx(&self, entity: RunScore, tx: &mut Transaction<'a, Sqlite>) -> Result<RunScore, Error> {
+        let id = entity.id.ok_or(Error::RowNotFound)?;
+        let now = audit_timestamp();
+
+        sqlx::query!(
+            r#"
+            UPDATE RunScore
+            SET run_id = ?, score = ?, normalized_its = ?, model_factor = ?, vram_gb = ?, updated_at = ?
+            WHERE id = ?
+            "#,
+            entity.run_id,
+            entity.score,
+            entity.normalized_its,
+            entity.model_factor,
+            entity.vram_gb,
+            now,
+            id
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(RunScore {
+            updated_at: Some(now),
+            ..entity
+        })
+    }
+
+    async fn delete_tx(&self, id: i64, tx: &mut Transaction<'a, Sqlite>) -> Result<(), Error> {
+        sqlx::query!("DELETE FROM RunScore WHERE id = ?", id)
+            .execute(&mut **tx)
+            .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<'a> BulkTransactionRepository<'a, RunScore, i64> for RunScoreRepository {
+    async fn bulk_create_tx(&self, entities: Vec<RunScore>, tx: &mut Transaction<'a, Sqlite>) -> Result<Vec<RunScore>, Error> {
+        if entities.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut created_results = Vec::with_capacity(entities.len());
+
+        for entity in entities {
+            let created_result = self.create_tx(entity, tx).await?;
+            created_results.push(created_result);
+        }
+
+        Ok(created_results)
+    }
+
+    async fn bulk_update_tx(&self, entities: Vec<RunScore>, tx: &mut Transaction<'a, Sqlite>) -> Result<Vec<RunScore>, Error> {
+        if entities.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut updated_results = Vec::with_capacity(entities.len());
+
+        for entity in entities {
+            let updated_result = self.update_tx(entity, tx).await?;
+            updated_results.push(updated_result);
+        }
+
+        Ok(updated_results)
+    }
+
+    async fn delete_all_tx(&self, tx: &mut Transaction<'a, Sqlite>) -> Result<usize, Error> {
+        let result = sqlx::query!("DELETE FROM RunScore")
+            .execute(&mut **tx)
+            .await?;
+
+        Ok(result.rows_affected() as usize)
+    }
+}
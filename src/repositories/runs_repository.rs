@@ -1,8 +1,36 @@
+use std::collections::BTreeMap;
+
 use async_trait::async_trait;
 use sqlx::{Error, SqlitePool, Transaction, Sqlite};
 
-use crate::models::runs::Run;
+use crate::models::moderation::MODERATION_STATUS_PENDING;
+use crate::models::reparse::RawStringFilter;
+use crate::models::runs::{
+    Run, RunAppInfo, RunDetail, RunDetailsInput, RunDeviceInfo, RunFeedEntry, RunItsInput, RunLibrariesInput,
+    RunSummary, RunSystemInfo,
+};
 use crate::repositories::traits::{Repository, TransactionRepository, BulkRepository, BulkTransactionRepository};
+use crate::repositories::audit_timestamp;
+use crate::repositories::batch::{json_ids, values_placeholders, MAX_BIND_PARAMETERS};
+use crate::repositories::connection::{retry_on_busy, TimedQuery};
+use crate::repositories::query_builder::Filter;
+
+/// Values `bulk_create_tx` binds for each run
+const INSERT_COLUMNS: usize = 12;
+
+/// Tables holding rows derived from a run, deleted before the run itself
+pub const RUN_CHILD_TABLES: &[&str] = &[
+    "performanceResult",
+    "AppDetails",
+    "AppRelease",
+    "SystemInfo",
+    "Libraries",
+    "GPU",
+    "RunMoreDetails",
+    "RunScore",
+    "run_tags",
+    "moderation_queue",
+];
 
 pub struct RunsRepository {
     pool: SqlitePool,
@@ -12,15 +40,452 @@ impl RunsRepository {
     pub fn new(pool: SqlitePool) -> Self {
         Self { pool }
     }
+
+    /// Whether run `id` exists in the tenant's dataset
+    pub async fn exists_for_tenant(&self, id: i64, tenant_id: &str) -> Result<bool, Error> {
+        let exists = sqlx::query_scalar!(
+            r#"SELECT EXISTS(SELECT 1 FROM runs WHERE id = ? AND tenant_id = ?) AS "exists!: bool""#,
+            id,
+            tenant_id
+        )
+        .fetch_one(&self.pool)
+        .timed("runs.exists_for_tenant", 2)
+        .await?;
+
+        Ok(exists)
+    }
+
+    /// Every tenant with at least one run
+    pub async fn find_tenant_ids(&self) -> Result<Vec<String>, Error> {
+        let tenant_ids = sqlx::query_scalar!("SELECT DISTINCT tenant_id FROM runs ORDER BY tenant_id")
+            .fetch_all(&self.pool)
+            .timed("runs.find_tenant_ids", 0)
+            .await?;
+
+        Ok(tenant_ids)
+    }
+
+    /// Find a run together with its processed performance, app, system, library, GPU and model data
+    pub async fn find_detail(&self, id: i64) -> Result<Option<RunDetail>, Error> {
+        let result = sqlx::query_as!(
+            RunDetail,
+            r#"
+            SELECT
+                r.id AS "run_id!",
+                r.timestamp,
+                r.model_name,
+                r.user,
+                r.notes,
+                pr.its AS "its?",
+                pr.avg_its AS "avg_its?",
+                rs.score AS "score?",
+                ad.app_name AS "app_name?",
+                ad.updated AS "app_updated?",
+                ad.hash AS "app_hash?",
+                si.arch AS "arch?",
+                si.cpu AS "cpu?",
+                si.system AS "system?",
+                si.release AS "release?",
+                si.python AS "python?",
+                l.torch AS "torch?",
+                l.xformers AS "xformers?",
+                l.diffusers AS "diffusers?",
+                l.transformers AS "transformers?",
+                g.device AS "device?",
+                g.driver AS "driver?",
+                g.gpu_chip AS "gpu_chip?",
+                g.brand AS "brand?",
+                g.isLaptop AS "is_laptop?: bool",
+                (SELECT COUNT(*) FROM GPU mg WHERE mg.run_id = r.id) AS "gpu_count!: i64",
+                mm.base_model AS "base_model?"
+            FROM runs r
+            LEFT JOIN performanceResult pr ON pr.run_id = r.id
+            LEFT JOIN RunScore rs ON rs.run_id = r.id
+            LEFT JOIN AppDetails ad ON ad.run_id = r.id
+            LEFT JOIN SystemInfo si ON si.run_id = r.id
+            LEFT JOIN Libraries l ON l.run_id = r.id
+            LEFT JOIN GPU g ON g.run_id = r.id AND g.gpu_index = 0
+            LEFT JOIN RunMoreDetails rmd ON rmd.run_id = r.id
+            LEFT JOIN ModelMap mm ON mm.id = rmd.ModelMapId
+            WHERE r.id = ?
+            LIMIT 1
+            "#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .timed("runs.find_detail", 1)
+        .await?;
+
+        Ok(result)
+    }
+
+    /// A tenant's most recently ingested runs with their GPU and average ITS, newest first, skipping runs held for moderation
+    pub async fn find_latest_feed_entries(&self, tenant_id: &str, limit: i64) -> Result<Vec<RunFeedEntry>, Error> {
+        let results = sqlx::query_as!(
+            RunFeedEntry,
+            r#"
+            SELECT
+                r.id AS "run_id!",
+                r.timestamp,
+                r.created_at,
+                r.model_name,
+                r.user,
+                (SELECT g.device FROM GPU g WHERE g.run_id = r.id ORDER BY g.gpu_index, g.id LIMIT 1) AS "device?: String",
+                (SELECT pr.avg_its FROM performanceResult pr WHERE pr.run_id = r.id ORDER BY pr.id LIMIT 1) AS "avg_its?: f64"
+            FROM runs r
+            WHERE r.tenant_id = ?
+              AND r.id NOT IN (SELECT run_id FROM moderation_queue WHERE status = ?)
+            ORDER BY r.id DESC
+            LIMIT ?
+            "#,
+            tenant_id,
+            MODERATION_STATUS_PENDING,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .timed("runs.find_latest_feed_entries", 2)
+        .await?;
+
+        Ok(results)
+    }
+
+    /// A page of a tenant's runs without their raw benchmark strings, newest first, skipping runs held for moderation
+    pub async fn find_summaries(&self, tenant_id: &str, limit: i64, offset: i64) -> Result<Vec<RunSummary>, Error> {
+        let results = sqlx::query_as!(
+            RunSummary,
+            r#"
+            SELECT
+                r.id AS "run_id!",
+                r.timestamp,
+                r.created_at,
+                r.model_name,
+                r.user,
+                r.notes
+            FROM runs r
+            WHERE r.tenant_id = ?
+              AND r.id NOT IN (SELECT run_id FROM moderation_queue WHERE status = 'pending')
+            ORDER BY r.id DESC
+            LIMIT ? OFFSET ?
+            "#,
+            tenant_id,
+            limit,
+            offset
+        )
+        .fetch_all(&self.pool)
+        .timed("runs.find_summaries", 3)
+        .await?;
+
+        Ok(results)
+    }
+
+    /// Every run with only the columns the process-its stage reads, newest first
+    pub async fn find_all_its_inputs(&self) -> Result<Vec<RunItsInput>, Error> {
+        let results = sqlx::query_as!(
+            RunItsInput,
+            r#"SELECT id AS "id!", vram_usage FROM runs ORDER BY id DESC"#
+        )
+        .fetch_all(&self.pool)
+        .timed("runs.find_all_its_inputs", 0)
+        .await?;
+
+        Ok(results)
+    }
+
+    /// Every run with only the columns the process-app-details stage reads, newest first
+    pub async fn find_all_app_infos(&self) -> Result<Vec<RunAppInfo>, Error> {
+        let results = sqlx::query_as!(
+            RunAppInfo,
+            r#"SELECT id AS "id!", info FROM runs ORDER BY id DESC"#
+        )
+        .fetch_all(&self.pool)
+        .timed("runs.find_all_app_infos", 0)
+        .await?;
+
+        Ok(results)
+    }
+
+    /// Every run with only the columns the process-system-info stage reads, newest first
+    pub async fn find_all_system_infos(&self) -> Result<Vec<RunSystemInfo>, Error> {
+        let results = sqlx::query_as!(
+            RunSystemInfo,
+            r#"SELECT id AS "id!", system_info FROM runs ORDER BY id DESC"#
+        )
+        .fetch_all(&self.pool)
+        .timed("runs.find_all_system_infos", 0)
+        .await?;
+
+        Ok(results)
+    }
+
+    /// Every run with only the columns the process-libraries stage reads, newest first
+    pub async fn find_all_libraries_inputs(&self) -> Result<Vec<RunLibrariesInput>, Error> {
+        let results = sqlx::query_as!(
+            RunLibrariesInput,
+            r#"SELECT id AS "id!", model_info, xformers FROM runs ORDER BY id DESC"#
+        )
+        .fetch_all(&self.pool)
+        .timed("runs.find_all_libraries_inputs", 0)
+        .await?;
+
+        Ok(results)
+    }
+
+    /// Every run with only the columns the process-gpu stage reads, newest first
+    pub async fn find_all_device_infos(&self) -> Result<Vec<RunDeviceInfo>, Error> {
+        let results = sqlx::query_as!(
+            RunDeviceInfo,
+            r#"SELECT id AS "id!", device_info FROM runs ORDER BY id DESC"#
+        )
+        .fetch_all(&self.pool)
+        .timed("runs.find_all_device_infos", 0)
+        .await?;
+
+        Ok(results)
+    }
+
+    /// Every run with only the columns the process-run-details stage reads, newest first
+    pub async fn find_all_details_inputs(&self) -> Result<Vec<RunDetailsInput>, Error> {
+        let results = sqlx::query_as!(
+            RunDetailsInput,
+            r#"SELECT id AS "id!", timestamp, model_name, user, notes FROM runs ORDER BY id DESC"#
+        )
+        .fetch_all(&self.pool)
+        .timed("runs.find_all_details_inputs", 0)
+        .await?;
+
+        Ok(results)
+    }
+
+    /// `find_all_its_inputs` within a transaction, for a rebuild that also writes in it
+    pub async fn find_all_its_inputs_tx(&self, tx: &mut Transaction<'_, Sqlite>) -> Result<Vec<RunItsInput>, Error> {
+        let results = sqlx::query_as!(
+            RunItsInput,
+            r#"SELECT id AS "id!", vram_usage FROM runs ORDER BY id DESC"#
+        )
+        .fetch_all(&mut **tx)
+        .timed("runs.find_all_its_inputs", 0)
+        .await?;
+
+        Ok(results)
+    }
+
+    /// `find_all_app_infos` within a transaction
+    pub async fn find_all_app_infos_tx(&self, tx: &mut Transaction<'_, Sqlite>) -> Result<Vec<RunAppInfo>, Error> {
+        let results = sqlx::query_as!(
+            RunAppInfo,
+            r#"SELECT id AS "id!", info FROM runs ORDER BY id DESC"#
+        )
+        .fetch_all(&mut **tx)
+        .timed("runs.find_all_app_infos", 0)
+        .await?;
+
+        Ok(results)
+    }
+
+    /// `find_all_system_infos` within a transaction
+    pub async fn find_all_system_infos_tx(&self, tx: &mut Transaction<'_, Sqlite>) -> Result<Vec<RunSystemInfo>, Error> {
+        let results = sqlx::query_as!(
+            RunSystemInfo,
+            r#"SELECT id AS "id!", system_info FROM runs ORDER BY id DESC"#
+        )
+        .fetch_all(&mut **tx)
+        .timed("runs.find_all_system_infos", 0)
+        .await?;
+
+        Ok(results)
+    }
+
+    /// `find_all_libraries_inputs` within a transaction
+    pub async fn find_all_libraries_inputs_tx(&self, tx: &mut Transaction<'_, Sqlite>) -> Result<Vec<RunLibrariesInput>, Error> {
+        let results = sqlx::query_as!(
+            RunLibrariesInput,
+            r#"SELECT id AS "id!", model_info, xformers FROM runs ORDER BY id DESC"#
+        )
+        .fetch_all(&mut **tx)
+        .timed("runs.find_all_libraries_inputs", 0)
+        .await?;
+
+        Ok(results)
+    }
+
+    /// `find_all_device_infos` within a transaction
+    pub async fn find_all_device_infos_tx(&self, tx: &mut Transaction<'_, Sqlite>) -> Result<Vec<RunDeviceInfo>, Error> {
+        let results = sqlx::query_as!(
+            RunDeviceInfo,
+            r#"SELECT id AS "id!", device_info FROM runs ORDER BY id DESC"#
+        )
+        .fetch_all(&mut **tx)
+        .timed("runs.find_all_device_infos", 0)
+        .await?;
+
+        Ok(results)
+    }
+
+    /// `find_all_details_inputs` within a transaction
+    pub async fn find_all_details_inputs_tx(&self, tx: &mut Transaction<'_, Sqlite>) -> Result<Vec<RunDetailsInput>, Error> {
+        let results = sqlx::query_as!(
+            RunDetailsInput,
+            r#"SELECT id AS "id!", timestamp, model_name, user, notes FROM runs ORDER BY id DESC"#
+        )
+        .fetch_all(&mut **tx)
+        .timed("runs.find_all_details_inputs", 0)
+        .await?;
+
+        Ok(results)
+    }
+
+    /// Ids of the tenant's runs submitted by `user`, matched on the raw run or its processed details
+    pub async fn find_ids_by_user_tx(&self, user: &str, tenant_id: &str, tx: &mut Transaction<'_, Sqlite>) -> Result<Vec<i64>, Error> {
+        let ids = sqlx::query_scalar!(
+            r#"
+            SELECT id AS "id!: i64" FROM runs WHERE user = ?1 AND tenant_id = ?2
+            UNION
+            SELECT rmd.run_id AS "id!: i64"
+            FROM RunMoreDetails rmd
+            JOIN runs r ON r.id = rmd.run_id
+            WHERE rmd.user = ?1 AND r.tenant_id = ?2
+            ORDER BY 1
+            "#,
+            user,
+            tenant_id
+        )
+        .fetch_all(&mut **tx)
+        .await?;
+
+        Ok(ids)
+    }
+
+    /// Runs whose raw `column` matches `filter`, in id order
+    ///
+    /// `column` must be one of the raw columns named by `ReparseStage::source_column`.
+    /// Regex filters rely on the `REGEXP` function `create_pool` registers.
+    pub async fn find_by_source_match_tx(
+        &self,
+        column: &str,
+        filter: &RawStringFilter,
+        tx: &mut Transaction<'_, Sqlite>,
+    ) -> Result<Vec<Run>, Error> {
+        let query = format!(
+            "SELECT id, timestamp, vram_usage, info, system_info, model_info, device_info, xformers, model_name, user, notes, created_at, updated_at FROM runs WHERE {} {} ? ORDER BY id",
+            column,
+            filter.operator()
+        );
+
+        sqlx::query_as::<_, Run>(&query)
+            .bind(filter.pattern())
+            .fetch_all(&mut **tx)
+            .await
+    }
+
+    /// Move every run of the tenant submitted as `from` to `to`, returning the number of runs changed
+    pub async fn rename_user_tx(&self, from: &str, to: &str, tenant_id: &str, tx: &mut Transaction<'_, Sqlite>) -> Result<u64, Error> {
+        let now = audit_timestamp();
+
+        let result = sqlx::query!(
+            "UPDATE runs SET user = ?, updated_at = ? WHERE user = ? AND tenant_id = ?",
+            to,
+            now,
+            from,
+            tenant_id
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Clear the notes of a run, returning the rows updated
+    pub async fn clear_notes_tx(&self, run_id: i64, tx: &mut Transaction<'_, Sqlite>) -> Result<u64, Error> {
+        let now = audit_timestamp();
+
+        let result = sqlx::query!(
+            "UPDATE runs SET notes = NULL, updated_at = ? WHERE id = ?",
+            now,
+            run_id
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// File the given runs under `tenant_id`, in the caller's transaction
+    pub async fn assign_tenant_tx(
+        &self,
+        run_ids: &[i64],
+        tenant_id: &str,
+        tx: &mut Transaction<'_, Sqlite>,
+    ) -> Result<u64, Error> {
+        let ids = json_ids(run_ids)?;
+        let result = sqlx::query("UPDATE runs SET tenant_id = ? WHERE id IN (SELECT value FROM json_each(?))")
+            .bind(tenant_id)
+            .bind(&ids)
+            .execute(&mut **tx)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Delete runs with every row derived from them, returning the rows deleted per table
+    pub async fn delete_with_children_tx(
+        &self,
+        run_ids: &[i64],
+        tx: &mut Transaction<'_, Sqlite>,
+    ) -> Result<BTreeMap<String, u64>, Error> {
+        let ids = json_ids(run_ids)?;
+        let mut deleted = BTreeMap::new();
+
+        // Table names come from the constant list above, never from user input
+        for table in RUN_CHILD_TABLES {
+            let result = sqlx::query(&format!("DELETE FROM {} WHERE run_id IN (SELECT value FROM json_each(?))", table))
+                .bind(&ids)
+                .execute(&mut **tx)
+                .await?;
+            deleted.insert(table.to_string(), result.rows_affected());
+        }
+
+        let result = sqlx::query("DELETE FROM runs WHERE id IN (SELECT value FROM json_each(?))")
+            .bind(&ids)
+            .execute(&mut **tx)
+            .await?;
+        deleted.insert("runs".to_string(), result.rows_affected());
+
+        Ok(deleted)
+    }
+
+    /// Delete the rows `tables` derived from `run_ids`, keeping the runs, returning the rows deleted
+    ///
+    /// `tables` must come from `RUN_CHILD_TABLES`.
+    pub async fn delete_derived_tx(
+        &self,
+        tables: &[&str],
+        run_ids: &[i64],
+        tx: &mut Transaction<'_, Sqlite>,
+    ) -> Result<u64, Error> {
+        let ids = json_ids(run_ids)?;
+        let mut deleted = 0;
+
+        for table in tables {
+            let result = sqlx::query(&format!("DELETE FROM {} WHERE run_id IN (SELECT value FROM json_each(?))", table))
+                .bind(&ids)
+                .execute(&mut **tx)
+                .await?;
+            deleted += result.rows_affected();
+        }
+
+        Ok(deleted)
+    }
 }
 
 #[async_trait]
 impl Repository<Run, i64> for RunsRepository {
     async fn create(&self, entity: Run) -> Result<Run, Error> {
-        let id = sqlx::query!(
+        let now = audit_timestamp();
+
+        let id = retry_on_busy(|| sqlx::query!(
             r#"
-            INSERT INTO runs (timestamp, vram_usage, info, system_info, model_info, device_info, xformers, model_name, user, notes)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            INSERT INTO runs (timestamp, vram_usage, info, system_info, model_info, device_info, xformers, model_name, user, notes, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
             entity.timestamp,
             entity.vram_usage,
@@ -31,14 +496,19 @@ impl Repository<Run, i64> for RunsRepository {
             entity.xformers,
             entity.model_name,
             entity.user,
-            entity.notes
+            entity.notes,
+            now,
+            now
         )
         .execute(&self.pool)
+        .timed("runs.create", 12))
         .await?
         .last_insert_rowid() as i64;
 
         Ok(Run {
             id: Some(id),
+            created_at: Some(now.clone()),
+            updated_at: Some(now),
             ..entity
         })
     }
@@ -47,13 +517,14 @@ impl Repository<Run, i64> for RunsRepository {
         let run = sqlx::query_as!(
             Run,
             r#"
-            SELECT id, timestamp, vram_usage, info, system_info, model_info, device_info, xformers, model_name, user, notes
+            SELECT id, timestamp, vram_usage, info, system_info, model_info, device_info, xformers, model_name, user, notes, created_at, updated_at
             FROM runs
             WHERE id = ?
             "#,
             id
         )
         .fetch_optional(&self.pool)
+        .timed("runs.find_by_id", 1)
         .await?;
 
         Ok(run)
@@ -63,12 +534,13 @@ impl Repository<Run, i64> for RunsRepository {
         let runs = sqlx::query_as!(
             Run,
             r#"
-            SELECT id, timestamp, vram_usage, info, system_info, model_info, device_info, xformers, model_name, user, notes
+            SELECT id, timestamp, vram_usage, info, system_info, model_info, device_info, xformers, model_name, user, notes, created_at, updated_at
             FROM runs
             ORDER BY id DESC
             "#
         )
         .fetch_all(&self.pool)
+        .timed("runs.find_all", 0)
         .await?;
 
         Ok(runs)
@@ -76,11 +548,12 @@ impl Repository<Run, i64> for RunsRepository {
 
     async fn update(&self, entity: Run) -> Result<Run, Error> {
         let id = entity.id.ok_or(Error::RowNotFound)?;
+        let now = audit_timestamp();
         
-        sqlx::query!(
+        retry_on_busy(|| sqlx::query!(
             r#"
             UPDATE runs
-            SET timestamp = ?, vram_usage = ?, info = ?, system_info = ?, model_info = ?, device_info = ?, xformers = ?, model_name = ?, user = ?, notes = ?
+            SET timestamp = ?, vram_usage = ?, info = ?, system_info = ?, model_info = ?, device_info = ?, xformers = ?, model_name = ?, user = ?, notes = ?, updated_at = ?
             WHERE id = ?
             "#,
             entity.timestamp,
@@ -93,17 +566,23 @@ impl Repository<Run, i64> for RunsRepository {
             entity.model_name,
             entity.user,
             entity.notes,
+            now,
             id
         )
         .execute(&self.pool)
+        .timed("runs.update", 12))
         .await?;
 
-        Ok(entity)
+        Ok(Run {
+            updated_at: Some(now),
+            ..entity
+        })
     }
 
     async fn delete(&self, id: i64) -> Result<(), Error> {
-        sqlx::query!("DELETE FROM runs WHERE id = ?", id)
+        retry_on_busy(|| sqlx::query!("DELETE FROM runs WHERE id = ?", id)
             .execute(&self.pool)
+            .timed("runs.delete", 1))
             .await?;
         Ok(())
     }
@@ -111,10 +590,28 @@ impl Repository<Run, i64> for RunsRepository {
     async fn count(&self) -> Result<i64, Error> {
         let count = sqlx::query!("SELECT COUNT(*) as count FROM runs")
             .fetch_one(&self.pool)
+            .timed("runs.count", 0)
             .await?
             .count;
         Ok(count)
     }
+
+    async fn exists(&self, id: i64) -> Result<bool, Error> {
+        let exists = sqlx::query_scalar!(r#"SELECT EXISTS(SELECT 1 FROM runs WHERE id = ?) AS "exists!: bool""#, id)
+            .fetch_one(&self.pool)
+            .timed("runs.exists", 1)
+            .await?;
+        Ok(exists)
+    }
+
+    async fn count_where(&self, filter: &Filter) -> Result<i64, Error> {
+        let count: i64 = sqlx::query_scalar(&filter.count_sql("runs")?)
+            .bind(&filter.value)
+            .fetch_one(&self.pool)
+            .timed("runs.count_where", 1)
+            .await?;
+        Ok(count)
+    }
 }
 
 #[async_trait]
@@ -182,10 +679,12 @@ impl BulkRepository<Run, i64> for RunsRepository {
 #[async_trait]
 impl<'a> TransactionRepository<'a, Run, i64> for RunsRepository {
     async fn create_tx(&self, entity: Run, tx: &mut Transaction<'a, Sqlite>) -> Result<Run, Error> {
+        let now = audit_timestamp();
+
         let id = sqlx::query!(
             r#"
-            INSERT INTO runs (timestamp, vram_usage, info, system_info, model_info, device_info, xformers, model_name, user, notes)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            INSERT INTO runs (timestamp, vram_usage, info, system_info, model_info, device_info, xformers, model_name, user, notes, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
             entity.timestamp,
             entity.vram_usage,
@@ -196,7 +695,9 @@ impl<'a> TransactionRepository<'a, Run, i64> for RunsRepository {
             entity.xformers,
             entity.model_name,
             entity.user,
-            entity.notes
+            entity.notes,
+            now,
+            now
         )
         .execute(&mut **tx)
         .await?
@@ -204,17 +705,20 @@ impl<'a> TransactionRepository<'a, Run, i64> for RunsRepository {
 
         Ok(Run {
             id: Some(id),
+            created_at: Some(now.clone()),
+            updated_at: Some(now),
             ..entity
         })
     }
 
     async fn update_tx(&self, entity: Run, tx: &mut Transaction<'a, Sqlite>) -> Result<Run, Error> {
         let id = entity.id.ok_or(Error::RowNotFound)?;
+        let now = audit_timestamp();
         
         sqlx::query!(
             r#"
             UPDATE runs
-            SET timestamp = ?, vram_usage = ?, info = ?, system_info = ?, model_info = ?, device_info = ?, xformers = ?, model_name = ?, user = ?, notes = ?
+            SET timestamp = ?, vram_usage = ?, info = ?, system_info = ?, model_info = ?, device_info = ?, xformers = ?, model_name = ?, user = ?, notes = ?, updated_at = ?
             WHERE id = ?
             "#,
             entity.timestamp,
@@ -227,12 +731,16 @@ impl<'a> TransactionRepository<'a, Run, i64> for RunsRepository {
             entity.model_name,
             entity.user,
             entity.notes,
+            now,
             id
         )
         .execute(&mut **tx)
         .await?;
 
-        Ok(entity)
+        Ok(Run {
+            updated_at: Some(now),
+            ..entity
+        })
     }
 
     async fn delete_tx(&self, id: i64, tx: &mut Transaction<'a, Sqlite>) -> Result<(), Error> {
@@ -250,12 +758,45 @@ impl<'a> BulkTransactionRepository<'a, Run, i64> for RunsRepository {
             return Ok(vec![]);
         }
 
+        let now = audit_timestamp();
         let mut created_runs = Vec::with_capacity(entities.len());
 
-        // Use batch processing for better performance
-        for entity in entities {
-            let created_run = self.create_tx(entity, tx).await?;
-            created_runs.push(created_run);
+        // One multi-row INSERT per batch, each under SQLite's bind parameter limit
+        for batch in entities.chunks(MAX_BIND_PARAMETERS / INSERT_COLUMNS) {
+            let sql = format!(
+                "INSERT INTO runs (timestamp, vram_usage, info, system_info, model_info, device_info, xformers, model_name, user, notes, created_at, updated_at) VALUES {}",
+                values_placeholders(INSERT_COLUMNS, batch.len())
+            );
+            let mut query = sqlx::query(&sql);
+            for entity in batch {
+                query = query
+                    .bind(&entity.timestamp)
+                    .bind(&entity.vram_usage)
+                    .bind(&entity.info)
+                    .bind(&entity.system_info)
+                    .bind(&entity.model_info)
+                    .bind(&entity.device_info)
+                    .bind(&entity.xformers)
+                    .bind(&entity.model_name)
+                    .bind(&entity.user)
+                    .bind(&entity.notes)
+                    .bind(&now)
+                    .bind(&now);
+            }
+            let last_id = query
+                .execute(&mut **tx)
+                .timed("runs.bulk_create", batch.len() * INSERT_COLUMNS)
+                .await?
+                .last_insert_rowid();
+
+            // Rows of one statement get consecutive ids, the last one reported
+            let first_id = last_id - batch.len() as i64 + 1;
+            created_runs.extend(batch.iter().enumerate().map(|(offset, entity)| Run {
+                id: Some(first_id + offset as i64),
+                created_at: Some(now.clone()),
+                updated_at: Some(now.clone()),
+                ..entity.clone()
+            }));
         }
 
         Ok(created_runs)
@@ -316,7 +857,9 @@ mod tests {
                 xformers TEXT,
                 model_name TEXT,
                 user TEXT,
-                notes TEXT
+                notes TEXT,
+                created_at TEXT,
+                updated_at TEXT
             )
             "#
         )
@@ -340,6 +883,8 @@ mod tests {
             model_name: Some("test-model".to_string()),
             user: Some("test-user".to_string()),
             notes: Some("Test notes".to_string()),
+            created_at: None,
+            updated_at: None,
         }
     }
 
@@ -616,6 +1161,8 @@ mod tests {
                 model_name: Some(format!("test-model-{}", i)),
                 user: Some("test-user".to_string()),
                 notes: Some(format!("Test notes {}", i)),
+                created_at: None,
+                updated_at: None,
             })
             .collect();
         
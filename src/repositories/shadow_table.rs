@@ -0,0 +1,128 @@
+use std::marker::PhantomData;
+
+use sqlx::{query::Query, sqlite::SqliteArguments, Error, Sqlite, Transaction};
+
+use crate::repositories::audit_timestamp;
+
+/// Suffix of the table a rebuild writes into before it replaces the live one
+pub const SHADOW_SUFFIX: &str = "_new";
+
+/// A derived row that a processing step rebuilds from the runs table
+pub trait ShadowRow {
+    /// The live table
+    const TABLE: &'static str;
+    /// Columns written, in bind order, without the created_at/updated_at audit columns
+    const COLUMNS: &'static [&'static str];
+
+    fn bind_columns<'q>(self, query: Query<'q, Sqlite, SqliteArguments<'q>>) -> Query<'q, Sqlite, SqliteArguments<'q>>;
+}
+
+/// A `<table>_new` copy of a derived table that is filled and then swapped in
+///
+/// Processing steps used to clear the live table and re-insert every row. With a
+/// shadow, the live table is never touched until `swap_tx` drops it and renames
+/// the shadow into its place, so the whole rebuild lands as a single schema
+/// change at commit. Everything happens in the caller's transaction: a failed
+/// rebuild rolls back with the shadow and leaves the live table as it was.
+pub struct ShadowTable<T: ShadowRow> {
+    /// Name and definition of every explicit index on the live table
+    indexes: Vec<(String, String)>,
+    insert_sql: String,
+    row: PhantomData<T>,
+}
+
+impl<T: ShadowRow> ShadowTable<T> {
+    /// Create an empty shadow with the live table's columns, constraints and indexes
+    pub async fn create_tx(tx: &mut Transaction<'_, Sqlite>) -> Result<Self, Error> {
+        let shadow = shadow_name(T::TABLE);
+
+        // Left behind only if a swap was interrupted outside a transaction
+        sqlx::query(&format!(r#"DROP TABLE IF EXISTS "{}""#, shadow))
+            .execute(&mut **tx)
+            .await?;
+
+        let table_sql: String = sqlx::query_scalar("SELECT sql FROM sqlite_master WHERE type = 'table' AND name = ?")
+            .bind(T::TABLE)
+            .fetch_optional(&mut **tx)
+            .await?
+            // A broken schema, not a missing row: the step fails rather than answering 404
+            .ok_or_else(|| Error::Configuration(format!("table {} does not exist", T::TABLE).into()))?;
+        sqlx::query(&format!(r#"CREATE TABLE "{}" {}"#, shadow, definition_body(&table_sql)))
+            .execute(&mut **tx)
+            .await?;
+
+        // Constraint indexes come with the table definition; explicit ones are copied under shadow names
+        let indexes: Vec<(String, String)> = sqlx::query_as(
+            "SELECT name, sql FROM sqlite_master WHERE type = 'index' AND tbl_name = ? AND sql IS NOT NULL",
+        )
+        .bind(T::TABLE)
+        .fetch_all(&mut **tx)
+        .await?;
+        for (name, sql) in &indexes {
+            let unique = if sql.to_ascii_uppercase().starts_with("CREATE UNIQUE") { "UNIQUE " } else { "" };
+            sqlx::query(&format!(
+                r#"CREATE {}INDEX "{}" ON "{}" {}"#,
+                unique,
+                shadow_name(name),
+                shadow,
+                definition_body(sql)
+            ))
+            .execute(&mut **tx)
+            .await?;
+        }
+
+        let columns = T::COLUMNS.join(", ");
+        let placeholders = vec!["?"; T::COLUMNS.len() + 2].join(", ");
+        // The shadow starts empty, so a duplicate can only come from the rebuild itself; the last one wins
+        let insert_sql = format!(
+            r#"INSERT OR REPLACE INTO "{}" ({}, created_at, updated_at) VALUES ({})"#,
+            shadow, columns, placeholders
+        );
+
+        Ok(Self { indexes, insert_sql, row: PhantomData })
+    }
+
+    /// Insert one rebuilt row into the shadow, returning its id
+    pub async fn insert_tx(&self, row: T, tx: &mut Transaction<'_, Sqlite>) -> Result<i64, Error> {
+        let now = audit_timestamp();
+
+        let id = row
+            .bind_columns(sqlx::query(&self.insert_sql))
+            .bind(now.clone())
+            .bind(now)
+            .execute(&mut **tx)
+            .await?
+            .last_insert_rowid();
+
+        Ok(id)
+    }
+
+    /// Replace the live table with the shadow, restoring the original index names
+    pub async fn swap_tx(self, tx: &mut Transaction<'_, Sqlite>) -> Result<(), Error> {
+        sqlx::query(&format!(r#"DROP TABLE "{}""#, T::TABLE))
+            .execute(&mut **tx)
+            .await?;
+        sqlx::query(&format!(r#"ALTER TABLE "{}" RENAME TO "{}""#, shadow_name(T::TABLE), T::TABLE))
+            .execute(&mut **tx)
+            .await?;
+
+        // SQLite cannot rename an index, so the shadow's are rebuilt under the live names
+        for (name, sql) in &self.indexes {
+            sqlx::query(&format!(r#"DROP INDEX "{}""#, shadow_name(name)))
+                .execute(&mut **tx)
+                .await?;
+            sqlx::query(sql).execute(&mut **tx).await?;
+        }
+
+        Ok(())
+    }
+}
+
+fn shadow_name(name: &str) -> String {
+    format!("{}{}", name, SHADOW_SUFFIX)
+}
+
+/// A CREATE TABLE/INDEX statement from `sqlite_master` from its opening parenthesis on
+fn definition_body(sql: &str) -> &str {
+    sql.find('(').map_or(sql, |start| &sql[start..])
+}
@@ -1,8 +1,15 @@
+use std::collections::HashMap;
+
 use async_trait::async_trait;
-use sqlx::{Error, SqlitePool, Transaction, Sqlite};
+use sqlx::{query::Query, sqlite::SqliteArguments, Error, SqlitePool, Transaction, Sqlite};
 
-use crate::models::system_info::SystemInfo;
+use crate::models::system_info::{CpuItsSample, SystemInfo, SystemInfoCpu};
 use crate::repositories::traits::{Repository, TransactionRepository, BulkRepository, BulkTransactionRepository};
+use crate::repositories::audit_timestamp;
+use crate::repositories::batch::{first_by_run, json_ids};
+use crate::repositories::connection::{retry_on_busy, TimedQuery};
+use crate::repositories::shadow_table::ShadowRow;
+use crate::repositories::query_builder::Filter;
 
 pub struct SystemInfoRepository {
     pool: SqlitePool,
@@ -18,7 +25,7 @@ impl SystemInfoRepository {
         let results = sqlx::query_as!(
             SystemInfo,
             r#"
-            SELECT id, run_id, arch, cpu, system, release, python
+            SELECT id, run_id, arch, cpu, system, release, python, ram_gb, swap_gb, os_bits, parser_version, created_at, updated_at
             FROM SystemInfo
             WHERE run_id = ?
             ORDER BY id DESC
@@ -26,17 +33,38 @@ impl SystemInfoRepository {
             run_id
         )
         .fetch_all(&self.pool)
+        .timed("system_info.find_by_run_id", 1)
         .await?;
 
         Ok(results)
     }
 
+    /// The newest system info row of each of the given runs, in one query
+    pub async fn find_by_run_ids(&self, run_ids: &[i64]) -> Result<HashMap<i64, SystemInfo>, Error> {
+        let ids = json_ids(run_ids)?;
+        let results = sqlx::query_as!(
+            SystemInfo,
+            r#"
+            SELECT id, run_id, arch, cpu, system, release, python, ram_gb, swap_gb, os_bits, parser_version, created_at, updated_at
+            FROM SystemInfo
+            WHERE run_id IN (SELECT value FROM json_each(?))
+            ORDER BY run_id, id DESC
+            "#,
+            ids
+        )
+        .fetch_all(&self.pool)
+        .timed("system_info.find_by_run_ids", 1)
+        .await?;
+
+        Ok(first_by_run(results, |row| row.run_id))
+    }
+
     /// Find system info by architecture
     pub async fn find_by_arch(&self, arch: &str) -> Result<Vec<SystemInfo>, Error> {
         let results = sqlx::query_as!(
             SystemInfo,
             r#"
-            SELECT id, run_id, arch, cpu, system, release, python
+            SELECT id, run_id, arch, cpu, system, release, python, ram_gb, swap_gb, os_bits, parser_version, created_at, updated_at
             FROM SystemInfo
             WHERE arch = ?
             ORDER BY id DESC
@@ -44,6 +72,7 @@ impl SystemInfoRepository {
             arch
         )
         .fetch_all(&self.pool)
+        .timed("system_info.find_by_arch", 1)
         .await?;
 
         Ok(results)
@@ -54,7 +83,7 @@ impl SystemInfoRepository {
         let results = sqlx::query_as!(
             SystemInfo,
             r#"
-            SELECT id, run_id, arch, cpu, system, release, python
+            SELECT id, run_id, arch, cpu, system, release, python, ram_gb, swap_gb, os_bits, parser_version, created_at, updated_at
             FROM SystemInfo
             WHERE system = ?
             ORDER BY id DESC
@@ -62,6 +91,106 @@ impl SystemInfoRepository {
             system
         )
         .fetch_all(&self.pool)
+        .timed("system_info.find_by_system", 1)
+        .await?;
+
+        Ok(results)
+    }
+
+    /// The raw CPU string of every system info row within a transaction
+    pub async fn find_all_cpus_tx(&self, tx: &mut Transaction<'_, Sqlite>) -> Result<Vec<SystemInfoCpu>, Error> {
+        let results = sqlx::query_as!(
+            SystemInfoCpu,
+            r#"SELECT id AS "id!", cpu FROM SystemInfo ORDER BY id"#
+        )
+        .fetch_all(&mut **tx)
+        .timed("system_info.find_all_cpus", 0)
+        .await?;
+
+        Ok(results)
+    }
+
+    /// Every system info row within a transaction, newest first
+    pub async fn find_all_tx(&self, tx: &mut Transaction<'_, Sqlite>) -> Result<Vec<SystemInfo>, Error> {
+        let results = sqlx::query_as!(
+            SystemInfo,
+            r#"
+            SELECT id, run_id, arch, cpu, system, release, python, ram_gb, swap_gb, os_bits, parser_version, created_at, updated_at
+            FROM SystemInfo
+            ORDER BY id DESC
+            "#
+        )
+        .fetch_all(&mut **tx)
+        .timed("system_info.find_all", 0)
+        .await?;
+
+        Ok(results)
+    }
+
+    /// Store the normalized CPU fields of a system info row within a transaction
+    pub async fn update_cpu_fields_tx(
+        &self,
+        id: i64,
+        vendor: Option<&str>,
+        family: Option<&str>,
+        generation: Option<i64>,
+        model: Option<&str>,
+        tx: &mut Transaction<'_, Sqlite>,
+    ) -> Result<(), Error> {
+        let now = audit_timestamp();
+
+        sqlx::query!(
+            r#"
+            UPDATE SystemInfo
+            SET cpu_vendor = ?, cpu_family = ?, cpu_generation = ?, cpu_model = ?, updated_at = ?
+            WHERE id = ?
+            "#,
+            vendor,
+            family,
+            generation,
+            model,
+            now,
+            id
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+
+    /// avg_its and normalized CPU of every single-GPU run of a tenant on a GPU
+    ///
+    /// The GPU is matched by reported device name or by mapped base GPU name.
+    pub async fn find_cpu_its_samples_by_gpu_name(&self, tenant_id: &str, gpu_name: &str) -> Result<Vec<CpuItsSample>, Error> {
+        let results = sqlx::query_as!(
+            CpuItsSample,
+            r#"
+            SELECT
+                si.cpu_vendor AS "cpu_vendor?",
+                si.cpu_family AS "cpu_family?",
+                si.cpu_generation AS "cpu_generation?: i64",
+                pr.avg_its AS "avg_its!: f64"
+            FROM GPU g
+            JOIN performanceResult pr ON pr.run_id = g.run_id
+            JOIN SystemInfo si ON si.run_id = g.run_id
+            WHERE pr.avg_its IS NOT NULL
+              AND NOT EXISTS (SELECT 1 FROM GPU mg WHERE mg.run_id = g.run_id AND mg.gpu_index > 0)
+              AND (
+                g.device = ?1 COLLATE NOCASE
+                OR g.device IN (
+                    SELECT gm.gpu_name
+                    FROM GPUMap gm
+                    JOIN GPUBase gb ON gb.id = gm.base_gpu_id
+                    WHERE gb.name = ?1 COLLATE NOCASE
+                )
+              )
+              AND g.run_id IN (SELECT r.id FROM runs r WHERE r.tenant_id = ?2)
+            "#,
+            gpu_name,
+            tenant_id
+        )
+        .fetch_all(&self.pool)
+        .timed("system_info.find_cpu_its_samples_by_gpu_name", 2)
         .await?;
 
         Ok(results)
@@ -69,8 +198,9 @@ impl SystemInfoRepository {
 
     /// Clear all system info
     pub async fn clear_all(&self) -> Result<(), Error> {
-        sqlx::query!("DELETE FROM SystemInfo")
+        retry_on_busy(|| sqlx::query!("DELETE FROM SystemInfo")
             .execute(&self.pool)
+            .timed("system_info.clear_all", 0))
             .await?;
         Ok(())
     }
@@ -84,27 +214,73 @@ impl SystemInfoRepository {
     }
 }
 
+impl ShadowRow for SystemInfo {
+    const TABLE: &'static str = "SystemInfo";
+    const COLUMNS: &'static [&'static str] = &["run_id", "arch", "cpu", "system", "release", "python", "ram_gb", "swap_gb", "os_bits", "parser_version"];
+
+    fn bind_columns<'q>(self, query: Query<'q, Sqlite, SqliteArguments<'q>>) -> Query<'q, Sqlite, SqliteArguments<'q>> {
+        query
+            .bind(self.run_id)
+            .bind(self.arch)
+            .bind(self.cpu)
+            .bind(self.system)
+            .bind(self.release)
+            .bind(self.python)
+            .bind(self.ram_gb)
+            .bind(self.swap_gb)
+            .bind(self.os_bits)
+            .bind(self.parser_version)
+    }
+}
+
 #[async_trait]
 impl Repository<SystemInfo, i64> for SystemInfoRepository {
     async fn create(&self, entity: SystemInfo) -> Result<SystemInfo, Error> {
-        let id = sqlx::query!(
+        let now = audit_timestamp();
+
+        let row = retry_on_busy(|| sqlx::query!(
             r#"
-            INSERT INTO SystemInfo (run_id, arch, cpu, system, release, python)
-            VALUES (?, ?, ?, ?, ?, ?)
+            INSERT INTO SystemInfo (run_id, arch, cpu, system, release, python, ram_gb, swap_gb, os_bits, parser_version, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT (run_id) DO UPDATE SET
+                arch = excluded.arch,
+                cpu = excluded.cpu,
+                system = excluded.system,
+                release = excluded.release,
+                python = excluded.python,
+                ram_gb = excluded.ram_gb,
+                swap_gb = excluded.swap_gb,
+                os_bits = excluded.os_bits,
+                cpu_vendor = NULL,
+                cpu_family = NULL,
+                cpu_generation = NULL,
+                cpu_model = NULL,
+                parser_version = excluded.parser_version,
+                updated_at = excluded.updated_at
+            RETURNING id AS "id!: i64", created_at
             "#,
             entity.run_id,
             entity.arch,
             entity.cpu,
             entity.system,
             entity.release,
-            entity.python
+            entity.python,
+            entity.ram_gb,
+            entity.swap_gb,
+            entity.os_bits,
+            entity.parser_version,
+            now,
+            now
         )
-        .execute(&self.pool)
-        .await?
-        .last_insert_rowid() as i64;
+        .fetch_one(&self.pool)
+        .timed("system_info.create", 12))
+        .await?;
 
         Ok(SystemInfo {
-            id: Some(id),
+            id: Some(row.id),
+            // On conflict the row keeps its original creation time
+            created_at: row.created_at,
+            updated_at: Some(now),
             ..entity
         })
     }
@@ -113,13 +289,14 @@ impl Repository<SystemInfo, i64> for SystemInfoRepository {
         let result = sqlx::query_as!(
             SystemInfo,
             r#"
-            SELECT id, run_id, arch, cpu, system, release, python
+            SELECT id, run_id, arch, cpu, system, release, python, ram_gb, swap_gb, os_bits, parser_version, created_at, updated_at
             FROM SystemInfo
             WHERE id = ?
             "#,
             id
         )
         .fetch_optional(&self.pool)
+        .timed("system_info.find_by_id", 1)
         .await?;
 
         Ok(result)
@@ -129,12 +306,13 @@ impl Repository<SystemInfo, i64> for SystemInfoRepository {
         let results = sqlx::query_as!(
             SystemInfo,
             r#"
-            SELECT id, run_id, arch, cpu, system, release, python
+            SELECT id, run_id, arch, cpu, system, release, python, ram_gb, swap_gb, os_bits, parser_version, created_at, updated_at
             FROM SystemInfo
             ORDER BY id DESC
             "#
         )
         .fetch_all(&self.pool)
+        .timed("system_info.find_all", 0)
         .await?;
 
         Ok(results)
@@ -142,11 +320,13 @@ impl Repository<SystemInfo, i64> for SystemInfoRepository {
 
     async fn update(&self, entity: SystemInfo) -> Result<SystemInfo, Error> {
         let id = entity.id.ok_or(Error::RowNotFound)?;
+        let now = audit_timestamp();
         
-        sqlx::query!(
+        retry_on_busy(|| sqlx::query!(
             r#"
             UPDATE SystemInfo
-            SET run_id = ?, arch = ?, cpu = ?, system = ?, release = ?, python = ?
+            SET run_id = ?, arch = ?, cpu = ?, system = ?, release = ?, python = ?,
+                ram_gb = ?, swap_gb = ?, os_bits = ?, parser_version = ?, updated_at = ?
             WHERE id = ?
             "#,
             entity.run_id,
@@ -155,17 +335,27 @@ impl Repository<SystemInfo, i64> for SystemInfoRepository {
             entity.system,
             entity.release,
             entity.python,
+            entity.ram_gb,
+            entity.swap_gb,
+            entity.os_bits,
+            entity.parser_version,
+            now,
             id
         )
         .execute(&self.pool)
+        .timed("system_info.update", 12))
         .await?;
 
-        Ok(entity)
+        Ok(SystemInfo {
+            updated_at: Some(now),
+            ..entity
+        })
     }
 
     async fn delete(&self, id: i64) -> Result<(), Error> {
-        sqlx::query!("DELETE FROM SystemInfo WHERE id = ?", id)
+        retry_on_busy(|| sqlx::query!("DELETE FROM SystemInfo WHERE id = ?", id)
             .execute(&self.pool)
+            .timed("system_info.delete", 1))
             .await?;
         Ok(())
     }
@@ -173,44 +363,90 @@ impl Repository<SystemInfo, i64> for SystemInfoRepository {
     async fn count(&self) -> Result<i64, Error> {
         let count = sqlx::query!("SELECT COUNT(*) as count FROM SystemInfo")
             .fetch_one(&self.pool)
+            .timed("system_info.count", 0)
             .await?
             .count;
         Ok(count)
     }
+
+    async fn exists(&self, id: i64) -> Result<bool, Error> {
+        let exists = sqlx::query_scalar!(r#"SELECT EXISTS(SELECT 1 FROM SystemInfo WHERE id = ?) AS "exists!: bool""#, id)
+            .fetch_one(&self.pool)
+            .timed("system_info.exists", 1)
+            .await?;
+        Ok(exists)
+    }
+
+    async fn count_where(&self, filter: &Filter) -> Result<i64, Error> {
+        let count: i64 = sqlx::query_scalar(&filter.count_sql("SystemInfo")?)
+            .bind(&filter.value)
+            .fetch_one(&self.pool)
+            .timed("system_info.count_where", 1)
+            .await?;
+        Ok(count)
+    }
 }
 
 #[async_trait]
 impl<'a> TransactionRepository<'a, SystemInfo, i64> for SystemInfoRepository {
     async fn create_tx(&self, entity: SystemInfo, tx: &mut Transaction<'a, Sqlite>) -> Result<SystemInfo, Error> {
-        let id = sqlx::query!(
+        let now = audit_timestamp();
+
+        let row = sqlx::query!(
             r#"
-            INSERT INTO SystemInfo (run_id, arch, cpu, system, release, python)
-            VALUES (?, ?, ?, ?, ?, ?)
+            INSERT INTO SystemInfo (run_id, arch, cpu, system, release, python, ram_gb, swap_gb, os_bits, parser_version, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT (run_id) DO UPDATE SET
+                arch = excluded.arch,
+                cpu = excluded.cpu,
+                system = excluded.system,
+                release = excluded.release,
+                python = excluded.python,
+                ram_gb = excluded.ram_gb,
+                swap_gb = excluded.swap_gb,
+                os_bits = excluded.os_bits,
+                cpu_vendor = NULL,
+                cpu_family = NULL,
+                cpu_generation = NULL,
+                cpu_model = NULL,
+                parser_version = excluded.parser_version,
+                updated_at = excluded.updated_at
+            RETURNING id AS "id!: i64", created_at
             "#,
             entity.run_id,
             entity.arch,
             entity.cpu,
             entity.system,
             entity.release,
-            entity.python
+            entity.python,
+            entity.ram_gb,
+            entity.swap_gb,
+            entity.os_bits,
+            entity.parser_version,
+            now,
+            now
         )
-        .execute(&mut **tx)
-        .await?
-        .last_insert_rowid() as i64;
+        .fetch_one(&mut **tx)
+        .await?;
 
         Ok(SystemInfo {
-            id: Some(id),
+            id: Some(row.id),
+            // On conflict the row keeps its original creation time
+            created_at: row.created_at,
+            updated_at: Some(now),
             ..entity
         })
     }
 
     async fn update_tx(&self, entity: SystemInfo, tx: &mut Transaction<'a, Sqlite>) -> Result<SystemInfo, Error> {
         let id = entity.id.ok_or(Error::RowNotFound)?;
+        let now = audit_timestamp();
         
         sqlx::query!(
             r#"
             UPDATE SystemInfo
-            SET run_id = ?, arch = ?, cpu = ?, system = ?, release = ?, python = ?
+            SET run_id = ?, arch = ?, cpu = ?, system = ?, release = ?, python = ?,
+                ram_gb = ?, swap_gb = ?, os_bits = ?, parser_version = ?, updated_at = ?
             WHERE id = ?
             "#,
             entity.run_id,
@@ -219,12 +455,20 @@ impl<'a> TransactionRepository<'a, SystemInfo, i64> for SystemInfoRepository {
             entity.system,
             entity.release,
             entity.python,
+            entity.ram_gb,
+            entity.swap_gb,
+            entity.os_bits,
+            entity.parser_version,
+            now,
             id
         )
         .execute(&mut **tx)
         .await?;
 
-        Ok(entity)
+        Ok(SystemInfo {
+            updated_at: Some(now),
+            ..entity
+        })
     }
 
     async fn delete_tx(&self, id: i64, tx: &mut Transaction<'a, Sqlite>) -> Result<(), Error> {
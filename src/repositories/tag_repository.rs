@@ -0,0 +1,179 @@
+use sqlx::{Error, SqlitePool, Transaction, Sqlite};
+
+use crate::models::tag::{RunTag, TagSummary, AUTO_TAG_CLOUD, AUTO_TAG_LAPTOP, AUTO_TAG_MULTI_GPU, TAG_SOURCE_AUTO, TAG_SOURCE_MANUAL};
+use crate::repositories::audit_timestamp;
+use crate::repositories::connection::{retry_on_busy, TimedQuery};
+
+pub struct TagRepository {
+    pool: SqlitePool,
+}
+
+impl TagRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Every tag in use in a tenant's dataset with the number of its runs carrying it, most used first
+    pub async fn find_all_with_counts(&self, tenant_id: &str) -> Result<Vec<TagSummary>, Error> {
+        let results = sqlx::query_as!(
+            TagSummary,
+            r#"
+            SELECT t.name AS "name!", COUNT(rt.run_id) AS "run_count!: i64"
+            FROM tags t
+            JOIN run_tags rt ON rt.tag_id = t.id
+            JOIN runs r ON r.id = rt.run_id
+            WHERE r.tenant_id = ?
+            GROUP BY t.id, t.name
+            ORDER BY COUNT(rt.run_id) DESC, t.name
+            "#,
+            tenant_id
+        )
+        .fetch_all(&self.pool)
+        .timed("tag.find_all_with_counts", 1)
+        .await?;
+
+        Ok(results)
+    }
+
+    /// Tags attached to a run, by name
+    pub async fn find_by_run_id(&self, run_id: i64) -> Result<Vec<RunTag>, Error> {
+        let results = sqlx::query_as!(
+            RunTag,
+            r#"
+            SELECT t.name AS "name!", rt.source AS "source!"
+            FROM run_tags rt
+            JOIN tags t ON t.id = rt.tag_id
+            WHERE rt.run_id = ?
+            ORDER BY t.name
+            "#,
+            run_id
+        )
+        .fetch_all(&self.pool)
+        .timed("tag.find_by_run_id", 1)
+        .await?;
+
+        Ok(results)
+    }
+
+    /// Attach manual tags to a run, creating tags that do not exist yet
+    ///
+    /// A tag the run already carries automatically becomes manual, so later
+    /// refreshes of the automatic tags keep it.
+    pub async fn add_to_run(&self, run_id: i64, names: &[String]) -> Result<(), Error> {
+        let mut tx = self.pool.begin().await?;
+        let now = audit_timestamp();
+
+        for name in names {
+            let tag_id = Self::find_or_create_tx(name, &now, &mut tx).await?;
+            sqlx::query!(
+                r#"
+                INSERT INTO run_tags (run_id, tag_id, source, created_at, updated_at)
+                VALUES (?1, ?2, ?3, ?4, ?4)
+                ON CONFLICT (run_id, tag_id) DO UPDATE SET source = excluded.source, updated_at = excluded.updated_at
+                "#,
+                run_id,
+                tag_id,
+                TAG_SOURCE_MANUAL,
+                now
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await
+    }
+
+    /// Detach a tag from a run, returning whether the run carried it
+    pub async fn remove_from_run(&self, run_id: i64, name: &str) -> Result<bool, Error> {
+        let result = retry_on_busy(|| sqlx::query!(
+            r#"
+            DELETE FROM run_tags
+            WHERE run_id = ? AND tag_id IN (SELECT id FROM tags WHERE name = ?)
+            "#,
+            run_id,
+            name
+        )
+        .execute(&self.pool)
+        .timed("tag.remove_from_run", 2))
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Recompute the automatic tags from the processed GPU data within a transaction
+    ///
+    /// Runs on a laptop GPU are tagged "laptop", runs reporting more than one
+    /// GPU "multi-gpu" and runs on cloud hardware "cloud". Manual tags are left
+    /// alone. Returns the number of automatic tags attached.
+    pub async fn refresh_auto_tags_tx(&self, tx: &mut Transaction<'_, Sqlite>) -> Result<u64, Error> {
+        let now = audit_timestamp();
+
+        sqlx::query!("DELETE FROM run_tags WHERE source = ?", TAG_SOURCE_AUTO)
+            .execute(&mut **tx)
+            .await?;
+
+        let laptop_tag_id = Self::find_or_create_tx(AUTO_TAG_LAPTOP, &now, tx).await?;
+        let laptop = sqlx::query!(
+            r#"
+            INSERT INTO run_tags (run_id, tag_id, source, created_at, updated_at)
+            SELECT DISTINCT g.run_id, ?1, ?2, ?3, ?3
+            FROM GPU g
+            WHERE g.isLaptop = 1 AND g.run_id IS NOT NULL
+            ON CONFLICT (run_id, tag_id) DO NOTHING
+            "#,
+            laptop_tag_id,
+            TAG_SOURCE_AUTO,
+            now
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        let multi_gpu_tag_id = Self::find_or_create_tx(AUTO_TAG_MULTI_GPU, &now, tx).await?;
+        let multi_gpu = sqlx::query!(
+            r#"
+            INSERT INTO run_tags (run_id, tag_id, source, created_at, updated_at)
+            SELECT DISTINCT g.run_id, ?1, ?2, ?3, ?3
+            FROM GPU g
+            WHERE g.gpu_index > 0 AND g.run_id IS NOT NULL
+            ON CONFLICT (run_id, tag_id) DO NOTHING
+            "#,
+            multi_gpu_tag_id,
+            TAG_SOURCE_AUTO,
+            now
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        let cloud_tag_id = Self::find_or_create_tx(AUTO_TAG_CLOUD, &now, tx).await?;
+        let cloud = sqlx::query!(
+            r#"
+            INSERT INTO run_tags (run_id, tag_id, source, created_at, updated_at)
+            SELECT DISTINCT g.run_id, ?1, ?2, ?3, ?3
+            FROM GPU g
+            WHERE g.is_cloud = 1 AND g.run_id IS NOT NULL
+            ON CONFLICT (run_id, tag_id) DO NOTHING
+            "#,
+            cloud_tag_id,
+            TAG_SOURCE_AUTO,
+            now
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(laptop.rows_affected() + multi_gpu.rows_affected() + cloud.rows_affected())
+    }
+
+    async fn find_or_create_tx(name: &str, now: &str, tx: &mut Transaction<'_, Sqlite>) -> Result<i64, Error> {
+        sqlx::query!(
+            "INSERT INTO tags (name, created_at, updated_at) VALUES (?1, ?2, ?2) ON CONFLICT (name) DO NOTHING",
+            name,
+            now
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        sqlx::query_scalar!(r#"SELECT id AS "id!" FROM tags WHERE name = ?"#, name)
+            .fetch_one(&mut **tx)
+            .await
+    }
+}
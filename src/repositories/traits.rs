@@ -1,6 +1,8 @@
 use async_trait::async_trait;
 use sqlx::{Error, Transaction, Sqlite};
 
+use crate::repositories::query_builder::Filter;
+
 /// Base trait for CRUD operations on a repository.
 #[async_trait]
 pub trait Repository<T, Id> {
@@ -10,6 +12,10 @@ pub trait Repository<T, Id> {
     async fn update(&self, entity: T) -> Result<T, Error>;
     async fn delete(&self, id: Id) -> Result<(), Error>;
     async fn count(&self) -> Result<i64, Error>;
+    /// Whether a row with `id` exists, without fetching it
+    async fn exists(&self, id: Id) -> Result<bool, Error>;
+    /// Number of rows whose `filter.field` equals `filter.value`
+    async fn count_where(&self, filter: &Filter) -> Result<i64, Error>;
 }
 
 /// Trait for repositories that support transactions.
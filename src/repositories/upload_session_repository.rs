@@ -0,0 +1,302 @@
+use async_trait::async_trait;
+use sqlx::{Error, SqlitePool};
+
+use crate::models::upload_session::{UploadPart, UploadSchemaReport, UploadSession, UPLOAD_STATUS_FAILED};
+use crate::services::ingest::schema_drift::SchemaDrift;
+use crate::repositories::traits::Repository;
+use crate::repositories::connection::{retry_on_busy, TimedQuery};
+use crate::repositories::query_builder::Filter;
+
+#[derive(Clone)]
+pub struct UploadSessionRepository {
+    pool: SqlitePool,
+}
+
+impl UploadSessionRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Update the status and message of an upload session
+    pub async fn update_status(&self, id: &str, status: &str, message: Option<&str>, updated_at: &str) -> Result<(), Error> {
+        retry_on_busy(|| sqlx::query!(
+            r#"
+            UPDATE UploadSession
+            SET status = ?, message = ?, updated_at = ?
+            WHERE id = ?
+            "#,
+            status,
+            message,
+            updated_at,
+            id
+        )
+        .execute(&self.pool)
+        .timed("upload_session.update_status", 4))
+        .await?;
+
+        Ok(())
+    }
+
+    /// Move an upload session from `from` to `to` status, unless another request already moved it
+    ///
+    /// Returns whether this call made the change, so exactly one of several
+    /// concurrent callers claims the session.
+    pub async fn transition_status(&self, id: &str, from: &str, to: &str, updated_at: &str) -> Result<bool, Error> {
+        let result = retry_on_busy(|| sqlx::query!(
+            r#"
+            UPDATE UploadSession
+            SET status = ?, updated_at = ?
+            WHERE id = ? AND status = ?
+            "#,
+            to,
+            updated_at,
+            id,
+            from
+        )
+        .execute(&self.pool)
+        .timed("upload_session.transition_status", 4))
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Fail the upload sessions left in `status` since before `before`, returning how many
+    pub async fn fail_stale(&self, status: &str, before: &str, message: &str, updated_at: &str) -> Result<u64, Error> {
+        let result = retry_on_busy(|| sqlx::query!(
+            r#"
+            UPDATE UploadSession
+            SET status = ?, message = ?, updated_at = ?
+            WHERE status = ? AND updated_at < ?
+            "#,
+            UPLOAD_STATUS_FAILED,
+            message,
+            updated_at,
+            status,
+            before
+        )
+        .execute(&self.pool)
+        .timed("upload_session.fail_stale", 4))
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Record a received part, replacing an earlier upload of the same part number
+    pub async fn upsert_part(&self, part: UploadPart) -> Result<UploadPart, Error> {
+        let id = retry_on_busy(|| sqlx::query!(
+            r#"
+            INSERT INTO UploadPart (upload_id, part_number, size)
+            VALUES (?, ?, ?)
+            ON CONFLICT (upload_id, part_number) DO UPDATE SET size = excluded.size
+            "#,
+            part.upload_id,
+            part.part_number,
+            part.size
+        )
+        .execute(&self.pool)
+        .timed("upload_session.upsert_part", 3))
+        .await?
+        .last_insert_rowid();
+
+        Ok(UploadPart {
+            id: Some(id),
+            ..part
+        })
+    }
+
+    /// Find the received parts of an upload session ordered by part number
+    pub async fn find_parts(&self, upload_id: &str) -> Result<Vec<UploadPart>, Error> {
+        let results = sqlx::query_as!(
+            UploadPart,
+            r#"
+            SELECT id, upload_id, part_number, size
+            FROM UploadPart
+            WHERE upload_id = ?
+            ORDER BY part_number
+            "#,
+            upload_id
+        )
+        .fetch_all(&self.pool)
+        .timed("upload_session.find_parts", 1)
+        .await?;
+
+        Ok(results)
+    }
+
+    /// Store the schema report of an upload, replacing an earlier report for the same id
+    pub async fn save_schema_report(&self, report: &UploadSchemaReport) -> Result<(), Error> {
+        let unknown_keys = serde_json::to_string(&report.drift.unknown_keys).map_err(|e| Error::Encode(Box::new(e)))?;
+        let missing_keys = serde_json::to_string(&report.drift.missing_keys).map_err(|e| Error::Encode(Box::new(e)))?;
+
+        retry_on_busy(|| sqlx::query!(
+            r#"
+            INSERT OR REPLACE INTO UploadSchemaReport (upload_id, file_name, format, unknown_keys, missing_keys, created_at, tenant_id)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            "#,
+            report.upload_id,
+            report.file_name,
+            report.format,
+            unknown_keys,
+            missing_keys,
+            report.created_at,
+            report.tenant_id
+        )
+        .execute(&self.pool)
+        .timed("upload_session.save_schema_report", 6))
+        .await?;
+
+        Ok(())
+    }
+
+    /// Find the schema report of an upload filed under the tenant
+    pub async fn find_schema_report(&self, upload_id: &str, tenant_id: &str) -> Result<Option<UploadSchemaReport>, Error> {
+        let row = sqlx::query!(
+            r#"
+            SELECT upload_id AS "upload_id!", file_name, format, unknown_keys, missing_keys, created_at, tenant_id
+            FROM UploadSchemaReport
+            WHERE upload_id = ? AND tenant_id = ?
+            "#,
+            upload_id,
+            tenant_id
+        )
+        .fetch_optional(&self.pool)
+        .timed("upload_session.find_schema_report", 1)
+        .await?;
+
+        row.map(|row| {
+            Ok(UploadSchemaReport {
+                upload_id: row.upload_id,
+                file_name: row.file_name,
+                format: row.format,
+                drift: SchemaDrift {
+                    unknown_keys: serde_json::from_str(&row.unknown_keys).map_err(|e| Error::Decode(Box::new(e)))?,
+                    missing_keys: serde_json::from_str(&row.missing_keys).map_err(|e| Error::Decode(Box::new(e)))?,
+                },
+                created_at: row.created_at,
+                tenant_id: row.tenant_id,
+            })
+        })
+        .transpose()
+    }
+}
+
+#[async_trait]
+impl Repository<UploadSession, String> for UploadSessionRepository {
+    async fn create(&self, entity: UploadSession) -> Result<UploadSession, Error> {
+        retry_on_busy(|| sqlx::query!(
+            r#"
+            INSERT INTO UploadSession (id, file_name, status, total_parts, total_size, message, created_at, updated_at, tenant_id)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+            entity.id,
+            entity.file_name,
+            entity.status,
+            entity.total_parts,
+            entity.total_size,
+            entity.message,
+            entity.created_at,
+            entity.updated_at,
+            entity.tenant_id
+        )
+        .execute(&self.pool)
+        .timed("upload_session.create", 8))
+        .await?;
+
+        Ok(entity)
+    }
+
+    async fn find_by_id(&self, id: String) -> Result<Option<UploadSession>, Error> {
+        let result = sqlx::query_as!(
+            UploadSession,
+            r#"
+            SELECT id AS "id!", file_name, status, total_parts, total_size, message, created_at, updated_at, tenant_id
+            FROM UploadSession
+            WHERE id = ?
+            "#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .timed("upload_session.find_by_id", 1)
+        .await?;
+
+        Ok(result)
+    }
+
+    async fn find_all(&self) -> Result<Vec<UploadSession>, Error> {
+        let results = sqlx::query_as!(
+            UploadSession,
+            r#"
+            SELECT id AS "id!", file_name, status, total_parts, total_size, message, created_at, updated_at, tenant_id
+            FROM UploadSession
+            ORDER BY created_at DESC
+            "#
+        )
+        .fetch_all(&self.pool)
+        .timed("upload_session.find_all", 0)
+        .await?;
+
+        Ok(results)
+    }
+
+    async fn update(&self, entity: UploadSession) -> Result<UploadSession, Error> {
+        retry_on_busy(|| sqlx::query!(
+            r#"
+            UPDATE UploadSession
+            SET file_name = ?, status = ?, total_parts = ?, total_size = ?, message = ?, updated_at = ?
+            WHERE id = ?
+            "#,
+            entity.file_name,
+            entity.status,
+            entity.total_parts,
+            entity.total_size,
+            entity.message,
+            entity.updated_at,
+            entity.id
+        )
+        .execute(&self.pool)
+        .timed("upload_session.update", 7))
+        .await?;
+
+        Ok(entity)
+    }
+
+    async fn delete(&self, id: String) -> Result<(), Error> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query!("DELETE FROM UploadPart WHERE upload_id = ?", id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query!("DELETE FROM UploadSession WHERE id = ?", id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn count(&self) -> Result<i64, Error> {
+        let count = sqlx::query!("SELECT COUNT(*) as count FROM UploadSession")
+            .fetch_one(&self.pool)
+            .timed("upload_session.count", 0)
+            .await?
+            .count;
+        Ok(count)
+    }
+
+    async fn exists(&self, id: String) -> Result<bool, Error> {
+        let exists = sqlx::query_scalar!(r#"SELECT EXISTS(SELECT 1 FROM UploadSession WHERE id = ?) AS "exists!: bool""#, id)
+            .fetch_one(&self.pool)
+            .timed("upload_session.exists", 1)
+            .await?;
+        Ok(exists)
+    }
+
+    async fn count_where(&self, filter: &Filter) -> Result<i64, Error> {
+        let count: i64 = sqlx::query_scalar(&filter.count_sql("UploadSession")?)
+            .bind(&filter.value)
+            .fetch_one(&self.pool)
+            .timed("upload_session.count_where", 1)
+            .await?;
+        Ok(count)
+    }
+}
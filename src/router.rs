@@ -0,0 +1,100 @@
+use std::sync::Arc;
+
+use axum::{extract::DefaultBodyLimit, routing::get, Router};
+use tracing::info;
+
+use crate::{
+    error::i18n::ErrorCatalog,
+    handlers,
+    middleware::{
+        allowed_methods::answer_options,
+        catch_panic::catch_panic_layer,
+        compression::compression_layer,
+        cors::cors_layer,
+        error_localization::localize_errors,
+        logging::log_requests,
+        maintenance_mode::{reject_writes_during_maintenance, MAINTENANCE_MODE_PATH},
+        request_metrics::count_requests,
+        tenant::resolve_tenant,
+    },
+    routes, AppState,
+};
+
+/// Build the application router from the admin, query and public route groups
+///
+/// Each group in `crate::routes` brings its own middleware and body size
+/// limits from `settings.body_limits`; routes of features switched off in
+/// `settings.features` are not mounted, and the dashboard SPA takes every
+/// other path when `settings.application.dashboard_dir` is set. CORS follows `settings.server.cors_origins`;
+/// request logging and response compression are added when enabled in settings.
+pub fn create_router(app_state: AppState, error_catalog: Arc<ErrorCatalog>) -> Router {
+    let request_logging = app_state.settings.logging.request_logging.clone();
+    let compression = app_state.settings.compression.clone();
+    let cors = cors_layer(&app_state.settings.server.cors_origins);
+
+    // Operational endpoints outside the groups
+    let ops_routes = Router::new()
+        .route("/health", get(health_check_endpoint))
+        .route("/env", get(show_environment))
+        .route("/metrics", get(handlers::metrics::metrics))
+        // Always reachable, so maintenance can be switched off again
+        .route(
+            MAINTENANCE_MODE_PATH,
+            get(handlers::maintenance::get_maintenance_mode).post(handlers::maintenance::set_maintenance_mode),
+        )
+        .layer(DefaultBodyLimit::max(app_state.settings.body_limits.json_max_bytes()));
+
+    let mut groups = Router::new()
+        .merge(ops_routes)
+        .merge(routes::admin::routes(&app_state))
+        .merge(routes::query::routes(&app_state))
+        .merge(routes::public::routes(&app_state));
+
+    if let Some(dashboard_dir) = &app_state.settings.application.dashboard_dir {
+        info!("Serving dashboard from {:?}", dashboard_dir);
+        groups = groups.merge(routes::dashboard::routes(dashboard_dir));
+    }
+
+    // The bare routes, which answer a method they do not take with their Allow header
+    let routes = groups.clone().with_state(app_state.clone());
+
+    let mut app = groups
+        // Tenant from the API key, which uploads are filed under and the runs list is scoped to
+        .layer(axum::middleware::from_fn_with_state(app_state.clone(), resolve_tenant))
+        // Inside error localization, so the 503 is translated like any other error
+        .layer(axum::middleware::from_fn_with_state(app_state.clone(), reject_writes_during_maintenance))
+        .layer(axum::middleware::from_fn_with_state(error_catalog, localize_errors))
+        // Answers preflights on every route, whatever methods the route takes
+        .layer(cors)
+        // Outside CORS, which would answer every OPTIONS as a preflight: other OPTIONS get 204
+        // with the route's Allow header, and 405s list OPTIONS too
+        .layer(axum::middleware::from_fn_with_state(routes, answer_options))
+        .with_state(app_state);
+
+    // Opt-in per environment (see [logging.request_logging]) for troubleshooting failed uploads
+    if request_logging.enabled {
+        info!("Request/response logging enabled");
+        let request_logging = Arc::new(request_logging);
+        app = app.layer(axum::middleware::from_fn_with_state(request_logging, log_requests));
+    }
+
+    // Outside request logging, so logged bodies are the uncompressed ones
+    if compression.enabled {
+        app = app.layer(compression_layer(&compression));
+    }
+
+    // Outside everything but the panic handling, so refused and failed requests are counted too
+    app = app.layer(axum::middleware::from_fn(count_requests));
+
+    // Outermost, so a panic anywhere below still gets a JSON 500 instead of a dropped connection
+    app.layer(catch_panic_layer())
+}
+
+async fn health_check_endpoint() -> &'static str {
+    "OK"
+}
+
+async fn show_environment() -> String {
+    let rust_env = std::env::var("RUST_ENV").unwrap_or_else(|_| "development".to_string());
+    format!("Current RUST_ENV: {}", rust_env)
+}
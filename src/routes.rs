@@ -0,0 +1,11 @@
+//! Route groups, each with its own middleware stack
+//!
+//! `admin` holds the pipeline steps, reference data curation and moderation,
+//! `query` the read-only stats and tags, and `public` the unauthenticated
+//! share links, feed, badges and benchmark submissions. `dashboard` serves
+//! the bundled frontend for every other path when configured.
+
+pub mod admin;
+pub mod dashboard;
+pub mod public;
+pub mod query;
@@ -0,0 +1,132 @@
+use axum::{
+    extract::DefaultBodyLimit,
+    routing::{get, post},
+    Router,
+};
+use tracing::info;
+
+use crate::{
+    handlers,
+    middleware::{
+        content_negotiation::negotiate_content_type, pipeline_tracking::track_pipeline_run,
+        request_transaction::transaction_per_request,
+    },
+    AppState,
+};
+
+/// Pipeline steps, reference data curation, moderation, data fixes and the `/admin` page
+///
+/// Every route runs in a request transaction; pipeline steps are also recorded
+/// in the dataset metadata, and curation routes honor the Accept header.
+pub fn routes(app_state: &AppState) -> Router<AppState> {
+    let body_limits = &app_state.settings.body_limits;
+
+    Router::new()
+        // Operator page with buttons posting to the pipeline routes below
+        .route("/admin", get(handlers::admin_page::admin_page))
+        .merge(pipeline_routes(app_state))
+        .merge(curation_routes(app_state))
+        .layer(DefaultBodyLimit::max(body_limits.json_max_bytes()))
+        .merge(bulk_import_routes(app_state))
+}
+
+/// Bulk imports of benchmark files, uploaded, downloaded or generated, allowed large bodies
+fn bulk_import_routes(app_state: &AppState) -> Router<AppState> {
+    let mut routes = Router::new()
+        .route("/api/save-data", post(handlers::admin::save_data))
+        .route("/api/ingest-url", post(handlers::remote_ingest::ingest_url))
+        .route("/api/admin/upstream-sync", post(handlers::upstream_sync::upstream_sync));
+    // Synthetic datasets for performance work, never served outside development
+    if app_state.settings.is_development() {
+        routes = routes.route("/api/admin/generate-data", post(handlers::load_data::generate_data));
+    } else {
+        info!("Load test data generator disabled");
+    }
+
+    routes
+        .route_layer(axum::middleware::from_fn_with_state(app_state.clone(), transaction_per_request))
+        .layer(DefaultBodyLimit::max(app_state.settings.body_limits.upload_max_bytes()))
+}
+
+/// Pipeline steps whose successful runs are recorded in the dataset metadata
+fn pipeline_routes(app_state: &AppState) -> Router<AppState> {
+    Router::new()
+        .route("/api/process-its", post(handlers::admin::process_its))
+        .route("/api/process-app-details", post(handlers::admin::process_app_details))
+        .route("/api/process-system-info", post(handlers::admin::process_system_info))
+        .route("/api/process-libraries", post(handlers::admin::process_libraries))
+        .route("/api/process-gpu", post(handlers::admin::process_gpu))
+        .route("/api/update-gpu-brands", post(handlers::admin::update_gpu_brands))
+        .route("/api/update-gpu-laptop-info", post(handlers::admin::update_gpu_laptop_info))
+        .route("/api/update-gpu-integrated-info", post(handlers::admin::update_gpu_integrated_info))
+        .route("/api/update-gpu-cloud-info", post(handlers::admin::update_gpu_cloud_info))
+        .route("/api/normalize-cpu-info", post(handlers::admin::normalize_cpu_info))
+        .route("/api/canonicalize-app-urls", post(handlers::admin::canonicalize_app_urls))
+        .route("/api/process-run-details", post(handlers::admin::process_run_details))
+        .route("/api/fix-app-names", post(handlers::admin::fix_app_names))
+        .route("/api/update-run-more-details-with-modelmapid", post(handlers::admin::update_run_more_details_with_modelmapid))
+        .route("/api/compute-run-scores", post(handlers::admin::compute_run_scores))
+        // Every stage above in dependency order, see handlers::pipeline
+        .route("/api/run-pipeline", post(handlers::pipeline::run_pipeline))
+        .route("/api/admin/seed-gpu-base", post(handlers::admin::seed_gpu_base))
+        .route("/api/admin/seed-model-map", post(handlers::admin::seed_model_map))
+        .route("/api/admin/gpu-dedup/apply", post(handlers::gpu_dedup::apply_gpu_dedup))
+        .route("/api/admin/backfill", post(handlers::admin::backfill))
+        // Outside run-pipeline: lookups call GitHub, slowly, when settings.commit_dates allows them
+        .route("/api/admin/enrich-commit-dates", post(handlers::admin::enrich_commit_dates))
+        // One step rebuilt for the runs whose raw string matches a pattern, see handlers::reparse
+        .route("/api/admin/reparse", post(handlers::reparse::reparse))
+        // Innermost, so the request transaction is finished before the step is recorded
+        .route_layer(axum::middleware::from_fn_with_state(app_state.clone(), transaction_per_request))
+        .route_layer(axum::middleware::from_fn_with_state(app_state.clone(), track_pipeline_run))
+}
+
+/// Reference data edits, mapping curation and the admin reports, honoring the Accept header
+fn curation_routes(app_state: &AppState) -> Router<AppState> {
+    let routes = Router::new()
+        .route("/api/app-details-analysis", get(handlers::admin::app_details_analysis))
+        .route("/api/gpu-prices", get(handlers::gpu_prices::list_gpu_prices).post(handlers::gpu_prices::create_gpu_price))
+        .route("/api/gpu-prices/{id}", get(handlers::gpu_prices::get_gpu_price).put(handlers::gpu_prices::update_gpu_price).delete(handlers::gpu_prices::delete_gpu_price))
+        // Reference data edits are versioned; a stale version gets 409
+        .route("/api/model-map/{id}", get(handlers::model_map::get_model_map).put(handlers::model_map::update_model_map))
+        .route("/api/gpu-map/{id}", get(handlers::gpu_map::get_gpu_map).put(handlers::gpu_map::update_gpu_map))
+        .route("/api/gpu-base/{id}", get(handlers::gpu_base::get_gpu_base).put(handlers::gpu_base::update_gpu_base))
+        // Mapping curation: export to CSV (see below), edit, then re-import
+        .route("/api/model-map/import", post(handlers::model_map::import_model_map))
+        // Raw benchmark strings beside their parsed rows, for diagnosing parses
+        .route("/api/runs/{id}/raw", get(handlers::runs::get_run_raw_sources))
+        // Current parsers on one run, written nowhere
+        .route("/api/admin/reparse-preview", post(handlers::reparse::reparse_preview))
+        .route("/api/admin/gpu-dedup/preview", get(handlers::gpu_dedup::preview_gpu_dedup))
+        .route("/api/admin/quality-report", get(handlers::quality::quality_report))
+        .route("/api/admin/dataset-versions", get(handlers::meta::list_dataset_versions))
+        .route("/api/admin/pipeline-history", get(handlers::pipeline::pipeline_history))
+        .route("/api/admin/health-history", get(handlers::metrics::health_history))
+        // Dry run only; the scheduled cleanup does the deleting
+        .route("/api/admin/storage-cleanup", get(handlers::retention::storage_cleanup_preview))
+        // Rebuilt on request; outside the pipeline routes so refreshing does not itself mark the cache stale
+        .route("/api/admin/refresh-percentiles", post(handlers::admin::refresh_percentiles))
+        // Runs the pipeline on a bundled mini-dataset in a throwaway in-memory database
+        .route("/api/admin/self-test", post(handlers::self_test::self_test))
+        // Deletes a submitter's runs and derived rows in the request transaction
+        .route("/api/admin/users/{user}/forget", post(handlers::users::forget_user))
+        // Files one submitter spelling under another, in the request transaction
+        .route("/api/admin/users/merge", post(handlers::users::merge_users))
+        // Runs held back by the notes filter; reviews run in the request transaction
+        .route("/api/admin/moderation", get(handlers::moderation::list_moderation_queue))
+        .route("/api/admin/moderation/{id}", post(handlers::moderation::review_moderation_entry))
+        // Data fixes kept as change sets; reverts run in the request transaction
+        .route("/api/admin/change-sets", get(handlers::change_sets::list_change_sets))
+        .route("/api/admin/change-sets/{id}/revert", post(handlers::change_sets::revert_change_set))
+        // Mounted even with exports switched off, when the handlers answer 404 themselves;
+        // unmounted, the paths would fall through to the `{id}` routes above
+        .route("/api/model-map/export", get(handlers::model_map::export_model_map))
+        .route("/api/gpu-map/export", get(handlers::gpu_map::export_gpu_map));
+    if !app_state.settings.features.enable_export {
+        info!("Mapping exports disabled");
+    }
+
+    routes
+        .route_layer(axum::middleware::from_fn_with_state(app_state.clone(), transaction_per_request))
+        .route_layer(axum::middleware::from_fn(negotiate_content_type))
+}
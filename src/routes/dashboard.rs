@@ -0,0 +1,23 @@
+use std::path::Path;
+
+use axum::{http::Uri, routing::any, Router};
+use tower_http::services::{ServeDir, ServeFile};
+
+use crate::{error::types::AppError, AppState};
+
+/// Serve the built dashboard SPA from `dist_dir` for every path no other route takes
+///
+/// Paths without a matching file get `index.html`, so client-side routes load
+/// on a reload. Unknown `/api` paths are answered with a JSON 404 instead.
+pub fn routes(dist_dir: &Path) -> Router<AppState> {
+    let index = ServeFile::new(dist_dir.join("index.html"));
+
+    Router::new()
+        .route("/api", any(unknown_api_path))
+        .route("/api/{*path}", any(unknown_api_path))
+        .fallback_service(ServeDir::new(dist_dir).fallback(index))
+}
+
+async fn unknown_api_path(uri: Uri) -> AppError {
+    AppError::not_found(format!("API route {}", uri.path()))
+}
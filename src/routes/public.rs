@@ -0,0 +1,72 @@
+use std::{sync::Arc, time::Duration};
+
+use axum::{
+    extract::DefaultBodyLimit,
+    routing::{get, post, put},
+    Router,
+};
+use tracing::info;
+
+use crate::{
+    handlers,
+    middleware::{
+        rate_limit::{rate_limit, RateLimiter},
+        request_transaction::transaction_per_request,
+        response_cache::{cache_responses, ResponseCache},
+    },
+    AppState,
+};
+
+/// Routes reachable without authentication: permalinks, the feed, the digest, badges and benchmark submissions
+///
+/// Keep them out of any auth layer.
+pub fn routes(app_state: &AppState) -> Router<AppState> {
+    let body_limits = &app_state.settings.body_limits;
+
+    let mut routes = Router::new()
+        .route("/api/share/{token}", get(handlers::share::get_shared_run))
+        .route("/api/feed.atom", get(handlers::feed::submissions_feed))
+        // Weekly (or daily, monthly) changes for community posts
+        .route("/api/digest", get(handlers::digest::digest))
+        .merge(badge_routes(app_state))
+        .layer(DefaultBodyLimit::max(body_limits.json_max_bytes()));
+
+    if app_state.settings.features.enable_public_submissions {
+        routes = routes.merge(submission_routes(app_state));
+    } else {
+        info!("Public submissions disabled");
+    }
+
+    routes
+}
+
+/// Embeddable badges: rate limited per client, then served from cache
+fn badge_routes(app_state: &AppState) -> Router<AppState> {
+    let badges = &app_state.settings.badges;
+
+    Router::new()
+        .route("/api/badge/gpu/{name}", get(handlers::badge::gpu_badge))
+        .route_layer(axum::middleware::from_fn_with_state(
+            Arc::new(ResponseCache::new(Duration::from_secs(badges.cache_ttl_seconds))),
+            cache_responses,
+        ))
+        .route_layer(axum::middleware::from_fn_with_state(
+            Arc::new(
+                RateLimiter::per_minute(badges.rate_limit_per_minute)
+                    .with_trusted_proxies(badges.trusted_proxies.clone()),
+            ),
+            rate_limit,
+        ))
+}
+
+/// Benchmark file uploads, allowed large bodies
+fn submission_routes(app_state: &AppState) -> Router<AppState> {
+    Router::new()
+        .route("/api/upload", post(handlers::upload::upload_file_compat))
+        .route("/api/uploads/init", post(handlers::uploads::init_upload))
+        .route("/api/uploads/{id}", get(handlers::uploads::get_upload))
+        .route("/api/uploads/{id}/part/{n}", put(handlers::uploads::upload_part))
+        .route("/api/uploads/{id}/complete", post(handlers::uploads::complete_upload))
+        .route_layer(axum::middleware::from_fn_with_state(app_state.clone(), transaction_per_request))
+        .layer(DefaultBodyLimit::max(app_state.settings.body_limits.upload_max_bytes()))
+}
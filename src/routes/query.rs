@@ -0,0 +1,41 @@
+use axum::{
+    extract::DefaultBodyLimit,
+    routing::{delete, get, post},
+    Router,
+};
+
+use crate::{
+    handlers,
+    middleware::{content_negotiation::negotiate_content_type, request_transaction::transaction_per_request},
+    AppState,
+};
+
+/// Runs, stats, leaderboards, dataset metadata, upload schema reports and run tags, honoring the Accept header (JSON, CSV or MessagePack)
+pub fn routes(app_state: &AppState) -> Router<AppState> {
+    Router::new()
+        .route("/api/upload-formats", get(handlers::upload_formats::list_upload_formats))
+        .route("/api/uploads/{id}/schema-report", get(handlers::uploads::get_schema_report))
+        .route("/api/leaderboard", get(handlers::stats::leaderboard))
+        .route("/api/top", get(handlers::stats::top_configurations))
+        .route("/api/estimate", post(handlers::estimate::estimate_its))
+        .route("/api/stats/its-per-dollar", get(handlers::stats::its_per_dollar))
+        .route("/api/stats/its-by-app", get(handlers::stats::its_by_app))
+        .route("/api/stats/its-by-app-version", get(handlers::stats::its_by_app_version))
+        .route("/api/stats/its-by-architecture", get(handlers::stats::its_by_architecture))
+        .route("/api/stats/cpu-impact", get(handlers::stats::cpu_impact))
+        .route("/api/stats/gpu-percentiles", get(handlers::stats::gpu_percentiles))
+        .route("/api/stats/counts", get(handlers::stats::grouped_counts))
+        .route("/api/stats/averages", get(handlers::stats::grouped_averages))
+        .route("/api/meta/dataset", get(handlers::meta::dataset_metadata))
+        .route("/api/meta/facets", get(handlers::meta::facets))
+        .route("/api/meta/wait-for-update", get(handlers::meta::wait_for_update))
+        .route("/api/runs", get(handlers::runs::list_runs))
+        .route("/api/runs/{id}/share", post(handlers::share::create_share_link))
+        // Run tags; manual tags come from here, automatic ones from the GPU laptop and cloud info steps
+        .route("/api/tags", get(handlers::tags::list_tags))
+        .route("/api/runs/{id}/tags", get(handlers::tags::get_run_tags).post(handlers::tags::add_run_tags))
+        .route("/api/runs/{id}/tags/{tag}", delete(handlers::tags::remove_run_tag))
+        .route_layer(axum::middleware::from_fn_with_state(app_state.clone(), transaction_per_request))
+        .route_layer(axum::middleware::from_fn(negotiate_content_type))
+        .layer(DefaultBodyLimit::max(app_state.settings.body_limits.json_max_bytes()))
+}
@@ -1,7 +1,59 @@
 // Modern directory-based module declarations
 pub mod data_processing;
 pub mod parsers;
+pub mod ingest;
+pub mod uploads;
+pub mod sharing;
+pub mod badges;
+pub mod feed;
+pub mod estimate;
+pub mod percentiles;
+pub mod cpu_impact;
+pub mod gpu_dedup;
+pub mod stage_timing;
+pub mod parsing_pool;
+pub mod anonymization;
+pub mod reference_revisions;
+pub mod laptop_detection;
+pub mod app_attribution;
+pub mod cloud_detection;
+pub mod admin_page;
+pub mod pipeline;
+pub mod alerts;
+pub mod notifications;
+pub mod remote_ingest;
+pub mod health_history;
+pub mod load_data;
+pub mod retention;
+pub mod digest;
+pub mod commit_dates;
 
 // Re-export main service types for easy access
 pub use data_processing::*;
 pub use parsers::*;
+pub use ingest::*;
+pub use uploads::*;
+pub use sharing::*;
+pub use badges::*;
+pub use feed::*;
+pub use estimate::*;
+pub use percentiles::*;
+pub use cpu_impact::*;
+pub use gpu_dedup::*;
+pub use stage_timing::*;
+pub use parsing_pool::*;
+pub use anonymization::*;
+pub use reference_revisions::*;
+pub use laptop_detection::*;
+pub use app_attribution::*;
+pub use cloud_detection::*;
+pub use admin_page::*;
+pub use pipeline::*;
+pub use alerts::*;
+pub use notifications::*;
+pub use remote_ingest::*;
+pub use health_history::*;
+pub use load_data::*;
+pub use retention::*;
+pub use digest::*;
+pub use commit_dates::*;
@@ -0,0 +1,107 @@
+use crate::{models::dataset_meta::DatasetMetadata, services::badges::escape_xml};
+
+/// Render the operator page: dataset stats, the last pipeline step and one button per step
+///
+/// `steps` are pipeline endpoints in the order they run, each posted to
+/// `/api/{step}`. Plain HTML forms without scripts; the browser shows the
+/// step's JSON response.
+pub fn render_admin_page(title: &str, metadata: &DatasetMetadata, steps: &[&str]) -> String {
+    let mut html = String::from("<!doctype html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str(&format!("<title>{}</title>\n", escape_xml(title)));
+    html.push_str("</head>\n<body>\n");
+    html.push_str(&format!("<h1>{}</h1>\n", escape_xml(title)));
+
+    html.push_str("<h2>Dataset</h2>\n");
+    html.push_str(&format!("<p>Dataset version {}</p>\n", metadata.dataset_version));
+    if let Some(source) = &metadata.source_upload {
+        html.push_str(&format!(
+            "<p>Source upload: {} ({})</p>\n",
+            escape_xml(&source.file_name),
+            escape_xml(source.uploaded_at.as_deref().unwrap_or("unknown time"))
+        ));
+    }
+    html.push_str("<table>\n<tr><th>Table</th><th>Rows</th></tr>\n");
+    for (table, rows) in &metadata.table_counts {
+        html.push_str(&format!("<tr><td>{}</td><td>{}</td></tr>\n", escape_xml(table), rows));
+    }
+    html.push_str("</table>\n");
+
+    html.push_str("<h2>Last pipeline step</h2>\n");
+    match &metadata.last_pipeline_step {
+        Some(step) => {
+            html.push_str(&format!(
+                "<p>{} at {}",
+                escape_xml(step),
+                escape_xml(metadata.last_pipeline_run_at.as_deref().unwrap_or("unknown time"))
+            ));
+            if let Some(duration_ms) = metadata.last_pipeline_duration_ms {
+                html.push_str(&format!(", took {:.0} ms", duration_ms));
+            }
+            html.push_str("</p>\n");
+            if !metadata.last_pipeline_stage_timings.is_empty() {
+                html.push_str("<table>\n<tr><th>Stage</th><th>Rows</th><th>Duration (ms)</th></tr>\n");
+                for timing in &metadata.last_pipeline_stage_timings {
+                    html.push_str(&format!(
+                        "<tr><td>{}</td><td>{}</td><td>{:.0}</td></tr>\n",
+                        escape_xml(&timing.stage),
+                        timing.rows,
+                        timing.duration_ms
+                    ));
+                }
+                html.push_str("</table>\n");
+            }
+        }
+        None => html.push_str("<p>No pipeline step has run yet</p>\n"),
+    }
+
+    html.push_str("<h2>Run pipeline steps</h2>\n");
+    for step in steps {
+        html.push_str(&format!(
+            "<form method=\"post\" action=\"/api/{step}\"><button type=\"submit\">{step}</button></form>\n"
+        ));
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+
+    fn metadata(last_pipeline_step: Option<&str>) -> DatasetMetadata {
+        DatasetMetadata {
+            dataset_version: 3,
+            table_counts: BTreeMap::from([("runs".to_string(), 42)]),
+            last_pipeline_step: last_pipeline_step.map(str::to_string),
+            last_pipeline_run_at: Some("2024-01-01T00:00:00+00:00".to_string()),
+            last_pipeline_duration_ms: Some(1234.4),
+            last_pipeline_stage_timings: Vec::new(),
+            source_upload: None,
+        }
+    }
+
+    #[test]
+    fn test_render_admin_page() {
+        let html = render_admin_page(
+            "Bench <admin>",
+            &metadata(Some("process-its")),
+            &["process-its", "compute-run-scores"],
+        );
+
+        assert!(html.contains("<title>Bench &lt;admin&gt;</title>"));
+        assert!(html.contains("<tr><td>runs</td><td>42</td></tr>"));
+        assert!(html.contains("<p>process-its at 2024-01-01T00:00:00+00:00, took 1234 ms</p>"));
+        assert_eq!(html.matches("<form method=\"post\"").count(), 2);
+        assert!(html.contains("action=\"/api/compute-run-scores\""));
+    }
+
+    #[test]
+    fn test_render_admin_page_before_any_step() {
+        let html = render_admin_page("Bench", &metadata(None), &[]);
+
+        assert!(html.contains("No pipeline step has run yet"));
+    }
+}
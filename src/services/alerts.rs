@@ -0,0 +1,161 @@
+use chrono::DateTime;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::settings::AlertsConfig,
+    models::pipeline_event::{PipelineEvent, PIPELINE_EVENT_FAILED, PIPELINE_EVENT_SUCCEEDED},
+};
+
+pub const ALERT_RULE_ERROR_RATE: &str = "error_rate";
+pub const ALERT_RULE_MIN_ROWS_OUT: &str = "min_rows_out";
+pub const ALERT_RULE_STAGE_DURATION: &str = "stage_duration";
+
+/// A threshold a pipeline run tripped
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PipelineAlert {
+    /// One of the `ALERT_RULE_*` names
+    pub rule: String,
+    /// The stage the alert concerns; none for the error rate
+    pub stage: Option<String>,
+    pub message: String,
+    pub value: f64,
+    pub threshold: f64,
+}
+
+/// Check a pipeline run against the configured thresholds
+///
+/// `run_events` are the stage executions of the run just finished;
+/// `recent_events` the latest `error_rate_window` executions, including the run's own.
+pub fn evaluate_alerts(config: &AlertsConfig, run_events: &[PipelineEvent], recent_events: &[PipelineEvent]) -> Vec<PipelineAlert> {
+    let mut alerts = Vec::new();
+
+    if let Some(max_error_rate) = config.max_error_rate_percent
+        && !recent_events.is_empty()
+    {
+        let failed = recent_events.iter().filter(|event| event.status == PIPELINE_EVENT_FAILED).count();
+        let error_rate = failed as f64 * 100.0 / recent_events.len() as f64;
+        if error_rate > max_error_rate {
+            alerts.push(PipelineAlert {
+                rule: ALERT_RULE_ERROR_RATE.to_string(),
+                stage: None,
+                message: format!(
+                    "{} of the last {} pipeline stage executions failed ({:.1}%, threshold {:.1}%)",
+                    failed,
+                    recent_events.len(),
+                    error_rate,
+                    max_error_rate
+                ),
+                value: error_rate,
+                threshold: max_error_rate,
+            });
+        }
+    }
+
+    for event in run_events {
+        // A failed stage wrote nothing, which the error rate already covers, and
+        // one rolled back with it had its rows undone
+        if event.status != PIPELINE_EVENT_SUCCEEDED {
+            continue;
+        }
+
+        if let (Some(&min_rows), Some(rows_out)) = (config.min_rows_out.get(&event.stage), event.rows_out)
+            && rows_out < min_rows
+        {
+            alerts.push(PipelineAlert {
+                rule: ALERT_RULE_MIN_ROWS_OUT.to_string(),
+                stage: Some(event.stage.clone()),
+                message: format!("{} wrote {} rows, expected at least {}", event.stage, rows_out, min_rows),
+                value: rows_out as f64,
+                threshold: min_rows as f64,
+            });
+        }
+
+        if let (Some(max_duration), Some(duration)) = (config.max_stage_duration_ms, event_duration_ms(event))
+            && duration > max_duration
+        {
+            alerts.push(PipelineAlert {
+                rule: ALERT_RULE_STAGE_DURATION.to_string(),
+                stage: Some(event.stage.clone()),
+                message: format!("{} took {:.0} ms, threshold {:.0} ms", event.stage, duration, max_duration),
+                value: duration,
+                threshold: max_duration,
+            });
+        }
+    }
+
+    alerts
+}
+
+/// Milliseconds between an event's start and finish, if both parse
+fn event_duration_ms(event: &PipelineEvent) -> Option<f64> {
+    let started = DateTime::parse_from_rfc3339(&event.started_at).ok()?;
+    let finished = DateTime::parse_from_rfc3339(&event.finished_at).ok()?;
+    Some((finished - started).num_microseconds()? as f64 / 1000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn event(stage: &str, status: &str, rows_out: Option<i64>, finished_at: &str) -> PipelineEvent {
+        PipelineEvent {
+            id: None,
+            stage: stage.to_string(),
+            started_at: "2024-01-01T00:00:00+00:00".to_string(),
+            finished_at: finished_at.to_string(),
+            rows_in: None,
+            rows_out,
+            status: status.to_string(),
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_no_thresholds_no_alerts() {
+        let events = vec![event("process-its", PIPELINE_EVENT_FAILED, None, "2024-01-01T01:00:00+00:00")];
+
+        assert!(evaluate_alerts(&AlertsConfig::default(), &events, &events).is_empty());
+    }
+
+    #[test]
+    fn test_error_rate_over_recent_events() {
+        let config = AlertsConfig {
+            max_error_rate_percent: Some(25.0),
+            ..AlertsConfig::default()
+        };
+        let ok = event("process-its", PIPELINE_EVENT_SUCCEEDED, Some(10), "2024-01-01T00:00:01+00:00");
+        let failed = event("process-gpu", PIPELINE_EVENT_FAILED, None, "2024-01-01T00:00:01+00:00");
+
+        let recent = vec![ok.clone(), ok.clone(), ok.clone(), failed.clone()];
+        assert!(evaluate_alerts(&config, &[], &recent).is_empty());
+
+        let recent = vec![ok.clone(), ok, failed.clone(), failed];
+        let alerts = evaluate_alerts(&config, &[], &recent);
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].rule, ALERT_RULE_ERROR_RATE);
+        assert_eq!(alerts[0].value, 50.0);
+    }
+
+    #[test]
+    fn test_min_rows_out_and_stage_duration() {
+        let config = AlertsConfig {
+            min_rows_out: HashMap::from([("process-its".to_string(), 100)]),
+            max_stage_duration_ms: Some(60_000.0),
+            ..AlertsConfig::default()
+        };
+        let events = vec![
+            event("process-its", PIPELINE_EVENT_SUCCEEDED, Some(40), "2024-01-01T00:00:01+00:00"),
+            event("process-gpu", PIPELINE_EVENT_SUCCEEDED, Some(0), "2024-01-01T00:02:00+00:00"),
+        ];
+
+        let alerts = evaluate_alerts(&config, &events, &events);
+        assert_eq!(alerts.len(), 2);
+        assert_eq!(alerts[0].rule, ALERT_RULE_MIN_ROWS_OUT);
+        assert_eq!(alerts[0].stage.as_deref(), Some("process-its"));
+        assert_eq!(alerts[1].rule, ALERT_RULE_STAGE_DURATION);
+        assert_eq!(alerts[1].stage.as_deref(), Some("process-gpu"));
+        assert_eq!(alerts[1].value, 120_000.0);
+    }
+}
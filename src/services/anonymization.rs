@@ -0,0 +1,142 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::{
+    config::settings::AnonymizationConfig,
+    models::{
+        digest::UserActivity,
+        run_score::LeaderboardEntry,
+        runs::{RunDetail, RunFeedEntry, RunListEntry},
+    },
+};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Bytes of the salted hash kept in a pseudonym (hex encoded, so twice as many characters)
+const PSEUDONYM_HASH_BYTES: usize = 8;
+
+/// Strips personal data from runs before they are published
+///
+/// Usernames are replaced with `user-<hex>`, a truncated HMAC-SHA256 of the name
+/// keyed with the instance's salt, so one submitter's runs can still be grouped
+/// without revealing who they are. Free-text notes are dropped entirely.
+pub struct AnonymizationService {
+    salt: Vec<u8>,
+    enabled: bool,
+}
+
+impl AnonymizationService {
+    pub fn new(config: &AnonymizationConfig) -> Self {
+        Self {
+            salt: config.salt.as_bytes().to_vec(),
+            enabled: config.enabled,
+        }
+    }
+
+    /// Whether a response is anonymized, given the request's `anonymize` parameter
+    ///
+    /// When anonymization is enabled in settings, `anonymize=false` cannot opt out.
+    pub fn applies(&self, requested: Option<bool>) -> bool {
+        self.enabled || requested.unwrap_or(false)
+    }
+
+    /// The stable pseudonym published in place of `user`
+    pub fn pseudonym(&self, user: &str) -> String {
+        let mut mac = HmacSha256::new_from_slice(&self.salt).expect("HMAC accepts keys of any length");
+        mac.update(user.trim().as_bytes());
+        let hash = mac.finalize().into_bytes();
+
+        let hex: String = hash[..PSEUDONYM_HASH_BYTES].iter().map(|byte| format!("{:02x}", byte)).collect();
+        format!("user-{}", hex)
+    }
+
+    /// Replace a username with its pseudonym; blank usernames are dropped
+    pub fn anonymize_user(&self, user: Option<String>) -> Option<String> {
+        user.filter(|user| !user.trim().is_empty()).map(|user| self.pseudonym(&user))
+    }
+
+    /// Anonymize every record when `applies(requested)`
+    pub fn apply<T: Anonymize>(&self, requested: Option<bool>, records: &mut [T]) {
+        if self.applies(requested) {
+            for record in records {
+                record.anonymize(self);
+            }
+        }
+    }
+}
+
+/// A published record that may carry a username or notes
+pub trait Anonymize {
+    fn anonymize(&mut self, service: &AnonymizationService);
+}
+
+impl Anonymize for RunDetail {
+    fn anonymize(&mut self, service: &AnonymizationService) {
+        self.user = service.anonymize_user(self.user.take());
+        self.notes = None;
+    }
+}
+
+impl Anonymize for RunFeedEntry {
+    fn anonymize(&mut self, service: &AnonymizationService) {
+        self.user = service.anonymize_user(self.user.take());
+    }
+}
+
+impl Anonymize for RunListEntry {
+    fn anonymize(&mut self, service: &AnonymizationService) {
+        self.run.user = service.anonymize_user(self.run.user.take());
+        self.run.notes = None;
+    }
+}
+
+impl Anonymize for LeaderboardEntry {
+    fn anonymize(&mut self, service: &AnonymizationService) {
+        self.user = service.anonymize_user(self.user.take());
+    }
+}
+
+impl Anonymize for UserActivity {
+    fn anonymize(&mut self, service: &AnonymizationService) {
+        self.user = service.pseudonym(&self.user);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn service(salt: &str, enabled: bool) -> AnonymizationService {
+        AnonymizationService::new(&AnonymizationConfig {
+            enabled,
+            salt: salt.to_string(),
+        })
+    }
+
+    #[test]
+    fn test_pseudonym_is_stable_per_salt() {
+        let first = service("first-instance-salt", false);
+        let second = service("second-instance-salt", false);
+
+        let pseudonym = first.pseudonym("alice");
+        assert_eq!(pseudonym.len(), "user-".len() + PSEUDONYM_HASH_BYTES * 2);
+        assert_eq!(pseudonym, first.pseudonym(" alice "));
+        assert_ne!(pseudonym, first.pseudonym("bob"));
+        assert_ne!(pseudonym, second.pseudonym("alice"));
+        assert!(!pseudonym.contains("alice"));
+    }
+
+    #[test]
+    fn test_blank_users_are_dropped() {
+        let service = service("first-instance-salt", false);
+        assert_eq!(service.anonymize_user(None), None);
+        assert_eq!(service.anonymize_user(Some("  ".to_string())), None);
+    }
+
+    #[test]
+    fn test_config_flag_overrides_request() {
+        assert!(!service("first-instance-salt", false).applies(None));
+        assert!(service("first-instance-salt", false).applies(Some(true)));
+        assert!(service("first-instance-salt", true).applies(Some(false)));
+    }
+}
@@ -0,0 +1,61 @@
+use crate::{config::settings::AppAttributionConfig, services::parsers::ParsedAppUrl};
+
+/// Credits an app repository to its project, following the configured fork map
+pub struct AppAttributor<'a> {
+    config: &'a AppAttributionConfig,
+}
+
+impl<'a> AppAttributor<'a> {
+    pub fn new(config: &'a AppAttributionConfig) -> Self {
+        Self { config }
+    }
+
+    /// The upstream `owner/repo` a listed fork was forked from, lowercased
+    pub fn upstream(&self, url: &ParsedAppUrl) -> Option<String> {
+        let repository = url.repository();
+
+        self.config
+            .fork_map
+            .iter()
+            .find(|(fork, _)| fork.eq_ignore_ascii_case(&repository))
+            .map(|(_, upstream)| upstream.to_lowercase())
+    }
+
+    /// The `owner/repo` a run on this URL counts toward: its upstream for a fork, otherwise its own
+    pub fn project(&self, url: &ParsedAppUrl) -> String {
+        self.upstream(url).unwrap_or_else(|| url.repository())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::services::parsers::AppDetailsParser;
+
+    #[test]
+    fn test_forks_are_credited_to_their_upstream() {
+        let config = AppAttributionConfig::default();
+        let attributor = AppAttributor::new(&config);
+
+        let fork = AppDetailsParser::parse_url_components("https://github.com/lshqqytiger/stable-diffusion-webui-directml").unwrap();
+        assert_eq!(attributor.upstream(&fork).as_deref(), Some("automatic1111/stable-diffusion-webui"));
+        assert_eq!(attributor.project(&fork), "automatic1111/stable-diffusion-webui");
+
+        let upstream = AppDetailsParser::parse_url_components("https://github.com/AUTOMATIC1111/stable-diffusion-webui").unwrap();
+        assert_eq!(attributor.upstream(&upstream), None);
+        assert_eq!(attributor.project(&upstream), "automatic1111/stable-diffusion-webui");
+    }
+
+    #[test]
+    fn test_fork_map_keys_ignore_case() {
+        let config = AppAttributionConfig {
+            fork_map: HashMap::from([("Someone/SDNext-Fork".to_string(), "Vladmandic/Automatic".to_string())]),
+        };
+        let attributor = AppAttributor::new(&config);
+
+        let fork = AppDetailsParser::parse_url_components("https://github.com/someone/sdnext-fork.git").unwrap();
+        assert_eq!(attributor.project(&fork), "vladmandic/automatic");
+    }
+}
@@ -0,0 +1,87 @@
+/// Approximate width of one character of 11px Verdana, the shields.io badge font
+const CHAR_WIDTH: f64 = 6.5;
+/// Horizontal padding on each side of a badge half
+const PADDING: f64 = 5.0;
+
+pub const COLOR_NO_DATA: &str = "#9f9f9f";
+
+/// Median of the samples, `None` when there are none
+pub fn median(values: &[f64]) -> Option<f64> {
+    let mut sorted: Vec<f64> = values.iter().copied().filter(|value| value.is_finite()).collect();
+    if sorted.is_empty() {
+        return None;
+    }
+    sorted.sort_by(|a, b| a.total_cmp(b));
+
+    let middle = sorted.len() / 2;
+    if sorted.len().is_multiple_of(2) {
+        Some((sorted[middle - 1] + sorted[middle]) / 2.0)
+    } else {
+        Some(sorted[middle])
+    }
+}
+
+/// Badge color for a median it/s, red for slow cards through bright green for fast ones
+pub fn its_color(its: f64) -> &'static str {
+    match its {
+        its if its < 5.0 => "#e05d44",
+        its if its < 10.0 => "#fe7d37",
+        its if its < 20.0 => "#dfb317",
+        its if its < 40.0 => "#97ca00",
+        _ => "#4c1",
+    }
+}
+
+/// Render a flat, shields.io-style two-part SVG badge
+pub fn render_badge(label: &str, message: &str, color: &str) -> String {
+    let label_width = text_width(label);
+    let message_width = text_width(message);
+    let total_width = label_width + message_width;
+    let label = escape_xml(label);
+    let message = escape_xml(message);
+
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{total}" height="20" role="img" aria-label="{label}: {message}"><title>{label}: {message}</title><linearGradient id="s" x2="0" y2="100%"><stop offset="0" stop-color="#bbb" stop-opacity=".1"/><stop offset="1" stop-opacity=".1"/></linearGradient><clipPath id="r"><rect width="{total}" height="20" rx="3" fill="#fff"/></clipPath><g clip-path="url(#r)"><rect width="{lw}" height="20" fill="#555"/><rect x="{lw}" width="{mw}" height="20" fill="{color}"/><rect width="{total}" height="20" fill="url(#s)"/></g><g fill="#fff" text-anchor="middle" font-family="Verdana,Geneva,DejaVu Sans,sans-serif" font-size="11"><text x="{lx}" y="15" fill="#010101" fill-opacity=".3">{label}</text><text x="{lx}" y="14">{label}</text><text x="{mx}" y="15" fill="#010101" fill-opacity=".3">{message}</text><text x="{mx}" y="14">{message}</text></g></svg>"##,
+        total = total_width,
+        lw = label_width,
+        mw = message_width,
+        lx = label_width / 2.0,
+        mx = label_width + message_width / 2.0,
+        color = escape_xml(color),
+    )
+}
+
+fn text_width(text: &str) -> f64 {
+    (text.chars().count() as f64 * CHAR_WIDTH + 2.0 * PADDING).round()
+}
+
+pub(crate) fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_median() {
+        assert_eq!(median(&[]), None);
+        assert_eq!(median(&[3.0, 1.0, 2.0]), Some(2.0));
+        assert_eq!(median(&[4.0, 1.0, 3.0, 2.0]), Some(2.5));
+        assert_eq!(median(&[f64::NAN, 5.0]), Some(5.0));
+    }
+
+    #[test]
+    fn test_render_badge_escapes_text() {
+        let svg = render_badge("<RTX & \"4090\">", "20.00 it/s", "#4c1");
+
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains("&lt;RTX &amp; &quot;4090&quot;&gt;"));
+        assert!(svg.contains("20.00 it/s"));
+        assert!(!svg.contains("<RTX"));
+    }
+}
@@ -0,0 +1,66 @@
+/// Datacenter GPU names, matched as whole words in the lowercased device name
+pub const DATACENTER_GPU_PATTERNS: &[&str] = &[
+    "tesla", "a100", "a800", "h100", "h200", "h800", "gh200", "b200", "nvidia a10", "a10g", "a30", "a40",
+    "nvidia l4", "l40", "l40s", "t4", "v100", "p100", "k80", "instinct", "mi100", "mi210", "mi250", "mi250x", "mi300x",
+];
+
+/// Driver strings of the vGPU drivers cloud instances ship with
+pub const CLOUD_DRIVER_HINTS: &[&str] = &["grid"];
+
+/// Markers in the release of the cloud vendors' kernels, e.g. "5.15.0-1040-aws" or "5.10.184-175.731.amzn2.x86_64"
+pub const CLOUD_KERNEL_HINTS: &[&str] = &["aws", "amzn", "azure", "gcp", "gke", "oracle"];
+
+/// Whether the device names a datacenter GPU per `DATACENTER_GPU_PATTERNS`
+pub fn is_datacenter_gpu(device: &str) -> bool {
+    let device = device.to_lowercase();
+
+    DATACENTER_GPU_PATTERNS.iter().any(|pattern| contains_word(&device, pattern))
+}
+
+/// The first cloud hint found in the driver or the kernel release, if any
+pub fn cloud_hint(driver: Option<&str>, kernel_release: Option<&str>) -> Option<&'static str> {
+    let driver = driver.unwrap_or_default().to_lowercase();
+    let kernel_release = kernel_release.unwrap_or_default().to_lowercase();
+
+    CLOUD_DRIVER_HINTS
+        .iter()
+        .find(|hint| contains_word(&driver, hint))
+        .or_else(|| CLOUD_KERNEL_HINTS.iter().find(|hint| kernel_release.contains(*hint)))
+        .copied()
+}
+
+/// Whether `pattern` occurs in `text` with no letter or digit on either side
+fn contains_word(text: &str, pattern: &str) -> bool {
+    text.match_indices(pattern).any(|(start, _)| {
+        let end = start + pattern.len();
+        let before = text[..start].chars().next_back();
+        let after = text[end..].chars().next();
+        !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_datacenter_gpu() {
+        assert!(is_datacenter_gpu("Tesla T4"));
+        assert!(is_datacenter_gpu("NVIDIA A100-SXM4-80GB"));
+        assert!(is_datacenter_gpu("NVIDIA H100 80GB HBM3"));
+        assert!(is_datacenter_gpu("NVIDIA A10G"));
+        assert!(is_datacenter_gpu("NVIDIA L4"));
+        assert!(!is_datacenter_gpu("NVIDIA GeForce RTX 4090"));
+        assert!(!is_datacenter_gpu("NVIDIA RTX A4000"));
+        assert!(!is_datacenter_gpu("AMD A10-7850K Radeon R7"));
+    }
+
+    #[test]
+    fn test_cloud_hint() {
+        assert_eq!(cloud_hint(None, Some("5.15.0-1040-aws")), Some("aws"));
+        assert_eq!(cloud_hint(None, Some("5.10.184-175.731.amzn2.x86_64")), Some("amzn"));
+        assert_eq!(cloud_hint(Some("535.129.03 GRID"), Some("6.5.0-1017-gcp")), Some("grid"));
+        assert_eq!(cloud_hint(Some("535.129.03"), Some("6.5.0-35-generic")), None);
+        assert_eq!(cloud_hint(None, None), None);
+    }
+}
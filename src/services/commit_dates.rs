@@ -0,0 +1,89 @@
+use std::time::Duration;
+
+use reqwest::{header, StatusCode};
+use serde::Deserialize;
+use tokio::time::Instant;
+
+use crate::{config::settings::CommitDatesConfig, error::types::AppError, models::app_details::AppCommit};
+
+/// Sent with every lookup; GitHub refuses requests without a User-Agent
+const USER_AGENT: &str = concat!("sd-its-benchmark/", env!("CARGO_PKG_VERSION"));
+
+#[derive(Deserialize)]
+struct CommitResponse {
+    commit: CommitDetails,
+}
+
+#[derive(Deserialize)]
+struct CommitDetails {
+    committer: CommitSignature,
+}
+
+#[derive(Deserialize)]
+struct CommitSignature {
+    date: String,
+}
+
+/// Looks up the dates of commits through the GitHub REST API, no faster than the configured rate
+///
+/// The committer date is used rather than the author date, as it is when the
+/// commit landed on the branch users pulled.
+pub struct CommitDateClient {
+    client: reqwest::Client,
+    api_url: String,
+    token: Option<String>,
+    interval: Duration,
+    next_request: Option<Instant>,
+}
+
+impl CommitDateClient {
+    pub fn new(config: &CommitDatesConfig) -> Result<Self, AppError> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout_seconds))
+            .user_agent(USER_AGENT)
+            .build()
+            .map_err(|e| AppError::internal(format!("Failed to build GitHub client: {}", e)))?;
+
+        Ok(Self {
+            client,
+            api_url: config.api_url.trim_end_matches('/').to_string(),
+            token: config.token.clone().filter(|token| !token.trim().is_empty()),
+            interval: Duration::from_secs(60) / config.requests_per_minute.max(1),
+            next_request: None,
+        })
+    }
+
+    /// The date of `commit`, `None` when GitHub does not know the repository or commit
+    ///
+    /// Waits until the configured rate allows another request. Any answer other
+    /// than the commit or a 404/422 is an error, e.g. GitHub's rate limit.
+    pub async fn commit_date(&mut self, commit: &AppCommit) -> Result<Option<String>, AppError> {
+        if let Some(next_request) = self.next_request {
+            tokio::time::sleep_until(next_request).await;
+        }
+        self.next_request = Some(Instant::now() + self.interval);
+
+        let url = format!("{}/repos/{}/{}/commits/{}", self.api_url, commit.owner, commit.repo, commit.hash);
+        let mut request = self.client.get(&url).header(header::ACCEPT, "application/vnd.github+json");
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| AppError::internal(format!("Lookup of {} failed: {}", url, e)))?;
+        match response.status() {
+            status if status.is_success() => {
+                let body: CommitResponse = response
+                    .json()
+                    .await
+                    .map_err(|e| AppError::internal(format!("Lookup of {} returned an unexpected body: {}", url, e)))?;
+                Ok(Some(body.commit.committer.date))
+            }
+            // Unknown repository or commit, and a hash too short to be unambiguous
+            StatusCode::NOT_FOUND | StatusCode::UNPROCESSABLE_ENTITY => Ok(None),
+            status => Err(AppError::internal(format!("Lookup of {} answered {}", url, status))),
+        }
+    }
+}
@@ -0,0 +1,149 @@
+use std::collections::BTreeMap;
+
+use crate::models::system_info::{CpuGroupStats, CpuImpact, CpuItsSample};
+
+/// Runs a CPU group needs before it takes part in the comparison
+pub const MIN_GROUP_RUNS: usize = 3;
+
+/// |t| from which a difference between CPU groups counts as measurable (about 95% confidence)
+pub const SIGNIFICANT_T: f64 = 2.0;
+
+/// CPU vendor, family and generation
+type CpuKey = (Option<String>, Option<String>, Option<i64>);
+
+/// Group a GPU's runs by CPU vendor, family and generation and compare the groups
+///
+/// Runs whose CPU could not be normalized are grouped together but never
+/// compared. Groups are ordered fastest first.
+pub fn summarize_cpu_impact(gpu: &str, samples: &[CpuItsSample]) -> CpuImpact {
+    let mut grouped: BTreeMap<CpuKey, Vec<f64>> = BTreeMap::new();
+    for sample in samples.iter().filter(|sample| sample.avg_its.is_finite()) {
+        grouped
+            .entry((sample.cpu_vendor.clone(), sample.cpu_family.clone(), sample.cpu_generation))
+            .or_default()
+            .push(sample.avg_its);
+    }
+
+    let mut groups: Vec<CpuGroupStats> = grouped
+        .into_iter()
+        .map(|((cpu_vendor, cpu_family, cpu_generation), its)| CpuGroupStats {
+            cpu_vendor,
+            cpu_family,
+            cpu_generation,
+            run_count: its.len(),
+            avg_its: mean(&its),
+            stddev_its: stddev(&its),
+        })
+        .collect();
+    groups.sort_by(|a, b| b.avg_its.total_cmp(&a.avg_its));
+
+    let compared: Vec<&CpuGroupStats> = groups
+        .iter()
+        .filter(|group| group.cpu_vendor.is_some() && group.run_count >= MIN_GROUP_RUNS)
+        .collect();
+
+    let (spread_pct, t_statistic, significant) = match (compared.first(), compared.last()) {
+        (Some(fastest), Some(slowest)) if compared.len() > 1 => {
+            let spread_pct = (slowest.avg_its > 0.0)
+                .then(|| (fastest.avg_its - slowest.avg_its) / slowest.avg_its * 100.0);
+            let t_statistic = welch_t(fastest, slowest);
+            let significant = match t_statistic {
+                Some(t) => t.abs() >= SIGNIFICANT_T,
+                // No variance within either group: any difference is measurable
+                None => fastest.avg_its > slowest.avg_its,
+            };
+            (spread_pct, t_statistic, significant)
+        }
+        _ => (None, None, false),
+    };
+
+    CpuImpact {
+        gpu: gpu.to_string(),
+        run_count: groups.iter().map(|group| group.run_count).sum(),
+        groups,
+        spread_pct,
+        t_statistic,
+        significant,
+    }
+}
+
+fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+/// Sample standard deviation, `None` for a single value
+fn stddev(values: &[f64]) -> Option<f64> {
+    if values.len() < 2 {
+        return None;
+    }
+    let mean = mean(values);
+    let variance = values.iter().map(|value| (value - mean).powi(2)).sum::<f64>() / (values.len() - 1) as f64;
+    Some(variance.sqrt())
+}
+
+/// Welch's t statistic for the difference in mean ITS of two groups
+fn welch_t(a: &CpuGroupStats, b: &CpuGroupStats) -> Option<f64> {
+    let standard_error = (a.stddev_its?.powi(2) / a.run_count as f64 + b.stddev_its?.powi(2) / b.run_count as f64).sqrt();
+    (standard_error > 0.0).then(|| (a.avg_its - b.avg_its) / standard_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(vendor: &str, family: &str, generation: i64, avg_its: f64) -> CpuItsSample {
+        CpuItsSample {
+            cpu_vendor: Some(vendor.to_string()),
+            cpu_family: Some(family.to_string()),
+            cpu_generation: Some(generation),
+            avg_its,
+        }
+    }
+
+    #[test]
+    fn test_cpu_impact_detects_measurable_difference() {
+        let samples = vec![
+            sample("Intel", "i9", 13, 20.0),
+            sample("Intel", "i9", 13, 20.5),
+            sample("Intel", "i9", 13, 19.5),
+            sample("Intel", "i5", 8, 15.0),
+            sample("Intel", "i5", 8, 15.5),
+            sample("Intel", "i5", 8, 14.5),
+            sample("AMD", "Ryzen 5", 3, 30.0),
+        ];
+
+        let impact = summarize_cpu_impact("RTX 4090", &samples);
+        assert_eq!(impact.run_count, 7);
+        assert_eq!(impact.groups.len(), 3);
+        // The single Ryzen run is listed but too small to compare
+        assert_eq!(impact.groups[0].cpu_family.as_deref(), Some("Ryzen 5"));
+        assert!(impact.significant);
+        assert!((impact.spread_pct.unwrap() - 33.333).abs() < 0.01);
+        assert!(impact.t_statistic.unwrap() > SIGNIFICANT_T);
+    }
+
+    #[test]
+    fn test_cpu_impact_with_overlapping_groups() {
+        let samples = vec![
+            sample("Intel", "i7", 10, 10.0),
+            sample("Intel", "i7", 10, 14.0),
+            sample("Intel", "i7", 10, 12.0),
+            sample("AMD", "Ryzen 7", 5, 11.0),
+            sample("AMD", "Ryzen 7", 5, 13.5),
+            sample("AMD", "Ryzen 7", 5, 12.0),
+        ];
+
+        let impact = summarize_cpu_impact("RTX 3060", &samples);
+        assert!(!impact.significant);
+        assert!(impact.t_statistic.unwrap().abs() < SIGNIFICANT_T);
+    }
+
+    #[test]
+    fn test_cpu_impact_without_comparable_groups() {
+        let impact = summarize_cpu_impact("RTX 3060", &[sample("Intel", "i7", 10, 10.0)]);
+        assert_eq!(impact.groups.len(), 1);
+        assert_eq!(impact.groups[0].stddev_its, None);
+        assert_eq!(impact.spread_pct, None);
+        assert!(!impact.significant);
+    }
+}
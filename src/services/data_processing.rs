@@ -1,13 +1,18 @@
 // Data processing services for admin operations
 pub mod analyze_app_details_service;
+pub mod compute_run_scores_service;
 pub mod fix_app_names_service;
+pub mod import_model_map_service;
 pub mod process_app_details_service;
 pub mod process_gpu_service;
 pub mod process_its_service;
 pub mod process_libraries_service;
 pub mod process_run_details_service;
 pub mod process_system_info_service;
+pub mod reparse_service;
 pub mod save_data_service;
+pub mod seed_gpu_base_service;
+pub mod seed_model_map_service;
 pub mod update_gpu_brands_service;
 pub mod update_gpu_laptop_info_service;
 pub mod update_run_more_details_service;
@@ -25,4 +30,9 @@ pub use update_gpu_laptop_info_service::*;
 pub use process_run_details_service::*;
 pub use analyze_app_details_service::*;
 pub use fix_app_names_service::*;
-pub use update_run_more_details_service::*; 
\ No newline at end of file
+pub use update_run_more_details_service::*;
+pub use compute_run_scores_service::*; 
+pub use seed_gpu_base_service::*;
+pub use seed_model_map_service::*;
+pub use import_model_map_service::*;
+pub use reparse_service::*;
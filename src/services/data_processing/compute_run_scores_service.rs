@@ -0,0 +1,262 @@
+use tracing::{error, info};
+
+use crate::{
+    config::settings::ScoringConfig,
+    error::types::AppError,
+    models::run_score::{RunScore, ScoringInput},
+    repositories::{
+        run_score_repository::RunScoreRepository,
+        traits::BulkTransactionRepository,
+    },
+    services::{parsers::GpuInfoParser, stage_timing::{StageTimer, StageTiming}},
+};
+use sqlx::{Sqlite, SqlitePool, Transaction};
+
+#[derive(Debug)]
+pub struct ComputeRunScoresOutput {
+    pub success: bool,
+    pub message: String,
+    pub total_runs: usize,
+    pub scored_rows: usize,
+    pub skipped_rows: usize,
+    pub stage_timings: Vec<StageTiming>,
+}
+
+/// Combines avg_its, VRAM and model base into a single 0-100 score
+pub struct ScoreCalculator {
+    config: ScoringConfig,
+}
+
+impl ScoreCalculator {
+    pub fn new(config: ScoringConfig) -> Self {
+        Self { config }
+    }
+
+    /// Look up the throughput factor for a base model
+    ///
+    /// Heavier base models (e.g. SDXL) produce fewer it/s on the same hardware, so their
+    /// avg_its is multiplied by a larger factor to make runs comparable.
+    pub fn model_factor(&self, base_model: Option<&str>) -> f64 {
+        base_model
+            .map(normalize_base_model_key)
+            .and_then(|key| self.config.model_base_factors.get(&key).copied())
+            .unwrap_or(self.config.default_model_factor)
+    }
+
+    /// Score every input that has an avg_its, normalizing against the best run in the set
+    pub fn score_all(&self, inputs: &[ScoringInput]) -> Vec<RunScore> {
+        let candidates: Vec<(i64, f64, f64, Option<f64>)> = inputs
+            .iter()
+            .filter_map(|input| {
+                let avg_its = input.avg_its.filter(|its| its.is_finite() && *its > 0.0)?;
+                let factor = self.model_factor(input.base_model.as_deref());
+                let vram_gb = input
+                    .device
+                    .as_deref()
+                    .and_then(GpuInfoParser::parse_vram_gb)
+                    .filter(|vram| *vram > 0.0);
+                Some((input.run_id, avg_its * factor, factor, vram_gb))
+            })
+            .collect();
+
+        let max_adjusted = candidates
+            .iter()
+            .map(|(_, adjusted, _, _)| *adjusted)
+            .fold(0.0_f64, f64::max);
+        let max_efficiency = candidates
+            .iter()
+            .filter_map(|(_, adjusted, _, vram)| vram.map(|v| adjusted / v))
+            .fold(0.0_f64, f64::max);
+
+        let total_weight = self.config.its_weight + self.config.vram_weight;
+
+        candidates
+            .into_iter()
+            .map(|(run_id, adjusted, factor, vram_gb)| {
+                let normalized_its = if max_adjusted > 0.0 {
+                    adjusted / max_adjusted * 100.0
+                } else {
+                    0.0
+                };
+
+                // Runs without a known VRAM size fall back to their throughput score
+                let normalized_efficiency = match vram_gb {
+                    Some(vram) if max_efficiency > 0.0 => adjusted / vram / max_efficiency * 100.0,
+                    _ => normalized_its,
+                };
+
+                let score = if total_weight > 0.0 {
+                    (self.config.its_weight * normalized_its
+                        + self.config.vram_weight * normalized_efficiency)
+                        / total_weight
+                } else {
+                    normalized_its
+                };
+
+                RunScore {
+                    id: None,
+                    run_id: Some(run_id),
+                    score: Some(score),
+                    normalized_its: Some(normalized_its),
+                    model_factor: Some(factor),
+                    vram_gb,
+                    created_at: None,
+                    updated_at: None,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Normalize a base model name into a factor lookup key ("SD 1.5" -> "sd15")
+pub fn normalize_base_model_key(base_model: &str) -> String {
+    base_model
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect::<String>()
+        .to_lowercase()
+}
+
+pub struct ComputeRunScoresService {
+    run_score_repository: RunScoreRepository,
+    pool: SqlitePool,
+    calculator: ScoreCalculator,
+}
+
+impl ComputeRunScoresService {
+    pub fn new(
+        run_score_repository: RunScoreRepository,
+        pool: SqlitePool,
+        config: ScoringConfig,
+    ) -> Self {
+        Self {
+            run_score_repository,
+            pool,
+            calculator: ScoreCalculator::new(config),
+        }
+    }
+
+    /// Compute a normalized score for every run
+    ///
+    /// This service:
+    /// 1. Fetches avg_its, mapped base model and GPU device per run
+    /// 2. Applies the configured model factor and VRAM weighting
+    /// 3. Replaces the contents of the RunScore table in a single transaction
+    ///
+    /// # Returns
+    /// * `ComputeRunScoresOutput` - Processing results and statistics
+    pub async fn compute_scores(&self) -> Result<ComputeRunScoresOutput, AppError> {
+        let mut tx = self.pool.begin().await.map_err(|e| {
+            error!("Failed to begin transaction: {}", e);
+            AppError::internal(format!("Failed to begin transaction: {}", e))
+        })?;
+
+        let mut output = self.compute_scores_tx(&mut tx).await?;
+
+        let mut timer = StageTimer::start("commit");
+        timer.db(tx.commit()).await.map_err(|e| {
+            error!("Failed to commit transaction: {}", e);
+            AppError::internal(format!("Failed to commit transaction: {}", e))
+        })?;
+        output.stage_timings.push(timer.finish(output.scored_rows));
+
+        Ok(output)
+    }
+
+    /// `compute_scores` within the caller's transaction, reading the scoring inputs through it
+    pub async fn compute_scores_tx(&self, tx: &mut Transaction<'_, Sqlite>) -> Result<ComputeRunScoresOutput, AppError> {
+        info!("Computing run scores");
+        let mut stage_timings = Vec::new();
+
+        let mut timer = StageTimer::start("fetch_scoring_inputs");
+        let inputs = timer.db(self.run_score_repository.find_scoring_inputs_tx(tx)).await.map_err(|e| {
+            error!("Failed to fetch scoring inputs: {}", e);
+            AppError::internal(format!("Failed to fetch scoring inputs: {}", e))
+        })?;
+        stage_timings.push(timer.finish(inputs.len()));
+
+        let timer = StageTimer::start("score_runs");
+        let total_runs = inputs.len();
+        // Each tenant's runs are normalized against that tenant's best run; inputs come ordered by tenant
+        let scores: Vec<RunScore> = inputs
+            .chunk_by(|a, b| a.tenant_id == b.tenant_id)
+            .flat_map(|tenant_inputs| self.calculator.score_all(tenant_inputs))
+            .collect();
+        let skipped_rows = total_runs - scores.len();
+        stage_timings.push(timer.finish(total_runs));
+
+        info!("Scored {} of {} runs ({} without avg_its)", scores.len(), total_runs, skipped_rows);
+
+        let mut timer = StageTimer::start("replace_run_scores");
+        timer.db(self.run_score_repository.delete_all_tx(tx)).await.map_err(|e| {
+            error!("Failed to clear run scores: {}", e);
+            AppError::internal(format!("Failed to clear run scores: {}", e))
+        })?;
+
+        let inserted = timer.db(self.run_score_repository.bulk_create_tx(scores, tx)).await.map_err(|e| {
+            error!("Failed to insert run scores: {}", e);
+            AppError::internal(format!("Failed to insert run scores: {}", e))
+        })?;
+        stage_timings.push(timer.finish(inserted.len()));
+
+        Ok(ComputeRunScoresOutput {
+            success: true,
+            message: "Run scores computed successfully".to_string(),
+            total_runs,
+            scored_rows: inserted.len(),
+            skipped_rows,
+            stage_timings,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(run_id: i64, avg_its: Option<f64>, base_model: Option<&str>, device: Option<&str>) -> ScoringInput {
+        ScoringInput {
+            run_id,
+            tenant_id: "default".to_string(),
+            avg_its,
+            base_model: base_model.map(|s| s.to_string()),
+            device: device.map(|s| s.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_model_factor_lookup() {
+        let calculator = ScoreCalculator::new(ScoringConfig::default());
+        assert_eq!(calculator.model_factor(Some("SDXL")), 2.5);
+        assert_eq!(calculator.model_factor(Some("SD 1.5")), 1.0);
+        assert_eq!(calculator.model_factor(Some("unknown-model")), 1.0);
+        assert_eq!(calculator.model_factor(None), 1.0);
+    }
+
+    #[test]
+    fn test_score_all_normalizes_across_models() {
+        let calculator = ScoreCalculator::new(ScoringConfig::default());
+        let scores = calculator.score_all(&[
+            input(1, Some(10.0), Some("sd15"), None),
+            input(2, Some(4.0), Some("sdxl"), None),
+            input(3, None, Some("sd15"), None),
+        ]);
+
+        assert_eq!(scores.len(), 2);
+        // SDXL run: 4.0 * 2.5 = 10.0 which ties with the SD 1.5 run
+        assert_eq!(scores[0].score, Some(100.0));
+        assert_eq!(scores[1].score, Some(100.0));
+    }
+
+    #[test]
+    fn test_score_all_rewards_vram_efficiency() {
+        let calculator = ScoreCalculator::new(ScoringConfig::default());
+        let scores = calculator.score_all(&[
+            input(1, Some(10.0), None, Some("NVIDIA GeForce RTX 3080 10GB")),
+            input(2, Some(10.0), None, Some("NVIDIA GeForce RTX 3090 24GB")),
+        ]);
+
+        assert_eq!(scores[0].vram_gb, Some(10.0));
+        assert!(scores[0].score.unwrap() > scores[1].score.unwrap());
+    }
+}
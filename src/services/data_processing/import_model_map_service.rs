@@ -0,0 +1,216 @@
+use std::collections::HashMap;
+
+use sqlx::SqlitePool;
+use tracing::{error, info};
+
+use crate::{
+    error::types::AppError,
+    models::model_map::{
+        ModelMap, ModelMapImportAction, ModelMapImportChange, ModelMapImportError, ModelMapImportReport,
+    },
+    repositories::{
+        model_map_repository::ModelMapRepository,
+        traits::TransactionRepository,
+    },
+};
+
+/// A CSV row that passed validation
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelMapImportRow {
+    pub line: u64,
+    pub model_name: String,
+    pub base_model: String,
+}
+
+/// Parse a model_name,base_model CSV, collecting blank and duplicate rows as errors
+///
+/// Fails outright only when the CSV is unreadable or the header lacks a required column.
+pub fn parse_model_map_csv(csv_text: &str) -> Result<(Vec<ModelMapImportRow>, Vec<ModelMapImportError>), AppError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .flexible(true)
+        .from_reader(csv_text.as_bytes());
+
+    let headers = reader
+        .headers()
+        .map_err(|e| AppError::validation(format!("Failed to read CSV header: {}", e)))?
+        .clone();
+    let column = |name: &str| {
+        headers
+            .iter()
+            .position(|header| header.eq_ignore_ascii_case(name))
+            .ok_or_else(|| AppError::validation(format!("CSV header must include a {} column", name)))
+    };
+    let model_name_column = column("model_name")?;
+    let base_model_column = column("base_model")?;
+
+    let mut rows = Vec::new();
+    let mut errors = Vec::new();
+    let mut seen: HashMap<String, u64> = HashMap::new();
+    for record in reader.records() {
+        let record = record.map_err(|e| AppError::validation(format!("Failed to read CSV: {}", e)))?;
+        let line = record.position().map_or(0, |position| position.line());
+        let model_name = record.get(model_name_column).unwrap_or_default();
+        let base_model = record.get(base_model_column).unwrap_or_default();
+
+        if model_name.is_empty() || base_model.is_empty() {
+            errors.push(ModelMapImportError {
+                line,
+                message: "model_name and base_model must not be blank".to_string(),
+            });
+            continue;
+        }
+        if let Some(first_line) = seen.get(model_name) {
+            errors.push(ModelMapImportError {
+                line,
+                message: format!("Duplicate model_name {} (first seen on line {})", model_name, first_line),
+            });
+            continue;
+        }
+
+        seen.insert(model_name.to_string(), line);
+        rows.push(ModelMapImportRow {
+            line,
+            model_name: model_name.to_string(),
+            base_model: base_model.to_string(),
+        });
+    }
+
+    Ok((rows, errors))
+}
+
+pub struct ImportModelMapService {
+    model_map_repository: ModelMapRepository,
+    pool: SqlitePool,
+}
+
+impl ImportModelMapService {
+    pub fn new(model_map_repository: ModelMapRepository, pool: SqlitePool) -> Self {
+        Self {
+            model_map_repository,
+            pool,
+        }
+    }
+
+    /// Diff a ModelMap CSV against the current table and optionally apply it
+    ///
+    /// This service:
+    /// 1. Parses and validates the CSV (blank values, duplicate model names)
+    /// 2. Classifies each valid row as new, updated or unchanged, matched by model_name
+    /// 3. When `apply` is set and no row failed validation, writes the new and updated rows
+    ///
+    /// # Returns
+    /// * `ModelMapImportReport` - The diff, validation errors and whether it was applied
+    pub async fn import_model_map(&self, csv_text: &str, apply: bool) -> Result<ModelMapImportReport, AppError> {
+        let (rows, errors) = parse_model_map_csv(csv_text)?;
+        let total_rows = rows.len() + errors.len();
+        info!("Importing {} ModelMap rows ({} invalid, apply={})", total_rows, errors.len(), apply);
+
+        // Reads and writes share a transaction so the diff matches what gets applied
+        let mut tx = self.pool.begin().await.map_err(|e| {
+            error!("Failed to begin transaction: {}", e);
+            AppError::internal(format!("Failed to begin transaction: {}", e))
+        })?;
+
+        let apply = apply && errors.is_empty();
+        let mut changes = Vec::with_capacity(rows.len());
+        for row in rows {
+            let existing = self.model_map_repository
+                .find_single_by_model_name_tx(&row.model_name, &mut tx)
+                .await
+                .map_err(|e| {
+                    error!("Failed to look up ModelMap {}: {}", row.model_name, e);
+                    AppError::internal(format!("Failed to look up ModelMap {}: {}", row.model_name, e))
+                })?;
+
+            let action = match &existing {
+                None => ModelMapImportAction::New,
+                Some(model_map) if model_map.base_model.as_deref() == Some(row.base_model.as_str()) => {
+                    ModelMapImportAction::Unchanged
+                }
+                Some(_) => ModelMapImportAction::Updated,
+            };
+
+            if apply && action != ModelMapImportAction::Unchanged {
+                let model_map = ModelMap {
+                    id: existing.as_ref().and_then(|model_map| model_map.id),
+                    model_name: Some(row.model_name.clone()),
+                    base_model: Some(row.base_model.clone()),
+                    version: existing.as_ref().map_or(1, |model_map| model_map.version),
+                    created_at: None,
+                    updated_at: None,
+                };
+                if existing.is_some() {
+                    self.model_map_repository.update_tx(model_map, &mut tx).await
+                } else {
+                    self.model_map_repository.create_tx(model_map, &mut tx).await
+                }
+                .map_err(|e| {
+                    error!("Failed to import ModelMap {}: {}", row.model_name, e);
+                    AppError::internal(format!("Failed to import ModelMap {}: {}", row.model_name, e))
+                })?;
+            }
+
+            changes.push(ModelMapImportChange {
+                line: row.line,
+                action,
+                model_name: row.model_name,
+                base_model: row.base_model,
+                previous_base_model: existing.and_then(|model_map| model_map.base_model),
+            });
+        }
+
+        if apply {
+            tx.commit().await.map_err(|e| {
+                error!("Failed to commit transaction: {}", e);
+                AppError::internal(format!("Failed to commit transaction: {}", e))
+            })?;
+        }
+
+        let count = |action| changes.iter().filter(|change| change.action == action).count();
+        let report = ModelMapImportReport {
+            applied: apply,
+            total_rows,
+            new_rows: count(ModelMapImportAction::New),
+            updated_rows: count(ModelMapImportAction::Updated),
+            unchanged_rows: count(ModelMapImportAction::Unchanged),
+            changes,
+            errors,
+        };
+
+        info!(
+            "ModelMap import {}: {} new, {} updated, {} unchanged, {} invalid",
+            if report.applied { "applied" } else { "previewed" },
+            report.new_rows,
+            report.updated_rows,
+            report.unchanged_rows,
+            report.errors.len()
+        );
+
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_model_map_csv_reports_blank_and_duplicate_rows() {
+        let csv_text = "model_name,base_model\nsd_xl_base_1.0,SDXL\n,SD 1.5\nsd_xl_base_1.0, SDXL 1.0\nv1-5-pruned-emaonly , SD 1.5\n";
+        let (rows, errors) = parse_model_map_csv(csv_text).unwrap();
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1].model_name, "v1-5-pruned-emaonly");
+        assert_eq!(rows[1].base_model, "SD 1.5");
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].line, 3);
+        assert_eq!(errors[1].line, 4);
+        assert!(errors[1].message.contains("line 2"));
+    }
+
+    #[test]
+    fn test_parse_model_map_csv_requires_columns() {
+        assert!(parse_model_map_csv("model_name\nsd_xl_base_1.0\n").is_err());
+    }
+}
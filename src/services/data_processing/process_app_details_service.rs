@@ -2,13 +2,14 @@ use tracing::{error, info, warn};
 
 use crate::{
     error::types::AppError,
-    models::app_details::AppDetails,
+    models::{app_details::AppDetails, app_release::AppRelease},
     repositories::{
         app_details_repository::AppDetailsRepository,
+        app_release_repository::AppReleaseRepository,
         runs_repository::RunsRepository,
-        traits::{Repository, BulkTransactionRepository},
+        traits::BulkTransactionRepository,
     },
-    services::parsers::AppDetailsParser,
+    services::parsers::{AppDetailsParser, APP_DETAILS_PARSER_VERSION},
 };
 use sqlx::SqlitePool;
 
@@ -25,6 +26,7 @@ pub struct ProcessAppDetailsOutput {
 pub struct ProcessAppDetailsService {
     runs_repository: RunsRepository,
     app_details_repository: AppDetailsRepository,
+    app_release_repository: AppReleaseRepository,
     pool: SqlitePool,
 }
 
@@ -37,6 +39,7 @@ impl ProcessAppDetailsService {
         Self {
             runs_repository,
             app_details_repository,
+            app_release_repository: AppReleaseRepository::new(pool.clone()),
             pool,
         }
     }
@@ -47,7 +50,8 @@ impl ProcessAppDetailsService {
     /// 1. Clears existing app details
     /// 2. Fetches all runs data
     /// 3. Parses app details from info strings using AppDetailsParser
-    /// 4. Inserts app details into the database
+    /// 4. Derives the release channel and release month of each app
+    /// 5. Inserts app details and app releases into the database
     /// 
     /// # Returns
     /// * `ProcessAppDetailsOutput` - Processing results and statistics
@@ -55,7 +59,7 @@ impl ProcessAppDetailsService {
         info!("Processing app details from runs table with transaction support");
 
         // Fetch all runs data
-        let runs = self.runs_repository.find_all().await.map_err(|e| {
+        let runs = self.runs_repository.find_all_app_infos().await.map_err(|e| {
             error!("Failed to fetch runs data: {}", e);
             AppError::internal(format!("Failed to fetch runs data: {}", e))
         })?;
@@ -96,7 +100,7 @@ impl ProcessAppDetailsService {
     }
 
     /// Execute transaction with bulk operations
-    async fn execute_transaction_with_bulk_operations(&self, runs: Vec<crate::models::runs::Run>) -> Result<Vec<AppDetails>, AppError> {
+    async fn execute_transaction_with_bulk_operations(&self, runs: Vec<crate::models::runs::RunAppInfo>) -> Result<Vec<AppDetails>, AppError> {
         let mut tx = self.pool.begin().await
             .map_err(|e| {
                 error!("Failed to begin transaction: {}", e);
@@ -112,10 +116,16 @@ impl ProcessAppDetailsService {
             })?;
         info!("Cleared {} existing app details", deleted_count);
 
+        self.app_release_repository.delete_all_tx(&mut tx).await
+            .map_err(|e| {
+                error!("Failed to clear app releases: {}", e);
+                AppError::internal(format!("Failed to clear app releases: {}", e))
+            })?;
+
         // Process all runs and create app details
         let mut app_details = Vec::new();
         for (index, run) in runs.iter().enumerate() {
-            match self.process_run_for_bulk(run, index) {
+            match Self::process_run_for_bulk(run) {
                 Ok(app_detail) => {
                     app_details.push(app_detail);
                     if index % 100 == 0 {
@@ -129,6 +139,8 @@ impl ProcessAppDetailsService {
             }
         }
 
+        let app_releases: Vec<AppRelease> = app_details.iter().map(Self::derive_app_release).collect();
+
         // Bulk insert all app details
         info!("Bulk inserting {} app details", app_details.len());
         let inserted_results = self.app_details_repository.bulk_create_tx(app_details, &mut tx).await
@@ -137,6 +149,12 @@ impl ProcessAppDetailsService {
                 AppError::internal(format!("Failed to bulk insert app details: {}", e))
             })?;
 
+        self.app_release_repository.bulk_create_tx(app_releases, &mut tx).await
+            .map_err(|e| {
+                error!("Failed to bulk insert app releases: {}", e);
+                AppError::internal(format!("Failed to bulk insert app releases: {}", e))
+            })?;
+
         // Commit transaction
         tx.commit().await
             .map_err(|e| {
@@ -149,11 +167,8 @@ impl ProcessAppDetailsService {
     }
 
     /// Process a single run and create app details (for bulk processing)
-    fn process_run_for_bulk(&self, run: &crate::models::runs::Run, index: usize) -> Result<AppDetails, AppError> {
-        let run_id = run.id.ok_or_else(|| {
-            error!("Run at index {} has no ID", index);
-            AppError::bad_request("Invalid run data".to_string())
-        })?;
+    pub(crate) fn process_run_for_bulk(run: &crate::models::runs::RunAppInfo) -> Result<AppDetails, AppError> {
+        let run_id = run.id;
 
         let info = run.info.as_ref().ok_or_else(|| {
             error!("Run {} has no info", run_id);
@@ -171,10 +186,25 @@ impl ProcessAppDetailsService {
             updated: app_details.updated,
             hash: app_details.hash,
             url: app_details.url,
+            parser_version: Some(APP_DETAILS_PARSER_VERSION),
+            created_at: None,
+            updated_at: None,
         };
 
         Ok(app_details_record)
     }
+
+    /// Derive the release channel (branch) and release month for an app details record
+    pub(crate) fn derive_app_release(app_details: &AppDetails) -> AppRelease {
+        AppRelease {
+            id: None,
+            run_id: app_details.run_id,
+            release_channel: app_details.url.as_deref().and_then(AppDetailsParser::parse_release_channel),
+            release_month: app_details.updated.as_deref().and_then(AppDetailsParser::parse_release_month),
+            created_at: None,
+            updated_at: None,
+        }
+    }
 }
 
 #[cfg(test)]
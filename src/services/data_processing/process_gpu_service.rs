@@ -6,9 +6,9 @@ use crate::{
     repositories::{
         gpu_repository::GpuRepository,
         runs_repository::RunsRepository,
-        traits::{Repository, BulkTransactionRepository},
+        traits::BulkTransactionRepository,
     },
-    services::parsers::GpuInfoParser,
+    services::parsers::{GpuInfoParser, GPU_INFO_PARSER_VERSION},
 };
 use sqlx::SqlitePool;
 
@@ -55,7 +55,7 @@ impl ProcessGpuService {
         info!("Processing GPU info from runs table with transaction support");
 
         // Fetch all runs data
-        let runs = self.runs_repository.find_all().await.map_err(|e| {
+        let runs = self.runs_repository.find_all_device_infos().await.map_err(|e| {
             error!("Failed to fetch runs data: {}", e);
             AppError::internal(format!("Failed to fetch runs data: {}", e))
         })?;
@@ -96,7 +96,7 @@ impl ProcessGpuService {
     }
 
     /// Execute transaction with bulk operations
-    async fn execute_transaction_with_bulk_operations(&self, runs: Vec<crate::models::runs::Run>) -> Result<Vec<Gpu>, AppError> {
+    async fn execute_transaction_with_bulk_operations(&self, runs: Vec<crate::models::runs::RunDeviceInfo>) -> Result<Vec<Gpu>, AppError> {
         let mut tx = self.pool.begin().await
             .map_err(|e| {
                 error!("Failed to begin transaction: {}", e);
@@ -115,9 +115,9 @@ impl ProcessGpuService {
         // Process all runs and create GPU records
         let mut gpu_records = Vec::new();
         for (index, run) in runs.iter().enumerate() {
-            match self.process_run_for_bulk(run, index) {
-                Ok(gpu) => {
-                    gpu_records.push(gpu);
+            match Self::process_run_for_bulk(run) {
+                Ok(gpus) => {
+                    gpu_records.extend(gpus);
                     if index % 100 == 0 {
                         info!("Processed {} runs", index + 1);
                     }
@@ -148,12 +148,9 @@ impl ProcessGpuService {
         Ok(inserted_results)
     }
 
-    /// Process a single run and create GPU record (for bulk processing)
-    fn process_run_for_bulk(&self, run: &crate::models::runs::Run, index: usize) -> Result<Gpu, AppError> {
-        let run_id = run.id.ok_or_else(|| {
-            error!("Run at index {} has no ID", index);
-            AppError::bad_request("Invalid run data".to_string())
-        })?;
+    /// Process a single run and create one GPU record per reported GPU (for bulk processing)
+    pub(crate) fn process_run_for_bulk(run: &crate::models::runs::RunDeviceInfo) -> Result<Vec<Gpu>, AppError> {
+        let run_id = run.id;
 
         let device_info = run.device_info.as_ref().ok_or_else(|| {
             error!("Run {} has no device_info", run_id);
@@ -161,20 +158,27 @@ impl ProcessGpuService {
         })?;
 
         // Parse device info to extract GPU information using our parser
-        let parsed_gpu_info = GpuInfoParser::parse(device_info);
-
-        // Create GPU record
-        let gpu_record = Gpu {
-            id: None,
-            run_id: Some(run_id),
-            device: parsed_gpu_info.device,
-            driver: parsed_gpu_info.driver,
-            gpu_chip: parsed_gpu_info.gpu_chip,
-            brand: None, // Will be populated by separate update process
-            is_laptop: None, // Will be populated by separate update process
-        };
-
-        Ok(gpu_record)
+        let gpu_records = GpuInfoParser::parse_all(device_info)
+            .into_iter()
+            .enumerate()
+            .map(|(gpu_index, parsed_gpu_info)| Gpu {
+                id: None,
+                run_id: Some(run_id),
+                device: parsed_gpu_info.device,
+                driver: parsed_gpu_info.driver,
+                gpu_chip: parsed_gpu_info.gpu_chip,
+                brand: None, // Will be populated by separate update process
+                is_laptop: None, // Will be populated by separate update process
+                is_integrated: None, // Will be populated by separate update process
+                is_cloud: None, // Will be populated by separate update process
+                gpu_index: gpu_index as i64,
+                parser_version: Some(GPU_INFO_PARSER_VERSION),
+                created_at: None,
+                updated_at: None,
+            })
+            .collect();
+
+        Ok(gpu_records)
     }
 }
 
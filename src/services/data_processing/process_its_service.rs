@@ -6,9 +6,9 @@ use crate::{
     repositories::{
         performance_result_repository::PerformanceResultRepository,
         runs_repository::RunsRepository,
-        traits::{Repository, BulkTransactionRepository},
+        traits::BulkTransactionRepository,
     },
-    services::parsers::PerformanceParser,
+    services::parsers::{PerformanceParser, VramUsageKind},
 };
 use sqlx::SqlitePool;
 
@@ -56,7 +56,7 @@ impl ProcessItsService {
         info!("Processing ITS data from runs table with transaction support");
 
         // Fetch all runs data
-        let runs = self.runs_repository.find_all().await.map_err(|e| {
+        let runs = self.runs_repository.find_all_its_inputs().await.map_err(|e| {
             error!("Failed to fetch runs data: {}", e);
             AppError::internal(format!("Failed to fetch runs data: {}", e))
         })?;
@@ -97,7 +97,7 @@ impl ProcessItsService {
     }
 
     /// Execute transaction with bulk operations
-    async fn execute_transaction_with_bulk_operations(&self, runs: Vec<crate::models::runs::Run>) -> Result<Vec<PerformanceResult>, AppError> {
+    async fn execute_transaction_with_bulk_operations(&self, runs: Vec<crate::models::runs::RunItsInput>) -> Result<Vec<PerformanceResult>, AppError> {
         let mut tx = self.pool.begin().await
             .map_err(|e| {
                 error!("Failed to begin transaction: {}", e);
@@ -116,7 +116,7 @@ impl ProcessItsService {
         // Process all runs and create performance results
         let mut performance_results = Vec::new();
         for (index, run) in runs.iter().enumerate() {
-            match self.process_run_for_bulk(run, index) {
+            match Self::process_run_for_bulk(run) {
                 Ok(performance_result) => {
                     performance_results.push(performance_result);
                     if index % 100 == 0 {
@@ -150,11 +150,8 @@ impl ProcessItsService {
     }
 
     /// Process a single run and create performance result (for bulk processing)
-    fn process_run_for_bulk(&self, run: &crate::models::runs::Run, index: usize) -> Result<PerformanceResult, AppError> {
-        let run_id = run.id.ok_or_else(|| {
-            error!("Run at index {} has no ID", index);
-            AppError::bad_request("Invalid run data".to_string())
-        })?;
+    pub(crate) fn process_run_for_bulk(run: &crate::models::runs::RunItsInput) -> Result<PerformanceResult, AppError> {
+        let run_id = run.id;
 
         let vram_usage = run.vram_usage.as_ref().ok_or_else(|| {
             error!("Run {} has no vram_usage", run_id);
@@ -169,12 +166,19 @@ impl ProcessItsService {
             warn!("Invalid performance data for run {}: {}", run_id, vram_usage);
         }
 
+        // Memory readings uploaded in place of ITS samples are kept out of ITS statistics
+        let vram_usage_kind = PerformanceParser::classify(vram_usage);
+        let avg_its = performance_data.avg_its.filter(|_| vram_usage_kind == VramUsageKind::Its);
+
         // Create performance result
         let performance_result = PerformanceResult {
             id: None,
             run_id: Some(run_id),
             its: Some(vram_usage.clone()),
-            avg_its: performance_data.avg_its,
+            avg_its,
+            vram_usage_kind: Some(vram_usage_kind.as_str().to_string()),
+            created_at: None,
+            updated_at: None,
         };
 
         Ok(performance_result)
@@ -6,9 +6,9 @@ use crate::{
     repositories::{
         libraries_repository::LibrariesRepository,
         runs_repository::RunsRepository,
-        traits::{Repository, BulkTransactionRepository},
+        traits::BulkTransactionRepository,
     },
-    services::parsers::LibrariesParser,
+    services::parsers::{LibrariesParser, LIBRARIES_PARSER_VERSION},
 };
 use sqlx::SqlitePool;
 
@@ -55,7 +55,7 @@ impl ProcessLibrariesService {
         info!("Processing libraries from runs table with transaction support");
 
         // Fetch all runs data
-        let runs = self.runs_repository.find_all().await.map_err(|e| {
+        let runs = self.runs_repository.find_all_libraries_inputs().await.map_err(|e| {
             error!("Failed to fetch runs data: {}", e);
             AppError::internal(format!("Failed to fetch runs data: {}", e))
         })?;
@@ -96,7 +96,7 @@ impl ProcessLibrariesService {
     }
 
     /// Execute transaction with bulk operations
-    async fn execute_transaction_with_bulk_operations(&self, runs: Vec<crate::models::runs::Run>) -> Result<Vec<Libraries>, AppError> {
+    async fn execute_transaction_with_bulk_operations(&self, runs: Vec<crate::models::runs::RunLibrariesInput>) -> Result<Vec<Libraries>, AppError> {
         let mut tx = self.pool.begin().await
             .map_err(|e| {
                 error!("Failed to begin transaction: {}", e);
@@ -115,7 +115,7 @@ impl ProcessLibrariesService {
         // Process all runs and create libraries
         let mut libraries_records = Vec::new();
         for (index, run) in runs.iter().enumerate() {
-            match self.process_run_for_bulk(run, index) {
+            match Self::process_run_for_bulk(run) {
                 Ok(libraries) => {
                     libraries_records.push(libraries);
                     if index % 100 == 0 {
@@ -149,11 +149,8 @@ impl ProcessLibrariesService {
     }
 
     /// Process a single run and create libraries record (for bulk processing)
-    fn process_run_for_bulk(&self, run: &crate::models::runs::Run, index: usize) -> Result<Libraries, AppError> {
-        let run_id = run.id.ok_or_else(|| {
-            error!("Run at index {} has no ID", index);
-            AppError::bad_request("Invalid run data".to_string())
-        })?;
+    pub(crate) fn process_run_for_bulk(run: &crate::models::runs::RunLibrariesInput) -> Result<Libraries, AppError> {
+        let run_id = run.id;
 
         let model_info = run.model_info.as_ref().ok_or_else(|| {
             error!("Run {} has no model_info", run_id);
@@ -177,6 +174,9 @@ impl ProcessLibrariesService {
             xformers1: Some(xformers.clone()), // Copy xformers value from runs table
             diffusers: parsed_libraries.diffusers,
             transformers: parsed_libraries.transformers,
+            parser_version: Some(LIBRARIES_PARSER_VERSION),
+            created_at: None,
+            updated_at: None,
         };
 
         Ok(libraries_record)
@@ -2,11 +2,11 @@ use tracing::{error, info, warn};
 
 use crate::{
     error::types::AppError,
-    models::{run_more_details::RunMoreDetails, runs::Run},
+    models::{run_more_details::RunMoreDetails, runs::RunDetailsInput},
     repositories::{
         run_more_details_repository::RunMoreDetailsRepository,
         runs_repository::RunsRepository,
-        traits::{Repository, BulkTransactionRepository},
+        traits::BulkTransactionRepository,
     },
 };
 use sqlx::SqlitePool;
@@ -51,7 +51,7 @@ impl ProcessRunDetailsService {
         info!("Processing run details from runs table to RunMoreDetails table with transaction support");
 
         // Fetch all runs data
-        let runs_data = self.runs_repository.find_all().await.map_err(|e| {
+        let runs_data = self.runs_repository.find_all_details_inputs().await.map_err(|e| {
             error!("Failed to fetch runs data: {}", e);
             AppError::internal(format!("Failed to fetch runs data: {}", e))
         })?;
@@ -94,7 +94,7 @@ impl ProcessRunDetailsService {
     }
 
     /// Execute transaction with bulk operations
-    async fn execute_transaction_with_bulk_operations(&self, runs: Vec<Run>) -> Result<Vec<RunMoreDetails>, AppError> {
+    async fn execute_transaction_with_bulk_operations(&self, runs: Vec<RunDetailsInput>) -> Result<Vec<RunMoreDetails>, AppError> {
         let mut tx = self.pool.begin().await
             .map_err(|e| {
                 error!("Failed to begin transaction: {}", e);
@@ -113,12 +113,12 @@ impl ProcessRunDetailsService {
         // Process all runs and create run more details
         let mut run_more_details = Vec::new();
         for run in &runs {
-            match self.process_run_for_bulk(run) {
+            match Self::process_run_for_bulk(run) {
                 Ok(run_detail) => {
                     run_more_details.push(run_detail);
                 }
                 Err(e) => {
-                    warn!("Failed to process run {}: {}", run.id.unwrap_or(0), e);
+                    warn!("Failed to process run {}: {}", run.id, e);
                     // Continue processing other runs
                 }
             }
@@ -144,11 +144,8 @@ impl ProcessRunDetailsService {
     }
 
     /// Process a single run and insert into RunMoreDetails (for bulk processing)
-    fn process_run_for_bulk(&self, run: &Run) -> Result<RunMoreDetails, AppError> {
-        let run_id = run.id.ok_or_else(|| {
-            error!("Run has no ID");
-            AppError::bad_request("Invalid run data".to_string())
-        })?;
+    pub(crate) fn process_run_for_bulk(run: &RunDetailsInput) -> Result<RunMoreDetails, AppError> {
+        let run_id = run.id;
 
         // Create RunMoreDetails record
         let run_more_details = RunMoreDetails {
@@ -159,6 +156,8 @@ impl ProcessRunDetailsService {
             user: run.user.clone(),
             notes: run.notes.clone(),
             model_map_id: None, // Will be populated by a later service
+            created_at: None,
+            updated_at: None,
         };
 
         Ok(run_more_details)
@@ -6,9 +6,9 @@ use crate::{
     repositories::{
         runs_repository::RunsRepository,
         system_info_repository::SystemInfoRepository,
-        traits::{Repository, BulkTransactionRepository},
+        traits::BulkTransactionRepository,
     },
-    services::parsers::SystemInfoParser,
+    services::parsers::{SystemInfoParser, SYSTEM_INFO_PARSER_VERSION},
 };
 use sqlx::SqlitePool;
 
@@ -55,7 +55,7 @@ impl ProcessSystemInfoService {
         info!("Processing system info from runs table with transaction support");
 
         // Fetch all runs data
-        let runs = self.runs_repository.find_all().await.map_err(|e| {
+        let runs = self.runs_repository.find_all_system_infos().await.map_err(|e| {
             error!("Failed to fetch runs data: {}", e);
             AppError::internal(format!("Failed to fetch runs data: {}", e))
         })?;
@@ -96,7 +96,7 @@ impl ProcessSystemInfoService {
     }
 
     /// Execute transaction with bulk operations
-    async fn execute_transaction_with_bulk_operations(&self, runs: Vec<crate::models::runs::Run>) -> Result<Vec<SystemInfo>, AppError> {
+    async fn execute_transaction_with_bulk_operations(&self, runs: Vec<crate::models::runs::RunSystemInfo>) -> Result<Vec<SystemInfo>, AppError> {
         let mut tx = self.pool.begin().await
             .map_err(|e| {
                 error!("Failed to begin transaction: {}", e);
@@ -115,7 +115,7 @@ impl ProcessSystemInfoService {
         // Process all runs and create system info
         let mut system_info_records = Vec::new();
         for (index, run) in runs.iter().enumerate() {
-            match self.process_run_for_bulk(run, index) {
+            match Self::process_run_for_bulk(run) {
                 Ok(Some(system_info)) => {
                     system_info_records.push(system_info);
                     if index % 100 == 0 {
@@ -156,11 +156,8 @@ impl ProcessSystemInfoService {
 
     /// Process a single run and create system info (for bulk processing)
     /// Returns Some(SystemInfo) if valid, None if skipped due to missing fields
-    fn process_run_for_bulk(&self, run: &crate::models::runs::Run, index: usize) -> Result<Option<SystemInfo>, AppError> {
-        let run_id = run.id.ok_or_else(|| {
-            error!("Run at index {} has no ID", index);
-            AppError::bad_request("Invalid run data".to_string())
-        })?;
+    pub(crate) fn process_run_for_bulk(run: &crate::models::runs::RunSystemInfo) -> Result<Option<SystemInfo>, AppError> {
+        let run_id = run.id;
 
         let system_info = run.system_info.as_ref().ok_or_else(|| {
             error!("Run {} has no system_info", run_id);
@@ -186,6 +183,12 @@ impl ProcessSystemInfoService {
                 system: parsed_system_info.system,
                 release: parsed_system_info.release,
                 python: parsed_system_info.python,
+                ram_gb: parsed_system_info.ram_gb,
+                swap_gb: parsed_system_info.swap_gb,
+                os_bits: parsed_system_info.os_bits,
+                parser_version: Some(SYSTEM_INFO_PARSER_VERSION),
+                created_at: None,
+                updated_at: None,
             };
 
             Ok(Some(system_info_record))
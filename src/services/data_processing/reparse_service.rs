@@ -0,0 +1,301 @@
+use sqlx::{Sqlite, SqlitePool, Transaction};
+use tracing::{error, info, warn};
+
+use crate::{
+    error::types::AppError,
+    handlers::common::DRY_RUN_SAMPLE_SIZE,
+    models::{
+        reparse::{RawStringFilter, ReparsePreview, ReparseResult, ReparseStage, StepPreview},
+        runs::{Run, RunAppInfo, RunDetailsInput, RunDeviceInfo, RunItsInput, RunLibrariesInput, RunSystemInfo},
+    },
+    repositories::{
+        app_details_repository::AppDetailsRepository, app_release_repository::AppReleaseRepository,
+        gpu_repository::GpuRepository, libraries_repository::LibrariesRepository,
+        performance_result_repository::PerformanceResultRepository,
+        run_more_details_repository::RunMoreDetailsRepository, runs_repository::RunsRepository,
+        system_info_repository::SystemInfoRepository,
+        traits::{BulkTransactionRepository, Repository},
+    },
+    services::data_processing::{
+        ProcessAppDetailsService, ProcessGpuService, ProcessItsService, ProcessLibrariesService,
+        ProcessRunDetailsService, ProcessSystemInfoService,
+    },
+};
+
+/// Runs the parsing steps' current parsers on single runs, or on the runs matching a pattern
+pub struct ReparseService {
+    pool: SqlitePool,
+}
+
+impl ReparseService {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Preview what each parsing step would write for a run, beside what it holds now
+    ///
+    /// Every step converts the run exactly as its processing stage does, so a
+    /// preview shows the effect of a parser change before the pipeline reruns.
+    pub async fn preview(&self, run_id: i64) -> Result<ReparsePreview, AppError> {
+        let run = RunsRepository::new(self.pool.clone())
+            .find_by_id(run_id)
+            .await
+            .map_err(|e| database_error(run_id, e))?
+            .ok_or_else(|| AppError::not_found(format!("Run with id {}", run_id)))?;
+        let run_ids = [run_id];
+
+        let mut performance = PerformanceResultRepository::new(self.pool.clone())
+            .find_by_run_ids(&run_ids)
+            .await
+            .map_err(|e| database_error(run_id, e))?;
+        let mut app_details = AppDetailsRepository::new(self.pool.clone())
+            .find_by_run_ids(&run_ids)
+            .await
+            .map_err(|e| database_error(run_id, e))?;
+        let mut app_release = AppReleaseRepository::new(self.pool.clone())
+            .find_by_run_ids(&run_ids)
+            .await
+            .map_err(|e| database_error(run_id, e))?;
+        let mut system_info = SystemInfoRepository::new(self.pool.clone())
+            .find_by_run_ids(&run_ids)
+            .await
+            .map_err(|e| database_error(run_id, e))?;
+        let mut libraries = LibrariesRepository::new(self.pool.clone())
+            .find_by_run_ids(&run_ids)
+            .await
+            .map_err(|e| database_error(run_id, e))?;
+        let mut gpus = GpuRepository::new(self.pool.clone())
+            .find_by_run_ids(&run_ids)
+            .await
+            .map_err(|e| database_error(run_id, e))?;
+        let mut run_details = RunMoreDetailsRepository::new(self.pool.clone())
+            .find_by_run_ids(&run_ids)
+            .await
+            .map_err(|e| database_error(run_id, e))?;
+
+        let app_details = step(
+            app_details.remove(&run_id),
+            ProcessAppDetailsService::process_run_for_bulk(&RunAppInfo {
+                id: run_id,
+                info: run.info.clone(),
+            })
+            .map(Some),
+        );
+        // Derived from the app details in the same step, so skipped along with them
+        let app_release = StepPreview {
+            current: app_release.remove(&run_id),
+            reparsed: app_details.reparsed.as_ref().map(ProcessAppDetailsService::derive_app_release),
+            skipped: app_details.skipped.clone(),
+        };
+
+        let preview = ReparsePreview {
+            run_id,
+            performance: step(
+                performance.remove(&run_id),
+                ProcessItsService::process_run_for_bulk(&RunItsInput {
+                    id: run_id,
+                    vram_usage: run.vram_usage.clone(),
+                })
+                .map(Some),
+            ),
+            app_details,
+            app_release,
+            system_info: step(
+                system_info.remove(&run_id),
+                ProcessSystemInfoService::process_run_for_bulk(&RunSystemInfo {
+                    id: run_id,
+                    system_info: run.system_info.clone(),
+                })
+                .and_then(|row| row.map(Some).ok_or_else(|| AppError::bad_request("Missing required system info fields"))),
+            ),
+            libraries: step(
+                libraries.remove(&run_id),
+                ProcessLibrariesService::process_run_for_bulk(&RunLibrariesInput {
+                    id: run_id,
+                    model_info: run.model_info.clone(),
+                    xformers: run.xformers.clone(),
+                })
+                .map(Some),
+            ),
+            gpus: step(
+                gpus.remove(&run_id).unwrap_or_default(),
+                ProcessGpuService::process_run_for_bulk(&RunDeviceInfo {
+                    id: run_id,
+                    device_info: run.device_info.clone(),
+                }),
+            ),
+            run_details: step(
+                run_details.remove(&run_id),
+                ProcessRunDetailsService::process_run_for_bulk(&RunDetailsInput {
+                    id: run_id,
+                    timestamp: run.timestamp.clone(),
+                    model_name: run.model_name.clone(),
+                    user: run.user.clone(),
+                    notes: run.notes.clone(),
+                })
+                .map(Some),
+            ),
+        };
+        info!("Previewed reparsing run {}", run_id);
+
+        Ok(preview)
+    }
+
+    /// Rerun `stage` on the runs whose raw string matches `filter`
+    ///
+    /// The stage's rows for the matched runs are deleted and rebuilt by its
+    /// current parser in `tx`; every other run keeps its rows, so a parser fix
+    /// for a family of strings needs no full-table rebuild. The result does not
+    /// depend on `dry_run`, which is only reported back; the caller rolls back.
+    /// GPU brand and laptop flags, normalized CPU fields and app URL projects
+    /// come from later update steps, so rerun those after reparsing `gpu`,
+    /// `system_info` or `app_details`.
+    pub async fn reparse(
+        &self,
+        stage: ReparseStage,
+        filter: &RawStringFilter,
+        dry_run: bool,
+        tx: &mut Transaction<'_, Sqlite>,
+    ) -> Result<ReparseResult, AppError> {
+        let runs_repo = RunsRepository::new(self.pool.clone());
+        let runs = runs_repo
+            .find_by_source_match_tx(stage.source_column(), filter, tx)
+            .await
+            .map_err(|e| {
+                error!("Failed to find runs to reparse: {}", e);
+                AppError::Database(e)
+            })?;
+        let run_ids: Vec<i64> = runs.iter().filter_map(|run| run.id).collect();
+
+        let rows_deleted = runs_repo
+            .delete_derived_tx(stage.derived_tables(), &run_ids, tx)
+            .await
+            .map_err(|e| {
+                error!("Failed to clear {:?} rows of the runs to reparse: {}", stage, e);
+                AppError::Database(e)
+            })?;
+
+        let (rows_inserted, skipped_runs) = match stage {
+            ReparseStage::Its => {
+                let (rows, skipped) = derive_rows(&runs, |run, id| {
+                    ProcessItsService::process_run_for_bulk(&RunItsInput { id, vram_usage: run.vram_usage.clone() })
+                        .map(Some)
+                });
+                (insert_rows(&PerformanceResultRepository::new(self.pool.clone()), rows, tx).await?, skipped)
+            }
+            ReparseStage::AppDetails => {
+                let (rows, skipped) = derive_rows(&runs, |run, id| {
+                    ProcessAppDetailsService::process_run_for_bulk(&RunAppInfo { id, info: run.info.clone() }).map(Some)
+                });
+                let releases = rows.iter().map(ProcessAppDetailsService::derive_app_release).collect();
+                let inserted = insert_rows(&AppDetailsRepository::new(self.pool.clone()), rows, tx).await?
+                    + insert_rows(&AppReleaseRepository::new(self.pool.clone()), releases, tx).await?;
+                (inserted, skipped)
+            }
+            ReparseStage::SystemInfo => {
+                let (rows, skipped) = derive_rows(&runs, |run, id| {
+                    ProcessSystemInfoService::process_run_for_bulk(&RunSystemInfo {
+                        id,
+                        system_info: run.system_info.clone(),
+                    })
+                });
+                (insert_rows(&SystemInfoRepository::new(self.pool.clone()), rows, tx).await?, skipped)
+            }
+            ReparseStage::Libraries => {
+                let (rows, skipped) = derive_rows(&runs, |run, id| {
+                    ProcessLibrariesService::process_run_for_bulk(&RunLibrariesInput {
+                        id,
+                        model_info: run.model_info.clone(),
+                        xformers: run.xformers.clone(),
+                    })
+                    .map(Some)
+                });
+                (insert_rows(&LibrariesRepository::new(self.pool.clone()), rows, tx).await?, skipped)
+            }
+            ReparseStage::Gpu => {
+                let (rows, skipped) = derive_rows(&runs, |run, id| {
+                    ProcessGpuService::process_run_for_bulk(&RunDeviceInfo { id, device_info: run.device_info.clone() })
+                        .map(Some)
+                });
+                let rows = rows.into_iter().flatten().collect();
+                (insert_rows(&GpuRepository::new(self.pool.clone()), rows, tx).await?, skipped)
+            }
+        };
+        info!(
+            "Reparsed {:?} for {} runs matching {:?}: {} rows deleted, {} inserted, {} runs skipped",
+            stage,
+            run_ids.len(),
+            filter,
+            rows_deleted,
+            rows_inserted,
+            skipped_runs
+        );
+        let (where_like, where_regex) = match filter {
+            RawStringFilter::Like(pattern) => (Some(pattern.clone()), None),
+            RawStringFilter::Regex(pattern) => (None, Some(pattern.clone())),
+        };
+
+        Ok(ReparseResult {
+            stage,
+            where_like,
+            where_regex,
+            dry_run,
+            matched_runs: run_ids.len(),
+            rows_deleted,
+            rows_inserted,
+            skipped_runs,
+            sample_run_ids: run_ids.into_iter().take(DRY_RUN_SAMPLE_SIZE).collect(),
+        })
+    }
+}
+
+/// Parse every run with a step's converter, returning its rows and the number of runs it rejected
+fn derive_rows<T>(runs: &[Run], parse: impl Fn(&Run, i64) -> Result<Option<T>, AppError>) -> (Vec<T>, usize) {
+    let mut rows = Vec::new();
+    let mut skipped = 0;
+    for run in runs {
+        let Some(id) = run.id else { continue };
+        match parse(run, id) {
+            Ok(Some(row)) => rows.push(row),
+            Ok(None) => skipped += 1,
+            Err(e) => {
+                warn!("Failed to reparse run {}: {}", id, e);
+                skipped += 1;
+            }
+        }
+    }
+
+    (rows, skipped)
+}
+
+async fn insert_rows<'a, T, R>(repo: &R, rows: Vec<T>, tx: &mut Transaction<'a, Sqlite>) -> Result<usize, AppError>
+where
+    R: BulkTransactionRepository<'a, T, i64>,
+{
+    let inserted = repo.bulk_create_tx(rows, tx).await.map_err(|e| {
+        error!("Failed to insert reparsed rows: {}", e);
+        AppError::Database(e)
+    })?;
+
+    Ok(inserted.len())
+}
+
+/// Pair a step's current rows with its reparsed ones; a step that fails writes nothing
+fn step<T: Default>(current: T, reparsed: Result<T, AppError>) -> StepPreview<T> {
+    match reparsed {
+        Ok(reparsed) => StepPreview { current, reparsed, skipped: None },
+        Err(e) => StepPreview { current, reparsed: T::default(), skipped: Some(skipped_reason(e)) },
+    }
+}
+
+fn skipped_reason(e: AppError) -> String {
+    match e {
+        AppError::BadRequest(message) => message,
+        e => e.to_string(),
+    }
+}
+
+fn database_error(run_id: i64, e: sqlx::Error) -> AppError {
+    error!("Failed to fetch run {} for a reparse preview: {}", run_id, e);
+    AppError::Database(e)
+}
@@ -1,14 +1,16 @@
 use serde::{Deserialize, Serialize};
-use tracing::{error, info};
+use sqlx::{Acquire, Sqlite, Transaction};
+use tracing::{error, info, warn};
 
 use crate::{
     error::types::AppError,
     models::runs::Run,
     repositories::{
         runs_repository::RunsRepository,
-        traits::{BulkTransactionRepository},
+        traits::{BulkTransactionRepository, TransactionRepository},
     },
     handlers::validation::RunData,
+    services::ingest::parse_benchmark_export,
 };
 use sqlx::SqlitePool;
 
@@ -22,6 +24,16 @@ pub struct SaveDataOutput {
     pub error_data: Vec<String>,
 }
 
+/// Number of runs inserted per bulk insert
+pub const DEFAULT_CHUNK_SIZE: usize = 500;
+
+/// Result of inserting runs chunk by chunk
+#[derive(Debug, Default)]
+pub struct ChunkedInsertResult {
+    pub inserted_runs: Vec<Run>,
+    pub error_data: Vec<String>,
+}
+
 pub struct SaveDataService {
     runs_repository: RunsRepository,
     pool: SqlitePool,
@@ -38,47 +50,36 @@ impl SaveDataService {
     pub async fn save_data(&self, file_content: Vec<u8>) -> Result<SaveDataOutput, AppError> {
         info!("Starting save data processing with transaction support");
 
-        // Parse JSON data from file content
-        let data: Vec<RunData> = serde_json::from_slice(&file_content)
+        // Parse JSON data from file content in any supported benchmark format
+        let (format_name, data) = parse_benchmark_export(&file_content)
             .map_err(|e| {
-                error!("Failed to parse JSON data: {}", e);
-                AppError::bad_request(format!("Invalid JSON format: {}", e))
+                error!("Failed to parse benchmark data: {}", e);
+                e
             })?;
 
         let total_rows = data.len();
-        info!("Parsed {} rows from JSON data", total_rows);
+        info!("Parsed {} rows from {} data", total_rows, format_name);
 
         // Convert RunData to Run models
-        let runs: Vec<Run> = data.into_iter().map(|row| Run {
-            id: None, // Will be set by database
-            timestamp: Some(row.timestamp),
-            vram_usage: Some(row.vram_usage),
-            info: Some(row.info),
-            system_info: Some(row.system_info),
-            model_info: Some(row.model_info),
-            device_info: Some(row.device_info),
-            xformers: Some(row.xformers),
-            model_name: Some(row.model_name),
-            user: Some(row.user),
-            notes: Some(row.notes),
-        }).collect();
+        let runs = runs_from_data(data);
 
         // Process data using direct transaction management
-        let result = self.execute_transaction_with_bulk_operations(runs).await;
+        let result = self.execute_transaction_with_bulk_operations(runs, DEFAULT_CHUNK_SIZE).await;
 
         match result {
-            Ok(inserted_runs) => {
-                let inserted_rows = inserted_runs.len();
-                info!("Save data processing completed successfully. Total: {}, Inserted: {}", 
-                      total_rows, inserted_rows);
+            Ok(insert_result) => {
+                let inserted_rows = insert_result.inserted_runs.len();
+                let error_rows = insert_result.error_data.len();
+                info!("Save data processing completed successfully. Total: {}, Inserted: {}, Errors: {}", 
+                      total_rows, inserted_rows, error_rows);
 
                 Ok(SaveDataOutput {
                     success: true,
                     message: "Data processed successfully with transaction support".to_string(),
                     total_rows,
                     inserted_rows,
-                    error_rows: 0, // No individual row errors with bulk operations
-                    error_data: vec![], // No individual row errors with bulk operations
+                    error_rows,
+                    error_data: insert_result.error_data,
                 })
             }
             Err(e) => {
@@ -106,47 +107,36 @@ impl SaveDataService {
     {
         info!("Starting save data processing with custom progress tracking");
 
-        // Parse JSON data from file content
-        let data: Vec<RunData> = serde_json::from_slice(&file_content)
+        // Parse JSON data from file content in any supported benchmark format
+        let (format_name, data) = parse_benchmark_export(&file_content)
             .map_err(|e| {
-                error!("Failed to parse JSON data: {}", e);
-                AppError::bad_request(format!("Invalid JSON format: {}", e))
+                error!("Failed to parse benchmark data: {}", e);
+                e
             })?;
 
         let total_rows = data.len();
-        info!("Parsed {} rows from JSON data", total_rows);
+        info!("Parsed {} rows from {} data", total_rows, format_name);
 
         // Convert RunData to Run models
-        let runs: Vec<Run> = data.into_iter().map(|row| Run {
-            id: None, // Will be set by database
-            timestamp: Some(row.timestamp),
-            vram_usage: Some(row.vram_usage),
-            info: Some(row.info),
-            system_info: Some(row.system_info),
-            model_info: Some(row.model_info),
-            device_info: Some(row.device_info),
-            xformers: Some(row.xformers),
-            model_name: Some(row.model_name),
-            user: Some(row.user),
-            notes: Some(row.notes),
-        }).collect();
+        let runs = runs_from_data(data);
 
         // Process data using direct transaction management
-        let result = self.execute_transaction_with_bulk_operations(runs).await;
+        let result = self.execute_transaction_with_bulk_operations(runs, DEFAULT_CHUNK_SIZE).await;
 
         match result {
-            Ok(inserted_runs) => {
-                let inserted_rows = inserted_runs.len();
-                info!("Save data processing completed successfully. Total: {}, Inserted: {}", 
-                      total_rows, inserted_rows);
+            Ok(insert_result) => {
+                let inserted_rows = insert_result.inserted_runs.len();
+                let error_rows = insert_result.error_data.len();
+                info!("Save data processing completed successfully. Total: {}, Inserted: {}, Errors: {}", 
+                      total_rows, inserted_rows, error_rows);
 
                 Ok(SaveDataOutput {
                     success: true,
                     message: "Data processed successfully with transaction support".to_string(),
                     total_rows,
                     inserted_rows,
-                    error_rows: 0, // No individual row errors with bulk operations
-                    error_data: vec![], // No individual row errors with bulk operations
+                    error_rows,
+                    error_data: insert_result.error_data,
                 })
             }
             Err(e) => {
@@ -171,47 +161,36 @@ impl SaveDataService {
     ) -> Result<SaveDataOutput, AppError> {
         info!("Starting save data processing in batches of {}", batch_size);
 
-        // Parse JSON data from file content
-        let data: Vec<RunData> = serde_json::from_slice(&file_content)
+        // Parse JSON data from file content in any supported benchmark format
+        let (format_name, data) = parse_benchmark_export(&file_content)
             .map_err(|e| {
-                error!("Failed to parse JSON data: {}", e);
-                AppError::bad_request(format!("Invalid JSON format: {}", e))
+                error!("Failed to parse benchmark data: {}", e);
+                e
             })?;
 
         let total_rows = data.len();
-        info!("Parsed {} rows from JSON data", total_rows);
+        info!("Parsed {} rows from {} data", total_rows, format_name);
 
         // Convert RunData to Run models
-        let runs: Vec<Run> = data.into_iter().map(|row| Run {
-            id: None, // Will be set by database
-            timestamp: Some(row.timestamp),
-            vram_usage: Some(row.vram_usage),
-            info: Some(row.info),
-            system_info: Some(row.system_info),
-            model_info: Some(row.model_info),
-            device_info: Some(row.device_info),
-            xformers: Some(row.xformers),
-            model_name: Some(row.model_name),
-            user: Some(row.user),
-            notes: Some(row.notes),
-        }).collect();
+        let runs = runs_from_data(data);
 
         // Process data using direct transaction management
-        let result = self.execute_transaction_with_bulk_operations(runs).await;
+        let result = self.execute_transaction_with_bulk_operations(runs, batch_size).await;
 
         match result {
-            Ok(inserted_runs) => {
-                let inserted_rows = inserted_runs.len();
-                info!("Save data processing completed successfully. Total: {}, Inserted: {}", 
-                      total_rows, inserted_rows);
+            Ok(insert_result) => {
+                let inserted_rows = insert_result.inserted_runs.len();
+                let error_rows = insert_result.error_data.len();
+                info!("Save data processing completed successfully. Total: {}, Inserted: {}, Errors: {}", 
+                      total_rows, inserted_rows, error_rows);
 
                 Ok(SaveDataOutput {
                     success: true,
                     message: format!("Data processed successfully in batches of {}", batch_size),
                     total_rows,
                     inserted_rows,
-                    error_rows: 0, // No individual row errors with bulk operations
-                    error_data: vec![], // No individual row errors with bulk operations
+                    error_rows,
+                    error_data: insert_result.error_data,
                 })
             }
             Err(e) => {
@@ -228,8 +207,8 @@ impl SaveDataService {
         }
     }
 
-    /// Execute transaction with bulk operations
-    async fn execute_transaction_with_bulk_operations(&self, runs: Vec<Run>) -> Result<Vec<Run>, AppError> {
+    /// Execute transaction with chunked bulk operations
+    async fn execute_transaction_with_bulk_operations(&self, runs: Vec<Run>, chunk_size: usize) -> Result<ChunkedInsertResult, AppError> {
         let mut tx = self.pool.begin().await
             .map_err(|e| {
                 error!("Failed to begin transaction: {}", e);
@@ -245,13 +224,7 @@ impl SaveDataService {
             })?;
         info!("Cleared {} existing runs", deleted_count);
 
-        // Bulk insert all runs
-        info!("Bulk inserting {} runs", runs.len());
-        let inserted_runs = self.runs_repository.bulk_create_tx(runs, &mut tx).await
-            .map_err(|e| {
-                error!("Failed to bulk insert runs: {}", e);
-                AppError::internal(format!("Failed to bulk insert runs: {}", e))
-            })?;
+        let insert_result = self.insert_runs_in_chunks_tx(runs, chunk_size, &mut tx).await?;
 
         // Commit transaction
         tx.commit().await
@@ -260,7 +233,87 @@ impl SaveDataService {
                 AppError::internal(format!("Failed to commit transaction: {}", e))
             })?;
 
-        info!("Successfully inserted {} runs", inserted_runs.len());
-        Ok(inserted_runs)
+        info!("Successfully inserted {} runs", insert_result.inserted_runs.len());
+        Ok(insert_result)
+    }
+
+    /// Insert runs in chunks through `bulk_create_tx`
+    /// 
+    /// This service:
+    /// 1. Splits the runs into chunks of `chunk_size`
+    /// 2. Bulk inserts each chunk inside its own savepoint
+    /// 3. If a chunk fails, rolls back its savepoint and retries only that chunk row by row
+    /// 4. Collects an error message for every row that still fails
+    /// 
+    /// # Returns
+    /// * `ChunkedInsertResult` - The inserted runs and per-row error messages
+    pub async fn insert_runs_in_chunks_tx(
+        &self,
+        runs: Vec<Run>,
+        chunk_size: usize,
+        tx: &mut Transaction<'_, Sqlite>,
+    ) -> Result<ChunkedInsertResult, AppError> {
+        let chunk_size = chunk_size.max(1);
+        let mut result = ChunkedInsertResult::default();
+
+        info!("Bulk inserting {} runs in chunks of {}", runs.len(), chunk_size);
+
+        for (chunk_index, chunk) in runs.chunks(chunk_size).enumerate() {
+            let first_row = chunk_index * chunk_size;
+
+            let mut savepoint = tx.begin().await.map_err(|e| {
+                error!("Failed to begin savepoint for chunk {}: {}", chunk_index + 1, e);
+                AppError::internal(format!("Failed to begin savepoint: {}", e))
+            })?;
+
+            match self.runs_repository.bulk_create_tx(chunk.to_vec(), &mut savepoint).await {
+                Ok(inserted_runs) => {
+                    savepoint.commit().await.map_err(|e| {
+                        error!("Failed to release savepoint for chunk {}: {}", chunk_index + 1, e);
+                        AppError::internal(format!("Failed to release savepoint: {}", e))
+                    })?;
+                    result.inserted_runs.extend(inserted_runs);
+                }
+                Err(e) => {
+                    warn!("Bulk insert of chunk {} failed, retrying row by row: {}", chunk_index + 1, e);
+                    savepoint.rollback().await.map_err(|e| {
+                        error!("Failed to roll back savepoint for chunk {}: {}", chunk_index + 1, e);
+                        AppError::internal(format!("Failed to roll back savepoint: {}", e))
+                    })?;
+
+                    for (offset, run) in chunk.iter().enumerate() {
+                        let row_number = first_row + offset + 1;
+                        match self.runs_repository.create_tx(run.clone(), tx).await {
+                            Ok(inserted_run) => result.inserted_runs.push(inserted_run),
+                            Err(e) => {
+                                warn!("Failed to insert row {}: {}", row_number, e);
+                                result.error_data.push(format!("Row {}: {}", row_number, e));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(result)
     }
-} 
\ No newline at end of file
+}
+
+/// Convert uploaded rows into `Run` models ready for insertion
+pub fn runs_from_data(data: Vec<RunData>) -> Vec<Run> {
+    data.into_iter().map(|row| Run {
+        id: None, // Will be set by database
+        timestamp: Some(row.timestamp),
+        vram_usage: Some(row.vram_usage),
+        info: Some(row.info),
+        system_info: Some(row.system_info),
+        model_info: Some(row.model_info),
+        device_info: Some(row.device_info),
+        xformers: Some(row.xformers),
+        model_name: Some(row.model_name),
+        user: Some(row.user),
+        notes: Some(row.notes),
+        created_at: None,
+        updated_at: None,
+    }).collect()
+}
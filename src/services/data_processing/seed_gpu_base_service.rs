@@ -0,0 +1,155 @@
+use sqlx::SqlitePool;
+use tracing::{error, info};
+
+use crate::{
+    error::types::AppError,
+    models::{
+        gpu_base::GpuBase,
+        gpu_spec::{GpuCatalogEntry, GpuSpec},
+    },
+    repositories::{
+        gpu_base_repository::GpuBaseRepository,
+        gpu_spec_repository::GpuSpecRepository,
+        traits::TransactionRepository,
+    },
+};
+
+/// Reference catalog of known GPUs, embedded in the binary
+const GPU_CATALOG_JSON: &str = include_str!("../../../data/gpu_catalog.json");
+
+/// Parse the bundled GPU catalog
+pub fn gpu_catalog() -> Result<Vec<GpuCatalogEntry>, AppError> {
+    serde_json::from_str(GPU_CATALOG_JSON).map_err(|e| {
+        error!("Failed to parse bundled GPU catalog: {}", e);
+        AppError::internal(format!("Failed to parse bundled GPU catalog: {}", e))
+    })
+}
+
+#[derive(Debug)]
+pub struct SeedGpuBaseOutput {
+    pub success: bool,
+    pub message: String,
+    pub total_entries: usize,
+    pub inserted_rows: usize,
+    pub updated_rows: usize,
+}
+
+pub struct SeedGpuBaseService {
+    gpu_base_repository: GpuBaseRepository,
+    gpu_spec_repository: GpuSpecRepository,
+    pool: SqlitePool,
+}
+
+impl SeedGpuBaseService {
+    pub fn new(gpu_base_repository: GpuBaseRepository, pool: SqlitePool) -> Self {
+        Self {
+            gpu_base_repository,
+            gpu_spec_repository: GpuSpecRepository::new(pool.clone()),
+            pool,
+        }
+    }
+
+    /// Seed GPUBase from the bundled GPU catalog
+    ///
+    /// This service:
+    /// 1. Parses the embedded GPU catalog
+    /// 2. Inserts catalog GPUs missing from GPUBase, matched by name
+    /// 3. Updates the brand, architecture and generation of GPUs that already exist
+    /// 4. Upserts VRAM and launch year into GPUSpec
+    ///
+    /// Existing GPUBase ids are kept, so GPUMap and gpu_prices stay valid.
+    ///
+    /// # Returns
+    /// * `SeedGpuBaseOutput` - Seeding results and statistics
+    pub async fn seed_gpu_base(&self) -> Result<SeedGpuBaseOutput, AppError> {
+        let catalog = gpu_catalog()?;
+        let total_entries = catalog.len();
+        info!("Seeding GPUBase from {} catalog entries", total_entries);
+
+        let mut tx = self.pool.begin().await.map_err(|e| {
+            error!("Failed to begin transaction: {}", e);
+            AppError::internal(format!("Failed to begin transaction: {}", e))
+        })?;
+
+        let mut inserted_rows = 0;
+        let mut updated_rows = 0;
+        for entry in catalog {
+            let existing = self.gpu_base_repository.find_by_name_tx(&entry.name, &mut tx).await.map_err(|e| {
+                error!("Failed to look up GPUBase {}: {}", entry.name, e);
+                AppError::internal(format!("Failed to look up GPUBase {}: {}", entry.name, e))
+            })?;
+
+            let gpu_base = GpuBase {
+                id: existing.as_ref().and_then(|gpu_base| gpu_base.id),
+                name: entry.name.clone(),
+                brand: Some(entry.brand.clone()),
+                architecture: Some(entry.architecture.clone()),
+                generation: Some(entry.generation.clone()),
+                version: existing.as_ref().map_or(1, |gpu_base| gpu_base.version),
+                created_at: None,
+                updated_at: None,
+            };
+            let gpu_base = if existing.is_some() {
+                updated_rows += 1;
+                self.gpu_base_repository.update_tx(gpu_base, &mut tx).await
+            } else {
+                inserted_rows += 1;
+                self.gpu_base_repository.create_tx(gpu_base, &mut tx).await
+            }
+            .map_err(|e| {
+                error!("Failed to upsert GPUBase {}: {}", entry.name, e);
+                AppError::internal(format!("Failed to upsert GPUBase {}: {}", entry.name, e))
+            })?;
+
+            let spec = GpuSpec {
+                id: None,
+                base_gpu_id: gpu_base.id,
+                vram_gb: Some(entry.vram_gb),
+                launch_year: Some(entry.launch_year),
+                created_at: None,
+                updated_at: None,
+            };
+            self.gpu_spec_repository.upsert_tx(spec, &mut tx).await.map_err(|e| {
+                error!("Failed to upsert GPUSpec for {}: {}", entry.name, e);
+                AppError::internal(format!("Failed to upsert GPUSpec for {}: {}", entry.name, e))
+            })?;
+        }
+
+        tx.commit().await.map_err(|e| {
+            error!("Failed to commit transaction: {}", e);
+            AppError::internal(format!("Failed to commit transaction: {}", e))
+        })?;
+
+        info!("GPUBase seeding complete: {} inserted, {} updated", inserted_rows, updated_rows);
+
+        Ok(SeedGpuBaseOutput {
+            success: true,
+            message: "GPUBase seeded from the bundled GPU catalog".to_string(),
+            total_entries,
+            inserted_rows,
+            updated_rows,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_bundled_gpu_catalog_is_valid() {
+        let catalog = gpu_catalog().expect("bundled catalog should parse");
+        assert!(!catalog.is_empty());
+
+        let names: HashSet<&str> = catalog.iter().map(|entry| entry.name.as_str()).collect();
+        assert_eq!(names.len(), catalog.len(), "catalog names must be unique");
+
+        for entry in &catalog {
+            assert!(["nvidia", "amd", "intel"].contains(&entry.brand.as_str()), "unknown brand for {}", entry.name);
+            assert!(!entry.architecture.is_empty() && !entry.generation.is_empty());
+            assert!(entry.vram_gb > 0.0);
+            assert!((2010..=2030).contains(&entry.launch_year));
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use sqlx::SqlitePool;
+use tracing::{error, info};
+
+use crate::{
+    error::types::AppError,
+    models::model_map::{ModelCatalogEntry, ModelMap},
+    repositories::{
+        model_map_repository::ModelMapRepository,
+        traits::TransactionRepository,
+    },
+};
+
+/// Reference catalog of well-known checkpoints and their hashes, embedded in the binary
+const MODEL_CATALOG_JSON: &str = include_str!("../../../data/model_catalog.json");
+
+/// Parse the bundled model catalog
+pub fn model_catalog() -> Result<Vec<ModelCatalogEntry>, AppError> {
+    serde_json::from_str(MODEL_CATALOG_JSON).map_err(|e| {
+        error!("Failed to parse bundled model catalog: {}", e);
+        AppError::internal(format!("Failed to parse bundled model catalog: {}", e))
+    })
+}
+
+#[derive(Debug)]
+pub struct SeedModelMapOutput {
+    pub success: bool,
+    pub message: String,
+    pub total_entries: usize,
+    pub inserted_rows: usize,
+    pub updated_rows: usize,
+    pub seeded_hashes: usize,
+}
+
+pub struct SeedModelMapService {
+    model_map_repository: ModelMapRepository,
+    pool: SqlitePool,
+}
+
+impl SeedModelMapService {
+    pub fn new(model_map_repository: ModelMapRepository, pool: SqlitePool) -> Self {
+        Self {
+            model_map_repository,
+            pool,
+        }
+    }
+
+    /// Seed ModelMap from the bundled model catalog
+    ///
+    /// This service:
+    /// 1. Parses the embedded model catalog
+    /// 2. Inserts catalog checkpoints missing from ModelMap, matched by model_name
+    /// 3. Updates the base model of checkpoints that already exist
+    /// 4. Points every known checkpoint hash at its ModelMap entry
+    ///
+    /// # Returns
+    /// * `SeedModelMapOutput` - Seeding results and statistics
+    pub async fn seed_model_map(&self) -> Result<SeedModelMapOutput, AppError> {
+        let catalog = model_catalog()?;
+        let total_entries = catalog.len();
+        info!("Seeding ModelMap from {} catalog entries", total_entries);
+
+        let mut tx = self.pool.begin().await.map_err(|e| {
+            error!("Failed to begin transaction: {}", e);
+            AppError::internal(format!("Failed to begin transaction: {}", e))
+        })?;
+
+        let mut inserted_rows = 0;
+        let mut updated_rows = 0;
+        let mut seeded_hashes = 0;
+        for entry in catalog {
+            let existing = self.model_map_repository
+                .find_single_by_model_name_tx(&entry.model_name, &mut tx)
+                .await
+                .map_err(|e| {
+                    error!("Failed to look up ModelMap {}: {}", entry.model_name, e);
+                    AppError::internal(format!("Failed to look up ModelMap {}: {}", entry.model_name, e))
+                })?;
+
+            let model_map = ModelMap {
+                id: existing.as_ref().and_then(|model_map| model_map.id),
+                model_name: Some(entry.model_name.clone()),
+                base_model: Some(entry.base_model.clone()),
+                version: existing.as_ref().map_or(1, |model_map| model_map.version),
+                created_at: None,
+                updated_at: None,
+            };
+            let model_map = if existing.is_some() {
+                updated_rows += 1;
+                self.model_map_repository.update_tx(model_map, &mut tx).await
+            } else {
+                inserted_rows += 1;
+                self.model_map_repository.create_tx(model_map, &mut tx).await
+            }
+            .map_err(|e| {
+                error!("Failed to upsert ModelMap {}: {}", entry.model_name, e);
+                AppError::internal(format!("Failed to upsert ModelMap {}: {}", entry.model_name, e))
+            })?;
+
+            let model_map_id = model_map.id.ok_or_else(|| {
+                error!("ModelMap {} has no ID", entry.model_name);
+                AppError::internal(format!("ModelMap {} has no ID", entry.model_name))
+            })?;
+            for hash in &entry.hashes {
+                self.model_map_repository
+                    .upsert_hash_tx(model_map_id, &hash.to_lowercase(), &mut tx)
+                    .await
+                    .map_err(|e| {
+                        error!("Failed to upsert checkpoint hash {}: {}", hash, e);
+                        AppError::internal(format!("Failed to upsert checkpoint hash {}: {}", hash, e))
+                    })?;
+                seeded_hashes += 1;
+            }
+        }
+
+        tx.commit().await.map_err(|e| {
+            error!("Failed to commit transaction: {}", e);
+            AppError::internal(format!("Failed to commit transaction: {}", e))
+        })?;
+
+        info!(
+            "ModelMap seeding complete: {} inserted, {} updated, {} hashes",
+            inserted_rows, updated_rows, seeded_hashes
+        );
+
+        Ok(SeedModelMapOutput {
+            success: true,
+            message: format!("ModelMap seeded from the bundled model catalog with {} checkpoint hashes", seeded_hashes),
+            total_entries,
+            inserted_rows,
+            updated_rows,
+            seeded_hashes,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_bundled_model_catalog_is_valid() {
+        let catalog = model_catalog().expect("bundled catalog should parse");
+        assert!(!catalog.is_empty());
+
+        let mut hashes = HashSet::new();
+        for entry in &catalog {
+            assert!(!entry.hashes.is_empty(), "{} has no hashes", entry.model_name);
+            for hash in &entry.hashes {
+                assert!(hash.len() == 8 || hash.len() == 10, "unexpected hash length for {}", entry.model_name);
+                assert!(hash.chars().all(|c| c.is_ascii_hexdigit()));
+                assert!(hashes.insert(hash.to_lowercase()), "duplicate hash {}", hash);
+            }
+        }
+    }
+}
@@ -1,12 +1,13 @@
 use tracing::{error, info, warn};
 
 use crate::{
+    config::settings::LaptopDetectionConfig,
     error::types::AppError,
     repositories::{
         gpu_repository::GpuRepository,
         traits::Repository,
     },
-
+    services::laptop_detection::LaptopDetector,
 };
 
 #[derive(Debug)]
@@ -118,11 +119,9 @@ impl UpdateGpuLaptopInfoService {
         Ok(is_laptop)
     }
 
-    /// Determine if GPU is in a laptop based on device string
+    /// Determine if GPU is in a laptop based on device string, using the default patterns
     fn is_gpu_in_laptop(&self, device_string: &str) -> bool {
-        device_string.contains("Laptop") || 
-        device_string.contains("Mobile") ||
-        (device_string.contains("AMD") && device_string.ends_with("M")) // AMD mobile GPUs often end with "M"
+        LaptopDetector::new(&LaptopDetectionConfig::default()).is_laptop(device_string)
     }
 }
 
@@ -2,11 +2,13 @@ use tracing::{error, info};
 
 use crate::{
     error::types::AppError,
+    models::model_map::ModelMap,
     repositories::{
         run_more_details_repository::RunMoreDetailsRepository,
         model_map_repository::ModelMapRepository,
         traits::Repository,
     },
+    services::parsers::ModelNameParser,
 };
 
 #[derive(Debug, serde::Serialize)]
@@ -35,7 +37,8 @@ impl UpdateRunMoreDetailsService {
     /// 
     /// This service:
     /// 1. Finds all RunMoreDetails records that don't have ModelMapId filled
-    /// 2. For each record, finds the corresponding ModelMapId from ModelMap based on model_name
+    /// 2. For each record, finds the corresponding ModelMapId from ModelMap based on model_name,
+    ///    falling back to the checkpoint hash in the model name (e.g. "name [6ce0161689]")
     /// 3. Updates the RunMoreDetails records with the found ModelMapId
     /// 4. Returns counts of updated and not found records
     /// 
@@ -62,6 +65,7 @@ impl UpdateRunMoreDetailsService {
 
         let mut updated_count = 0;
         let mut not_found_count = 0;
+        let mut hash_matched_count = 0;
 
         // For each run, find the corresponding ModelMapId from ModelMap based on model_name
         for run in &runs_without_modelmapid {
@@ -74,11 +78,19 @@ impl UpdateRunMoreDetailsService {
                 }
             };
 
-            let model_map_entry = self.model_map_repository.find_single_by_model_name(model_name).await.map_err(|e| {
+            let mut model_map_entry = self.model_map_repository.find_single_by_model_name(model_name).await.map_err(|e| {
                 error!("Failed to query ModelMap for model_name '{}': {}", model_name, e);
                 AppError::internal(format!("Failed to query ModelMap for model_name '{}': {}", model_name, e))
             })?;
 
+            // Fall back to the checkpoint hash embedded in the model name
+            if model_map_entry.is_none() {
+                model_map_entry = self.find_by_checkpoint_hash(model_name).await?;
+                if model_map_entry.is_some() {
+                    hash_matched_count += 1;
+                }
+            }
+
             if let Some(model_map_entry) = model_map_entry {
                 // Update RunMoreDetails with the found ModelMapId
                 let run_id = run.id.ok_or_else(|| {
@@ -105,16 +117,36 @@ impl UpdateRunMoreDetailsService {
             }
         }
 
-        let message = format!("RunMoreDetails updated with ModelMapId successfully. Updated: {}, Not found: {}", 
-                             updated_count, not_found_count);
+        let message = format!("RunMoreDetails updated with ModelMapId successfully. Updated: {} ({} by checkpoint hash), Not found: {}", 
+                             updated_count, hash_matched_count, not_found_count);
 
-        info!("RunMoreDetails update complete: {} updated, {} not found", updated_count, not_found_count);
+        info!("RunMoreDetails update complete: {} updated ({} by checkpoint hash), {} not found",
+              updated_count, hash_matched_count, not_found_count);
 
         Ok(UpdateRunMoreDetailsOutput {
             success: true,
             message,
         })
     }
+
+    /// Resolve a ModelMap entry from the checkpoint hash in a model name, if it has one
+    async fn find_by_checkpoint_hash(&self, model_name: &str) -> Result<Option<ModelMap>, AppError> {
+        let Some(hash) = ModelNameParser::parse_checkpoint_hash(model_name) else {
+            return Ok(None);
+        };
+
+        for key in ModelNameParser::hash_lookup_keys(&hash) {
+            let entry = self.model_map_repository.find_by_hash(&key).await.map_err(|e| {
+                error!("Failed to query ModelMap for checkpoint hash '{}': {}", key, e);
+                AppError::internal(format!("Failed to query ModelMap for checkpoint hash '{}': {}", key, e))
+            })?;
+            if entry.is_some() {
+                return Ok(entry);
+            }
+        }
+
+        Ok(None)
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,99 @@
+use crate::models::digest::Digest;
+
+/// Render a digest as plain text for a forum post, one section per paragraph
+///
+/// Empty sections are left out, except the run count.
+pub fn render_digest_summary(digest: &Digest) -> String {
+    let runs = match digest.new_runs {
+        1 => "1 new benchmark run".to_string(),
+        count => format!("{} new benchmark runs", count),
+    };
+    let mut sections = vec![format!("SD-ITS-Benchmark {}: {}.", digest.period.label(), runs)];
+
+    if !digest.new_gpus.is_empty() {
+        let gpus: Vec<String> =
+            digest.new_gpus.iter().map(|gpu| format!("{} ({})", gpu.device, plural(gpu.runs, "run"))).collect();
+        sections.push(format!("New GPUs: {}.", gpus.join(", ")));
+    }
+
+    if !digest.its_improvements.is_empty() {
+        let mut section = String::from("Biggest ITS improvements:");
+        for improvement in &digest.its_improvements {
+            section.push_str(&format!(
+                "\n- {}: {:.2} -> {:.2} it/s (+{:.1}%)",
+                improvement.device,
+                improvement.previous_best_its,
+                improvement.best_its,
+                improvement.improvement_percent()
+            ));
+        }
+        sections.push(section);
+    }
+
+    if !digest.active_users.is_empty() {
+        let users: Vec<String> =
+            digest.active_users.iter().map(|user| format!("{} ({})", user.user, plural(user.runs, "run"))).collect();
+        sections.push(format!("Most active submitters: {}.", users.join(", ")));
+    }
+
+    sections.join("\n\n")
+}
+
+fn plural(count: i64, noun: &str) -> String {
+    if count == 1 { format!("1 {}", noun) } else { format!("{} {}s", count, noun) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::digest::{DigestPeriod, ItsImprovement, NewGpu, UserActivity};
+
+    fn digest() -> Digest {
+        Digest {
+            period: DigestPeriod::Week,
+            since: "2024-01-01T00:00:00+00:00".to_string(),
+            until: "2024-01-08T00:00:00+00:00".to_string(),
+            new_runs: 12,
+            new_gpus: vec![NewGpu {
+                device: "NVIDIA GeForce RTX 5090".to_string(),
+                runs: 1,
+            }],
+            its_improvements: vec![ItsImprovement {
+                device: "NVIDIA GeForce RTX 4090".to_string(),
+                previous_best_its: 20.0,
+                best_its: 25.0,
+            }],
+            active_users: vec![UserActivity {
+                user: "alice".to_string(),
+                runs: 7,
+            }],
+            summary: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_render_digest_summary() {
+        let summary = render_digest_summary(&digest());
+
+        assert_eq!(
+            summary,
+            "SD-ITS-Benchmark this week: 12 new benchmark runs.\n\n\
+             New GPUs: NVIDIA GeForce RTX 5090 (1 run).\n\n\
+             Biggest ITS improvements:\n- NVIDIA GeForce RTX 4090: 20.00 -> 25.00 it/s (+25.0%)\n\n\
+             Most active submitters: alice (7 runs)."
+        );
+    }
+
+    #[test]
+    fn test_render_digest_summary_skips_empty_sections() {
+        let digest = Digest {
+            new_runs: 1,
+            new_gpus: Vec::new(),
+            its_improvements: Vec::new(),
+            active_users: Vec::new(),
+            ..digest()
+        };
+
+        assert_eq!(render_digest_summary(&digest), "SD-ITS-Benchmark this week: 1 new benchmark run.");
+    }
+}
@@ -0,0 +1,214 @@
+use sqlx::SqlitePool;
+use tracing::{error, info};
+
+use crate::{
+    error::types::AppError,
+    models::{
+        estimate::{EstimateRequest, ItsEstimate, ItsSample, MatchLevel},
+        gpu_base::GpuBase,
+    },
+    repositories::{
+        gpu_base_repository::GpuBaseRepository,
+        gpu_map_repository::GpuMapRepository,
+        gpu_repository::GpuRepository,
+        model_map_repository::ModelMapRepository,
+        traits::Repository,
+    },
+    services::percentiles::percentile,
+};
+
+/// Estimates the ITS a user can expect from the closest comparable runs
+///
+/// Runs on the exact GPU are preferred, then runs on any GPU mapped to the same
+/// base GPU, then runs on GPUs of the same architecture. At each level the
+/// samples must share the requested base model; they are further narrowed to the
+/// requested torch version when any match it.
+pub struct EstimateService {
+    pool: SqlitePool,
+}
+
+impl EstimateService {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// The estimate from the tenant's closest level with comparable runs, `None` when no level has any
+    pub async fn estimate(&self, tenant_id: &str, request: &EstimateRequest) -> Result<Option<ItsEstimate>, AppError> {
+        let gpu = request.gpu.trim();
+        if gpu.is_empty() {
+            return Err(AppError::validation("gpu must not be empty"));
+        }
+        let torch = request.torch.as_deref().map(str::trim).filter(|torch| !torch.is_empty());
+        let base_model = match request.model.as_deref().map(str::trim).filter(|model| !model.is_empty()) {
+            Some(model) => Some(self.resolve_base_model(model).await?),
+            None => None,
+        };
+        info!("Estimating ITS for {} (torch: {:?}, base model: {:?})", gpu, torch, base_model);
+
+        let gpu_repository = GpuRepository::new(self.pool.clone());
+
+        let samples = gpu_repository.find_its_samples_by_device(tenant_id, gpu).await.map_err(database_error)?;
+        if let Some(estimate) = summarize(MatchLevel::ExactGpu, gpu, &samples, base_model.as_deref(), torch) {
+            return Ok(Some(estimate));
+        }
+
+        let Some(base_gpu) = self.resolve_base_gpu(gpu).await? else {
+            return Ok(None);
+        };
+        let Some(base_gpu_id) = base_gpu.id else {
+            return Ok(None);
+        };
+
+        let samples = gpu_repository.find_its_samples_by_base_gpu_id(tenant_id, base_gpu_id).await.map_err(database_error)?;
+        if let Some(estimate) = summarize(MatchLevel::GpuBase, &base_gpu.name, &samples, base_model.as_deref(), torch) {
+            return Ok(Some(estimate));
+        }
+
+        let Some(architecture) = base_gpu.architecture.as_deref() else {
+            return Ok(None);
+        };
+        let samples = gpu_repository.find_its_samples_by_architecture(tenant_id, architecture).await.map_err(database_error)?;
+        Ok(summarize(MatchLevel::Architecture, architecture, &samples, base_model.as_deref(), torch))
+    }
+
+    /// A mapped model name resolves to its base model; anything else is taken as a base model already
+    async fn resolve_base_model(&self, model: &str) -> Result<String, AppError> {
+        let model_map = ModelMapRepository::new(self.pool.clone())
+            .find_single_by_model_name(model)
+            .await
+            .map_err(database_error)?;
+
+        Ok(model_map
+            .and_then(|model_map| model_map.base_model)
+            .unwrap_or_else(|| model.to_string()))
+    }
+
+    /// The base GPU a device name is mapped to, or the base GPU with that name
+    async fn resolve_base_gpu(&self, gpu: &str) -> Result<Option<GpuBase>, AppError> {
+        let gpu_base_repository = GpuBaseRepository::new(self.pool.clone());
+
+        let mapped_base_gpu_id = GpuMapRepository::new(self.pool.clone())
+            .find_by_gpu_name(gpu)
+            .await
+            .map_err(database_error)?
+            .into_iter()
+            .find_map(|gpu_map| gpu_map.base_gpu_id);
+        if let Some(base_gpu_id) = mapped_base_gpu_id {
+            return gpu_base_repository.find_by_id(base_gpu_id).await.map_err(database_error);
+        }
+
+        Ok(gpu_base_repository
+            .find_by_name(gpu)
+            .await
+            .map_err(database_error)?
+            .into_iter()
+            .next())
+    }
+}
+
+fn database_error(e: sqlx::Error) -> AppError {
+    error!("Failed to fetch samples for ITS estimate: {}", e);
+    AppError::Database(e)
+}
+
+/// Narrow samples to the base model (required) and torch version (preferred) and summarize them
+pub fn summarize(
+    match_level: MatchLevel,
+    matched_on: &str,
+    samples: &[ItsSample],
+    base_model: Option<&str>,
+    torch: Option<&str>,
+) -> Option<ItsEstimate> {
+    let same_model: Vec<&ItsSample> = samples
+        .iter()
+        .filter(|sample| sample.avg_its.is_finite())
+        .filter(|sample| match base_model {
+            Some(base_model) => sample
+                .base_model
+                .as_deref()
+                .is_some_and(|sample_model| sample_model.eq_ignore_ascii_case(base_model)),
+            None => true,
+        })
+        .collect();
+
+    let same_torch: Vec<&ItsSample> = match torch {
+        Some(torch) => same_model
+            .iter()
+            .copied()
+            .filter(|sample| sample.torch.as_deref().is_some_and(|sample_torch| torch_matches(sample_torch, torch)))
+            .collect(),
+        None => Vec::new(),
+    };
+    let torch_matched = !same_torch.is_empty();
+    let chosen = if torch_matched { same_torch } else { same_model };
+
+    let mut its: Vec<f64> = chosen.iter().map(|sample| sample.avg_its).collect();
+    if its.is_empty() {
+        return None;
+    }
+    its.sort_by(|a, b| a.total_cmp(b));
+
+    Some(ItsEstimate {
+        match_level,
+        matched_on: matched_on.to_string(),
+        torch_matched,
+        base_model: base_model.map(str::to_string),
+        sample_count: its.len(),
+        min_its: its[0],
+        p25_its: percentile(&its, 0.25),
+        median_its: percentile(&its, 0.5),
+        p75_its: percentile(&its, 0.75),
+        max_its: its[its.len() - 1],
+    })
+}
+
+/// "2.1" matches "2.1.0" and "2.1+cu118" but not "2.10"
+fn torch_matches(sample_torch: &str, requested: &str) -> bool {
+    sample_torch == requested
+        || sample_torch
+            .strip_prefix(requested)
+            .is_some_and(|rest| rest.starts_with('.') || rest.starts_with('+'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(avg_its: f64, torch: &str, base_model: &str) -> ItsSample {
+        ItsSample {
+            avg_its,
+            torch: Some(torch.to_string()),
+            base_model: Some(base_model.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_summarize_prefers_matching_torch() {
+        let samples = vec![
+            sample(4.0, "2.1.0+cu118", "sdxl"),
+            sample(6.0, "2.1.0+cu121", "sdxl"),
+            sample(2.0, "2.0.1", "sdxl"),
+            sample(9.0, "2.1.0", "sd15"),
+        ];
+
+        let estimate = summarize(MatchLevel::ExactGpu, "RTX 3060", &samples, Some("SDXL"), Some("2.1")).unwrap();
+        assert!(estimate.torch_matched);
+        assert_eq!(estimate.sample_count, 2);
+        assert_eq!(estimate.median_its, 5.0);
+
+        let estimate = summarize(MatchLevel::ExactGpu, "RTX 3060", &samples, Some("sdxl"), Some("1.13")).unwrap();
+        assert!(!estimate.torch_matched);
+        assert_eq!(estimate.sample_count, 3);
+        assert_eq!((estimate.min_its, estimate.max_its), (2.0, 6.0));
+
+        assert!(summarize(MatchLevel::ExactGpu, "RTX 3060", &samples, Some("sd21"), None).is_none());
+    }
+
+    #[test]
+    fn test_torch_matches() {
+        assert!(torch_matches("2.1.0", "2.1"));
+        assert!(torch_matches("2.1+cu118", "2.1"));
+        assert!(torch_matches("2.1.0", "2.1.0"));
+        assert!(!torch_matches("2.10.0", "2.1"));
+    }
+}
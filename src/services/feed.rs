@@ -0,0 +1,61 @@
+use crate::services::badges::escape_xml;
+
+/// One `<entry>` of an Atom feed
+#[derive(Debug, Clone, PartialEq)]
+pub struct AtomEntry {
+    pub id: String,
+    pub title: String,
+    pub link: String,
+    /// RFC 3339
+    pub updated: String,
+    pub summary: String,
+}
+
+/// Render an Atom 1.0 feed document
+///
+/// `feed_url` doubles as the feed id and its `rel="self"` link.
+pub fn render_atom_feed(title: &str, feed_url: &str, updated: &str, entries: &[AtomEntry]) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    xml.push_str("<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+    xml.push_str(&format!("  <title>{}</title>\n", escape_xml(title)));
+    xml.push_str(&format!("  <id>{}</id>\n", escape_xml(feed_url)));
+    xml.push_str(&format!("  <link rel=\"self\" href=\"{}\"/>\n", escape_xml(feed_url)));
+    xml.push_str(&format!("  <updated>{}</updated>\n", escape_xml(updated)));
+
+    for entry in entries {
+        xml.push_str("  <entry>\n");
+        xml.push_str(&format!("    <title>{}</title>\n", escape_xml(&entry.title)));
+        xml.push_str(&format!("    <id>{}</id>\n", escape_xml(&entry.id)));
+        xml.push_str(&format!("    <link href=\"{}\"/>\n", escape_xml(&entry.link)));
+        xml.push_str(&format!("    <updated>{}</updated>\n", escape_xml(&entry.updated)));
+        xml.push_str(&format!("    <summary>{}</summary>\n", escape_xml(&entry.summary)));
+        xml.push_str("  </entry>\n");
+    }
+
+    xml.push_str("</feed>\n");
+    xml
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_atom_feed() {
+        let entries = vec![AtomEntry {
+            id: "urn:sd-its-benchmark:run:1".to_string(),
+            title: "RTX 4090 <fast> & sdxl".to_string(),
+            link: "http://localhost:4000/api/share/abc".to_string(),
+            updated: "2024-01-01T00:00:00+00:00".to_string(),
+            summary: "20.00 it/s".to_string(),
+        }];
+
+        let xml = render_atom_feed("Runs", "http://localhost:4000/api/feed.atom", "2024-01-01T00:00:00+00:00", &entries);
+
+        assert!(xml.starts_with("<?xml"));
+        assert!(xml.contains("<id>http://localhost:4000/api/feed.atom</id>"));
+        assert!(xml.contains("<title>RTX 4090 &lt;fast&gt; &amp; sdxl</title>"));
+        assert!(xml.contains("<link href=\"http://localhost:4000/api/share/abc\"/>"));
+        assert_eq!(xml.matches("<entry>").count(), 1);
+    }
+}
@@ -0,0 +1,111 @@
+use std::collections::BTreeMap;
+
+use crate::models::gpu_dedup::{GpuDedupCluster, GpuDeviceCount};
+
+/// Trademark marks some drivers put into device names
+const TRADEMARK_MARKS: &[&str] = &["(R)", "(r)", "(TM)", "(tm)", "®", "™"];
+
+/// Drop trademark marks and collapse whitespace, keeping the spelling otherwise
+///
+/// "NVIDIA  GeForce(R) RTX 3080 " -> "NVIDIA GeForce RTX 3080"
+pub fn clean_gpu_device(device: &str) -> String {
+    let mut cleaned = device.to_string();
+    for mark in TRADEMARK_MARKS {
+        cleaned = cleaned.replace(mark, " ");
+    }
+    cleaned.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Group device strings that clean up to the same name, ignoring case
+///
+/// The canonical spelling of a cluster is the cleaned spelling reported by the
+/// most GPU rows, the alphabetically first one on a tie. Only clusters with at
+/// least one device string to rewrite are returned, ordered by canonical name.
+pub fn find_gpu_dedup_clusters(devices: &[GpuDeviceCount]) -> Vec<GpuDedupCluster> {
+    let mut grouped: BTreeMap<String, Vec<&GpuDeviceCount>> = BTreeMap::new();
+    for device in devices {
+        let key = clean_gpu_device(&device.device).to_lowercase();
+        if !key.is_empty() {
+            grouped.entry(key).or_default().push(device);
+        }
+    }
+
+    grouped
+        .into_values()
+        .filter_map(|members| {
+            let mut spellings: BTreeMap<String, i64> = BTreeMap::new();
+            for member in &members {
+                *spellings.entry(clean_gpu_device(&member.device)).or_default() += member.row_count;
+            }
+            let canonical = spellings
+                .into_iter()
+                .max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(&a.0)))
+                .map(|(spelling, _)| spelling)?;
+
+            let variants: Vec<GpuDeviceCount> = members
+                .into_iter()
+                .filter(|member| member.device != canonical)
+                .cloned()
+                .collect();
+            if variants.is_empty() {
+                return None;
+            }
+
+            Some(GpuDedupCluster {
+                rows_to_update: variants.iter().map(|variant| variant.row_count).sum(),
+                canonical,
+                variants,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn device(device: &str, row_count: i64) -> GpuDeviceCount {
+        GpuDeviceCount {
+            device: device.to_string(),
+            row_count,
+        }
+    }
+
+    #[test]
+    fn test_clean_gpu_device() {
+        assert_eq!(clean_gpu_device("NVIDIA  GeForce(R) RTX 3080 "), "NVIDIA GeForce RTX 3080");
+        assert_eq!(clean_gpu_device("Intel(R) Arc(TM) A770 Graphics"), "Intel Arc A770 Graphics");
+        assert_eq!(clean_gpu_device("   "), "");
+    }
+
+    #[test]
+    fn test_find_gpu_dedup_clusters() {
+        let devices = vec![
+            device("NVIDIA GeForce RTX 3080", 10),
+            device("NVIDIA GeForce RTX 3080 ", 2),
+            device("nvidia geforce rtx 3080", 1),
+            device("NVIDIA GeForce RTX 4090", 5),
+            device("Intel(R) Arc(TM) A770 Graphics", 3),
+        ];
+
+        let clusters = find_gpu_dedup_clusters(&devices);
+        assert_eq!(clusters.len(), 2);
+
+        // A single spelling is still cleaned up
+        assert_eq!(clusters[0].canonical, "Intel Arc A770 Graphics");
+        assert_eq!(clusters[0].variants, vec![device("Intel(R) Arc(TM) A770 Graphics", 3)]);
+
+        assert_eq!(clusters[1].canonical, "NVIDIA GeForce RTX 3080");
+        assert_eq!(clusters[1].variants.len(), 2);
+        assert_eq!(clusters[1].rows_to_update, 3);
+    }
+
+    #[test]
+    fn test_find_gpu_dedup_clusters_tie_and_clean_data() {
+        let clusters = find_gpu_dedup_clusters(&[device("RTX 3060", 2), device("rtx 3060", 2)]);
+        assert_eq!(clusters[0].canonical, "RTX 3060");
+        assert_eq!(clusters[0].variants, vec![device("rtx 3060", 2)]);
+
+        assert!(find_gpu_dedup_clusters(&[device("RTX 3060", 2), device("RTX 4090", 1)]).is_empty());
+    }
+}
@@ -0,0 +1,116 @@
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use sqlx::SqlitePool;
+use tokio::{task::JoinHandle, time::MissedTickBehavior};
+use tracing::{error, info};
+
+use crate::{
+    config::settings::HealthHistoryConfig,
+    error::types::AppError,
+    middleware::request_metrics::{request_metrics, RequestMetrics},
+    models::health_sample::HealthSample,
+    repositories::{dataset_meta_repository::DatasetMetaRepository, health_sample_repository::HealthSampleRepository},
+};
+
+/// Records health samples, with request rates over the time since the previous one
+pub struct HealthSampler {
+    pool: SqlitePool,
+    previous_at: Instant,
+    previous_requests: RequestMetrics,
+}
+
+impl HealthSampler {
+    /// A sampler whose first rates cover the time since it was created
+    pub fn new(pool: SqlitePool) -> Self {
+        Self {
+            pool,
+            previous_at: Instant::now(),
+            previous_requests: request_metrics(),
+        }
+    }
+
+    /// Take a sample and record it
+    pub async fn record(&mut self) -> Result<HealthSample, AppError> {
+        let repository = HealthSampleRepository::new(self.pool.clone());
+        let db_size_bytes = repository
+            .database_size()
+            .await
+            .map_err(|e| database_error("fetch the database size", e))?;
+        let row_counts = DatasetMetaRepository::new(self.pool.clone())
+            .count_table_rows()
+            .await
+            .map_err(|e| database_error("count table rows", e))?;
+
+        let now = Instant::now();
+        let requests = request_metrics();
+        let minutes = now.duration_since(self.previous_at).as_secs_f64() / 60.0;
+        let request_count = requests.requests.saturating_sub(self.previous_requests.requests) as i64;
+        let server_errors = requests.server_errors.saturating_sub(self.previous_requests.server_errors) as i64;
+
+        let mut sample = HealthSample {
+            id: None,
+            sampled_at: Utc::now().to_rfc3339(),
+            db_size_bytes,
+            row_counts,
+            pool_connections: self.pool.size() as i64,
+            pool_idle_connections: self.pool.num_idle() as i64,
+            pool_max_connections: self.pool.options().get_max_connections() as i64,
+            requests: request_count,
+            server_errors,
+            requests_per_minute: if minutes > 0.0 { request_count as f64 / minutes } else { 0.0 },
+        };
+        sample.id = Some(repository.create(&sample).await.map_err(|e| database_error("record a health sample", e))?);
+
+        self.previous_at = now;
+        self.previous_requests = requests;
+
+        Ok(sample)
+    }
+
+    /// Delete the samples older than `retention_days`, returning how many were deleted
+    pub async fn prune(&self, retention_days: u64) -> Result<u64, AppError> {
+        let cutoff = Utc::now() - chrono::Duration::days(retention_days as i64);
+
+        HealthSampleRepository::new(self.pool.clone())
+            .delete_before(&cutoff.to_rfc3339())
+            .await
+            .map_err(|e| database_error("delete old health samples", e))
+    }
+}
+
+/// Start recording health samples when `settings.health_history.enabled` is set
+///
+/// A sample is recorded every `interval_seconds`, the first one interval after
+/// startup, and samples past the retention are deleted along the way. A failed
+/// sample is logged and skipped.
+pub fn spawn_health_sampling(pool: SqlitePool, config: &HealthHistoryConfig) -> Option<JoinHandle<()>> {
+    if !config.enabled {
+        return None;
+    }
+
+    let period = Duration::from_secs(config.interval_seconds);
+    let retention_days = config.retention_days;
+    info!("Recording health samples every {} seconds", config.interval_seconds);
+
+    Some(tokio::spawn(async move {
+        let mut sampler = HealthSampler::new(pool);
+        let mut interval = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            if let Err(e) = sampler.record().await {
+                error!("Health sample failed: {}", e);
+                continue;
+            }
+            if let Err(e) = sampler.prune(retention_days).await {
+                error!("Pruning health samples failed: {}", e);
+            }
+        }
+    }))
+}
+
+fn database_error(action: &str, e: sqlx::Error) -> AppError {
+    error!("Failed to {}: {}", action, e);
+    AppError::Database(e)
+}
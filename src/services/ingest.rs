@@ -0,0 +1,29 @@
+// Ingest adapters for the benchmark export formats we accept
+pub mod benchmark_format;
+pub mod webui_format;
+pub mod comfyui_format;
+pub mod invokeai_format;
+
+// Decoding of uploaded files before they are parsed
+pub mod text_encoding;
+
+// Checks applied to parsed rows from any format
+pub mod pii_scan;
+pub mod notes_filter;
+pub mod schema_drift;
+pub mod content_hash;
+pub mod ingest_delta;
+pub mod row_limits;
+
+// Re-export all adapters for easy access
+pub use benchmark_format::*;
+pub use webui_format::*;
+pub use comfyui_format::*;
+pub use invokeai_format::*;
+pub use text_encoding::*;
+pub use pii_scan::*;
+pub use notes_filter::*;
+pub use schema_drift::*;
+pub use content_hash::*;
+pub use ingest_delta::*;
+pub use row_limits::*;
@@ -0,0 +1,139 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::info;
+
+use crate::{
+    error::types::AppError,
+    handlers::validation::RunData,
+    services::ingest::{decode_upload, ComfyUiFormat, InvokeAiFormat, WebUiFormat},
+};
+
+/// An uploadable benchmark export format
+///
+/// Adapters convert their export into WebUI-style `RunData` rows so the
+/// existing processing pipeline (parsers, GPU/app/library processing) can be
+/// reused unchanged.
+pub trait BenchmarkFormat: Send + Sync {
+    /// Short identifier of the format, e.g. "webui"
+    fn name(&self) -> &'static str;
+
+    /// Version of the export layout this adapter accepts
+    fn version(&self) -> &'static str;
+
+    /// Human readable description of the export
+    fn description(&self) -> &'static str;
+
+    /// JSON Schema of the accepted document, generated from the serde models
+    fn schema(&self) -> Value;
+
+    /// Check whether the parsed JSON document is in this format
+    fn detect(&self, document: &Value) -> bool;
+
+    /// Convert the JSON document into run rows
+    fn to_run_data(&self, document: &Value) -> Result<Vec<RunData>, AppError>;
+}
+
+/// Machine-readable description of an accepted upload format
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UploadFormat {
+    pub name: String,
+    pub version: String,
+    pub description: String,
+    pub schema: Value,
+}
+
+/// All registered formats, in detection order
+pub fn registered_formats() -> Vec<Box<dyn BenchmarkFormat>> {
+    vec![
+        Box::new(WebUiFormat),
+        Box::new(ComfyUiFormat),
+        Box::new(InvokeAiFormat),
+    ]
+}
+
+/// Describe every registered format, including its JSON Schema
+pub fn upload_formats() -> Vec<UploadFormat> {
+    registered_formats()
+        .iter()
+        .map(|format| UploadFormat {
+            name: format.name().to_string(),
+            version: format.version().to_string(),
+            description: format.description().to_string(),
+            schema: format.schema(),
+        })
+        .collect()
+}
+
+/// Detect the format of an uploaded file and convert it into run rows
+///
+/// Files that are not clean UTF-8 are repaired as `decode_upload` describes.
+///
+/// # Arguments
+/// * `file_content` - The raw uploaded JSON file
+///
+/// # Returns
+/// * `(&'static str, Vec<RunData>)` - The detected format name and its rows
+pub fn parse_benchmark_export(file_content: &[u8]) -> Result<(&'static str, Vec<RunData>), AppError> {
+    let decoded = decode_upload(file_content);
+    let document = parse_benchmark_document(decoded.text.as_bytes())?;
+    let format = detect_benchmark_format(&document)?;
+
+    let mut rows = format.to_run_data(&document)?;
+    decoded.restore(&mut rows);
+    Ok((format.name(), rows))
+}
+
+/// Parse an uploaded file as a JSON document
+pub fn parse_benchmark_document(file_content: &[u8]) -> Result<Value, AppError> {
+    serde_json::from_slice(file_content).map_err(|e| AppError::bad_request(format!("Invalid JSON format: {}", e)))
+}
+
+/// Find the registered format a parsed document is in
+pub fn detect_benchmark_format(document: &Value) -> Result<Box<dyn BenchmarkFormat>, AppError> {
+    let format = registered_formats()
+        .into_iter()
+        .find(|format| format.detect(document))
+        .ok_or_else(|| {
+            let names: Vec<&str> = registered_formats().iter().map(|format| format.name()).collect();
+            AppError::bad_request(format!(
+                "Unrecognized benchmark format. Supported formats: {}",
+                names.join(", ")
+            ))
+        })?;
+
+    info!("Detected {} benchmark format", format.name());
+    Ok(format)
+}
+
+/// Join ITS samples into the "a/b/c" form stored in `runs.vram_usage`
+pub(crate) fn join_its_values(its_values: &[f64]) -> String {
+    its_values
+        .iter()
+        .map(|value| value.to_string())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_benchmark_export_unknown_format() {
+        let result = parse_benchmark_export(br#"{"foo": "bar"}"#);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_upload_formats_have_schemas() {
+        let formats = upload_formats();
+        assert_eq!(formats.len(), registered_formats().len());
+        assert!(formats.iter().all(|format| format.schema.get("$schema").is_some()));
+    }
+
+    #[test]
+    fn test_join_its_values() {
+        assert_eq!(join_its_values(&[1.5, 2.0, 2.25]), "1.5/2/2.25");
+        assert_eq!(join_its_values(&[]), "");
+    }
+}
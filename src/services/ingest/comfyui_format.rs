@@ -0,0 +1,166 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    error::types::AppError,
+    handlers::validation::RunData,
+    services::ingest::{join_its_values, BenchmarkFormat},
+};
+
+pub const COMFYUI_REPOSITORY_URL: &str = "https://github.com/comfyanonymous/ComfyUI";
+
+/// ComfyUI benchmark export: one system/device description shared by many results
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ComfyUiExport {
+    pub comfyui_version: String,
+    pub commit: Option<String>,
+    pub updated: Option<String>,
+    pub branch: Option<String>,
+    #[serde(default)]
+    pub system: ComfyUiSystem,
+    pub device: ComfyUiDevice,
+    pub results: Vec<ComfyUiResult>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct ComfyUiSystem {
+    pub os: Option<String>,
+    pub os_release: Option<String>,
+    pub arch: Option<String>,
+    pub python: Option<String>,
+    pub pytorch: Option<String>,
+    pub xformers: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ComfyUiDevice {
+    pub name: String,
+    pub vram_gb: Option<f64>,
+    pub driver: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ComfyUiResult {
+    pub timestamp: String,
+    pub model: String,
+    pub its: Vec<f64>,
+    pub user: Option<String>,
+    pub notes: Option<String>,
+}
+
+pub struct ComfyUiFormat;
+
+impl BenchmarkFormat for ComfyUiFormat {
+    fn name(&self) -> &'static str {
+        "comfyui"
+    }
+
+    fn version(&self) -> &'static str {
+        "1"
+    }
+
+    fn description(&self) -> &'static str {
+        "ComfyUI benchmark export: shared system/device info with a list of results"
+    }
+
+    fn schema(&self) -> Value {
+        schemars::schema_for!(ComfyUiExport).to_value()
+    }
+
+    fn detect(&self, document: &Value) -> bool {
+        document.get("comfyui_version").is_some()
+    }
+
+    fn to_run_data(&self, document: &Value) -> Result<Vec<RunData>, AppError> {
+        let export: ComfyUiExport = serde_json::from_value(document.clone())
+            .map_err(|e| AppError::bad_request(format!("Invalid ComfyUI benchmark export: {}", e)))?;
+
+        let url = match &export.branch {
+            Some(branch) => format!("{}/tree/{}", COMFYUI_REPOSITORY_URL, branch),
+            None => COMFYUI_REPOSITORY_URL.to_string(),
+        };
+        let mut info = String::from("app:ComfyUI");
+        if let Some(updated) = &export.updated {
+            info.push_str(&format!(" updated:{}", updated));
+        }
+        info.push_str(&format!(
+            " hash:{} url:{}",
+            export.commit.as_deref().unwrap_or(&export.comfyui_version),
+            url
+        ));
+
+        let system = &export.system;
+        let system_info = format!(
+            "arch:{} system:{} release:{} python:{}",
+            system.arch.as_deref().unwrap_or_default(),
+            system.os.as_deref().unwrap_or_default(),
+            system.os_release.as_deref().unwrap_or_default(),
+            system.python.as_deref().unwrap_or_default()
+        );
+        let model_info = format!(
+            "torch:{} xformers:{}",
+            system.pytorch.as_deref().unwrap_or_default(),
+            system.xformers.as_deref().unwrap_or_default()
+        );
+
+        let mut device_info = format!("device:{}", export.device.name);
+        if let Some(vram_gb) = export.device.vram_gb {
+            device_info.push_str(&format!(" {}GB", vram_gb));
+        }
+        if let Some(driver) = &export.device.driver {
+            device_info.push_str(&format!(" driver:{}", driver));
+        }
+
+        let xformers = if system.xformers.is_some() { "True" } else { "False" };
+
+        Ok(export
+            .results
+            .into_iter()
+            .map(|result| RunData {
+                timestamp: result.timestamp,
+                vram_usage: join_its_values(&result.its),
+                info: info.clone(),
+                system_info: system_info.clone(),
+                model_info: model_info.clone(),
+                device_info: device_info.clone(),
+                xformers: xformers.to_string(),
+                model_name: result.model,
+                user: result.user.unwrap_or_default(),
+                notes: result.notes.unwrap_or_default(),
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_comfyui_to_run_data() {
+        let document = json!({
+            "comfyui_version": "0.2.2",
+            "commit": "4f7a3cb",
+            "updated": "2024-09-10",
+            "branch": "master",
+            "system": { "os": "Windows", "python": "3.11.9", "pytorch": "2.4.0+cu124" },
+            "device": { "name": "NVIDIA GeForce RTX 4090", "vram_gb": 24, "driver": "560.70" },
+            "results": [
+                { "timestamp": "2024-09-11T10:00:00Z", "model": "sd_xl_base_1.0", "its": [10.5, 11.0] }
+            ]
+        });
+
+        assert!(ComfyUiFormat.detect(&document));
+        let rows = ComfyUiFormat.to_run_data(&document).unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].vram_usage, "10.5/11");
+        assert_eq!(
+            rows[0].info,
+            "app:ComfyUI updated:2024-09-10 hash:4f7a3cb url:https://github.com/comfyanonymous/ComfyUI/tree/master"
+        );
+        assert_eq!(rows[0].device_info, "device:NVIDIA GeForce RTX 4090 24GB driver:560.70");
+        assert_eq!(rows[0].xformers, "False");
+    }
+}
@@ -0,0 +1,154 @@
+use serde_json::json;
+use sha2::{Digest, Sha256};
+
+use crate::{handlers::validation::RunData, models::runs::Run};
+
+/// Hex SHA-256 of a row's fields as uploaded, identifying rows already ingested
+///
+/// Taken before personal data is redacted, so the same upstream row hashes the
+/// same whatever the PII scan mode.
+pub fn row_content_hash(row: &RunData) -> String {
+    fields_hash(json!([
+        row.timestamp,
+        row.vram_usage,
+        row.info,
+        row.system_info,
+        row.model_info,
+        row.device_info,
+        row.xformers,
+        row.model_name,
+        row.user,
+        row.notes,
+    ]))
+}
+
+/// The content hash of a stored run, for runs ingested before hashes were recorded
+///
+/// Matches `row_content_hash` of its upload unless the run was redacted or edited since.
+pub fn run_content_hash(run: &Run) -> String {
+    let field = |value: &Option<String>| value.clone().unwrap_or_default();
+    fields_hash(json!([
+        field(&run.timestamp),
+        field(&run.vram_usage),
+        field(&run.info),
+        field(&run.system_info),
+        field(&run.model_info),
+        field(&run.device_info),
+        field(&run.xformers),
+        field(&run.model_name),
+        field(&run.user),
+        field(&run.notes),
+    ]))
+}
+
+/// Hex SHA-256 of a row's fields apart from its ITS (`vram_usage`)
+///
+/// Rows sharing a fingerprint but not a content hash are the same benchmark
+/// reported with different ITS, such as a rerun.
+pub fn row_fingerprint(row: &RunData) -> String {
+    fields_hash(json!([
+        row.timestamp,
+        row.info,
+        row.system_info,
+        row.model_info,
+        row.device_info,
+        row.xformers,
+        row.model_name,
+        row.user,
+        row.notes,
+    ]))
+}
+
+/// The fingerprint of a stored run, matching `row_fingerprint` of its upload like `run_content_hash`
+pub fn run_fingerprint(run: &Run) -> String {
+    let field = |value: &Option<String>| value.clone().unwrap_or_default();
+    fields_hash(json!([
+        field(&run.timestamp),
+        field(&run.info),
+        field(&run.system_info),
+        field(&run.model_info),
+        field(&run.device_info),
+        field(&run.xformers),
+        field(&run.model_name),
+        field(&run.user),
+        field(&run.notes),
+    ]))
+}
+
+/// Content hash and fingerprint of one row
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RowHashes {
+    pub content_hash: String,
+    pub fingerprint: String,
+}
+
+impl RowHashes {
+    pub fn of_row(row: &RunData) -> Self {
+        Self {
+            content_hash: row_content_hash(row),
+            fingerprint: row_fingerprint(row),
+        }
+    }
+
+    pub fn of_run(run: &Run) -> Self {
+        Self {
+            content_hash: run_content_hash(run),
+            fingerprint: run_fingerprint(run),
+        }
+    }
+}
+
+fn fields_hash(fields: serde_json::Value) -> String {
+    Sha256::digest(fields.to_string().as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::services::data_processing::save_data_service::runs_from_data;
+
+    use super::*;
+
+    fn row(notes: &str) -> RunData {
+        RunData {
+            timestamp: "2024-01-01T10:00:00Z".to_string(),
+            vram_usage: "10.0/11.0".to_string(),
+            info: "app:test".to_string(),
+            system_info: "system:Linux".to_string(),
+            model_info: "torch:2.1".to_string(),
+            device_info: "device:NVIDIA GeForce RTX 4090".to_string(),
+            xformers: "True".to_string(),
+            model_name: "sdxl".to_string(),
+            user: "tester".to_string(),
+            notes: notes.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_row_content_hash_depends_on_every_field() {
+        assert_eq!(row_content_hash(&row("")), row_content_hash(&row("")));
+        assert_ne!(row_content_hash(&row("")), row_content_hash(&row("rerun")));
+        assert_eq!(row_content_hash(&row("")).len(), 64);
+    }
+
+    #[test]
+    fn test_run_content_hash_matches_its_row() {
+        let hash = row_content_hash(&row("rerun"));
+        let run = runs_from_data(vec![row("rerun")]).remove(0);
+
+        assert_eq!(run_content_hash(&run), hash);
+        assert_eq!(run_fingerprint(&run), row_fingerprint(&row("rerun")));
+    }
+
+    #[test]
+    fn test_row_fingerprint_ignores_its() {
+        let mut rerun = row("");
+        rerun.vram_usage = "12.0/13.0".to_string();
+
+        assert_eq!(row_fingerprint(&rerun), row_fingerprint(&row("")));
+        assert_ne!(row_content_hash(&rerun), row_content_hash(&row("")));
+        assert_ne!(row_fingerprint(&row("")), row_fingerprint(&row("rerun")));
+    }
+}
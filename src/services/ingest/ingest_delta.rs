@@ -0,0 +1,84 @@
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use super::content_hash::RowHashes;
+
+/// What an appended upload changed, by row
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IngestDelta {
+    /// Rows whose fingerprint was not ingested before
+    pub new_rows: usize,
+    /// Rows identical to one ingested before, or earlier in the upload
+    pub duplicate_rows: usize,
+    /// Rows with the fingerprint of an ingested row but a different ITS
+    pub updated_rows: usize,
+}
+
+impl IngestDelta {
+    pub fn add(&mut self, other: IngestDelta) {
+        self.new_rows += other.new_rows;
+        self.duplicate_rows += other.duplicate_rows;
+        self.updated_rows += other.updated_rows;
+    }
+}
+
+/// Content hashes and fingerprints of the rows ingested so far
+#[derive(Debug, Clone, Default)]
+pub struct KnownRows {
+    pub content_hashes: HashSet<String>,
+    pub fingerprints: HashSet<String>,
+}
+
+impl KnownRows {
+    /// Classify rows against the known ones, then count them as known
+    pub fn classify(&mut self, rows: &[RowHashes]) -> IngestDelta {
+        let mut delta = IngestDelta::default();
+        for row in rows {
+            if !self.content_hashes.insert(row.content_hash.clone()) {
+                delta.duplicate_rows += 1;
+            } else if !self.fingerprints.insert(row.fingerprint.clone()) {
+                delta.updated_rows += 1;
+            } else {
+                delta.new_rows += 1;
+            }
+        }
+
+        delta
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(hashes: &[(&str, &str)]) -> Vec<RowHashes> {
+        hashes
+            .iter()
+            .map(|(content_hash, fingerprint)| RowHashes {
+                content_hash: content_hash.to_string(),
+                fingerprint: fingerprint.to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_classify_against_known_rows() {
+        let mut known = KnownRows {
+            content_hashes: HashSet::from(["a1".to_string()]),
+            fingerprints: HashSet::from(["a".to_string()]),
+        };
+
+        let delta = known.classify(&rows(&[("a1", "a"), ("a2", "a"), ("b1", "b"), ("b1", "b")]));
+
+        assert_eq!(
+            delta,
+            IngestDelta {
+                new_rows: 1,
+                duplicate_rows: 2,
+                updated_rows: 1,
+            }
+        );
+        assert_eq!(known.classify(&rows(&[("a2", "a")])).duplicate_rows, 1);
+    }
+}
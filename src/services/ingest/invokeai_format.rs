@@ -0,0 +1,137 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    error::types::AppError,
+    handlers::validation::RunData,
+    services::ingest::{join_its_values, BenchmarkFormat},
+};
+
+pub const INVOKEAI_REPOSITORY_URL: &str = "https://github.com/invoke-ai/InvokeAI";
+
+/// InvokeAI benchmark export: a list of benchmarks, each carrying its own device
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct InvokeAiExport {
+    pub invokeai_version: String,
+    pub platform: Option<String>,
+    pub python_version: Option<String>,
+    pub torch_version: Option<String>,
+    pub xformers_version: Option<String>,
+    pub benchmarks: Vec<InvokeAiBenchmark>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct InvokeAiBenchmark {
+    pub timestamp: String,
+    pub model_name: String,
+    pub gpu_name: String,
+    pub vram_gb: Option<f64>,
+    pub iterations_per_second: Vec<f64>,
+    pub user: Option<String>,
+    pub notes: Option<String>,
+}
+
+pub struct InvokeAiFormat;
+
+impl BenchmarkFormat for InvokeAiFormat {
+    fn name(&self) -> &'static str {
+        "invokeai"
+    }
+
+    fn version(&self) -> &'static str {
+        "1"
+    }
+
+    fn description(&self) -> &'static str {
+        "InvokeAI benchmark export: runtime versions with a list of per-GPU benchmarks"
+    }
+
+    fn schema(&self) -> Value {
+        schemars::schema_for!(InvokeAiExport).to_value()
+    }
+
+    fn detect(&self, document: &Value) -> bool {
+        document.get("invokeai_version").is_some()
+    }
+
+    fn to_run_data(&self, document: &Value) -> Result<Vec<RunData>, AppError> {
+        let export: InvokeAiExport = serde_json::from_value(document.clone())
+            .map_err(|e| AppError::bad_request(format!("Invalid InvokeAI benchmark export: {}", e)))?;
+
+        let info = format!(
+            "app:InvokeAI hash:{} url:{}",
+            export.invokeai_version, INVOKEAI_REPOSITORY_URL
+        );
+        let system_info = format!(
+            "system:{} python:{}",
+            export.platform.as_deref().unwrap_or_default(),
+            export.python_version.as_deref().unwrap_or_default()
+        );
+        let model_info = format!(
+            "torch:{} xformers:{}",
+            export.torch_version.as_deref().unwrap_or_default(),
+            export.xformers_version.as_deref().unwrap_or_default()
+        );
+        let xformers = if export.xformers_version.is_some() { "True" } else { "False" };
+
+        Ok(export
+            .benchmarks
+            .into_iter()
+            .map(|benchmark| {
+                let device_info = match benchmark.vram_gb {
+                    Some(vram_gb) => format!("device:{} {}GB", benchmark.gpu_name, vram_gb),
+                    None => format!("device:{}", benchmark.gpu_name),
+                };
+
+                RunData {
+                    timestamp: benchmark.timestamp,
+                    vram_usage: join_its_values(&benchmark.iterations_per_second),
+                    info: info.clone(),
+                    system_info: system_info.clone(),
+                    model_info: model_info.clone(),
+                    device_info,
+                    xformers: xformers.to_string(),
+                    model_name: benchmark.model_name,
+                    user: benchmark.user.unwrap_or_default(),
+                    notes: benchmark.notes.unwrap_or_default(),
+                }
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_invokeai_to_run_data() {
+        let document = json!({
+            "invokeai_version": "4.2.7",
+            "platform": "Linux",
+            "torch_version": "2.3.1",
+            "xformers_version": "0.0.26",
+            "benchmarks": [
+                {
+                    "timestamp": "2024-08-01T12:00:00Z",
+                    "model_name": "juggernautXL",
+                    "gpu_name": "NVIDIA GeForce RTX 3090",
+                    "vram_gb": 24,
+                    "iterations_per_second": [6.0, 6.5],
+                    "user": "tester"
+                }
+            ]
+        });
+
+        assert!(InvokeAiFormat.detect(&document));
+        let rows = InvokeAiFormat.to_run_data(&document).unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].vram_usage, "6/6.5");
+        assert_eq!(rows[0].device_info, "device:NVIDIA GeForce RTX 3090 24GB");
+        assert_eq!(rows[0].model_info, "torch:2.3.1 xformers:0.0.26");
+        assert_eq!(rows[0].xformers, "True");
+        assert_eq!(rows[0].user, "tester");
+    }
+}
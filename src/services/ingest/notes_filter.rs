@@ -0,0 +1,204 @@
+use crate::{
+    config::settings::{NotesFilterAction, NotesFilterConfig},
+    error::types::AppError,
+    handlers::validation::RunData,
+};
+
+pub const REASON_LINKS: &str = "links";
+pub const REASON_MARKUP: &str = "markup";
+pub const REASON_PROFANITY: &str = "profanity";
+
+/// BBCode tags recognised by the markup check
+const BBCODE_TAGS: &[&str] = &["url", "img", "b", "i", "u", "s", "color", "size", "quote", "code"];
+
+/// Characters wrapped around a word in free text, ignored when looking for a `www.` link
+const WRAPPING_CHARS: &[char] = &['"', '\'', '(', '[', '<', '{'];
+
+/// A rule's reason, the action configured for it and the check itself
+type NotesCheck<'a> = (&'static str, NotesFilterAction, fn(&NotesFilter<'a>, &str) -> bool);
+
+/// Checks uploaded notes against the configured link, markup and profanity rules
+pub struct NotesFilter<'a> {
+    config: &'a NotesFilterConfig,
+}
+
+impl<'a> NotesFilter<'a> {
+    pub fn new(config: &'a NotesFilterConfig) -> Self {
+        Self { config }
+    }
+
+    /// Fail the upload on the first row whose notes break a rule set to block
+    ///
+    /// The error names the row and the rules, never the offending text.
+    pub fn reject_blocked(&self, rows: &[RunData]) -> Result<(), AppError> {
+        for (index, row) in rows.iter().enumerate() {
+            let blocked = self.reasons(&row.notes, NotesFilterAction::Block);
+            if !blocked.is_empty() {
+                return Err(AppError::validation(format!(
+                    "Notes at index {} are not allowed: {}",
+                    index,
+                    blocked.join(", ")
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Rules set to flag that the notes break, empty when the run can be published
+    pub fn flag_reasons(&self, notes: &str) -> Vec<&'static str> {
+        self.reasons(notes, NotesFilterAction::Flag)
+    }
+
+    fn reasons(&self, notes: &str, action: NotesFilterAction) -> Vec<&'static str> {
+        let checks: [NotesCheck<'a>; 3] = [
+            (REASON_LINKS, self.config.links, |_, notes| contains_link(notes)),
+            (REASON_MARKUP, self.config.markup, |_, notes| contains_markup(notes)),
+            (REASON_PROFANITY, self.config.profanity, Self::contains_profanity),
+        ];
+
+        checks
+            .into_iter()
+            .filter(|(_, configured, check)| *configured == action && check(self, notes))
+            .map(|(reason, _, _)| reason)
+            .collect()
+    }
+
+    fn contains_profanity(&self, notes: &str) -> bool {
+        notes
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .any(|word| self.config.profanity_words.iter().any(|blocked| blocked.eq_ignore_ascii_case(word)))
+    }
+}
+
+/// `http://`/`https://` anywhere, or a word starting with `www.`
+fn contains_link(text: &str) -> bool {
+    let lowercase = text.to_ascii_lowercase();
+    lowercase.contains("http://")
+        || lowercase.contains("https://")
+        || lowercase
+            .split_whitespace()
+            .any(|word| word.trim_start_matches(WRAPPING_CHARS).starts_with("www."))
+}
+
+/// HTML tags, markdown links and images, and BBCode tags
+fn contains_markup(text: &str) -> bool {
+    let lowercase = text.to_ascii_lowercase();
+    lowercase.contains("](") || contains_html_tag(&lowercase) || contains_bbcode_tag(&lowercase)
+}
+
+/// A `<` opening or closing a named tag that is closed by a later `>`
+///
+/// Comparisons such as `a < b` and emoticons such as `<3` are not tags.
+fn contains_html_tag(text: &str) -> bool {
+    text.match_indices('<').any(|(start, _)| {
+        let rest = &text[start + 1..];
+        let name = rest.strip_prefix('/').unwrap_or(rest);
+        name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '!') && rest.contains('>')
+    })
+}
+
+fn contains_bbcode_tag(text: &str) -> bool {
+    text.match_indices('[').any(|(start, _)| {
+        let rest = &text[start + 1..];
+        let rest = rest.strip_prefix('/').unwrap_or(rest);
+        BBCODE_TAGS.iter().any(|tag| {
+            rest.strip_prefix(tag)
+                .is_some_and(|after| after.starts_with(']') || after.starts_with('='))
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(action: NotesFilterAction) -> NotesFilterConfig {
+        NotesFilterConfig {
+            links: action,
+            markup: action,
+            profanity: action,
+            profanity_words: vec!["darn".to_string()],
+        }
+    }
+
+    fn run(notes: &str) -> RunData {
+        RunData {
+            timestamp: "2024-01-01 00:00:00".to_string(),
+            vram_usage: "8/24".to_string(),
+            info: String::new(),
+            system_info: String::new(),
+            model_info: String::new(),
+            device_info: String::new(),
+            xformers: String::new(),
+            model_name: String::new(),
+            user: "tester".to_string(),
+            notes: notes.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_detects_links() {
+        assert!(contains_link("see https://example.com for details"));
+        assert!(contains_link("mirror at (www.example.com)"));
+        assert!(contains_link("HTTP://EXAMPLE.COM"));
+        assert!(!contains_link("ran with --xformers on www"));
+        assert!(!contains_link("torch 2.1, cuda 12.1"));
+    }
+
+    #[test]
+    fn test_detects_markup() {
+        assert!(contains_markup("<b>fast</b>"));
+        assert!(contains_markup("<script>alert(1)</script>"));
+        assert!(contains_markup("![chart](image.png)"));
+        assert!(contains_markup("[url=example]here[/url]"));
+        assert!(!contains_markup("batch < 4 and steps > 20"));
+        assert!(!contains_markup("love this card <3"));
+        assert!(!contains_markup("[sdxl] base model"));
+    }
+
+    #[test]
+    fn test_profanity_matches_whole_words_ignoring_case() {
+        let config = config(NotesFilterAction::Flag);
+        let filter = NotesFilter::new(&config);
+
+        assert_eq!(filter.flag_reasons("Darn, slow again"), [REASON_PROFANITY]);
+        assert!(filter.flag_reasons("darning socks while it renders").is_empty());
+    }
+
+    #[test]
+    fn test_flag_reasons_only_lists_rules_set_to_flag() {
+        let config = NotesFilterConfig {
+            links: NotesFilterAction::Flag,
+            markup: NotesFilterAction::Off,
+            ..config(NotesFilterAction::Block)
+        };
+        let filter = NotesFilter::new(&config);
+
+        assert_eq!(filter.flag_reasons("<a href=\"https://example.com\">x</a>"), [REASON_LINKS]);
+        assert!(filter.flag_reasons("plain notes").is_empty());
+    }
+
+    #[test]
+    fn test_reject_blocked_names_row_and_rule() {
+        let config = config(NotesFilterAction::Block);
+        let filter = NotesFilter::new(&config);
+
+        assert!(filter.reject_blocked(&[run("plain notes")]).is_ok());
+        let err = filter.reject_blocked(&[run("plain notes"), run("buy at www.example.com")]).unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("index 1"));
+        assert!(message.contains(REASON_LINKS));
+        assert!(!message.contains("example.com"));
+    }
+
+    #[test]
+    fn test_off_checks_nothing() {
+        let config = config(NotesFilterAction::Off);
+        let filter = NotesFilter::new(&config);
+
+        assert!(filter.reject_blocked(&[run("<b>darn</b> https://example.com")]).is_ok());
+        assert!(filter.flag_reasons("<b>darn</b> https://example.com").is_empty());
+    }
+}
@@ -0,0 +1,266 @@
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{config::settings::PiiScanMode, error::types::AppError, handlers::validation::RunData};
+
+pub const REDACTED_EMAIL: &str = "[redacted-email]";
+pub const REDACTED_IP: &str = "[redacted-ip]";
+pub const REDACTED_PATH: &str = "[redacted-path]";
+
+/// Directory prefixes that are followed by a username, matched ignoring ASCII case
+const HOME_DIR_MARKERS: &[&str] = &["/home/", "/users/", "\\users\\"];
+
+/// Characters wrapped around a value in free text, kept outside the redaction
+const WRAPPING_CHARS: &[char] = &['"', '\'', '(', ')', '[', ']', '<', '>', '{', '}', ',', ';', '.', '!', '?'];
+
+/// Personal data found in an upload, by kind
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PiiRedactionCounts {
+    pub emails: usize,
+    pub ip_addresses: usize,
+    pub home_paths: usize,
+}
+
+impl PiiRedactionCounts {
+    pub fn total(&self) -> usize {
+        self.emails + self.ip_addresses + self.home_paths
+    }
+
+    pub fn add(&mut self, other: PiiRedactionCounts) {
+        self.emails += other.emails;
+        self.ip_addresses += other.ip_addresses;
+        self.home_paths += other.home_paths;
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "{} email(s), {} IP address(es), {} home directory path(s)",
+            self.emails, self.ip_addresses, self.home_paths
+        )
+    }
+}
+
+/// Apply the configured PII scan to the notes and info of every uploaded row
+///
+/// In redact mode the personal data is replaced with placeholders in place and
+/// the counts are returned; in reject mode the first row containing any fails
+/// the upload. The error names the row and field, never the data itself.
+pub fn scan_runs_for_pii(rows: &mut [RunData], mode: PiiScanMode) -> Result<PiiRedactionCounts, AppError> {
+    let mut total = PiiRedactionCounts::default();
+    if mode == PiiScanMode::Off {
+        return Ok(total);
+    }
+
+    for (index, row) in rows.iter_mut().enumerate() {
+        for (field, value) in [("notes", &mut row.notes), ("info", &mut row.info)] {
+            let (redacted, counts) = redact_pii(value);
+            if counts.total() == 0 {
+                continue;
+            }
+            if mode == PiiScanMode::Reject {
+                return Err(AppError::validation(format!(
+                    "Personal data found in {} at index {}: {}",
+                    field,
+                    index,
+                    counts.describe()
+                )));
+            }
+            *value = redacted;
+            total.add(counts);
+        }
+    }
+
+    Ok(total)
+}
+
+/// Replace emails, IP addresses and home directory paths in free text
+///
+/// Text is scanned per whitespace-separated token, so whitespace and the
+/// `key:value` layout of the info field are preserved.
+pub fn redact_pii(text: &str) -> (String, PiiRedactionCounts) {
+    let mut counts = PiiRedactionCounts::default();
+    let mut redacted = String::with_capacity(text.len());
+
+    for piece in text.split_inclusive(char::is_whitespace) {
+        let token = piece.trim_end_matches(char::is_whitespace);
+        redacted.push_str(&redact_token(token, &mut counts));
+        redacted.push_str(&piece[token.len()..]);
+    }
+
+    (redacted, counts)
+}
+
+fn redact_token(token: &str, counts: &mut PiiRedactionCounts) -> String {
+    // The rest of a path names the user's files as well, so it all goes
+    if let Some(start) = home_path_start(token) {
+        counts.home_paths += 1;
+        return format!("{}{}", &token[..start], REDACTED_PATH);
+    }
+
+    let start = token.len() - token.trim_start_matches(WRAPPING_CHARS).len();
+    let end = token.trim_end_matches(WRAPPING_CHARS).len();
+    if start >= end {
+        return token.to_string();
+    }
+    let core = &token[start..end];
+
+    let core = if core.contains(':') && core.parse::<Ipv6Addr>().is_ok() {
+        counts.ip_addresses += 1;
+        REDACTED_IP.to_string()
+    } else {
+        redact_ipv4(&redact_emails(core, counts), counts)
+    };
+
+    format!("{}{}{}", &token[..start], core, &token[end..])
+}
+
+/// Byte offset where a home directory path starts, including a Windows drive letter
+fn home_path_start(token: &str) -> Option<usize> {
+    let lowercase = token.to_ascii_lowercase();
+    let start = HOME_DIR_MARKERS
+        .iter()
+        .filter_map(|marker| {
+            let start = lowercase.find(marker)?;
+            // A bare "/home/" names nobody
+            (lowercase.len() > start + marker.len()).then_some(start)
+        })
+        .min()?;
+
+    let bytes = token.as_bytes();
+    // The drive letter stands alone, unlike the end of a "path:" key
+    let has_drive_letter = start >= 2
+        && bytes[start - 1] == b':'
+        && bytes[start - 2].is_ascii_alphabetic()
+        && (start == 2 || !bytes[start - 3].is_ascii_alphanumeric());
+    if has_drive_letter {
+        Some(start - 2)
+    } else {
+        Some(start)
+    }
+}
+
+fn redact_emails(text: &str, counts: &mut PiiRedactionCounts) -> String {
+    let is_local_char = |c: char| c.is_ascii_alphanumeric() || "._%+-".contains(c);
+    let is_domain_char = |c: char| c.is_ascii_alphanumeric() || ".-".contains(c);
+
+    let mut redacted = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(at) = rest.find('@') {
+        let local_start = rest[..at]
+            .char_indices()
+            .rev()
+            .take_while(|(_, c)| is_local_char(*c))
+            .last()
+            .map_or(at, |(i, _)| i);
+        let domain_len = rest[at + 1..]
+            .char_indices()
+            .take_while(|(_, c)| is_domain_char(*c))
+            .last()
+            .map_or(0, |(i, c)| i + c.len_utf8());
+        let domain = rest[at + 1..at + 1 + domain_len].trim_end_matches(['.', '-']);
+        let has_tld = domain
+            .rsplit_once('.')
+            .is_some_and(|(name, tld)| !name.is_empty() && tld.len() >= 2 && tld.chars().all(|c| c.is_ascii_alphabetic()));
+
+        if local_start < at && has_tld {
+            redacted.push_str(&rest[..local_start]);
+            redacted.push_str(REDACTED_EMAIL);
+            counts.emails += 1;
+            rest = &rest[at + 1 + domain.len()..];
+        } else {
+            redacted.push_str(&rest[..=at]);
+            rest = &rest[at + 1..];
+        }
+    }
+    redacted.push_str(rest);
+
+    redacted
+}
+
+fn redact_ipv4(text: &str, counts: &mut PiiRedactionCounts) -> String {
+    let mut redacted = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find(|c: char| c.is_ascii_digit()) {
+        let len = rest[start..]
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(rest.len() - start);
+        let candidate = rest[start..start + len].trim_end_matches('.');
+
+        redacted.push_str(&rest[..start]);
+        if candidate.parse::<Ipv4Addr>().is_ok() {
+            redacted.push_str(REDACTED_IP);
+            counts.ip_addresses += 1;
+        } else {
+            redacted.push_str(candidate);
+        }
+        rest = &rest[start + candidate.len()..];
+    }
+    redacted.push_str(rest);
+
+    redacted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(info: &str, notes: &str) -> RunData {
+        RunData {
+            timestamp: "2024-01-01T00:00:00Z".to_string(),
+            vram_usage: "8/24".to_string(),
+            info: info.to_string(),
+            system_info: String::new(),
+            model_info: String::new(),
+            device_info: String::new(),
+            xformers: String::new(),
+            model_name: String::new(),
+            user: String::new(),
+            notes: notes.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_redact_pii() {
+        let (redacted, counts) = redact_pii("ping me (alice.b+sd@mail.example.com) from 192.168.1.20.");
+        assert_eq!(redacted, format!("ping me ({}) from {}.", REDACTED_EMAIL, REDACTED_IP));
+        assert_eq!(counts, PiiRedactionCounts { emails: 1, ip_addresses: 1, home_paths: 0 });
+
+        let (redacted, counts) = redact_pii("models in C:\\Users\\Alice\\sd and path:/home/bob/webui, host fe80::1");
+        assert_eq!(
+            redacted,
+            format!("models in {} and path:{} host {}", REDACTED_PATH, REDACTED_PATH, REDACTED_IP)
+        );
+        assert_eq!(counts, PiiRedactionCounts { emails: 0, ip_addresses: 1, home_paths: 2 });
+    }
+
+    #[test]
+    fn test_benchmark_fields_are_left_alone() {
+        for text in [
+            "app:automatic updated:2024-01-01 hash:5ab7f213 url:https://github.com/AUTOMATIC1111/stable-diffusion-webui",
+            "torch:2.0.1+cu118 xformers:0.0.20 at 10:30:00",
+            "diffusers@0.21.4 on /home/ and 999.1.1.1",
+        ] {
+            let (redacted, counts) = redact_pii(text);
+            assert_eq!(redacted, text);
+            assert_eq!(counts.total(), 0, "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_scan_runs_for_pii_modes() {
+        let mut rows = vec![run("app:automatic", "by bob@example.org"), run("ip:10.0.0.1", "")];
+
+        assert_eq!(scan_runs_for_pii(&mut rows, PiiScanMode::Off).unwrap().total(), 0);
+        assert_eq!(rows[0].notes, "by bob@example.org");
+
+        let err = scan_runs_for_pii(&mut rows, PiiScanMode::Reject).unwrap_err();
+        assert!(!err.to_string().contains("bob@example.org"));
+        assert_eq!(rows[0].notes, "by bob@example.org");
+
+        let counts = scan_runs_for_pii(&mut rows, PiiScanMode::Redact).unwrap();
+        assert_eq!(counts, PiiRedactionCounts { emails: 1, ip_addresses: 1, home_paths: 0 });
+        assert_eq!(rows[0].notes, format!("by {}", REDACTED_EMAIL));
+        assert_eq!(rows[1].info, format!("ip:{}", REDACTED_IP));
+    }
+}
@@ -0,0 +1,80 @@
+use crate::{config::settings::FileUploadConfig, error::types::AppError, handlers::validation::RunData};
+
+/// Refuse an upload with more rows, or a longer field, than `config` allows
+///
+/// Checked before any row is inserted, so a pathological file never reaches
+/// SQLite; the error names the first offending row by its index in the upload.
+pub fn check_row_limits(rows: &[RunData], config: &FileUploadConfig) -> Result<(), AppError> {
+    if rows.len() > config.max_rows {
+        return Err(AppError::validation(format!(
+            "Upload has {} rows, more than the {} allowed",
+            rows.len(),
+            config.max_rows
+        )));
+    }
+
+    for (index, row) in rows.iter().enumerate() {
+        if let Some((field, value)) = row.fields().into_iter().find(|(_, value)| value.len() > config.max_field_bytes) {
+            return Err(AppError::validation(format!(
+                "Field {} at index {} is {} bytes, more than the {} allowed",
+                field,
+                index,
+                value.len(),
+                config.max_field_bytes
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(notes: &str) -> RunData {
+        RunData {
+            timestamp: "2024-01-01T00:00:00Z".to_string(),
+            vram_usage: "1.0/2.0".to_string(),
+            info: "app:test".to_string(),
+            system_info: String::new(),
+            model_info: String::new(),
+            device_info: String::new(),
+            xformers: String::new(),
+            model_name: String::new(),
+            user: String::new(),
+            notes: notes.to_string(),
+        }
+    }
+
+    fn config(max_rows: usize, max_field_bytes: usize) -> FileUploadConfig {
+        FileUploadConfig {
+            max_rows,
+            max_field_bytes,
+            ..FileUploadConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_rows_within_limits_pass() {
+        let rows = [row("short"), row("twelve bytes")];
+        assert!(check_row_limits(&rows, &config(2, 20)).is_ok());
+        assert!(check_row_limits(&[], &config(1, 1)).is_ok());
+    }
+
+    #[test]
+    fn test_too_many_rows() {
+        let error = check_row_limits(&[row(""), row(""), row("")], &config(2, 100)).unwrap_err();
+        assert_eq!(error.to_string(), "Validation error: Upload has 3 rows, more than the 2 allowed");
+    }
+
+    #[test]
+    fn test_oversized_field_names_its_row() {
+        let rows = [row("fine"), row(&"x".repeat(101))];
+        let error = check_row_limits(&rows, &config(10, 100)).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Validation error: Field notes at index 1 is 101 bytes, more than the 100 allowed"
+        );
+    }
+}
@@ -0,0 +1,199 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// A key found in an upload that its format does not declare
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnknownKey {
+    /// Dotted path, `[]` standing for any array element, e.g. "[].gpu_temp"
+    pub path: String,
+    /// Objects the key appeared in
+    pub occurrences: usize,
+}
+
+/// How an uploaded document's keys differ from its format's JSON Schema
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaDrift {
+    /// Keys the format does not declare, ignored when parsing
+    pub unknown_keys: Vec<UnknownKey>,
+    /// Required keys absent from at least one object at their path
+    pub missing_keys: Vec<String>,
+}
+
+impl SchemaDrift {
+    pub fn is_empty(&self) -> bool {
+        self.unknown_keys.is_empty() && self.missing_keys.is_empty()
+    }
+}
+
+/// Compare a document's keys with the JSON Schema of its format
+///
+/// Only objects the schema describes with `properties` are checked; maps and
+/// free-form values are not. Only keys the schema lists as `required` can be
+/// missing, so optional fields left out of an export are not reported.
+pub fn detect_schema_drift(schema: &Value, document: &Value) -> SchemaDrift {
+    let mut walk = DriftWalk {
+        root: schema,
+        missing: BTreeSet::new(),
+        unknown: BTreeMap::new(),
+    };
+    walk.visit(schema, document, "");
+
+    SchemaDrift {
+        unknown_keys: walk
+            .unknown
+            .into_iter()
+            .map(|(path, occurrences)| UnknownKey { path, occurrences })
+            .collect(),
+        missing_keys: walk.missing.into_iter().collect(),
+    }
+}
+
+struct DriftWalk<'a> {
+    root: &'a Value,
+    missing: BTreeSet<String>,
+    unknown: BTreeMap<String, usize>,
+}
+
+impl<'a> DriftWalk<'a> {
+    fn visit(&mut self, schema: &'a Value, value: &Value, path: &str) {
+        match value {
+            Value::Object(object) => {
+                let Some((properties, required)) = self.properties(schema) else { return };
+                for key in required.iter().filter(|key| !object.contains_key(**key)) {
+                    self.missing.insert(child_path(path, key));
+                }
+                for (key, child) in object {
+                    let key_path = child_path(path, key);
+                    match properties.get(key.as_str()) {
+                        Some(property_schema) => self.visit(property_schema, child, &key_path),
+                        None => *self.unknown.entry(key_path).or_default() += 1,
+                    }
+                }
+            }
+            Value::Array(items) => {
+                let Some(item_schema) = self.items(schema) else { return };
+                let item_path = format!("{}[]", path);
+                for item in items {
+                    self.visit(item_schema, item, &item_path);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Declared and required properties of an object schema, merged across `$ref`, `allOf`, `anyOf` and `oneOf`
+    fn properties(&self, schema: &'a Value) -> Option<(BTreeMap<&'a str, &'a Value>, BTreeSet<&'a str>)> {
+        let mut merged: Option<(BTreeMap<&'a str, &'a Value>, BTreeSet<&'a str>)> = None;
+        for subschema in self.subschemas(schema) {
+            if let Some(properties) = subschema.get("properties").and_then(Value::as_object) {
+                let (declared, required) = merged.get_or_insert_with(Default::default);
+                declared.extend(properties.iter().map(|(key, value)| (key.as_str(), value)));
+                if let Some(keys) = subschema.get("required").and_then(Value::as_array) {
+                    required.extend(keys.iter().filter_map(Value::as_str));
+                }
+            }
+        }
+        merged
+    }
+
+    fn items(&self, schema: &'a Value) -> Option<&'a Value> {
+        self.subschemas(schema).into_iter().find_map(|subschema| subschema.get("items"))
+    }
+
+    /// The schema itself and every schema it references or combines, with `$ref`s resolved
+    fn subschemas(&self, schema: &'a Value) -> Vec<&'a Value> {
+        let mut found = Vec::new();
+        let mut pending = vec![schema];
+        while let Some(schema) = pending.pop() {
+            // Bounded, in case of recursive definitions
+            if found.len() > 64 {
+                break;
+            }
+            found.push(schema);
+            if let Some(target) = schema.get("$ref").and_then(Value::as_str).and_then(|reference| self.resolve(reference)) {
+                pending.push(target);
+            }
+            for combinator in ["allOf", "anyOf", "oneOf"] {
+                if let Some(members) = schema.get(combinator).and_then(Value::as_array) {
+                    pending.extend(members);
+                }
+            }
+        }
+        found
+    }
+
+    /// Resolve a local `#/...` reference against the root schema
+    fn resolve(&self, reference: &str) -> Option<&'a Value> {
+        self.root.pointer(reference.strip_prefix('#')?)
+    }
+}
+
+fn child_path(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "required": ["version", "results"],
+            "properties": {
+                "version": { "type": "string" },
+                "system": { "anyOf": [{ "$ref": "#/$defs/System" }, { "type": "null" }] },
+                "results": { "type": "array", "items": { "$ref": "#/$defs/Result" } },
+                "extras": { "type": "object", "additionalProperties": { "type": "string" } }
+            },
+            "$defs": {
+                "System": { "type": "object", "properties": { "os": { "type": "string" } } },
+                "Result": {
+                    "required": ["its", "steps"],
+                    "allOf": [{ "$ref": "#/$defs/Base" }],
+                    "properties": { "its": { "type": "number" }, "steps": { "type": "integer" } }
+                },
+                "Base": { "type": "object", "required": ["model"], "properties": { "model": { "type": "string" } } }
+            }
+        })
+    }
+
+    #[test]
+    fn test_matching_document_has_no_drift() {
+        let document = json!({
+            "version": "1",
+            "system": { "os": "linux" },
+            "results": [{ "its": 1.5, "steps": 20, "model": "sdxl" }, { "its": 2.0, "steps": 30, "model": "sd15" }],
+            "extras": { "anything": "goes" }
+        });
+
+        assert!(detect_schema_drift(&schema(), &document).is_empty());
+    }
+
+    #[test]
+    fn test_reports_unknown_and_missing_keys() {
+        let document = json!({
+            "system": { "os": "linux", "kernel": "6.1" },
+            "results": [{ "its": 1.5, "steps": 20, "gpu_temp": 70 }, { "its": 2.0, "model": "sd15", "gpu_temp": 65 }]
+        });
+
+        let drift = detect_schema_drift(&schema(), &document);
+
+        assert_eq!(
+            drift.unknown_keys,
+            vec![
+                UnknownKey { path: "results[].gpu_temp".to_string(), occurrences: 2 },
+                UnknownKey { path: "system.kernel".to_string(), occurrences: 1 },
+            ]
+        );
+        assert_eq!(drift.missing_keys, vec!["results[].model", "results[].steps", "version"]);
+    }
+}
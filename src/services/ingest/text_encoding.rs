@@ -0,0 +1,308 @@
+use serde::{Deserialize, Serialize};
+
+use crate::handlers::validation::RunData;
+
+/// First code point of the private use plane standing in for repaired characters
+///
+/// Repairs are left in the decoded text as placeholders so they can be traced
+/// to the rows they end up in once the document is parsed. A file that already
+/// holds characters of the plane, raw or as JSON `\u` escapes, is decoded
+/// without placeholders and its repairs are only counted.
+const REPAIR_PLACEHOLDER_BASE: u32 = 0x10_0000;
+
+/// Windows-1252 characters of bytes 0x80-0x9F; the five bytes it leaves undefined are `None`
+const WINDOWS_1252_HIGH_CONTROLS: [Option<char>; 32] = [
+    Some('€'), None, Some('‚'), Some('ƒ'), Some('„'), Some('…'), Some('†'), Some('‡'),
+    Some('ˆ'), Some('‰'), Some('Š'), Some('‹'), Some('Œ'), None, Some('Ž'), None,
+    None, Some('‘'), Some('’'), Some('“'), Some('”'), Some('•'), Some('–'), Some('—'),
+    Some('˜'), Some('™'), Some('š'), Some('›'), Some('œ'), None, Some('ž'), Some('Ÿ'),
+];
+
+/// An uploaded file decoded to text, with the characters that needed repairing
+pub struct DecodedUpload {
+    /// The text, repaired characters still as placeholders
+    pub text: String,
+    pub encoding: &'static str,
+    pub byte_order_mark: bool,
+    /// Whether repairs go into the text as placeholders, or as the characters themselves
+    placeholders: bool,
+    /// Characters the placeholders stand for, by placeholder offset from `REPAIR_PLACEHOLDER_BASE`
+    replacements: Vec<char>,
+    repaired_characters: usize,
+}
+
+/// The characters of one row that were not valid in the file's encoding
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RowEncodingRepair {
+    /// Index of the row in the upload
+    pub row: usize,
+    /// Fields holding repaired characters, in `RunData` order
+    pub fields: Vec<String>,
+    /// The repaired characters as they now read, in order, e.g. `“”`
+    pub characters: String,
+}
+
+/// How an upload was decoded, when it was not clean UTF-8
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EncodingReport {
+    /// `utf-8`, `utf-16le` or `utf-16be`
+    pub encoding: String,
+    pub byte_order_mark: bool,
+    /// Bytes read as Windows-1252, plus anything undecodable replaced with U+FFFD
+    pub repaired_characters: usize,
+    /// Rows holding repaired characters; repairs outside the rows' fields, or in a
+    /// file that already used the placeholder plane, are only counted
+    pub rows: Vec<RowEncodingRepair>,
+}
+
+impl DecodedUpload {
+    /// Put the repaired characters in place of their placeholders in `rows`, reporting them per row
+    ///
+    /// Returns `None` for a file that was clean UTF-8 without a byte order mark.
+    pub fn restore(&self, rows: &mut [RunData]) -> Option<EncodingReport> {
+        if !self.byte_order_mark && self.repaired_characters == 0 {
+            return None;
+        }
+
+        let mut repaired_rows = Vec::new();
+        if !self.replacements.is_empty() {
+            for (index, row) in rows.iter_mut().enumerate() {
+                let mut fields = Vec::new();
+                let mut characters = String::new();
+                for (field, value) in run_data_fields(row) {
+                    let before = characters.len();
+                    *value = value.chars().map(|c| self.replacement(c, &mut characters)).collect();
+                    if characters.len() > before {
+                        fields.push(field.to_string());
+                    }
+                }
+                if !fields.is_empty() {
+                    repaired_rows.push(RowEncodingRepair { row: index, fields, characters });
+                }
+            }
+        }
+
+        Some(EncodingReport {
+            encoding: self.encoding.to_string(),
+            byte_order_mark: self.byte_order_mark,
+            repaired_characters: self.repaired_characters,
+            rows: repaired_rows,
+        })
+    }
+
+    /// The character `c` stands for, noting it in `repaired` when it is a placeholder
+    fn replacement(&self, c: char, repaired: &mut String) -> char {
+        let repair = (c as u32)
+            .checked_sub(REPAIR_PLACEHOLDER_BASE)
+            .and_then(|offset| self.replacements.get(offset as usize));
+        match repair {
+            Some(&repair) => {
+                repaired.push(repair);
+                repair
+            }
+            None => c,
+        }
+    }
+
+    /// Append `repair` to the text as its placeholder
+    fn push_repair(&mut self, repair: char) {
+        self.repaired_characters += 1;
+        if !self.placeholders {
+            self.text.push(repair);
+            return;
+        }
+
+        let offset = match self.replacements.iter().position(|&c| c == repair) {
+            Some(offset) => offset,
+            None => {
+                self.replacements.push(repair);
+                self.replacements.len() - 1
+            }
+        };
+        let placeholder = char::from_u32(REPAIR_PLACEHOLDER_BASE + offset as u32)
+            .expect("Windows-1252 and U+FFFD give far fewer repairs than the plane holds");
+        self.text.push(placeholder);
+    }
+}
+
+/// Decode an uploaded file, repairing what is not valid instead of rejecting the file
+///
+/// A UTF-8 byte order mark is dropped, and a UTF-16 one selects UTF-16. Bytes
+/// that are not UTF-8 are read as Windows-1252, which is what spreadsheet and
+/// editor exports on Windows produce, e.g. curly quotes as 0x93/0x94; bytes it
+/// leaves undefined, and unpaired UTF-16 surrogates, become U+FFFD. Every such
+/// character is counted, and `DecodedUpload::restore` traces it to its row.
+pub fn decode_upload(bytes: &[u8]) -> DecodedUpload {
+    let decoded = decode(bytes, false);
+    // Placeholders would be mistaken for characters the file really holds
+    if decoded.repaired_characters == 0 || uses_placeholder_plane(&decoded.text) {
+        return decoded;
+    }
+    decode(bytes, true)
+}
+
+fn decode(bytes: &[u8], placeholders: bool) -> DecodedUpload {
+    let (encoding, byte_order_mark, body) = match bytes {
+        [0xEF, 0xBB, 0xBF, rest @ ..] => ("utf-8", true, rest),
+        [0xFF, 0xFE, rest @ ..] => ("utf-16le", true, rest),
+        [0xFE, 0xFF, rest @ ..] => ("utf-16be", true, rest),
+        _ => ("utf-8", false, bytes),
+    };
+    let mut decoded = DecodedUpload {
+        text: String::with_capacity(body.len()),
+        encoding,
+        byte_order_mark,
+        placeholders,
+        replacements: Vec::new(),
+        repaired_characters: 0,
+    };
+
+    if encoding == "utf-8" {
+        for chunk in body.utf8_chunks() {
+            decoded.text.push_str(chunk.valid());
+            for &byte in chunk.invalid() {
+                decoded.push_repair(windows_1252_char(byte));
+            }
+        }
+    } else {
+        let units = body.chunks(2).map(|pair| match (encoding, pair) {
+            ("utf-16le", [low, high]) => u16::from_le_bytes([*low, *high]),
+            (_, [high, low]) => u16::from_be_bytes([*high, *low]),
+            // A dangling odd byte, decoded as an unpaired surrogate
+            _ => 0xD800,
+        });
+        for c in char::decode_utf16(units) {
+            match c {
+                Ok(c) => decoded.text.push(c),
+                Err(_) => decoded.push_repair(char::REPLACEMENT_CHARACTER),
+            }
+        }
+    }
+
+    decoded
+}
+
+/// Whether `text` holds a character of the placeholder plane, or a JSON `\u` escape of one
+fn uses_placeholder_plane(text: &str) -> bool {
+    text.chars().any(|c| c as u32 >= REPAIR_PLACEHOLDER_BASE)
+        || text.match_indices("\\u").any(|(start, _)| {
+            // Characters of the plane are escaped as a pair starting with a high surrogate from U+DBC0
+            text.get(start + 2..start + 6)
+                .and_then(|hex| u16::from_str_radix(hex, 16).ok())
+                .is_some_and(|unit| (0xDBC0..=0xDBFF).contains(&unit))
+        })
+}
+
+/// The Windows-1252 character of a byte that is not valid UTF-8
+fn windows_1252_char(byte: u8) -> char {
+    match byte {
+        0x80..=0x9F => WINDOWS_1252_HIGH_CONTROLS[(byte - 0x80) as usize].unwrap_or(char::REPLACEMENT_CHARACTER),
+        // Latin-1 and Windows-1252 agree from 0xA0 up; lower bytes are ASCII and never invalid
+        _ => char::from(byte),
+    }
+}
+
+fn run_data_fields(row: &mut RunData) -> [(&'static str, &mut String); 10] {
+    [
+        ("timestamp", &mut row.timestamp),
+        ("vram_usage", &mut row.vram_usage),
+        ("info", &mut row.info),
+        ("system_info", &mut row.system_info),
+        ("model_info", &mut row.model_info),
+        ("device_info", &mut row.device_info),
+        ("xformers", &mut row.xformers),
+        ("model_name", &mut row.model_name),
+        ("user", &mut row.user),
+        ("notes", &mut row.notes),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(notes: &str, device_info: &str) -> RunData {
+        RunData {
+            timestamp: "2024-01-01T00:00:00Z".to_string(),
+            vram_usage: "1.0/2.0".to_string(),
+            info: "app:test".to_string(),
+            system_info: String::new(),
+            model_info: String::new(),
+            device_info: device_info.to_string(),
+            xformers: String::new(),
+            model_name: String::new(),
+            user: String::new(),
+            notes: notes.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_clean_utf8_is_not_reported() {
+        let decoded = decode_upload("[\"“quoted” naïve\"]".as_bytes());
+        assert_eq!(decoded.text, "[\"“quoted” naïve\"]");
+        assert!(decoded.restore(&mut [row("", "")]).is_none());
+    }
+
+    #[test]
+    fn test_utf8_byte_order_mark_is_dropped() {
+        let decoded = decode_upload(b"\xEF\xBB\xBF[]");
+        assert_eq!(decoded.text, "[]");
+        let report = decoded.restore(&mut []).unwrap();
+        assert!(report.byte_order_mark);
+        assert_eq!(report.repaired_characters, 0);
+    }
+
+    #[test]
+    fn test_windows_1252_bytes_are_repaired_per_row() {
+        let decoded = decode_upload(b"\x93fast\x94 caf\xE9 \x81");
+        let mut rows = [row("plain", "AMD"), row(&decoded.text, "Radeon\u{2122}")];
+
+        let report = decoded.restore(&mut rows).unwrap();
+        assert_eq!(rows[1].notes, "“fast” café \u{FFFD}");
+        assert_eq!(report.encoding, "utf-8");
+        assert_eq!(report.repaired_characters, 4);
+        assert_eq!(
+            report.rows,
+            [RowEncodingRepair { row: 1, fields: vec!["notes".to_string()], characters: "“”é\u{FFFD}".to_string() }]
+        );
+        // A ™ that was valid UTF-8 in the first place is left alone
+        assert_eq!(rows[1].device_info, "Radeon\u{2122}");
+    }
+
+    #[test]
+    fn test_files_using_the_placeholder_plane_only_count_repairs() {
+        // A real U+100000 next to a Windows-1252 curly quote
+        let decoded = decode_upload(b"\xF4\x80\x80\x80 \x93fast\x94");
+        let mut rows = [row(&decoded.text, "")];
+        let report = decoded.restore(&mut rows).unwrap();
+        assert_eq!(rows[0].notes, "\u{100000} “fast”");
+        assert_eq!(report.repaired_characters, 2);
+        assert!(report.rows.is_empty());
+
+        // The same character escaped in JSON, which only turns into it once parsed
+        let decoded = decode_upload(b"[\"\\uDBC0\\uDC00 \x93fast\x94\"]");
+        assert_eq!(decoded.text, "[\"\\uDBC0\\uDC00 “fast”\"]");
+        let mut rows = [row("\u{100000} “fast”", "")];
+        let report = decoded.restore(&mut rows).unwrap();
+        assert_eq!(rows[0].notes, "\u{100000} “fast”");
+        assert_eq!(report.repaired_characters, 2);
+        assert!(report.rows.is_empty());
+    }
+
+    #[test]
+    fn test_utf16_with_byte_order_mark() {
+        let mut bytes = vec![0xFF, 0xFE];
+        bytes.extend("[\"é\"]".encode_utf16().flat_map(u16::to_le_bytes));
+        assert_eq!(decode_upload(&bytes).text, "[\"é\"]");
+
+        let mut bytes = vec![0xFE, 0xFF];
+        bytes.extend("ok".encode_utf16().flat_map(u16::to_be_bytes));
+        bytes.extend([0xD8, 0x00]);
+        let decoded = decode_upload(&bytes);
+        let mut rows = [row(&decoded.text, "")];
+        let report = decoded.restore(&mut rows).unwrap();
+        assert_eq!(rows[0].notes, "ok\u{FFFD}");
+        assert_eq!(report.encoding, "utf-16be");
+        assert_eq!(report.repaired_characters, 1);
+    }
+}
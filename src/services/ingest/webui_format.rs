@@ -0,0 +1,136 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    error::types::AppError,
+    handlers::validation::RunData,
+    services::ingest::BenchmarkFormat,
+};
+
+/// One row of the WebUI export
+///
+/// Version 2 rows may also report system memory and platform bitness. They
+/// are folded into `system_info` as "ram:", "swap:" and "bits:" keys so the
+/// system info processing step picks them up.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WebUiRow {
+    #[serde(flatten)]
+    pub run: RunData,
+    /// Installed system memory in GB
+    pub ram_gb: Option<f64>,
+    /// Swap size in GB
+    pub swap_gb: Option<f64>,
+    /// Platform bitness, 32 or 64
+    pub os_bits: Option<i64>,
+}
+
+impl WebUiRow {
+    fn into_run_data(self) -> RunData {
+        let mut run = self.run;
+        let fields = [
+            ("ram", self.ram_gb.map(|ram_gb| ram_gb.to_string())),
+            ("swap", self.swap_gb.map(|swap_gb| swap_gb.to_string())),
+            ("bits", self.os_bits.map(|os_bits| os_bits.to_string())),
+        ];
+
+        for (key, value) in fields {
+            let prefix = format!("{}:", key);
+            let present = run.system_info.split_whitespace().any(|part| part.starts_with(&prefix));
+            if let Some(value) = value
+                && !present
+            {
+                if !run.system_info.is_empty() {
+                    run.system_info.push(' ');
+                }
+                run.system_info.push_str(&format!("{}{}", prefix, value));
+            }
+        }
+
+        run
+    }
+}
+
+/// The original SD WebUI benchmark export: a JSON array of run rows
+pub struct WebUiFormat;
+
+impl BenchmarkFormat for WebUiFormat {
+    fn name(&self) -> &'static str {
+        "webui"
+    }
+
+    fn version(&self) -> &'static str {
+        "2"
+    }
+
+    fn description(&self) -> &'static str {
+        "SD WebUI benchmark extension export: a JSON array of run rows, optionally with RAM, swap and OS bitness"
+    }
+
+    fn schema(&self) -> Value {
+        schemars::schema_for!(Vec<WebUiRow>).to_value()
+    }
+
+    fn detect(&self, document: &Value) -> bool {
+        match document.as_array() {
+            Some(rows) => rows
+                .first()
+                .is_none_or(|row| row.get("vram_usage").is_some() && row.get("info").is_some()),
+            None => false,
+        }
+    }
+
+    fn to_run_data(&self, document: &Value) -> Result<Vec<RunData>, AppError> {
+        let rows: Vec<WebUiRow> = serde_json::from_value(document.clone())
+            .map_err(|e| AppError::bad_request(format!("Invalid WebUI benchmark data: {}", e)))?;
+
+        Ok(rows.into_iter().map(WebUiRow::into_run_data).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_detect_webui_format() {
+        let document = json!([{ "vram_usage": "1.5/2.0", "info": "app:test" }]);
+        assert!(WebUiFormat.detect(&document));
+        assert!(WebUiFormat.detect(&json!([])));
+        assert!(!WebUiFormat.detect(&json!({ "comfyui_version": "0.2.2" })));
+    }
+
+    fn row(system_info: &str) -> Value {
+        json!({
+            "timestamp": "2024-01-01T00:00:00Z",
+            "vram_usage": "10.5/11.0",
+            "info": "app:automatic1111",
+            "system_info": system_info,
+            "model_info": "torch:2.1.0",
+            "device_info": "device:NVIDIA GeForce RTX 4090",
+            "xformers": "True",
+            "model_name": "sd_xl_base_1.0",
+            "user": "tester",
+            "notes": ""
+        })
+    }
+
+    #[test]
+    fn test_webui_v2_memory_fields() {
+        let mut v2_row = row("arch:x86_64 system:Linux");
+        v2_row["ram_gb"] = json!(31.9);
+        v2_row["swap_gb"] = json!(8);
+        v2_row["os_bits"] = json!(64);
+
+        let mut reported_row = row("arch:x86_64 ram:64GB");
+        reported_row["ram_gb"] = json!(32);
+
+        let rows = WebUiFormat.to_run_data(&json!([v2_row, reported_row, row("arch:x86_64")])).unwrap();
+        assert_eq!(rows[0].system_info, "arch:x86_64 system:Linux ram:31.9 swap:8 bits:64");
+        // Keys already in the system_info string win
+        assert_eq!(rows[1].system_info, "arch:x86_64 ram:64GB");
+        // Version 1 rows are accepted unchanged
+        assert_eq!(rows[2].system_info, "arch:x86_64");
+    }
+}
@@ -0,0 +1,61 @@
+use crate::config::settings::LaptopDetectionConfig;
+
+/// Tells laptop GPUs from desktop ones by the configured device name patterns
+pub struct LaptopDetector<'a> {
+    config: &'a LaptopDetectionConfig,
+}
+
+impl<'a> LaptopDetector<'a> {
+    pub fn new(config: &'a LaptopDetectionConfig) -> Self {
+        Self { config }
+    }
+
+    /// The first pattern found in the device name, ignoring case; none for a desktop part
+    pub fn matching_pattern(&self, device: &str) -> Option<&'a str> {
+        let device = device.to_lowercase();
+
+        self.config
+            .patterns
+            .iter()
+            .find(|pattern| !pattern.is_empty() && device.contains(&pattern.to_lowercase()))
+            .map(String::as_str)
+    }
+
+    pub fn is_laptop(&self, device: &str) -> bool {
+        self.matching_pattern(device).is_some()
+    }
+
+    pub fn patterns(&self) -> &'a [String] {
+        &self.config.patterns
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_patterns_ignore_case() {
+        let config = LaptopDetectionConfig::default();
+        let detector = LaptopDetector::new(&config);
+
+        assert_eq!(detector.matching_pattern("NVIDIA GeForce RTX 3070 Laptop GPU"), Some("Laptop GPU"));
+        assert_eq!(detector.matching_pattern("nvidia geforce rtx 2080 with max-q design"), Some("Max-Q"));
+        assert_eq!(detector.matching_pattern("AMD Radeon RX 6800M"), Some("RX 6800M"));
+        assert_eq!(detector.matching_pattern("NVIDIA GeForce MX450"), Some("MX450"));
+        assert!(!detector.is_laptop("NVIDIA GeForce RTX 4090"));
+        assert!(!detector.is_laptop("AMD Radeon RX 6800"));
+    }
+
+    #[test]
+    fn test_first_listed_pattern_wins() {
+        let config = LaptopDetectionConfig {
+            patterns: vec!["Mobile".to_string(), "RX 6800M".to_string(), String::new()],
+        };
+        let detector = LaptopDetector::new(&config);
+
+        assert_eq!(detector.matching_pattern("AMD Radeon RX 6800M Mobile"), Some("Mobile"));
+        // An empty pattern matches nothing
+        assert_eq!(detector.matching_pattern("AMD Radeon RX 6800"), None);
+    }
+}
@@ -0,0 +1,210 @@
+use std::io::Write;
+
+use chrono::DateTime;
+use serde_json::json;
+
+/// Seed used when none is given, so two runs produce the same dataset
+pub const DEFAULT_GENERATOR_SEED: u64 = 42;
+
+/// Runs are spread over the two years from 2023-01-01
+const FIRST_TIMESTAMP: i64 = 1_672_531_200;
+const TIMESTAMP_SPAN_SECONDS: u64 = 2 * 365 * 24 * 3600;
+
+/// Device strings as the webui extension submits them, with a typical avg_its
+const DEVICES: &[(&str, f64)] = &[
+    ("device:NVIDIA GeForce RTX 4090 24GB driver:536.99", 25.0),
+    ("device:NVIDIA GeForce RTX 4080 16GB driver:536.99", 19.5),
+    ("device:NVIDIA GeForce RTX 4070 Ti 12GB driver:537.13", 15.0),
+    ("device:NVIDIA GeForce RTX 3090 24GB driver:531.79", 17.0),
+    ("device:NVIDIA GeForce RTX 3080 Laptop GPU 16GB driver:531.41", 10.5),
+    ("device:NVIDIA GeForce RTX 3060 12GB driver:531.41", 8.0),
+    ("device:NVIDIA GeForce RTX 2070 SUPER 8GB driver:537.13", 6.8),
+    ("device:NVIDIA GeForce GTX 1660 Ti 6GB driver:531.61", 3.2),
+    ("device:NVIDIA A100-SXM4-40GB 40GB driver:525.85.12", 22.0),
+    ("device:NVIDIA Tesla T4 16GB driver:525.105.17", 4.5),
+    ("device:AMD Radeon RX 7900 XTX 24GB driver:23.7.1", 18.0),
+    ("device:AMD Radeon RX 6800 XT 16GB driver:23.5.2", 9.5),
+    ("device:Intel(R) Arc(TM) A770 Graphics 16GB driver:31.0.101.4575", 9.0),
+];
+
+const APPS: &[&str] = &[
+    "app:stable-diffusion-webui updated:2023-08-31 hash:5ef669de url:https://github.com/AUTOMATIC1111/stable-diffusion-webui/tree/master",
+    "app:stable-diffusion-webui updated:2024-01-13 hash:cf2772fa url:https://github.com/AUTOMATIC1111/stable-diffusion-webui/tree/master",
+    "app:automatic updated:2023-09-14 hash:3c6b7d94 url:https://github.com/vladmandic/automatic/tree/master",
+    "app:automatic updated:2024-02-01 hash:6a8b7e12 url:https://github.com/vladmandic/automatic/tree/dev",
+    "app:stable-diffusion-webui-forge updated:2024-02-06 hash:b59deaa3 url:https://github.com/lllyasviel/stable-diffusion-webui-forge/tree/main",
+];
+
+const SYSTEMS: &[&str] = &[
+    "arch:x86_64 cpu:AMD64 Family 25 Model 33 Stepping 0, AuthenticAMD system:Windows release:10 python:3.10.6 ram:32GB swap:8GB bits:64",
+    "arch:x86_64 cpu:Intel64 Family 6 Model 151 Stepping 2, GenuineIntel system:Windows release:10 python:3.10.11 ram:16GB swap:4GB bits:64",
+    "arch:x86_64 cpu:x86_64 system:Linux release:5.15.0-83-generic python:3.10.12 ram:64GB swap:16GB bits:64",
+    "arch:x86_64 cpu:x86_64 system:Linux release:6.2.0-33-generic python:3.11.5 ram:128GB swap:2GB bits:64",
+];
+
+const LIBRARIES: &[(&str, &str)] = &[
+    ("torch:2.0.1+cu118 xformers:0.0.20 diffusers:0.19.3 transformers:4.30.2", "True"),
+    ("torch:2.1.0+cu121 xformers:0.0.22 diffusers:0.21.4 transformers:4.33.2", "True"),
+    ("torch:2.1.2+cu121 diffusers:0.25.0 transformers:4.36.2", "False"),
+    ("torch:2.0.0+rocm5.4.2 diffusers:0.19.3 transformers:4.30.2", "False"),
+];
+
+/// Model names with their speed relative to SD 1.5
+const MODELS: &[(&str, f64)] = &[
+    ("v1-5-pruned-emaonly.safetensors [6ce0161689]", 1.0),
+    ("dreamshaper_8.safetensors [879db523c3]", 1.0),
+    ("realisticVisionV51_v51VAE.safetensors [15012c538f]", 1.0),
+    ("sd_xl_base_1.0.safetensors [31e35c80fc]", 0.35),
+    ("juggernautXL_v9.safetensors [c9e3e68f89]", 0.35),
+];
+
+const NOTES: &[&str] = &["", "", "", "medvram", "batch 4", "sdp attention", "fp16", "no-half-vae"];
+
+/// Deterministic synthetic benchmark exports for load and performance testing
+///
+/// Runs are drawn from fixed lists of real-looking devices, apps, systems,
+/// libraries and models, with ITS values around each device's typical speed,
+/// so the processing steps and stats see the same shapes as real uploads.
+/// The same seed always gives the same export.
+pub struct SyntheticDataGenerator {
+    state: u64,
+}
+
+impl SyntheticDataGenerator {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// A webui-format export with `rows` runs
+    pub fn export(&mut self, rows: usize) -> Vec<u8> {
+        // Submitters come back, so there are about 20 runs per user
+        let users = rows / 20 + 1;
+
+        let mut export = Vec::with_capacity(rows * 700 + 2);
+        export.push(b'[');
+        for index in 0..rows {
+            if index > 0 {
+                export.push(b',');
+            }
+            let (device, device_its) = self.pick(DEVICES);
+            let (model_name, model_speed) = self.pick(MODELS);
+            let (model_info, xformers) = self.pick(LIBRARIES);
+            let timestamp = DateTime::from_timestamp(FIRST_TIMESTAMP + (self.next() % TIMESTAMP_SPAN_SECONDS) as i64, 0).unwrap_or_default();
+            let its = (0..3)
+                .map(|_| format!("{:.2}", device_its * model_speed * (0.85 + 0.3 * self.unit())))
+                .collect::<Vec<_>>()
+                .join("/");
+            let run = json!({
+                "timestamp": timestamp.format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string(),
+                "vram_usage": its,
+                "info": self.pick(APPS),
+                "system_info": self.pick(SYSTEMS),
+                "model_info": model_info,
+                "device_info": device,
+                "xformers": xformers,
+                "model_name": model_name,
+                "user": format!("loadtest-{}", self.next() as usize % users),
+                "notes": self.pick(NOTES),
+            });
+            // Writing to a Vec cannot fail
+            let _ = serde_json::to_writer(&mut export, &run);
+        }
+        let _ = export.write_all(b"]");
+
+        export
+    }
+
+    /// splitmix64
+    fn next(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in [0, 1)
+    fn unit(&mut self) -> f64 {
+        (self.next() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn pick<T: Copy>(&mut self, items: &[T]) -> T {
+        items[self.next() as usize % items.len()]
+    }
+}
+
+/// The `generate` subcommand: `generate --rows N [--seed N] [--output PATH] [--ingest]`
+#[derive(Debug, PartialEq)]
+pub struct GenerateCommand {
+    pub rows: usize,
+    pub seed: u64,
+    /// File to write the export to; defaults to `synthetic_<rows>_<seed>.json`
+    pub output: Option<String>,
+    /// Append the export to the configured database instead of writing it out
+    pub ingest: bool,
+}
+
+impl GenerateCommand {
+    /// Parse the arguments following `generate`
+    pub fn parse(args: &[String]) -> Result<Self, String> {
+        let mut rows = None;
+        let mut seed = DEFAULT_GENERATOR_SEED;
+        let mut output = None;
+        let mut ingest = false;
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().ok_or_else(|| format!("{} needs a value", arg));
+            match arg.as_str() {
+                "--rows" => rows = Some(value()?.parse().map_err(|e| format!("Invalid --rows: {}", e))?),
+                "--seed" => seed = value()?.parse().map_err(|e| format!("Invalid --seed: {}", e))?,
+                "--output" => output = Some(value()?.clone()),
+                "--ingest" => ingest = true,
+                other => return Err(format!("Unknown argument: {}", other)),
+            }
+        }
+
+        Ok(Self {
+            rows: rows.ok_or("--rows is required")?,
+            seed,
+            output,
+            ingest,
+        })
+    }
+
+    pub fn output_path(&self) -> String {
+        self.output.clone().unwrap_or_else(|| format!("synthetic_{}_{}.json", self.rows, self.seed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_export_is_reproducible_per_seed() {
+        let export = SyntheticDataGenerator::new(7).export(50);
+        assert_eq!(export, SyntheticDataGenerator::new(7).export(50));
+        assert_ne!(export, SyntheticDataGenerator::new(8).export(50));
+
+        let runs: Vec<serde_json::Value> = serde_json::from_slice(&export).unwrap();
+        assert_eq!(runs.len(), 50);
+        assert!(runs.iter().all(|run| run["vram_usage"].as_str().unwrap().split('/').count() == 3));
+        assert_eq!(serde_json::from_slice::<Vec<serde_json::Value>>(&SyntheticDataGenerator::new(7).export(0)).unwrap().len(), 0);
+    }
+
+    #[test]
+    fn test_generate_command_parses_flags() {
+        let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+
+        let command = GenerateCommand::parse(&args(&["--rows", "100000", "--ingest"])).unwrap();
+        assert_eq!(command, GenerateCommand { rows: 100_000, seed: DEFAULT_GENERATOR_SEED, output: None, ingest: true });
+        assert_eq!(command.output_path(), "synthetic_100000_42.json");
+        assert_eq!(GenerateCommand::parse(&args(&["--rows", "5", "--seed", "9", "--output", "runs.json"])).unwrap().output_path(), "runs.json");
+
+        assert!(GenerateCommand::parse(&args(&["--seed", "9"])).is_err());
+        assert!(GenerateCommand::parse(&args(&["--rows"])).is_err());
+        assert!(GenerateCommand::parse(&args(&["--rows", "many"])).is_err());
+        assert!(GenerateCommand::parse(&args(&["--rows", "5", "--verbose"])).is_err());
+    }
+}
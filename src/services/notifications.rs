@@ -0,0 +1,55 @@
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::{error::types::AppError, services::alerts::PipelineAlert};
+
+/// JSON body of an alert webhook
+#[derive(Debug, Clone, Serialize)]
+pub struct AlertNotification<'a> {
+    /// Application name from settings, to tell instances apart
+    pub source: &'a str,
+    pub alerts: &'a [PipelineAlert],
+}
+
+/// Delivers notifications by POSTing JSON to a webhook URL
+#[derive(Debug, Clone)]
+pub struct WebhookNotifier {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl WebhookNotifier {
+    pub fn new(url: &str, timeout: Duration) -> Result<Self, AppError> {
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .map_err(|e| AppError::internal(format!("Failed to build webhook client: {}", e)))?;
+
+        Ok(Self {
+            client,
+            url: url.to_string(),
+        })
+    }
+
+    /// POST `payload`; any non-success status is an error
+    pub async fn send<T: Serialize + ?Sized>(&self, payload: &T) -> Result<(), AppError> {
+        let response = self
+            .client
+            .post(&self.url)
+            .json(payload)
+            .send()
+            .await
+            .map_err(|e| AppError::internal(format!("Webhook request to {} failed: {}", self.url, e)))?;
+
+        if !response.status().is_success() {
+            return Err(AppError::internal(format!(
+                "Webhook {} answered {}",
+                self.url,
+                response.status()
+            )));
+        }
+
+        Ok(())
+    }
+}
@@ -1,13 +1,19 @@
 // Parser modules for data transformation
+pub mod tokenizer;
 pub mod app_details_parser;
 pub mod system_info_parser;
 pub mod gpu_info_parser;
 pub mod libraries_parser;
 pub mod performance_parser;
+pub mod model_name_parser;
+pub mod cpu_parser;
 
 // Re-export all parsers for easy access
+pub use tokenizer::*;
 pub use app_details_parser::*;
 pub use system_info_parser::*;
 pub use gpu_info_parser::*;
 pub use libraries_parser::*;
-pub use performance_parser::*; 
\ No newline at end of file
+pub use performance_parser::*;
+pub use model_name_parser::*;
+pub use cpu_parser::*; 
\ No newline at end of file
@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+use super::tokenizer::tokenize;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ParsedAppDetails {
     pub app_name: Option<String>,
     pub updated: Option<String>,
@@ -8,8 +10,27 @@ pub struct ParsedAppDetails {
     pub url: Option<String>,
 }
 
+/// The host and repository an app URL points at, lowercased
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParsedAppUrl {
+    pub host: String,
+    pub owner: String,
+    pub repo: String,
+}
+
+impl ParsedAppUrl {
+    /// `owner/repo`, the key of `settings.app_attribution.fork_map`
+    pub fn repository(&self) -> String {
+        format!("{}/{}", self.owner, self.repo)
+    }
+}
+
 pub struct AppDetailsParser;
 
+/// Version of `AppDetailsParser`, recorded on the AppDetails rows it produces; the
+/// whitespace-splitting parser used by the process-app-details handler is version 1
+pub const APP_DETAILS_PARSER_VERSION: i64 = 2;
+
 impl AppDetailsParser {
     /// Parse application details from the info string
     /// 
@@ -21,7 +42,6 @@ impl AppDetailsParser {
     /// # Returns
     /// * `ParsedAppDetails` - Structured application details
     pub fn parse(info_string: &str) -> ParsedAppDetails {
-        let parts = info_string.split(' ');
         let mut app_details = ParsedAppDetails {
             app_name: None,
             updated: None,
@@ -29,15 +49,11 @@ impl AppDetailsParser {
             url: None,
         };
 
-        for part in parts {
-            let colon_index = match part.find(':') {
-                Some(index) => index,
-                None => continue,
+        for token in tokenize(info_string) {
+            let Some((key, value)) = token.pair() else {
+                continue;
             };
 
-            let key = &part[..colon_index];
-            let value = &part[colon_index + 1..];
-
             match key {
                 "app" => app_details.app_name = Some(value.to_string()),
                 "updated" => app_details.updated = Some(value.to_string()),
@@ -67,6 +83,86 @@ impl AppDetailsParser {
         app_details.url.is_some()
     }
 
+    /// Extract the release channel (git branch) from an app URL
+    /// 
+    /// The URL format is typically: "https://github.com/owner/repo.git/tree/master"
+    /// 
+    /// # Arguments
+    /// * `url` - The app URL to analyze
+    /// 
+    /// # Returns
+    /// * `Option<String>` - The branch name, if the URL contains one
+    pub fn parse_release_channel(url: &str) -> Option<String> {
+        let (_, branch) = url.split_once("/tree/")?;
+        let branch = branch.split('/').next().unwrap_or_default().trim();
+
+        if branch.is_empty() {
+            None
+        } else {
+            Some(branch.to_lowercase())
+        }
+    }
+
+    /// Split an app URL into host, owner and repository
+    ///
+    /// Case, a `.git` suffix, a port, credentials and anything after the
+    /// repository (e.g. `/tree/master`) are dropped, so the spellings of one
+    /// clone URL agree. SSH remotes like `git@github.com:owner/repo.git` are read too.
+    ///
+    /// # Arguments
+    /// * `url` - The app URL to analyze
+    ///
+    /// # Returns
+    /// * `Option<ParsedAppUrl>` - The components, if the URL names a host, owner and repository
+    pub fn parse_url_components(url: &str) -> Option<ParsedAppUrl> {
+        let url = url.trim();
+        let url = url.split(['?', '#']).next().unwrap_or_default();
+        let location = match url.split_once("://") {
+            Some((_, location)) => location.to_string(),
+            None => match url.strip_prefix("git@") {
+                Some(remote) => remote.replacen(':', "/", 1),
+                None => url.to_string(),
+            },
+        };
+
+        let mut segments = location.split('/').filter(|segment| !segment.is_empty());
+        let authority = segments.next()?;
+        let host = authority.rsplit('@').next().unwrap_or_default();
+        let host = host.split(':').next().unwrap_or_default().to_lowercase();
+        let owner = segments.next()?.to_lowercase();
+        let repo = segments.next()?.to_lowercase();
+        let repo = repo.strip_suffix(".git").unwrap_or(&repo).to_string();
+
+        if host.is_empty() || repo.is_empty() {
+            None
+        } else {
+            Some(ParsedAppUrl { host, owner, repo })
+        }
+    }
+
+    /// Bucket an `updated` date into its release month
+    /// 
+    /// # Arguments
+    /// * `updated` - The updated date, typically "YYYY-MM-DD"
+    /// 
+    /// # Returns
+    /// * `Option<String>` - The month as "YYYY-MM", if the date is well-formed
+    pub fn parse_release_month(updated: &str) -> Option<String> {
+        let month = updated.trim().get(..7)?;
+        let (year, month_number) = month.split_once('-')?;
+
+        let is_valid = year.len() == 4
+            && year.chars().all(|c| c.is_ascii_digit())
+            && matches!(month_number.parse::<u32>(), Ok(1..=12))
+            && month_number.len() == 2;
+
+        if is_valid {
+            Some(month.to_string())
+        } else {
+            None
+        }
+    }
+
     /// Get a summary of the parsed app details
     /// 
     /// # Arguments
@@ -164,6 +260,56 @@ mod tests {
         assert!(!AppDetailsParser::is_valid(&invalid_details));
     }
 
+    #[test]
+    fn test_parse_release_channel() {
+        assert_eq!(
+            AppDetailsParser::parse_release_channel("https://github.com/AUTOMATIC1111/stable-diffusion-webui.git/tree/master"),
+            Some("master".to_string())
+        );
+        assert_eq!(
+            AppDetailsParser::parse_release_channel("https://github.com/vladmandic/automatic/tree/dev/"),
+            Some("dev".to_string())
+        );
+        assert_eq!(AppDetailsParser::parse_release_channel("https://github.com/comfyanonymous/ComfyUI"), None);
+    }
+
+    #[test]
+    fn test_parse_url_components() {
+        let webui = Some(ParsedAppUrl {
+            host: "github.com".to_string(),
+            owner: "automatic1111".to_string(),
+            repo: "stable-diffusion-webui".to_string(),
+        });
+        assert_eq!(
+            AppDetailsParser::parse_url_components("https://github.com/AUTOMATIC1111/stable-diffusion-webui.git/tree/master"),
+            webui
+        );
+        assert_eq!(
+            AppDetailsParser::parse_url_components("  HTTPS://GitHub.com:443/automatic1111/stable-diffusion-webui/  "),
+            webui
+        );
+        assert_eq!(AppDetailsParser::parse_url_components("git@github.com:AUTOMATIC1111/stable-diffusion-webui.git"), webui);
+        assert_eq!(
+            AppDetailsParser::parse_url_components("https://token@github.com/automatic1111/stable-diffusion-webui?tab=readme"),
+            webui
+        );
+        assert_eq!(
+            AppDetailsParser::parse_url_components("https://github.com/AUTOMATIC1111/stable-diffusion-webui").unwrap().repository(),
+            "automatic1111/stable-diffusion-webui"
+        );
+
+        assert_eq!(AppDetailsParser::parse_url_components("https://github.com/vladmandic"), None);
+        assert_eq!(AppDetailsParser::parse_url_components("https://github.com/owner/.git"), None);
+        assert_eq!(AppDetailsParser::parse_url_components(""), None);
+    }
+
+    #[test]
+    fn test_parse_release_month() {
+        assert_eq!(AppDetailsParser::parse_release_month("2023-05-18"), Some("2023-05".to_string()));
+        assert_eq!(AppDetailsParser::parse_release_month("2023-13-01"), None);
+        assert_eq!(AppDetailsParser::parse_release_month("unknown"), None);
+    }
+
     #[test]
     fn test_get_summary() {
         let app_details = ParsedAppDetails {
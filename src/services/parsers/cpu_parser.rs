@@ -0,0 +1,251 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ParsedCpu {
+    pub vendor: Option<String>,
+    pub family: Option<String>,
+    pub generation: Option<i64>,
+    pub model: Option<String>,
+}
+
+pub struct CpuParser;
+
+impl CpuParser {
+    /// Normalize a SystemInfo.cpu string into vendor, family, generation and model
+    ///
+    /// Examples:
+    /// * "Intel(R) Core(TM) i7-10700K CPU @ 3.80GHz" -> Intel / i7 / 10 / i7-10700K
+    /// * "AMD Ryzen 7 5800X 8-Core Processor" -> AMD / Ryzen 7 / 5 / 5800X
+    /// * "Apple M2 Pro" -> Apple / M2 / 2 / M2 Pro
+    ///
+    /// Strings that only name the vendor (e.g. "Intel64 Family 6 Model 165
+    /// Stepping 5, GenuineIntel") get the vendor alone.
+    ///
+    /// # Arguments
+    /// * `cpu_string` - The raw CPU string to parse
+    ///
+    /// # Returns
+    /// * `ParsedCpu` - The normalized CPU fields that could be recognized
+    pub fn parse(cpu_string: &str) -> ParsedCpu {
+        let cleaned = cpu_string
+            .replace("(R)", " ")
+            .replace("(r)", " ")
+            .replace("(TM)", " ")
+            .replace("(tm)", " ");
+        let tokens: Vec<&str> = cleaned
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|token| !token.is_empty())
+            .collect();
+        let lowercase = cleaned.to_lowercase();
+
+        if lowercase.contains("intel") {
+            Self::parse_intel(&tokens)
+        } else if lowercase.contains("amd") {
+            Self::parse_amd(&tokens)
+        } else if lowercase.contains("apple") {
+            Self::parse_apple(&tokens)
+        } else {
+            ParsedCpu::default()
+        }
+    }
+
+    fn parse_intel(tokens: &[&str]) -> ParsedCpu {
+        let mut parsed = ParsedCpu {
+            vendor: Some("Intel".to_string()),
+            ..ParsedCpu::default()
+        };
+
+        // Core i3/i5/i7/i9, e.g. "i7-10700K"
+        if let Some(model) = tokens.iter().find(|token| Self::is_core_model(token)) {
+            let (family, number) = model.split_once('-').unwrap_or((model, ""));
+            parsed.family = Some(family.to_lowercase());
+            parsed.generation = Self::intel_generation(number);
+            parsed.model = Some(format!("{}-{}", family.to_lowercase(), number.to_uppercase()));
+            return parsed;
+        }
+
+        // Core Ultra 5/7/9, e.g. "Core Ultra 7 155H"
+        if let Some(index) = tokens.iter().position(|token| token.eq_ignore_ascii_case("ultra"))
+            && let Some(tier) = tokens.get(index + 1)
+        {
+            parsed.family = Some(format!("Ultra {}", tier));
+            parsed.model = tokens.get(index + 2).map(|model| model.to_uppercase());
+            parsed.generation = parsed.model.as_deref().and_then(|model| Self::leading_digits(model, 1));
+            return parsed;
+        }
+
+        for family in ["Xeon", "Pentium", "Celeron", "Atom"] {
+            if let Some(index) = tokens.iter().position(|token| token.eq_ignore_ascii_case(family)) {
+                parsed.family = Some(family.to_string());
+                parsed.model = tokens
+                    .get(index + 1)
+                    .filter(|model| !Self::is_noise(model))
+                    .map(|model| model.to_string());
+                return parsed;
+            }
+        }
+
+        parsed
+    }
+
+    fn parse_amd(tokens: &[&str]) -> ParsedCpu {
+        let mut parsed = ParsedCpu {
+            vendor: Some("AMD".to_string()),
+            ..ParsedCpu::default()
+        };
+
+        if let Some(index) = tokens.iter().position(|token| token.eq_ignore_ascii_case("ryzen")) {
+            // "Ryzen 7 5800X", "Ryzen Threadripper 3970X", "Ryzen Threadripper PRO 5995WX"
+            let mut family = vec!["Ryzen"];
+            let mut next = index + 1;
+            while let Some(token) = tokens.get(next)
+                && !token.chars().any(|c| c.is_ascii_digit() && token.len() > 2)
+                && !Self::is_noise(token)
+            {
+                family.push(token);
+                next += 1;
+            }
+            parsed.family = Some(family.join(" "));
+            if let Some(model) = tokens.get(next).filter(|model| !Self::is_noise(model)) {
+                parsed.model = Some(model.to_uppercase());
+                parsed.generation = Self::leading_digits(model, 1);
+            }
+            return parsed;
+        }
+
+        for family in ["EPYC", "Athlon", "Phenom", "FX"] {
+            if let Some(index) = tokens.iter().position(|token| token.eq_ignore_ascii_case(family)) {
+                parsed.family = Some(family.to_string());
+                parsed.model = tokens
+                    .get(index + 1)
+                    .filter(|model| !Self::is_noise(model))
+                    .map(|model| model.to_uppercase());
+                return parsed;
+            }
+        }
+
+        parsed
+    }
+
+    fn parse_apple(tokens: &[&str]) -> ParsedCpu {
+        let mut parsed = ParsedCpu {
+            vendor: Some("Apple".to_string()),
+            ..ParsedCpu::default()
+        };
+
+        // "Apple M2 Pro"
+        if let Some(index) = tokens.iter().position(|token| {
+            token.len() > 1 && token.starts_with(['M', 'm']) && token[1..].chars().all(|c| c.is_ascii_digit())
+        }) {
+            let chip = tokens[index].to_uppercase();
+            parsed.generation = chip[1..].parse().ok();
+            parsed.model = Some(match tokens.get(index + 1) {
+                Some(variant) if ["Pro", "Max", "Ultra"].iter().any(|v| v.eq_ignore_ascii_case(variant)) => {
+                    format!("{} {}", chip, variant)
+                }
+                _ => chip.clone(),
+            });
+            parsed.family = Some(chip);
+        }
+
+        parsed
+    }
+
+    /// "i7-10700K" style tokens
+    fn is_core_model(token: &str) -> bool {
+        let lowercase = token.to_lowercase();
+        ["i3-", "i5-", "i7-", "i9-"].iter().any(|prefix| lowercase.starts_with(prefix))
+    }
+
+    /// Generation from the number after "iN-": "10700K" -> 10, "8700K" -> 8, "1135G7" -> 11
+    fn intel_generation(number: &str) -> Option<i64> {
+        let digits: String = number.chars().take_while(|c| c.is_ascii_digit()).collect();
+        let rest = &number[digits.len()..];
+        let generation_digits = match digits.len() {
+            5 => 2,
+            4 if digits.starts_with('1') && rest.starts_with(['G', 'g']) => 2,
+            1..=4 => 1,
+            _ => return None,
+        };
+        digits[..generation_digits].parse().ok()
+    }
+
+    fn leading_digits(token: &str, count: usize) -> Option<i64> {
+        let digits: String = token.chars().take(count).collect();
+        (digits.len() == count && digits.chars().all(|c| c.is_ascii_digit()))
+            .then(|| digits.parse().ok())
+            .flatten()
+    }
+
+    /// Tokens trailing the model name that carry no model information
+    fn is_noise(token: &str) -> bool {
+        let lowercase = token.to_lowercase();
+        lowercase == "cpu"
+            || lowercase == "processor"
+            || lowercase == "@"
+            || lowercase.ends_with("ghz")
+            || lowercase.ends_with("-core")
+            || lowercase.starts_with("with")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parsed(vendor: &str, family: &str, generation: Option<i64>, model: &str) -> ParsedCpu {
+        ParsedCpu {
+            vendor: Some(vendor.to_string()),
+            family: Some(family.to_string()),
+            generation,
+            model: Some(model.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_parse_intel_core() {
+        assert_eq!(
+            CpuParser::parse("Intel(R) Core(TM) i7-10700K CPU @ 3.80GHz"),
+            parsed("Intel", "i7", Some(10), "i7-10700K")
+        );
+        assert_eq!(CpuParser::parse("Intel Core i5-8400"), parsed("Intel", "i5", Some(8), "i5-8400"));
+        assert_eq!(
+            CpuParser::parse("11th Gen Intel(R) Core(TM) i5-1135G7 @ 2.40GHz"),
+            parsed("Intel", "i5", Some(11), "i5-1135G7")
+        );
+        assert_eq!(CpuParser::parse("Intel(R) Core(TM) i9-13900K"), parsed("Intel", "i9", Some(13), "i9-13900K"));
+    }
+
+    #[test]
+    fn test_parse_intel_other_families() {
+        assert_eq!(CpuParser::parse("Intel(R) Core(TM) Ultra 7 155H"), parsed("Intel", "Ultra 7", Some(1), "155H"));
+
+        let xeon = CpuParser::parse("Intel(R) Xeon(R) CPU @ 2.20GHz");
+        assert_eq!(xeon.family.as_deref(), Some("Xeon"));
+        assert_eq!(xeon.model, None);
+
+        let vendor_only = CpuParser::parse("Intel64 Family 6 Model 165 Stepping 5, GenuineIntel");
+        assert_eq!(vendor_only.vendor.as_deref(), Some("Intel"));
+        assert_eq!(vendor_only.family, None);
+    }
+
+    #[test]
+    fn test_parse_amd() {
+        assert_eq!(
+            CpuParser::parse("AMD Ryzen 7 5800X 8-Core Processor"),
+            parsed("AMD", "Ryzen 7", Some(5), "5800X")
+        );
+        assert_eq!(
+            CpuParser::parse("AMD Ryzen Threadripper 3970X 32-Core Processor"),
+            parsed("AMD", "Ryzen Threadripper", Some(3), "3970X")
+        );
+        assert_eq!(CpuParser::parse("AMD64 Family 25 Model 33 Stepping 0, AuthenticAMD").family, None);
+    }
+
+    #[test]
+    fn test_parse_apple_and_unknown() {
+        assert_eq!(CpuParser::parse("Apple M2 Pro"), parsed("Apple", "M2", Some(2), "M2 Pro"));
+        assert_eq!(CpuParser::parse("arm"), ParsedCpu::default());
+        assert_eq!(CpuParser::parse(""), ParsedCpu::default());
+    }
+}
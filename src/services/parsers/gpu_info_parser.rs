@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+use super::tokenizer::tokenize;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ParsedGpuInfo {
     pub device: Option<String>,
     pub driver: Option<String>,
@@ -9,6 +11,21 @@ pub struct ParsedGpuInfo {
 
 pub struct GpuInfoParser;
 
+/// Version of `GpuInfoParser`, recorded on the GPU rows it produces; the
+/// whitespace-splitting parser used by the process-gpu handler is version 1
+pub const GPU_INFO_PARSER_VERSION: i64 = 2;
+
+/// Largest device count marker taken at face value
+pub const MAX_GPUS_PER_RUN: usize = 16;
+
+/// Lowercase names of Intel and AMD integrated GPUs, matched as whole words after
+/// "(R)"/"(TM)" marks are dropped, so "Radeon RX Vega 64" is not taken for "Vega 6"
+pub const INTEGRATED_GPU_PATTERNS: &[&str] = &[
+    "uhd graphics", "iris", "hd graphics", "intel graphics", "intel arc graphics",
+    "radeon graphics", "vega 3", "vega 6", "vega 7", "vega 8", "vega 9", "vega 10", "vega 11",
+    "radeon 610m", "radeon 660m", "radeon 680m", "radeon 740m", "radeon 760m", "radeon 780m", "radeon 880m", "radeon 890m",
+];
+
 impl GpuInfoParser {
     /// Parse GPU information from the device_info string
     /// 
@@ -20,7 +37,6 @@ impl GpuInfoParser {
     /// # Returns
     /// * `ParsedGpuInfo` - Structured GPU information
     pub fn parse(device_info_string: &str) -> ParsedGpuInfo {
-        let parts: Vec<&str> = device_info_string.split(' ').collect();
         let mut parsed_gpu_info = ParsedGpuInfo {
             device: None,
             driver: None,
@@ -30,30 +46,25 @@ impl GpuInfoParser {
         let mut in_gpu_chip = false;
         let mut gpu_chip_parts = Vec::new();
 
-        for part in parts {
-            let colon_index = match part.find(':') {
-                Some(index) => index,
-                None => {
-                    // Handle non-colon parts
-                    if part.contains("GB") {
-                        // Append GB value to the device if it's a memory size
-                        if let Some(ref mut device) = parsed_gpu_info.device {
-                            device.push(' ');
-                            device.push_str(part);
-                        }
-                    } else if in_gpu_chip {
-                        gpu_chip_parts.push(part);
-                    } else if let Some(ref mut device) = parsed_gpu_info.device {
+        for token in tokenize(device_info_string) {
+            let part = token.as_str();
+            let Some((key, value)) = token.pair() else {
+                // Handle non-colon parts
+                if part.contains("GB") {
+                    // Append GB value to the device if it's a memory size
+                    if let Some(ref mut device) = parsed_gpu_info.device {
                         device.push(' ');
                         device.push_str(part);
                     }
-                    continue;
+                } else if in_gpu_chip {
+                    gpu_chip_parts.push(part);
+                } else if let Some(ref mut device) = parsed_gpu_info.device {
+                    device.push(' ');
+                    device.push_str(part);
                 }
+                continue;
             };
 
-            let key = &part[..colon_index];
-            let value = &part[colon_index + 1..];
-
             match key {
                 "device" => {
                     parsed_gpu_info.device = Some(value.to_string());
@@ -80,6 +91,88 @@ impl GpuInfoParser {
         parsed_gpu_info
     }
 
+    /// Parse a device_info string into one entry per GPU it reports
+    /// 
+    /// Segments come from `split_devices`; a single device with a "(N)" device
+    /// count gives N identical entries. A driver reported once applies to every GPU.
+    /// 
+    /// # Arguments
+    /// * `device_info_string` - The raw device info string to parse
+    /// 
+    /// # Returns
+    /// * `Vec<ParsedGpuInfo>` - One entry per GPU, in reported order, never empty
+    pub fn parse_all(device_info_string: &str) -> Vec<ParsedGpuInfo> {
+        let mut gpus: Vec<ParsedGpuInfo> = Self::split_devices(device_info_string)
+            .into_iter()
+            .map(Self::parse)
+            .collect();
+
+        let shared_driver = gpus.iter().find_map(|gpu| gpu.driver.clone());
+        for gpu in &mut gpus {
+            if gpu.driver.is_none() {
+                gpu.driver = shared_driver.clone();
+            }
+        }
+
+        if let [gpu] = gpus.as_slice() {
+            let count = gpu.device.as_deref().map_or(1, Self::device_count);
+            if count > 1 {
+                gpus = vec![gpu.clone(); count];
+            }
+        }
+
+        gpus
+    }
+
+    /// Split a device_info string into one segment per reported GPU
+    /// 
+    /// Every `device:` key starts a new GPU; text before the first one stays with
+    /// the first GPU. A string without any `device:` key is a single segment.
+    /// 
+    /// # Arguments
+    /// * `device_info_string` - The raw device info string to split
+    /// 
+    /// # Returns
+    /// * `Vec<&str>` - One segment per GPU, never empty
+    pub fn split_devices(device_info_string: &str) -> Vec<&str> {
+        let starts: Vec<usize> = device_info_string
+            .match_indices("device:")
+            .map(|(index, _)| index)
+            .filter(|&index| index == 0 || device_info_string[..index].ends_with(char::is_whitespace))
+            .collect();
+
+        if starts.len() < 2 {
+            return vec![device_info_string];
+        }
+
+        let mut segments = Vec::with_capacity(starts.len());
+        for (position, &start) in starts.iter().enumerate() {
+            let segment_start = if position == 0 { 0 } else { start };
+            let segment_end = starts.get(position + 1).copied().unwrap_or(device_info_string.len());
+            segments.push(device_info_string[segment_start..segment_end].trim());
+        }
+        segments
+    }
+
+    /// Number of GPUs a device name reports through a "(N)" device count marker
+    /// 
+    /// System info extensions write the CUDA device count after the name, e.g.
+    /// "NVIDIA GeForce RTX 3090 (2) (sm_86)". Markers outside 1..=`MAX_GPUS_PER_RUN`
+    /// are ignored.
+    /// 
+    /// # Arguments
+    /// * `device_string` - The parsed device string
+    /// 
+    /// # Returns
+    /// * `usize` - The number of GPUs, 1 when no marker is present
+    pub fn device_count(device_string: &str) -> usize {
+        device_string
+            .split_whitespace()
+            .filter_map(|token| token.strip_prefix('(')?.strip_suffix(')')?.parse::<usize>().ok())
+            .find(|count| (1..=MAX_GPUS_PER_RUN).contains(count))
+            .unwrap_or(1)
+    }
+
     /// Validate if the parsed GPU info contains valid data
     /// 
     /// # Arguments
@@ -129,6 +222,53 @@ impl GpuInfoParser {
         device_string.contains("Laptop") || device_string.contains("Mobile")
     }
 
+    /// Determine if a device string names an integrated GPU, per `INTEGRATED_GPU_PATTERNS`
+    pub fn is_integrated_gpu(device_string: &str) -> bool {
+        let device = device_string.to_lowercase().replace("(tm)", "").replace("(r)", "");
+
+        INTEGRATED_GPU_PATTERNS.iter().any(|pattern| {
+            device.match_indices(pattern).any(|(start, _)| {
+                let end = start + pattern.len();
+                let before = device[..start].chars().next_back();
+                let after = device[end..].chars().next();
+                !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric)
+            })
+        })
+    }
+
+    /// Extract the VRAM size in GB from a device string
+    /// 
+    /// Matches tokens such as "8GB", "(12GB)" or "24 GB"
+    /// 
+    /// # Arguments
+    /// * `device_string` - The device string to analyze
+    /// 
+    /// # Returns
+    /// * `Option<f64>` - The VRAM size in GB, if present
+    pub fn parse_vram_gb(device_string: &str) -> Option<f64> {
+        let tokens: Vec<&str> = device_string
+            .split(|c: char| c.is_whitespace() || c == '(' || c == ')' || c == ',')
+            .filter(|token| !token.is_empty())
+            .collect();
+
+        for (index, token) in tokens.iter().enumerate() {
+            let upper = token.to_uppercase();
+            if let Some(number) = upper.strip_suffix("GB") {
+                if let Ok(value) = number.parse::<f64>() {
+                    return Some(value);
+                }
+                if number.is_empty()
+                    && index > 0
+                    && let Ok(value) = tokens[index - 1].parse::<f64>()
+                {
+                    return Some(value);
+                }
+            }
+        }
+
+        None
+    }
+
     /// Get the brand name from a device string
     /// 
     /// # Arguments
@@ -228,6 +368,38 @@ mod tests {
         assert_eq!(summary, "device:NVIDIA driver:470.82.01 NVIDIA GeForce RTX 3080");
     }
 
+    #[test]
+    fn test_parse_all_multi_gpu() {
+        let gpus = GpuInfoParser::parse_all("device:RTX 3090 driver:535.86 device:RTX 3080");
+        assert_eq!(gpus.len(), 2);
+        assert_eq!(gpus[1].device, Some("RTX 3080".to_string()));
+        assert_eq!(gpus[1].driver, Some("535.86".to_string()));
+
+        let gpus = GpuInfoParser::parse_all("device:RTX 3090 (4) driver:535.86");
+        assert_eq!(gpus.len(), 4);
+
+        assert_eq!(GpuInfoParser::parse_all("device:NVIDIA driver:470.82.01").len(), 1);
+    }
+
+    #[test]
+    fn test_split_devices() {
+        assert_eq!(
+            GpuInfoParser::split_devices("device:RTX 3090 driver:535.86 device:RTX 3080 driver:535.86"),
+            vec!["device:RTX 3090 driver:535.86", "device:RTX 3080 driver:535.86"]
+        );
+        assert_eq!(GpuInfoParser::split_devices("device:cuda:0 driver:535.86"), vec!["device:cuda:0 driver:535.86"]);
+        assert_eq!(GpuInfoParser::split_devices("gpu:RTX 4090 device:RTX 4090"), vec!["gpu:RTX 4090 device:RTX 4090"]);
+        assert_eq!(GpuInfoParser::split_devices(""), vec![""]);
+    }
+
+    #[test]
+    fn test_device_count() {
+        assert_eq!(GpuInfoParser::device_count("NVIDIA GeForce RTX 3090 (2) (sm_86) (8, 6)"), 2);
+        assert_eq!(GpuInfoParser::device_count("NVIDIA GeForce RTX 4090 (1) (sm_89)"), 1);
+        assert_eq!(GpuInfoParser::device_count("NVIDIA GeForce RTX 4090 (24GB)"), 1);
+        assert_eq!(GpuInfoParser::device_count("NVIDIA A100 (64)"), 1);
+    }
+
     #[test]
     fn test_is_laptop_gpu() {
         assert!(GpuInfoParser::is_laptop_gpu("NVIDIA GeForce RTX 3080 Laptop"));
@@ -235,6 +407,27 @@ mod tests {
         assert!(!GpuInfoParser::is_laptop_gpu("NVIDIA GeForce RTX 3080"));
     }
 
+    #[test]
+    fn test_is_integrated_gpu() {
+        assert!(GpuInfoParser::is_integrated_gpu("Intel(R) UHD Graphics 620"));
+        assert!(GpuInfoParser::is_integrated_gpu("Intel(R) Iris(R) Xe Graphics"));
+        assert!(GpuInfoParser::is_integrated_gpu("AMD Radeon(TM) Vega 8 Graphics"));
+        assert!(GpuInfoParser::is_integrated_gpu("AMD Radeon(TM) Graphics"));
+        assert!(GpuInfoParser::is_integrated_gpu("AMD Radeon 780M"));
+        assert!(!GpuInfoParser::is_integrated_gpu("AMD Radeon RX Vega 64"));
+        assert!(!GpuInfoParser::is_integrated_gpu("AMD Radeon HD 7970"));
+        assert!(!GpuInfoParser::is_integrated_gpu("Intel(R) Arc(TM) A770 Graphics"));
+        assert!(!GpuInfoParser::is_integrated_gpu("NVIDIA GeForce RTX 3080"));
+    }
+
+    #[test]
+    fn test_parse_vram_gb() {
+        assert_eq!(GpuInfoParser::parse_vram_gb("NVIDIA GeForce RTX 3080 10GB"), Some(10.0));
+        assert_eq!(GpuInfoParser::parse_vram_gb("AMD Radeon RX 6800 (16GB)"), Some(16.0));
+        assert_eq!(GpuInfoParser::parse_vram_gb("NVIDIA A100 80 GB"), Some(80.0));
+        assert_eq!(GpuInfoParser::parse_vram_gb("NVIDIA GeForce RTX 3080"), None);
+    }
+
     #[test]
     fn test_get_brand_name() {
         assert_eq!(GpuInfoParser::get_brand_name("NVIDIA GeForce RTX 3080"), "nvidia");
@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+use super::tokenizer::tokenize;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ParsedLibraries {
     pub torch: Option<String>,
     pub xformers: Option<String>,
@@ -10,6 +12,10 @@ pub struct ParsedLibraries {
 
 pub struct LibrariesParser;
 
+/// Version of `LibrariesParser`, recorded on the Libraries rows it produces; the
+/// whitespace-splitting parser used by the process-libraries handler is version 1
+pub const LIBRARIES_PARSER_VERSION: i64 = 2;
+
 impl LibrariesParser {
     /// Parse library information from the model_info string
     /// 
@@ -21,7 +27,6 @@ impl LibrariesParser {
     /// # Returns
     /// * `ParsedLibraries` - Structured library information
     pub fn parse(model_info_string: &str) -> ParsedLibraries {
-        let parts: Vec<&str> = model_info_string.split(' ').collect();
         let mut parsed_libraries = ParsedLibraries {
             torch: None,
             xformers: None,
@@ -32,23 +37,18 @@ impl LibrariesParser {
         let mut torch_flag = false;
         let mut torch_value = String::new();
 
-        for part in parts {
-            let colon_index = match part.find(':') {
-                Some(index) => index,
-                None => {
-                    // If we're collecting torch info and this part has no colon, continue collecting
-                    if torch_flag {
-                        torch_value.push(' ');
-                        torch_value.push_str(part);
-                        parsed_libraries.torch = Some(torch_value.trim().to_string());
-                    }
-                    continue;
+        for token in tokenize(model_info_string) {
+            let part = token.as_str();
+            let Some((key, value)) = token.pair() else {
+                // If we're collecting torch info and this part has no colon, continue collecting
+                if torch_flag {
+                    torch_value.push(' ');
+                    torch_value.push_str(part);
+                    parsed_libraries.torch = Some(torch_value.trim().to_string());
                 }
+                continue;
             };
 
-            let key = &part[..colon_index];
-            let value = &part[colon_index + 1..];
-
             match key {
                 "torch" => {
                     // Start collecting the torch value
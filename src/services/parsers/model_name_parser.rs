@@ -0,0 +1,71 @@
+pub struct ModelNameParser;
+
+impl ModelNameParser {
+    /// Extract the checkpoint hash from a model name
+    /// 
+    /// WebUI reports checkpoints as "name [hash]", e.g. "v1-5-pruned-emaonly [6ce0161689]",
+    /// where the hash is the 10-character short SHA-256 or the legacy 8-character hash.
+    /// 
+    /// # Arguments
+    /// * `model_name` - The model name to analyze
+    /// 
+    /// # Returns
+    /// * `Option<String>` - The lowercased hash, if the model name contains one
+    pub fn parse_checkpoint_hash(model_name: &str) -> Option<String> {
+        let (_, rest) = model_name.rsplit_once(['[', '('])?;
+        let (hash, _) = rest.split_once([']', ')'])?;
+        let hash = hash.trim();
+
+        let is_hash = (8..=64).contains(&hash.len()) && hash.chars().all(|c| c.is_ascii_hexdigit());
+        if is_hash {
+            Some(hash.to_lowercase())
+        } else {
+            None
+        }
+    }
+
+    /// Candidate keys to look a checkpoint hash up by, most specific first
+    /// 
+    /// A full SHA-256 also matches its 10-character short form.
+    /// 
+    /// # Arguments
+    /// * `hash` - A hash returned by `parse_checkpoint_hash`
+    /// 
+    /// # Returns
+    /// * `Vec<String>` - Lookup keys for the hash
+    pub fn hash_lookup_keys(hash: &str) -> Vec<String> {
+        let mut keys = vec![hash.to_string()];
+        if hash.len() > 10 {
+            keys.push(hash[..10].to_string());
+        }
+        keys
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_checkpoint_hash() {
+        assert_eq!(
+            ModelNameParser::parse_checkpoint_hash("v1-5-pruned-emaonly [6ce0161689]"),
+            Some("6ce0161689".to_string())
+        );
+        assert_eq!(
+            ModelNameParser::parse_checkpoint_hash("sd_xl_base_1.0.safetensors [31E35C80FC]"),
+            Some("31e35c80fc".to_string())
+        );
+        assert_eq!(ModelNameParser::parse_checkpoint_hash("model (7460a6fa)"), Some("7460a6fa".to_string()));
+        assert_eq!(ModelNameParser::parse_checkpoint_hash("stable-diffusion-v1-5"), None);
+        assert_eq!(ModelNameParser::parse_checkpoint_hash("my model [v2]"), None);
+    }
+
+    #[test]
+    fn test_hash_lookup_keys() {
+        assert_eq!(ModelNameParser::hash_lookup_keys("6ce0161689"), vec!["6ce0161689"]);
+
+        let full = "6ce0161689b3853acaa03779ec93eafe75a02f4ced659bee03f50797806fa2fa";
+        assert_eq!(ModelNameParser::hash_lookup_keys(full), vec![full, "6ce0161689"]);
+    }
+}
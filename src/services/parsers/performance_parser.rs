@@ -1,6 +1,36 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+use crate::models::performance_result::{VRAM_USAGE_KIND_ITS, VRAM_USAGE_KIND_MEMORY, VRAM_USAGE_KIND_UNKNOWN};
+
+/// Smallest unitless value read as megabytes; a whole number this large is no ITS sample
+pub const MIN_UNITLESS_MEMORY_MB: f64 = 1000.0;
+
+/// Units that only appear in memory readings, matched ignoring ASCII case
+const MEMORY_UNITS: &[&str] = &["gib", "mib", "kib", "gb", "mb", "kb", "%"];
+
+/// What a vram_usage string holds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VramUsageKind {
+    /// ITS samples, as the pipeline expects
+    Its,
+    /// Memory values put there by the uploader
+    Memory,
+    /// Nothing that parses as a number
+    Unknown,
+}
+
+impl VramUsageKind {
+    /// Value stored in `performanceResult.vram_usage_kind`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VramUsageKind::Its => VRAM_USAGE_KIND_ITS,
+            VramUsageKind::Memory => VRAM_USAGE_KIND_MEMORY,
+            VramUsageKind::Unknown => VRAM_USAGE_KIND_UNKNOWN,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ParsedPerformanceData {
     pub its_values: Vec<f64>,
     pub avg_its: Option<f64>,
@@ -58,6 +88,34 @@ impl PerformanceParser {
         }
     }
 
+    /// Classify whether a vram_usage string holds ITS samples or memory values
+    ///
+    /// Despite its name the column carries ITS samples, but some uploads put
+    /// real memory readings there. A memory unit (GB, MiB, %) marks the string
+    /// as memory, as do unitless values that are all whole megabyte counts such
+    /// as "8123/24564". A fast GPU's samples may well exceed 100 it/s, so
+    /// magnitude alone decides nothing below that. Unitless GB readings such as
+    /// "7.5/24" cannot be told apart from ITS samples and are classified as ITS.
+    pub fn classify(vram_usage_string: &str) -> VramUsageKind {
+        let lowercase = vram_usage_string.to_ascii_lowercase();
+        if MEMORY_UNITS.iter().any(|unit| lowercase.contains(unit)) {
+            return VramUsageKind::Memory;
+        }
+
+        let performance_data = Self::parse(vram_usage_string);
+        if performance_data.its_values.is_empty() {
+            VramUsageKind::Unknown
+        } else if performance_data
+            .its_values
+            .iter()
+            .all(|value| *value >= MIN_UNITLESS_MEMORY_MB && value.fract() == 0.0)
+        {
+            VramUsageKind::Memory
+        } else {
+            VramUsageKind::Its
+        }
+    }
+
     /// Validate if the parsed performance data contains valid data
     /// 
     /// # Arguments
@@ -240,6 +298,29 @@ mod tests {
         assert!(summary.contains("max: 2.10"));
     }
 
+    #[test]
+    fn test_classify_its_samples() {
+        assert_eq!(PerformanceParser::classify("1.5/2.1/1.8"), VramUsageKind::Its);
+        assert_eq!(PerformanceParser::classify("10.5/11"), VramUsageKind::Its);
+        // Fast GPUs and one-step models exceed 100 it/s
+        assert_eq!(PerformanceParser::classify("142.7/150.3"), VramUsageKind::Its);
+        assert_eq!(PerformanceParser::classify("999999.999/0.001"), VramUsageKind::Its);
+    }
+
+    #[test]
+    fn test_classify_memory_values() {
+        assert_eq!(PerformanceParser::classify("8GB"), VramUsageKind::Memory);
+        assert_eq!(PerformanceParser::classify("7.5 GiB / 24 GiB"), VramUsageKind::Memory);
+        assert_eq!(PerformanceParser::classify("85%"), VramUsageKind::Memory);
+        assert_eq!(PerformanceParser::classify("8123/24564"), VramUsageKind::Memory);
+    }
+
+    #[test]
+    fn test_classify_unknown() {
+        assert_eq!(PerformanceParser::classify(""), VramUsageKind::Unknown);
+        assert_eq!(PerformanceParser::classify("n/a"), VramUsageKind::Unknown);
+    }
+
     #[test]
     fn test_validate_with_errors_valid() {
         let result = PerformanceParser::validate_with_errors("1.5/2.1/1.8");
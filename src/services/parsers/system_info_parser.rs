@@ -1,20 +1,34 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+use super::tokenizer::{tokenize, Token};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ParsedSystemInfo {
     pub arch: Option<String>,
     pub cpu: Option<String>,
     pub system: Option<String>,
     pub release: Option<String>,
     pub python: Option<String>,
+    /// Installed system memory in GB
+    pub ram_gb: Option<f64>,
+    /// Swap size in GB
+    pub swap_gb: Option<f64>,
+    /// Platform bitness, 32 or 64
+    pub os_bits: Option<i64>,
 }
 
 pub struct SystemInfoParser;
 
+/// Version of `SystemInfoParser`, recorded on the SystemInfo rows it produces
+pub const SYSTEM_INFO_PARSER_VERSION: i64 = 1;
+
 impl SystemInfoParser {
     /// Parse system information from the system_info string
     /// 
     /// The system_info string format is typically: "arch:x86_64 cpu:Intel system:Linux release:5.15.0 python:3.9.0"
+    ///
+    /// Newer exports may also carry "ram:32GB swap:8GB bits:64"; memory values
+    /// without a unit are taken as GB.
     /// 
     /// # Arguments
     /// * `system_info_string` - The raw system info string to parse
@@ -28,27 +42,25 @@ impl SystemInfoParser {
             system: None,
             release: None,
             python: None,
+            ram_gb: None,
+            swap_gb: None,
+            os_bits: None,
         };
 
         // Split by spaces and process each part
-        let parts: Vec<&str> = system_info_string.split(' ').collect();
+        let parts: Vec<Token> = tokenize(system_info_string).collect();
         let mut i = 0;
         
         while i < parts.len() {
-            let part = parts[i];
-            
-            if let Some(colon_index) = part.find(':') {
-                let key = &part[..colon_index];
-                let value = &part[colon_index + 1..];
-                
+            if let Some((key, value)) = parts[i].pair() {
                 // Handle multi-word values
                 let mut full_value = value.to_string();
                 let mut next_i = i + 1;
                 
                 // Look ahead for multi-word values (especially for CPU)
-                while next_i < parts.len() && !parts[next_i].contains(':') {
+                while next_i < parts.len() && parts[next_i].pair().is_none() {
                     full_value.push(' ');
-                    full_value.push_str(parts[next_i]);
+                    full_value.push_str(parts[next_i].as_str());
                     next_i += 1;
                 }
                 
@@ -68,6 +80,15 @@ impl SystemInfoParser {
                     "python" => {
                         system_info.python = Some(full_value);
                     }
+                    "ram" => {
+                        system_info.ram_gb = Self::parse_memory_gb(&full_value);
+                    }
+                    "swap" => {
+                        system_info.swap_gb = Self::parse_memory_gb(&full_value);
+                    }
+                    "bits" => {
+                        system_info.os_bits = Self::parse_bits(&full_value);
+                    }
                     _ => {}
                 }
                 
@@ -80,6 +101,33 @@ impl SystemInfoParser {
         system_info
     }
 
+    /// Parse a memory size such as "32", "31.9GB", "16 GiB" or "8192MB" into GB
+    fn parse_memory_gb(value: &str) -> Option<f64> {
+        let compact: String = value.split_whitespace().collect::<String>().to_lowercase();
+        let number_end = compact
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(compact.len());
+        let (number, unit) = compact.split_at(number_end);
+        let number: f64 = number.parse().ok()?;
+
+        let gb = match unit {
+            "" | "g" | "gb" | "gib" => number,
+            "m" | "mb" | "mib" => number / 1024.0,
+            "t" | "tb" | "tib" => number * 1024.0,
+            _ => return None,
+        };
+        (gb.is_finite() && gb > 0.0).then_some(gb)
+    }
+
+    /// Parse platform bitness such as "64", "64bit" or "32-bit"
+    fn parse_bits(value: &str) -> Option<i64> {
+        let digits: String = value.trim().chars().take_while(|c| c.is_ascii_digit()).collect();
+        match digits.parse() {
+            Ok(bits @ (32 | 64)) => Some(bits),
+            _ => None,
+        }
+    }
+
     /// Validate if the parsed system info contains valid data
     /// 
     /// # Arguments
@@ -124,6 +172,18 @@ impl SystemInfoParser {
         if let Some(python) = &system_info.python {
             parts.push(format!("python:{}", python));
         }
+
+        if let Some(ram_gb) = system_info.ram_gb {
+            parts.push(format!("ram:{}", ram_gb));
+        }
+
+        if let Some(swap_gb) = system_info.swap_gb {
+            parts.push(format!("swap:{}", swap_gb));
+        }
+
+        if let Some(os_bits) = system_info.os_bits {
+            parts.push(format!("bits:{}", os_bits));
+        }
         
         parts.join(" ")
     }
@@ -201,6 +261,9 @@ mod tests {
             system: None,
             release: None,
             python: None,
+            ram_gb: None,
+            swap_gb: None,
+            os_bits: None,
         };
         assert!(SystemInfoParser::is_valid(&valid_info));
 
@@ -210,6 +273,9 @@ mod tests {
             system: None,
             release: None,
             python: None,
+            ram_gb: None,
+            swap_gb: None,
+            os_bits: None,
         };
         assert!(!SystemInfoParser::is_valid(&invalid_info));
     }
@@ -222,9 +288,31 @@ mod tests {
             system: Some("Linux".to_string()),
             release: Some("5.15.0".to_string()),
             python: Some("3.9.0".to_string()),
+            ram_gb: None,
+            swap_gb: None,
+            os_bits: None,
         };
         
         let summary = SystemInfoParser::get_summary(&system_info);
         assert_eq!(summary, "arch:x86_64 cpu:Intel Core i7 system:Linux release:5.15.0 python:3.9.0");
     }
-} 
\ No newline at end of file
+
+    #[test]
+    fn test_parse_system_info_memory_and_bits() {
+        let result = SystemInfoParser::parse("arch:x86_64 system:Linux ram:31.9GB swap:8192MB bits:64bit python:3.10.6");
+        assert_eq!(result.ram_gb, Some(31.9));
+        assert_eq!(result.swap_gb, Some(8.0));
+        assert_eq!(result.os_bits, Some(64));
+        assert_eq!(result.python, Some("3.10.6".to_string()));
+
+        let result = SystemInfoParser::parse("ram:16 GiB swap:0 bits:48");
+        assert_eq!(result.ram_gb, Some(16.0));
+        assert_eq!(result.swap_gb, None);
+        assert_eq!(result.os_bits, None);
+
+        assert_eq!(
+            SystemInfoParser::get_summary(&SystemInfoParser::parse("python:3.9.0 ram:32gb bits:32")),
+            "python:3.9.0 ram:32 bits:32"
+        );
+    }
+}
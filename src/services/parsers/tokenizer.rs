@@ -0,0 +1,49 @@
+/// One space-separated token of a submission string such as
+/// `"arch:x86_64 cpu:AMD Ryzen 9 system:Linux"`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Token<'a>(&'a str);
+
+impl<'a> Token<'a> {
+    /// The token exactly as it appeared in the input
+    pub fn as_str(self) -> &'a str {
+        self.0
+    }
+
+    /// The key and value of a `key:value` token, split on the first colon
+    ///
+    /// The value may itself contain colons (`url:https://...`). Tokens without a
+    /// colon are the continuation of a multi-word value and return `None`.
+    pub fn pair(self) -> Option<(&'a str, &'a str)> {
+        self.0.split_once(':')
+    }
+}
+
+/// Split a submission string into tokens on single spaces
+///
+/// Consecutive spaces produce empty tokens so that joining the tokens with
+/// `' '` always reproduces the input.
+pub fn tokenize(input: &str) -> impl Iterator<Item = Token<'_>> {
+    input.split(' ').map(Token)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenize_pairs_and_words() {
+        let tokens: Vec<_> = tokenize("cpu:AMD Ryzen url:https://example.com").collect();
+
+        assert_eq!(tokens[0].pair(), Some(("cpu", "AMD")));
+        assert_eq!(tokens[1].pair(), None);
+        assert_eq!(tokens[1].as_str(), "Ryzen");
+        assert_eq!(tokens[2].pair(), Some(("url", "https://example.com")));
+    }
+
+    #[test]
+    fn test_tokenize_round_trips() {
+        let input = "  torch:2.0.1+cu118 autocast  half ";
+        let rejoined: Vec<_> = tokenize(input).map(Token::as_str).collect();
+        assert_eq!(rejoined.join(" "), input);
+    }
+}
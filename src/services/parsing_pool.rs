@@ -0,0 +1,134 @@
+use std::{collections::VecDeque, sync::Arc};
+
+use tokio::{sync::mpsc, task::JoinHandle};
+use tracing::error;
+
+use crate::{config::settings::ParsingConfig, error::types::AppError};
+
+/// Parses rows in chunks on blocking worker threads, ahead of the database writes
+///
+/// At most `workers` chunks are parsed at once and at most `queue_depth` parsed
+/// chunks wait for the writer, so a slow write stage holds back parsing instead
+/// of buffering the whole table in memory.
+#[derive(Debug, Clone)]
+pub struct ParsingPool {
+    workers: usize,
+    chunk_size: usize,
+    queue_depth: usize,
+}
+
+impl ParsingPool {
+    pub fn new(config: &ParsingConfig) -> Self {
+        Self {
+            workers: config.workers.max(1),
+            chunk_size: config.chunk_size.max(1),
+            queue_depth: config.queue_depth.max(1),
+        }
+    }
+
+    /// Map every row through `parse`, yielding the parsed chunks in input order
+    ///
+    /// The receiver yields an error if a worker panics; dropping it stops the
+    /// pool from starting further chunks.
+    pub fn parse<T, U, F>(&self, rows: Vec<T>, parse: F) -> mpsc::Receiver<Result<Vec<U>, AppError>>
+    where
+        T: Send + 'static,
+        U: Send + 'static,
+        F: Fn(T) -> U + Send + Sync + 'static,
+    {
+        let (sender, receiver) = mpsc::channel(self.queue_depth);
+        let workers = self.workers;
+        let chunk_size = self.chunk_size;
+        let parse = Arc::new(parse);
+
+        tokio::spawn(async move {
+            let mut rows = rows.into_iter();
+            let mut in_flight: VecDeque<JoinHandle<Vec<U>>> = VecDeque::with_capacity(workers);
+
+            loop {
+                while in_flight.len() < workers {
+                    let chunk: Vec<T> = rows.by_ref().take(chunk_size).collect();
+                    if chunk.is_empty() {
+                        break;
+                    }
+                    let parse = Arc::clone(&parse);
+                    in_flight.push_back(tokio::task::spawn_blocking(move || {
+                        chunk.into_iter().map(|row| parse(row)).collect()
+                    }));
+                }
+
+                let Some(handle) = in_flight.pop_front() else {
+                    break;
+                };
+                let parsed = handle.await.map_err(|e| {
+                    error!("Parsing worker failed: {}", e);
+                    AppError::internal(format!("Parsing worker failed: {}", e))
+                });
+                let failed = parsed.is_err();
+                if sender.send(parsed).await.is_err() || failed {
+                    break;
+                }
+            }
+        });
+
+        receiver
+    }
+}
+
+impl Default for ParsingPool {
+    fn default() -> Self {
+        Self::new(&ParsingConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool(workers: usize, chunk_size: usize) -> ParsingPool {
+        ParsingPool::new(&ParsingConfig {
+            workers,
+            chunk_size,
+            queue_depth: 1,
+        })
+    }
+
+    async fn collect<U>(mut receiver: mpsc::Receiver<Result<Vec<U>, AppError>>) -> Vec<Vec<U>> {
+        let mut chunks = Vec::new();
+        while let Some(chunk) = receiver.recv().await {
+            chunks.push(chunk.unwrap());
+        }
+        chunks
+    }
+
+    #[tokio::test]
+    async fn test_parse_keeps_input_order() {
+        let chunks = collect(pool(4, 3).parse((0..10).collect(), |row: i32| row * 2)).await;
+
+        assert_eq!(chunks.iter().map(Vec::len).collect::<Vec<_>>(), vec![3, 3, 3, 1]);
+        assert_eq!(chunks.concat(), (0..10).map(|row| row * 2).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn test_parse_empty_input() {
+        assert!(collect(pool(2, 10).parse(Vec::<i32>::new(), |row| row)).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_parse_reports_worker_panic() {
+        let mut receiver = pool(1, 1).parse(vec![1, 2], |row: i32| {
+            assert!(row < 2, "unparseable row");
+            row
+        });
+
+        assert_eq!(receiver.recv().await.unwrap().unwrap(), vec![1]);
+        assert!(receiver.recv().await.unwrap().is_err());
+        assert!(receiver.recv().await.is_none());
+    }
+
+    #[test]
+    fn test_zero_settings_are_clamped() {
+        let pool = pool(0, 0);
+        assert_eq!((pool.workers, pool.chunk_size, pool.queue_depth), (1, 1, 1));
+    }
+}
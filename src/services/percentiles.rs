@@ -0,0 +1,234 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+use tracing::{error, info};
+
+use crate::{
+    error::types::AppError,
+    models::{
+        dataset_meta::{META_LAST_PIPELINE_RUN_AT, META_SOURCE_UPLOADED_AT},
+        gpu_percentile::{DeviceItsSample, GpuPercentiles, GpuPercentilesReport},
+    },
+    repositories::{
+        dataset_meta_repository::DatasetMetaRepository, gpu_percentile_repository::GpuPercentileRepository,
+        gpu_repository::GpuRepository, runs_repository::RunsRepository,
+    },
+};
+
+#[derive(Debug)]
+pub struct RefreshPercentilesOutput {
+    pub message: String,
+    /// Runs the percentiles were computed from
+    pub total_runs: usize,
+    /// GPUs now in the cache
+    pub gpu_rows: usize,
+}
+
+/// Per-GPU avg_its percentiles, served from the `gpu_percentiles` cache
+///
+/// Percentiles need every run of a GPU sorted, which SQLite cannot do in a
+/// query, so the cache is rebuilt on request rather than kept up to date.
+pub struct PercentileService {
+    pool: SqlitePool,
+}
+
+impl PercentileService {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Recompute the percentiles of every tenant's GPUs and replace the cache with them
+    pub async fn refresh(&self) -> Result<RefreshPercentilesOutput, AppError> {
+        let tenant_ids = RunsRepository::new(self.pool.clone())
+            .find_tenant_ids()
+            .await
+            .map_err(|e| database_error("fetch tenants", e))?;
+        let gpu_repository = GpuRepository::new(self.pool.clone());
+        let computed_at = Utc::now().to_rfc3339();
+
+        let mut total_runs = 0;
+        let mut tenant_entries = Vec::with_capacity(tenant_ids.len());
+        for tenant_id in tenant_ids {
+            let samples = gpu_repository
+                .find_device_its_samples(&tenant_id, None)
+                .await
+                .map_err(|e| database_error("fetch ITS samples for percentiles", e))?;
+            total_runs += samples.len();
+            tenant_entries.push((compute_gpu_percentiles(&samples, &computed_at), tenant_id));
+        }
+        let gpu_rows: usize = tenant_entries.iter().map(|(entries, _)| entries.len()).sum();
+        info!("Refreshing percentiles of {} GPUs from {} runs", gpu_rows, total_runs);
+
+        let repository = GpuPercentileRepository::new(self.pool.clone());
+        let mut tx = self.pool.begin().await.map_err(|e| database_error("begin transaction", e))?;
+        repository
+            .delete_all_tx(&mut tx)
+            .await
+            .map_err(|e| database_error("clear cached percentiles", e))?;
+        for (entries, tenant_id) in &tenant_entries {
+            repository
+                .insert_all_tx(tenant_id, entries, &mut tx)
+                .await
+                .map_err(|e| database_error("cache percentiles", e))?;
+        }
+        tx.commit().await.map_err(|e| database_error("commit transaction", e))?;
+
+        Ok(RefreshPercentilesOutput {
+            message: format!("Refreshed percentiles of {} GPUs", gpu_rows),
+            total_runs,
+            gpu_rows,
+        })
+    }
+
+    /// Percentiles of every GPU in a tenant's dataset, or of `gpu` only, preferring the cache
+    ///
+    /// Until the tenant's cache is first refreshed they are computed from the runs.
+    pub async fn report(&self, tenant_id: &str, gpu: Option<&str>) -> Result<GpuPercentilesReport, AppError> {
+        let repository = GpuPercentileRepository::new(self.pool.clone());
+        let computed_at = repository
+            .last_computed_at(tenant_id)
+            .await
+            .map_err(|e| database_error("fetch percentile cache age", e))?;
+
+        let Some(computed_at) = computed_at else {
+            let samples = GpuRepository::new(self.pool.clone())
+                .find_device_its_samples(tenant_id, gpu)
+                .await
+                .map_err(|e| database_error("fetch ITS samples for percentiles", e))?;
+            let mut gpus = compute_gpu_percentiles(&samples, &Utc::now().to_rfc3339());
+            gpus.sort_by(|a, b| b.median_its.total_cmp(&a.median_its));
+            return Ok(GpuPercentilesReport {
+                cached: false,
+                computed_at: None,
+                stale: false,
+                gpus,
+            });
+        };
+
+        let gpus = repository
+            .find_all(tenant_id, gpu)
+            .await
+            .map_err(|e| database_error("fetch cached percentiles", e))?;
+        let stale = self.changed_since(&computed_at).await?;
+
+        Ok(GpuPercentilesReport {
+            cached: true,
+            computed_at: Some(computed_at),
+            stale,
+            gpus,
+        })
+    }
+
+    /// Whether data was uploaded or the pipeline ran after `computed_at`
+    async fn changed_since(&self, computed_at: &str) -> Result<bool, AppError> {
+        let Some(computed_at) = parse_timestamp(computed_at) else {
+            return Ok(true);
+        };
+
+        let repository = DatasetMetaRepository::new(self.pool.clone());
+        for key in [META_SOURCE_UPLOADED_AT, META_LAST_PIPELINE_RUN_AT] {
+            let changed_at = repository
+                .find_by_key(key)
+                .await
+                .map_err(|e| database_error("fetch dataset metadata", e))?
+                .and_then(|entry| entry.value)
+                .and_then(|value| parse_timestamp(&value));
+            if changed_at.is_some_and(|changed_at| changed_at > computed_at) {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+}
+
+fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value).ok().map(|timestamp| timestamp.with_timezone(&Utc))
+}
+
+fn database_error(action: &str, e: sqlx::Error) -> AppError {
+    error!("Failed to {}: {}", action, e);
+    AppError::Database(e)
+}
+
+/// Group samples by device and summarize each device's avg_its, by device name
+///
+/// Non-finite avg_its values are left out.
+pub fn compute_gpu_percentiles(samples: &[DeviceItsSample], computed_at: &str) -> Vec<GpuPercentiles> {
+    let mut grouped: BTreeMap<&str, Vec<f64>> = BTreeMap::new();
+    for sample in samples.iter().filter(|sample| sample.avg_its.is_finite()) {
+        grouped.entry(sample.device.as_str()).or_default().push(sample.avg_its);
+    }
+
+    grouped
+        .into_iter()
+        .map(|(gpu, mut its)| {
+            its.sort_by(|a, b| a.total_cmp(b));
+            GpuPercentiles {
+                gpu: gpu.to_string(),
+                run_count: its.len() as i64,
+                min_its: its[0],
+                p10_its: percentile(&its, 0.1),
+                p25_its: percentile(&its, 0.25),
+                median_its: percentile(&its, 0.5),
+                p75_its: percentile(&its, 0.75),
+                p90_its: percentile(&its, 0.9),
+                max_its: its[its.len() - 1],
+                computed_at: computed_at.to_string(),
+            }
+        })
+        .collect()
+}
+
+/// Linearly interpolated percentile of sorted, non-empty values
+pub fn percentile(sorted: &[f64], fraction: f64) -> f64 {
+    let rank = fraction * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(device: &str, avg_its: f64) -> DeviceItsSample {
+        DeviceItsSample {
+            device: device.to_string(),
+            avg_its,
+        }
+    }
+
+    #[test]
+    fn test_percentile() {
+        let values = [1.0, 2.0, 3.0, 4.0];
+        assert_eq!(percentile(&values, 0.0), 1.0);
+        assert_eq!(percentile(&values, 0.5), 2.5);
+        assert_eq!(percentile(&values, 0.25), 1.75);
+        assert_eq!(percentile(&values, 1.0), 4.0);
+    }
+
+    #[test]
+    fn test_compute_gpu_percentiles_groups_by_device() {
+        let samples = vec![
+            sample("RTX 4090", 30.0),
+            sample("RTX 3060", 8.0),
+            sample("RTX 4090", 10.0),
+            sample("RTX 4090", 20.0),
+            sample("RTX 3060", f64::NAN),
+        ];
+
+        let entries = compute_gpu_percentiles(&samples, "2024-01-01T00:00:00+00:00");
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].gpu, "RTX 3060");
+        assert_eq!(entries[0].run_count, 1);
+        assert_eq!(entries[0].p90_its, 8.0);
+        assert_eq!(entries[1].run_count, 3);
+        assert_eq!(entries[1].min_its, 10.0);
+        assert_eq!(entries[1].median_its, 20.0);
+        assert_eq!(entries[1].p10_its, 12.0);
+        assert_eq!(entries[1].max_its, 30.0);
+    }
+}
@@ -0,0 +1,189 @@
+use async_trait::async_trait;
+use serde_json::Value;
+use sqlx::{Sqlite, Transaction};
+
+use crate::{error::types::AppError, AppState};
+
+/// One processing stage that derives tables from the imported runs
+///
+/// Every stage of a run executes in the one transaction the orchestrator
+/// passes in, reading and writing only through it, so a failing stage rolls
+/// back the stages before it as well. `dependencies` names the stages whose
+/// output this one reads; the registry orders stages so those run first.
+#[async_trait]
+pub trait PipelineStage: Send + Sync {
+    /// Unique name, also the stage's `/api/{name}` endpoint
+    fn name(&self) -> &'static str;
+
+    fn dependencies(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Run the stage within `tx`, returning its row counts and summary
+    async fn execute(&self, state: &AppState, tx: &mut Transaction<'_, Sqlite>) -> Result<StageOutput, AppError>;
+}
+
+/// What a stage reports back to the orchestrator
+#[derive(Debug, Clone, Default)]
+pub struct StageOutput {
+    /// Rows the stage read, where it reports them
+    pub rows_in: Option<usize>,
+    /// Rows the stage inserted or updated
+    pub rows_out: Option<usize>,
+    /// The stage's own summary, e.g. its endpoint's response
+    pub summary: Value,
+}
+
+/// The registered pipeline stages
+#[derive(Default)]
+pub struct PipelineRegistry {
+    stages: Vec<Box<dyn PipelineStage>>,
+}
+
+impl PipelineRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(mut self, stage: impl PipelineStage + 'static) -> Self {
+        self.stages.push(Box::new(stage));
+        self
+    }
+
+    /// Registered stage names, in registration order
+    pub fn names(&self) -> Vec<&'static str> {
+        self.stages.iter().map(|stage| stage.name()).collect()
+    }
+
+    /// The given names that are not registered stages
+    pub fn unknown_stages<'a>(&self, names: &'a [String]) -> Vec<&'a str> {
+        let registered = self.names();
+        names
+            .iter()
+            .map(String::as_str)
+            .filter(|name| !registered.contains(name))
+            .collect()
+    }
+
+    /// All stages ordered so each runs after its dependencies
+    ///
+    /// Stages without an ordering constraint between them keep their
+    /// registration order. Duplicate names, unknown dependencies and cycles
+    /// are errors.
+    pub fn execution_order(&self) -> Result<Vec<&dyn PipelineStage>, AppError> {
+        let names = self.names();
+        for (index, stage) in self.stages.iter().enumerate() {
+            if names[..index].contains(&stage.name()) {
+                return Err(AppError::internal(format!("Pipeline stage {} is registered twice", stage.name())));
+            }
+            if let Some(unknown) = stage.dependencies().iter().find(|dependency| !names.contains(dependency)) {
+                return Err(AppError::internal(format!(
+                    "Pipeline stage {} depends on unknown stage {}",
+                    stage.name(),
+                    unknown
+                )));
+            }
+        }
+
+        let mut ordered: Vec<&dyn PipelineStage> = Vec::with_capacity(self.stages.len());
+        while ordered.len() < self.stages.len() {
+            let ready = self.stages.iter().map(Box::as_ref).find(|stage| {
+                !ordered.iter().any(|done| done.name() == stage.name())
+                    && stage
+                        .dependencies()
+                        .iter()
+                        .all(|dependency| ordered.iter().any(|done| done.name() == *dependency))
+            });
+            match ready {
+                Some(stage) => ordered.push(stage),
+                None => {
+                    let blocked: Vec<&str> = self
+                        .stages
+                        .iter()
+                        .map(|stage| stage.name())
+                        .filter(|name| !ordered.iter().any(|done| done.name() == *name))
+                        .collect();
+                    return Err(AppError::internal(format!(
+                        "Pipeline stages {} depend on each other",
+                        blocked.join(", ")
+                    )));
+                }
+            }
+        }
+
+        Ok(ordered)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TestStage(&'static str, &'static [&'static str]);
+
+    #[async_trait]
+    impl PipelineStage for TestStage {
+        fn name(&self) -> &'static str {
+            self.0
+        }
+
+        fn dependencies(&self) -> &'static [&'static str] {
+            self.1
+        }
+
+        async fn execute(&self, _state: &AppState, _tx: &mut Transaction<'_, Sqlite>) -> Result<StageOutput, AppError> {
+            Ok(StageOutput::default())
+        }
+    }
+
+    fn order(registry: &PipelineRegistry) -> Vec<&'static str> {
+        registry.execution_order().unwrap().iter().map(|stage| stage.name()).collect()
+    }
+
+    #[test]
+    fn test_execution_order_runs_dependencies_first() {
+        let registry = PipelineRegistry::new()
+            .register(TestStage("scores", &["its", "gpu"]))
+            .register(TestStage("brands", &["gpu"]))
+            .register(TestStage("its", &[]))
+            .register(TestStage("gpu", &[]));
+
+        assert_eq!(order(&registry), vec!["its", "gpu", "scores", "brands"]);
+    }
+
+    #[test]
+    fn test_execution_order_keeps_registration_order_otherwise() {
+        let registry = PipelineRegistry::new()
+            .register(TestStage("b", &[]))
+            .register(TestStage("a", &[]))
+            .register(TestStage("c", &[]));
+
+        assert_eq!(order(&registry), vec!["b", "a", "c"]);
+    }
+
+    #[test]
+    fn test_execution_order_rejects_unknown_dependencies_and_cycles() {
+        let unknown = PipelineRegistry::new().register(TestStage("scores", &["vram"]));
+        assert!(unknown.execution_order().is_err());
+
+        let cycle = PipelineRegistry::new()
+            .register(TestStage("its", &[]))
+            .register(TestStage("a", &["b"]))
+            .register(TestStage("b", &["a"]));
+        let error = cycle.execution_order().err().unwrap();
+        assert!(error.to_string().contains("a, b"), "{}", error);
+
+        let duplicate = PipelineRegistry::new()
+            .register(TestStage("its", &[]))
+            .register(TestStage("its", &[]));
+        assert!(duplicate.execution_order().is_err());
+    }
+
+    #[test]
+    fn test_unknown_stages() {
+        let registry = PipelineRegistry::new().register(TestStage("its", &[])).register(TestStage("gpu", &[]));
+        let names = vec!["gpu".to_string(), "vram".to_string()];
+
+        assert_eq!(registry.unknown_stages(&names), vec!["vram"]);
+    }
+}
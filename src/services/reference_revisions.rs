@@ -0,0 +1,118 @@
+use std::collections::BTreeMap;
+
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use sqlx::SqlitePool;
+
+use crate::{
+    config::settings::ScoringConfig,
+    handlers::admin::LEGACY_PARSER_VERSION,
+    models::dataset_version::ReferenceRevisions,
+    repositories::{
+        gpu_base_repository::GpuBaseRepository, gpu_map_repository::GpuMapRepository,
+        model_map_repository::ModelMapRepository, traits::Repository,
+    },
+    services::parsers::{
+        APP_DETAILS_PARSER_VERSION, GPU_INFO_PARSER_VERSION, LIBRARIES_PARSER_VERSION, SYSTEM_INFO_PARSER_VERSION,
+    },
+};
+
+/// Hash the current ModelMap, GPUMap and GPUBase contents and the processing rules
+///
+/// Only the mapped values count, so rewriting a row without changing it keeps
+/// its revision; audit timestamps and row versions are ignored.
+pub async fn capture_reference_revisions(pool: &SqlitePool, scoring: &ScoringConfig) -> Result<ReferenceRevisions, sqlx::Error> {
+    let mut model_map = ModelMapRepository::new(pool.clone()).find_all().await?;
+    model_map.sort_by_key(|row| row.id);
+    let mut gpu_map = GpuMapRepository::new(pool.clone()).find_all().await?;
+    gpu_map.sort_by_key(|row| row.id);
+    let mut gpu_base = GpuBaseRepository::new(pool.clone()).find_all().await?;
+    gpu_base.sort_by_key(|row| row.id);
+
+    Ok(ReferenceRevisions {
+        model_map_hash: content_hash(
+            model_map
+                .iter()
+                .map(|row| json!([row.id, row.model_name, row.base_model])),
+        ),
+        gpu_map_hash: content_hash(gpu_map.iter().map(|row| json!([row.id, row.gpu_name, row.base_gpu_id]))),
+        gpu_base_hash: content_hash(
+            gpu_base
+                .iter()
+                .map(|row| json!([row.id, row.name, row.brand, row.architecture, row.generation])),
+        ),
+        rules_hash: rules_hash(scoring),
+    })
+}
+
+/// Hash of the parser versions and scoring settings the processing steps apply
+pub fn rules_hash(scoring: &ScoringConfig) -> String {
+    // Sorted, so the hash does not depend on HashMap iteration order
+    let model_base_factors: BTreeMap<&String, &f64> = scoring.model_base_factors.iter().collect();
+    let rules = json!({
+        "parser_versions": {
+            "legacy": LEGACY_PARSER_VERSION,
+            "app_details": APP_DETAILS_PARSER_VERSION,
+            "system_info": SYSTEM_INFO_PARSER_VERSION,
+            "libraries": LIBRARIES_PARSER_VERSION,
+            "gpu": GPU_INFO_PARSER_VERSION,
+        },
+        "scoring": {
+            "its_weight": scoring.its_weight,
+            "vram_weight": scoring.vram_weight,
+            "default_model_factor": scoring.default_model_factor,
+            "model_base_factors": model_base_factors,
+        },
+    });
+
+    content_hash(std::iter::once(rules))
+}
+
+/// Hex SHA-256 over one JSON value per line
+fn content_hash(values: impl Iterator<Item = serde_json::Value>) -> String {
+    let mut hasher = Sha256::new();
+    for value in values {
+        hasher.update(value.to_string().as_bytes());
+        hasher.update(b"\n");
+    }
+
+    hasher.finalize().iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn scoring(factors: &[(&str, f64)]) -> ScoringConfig {
+        ScoringConfig {
+            its_weight: 0.7,
+            vram_weight: 0.3,
+            default_model_factor: 1.0,
+            model_base_factors: factors.iter().map(|(model, factor)| (model.to_string(), *factor)).collect::<HashMap<_, _>>(),
+        }
+    }
+
+    #[test]
+    fn test_rules_hash_ignores_factor_order() {
+        let factors = [("SD 1.5", 1.0), ("SDXL", 2.5), ("SD 2.1", 1.2)];
+        let reversed: Vec<_> = factors.iter().rev().copied().collect();
+
+        assert_eq!(rules_hash(&scoring(&factors)), rules_hash(&scoring(&reversed)));
+    }
+
+    #[test]
+    fn test_rules_hash_changes_with_scoring() {
+        assert_ne!(rules_hash(&scoring(&[("SDXL", 2.5)])), rules_hash(&scoring(&[("SDXL", 2.0)])));
+    }
+
+    #[test]
+    fn test_content_hash_depends_on_row_boundaries() {
+        let joined = content_hash([json!("ab")].into_iter());
+        let split = content_hash([json!("a"), json!("b")].into_iter());
+
+        assert_ne!(joined, split);
+        assert_eq!(joined.len(), 64);
+    }
+}
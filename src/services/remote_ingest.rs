@@ -0,0 +1,160 @@
+use std::{sync::Arc, time::Duration};
+
+use reqwest::{redirect, Url};
+
+use crate::error::types::AppError;
+
+/// Redirects followed before a download is refused
+const MAX_REDIRECTS: usize = 5;
+
+/// File name used when the URL path does not end in one
+const DEFAULT_REMOTE_FILE_NAME: &str = "download.json";
+
+/// A downloaded benchmark file
+#[derive(Debug, Clone)]
+pub struct RemoteFile {
+    pub url: String,
+    /// Last segment of the URL path
+    pub file_name: String,
+    pub content: Vec<u8>,
+}
+
+/// Downloads benchmark files, only from an allowlist of hosts
+///
+/// Redirects are followed only while they stay on allowed hosts, and bodies
+/// larger than `max_bytes` are refused without being read in full.
+#[derive(Debug, Clone)]
+pub struct RemoteFileFetcher {
+    client: reqwest::Client,
+    allowed_hosts: Arc<Vec<String>>,
+    max_bytes: usize,
+}
+
+impl RemoteFileFetcher {
+    pub fn new(allowed_hosts: &[String], timeout: Duration, max_bytes: usize) -> Result<Self, AppError> {
+        let allowed_hosts = Arc::new(allowed_hosts.iter().map(|host| host.trim().to_ascii_lowercase()).collect::<Vec<_>>());
+        let redirect_hosts = allowed_hosts.clone();
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .redirect(redirect::Policy::custom(move |attempt| {
+                if attempt.previous().len() > MAX_REDIRECTS {
+                    attempt.error("too many redirects")
+                } else if is_allowed(&redirect_hosts, attempt.url()) {
+                    attempt.follow()
+                } else {
+                    attempt.stop()
+                }
+            }))
+            .build()
+            .map_err(|e| AppError::internal(format!("Failed to build download client: {}", e)))?;
+
+        Ok(Self {
+            client,
+            allowed_hosts,
+            max_bytes,
+        })
+    }
+
+    /// Parse `url`, refusing anything but http(s) on an allowed host
+    pub fn check_url(&self, url: &str) -> Result<Url, AppError> {
+        let parsed = Url::parse(url).map_err(|e| AppError::bad_request(format!("Invalid URL '{}': {}", url, e)))?;
+        if !matches!(parsed.scheme(), "http" | "https") {
+            return Err(AppError::bad_request(format!("URL '{}' must use http or https", url)));
+        }
+        if !is_allowed(&self.allowed_hosts, &parsed) {
+            return Err(AppError::bad_request(format!(
+                "Host of '{}' is not allowed; allowed hosts: {}",
+                url,
+                self.allowed_hosts.join(", ")
+            )));
+        }
+
+        Ok(parsed)
+    }
+
+    /// Download `url`
+    pub async fn fetch(&self, url: &str) -> Result<RemoteFile, AppError> {
+        let parsed = self.check_url(url)?;
+        let file_name = parsed
+            .path_segments()
+            .and_then(|mut segments| segments.next_back())
+            .filter(|segment| !segment.is_empty())
+            .unwrap_or(DEFAULT_REMOTE_FILE_NAME)
+            .to_string();
+
+        let mut response = self
+            .client
+            .get(parsed)
+            .send()
+            .await
+            .map_err(|e| AppError::file_upload(format!("Download of {} failed: {}", url, e)))?;
+        if !response.status().is_success() {
+            return Err(AppError::file_upload(format!(
+                "Download of {} answered {}",
+                url,
+                response.status()
+            )));
+        }
+
+        let too_large = || {
+            AppError::file_upload(format!(
+                "Download of {} exceeds the maximum size of {} bytes",
+                url, self.max_bytes
+            ))
+        };
+        if response.content_length().is_some_and(|length| length > self.max_bytes as u64) {
+            return Err(too_large());
+        }
+
+        let mut content = Vec::new();
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|e| AppError::file_upload(format!("Download of {} failed: {}", url, e)))?
+        {
+            if content.len() + chunk.len() > self.max_bytes {
+                return Err(too_large());
+            }
+            content.extend_from_slice(&chunk);
+        }
+
+        Ok(RemoteFile {
+            url: url.to_string(),
+            file_name,
+            content,
+        })
+    }
+}
+
+fn is_allowed(allowed_hosts: &[String], url: &Url) -> bool {
+    url.host_str()
+        .is_some_and(|host| allowed_hosts.iter().any(|allowed| allowed.eq_ignore_ascii_case(host)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fetcher() -> RemoteFileFetcher {
+        RemoteFileFetcher::new(&["Raw.GitHubUserContent.com".to_string()], Duration::from_secs(5), 1024).unwrap()
+    }
+
+    #[test]
+    fn test_check_url_accepts_allowed_hosts() {
+        let url = fetcher()
+            .check_url("https://raw.githubusercontent.com/vladmandic/sd-data/main/benchmark.json")
+            .unwrap();
+
+        assert_eq!(url.host_str(), Some("raw.githubusercontent.com"));
+    }
+
+    #[test]
+    fn test_check_url_rejects_other_hosts_and_schemes() {
+        let fetcher = fetcher();
+
+        assert!(fetcher.check_url("https://evil.example.com/benchmark.json").is_err());
+        assert!(fetcher.check_url("https://raw.githubusercontent.com.evil.example.com/benchmark.json").is_err());
+        assert!(fetcher.check_url("file:///etc/passwd").is_err());
+        assert!(fetcher.check_url("not a url").is_err());
+    }
+}
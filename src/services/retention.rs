@@ -0,0 +1,452 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::SqlitePool;
+use tokio::{task::JoinHandle, time::MissedTickBehavior};
+use tracing::{error, info, warn};
+
+use crate::{
+    config::{database::database_url, Settings},
+    error::types::AppError,
+    middleware::maintenance_mode::ensure_not_in_maintenance,
+    repositories::upload_session_repository::UploadSessionRepository,
+    services::uploads::{upload_staging_dir, ResumableUploadService},
+};
+
+/// Where a stored file lives, and so why it was written
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StorageArea {
+    /// Resumable upload sessions staged under `file_upload.temp_dir`, one directory each
+    UploadStaging,
+    /// Database backups under `retention.backup_dir`, written by the deployment's backup job
+    Backups,
+    /// Rotated copies of `logging.file_path`, e.g. `app.log.1`
+    Logs,
+}
+
+/// Directory the settings are layered from, relative to the working directory
+const CONFIG_DIR: &str = "config";
+
+/// Why a file is pruned
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PruneReason {
+    /// Older than `retention.max_age_days`
+    Age,
+    /// Among the oldest backups and logs over `retention.max_total_bytes`
+    Quota,
+}
+
+/// A file the cleanup deletes, or would delete in a dry run
+///
+/// For [`StorageArea::UploadStaging`] the path is a whole session directory and
+/// the size is that of its parts.
+#[derive(Debug, Clone, Serialize)]
+pub struct PrunedFile {
+    pub path: String,
+    pub area: StorageArea,
+    pub size_bytes: u64,
+    pub modified_at: String,
+    pub reason: PruneReason,
+}
+
+/// What one cleanup deleted, or would delete in a dry run
+#[derive(Debug, Clone, Serialize)]
+pub struct CleanupReport {
+    pub dry_run: bool,
+    pub scanned_files: usize,
+    /// Size of the scanned files before the cleanup
+    pub total_bytes: u64,
+    pub freed_bytes: u64,
+    pub files: Vec<PrunedFile>,
+    /// Files that could not be deleted, with the error
+    pub failures: Vec<String>,
+}
+
+struct StoredFile {
+    path: PathBuf,
+    area: StorageArea,
+    size_bytes: u64,
+    modified_at: DateTime<Utc>,
+}
+
+/// A directory whose files the cleanup manages
+struct ManagedDir {
+    area: StorageArea,
+    dir: PathBuf,
+    /// When set, only the files directly in `dir` whose name starts with it
+    file_prefix: Option<String>,
+    /// Each directory directly in `dir` is one upload session, pruned whole
+    sessions: bool,
+}
+
+impl ManagedDir {
+    /// Whether the cleanup could delete `path`, or anything under it
+    fn covers(&self, path: &Path) -> bool {
+        let (dir, path) = (absolute(&self.dir), absolute(path));
+        match &self.file_prefix {
+            Some(prefix) => {
+                path.parent() == Some(dir.as_path())
+                    && path.file_name().and_then(|name| name.to_str()).is_some_and(|name| name.starts_with(prefix.as_str()))
+            }
+            None => path.starts_with(&dir),
+        }
+    }
+
+    fn matches(&self, file_name: &str) -> bool {
+        self.file_prefix.as_deref().is_none_or(|prefix| file_name.starts_with(prefix))
+    }
+}
+
+/// Age and quota limits over the storage areas, from `settings.retention`
+pub struct StorageRetention {
+    areas: Vec<ManagedDir>,
+    /// Never deleted, e.g. the log file being written
+    protected: Vec<PathBuf>,
+    /// Paths no area may cover, e.g. the database file
+    guarded: Vec<PathBuf>,
+    /// Areas left unmanaged because they cover a guarded path, one message each
+    rejected: Vec<String>,
+    max_age: chrono::Duration,
+    max_total_bytes: Option<u64>,
+}
+
+impl StorageRetention {
+    /// Limits over the upload staging and backup directories and the rotated logs; the active log file is kept
+    ///
+    /// The database the server opens, with its `-wal`, `-shm` and `-journal`
+    /// files, and the config directory are guarded: an area that would cover
+    /// any of them is rejected rather than managed.
+    pub fn from_settings(settings: &Settings) -> Self {
+        let mut retention = Self::new(settings.retention.max_age_days, settings.retention.max_total_bytes);
+        retention.guarded.push(PathBuf::from(CONFIG_DIR));
+        // The pool opens DATABASE_URL, not `settings.database.url`; the demo's database is in memory
+        if !settings.is_demo() {
+            retention.guarded.extend(database_files(&database_url()));
+        }
+
+        retention = retention
+            .upload_sessions(upload_staging_dir(&settings.file_upload))
+            .area(StorageArea::Backups, settings.retention.backup_dir.clone());
+        if let Some(log_file) = &settings.logging.file_path {
+            retention = retention.rotated_logs(log_file);
+        }
+
+        retention
+    }
+
+    pub fn new(max_age_days: u64, max_total_bytes: Option<u64>) -> Self {
+        Self {
+            areas: Vec::new(),
+            protected: Vec::new(),
+            guarded: Vec::new(),
+            rejected: Vec::new(),
+            max_age: chrono::Duration::days(max_age_days as i64),
+            max_total_bytes,
+        }
+    }
+
+    /// Manage the files under `dir`; a directory already managed is not scanned twice
+    pub fn area(self, area: StorageArea, dir: PathBuf) -> Self {
+        if self.areas.iter().any(|managed| managed.dir == dir) {
+            return self;
+        }
+        self.manage(ManagedDir { area, dir, file_prefix: None, sessions: false })
+    }
+
+    /// Manage the upload sessions staged in `dir`, each aged by its most recently written part
+    ///
+    /// A session left unfinished past the age limit is deleted with all its
+    /// parts, so a half-deleted session is never left behind. The quota never
+    /// deletes a session, which may still be in use.
+    pub fn upload_sessions(self, dir: PathBuf) -> Self {
+        if self.areas.iter().any(|managed| managed.dir == dir) {
+            return self;
+        }
+        self.manage(ManagedDir {
+            area: StorageArea::UploadStaging,
+            dir,
+            file_prefix: None,
+            sessions: true,
+        })
+    }
+
+    /// Manage the rotated copies of `log_file`, the files next to it named `<name>.<suffix>`
+    ///
+    /// Only those files are touched, never the log itself or anything else in
+    /// its directory, which may well be the working directory.
+    pub fn rotated_logs(mut self, log_file: &Path) -> Self {
+        self.protected.push(log_file.to_path_buf());
+        let Some(file_name) = log_file.file_name().and_then(|name| name.to_str()) else {
+            return self;
+        };
+        let dir = log_file.parent().filter(|dir| !dir.as_os_str().is_empty()).unwrap_or(Path::new("."));
+        // Already managed whole, rotated logs included
+        if self.areas.iter().any(|managed| managed.dir == dir && managed.file_prefix.is_none()) {
+            return self;
+        }
+
+        self.manage(ManagedDir {
+            area: StorageArea::Logs,
+            dir: dir.to_path_buf(),
+            file_prefix: Some(format!("{}.", file_name)),
+            sessions: false,
+        })
+    }
+
+    /// Why areas were left unmanaged, empty when every configured one is managed
+    pub fn rejected(&self) -> &[String] {
+        &self.rejected
+    }
+
+    fn manage(mut self, managed: ManagedDir) -> Self {
+        match self.guarded.iter().find(|guarded| managed.covers(guarded)) {
+            Some(guarded) => self.rejected.push(format!(
+                "Retention area {} is not managed: it covers {}",
+                managed.dir.display(),
+                guarded.display()
+            )),
+            None => self.areas.push(managed),
+        }
+        self
+    }
+
+    /// Delete the files past the limits as of `now`, or with `dry_run` only report them
+    ///
+    /// Files older than the maximum age go first; if the rest still exceed the
+    /// quota, the oldest backups and logs go until it fits. Upload sessions
+    /// count towards the quota but are only ever deleted by age. Missing directories are
+    /// skipped, and a file that cannot be deleted is reported without stopping
+    /// the cleanup.
+    pub fn run(&self, now: DateTime<Utc>, dry_run: bool) -> Result<CleanupReport, AppError> {
+        let mut stored = Vec::new();
+        for managed in &self.areas {
+            self.scan(managed, &managed.dir, &mut stored).map_err(|e| {
+                error!("Failed to scan {}: {}", managed.dir.display(), e);
+                AppError::internal(format!("Failed to scan {}: {}", managed.dir.display(), e))
+            })?;
+        }
+        stored.sort_by_key(|file| file.modified_at);
+
+        let total_bytes: u64 = stored.iter().map(|file| file.size_bytes).sum();
+        let cutoff = now - self.max_age;
+        let mut remaining_bytes = total_bytes;
+        let mut pruned = Vec::new();
+        // Oldest first, so once a file is within both limits every newer one is too
+        for file in &stored {
+            let reason = if file.modified_at < cutoff {
+                PruneReason::Age
+            } else if self.max_total_bytes.is_none_or(|quota| remaining_bytes <= quota) {
+                break;
+            } else if file.area == StorageArea::UploadStaging {
+                // A session within the age limit may still be receiving parts or being assembled
+                continue;
+            } else {
+                PruneReason::Quota
+            };
+            remaining_bytes -= file.size_bytes;
+            pruned.push((file, reason));
+        }
+
+        let mut report = CleanupReport {
+            dry_run,
+            scanned_files: stored.len(),
+            total_bytes,
+            freed_bytes: 0,
+            files: Vec::new(),
+            failures: Vec::new(),
+        };
+        for (file, reason) in pruned {
+            if !dry_run
+                && let Err(e) = remove(file)
+            {
+                warn!("Failed to delete {}: {}", file.path.display(), e);
+                report.failures.push(format!("{}: {}", file.path.display(), e));
+                continue;
+            }
+            report.freed_bytes += file.size_bytes;
+            report.files.push(PrunedFile {
+                path: file.path.display().to_string(),
+                area: file.area,
+                size_bytes: file.size_bytes,
+                modified_at: file.modified_at.to_rfc3339(),
+                reason,
+            });
+        }
+
+        Ok(report)
+    }
+
+    fn scan(&self, managed: &ManagedDir, dir: &Path, stored: &mut Vec<StoredFile>) -> io::Result<()> {
+        let entries = match fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(e),
+        };
+
+        for entry in entries {
+            let entry = entry?;
+            let path = entry.path();
+            let metadata = entry.metadata()?;
+            if managed.sessions {
+                if metadata.is_dir() {
+                    stored.push(session_dir(managed.area, path, &metadata)?);
+                }
+            } else if metadata.is_dir() {
+                // A nested managed directory is scanned as its own area
+                if managed.file_prefix.is_none() && !self.areas.iter().any(|other| other.dir == path) {
+                    self.scan(managed, &path, stored)?;
+                }
+            } else if metadata.is_file()
+                && managed.matches(&entry.file_name().to_string_lossy())
+                && !self.protected.contains(&path)
+            {
+                stored.push(StoredFile {
+                    path,
+                    area: managed.area,
+                    size_bytes: metadata.len(),
+                    modified_at: metadata.modified()?.into(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A staged upload session as one file: the size of its parts, last modified when its newest part was written
+///
+/// A session without parts is aged by its directory, created when the session started.
+fn session_dir(area: StorageArea, path: PathBuf, metadata: &fs::Metadata) -> io::Result<StoredFile> {
+    let mut size_bytes = 0;
+    let mut newest_part = None;
+    for entry in fs::read_dir(&path)? {
+        let metadata = entry?.metadata()?;
+        if metadata.is_file() {
+            size_bytes += metadata.len();
+            newest_part = newest_part.max(Some(metadata.modified()?));
+        }
+    }
+    let modified_at = match newest_part {
+        Some(modified_at) => modified_at,
+        None => metadata.modified()?,
+    };
+
+    Ok(StoredFile {
+        path,
+        area,
+        size_bytes,
+        modified_at: modified_at.into(),
+    })
+}
+
+fn remove(file: &StoredFile) -> io::Result<()> {
+    match file.area {
+        StorageArea::UploadStaging => fs::remove_dir_all(&file.path),
+        StorageArea::Backups | StorageArea::Logs => fs::remove_file(&file.path),
+    }
+}
+
+/// `path` made absolute against the working directory, without touching the filesystem
+fn absolute(path: &Path) -> PathBuf {
+    std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// The files of a `sqlite:` database URL: the database and its journals, none for an in-memory database
+fn database_files(url: &str) -> Vec<PathBuf> {
+    let Some(path) = url.strip_prefix("sqlite:") else {
+        return Vec::new();
+    };
+    let path = path.strip_prefix("//").unwrap_or(path);
+    let path = path.split('?').next().unwrap_or_default();
+    if path.is_empty() || path == ":memory:" {
+        return Vec::new();
+    }
+
+    let mut files = vec![PathBuf::from(path)];
+    files.extend(["-wal", "-shm", "-journal"].map(|suffix| PathBuf::from(format!("{}{}", path, suffix))));
+    files
+}
+
+/// Report what a cleanup would delete now, without deleting anything
+pub async fn preview_storage_cleanup(settings: &Settings) -> Result<CleanupReport, AppError> {
+    let retention = StorageRetention::from_settings(settings);
+
+    tokio::task::spawn_blocking(move || retention.run(Utc::now(), true))
+        .await
+        .map_err(|e| AppError::internal(format!("Storage cleanup preview failed: {}", e)))?
+}
+
+/// Mark the upload sessions whose staging directories a cleanup deleted as failed
+async fn expire_pruned_sessions(uploads: &ResumableUploadService, report: &CleanupReport) {
+    let pruned = report.files.iter().filter(|file| file.area == StorageArea::UploadStaging);
+    for id in pruned.filter_map(|file| Path::new(&file.path).file_name()?.to_str()) {
+        if let Err(e) = uploads.expire(id).await {
+            warn!("Failed to expire upload {}: {}", id, e);
+        }
+    }
+}
+
+/// Start pruning stored files when `settings.retention.enabled` is set
+///
+/// The first cleanup runs one interval after startup; a failed cleanup is
+/// logged and retried at the next interval. Nothing is deleted while
+/// maintenance mode is on; the cleanup waits for the next interval. Upload
+/// sessions whose staged parts were deleted are marked failed, as are those
+/// stalled while ingesting.
+pub fn spawn_storage_cleanup(pool: SqlitePool, settings: &Settings) -> Option<JoinHandle<()>> {
+    if !settings.retention.enabled {
+        return None;
+    }
+
+    let period = Duration::from_secs(settings.retention.interval_seconds);
+    let retention = std::sync::Arc::new(StorageRetention::from_settings(settings));
+    let uploads = ResumableUploadService::new(
+        UploadSessionRepository::new(pool.clone()),
+        upload_staging_dir(&settings.file_upload),
+        settings.file_upload.max_resumable_size_mb * 1024 * 1024,
+    );
+    info!("Pruning stored files every {} seconds", settings.retention.interval_seconds);
+
+    Some(tokio::spawn(async move {
+        let mut interval = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            match ensure_not_in_maintenance(&pool).await {
+                Ok(()) => {}
+                Err(AppError::Maintenance(_)) => {
+                    info!("Skipped storage cleanup during maintenance");
+                    continue;
+                }
+                Err(e) => {
+                    error!("Storage cleanup skipped: {}", e);
+                    continue;
+                }
+            }
+            let retention = retention.clone();
+            match tokio::task::spawn_blocking(move || retention.run(Utc::now(), false)).await {
+                Ok(Ok(report)) => {
+                    info!(
+                        "Storage cleanup deleted {} of {} files, freeing {} bytes",
+                        report.files.len(),
+                        report.scanned_files,
+                        report.freed_bytes
+                    );
+                    expire_pruned_sessions(&uploads, &report).await;
+                }
+                Ok(Err(e)) => error!("Storage cleanup failed: {}", e),
+                Err(e) => error!("Storage cleanup task failed: {}", e),
+            }
+            if let Err(e) = uploads.expire_stalled(Utc::now()).await {
+                error!("Failed to expire stalled uploads: {}", e);
+            }
+        }
+    }))
+}
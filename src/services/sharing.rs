@@ -0,0 +1,5 @@
+// Signed share links for public run permalinks
+pub mod share_token_service;
+
+// Re-export for easy access
+pub use share_token_service::*;
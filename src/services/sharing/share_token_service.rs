@@ -0,0 +1,141 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::{config::settings::SharingConfig, error::types::AppError};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Longest share link lifetime; larger configured TTLs are clamped to it
+const MAX_TOKEN_TTL_DAYS: u64 = 36_500;
+
+/// Issues and verifies share tokens for run permalinks
+///
+/// A token is `base64url("<run_id>:<expires_unix>") . base64url(hmac_sha256)`, so
+/// links need no storage and every link is revoked by rotating the secret. An
+/// `expires_unix` of 0 means the link never expires.
+pub struct ShareTokenService {
+    secret: Vec<u8>,
+    ttl: Option<Duration>,
+}
+
+impl ShareTokenService {
+    pub fn new(config: &SharingConfig) -> Self {
+        Self {
+            secret: config.secret.as_bytes().to_vec(),
+            ttl: config
+                .token_ttl_days
+                .map(|days| Duration::days(days.min(MAX_TOKEN_TTL_DAYS) as i64)),
+        }
+    }
+
+    /// Create a token for a run, returning it with its expiry
+    pub fn issue(&self, run_id: i64) -> (String, Option<DateTime<Utc>>) {
+        self.issue_at(run_id, Utc::now())
+    }
+
+    fn issue_at(&self, run_id: i64, now: DateTime<Utc>) -> (String, Option<DateTime<Utc>>) {
+        let expires_at = self.ttl.map(|ttl| now + ttl);
+        let payload = format!("{}:{}", run_id, expires_at.map(|at| at.timestamp()).unwrap_or(0));
+        let signature = self.mac(payload.as_bytes()).finalize().into_bytes();
+
+        let token = format!("{}.{}", URL_SAFE_NO_PAD.encode(payload), URL_SAFE_NO_PAD.encode(signature));
+        (token, expires_at)
+    }
+
+    /// The run id a token was issued for
+    ///
+    /// Malformed, forged and expired tokens are all reported as a missing share
+    /// link so the response does not help anyone guess valid tokens.
+    pub fn verify(&self, token: &str) -> Result<i64, AppError> {
+        self.verify_at(token, Utc::now())
+    }
+
+    fn verify_at(&self, token: &str, now: DateTime<Utc>) -> Result<i64, AppError> {
+        let not_found = || AppError::not_found("Share link not found or expired");
+
+        let (payload, signature) = token.split_once('.').ok_or_else(not_found)?;
+        let payload = URL_SAFE_NO_PAD.decode(payload).map_err(|_| not_found())?;
+        let signature = URL_SAFE_NO_PAD.decode(signature).map_err(|_| not_found())?;
+        self.mac(&payload).verify_slice(&signature).map_err(|_| not_found())?;
+
+        let payload = String::from_utf8(payload).map_err(|_| not_found())?;
+        let (run_id, expires_unix) = payload.split_once(':').ok_or_else(not_found)?;
+        let run_id = run_id.parse::<i64>().map_err(|_| not_found())?;
+        let expires_unix = expires_unix.parse::<i64>().map_err(|_| not_found())?;
+
+        if expires_unix != 0 {
+            let expires_at = Utc.timestamp_opt(expires_unix, 0).single().ok_or_else(not_found)?;
+            if expires_at <= now {
+                return Err(not_found());
+            }
+        }
+
+        Ok(run_id)
+    }
+
+    fn mac(&self, payload: &[u8]) -> HmacSha256 {
+        let mut mac = HmacSha256::new_from_slice(&self.secret).expect("HMAC accepts keys of any length");
+        mac.update(payload);
+        mac
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn service(secret: &str, token_ttl_days: Option<u64>) -> ShareTokenService {
+        ShareTokenService::new(&SharingConfig {
+            secret: secret.to_string(),
+            token_ttl_days,
+        })
+    }
+
+    #[test]
+    fn test_issued_token_verifies() {
+        let service = service("a-sufficiently-long-secret", None);
+        let (token, expires_at) = service.issue(42);
+
+        assert!(expires_at.is_none());
+        assert_eq!(service.verify(&token).unwrap(), 42);
+    }
+
+    #[test]
+    fn test_tampered_or_foreign_tokens_are_rejected() {
+        let service_a = service("a-sufficiently-long-secret", None);
+        let service_b = service("another-sufficiently-long-secret", None);
+        let (token, _) = service_a.issue(42);
+
+        assert!(service_b.verify(&token).is_err());
+
+        let (_, signature) = token.split_once('.').unwrap();
+        let forged = format!("{}.{}", URL_SAFE_NO_PAD.encode("43:0"), signature);
+        assert!(service_a.verify(&forged).is_err());
+
+        assert!(service_a.verify("not-a-token").is_err());
+        assert!(service_a.verify("").is_err());
+    }
+
+    #[test]
+    fn test_tokens_expire_after_ttl() {
+        let service = service("a-sufficiently-long-secret", Some(7));
+        let issued_at = Utc::now();
+        let (token, expires_at) = service.issue_at(42, issued_at);
+
+        assert_eq!(expires_at, Some(issued_at + Duration::days(7)));
+        assert_eq!(service.verify_at(&token, issued_at + Duration::days(6)).unwrap(), 42);
+        assert!(service.verify_at(&token, issued_at + Duration::days(8)).is_err());
+    }
+
+    #[test]
+    fn test_oversized_ttl_is_clamped() {
+        let service = service("a-sufficiently-long-secret", Some(u64::MAX));
+        let issued_at = Utc::now();
+        let (token, expires_at) = service.issue_at(42, issued_at);
+
+        assert_eq!(expires_at, Some(issued_at + Duration::days(MAX_TOKEN_TTL_DAYS as i64)));
+        assert_eq!(service.verify_at(&token, issued_at).unwrap(), 42);
+    }
+}
@@ -0,0 +1,137 @@
+use std::{
+    cell::RefCell,
+    future::Future,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+use tracing::{field, info, info_span, Instrument, Span};
+
+/// Wall-clock and database time spent in one stage of a processing step
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StageTiming {
+    pub stage: String,
+    pub duration_ms: f64,
+    pub rows: usize,
+    /// `None` when the stage took no measurable time
+    pub rows_per_sec: Option<f64>,
+    pub db_time_ms: f64,
+}
+
+tokio::task_local! {
+    static STAGE_TIMINGS: RefCell<Vec<StageTiming>>;
+}
+
+/// Times one stage inside a `pipeline_stage` tracing span
+///
+/// Database calls awaited through `db` run inside the span and count towards
+/// `db_time_ms`; everything else between `start` and `finish` is wall-clock only.
+pub struct StageTimer {
+    stage: &'static str,
+    span: Span,
+    started: Instant,
+    db_time: Duration,
+}
+
+impl StageTimer {
+    pub fn start(stage: &'static str) -> Self {
+        let span = info_span!(
+            "pipeline_stage",
+            stage,
+            duration_ms = field::Empty,
+            rows = field::Empty,
+            rows_per_sec = field::Empty,
+            db_time_ms = field::Empty,
+        );
+
+        Self {
+            stage,
+            span,
+            started: Instant::now(),
+            db_time: Duration::ZERO,
+        }
+    }
+
+    /// Await a database future, adding its time to the stage's DB time
+    pub async fn db<F: Future>(&mut self, future: F) -> F::Output {
+        let started = Instant::now();
+        let output = future.instrument(self.span.clone()).await;
+        self.db_time += started.elapsed();
+        output
+    }
+
+    /// Close the stage, record its fields on the span and report it to the enclosing collector
+    pub fn finish(self, rows: usize) -> StageTiming {
+        let elapsed = self.started.elapsed();
+        let duration_ms = elapsed.as_secs_f64() * 1000.0;
+        let rows_per_sec = (elapsed.as_secs_f64() > 0.0).then(|| rows as f64 / elapsed.as_secs_f64());
+        let db_time_ms = self.db_time.as_secs_f64() * 1000.0;
+
+        self.span.record("duration_ms", duration_ms);
+        self.span.record("rows", rows);
+        if let Some(rows_per_sec) = rows_per_sec {
+            self.span.record("rows_per_sec", rows_per_sec);
+        }
+        self.span.record("db_time_ms", db_time_ms);
+        self.span.in_scope(|| {
+            info!(
+                "Stage {} finished: {} rows in {:.1}ms ({:.1}ms in the database)",
+                self.stage, rows, duration_ms, db_time_ms
+            );
+        });
+
+        let timing = StageTiming {
+            stage: self.stage.to_string(),
+            duration_ms,
+            rows,
+            rows_per_sec,
+            db_time_ms,
+        };
+        let _ = STAGE_TIMINGS.try_with(|timings| timings.borrow_mut().push(timing.clone()));
+        timing
+    }
+}
+
+/// Run a future and return every stage timing finished while it ran
+pub async fn collect_stage_timings<F: Future>(future: F) -> (F::Output, Vec<StageTiming>) {
+    STAGE_TIMINGS
+        .scope(RefCell::new(Vec::new()), async move {
+            let output = future.await;
+            let timings = STAGE_TIMINGS.with(|timings| timings.take());
+            (output, timings)
+        })
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_stage_timer_tracks_db_time() {
+        let mut timer = StageTimer::start("insert_rows");
+        timer.db(tokio::time::sleep(Duration::from_millis(20))).await;
+        let timing = timer.finish(100);
+
+        assert_eq!(timing.stage, "insert_rows");
+        assert_eq!(timing.rows, 100);
+        assert!(timing.db_time_ms >= 20.0);
+        assert!(timing.duration_ms >= timing.db_time_ms);
+        assert!(timing.rows_per_sec.unwrap() > 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_collect_stage_timings_gathers_finished_stages() {
+        let ((), timings) = collect_stage_timings(async {
+            StageTimer::start("fetch").finish(3);
+            StageTimer::start("write").finish(2);
+        })
+        .await;
+
+        let stages: Vec<_> = timings.iter().map(|timing| timing.stage.as_str()).collect();
+        assert_eq!(stages, ["fetch", "write"]);
+
+        // Outside a collector finishing a stage is still fine
+        assert_eq!(StageTimer::start("standalone").finish(0).rows, 0);
+    }
+}
@@ -0,0 +1,5 @@
+// Resumable (chunked) upload support
+pub mod resumable_upload_service;
+
+// Re-export for easy access
+pub use resumable_upload_service::*;
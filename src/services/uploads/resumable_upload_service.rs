@@ -0,0 +1,386 @@
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use tokio::fs;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::{
+    config::settings::FileUploadConfig,
+    error::types::AppError,
+    models::upload_session::{
+        CreateUploadSession, UploadPart, UploadProgress, UploadSession, UPLOAD_STATUS_COMPLETED,
+        UPLOAD_STATUS_FAILED, UPLOAD_STATUS_INGESTING, UPLOAD_STATUS_PENDING,
+    },
+    repositories::{traits::Repository, upload_session_repository::UploadSessionRepository},
+};
+
+/// Where the parts of resumable uploads are staged, one directory per session
+pub fn upload_staging_dir(config: &FileUploadConfig) -> PathBuf {
+    config.temp_dir.join("uploads")
+}
+
+/// How long a session may stay `ingesting` before the storage cleanup fails it
+pub const STALLED_INGEST_TIMEOUT_MINUTES: i64 = 60;
+
+/// The claim `assemble` takes on a session, held until `finish` records the outcome
+///
+/// Dropped unsettled, when the request is cancelled or panics before the
+/// outcome is recorded, it fails the session in the background and removes
+/// its staged parts, so the session is never left `ingesting`.
+pub struct UploadClaim {
+    id: String,
+    repository: Option<UploadSessionRepository>,
+    session_dir: PathBuf,
+}
+
+impl UploadClaim {
+    /// The claim is settled; dropping it no longer fails the session
+    fn settle(&mut self) {
+        self.repository = None;
+    }
+}
+
+impl Drop for UploadClaim {
+    fn drop(&mut self) {
+        let Some(repository) = self.repository.take() else {
+            return;
+        };
+        let (id, session_dir) = (std::mem::take(&mut self.id), std::mem::take(&mut self.session_dir));
+        warn!("Upload {} was interrupted while it was being completed", id);
+
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            error!("Upload {} stays ingesting until the cleanup fails it: no runtime to release it", id);
+            return;
+        };
+        runtime.spawn(async move {
+            let message = Some("Upload was interrupted while it was being completed");
+            if let Err(e) = repository
+                .update_status(&id, UPLOAD_STATUS_FAILED, message, &Utc::now().to_rfc3339())
+                .await
+            {
+                error!("Failed to release upload {}: {}", id, e);
+                return;
+            }
+            if let Err(e) = fs::remove_dir_all(&session_dir).await {
+                warn!("Failed to remove staging directory for upload {}: {}", id, e);
+            }
+        });
+    }
+}
+
+pub struct ResumableUploadService {
+    repository: UploadSessionRepository,
+    staging_dir: PathBuf,
+    max_size_bytes: usize,
+}
+
+impl ResumableUploadService {
+    pub fn new(repository: UploadSessionRepository, staging_dir: PathBuf, max_size_bytes: usize) -> Self {
+        Self {
+            repository,
+            staging_dir,
+            max_size_bytes,
+        }
+    }
+
+    /// Start a new upload session for the tenant
+    /// 
+    /// # Returns
+    /// * `UploadSession` - The pending session; its id is used for the part and complete calls
+    pub async fn init(&self, tenant_id: &str, request: CreateUploadSession) -> Result<UploadSession, AppError> {
+        if request.file_name.trim().is_empty() {
+            return Err(AppError::validation("file_name must not be empty"));
+        }
+        if request.total_parts.is_some_and(|parts| parts < 1) {
+            return Err(AppError::validation("total_parts must be at least 1"));
+        }
+        if let Some(total_size) = request.total_size {
+            self.check_size(total_size)?;
+        }
+
+        let now = Utc::now().to_rfc3339();
+        let session = UploadSession {
+            id: Uuid::new_v4().to_string(),
+            file_name: request.file_name,
+            status: UPLOAD_STATUS_PENDING.to_string(),
+            total_parts: request.total_parts,
+            total_size: request.total_size,
+            message: None,
+            created_at: now.clone(),
+            updated_at: now,
+            tenant_id: tenant_id.to_string(),
+        };
+
+        fs::create_dir_all(self.session_dir(&session.id)).await.map_err(|e| {
+            error!("Failed to create staging directory for upload {}: {}", session.id, e);
+            AppError::internal(format!("Failed to create staging directory: {}", e))
+        })?;
+
+        let session = self.repository.create(session).await.map_err(AppError::Database)?;
+        info!("Started upload {} for {}", session.id, session.file_name);
+
+        Ok(session)
+    }
+
+    /// Store one part of an upload, replacing an earlier copy of the same part
+    /// 
+    /// # Returns
+    /// * `UploadProgress` - The session progress after storing the part
+    pub async fn store_part(&self, tenant_id: &str, id: &str, part_number: i64, data: &[u8]) -> Result<UploadProgress, AppError> {
+        let session = self.find_session(tenant_id, id).await?;
+        if session.status != UPLOAD_STATUS_PENDING {
+            return Err(AppError::bad_request(format!(
+                "Upload {} is {} and no longer accepts parts",
+                id, session.status
+            )));
+        }
+        if part_number < 1 || session.total_parts.is_some_and(|parts| part_number > parts) {
+            return Err(AppError::bad_request(format!("Invalid part number {}", part_number)));
+        }
+        if data.is_empty() {
+            return Err(AppError::bad_request("Upload part is empty"));
+        }
+
+        let other_parts_size: i64 = self
+            .repository
+            .find_parts(id)
+            .await
+            .map_err(AppError::Database)?
+            .iter()
+            .filter(|part| part.part_number != part_number)
+            .map(|part| part.size)
+            .sum();
+        self.check_size(other_parts_size + data.len() as i64)?;
+
+        fs::write(self.part_path(id, part_number), data).await.map_err(|e| {
+            error!("Failed to write part {} of upload {}: {}", part_number, id, e);
+            AppError::internal(format!("Failed to store upload part: {}", e))
+        })?;
+
+        self.repository
+            .upsert_part(UploadPart {
+                id: None,
+                upload_id: id.to_string(),
+                part_number,
+                size: data.len() as i64,
+            })
+            .await
+            .map_err(AppError::Database)?;
+
+        self.progress(tenant_id, id).await
+    }
+
+    /// Report how much of an upload has been received
+    pub async fn progress(&self, tenant_id: &str, id: &str) -> Result<UploadProgress, AppError> {
+        let session = self.find_session(tenant_id, id).await?;
+        let parts = self.repository.find_parts(id).await.map_err(AppError::Database)?;
+
+        let received_parts = parts.len() as i64;
+        let bytes_received: i64 = parts.iter().map(|part| part.size).sum();
+
+        let percent_complete = if session.status == UPLOAD_STATUS_COMPLETED {
+            Some(100.0)
+        } else if let Some(total_size) = session.total_size.filter(|size| *size > 0) {
+            Some((bytes_received as f64 / total_size as f64 * 100.0).min(100.0))
+        } else {
+            session
+                .total_parts
+                .map(|total_parts| (received_parts as f64 / total_parts as f64 * 100.0).min(100.0))
+        };
+
+        Ok(UploadProgress {
+            session,
+            received_parts,
+            bytes_received,
+            percent_complete,
+        })
+    }
+
+    /// Check that every part arrived and concatenate them in order
+    /// 
+    /// This service:
+    /// 1. Verifies parts 1..=N are all present (and match total_parts/total_size when declared)
+    /// 2. Claims the session by moving it from pending to ingesting
+    /// 3. Reads the parts from the staging directory in order, releasing the claim on failure
+    ///
+    /// A session that is no longer pending, including one a concurrent call
+    /// claimed first, is an `InvalidState` error (409), so it is ingested once.
+    /// 
+    /// # Returns
+    /// * `(UploadSession, Vec<u8>, UploadClaim)` - The session, the assembled file and the claim to pass to `finish`
+    pub async fn assemble(&self, tenant_id: &str, id: &str) -> Result<(UploadSession, Vec<u8>, UploadClaim), AppError> {
+        let session = self.find_session(tenant_id, id).await?;
+        if session.status != UPLOAD_STATUS_PENDING {
+            return Err(AppError::invalid_state(format!("Upload {} is already {}", id, session.status)));
+        }
+
+        let parts = self.repository.find_parts(id).await.map_err(AppError::Database)?;
+        if parts.is_empty() {
+            return Err(AppError::bad_request(format!("Upload {} has no parts", id)));
+        }
+
+        if let Some(missing) = (1..=parts.len() as i64).find(|number| parts[(*number - 1) as usize].part_number != *number) {
+            return Err(AppError::bad_request(format!("Upload {} is missing part {}", id, missing)));
+        }
+        if let Some(total_parts) = session.total_parts
+            && parts.len() as i64 != total_parts
+        {
+            return Err(AppError::bad_request(format!(
+                "Upload {} has {} of {} parts",
+                id,
+                parts.len(),
+                total_parts
+            )));
+        }
+
+        let claimed = self
+            .repository
+            .transition_status(id, UPLOAD_STATUS_PENDING, UPLOAD_STATUS_INGESTING, &Utc::now().to_rfc3339())
+            .await
+            .map_err(AppError::Database)?;
+        if !claimed {
+            return Err(AppError::invalid_state(format!("Upload {} is already being completed", id)));
+        }
+        let mut claim = UploadClaim {
+            id: id.to_string(),
+            repository: Some(self.repository.clone()),
+            session_dir: self.session_dir(id),
+        };
+
+        let file = match self.read_parts(&session, &parts).await {
+            Ok(file) => file,
+            Err(e) => {
+                // Left pending, so the client can replace a part and complete again
+                self.repository
+                    .transition_status(id, UPLOAD_STATUS_INGESTING, UPLOAD_STATUS_PENDING, &Utc::now().to_rfc3339())
+                    .await
+                    .map_err(AppError::Database)?;
+                claim.settle();
+                return Err(e);
+            }
+        };
+        info!("Assembled upload {} ({} parts, {} bytes)", id, parts.len(), file.len());
+
+        Ok((session, file, claim))
+    }
+
+    /// Concatenate the staged parts in order, checking the declared total size
+    async fn read_parts(&self, session: &UploadSession, parts: &[UploadPart]) -> Result<Vec<u8>, AppError> {
+        let id = &session.id;
+        let mut file = Vec::with_capacity(parts.iter().map(|part| part.size as usize).sum());
+        for part in parts {
+            let data = fs::read(self.part_path(id, part.part_number)).await.map_err(|e| {
+                error!("Failed to read part {} of upload {}: {}", part.part_number, id, e);
+                AppError::internal(format!("Failed to read upload part: {}", e))
+            })?;
+            file.extend_from_slice(&data);
+        }
+
+        if let Some(total_size) = session.total_size
+            && file.len() as i64 != total_size
+        {
+            return Err(AppError::bad_request(format!(
+                "Upload {} is {} bytes but {} were declared",
+                id,
+                file.len(),
+                total_size
+            )));
+        }
+
+        Ok(file)
+    }
+
+    /// Record the ingest outcome of a claimed session and remove the staged parts
+    pub async fn finish(&self, mut claim: UploadClaim, outcome: Result<String, String>) -> Result<(), AppError> {
+        let id = claim.id.clone();
+        match outcome {
+            Ok(message) => self.set_status(&id, UPLOAD_STATUS_COMPLETED, Some(&message)).await?,
+            Err(message) => self.set_status(&id, UPLOAD_STATUS_FAILED, Some(&message)).await?,
+        }
+        claim.settle();
+
+        if let Err(e) = fs::remove_dir_all(self.session_dir(&id)).await {
+            warn!("Failed to remove staging directory for upload {}: {}", id, e);
+        }
+
+        Ok(())
+    }
+
+    /// Fail a pending upload whose staged parts were pruned by the storage cleanup
+    ///
+    /// An upload still `ingesting` is failed too once it has stalled, see `expire_stalled`.
+    pub async fn expire(&self, id: &str) -> Result<(), AppError> {
+        let Some(session) = self.repository.find_by_id(id.to_string()).await.map_err(AppError::Database)? else {
+            return Ok(());
+        };
+        let stalled = session.status == UPLOAD_STATUS_INGESTING && session.updated_at < stalled_before(Utc::now());
+        if session.status == UPLOAD_STATUS_PENDING || stalled {
+            self.set_status(id, UPLOAD_STATUS_FAILED, Some("Upload expired before it was completed"))
+                .await?;
+            info!("Expired upload {}", id);
+        }
+        Ok(())
+    }
+
+    /// Fail the uploads left `ingesting` for longer than `STALLED_INGEST_TIMEOUT_MINUTES` as of `now`
+    ///
+    /// A claim released normally never stalls; this catches one whose process
+    /// died mid-ingest. Returns how many uploads were failed.
+    pub async fn expire_stalled(&self, now: DateTime<Utc>) -> Result<u64, AppError> {
+        let failed = self
+            .repository
+            .fail_stale(
+                UPLOAD_STATUS_INGESTING,
+                &stalled_before(now),
+                "Upload stalled while it was being completed",
+                &now.to_rfc3339(),
+            )
+            .await
+            .map_err(AppError::Database)?;
+        if failed > 0 {
+            warn!("Failed {} uploads stalled while ingesting", failed);
+        }
+        Ok(failed)
+    }
+
+    /// The session, unless it does not exist or another tenant started it
+    async fn find_session(&self, tenant_id: &str, id: &str) -> Result<UploadSession, AppError> {
+        self.repository
+            .find_by_id(id.to_string())
+            .await
+            .map_err(AppError::Database)?
+            .filter(|session| session.tenant_id == tenant_id)
+            .ok_or_else(|| AppError::not_found(format!("Upload {}", id)))
+    }
+
+    async fn set_status(&self, id: &str, status: &str, message: Option<&str>) -> Result<(), AppError> {
+        self.repository
+            .update_status(id, status, message, &Utc::now().to_rfc3339())
+            .await
+            .map_err(AppError::Database)
+    }
+
+    fn check_size(&self, size: i64) -> Result<(), AppError> {
+        if size > self.max_size_bytes as i64 {
+            return Err(AppError::bad_request(format!(
+                "Upload size {} exceeds maximum allowed size of {} bytes",
+                size, self.max_size_bytes
+            )));
+        }
+        Ok(())
+    }
+
+    fn session_dir(&self, id: &str) -> PathBuf {
+        self.staging_dir.join(id)
+    }
+
+    fn part_path(&self, id: &str, part_number: i64) -> PathBuf {
+        self.session_dir(id).join(format!("part_{:06}", part_number))
+    }
+}
+
+/// Sessions last updated before this, as of `now`, have stalled if still ingesting
+fn stalled_before(now: DateTime<Utc>) -> String {
+    (now - chrono::Duration::minutes(STALLED_INGEST_TIMEOUT_MINUTES)).to_rfc3339()
+}
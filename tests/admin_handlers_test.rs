@@ -129,6 +129,8 @@ async fn test_save_data_replaces_existing_data() {
         model_name: Some("initial-model".to_string()),
         user: Some("initial-user".to_string()),
         notes: Some("Initial notes".to_string()),
+        created_at: None,
+        updated_at: None,
     };
     runs_repo.create(initial_run).await.unwrap();
 
@@ -255,4 +257,215 @@ async fn test_save_data_empty_array() {
     assert_eq!(response_json["rows_processed"], 0);
     assert_eq!(response_json["rows_inserted"], 0);
     assert_eq!(response_json["rows_failed"], 0);
-}
\ No newline at end of file
+}
+#[tokio::test]
+async fn test_save_data_comfyui_export() {
+    let app_state = create_test_app_state().await;
+
+    let test_data = json!({
+        "comfyui_version": "0.2.2",
+        "commit": "4f7a3cb",
+        "updated": "2024-09-10",
+        "system": { "os": "Linux", "python": "3.11.9", "pytorch": "2.4.0+cu124" },
+        "device": { "name": "NVIDIA GeForce RTX 4090", "vram_gb": 24 },
+        "results": [
+            { "timestamp": "2024-09-11T10:00:00Z", "model": "sd_xl_base_1.0", "its": [10.5, 11.0] },
+            { "timestamp": "2024-09-11T10:05:00Z", "model": "v1-5-pruned-emaonly", "its": [30.0] }
+        ]
+    });
+
+    let app = Router::new()
+        .route("/api/save-data", post(save_data))
+        .with_state(app_state.clone());
+
+    let boundary = "----WebKitFormBoundary7MA4YWxkTrZu0gW";
+    let body = create_multipart_body(&test_data.to_string());
+
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/save-data")
+        .header("content-type", format!("multipart/form-data; boundary={}", boundary))
+        .body(Body::from(body))
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let response_json: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(response_json["rows_inserted"], 2);
+
+    let runs_repo = RunsRepository::new(app_state.db.clone());
+    let all_runs = runs_repo.find_all().await.unwrap();
+    assert_eq!(all_runs.len(), 2);
+    assert!(all_runs.iter().all(|run| run.info.as_deref().is_some_and(|info| info.starts_with("app:ComfyUI"))));
+    assert!(all_runs.iter().any(|run| run.vram_usage.as_deref() == Some("10.5/11")));
+}
+
+#[tokio::test]
+async fn test_save_data_unknown_format() {
+    let app_state = create_test_app_state().await;
+
+    let app = Router::new()
+        .route("/api/save-data", post(save_data))
+        .with_state(app_state);
+
+    let boundary = "----WebKitFormBoundary7MA4YWxkTrZu0gW";
+    let body = create_multipart_body(r#"{"some_other_tool": "1.0"}"#);
+
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/save-data")
+        .header("content-type", format!("multipart/form-data; boundary={}", boundary))
+        .body(Body::from(body))
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+fn create_multi_file_body(files: &[(&str, String)]) -> String {
+    let boundary = "----WebKitFormBoundary7MA4YWxkTrZu0gW";
+    let mut body = String::new();
+    for (file_name, json_data) in files {
+        body.push_str(&format!(
+            "--{boundary}\r\n\
+            Content-Disposition: form-data; name=\"file\"; filename=\"{file_name}\"\r\n\
+            Content-Type: application/json\r\n\
+            \r\n\
+            {json_data}\r\n"
+        ));
+    }
+    body.push_str(&format!("--{boundary}--\r\n"));
+    body
+}
+
+fn monthly_export(users: &[&str]) -> String {
+    let rows: Vec<Value> = users
+        .iter()
+        .map(|user| {
+            json!({
+                "timestamp": "2024-01-01T10:00:00Z",
+                "vram_usage": "8GB",
+                "info": "app:test",
+                "system_info": "Windows 11",
+                "model_info": "SDXL",
+                "device_info": "RTX 4090",
+                "xformers": "true",
+                "model_name": "stable-diffusion-xl",
+                "user": user,
+                "notes": ""
+            })
+        })
+        .collect();
+    json!(rows).to_string()
+}
+
+async fn post_files(app_state: &AppState, uri: &str, files: &[(&str, String)]) -> (StatusCode, Value) {
+    let app = Router::new()
+        .route("/api/save-data", post(save_data))
+        .with_state(app_state.clone());
+    let boundary = "----WebKitFormBoundary7MA4YWxkTrZu0gW";
+
+    let request = Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header("content-type", format!("multipart/form-data; boundary={}", boundary))
+        .body(Body::from(create_multi_file_body(files)))
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+}
+
+#[tokio::test]
+async fn test_save_data_multiple_files_replace_atomically() {
+    let app_state = create_test_app_state().await;
+    let runs_repo = RunsRepository::new(app_state.db.clone());
+
+    let (status, _) = post_files(&app_state, "/api/save-data", &[("old.json", monthly_export(&["old"]))]).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = post_files(
+        &app_state,
+        "/api/save-data",
+        &[("january.json", monthly_export(&["a", "b"])), ("february.json", monthly_export(&["c"]))],
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["success"], true);
+    assert_eq!(body["rows_inserted"], 3);
+    let files = body["files"].as_array().unwrap();
+    assert_eq!(files.len(), 2);
+    assert_eq!(files[0]["file_name"], "january.json");
+    assert_eq!(files[0]["rows_inserted"], 2);
+    assert_eq!(files[1]["file_name"], "february.json");
+    assert_eq!(files[1]["rows_inserted"], 1);
+    // Both files replace the earlier data together
+    assert_eq!(runs_repo.count().await.unwrap(), 3);
+
+    // One bad file refuses the whole request and keeps the current data
+    let (status, _) = post_files(
+        &app_state,
+        "/api/save-data",
+        &[("march.json", monthly_export(&["d"])), ("broken.json", r#"{"some_other_tool": "1.0"}"#.to_string())],
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(runs_repo.count().await.unwrap(), 3);
+}
+
+#[tokio::test]
+async fn test_save_data_append_ingests_files_separately() {
+    let app_state = create_test_app_state().await;
+    let runs_repo = RunsRepository::new(app_state.db.clone());
+
+    let (status, _) = post_files(&app_state, "/api/save-data", &[("old.json", monthly_export(&["old"]))]).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = post_files(
+        &app_state,
+        "/api/save-data?append=true",
+        &[("january.json", monthly_export(&["a", "b"])), ("broken.json", r#"{"some_other_tool": "1.0"}"#.to_string())],
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["success"], false);
+    assert_eq!(body["rows_inserted"], 2);
+    let files = body["files"].as_array().unwrap();
+    assert_eq!(files[0]["success"], true);
+    assert_eq!(files[1]["success"], false);
+    assert_eq!(files[1]["status_code"], 400);
+
+    // The earlier run is kept and the good file's runs are added despite the bad file
+    assert_eq!(runs_repo.count().await.unwrap(), 3);
+}
+
+#[tokio::test]
+async fn test_save_data_append_reports_delta() {
+    let app_state = create_test_app_state().await;
+
+    let (status, body) = post_files(&app_state, "/api/save-data", &[("old.json", monthly_export(&["a", "b"]))]).await;
+    assert_eq!(status, StatusCode::OK);
+    // Replacing the data has nothing to compare against
+    assert!(body.get("delta").is_none());
+
+    let mut rows: Vec<Value> = serde_json::from_str(&monthly_export(&["a", "b", "c"])).unwrap();
+    rows[1]["vram_usage"] = json!("9GB");
+    let (status, body) = post_files(
+        &app_state,
+        "/api/save-data?append=true",
+        &[("rerun.json", json!(rows).to_string()), ("again.json", monthly_export(&["c"]))],
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let files = body["files"].as_array().unwrap();
+    assert_eq!(files[0]["delta"], json!({ "new_rows": 1, "duplicate_rows": 1, "updated_rows": 1 }));
+    // Later files of a request are compared against the earlier ones too
+    assert_eq!(files[1]["delta"], json!({ "new_rows": 0, "duplicate_rows": 1, "updated_rows": 0 }));
+    assert_eq!(body["delta"], json!({ "new_rows": 1, "duplicate_rows": 2, "updated_rows": 1 }));
+    // The report leaves what is inserted unchanged
+    assert_eq!(body["rows_inserted"], 4);
+}
@@ -0,0 +1,67 @@
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    Router,
+};
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}},
+    error::i18n::ErrorCatalog,
+    router::create_router,
+};
+
+async fn create_app() -> Router {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    let app_state = AppState {
+        db: db_pool,
+        settings: Settings::default(),
+    };
+    create_router(app_state, Arc::new(ErrorCatalog::bundled().unwrap()))
+}
+
+async fn admin_page(app: &Router) -> String {
+    let request = Request::builder().uri("/admin").body(Body::empty()).unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let content_type = response.headers().get(header::CONTENT_TYPE).unwrap().to_str().unwrap();
+    assert!(content_type.starts_with("text/html"), "{}", content_type);
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    String::from_utf8(bytes.to_vec()).unwrap()
+}
+
+#[tokio::test]
+async fn test_admin_page_shows_stats_and_last_pipeline_step() {
+    let app = create_app().await;
+
+    let html = admin_page(&app).await;
+    assert!(html.contains("<tr><td>runs</td><td>0</td></tr>"), "{}", html);
+    assert!(html.contains("No pipeline step has run yet"));
+    assert!(html.contains("<form method=\"post\" action=\"/api/process-its\">"));
+
+    assert!(html.contains("<form method=\"post\" action=\"/api/run-pipeline\">"));
+
+    // The page's buttons post like this, without a body
+    for uri in ["/api/run-pipeline", "/api/process-its"] {
+        let request = Request::builder()
+            .method("POST")
+            .uri(uri)
+            .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body(Body::empty())
+            .unwrap();
+        assert_eq!(app.clone().oneshot(request).await.unwrap().status(), StatusCode::OK, "{}", uri);
+    }
+
+    let html = admin_page(&app).await;
+    assert!(html.contains("<p>process-its at "), "{}", html);
+}
@@ -0,0 +1,99 @@
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{header, Request, Response, StatusCode},
+    Router,
+};
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}},
+    error::i18n::ErrorCatalog,
+    router::create_router,
+};
+
+const ORIGIN: &str = "http://localhost:4022";
+
+async fn create_app() -> Router {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    let mut settings = Settings::default();
+    settings.server.cors_origins = vec![ORIGIN.to_string()];
+
+    create_router(AppState { db: db_pool, settings }, Arc::new(ErrorCatalog::bundled().unwrap()))
+}
+
+async fn send(app: &Router, request: Request<Body>) -> Response<Body> {
+    app.clone().oneshot(request).await.unwrap()
+}
+
+fn header_value(response: &Response<Body>, name: header::HeaderName) -> &str {
+    response.headers().get(name).and_then(|value| value.to_str().ok()).unwrap_or_default()
+}
+
+#[tokio::test]
+async fn test_options_lists_allowed_methods() {
+    let app = create_app().await;
+
+    let response = send(&app, Request::options("/api/leaderboard").body(Body::empty()).unwrap()).await;
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    let allow = header_value(&response, header::ALLOW);
+    for method in ["GET", "HEAD", "OPTIONS"] {
+        assert!(allow.contains(method), "{}", allow);
+    }
+
+    let response = send(&app, Request::options("/api/process-gpu").body(Body::empty()).unwrap()).await;
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    assert!(header_value(&response, header::ALLOW).contains("POST"));
+
+    // Unknown paths stay 404
+    let response = send(&app, Request::options("/api/missing").body(Body::empty()).unwrap()).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_head_and_method_not_allowed() {
+    let app = create_app().await;
+
+    let response = send(&app, Request::head("/health").body(Body::empty()).unwrap()).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    assert!(body.is_empty());
+
+    let response = send(&app, Request::delete("/api/leaderboard").body(Body::empty()).unwrap()).await;
+    assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+    let allow = header_value(&response, header::ALLOW);
+    assert!(allow.contains("GET") && allow.contains("OPTIONS"), "{}", allow);
+}
+
+#[tokio::test]
+async fn test_cors_preflight_on_any_route() {
+    let app = create_app().await;
+
+    let preflight = Request::options("/api/process-gpu")
+        .header(header::ORIGIN, ORIGIN)
+        .header(header::ACCESS_CONTROL_REQUEST_METHOD, "POST")
+        .body(Body::empty())
+        .unwrap();
+    let response = send(&app, preflight).await;
+    assert!(response.status().is_success());
+    assert_eq!(header_value(&response, header::ACCESS_CONTROL_ALLOW_ORIGIN), ORIGIN);
+    assert!(header_value(&response, header::ACCESS_CONTROL_ALLOW_METHODS).contains("POST"));
+
+    let request = Request::get("/health").header(header::ORIGIN, ORIGIN).body(Body::empty()).unwrap();
+    let response = send(&app, request).await;
+    assert_eq!(header_value(&response, header::ACCESS_CONTROL_ALLOW_ORIGIN), ORIGIN);
+
+    // Origins not listed get no CORS headers
+    let request = Request::get("/health").header(header::ORIGIN, "http://example.com").body(Body::empty()).unwrap();
+    let response = send(&app, request).await;
+    assert!(response.headers().get(header::ACCESS_CONTROL_ALLOW_ORIGIN).is_none());
+}
@@ -140,6 +140,8 @@ async fn create_required_runs(pool: &SqlitePool) -> Result<(), Box<dyn std::erro
             model_name: Some("test-model".to_string()),
             user: Some("test-user".to_string()),
             notes: Some(format!("Test run {}", i)),
+            created_at: None,
+            updated_at: None,
         };
         runs_repository.create(run).await?;
     }
@@ -158,6 +160,9 @@ fn create_test_app_details() -> Vec<AppDetails> {
             updated: Some("2024-01-01".to_string()),
             hash: Some("abc123".to_string()),
             url: Some("https://example.com/app1".to_string()),
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
         },
         // Null app_name but has URL
         AppDetails {
@@ -167,6 +172,9 @@ fn create_test_app_details() -> Vec<AppDetails> {
             updated: Some("2024-01-02".to_string()),
             hash: Some("def456".to_string()),
             url: Some("https://example.com/app2".to_string()),
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
         },
         // Null app_name but has URL (another case)
         AppDetails {
@@ -176,6 +184,9 @@ fn create_test_app_details() -> Vec<AppDetails> {
             updated: Some("2024-01-03".to_string()),
             hash: Some("ghi789".to_string()),
             url: Some("https://example.com/app3".to_string()),
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
         },
         // Both app_name and URL are null
         AppDetails {
@@ -185,6 +196,9 @@ fn create_test_app_details() -> Vec<AppDetails> {
             updated: Some("2024-01-04".to_string()),
             hash: Some("jkl012".to_string()),
             url: None,
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
         },
     ]
 }
@@ -200,6 +214,9 @@ fn create_complete_app_details() -> Vec<AppDetails> {
             updated: Some("2024-01-01".to_string()),
             hash: Some("abc123".to_string()),
             url: Some("https://example.com/complete1".to_string()),
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
         },
         // Complete data
         AppDetails {
@@ -209,6 +226,9 @@ fn create_complete_app_details() -> Vec<AppDetails> {
             updated: Some("2024-01-02".to_string()),
             hash: Some("def456".to_string()),
             url: Some("https://example.com/complete2".to_string()),
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
         },
     ]
 } 
\ No newline at end of file
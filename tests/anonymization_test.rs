@@ -0,0 +1,139 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    routing::get,
+    Router,
+};
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}, settings::AnonymizationConfig},
+    handlers::{feed::submissions_feed, share::get_shared_run, stats::leaderboard},
+    models::{performance_result::PerformanceResult, runs::Run},
+    repositories::{
+        performance_result_repository::PerformanceResultRepository,
+        runs_repository::RunsRepository,
+        traits::Repository,
+    },
+    services::{anonymization::AnonymizationService, sharing::ShareTokenService},
+};
+
+const USER: &str = "alice-from-discord";
+const NOTES: &str = "reach me at alice@example.com";
+
+async fn create_test_app_state(anonymization_enabled: bool) -> AppState {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    AppState {
+        db: db_pool,
+        settings: Settings {
+            anonymization: AnonymizationConfig {
+                enabled: anonymization_enabled,
+                salt: "test-instance-anonymization-salt".to_string(),
+            },
+            ..Settings::default()
+        },
+    }
+}
+
+fn create_app(app_state: AppState) -> Router {
+    Router::new()
+        .route("/api/share/{token}", get(get_shared_run))
+        .route("/api/feed.atom", get(submissions_feed))
+        .route("/api/leaderboard", get(leaderboard))
+        .with_state(app_state)
+}
+
+async fn fetch(app: &Router, uri: &str) -> String {
+    let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK, "{}", uri);
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    String::from_utf8(bytes.to_vec()).unwrap()
+}
+
+/// Insert a run by `USER` with `NOTES` and an avg_its, returning its share link
+async fn insert_run(app_state: &AppState) -> String {
+    let run_id = RunsRepository::new(app_state.db.clone())
+        .create(Run {
+            id: None,
+            timestamp: Some("2024-01-01T00:00:00Z".to_string()),
+            vram_usage: None,
+            info: None,
+            system_info: None,
+            model_info: None,
+            device_info: None,
+            xformers: None,
+            model_name: Some("sd-v1-5".to_string()),
+            user: Some(USER.to_string()),
+            notes: Some(NOTES.to_string()),
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap()
+        .id
+        .unwrap();
+
+    PerformanceResultRepository::new(app_state.db.clone())
+        .create(PerformanceResult { id: None, run_id: Some(run_id), its: None, avg_its: Some(12.5), vram_usage_kind: None, created_at: None, updated_at: None })
+        .await
+        .unwrap();
+
+    let (token, _) = ShareTokenService::new(&app_state.settings.sharing).issue(run_id);
+    format!("/api/share/{}", token)
+}
+
+fn public_uris(share_url: &str, query: &str) -> Vec<String> {
+    vec![
+        format!("{}?{}", share_url, query),
+        format!("/api/feed.atom?{}", query),
+        format!("/api/leaderboard?metric=avg_its&{}", query),
+    ]
+}
+
+#[tokio::test]
+async fn test_anonymize_parameter_hides_usernames_and_notes() {
+    let app_state = create_test_app_state(false).await;
+    let share_url = insert_run(&app_state).await;
+    let pseudonym = AnonymizationService::new(&app_state.settings.anonymization).pseudonym(USER);
+    let app = create_app(app_state);
+
+    for uri in public_uris(&share_url, "anonymize=true") {
+        let body = fetch(&app, &uri).await;
+        assert!(!body.contains(USER), "{} leaked the username: {}", uri, body);
+        assert!(!body.contains("alice@example.com"), "{} leaked the notes: {}", uri, body);
+        assert!(body.contains(&pseudonym), "{} has no pseudonym: {}", uri, body);
+    }
+
+    // Without the parameter the feed publishes runs as submitted, but share links never do
+    let body = fetch(&app, "/api/feed.atom?anonymize=false").await;
+    assert!(body.contains(USER));
+
+    let body = fetch(&app, &share_url).await;
+    assert!(!body.contains(USER), "share link leaked the username: {}", body);
+    assert!(!body.contains(NOTES), "share link leaked the notes: {}", body);
+    assert!(body.contains(&pseudonym));
+}
+
+#[tokio::test]
+async fn test_enabled_anonymization_cannot_be_opted_out_of() {
+    let app_state = create_test_app_state(true).await;
+    let share_url = insert_run(&app_state).await;
+    let app = create_app(app_state);
+
+    for query in ["anonymize=false", "anonymize=true", "metric_only=1"] {
+        for uri in public_uris(&share_url, query) {
+            let body = fetch(&app, &uri).await;
+            assert!(!body.contains(USER), "{} leaked the username: {}", uri, body);
+            assert!(!body.contains("alice@example.com"), "{} leaked the notes: {}", uri, body);
+        }
+    }
+}
@@ -53,6 +53,8 @@ async fn setup_test_app_details_data(pool: &SqlitePool) -> Vec<AppDetails> {
             model_name: Some("test-model-1".to_string()),
             user: Some("test-user-1".to_string()),
             notes: Some("test-notes-1".to_string()),
+            created_at: None,
+            updated_at: None,
         },
         Run {
             id: None,
@@ -66,6 +68,8 @@ async fn setup_test_app_details_data(pool: &SqlitePool) -> Vec<AppDetails> {
             model_name: Some("test-model-2".to_string()),
             user: Some("test-user-2".to_string()),
             notes: Some("test-notes-2".to_string()),
+            created_at: None,
+            updated_at: None,
         },
         Run {
             id: None,
@@ -79,6 +83,8 @@ async fn setup_test_app_details_data(pool: &SqlitePool) -> Vec<AppDetails> {
             model_name: Some("test-model-3".to_string()),
             user: Some("test-user-3".to_string()),
             notes: Some("test-notes-3".to_string()),
+            created_at: None,
+            updated_at: None,
         },
         Run {
             id: None,
@@ -92,6 +98,8 @@ async fn setup_test_app_details_data(pool: &SqlitePool) -> Vec<AppDetails> {
             model_name: Some("test-model-4".to_string()),
             user: Some("test-user-4".to_string()),
             notes: Some("test-notes-4".to_string()),
+            created_at: None,
+            updated_at: None,
         },
         Run {
             id: None,
@@ -105,6 +113,8 @@ async fn setup_test_app_details_data(pool: &SqlitePool) -> Vec<AppDetails> {
             model_name: Some("test-model-5".to_string()),
             user: Some("test-user-5".to_string()),
             notes: Some("test-notes-5".to_string()),
+            created_at: None,
+            updated_at: None,
         },
     ];
 
@@ -123,6 +133,9 @@ async fn setup_test_app_details_data(pool: &SqlitePool) -> Vec<AppDetails> {
             url: Some("https://example1.com".to_string()),
             hash: Some("abc123".to_string()),
             updated: Some("2024-01-01".to_string()),
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
         },
         AppDetails {
             id: None,
@@ -131,6 +144,9 @@ async fn setup_test_app_details_data(pool: &SqlitePool) -> Vec<AppDetails> {
             url: Some("https://example2.com".to_string()),
             hash: Some("def456".to_string()),
             updated: Some("2024-01-02".to_string()),
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
         },
         AppDetails {
             id: None,
@@ -139,6 +155,9 @@ async fn setup_test_app_details_data(pool: &SqlitePool) -> Vec<AppDetails> {
             url: None, // NULL url
             hash: Some("ghi789".to_string()),
             updated: Some("2024-01-03".to_string()),
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
         },
         AppDetails {
             id: None,
@@ -147,6 +166,9 @@ async fn setup_test_app_details_data(pool: &SqlitePool) -> Vec<AppDetails> {
             url: Some("https://example4.com".to_string()),
             hash: Some("jkl012".to_string()),
             updated: Some("2024-01-04".to_string()),
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
         },
         AppDetails {
             id: None,
@@ -155,6 +177,9 @@ async fn setup_test_app_details_data(pool: &SqlitePool) -> Vec<AppDetails> {
             url: Some("https://example5.com".to_string()),
             hash: Some("mno345".to_string()),
             updated: Some("2024-01-05".to_string()),
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
         },
     ];
 
@@ -321,6 +346,8 @@ async fn test_app_details_analysis_edge_cases() {
             model_name: Some("test-model-1".to_string()),
             user: Some("test-user-1".to_string()),
             notes: Some("test-notes-1".to_string()),
+            created_at: None,
+            updated_at: None,
         },
         Run {
             id: None,
@@ -334,6 +361,8 @@ async fn test_app_details_analysis_edge_cases() {
             model_name: Some("test-model-2".to_string()),
             user: Some("test-user-2".to_string()),
             notes: Some("test-notes-2".to_string()),
+            created_at: None,
+            updated_at: None,
         },
         Run {
             id: None,
@@ -347,6 +376,8 @@ async fn test_app_details_analysis_edge_cases() {
             model_name: Some("test-model-3".to_string()),
             user: Some("test-user-3".to_string()),
             notes: Some("test-notes-3".to_string()),
+            created_at: None,
+            updated_at: None,
         },
     ];
 
@@ -366,6 +397,9 @@ async fn test_app_details_analysis_edge_cases() {
             url: Some("https://app1.com".to_string()),
             hash: Some("hash1".to_string()),
             updated: Some("2024-01-01".to_string()),
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
         },
         AppDetails {
             id: None,
@@ -374,6 +408,9 @@ async fn test_app_details_analysis_edge_cases() {
             url: None,
             hash: Some("hash2".to_string()),
             updated: Some("2024-01-02".to_string()),
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
         },
         AppDetails {
             id: None,
@@ -382,6 +419,9 @@ async fn test_app_details_analysis_edge_cases() {
             url: Some("https://app3.com".to_string()),
             hash: Some("hash3".to_string()),
             updated: Some("2024-01-03".to_string()),
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
         },
     ];
 
@@ -0,0 +1,153 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    routing::{get, post},
+    Router,
+};
+use serde_json::Value;
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}},
+    handlers::{admin::{canonicalize_app_urls, process_app_details}, stats::its_by_app},
+    models::{performance_result::PerformanceResult, runs::Run},
+    repositories::{
+        performance_result_repository::PerformanceResultRepository,
+        runs_repository::RunsRepository,
+        traits::Repository,
+    },
+};
+
+/// process_app_details reads runs through the pool while holding a transaction,
+/// so the test database must be a file shared by several connections
+async fn create_test_app_state(name: &str) -> AppState {
+    let path = std::env::temp_dir().join(format!("sd_its_{}_{}.db", name, std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let db_config = DatabaseConfig {
+        url: format!("sqlite:{}?mode=rwc", path.display()),
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    AppState {
+        db: db_pool,
+        settings: Settings::default(),
+    }
+}
+
+fn create_app(app_state: AppState) -> Router {
+    Router::new()
+        .route("/api/process-app-details", post(process_app_details))
+        .route("/api/canonicalize-app-urls", post(canonicalize_app_urls))
+        .route("/api/stats/its-by-app", get(its_by_app))
+        .with_state(app_state)
+}
+
+async fn send(app: &Router, method: &str, uri: &str) -> (StatusCode, Value) {
+    let request = Request::builder().method(method).uri(uri).body(Body::empty()).unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+/// Insert a run with the given info string and avg_its
+async fn create_run(app_state: &AppState, info: &str, avg_its: f64) {
+    let run = RunsRepository::new(app_state.db.clone())
+        .create(Run {
+            id: None,
+            timestamp: Some("2024-01-01T00:00:00Z".to_string()),
+            vram_usage: None,
+            info: Some(info.to_string()),
+            system_info: None,
+            model_info: None,
+            device_info: None,
+            xformers: None,
+            model_name: None,
+            user: None,
+            notes: None,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap();
+
+    PerformanceResultRepository::new(app_state.db.clone())
+        .create(PerformanceResult { id: None, run_id: run.id, its: None, avg_its: Some(avg_its), vram_usage_kind: None, created_at: None, updated_at: None })
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_its_by_app_groups_url_spellings_and_forks_by_project() {
+    let app_state = create_test_app_state("its_by_app").await;
+    create_run(&app_state, "app:webui hash:aaa url:https://github.com/AUTOMATIC1111/stable-diffusion-webui.git/tree/master", 10.0).await;
+    create_run(&app_state, "app:webui hash:bbb url:https://github.com/automatic1111/stable-diffusion-webui/", 12.0).await;
+    create_run(&app_state, "app:directml hash:ccc url:https://github.com/lshqqytiger/stable-diffusion-webui-directml.git/tree/master", 5.0).await;
+    create_run(&app_state, "app:ComfyUI hash:ddd url:https://github.com/comfyanonymous/ComfyUI", 18.0).await;
+    create_run(&app_state, "app:unknown hash:eee", 1.0).await;
+    let app = create_app(app_state.clone());
+
+    let (status, _) = send(&app, "POST", "/api/process-app-details").await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = send(&app, "POST", "/api/canonicalize-app-urls").await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["total_rows"], 5);
+    assert_eq!(body["parsed_rows"], 4);
+    assert_eq!(body["fork_rows"], 1);
+
+    let (owner, repo): (Option<String>, Option<String>) =
+        sqlx::query_as("SELECT url_owner, url_repo FROM AppDetails WHERE url LIKE '%directml%'")
+            .fetch_one(&app_state.db)
+            .await
+            .unwrap();
+    assert_eq!(owner.as_deref(), Some("lshqqytiger"));
+    assert_eq!(repo.as_deref(), Some("stable-diffusion-webui-directml"));
+
+    let (status, stats) = send(&app, "GET", "/api/stats/its-by-app").await;
+    assert_eq!(status, StatusCode::OK, "{}", stats);
+    let rows = stats["data"].as_array().unwrap();
+    assert_eq!(rows.len(), 3);
+
+    let webui = rows.iter().find(|row| row["project"] == "automatic1111/stable-diffusion-webui").unwrap();
+    assert_eq!(webui["run_count"], 3);
+    assert_eq!(webui["fork_run_count"], 1);
+    assert_eq!(webui["avg_its"], 9.0);
+
+    let comfy = rows.iter().find(|row| row["project"] == "comfyanonymous/comfyui").unwrap();
+    assert_eq!(comfy["run_count"], 1);
+    assert_eq!(comfy["fork_run_count"], 0);
+
+    // The run without a URL is kept apart
+    let unattributed = rows.iter().find(|row| row["project"].is_null()).unwrap();
+    assert_eq!(unattributed["run_count"], 1);
+}
+
+#[tokio::test]
+async fn test_rebuilding_app_details_keeps_projects_and_commit_dates() {
+    let app_state = create_test_app_state("rebuild_app_details").await;
+    create_run(&app_state, "app:directml hash:ABC1234 url:https://github.com/lshqqytiger/stable-diffusion-webui-directml.git", 5.0).await;
+    sqlx::query("INSERT INTO commit_dates (owner, repo, hash, commit_date, looked_up_at) VALUES ('lshqqytiger', 'stable-diffusion-webui-directml', 'abc1234', '2023-03-02T10:00:00Z', '2024-01-01T00:00:00Z')")
+        .execute(&app_state.db)
+        .await
+        .unwrap();
+    let app = create_app(app_state.clone());
+
+    // Running the rebuild again must not lose what the first one derived
+    for _ in 0..2 {
+        let (status, body) = send(&app, "POST", "/api/process-app-details").await;
+        assert_eq!(status, StatusCode::OK, "{}", body);
+
+        let (project, commit_date): (Option<String>, Option<String>) =
+            sqlx::query_as("SELECT project, commit_date FROM AppDetails")
+                .fetch_one(&app_state.db)
+                .await
+                .unwrap();
+        assert_eq!(project.as_deref(), Some("automatic1111/stable-diffusion-webui"));
+        assert_eq!(commit_date.as_deref(), Some("2023-03-02T10:00:00Z"));
+    }
+}
@@ -0,0 +1,132 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    routing::{get, post},
+    Router,
+};
+use serde_json::Value;
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}},
+    handlers::{admin::process_app_details, stats::its_by_app_version},
+    models::{performance_result::PerformanceResult, runs::Run},
+    repositories::{
+        app_release_repository::AppReleaseRepository,
+        performance_result_repository::PerformanceResultRepository,
+        runs_repository::RunsRepository,
+        traits::Repository,
+    },
+};
+
+/// process_app_details reads runs through the pool while holding a transaction,
+/// so the test database must be a file shared by several connections
+async fn create_test_app_state(name: &str) -> AppState {
+    let path = std::env::temp_dir().join(format!("sd_its_{}_{}.db", name, std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let db_config = DatabaseConfig {
+        url: format!("sqlite:{}?mode=rwc", path.display()),
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    AppState {
+        db: db_pool,
+        settings: Settings::default(),
+    }
+}
+
+fn create_app(app_state: AppState) -> Router {
+    Router::new()
+        .route("/api/process-app-details", post(process_app_details))
+        .route("/api/stats/its-by-app-version", get(its_by_app_version))
+        .with_state(app_state)
+}
+
+async fn send(app: &Router, method: &str, uri: &str) -> (StatusCode, Value) {
+    let request = Request::builder().method(method).uri(uri).body(Body::empty()).unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+/// Insert a run with the given info string and avg_its
+async fn create_run(app_state: &AppState, info: &str, avg_its: f64) -> i64 {
+    let run = RunsRepository::new(app_state.db.clone())
+        .create(Run {
+            id: None,
+            timestamp: Some("2024-01-01T00:00:00Z".to_string()),
+            vram_usage: None,
+            info: Some(info.to_string()),
+            system_info: None,
+            model_info: None,
+            device_info: None,
+            xformers: None,
+            model_name: None,
+            user: None,
+            notes: None,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap();
+
+    PerformanceResultRepository::new(app_state.db.clone())
+        .create(PerformanceResult { id: None, run_id: run.id, its: None, avg_its: Some(avg_its), vram_usage_kind: None, created_at: None, updated_at: None })
+        .await
+        .unwrap();
+
+    run.id.unwrap()
+}
+
+#[tokio::test]
+async fn test_process_app_details_derives_release_info() {
+    let app_state = create_test_app_state("derives_release_info").await;
+    let run_id = create_run(
+        &app_state,
+        "app:stable-diffusion-webui.git updated:2023-05-18 hash:89f9faa6 url:https://github.com/AUTOMATIC1111/stable-diffusion-webui.git/tree/dev",
+        12.0,
+    ).await;
+    let app = create_app(app_state.clone());
+
+    let (status, _) = send(&app, "POST", "/api/process-app-details").await;
+    assert_eq!(status, StatusCode::OK);
+
+    let releases = AppReleaseRepository::new(app_state.db.clone()).find_by_run_id(run_id).await.unwrap();
+    assert_eq!(releases.len(), 1);
+    assert_eq!(releases[0].release_channel, Some("dev".to_string()));
+    assert_eq!(releases[0].release_month, Some("2023-05".to_string()));
+}
+
+#[tokio::test]
+async fn test_its_by_app_version_groups_by_app_and_month() {
+    let app_state = create_test_app_state("its_by_app_version").await;
+    create_run(&app_state, "app:automatic updated:2023-05-01 hash:aaa url:https://github.com/vladmandic/automatic/tree/master", 10.0).await;
+    create_run(&app_state, "app:automatic updated:2023-05-20 hash:bbb url:https://github.com/vladmandic/automatic/tree/master", 14.0).await;
+    create_run(&app_state, "app:automatic updated:2023-06-02 hash:ccc url:https://github.com/vladmandic/automatic/tree/master", 20.0).await;
+    create_run(&app_state, "app:ComfyUI updated:2023-06-10 hash:ddd url:https://github.com/comfyanonymous/ComfyUI", 18.0).await;
+    let app = create_app(app_state);
+
+    send(&app, "POST", "/api/process-app-details").await;
+
+    let (status, stats) = send(&app, "GET", "/api/stats/its-by-app-version").await;
+    assert_eq!(status, StatusCode::OK);
+    let rows = stats["data"].as_array().unwrap();
+    assert_eq!(rows.len(), 3);
+
+    let may = rows.iter().find(|row| row["app_name"] == "automatic" && row["release_month"] == "2023-05").unwrap();
+    assert_eq!(may["run_count"], 2);
+    assert_eq!(may["avg_its"], 12.0);
+    assert_eq!(may["release_channel"], "master");
+
+    let (status, filtered) = send(&app, "GET", "/api/stats/its-by-app-version?app=ComfyUI").await;
+    assert_eq!(status, StatusCode::OK);
+    let rows = filtered["data"].as_array().unwrap();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0]["release_channel"], Value::Null);
+    assert_eq!(rows[0]["release_month"], "2023-06");
+}
@@ -0,0 +1,139 @@
+use std::time::Duration;
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    routing::get,
+    Router,
+};
+use serde_json::Value;
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}},
+    handlers::model_map,
+    models::{model_map::ModelMap, runs::Run},
+    repositories::{
+        model_map_repository::ModelMapRepository,
+        runs_repository::RunsRepository,
+        traits::Repository,
+    },
+};
+
+async fn create_test_app_state() -> AppState {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    AppState {
+        db: db_pool,
+        settings: Settings::default(),
+    }
+}
+
+fn test_run() -> Run {
+    Run {
+        id: None,
+        timestamp: Some("2024-01-01T00:00:00Z".to_string()),
+        vram_usage: None,
+        info: None,
+        system_info: None,
+        model_info: None,
+        device_info: None,
+        xformers: None,
+        model_name: None,
+        user: None,
+        notes: None,
+        created_at: None,
+        updated_at: None,
+    }
+}
+
+#[tokio::test]
+async fn test_create_and_update_stamp_audit_columns() {
+    let app_state = create_test_app_state().await;
+    let repository = RunsRepository::new(app_state.db.clone());
+
+    let created = repository.create(test_run()).await.unwrap();
+    assert!(created.created_at.is_some());
+    assert_eq!(created.created_at, created.updated_at);
+
+    let stored = repository.find_by_id(created.id.unwrap()).await.unwrap().unwrap();
+    assert_eq!(stored.created_at, created.created_at);
+    assert_eq!(stored.updated_at, created.updated_at);
+
+    tokio::time::sleep(Duration::from_millis(5)).await;
+    repository
+        .update(Run { notes: Some("rerun".to_string()), ..stored.clone() })
+        .await
+        .unwrap();
+
+    let updated = repository.find_by_id(created.id.unwrap()).await.unwrap().unwrap();
+    assert_eq!(updated.created_at, stored.created_at, "created_at must not change on update");
+    assert!(updated.updated_at > stored.updated_at, "updated_at must move forward on update");
+}
+
+#[tokio::test]
+async fn test_audit_columns_are_exposed_in_api_responses() {
+    let app_state = create_test_app_state().await;
+    let created = ModelMapRepository::new(app_state.db.clone())
+        .create(ModelMap {
+            id: None,
+            model_name: Some("v1-5-pruned-emaonly".to_string()),
+            base_model: Some("SD 1.5".to_string()),
+            version: 1,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap();
+
+    let app = Router::new()
+        .route("/api/model-map/{id}", get(model_map::get_model_map))
+        .with_state(app_state);
+    let request = Request::builder()
+        .uri(format!("/api/model-map/{}", created.id.unwrap()))
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body: Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(body["data"]["created_at"], created.created_at.unwrap().as_str());
+    assert!(body["data"]["updated_at"].is_string());
+}
+
+#[tokio::test]
+async fn test_initialize_database_adds_audit_columns_to_existing_tables() {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let pool = create_pool(&db_config).await.unwrap();
+
+    // A runs table from before the audit columns existed
+    sqlx::query("CREATE TABLE runs (id INTEGER PRIMARY KEY, timestamp TEXT, vram_usage TEXT, info TEXT, system_info TEXT, model_info TEXT, device_info TEXT, xformers TEXT, model_name TEXT, user TEXT, notes TEXT)")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("INSERT INTO runs (id, timestamp) VALUES (1, '2023-05-01T00:00:00Z')")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    initialize_database(&pool).await.unwrap();
+
+    let legacy = RunsRepository::new(pool.clone()).find_by_id(1).await.unwrap().unwrap();
+    assert_eq!(legacy.created_at, None);
+    assert_eq!(legacy.updated_at, None);
+
+    let created = RunsRepository::new(pool).create(test_run()).await.unwrap();
+    assert!(created.created_at.is_some());
+}
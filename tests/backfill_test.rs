@@ -0,0 +1,140 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    routing::post,
+    Router,
+};
+use serde_json::Value;
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}},
+    handlers::admin::{backfill, process_gpu},
+    models::runs::Run,
+    repositories::{gpu_repository::GpuRepository, runs_repository::RunsRepository, traits::Repository},
+};
+
+async fn create_test_app_state() -> AppState {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    AppState {
+        db: db_pool,
+        settings: Settings::default(),
+    }
+}
+
+fn create_app(app_state: AppState) -> Router {
+    Router::new()
+        .route("/api/process-gpu", post(process_gpu))
+        .route("/api/admin/backfill", post(backfill))
+        .with_state(app_state)
+}
+
+async fn post_uri(app: &Router, uri: &str) -> (StatusCode, Value) {
+    let request = Request::builder().method("POST").uri(uri).body(Body::empty()).unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+async fn create_run(app_state: &AppState, device_info: &str) -> i64 {
+    RunsRepository::new(app_state.db.clone())
+        .create(Run {
+            id: None,
+            timestamp: Some("2024-01-01T00:00:00Z".to_string()),
+            vram_usage: None,
+            info: None,
+            system_info: None,
+            model_info: None,
+            device_info: Some(device_info.to_string()),
+            xformers: None,
+            model_name: None,
+            user: Some("tester".to_string()),
+            notes: None,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap()
+        .id
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_backfill_gpu_reparses_and_reports_diffs() {
+    let app_state = create_test_app_state().await;
+    let changed_run = create_run(&app_state, "device:NVIDIA driver:470.82.01 NVIDIA GeForce RTX 3080").await;
+    create_run(&app_state, "device:NVIDIA GeForce RTX 3060 driver:531.41").await;
+    let app = create_app(app_state.clone());
+
+    // process-gpu still parses with the legacy parser
+    let (status, body) = post_uri(&app, "/api/process-gpu").await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+
+    let legacy = GpuRepository::new(app_state.db.clone()).find_by_run_id(changed_run).await.unwrap();
+    assert_eq!(legacy.len(), 1);
+    assert_eq!(legacy[0].parser_version, Some(1));
+    assert_eq!(legacy[0].device.as_deref(), Some("NVIDIA NVIDIA GeForce RTX 3080"));
+    assert_eq!(legacy[0].gpu_chip, None);
+
+    let (status, body) = post_uri(&app, "/api/admin/backfill?stage=gpu&parser_version=2").await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["stage"], "gpu");
+    assert_eq!(body["parser_version"], 2);
+    assert_eq!(body["runs_processed"], 2);
+    assert_eq!(body["rows_inserted"], 2);
+    assert_eq!(body["runs_changed"], 1);
+    let diff = &body["diffs"][0];
+    assert_eq!(diff["run_id"], changed_run);
+    assert_eq!(diff["before"][0]["device"], "NVIDIA NVIDIA GeForce RTX 3080");
+    assert_eq!(diff["after"][0]["device"], "NVIDIA");
+    assert_eq!(diff["after"][0]["gpu_chip"], "NVIDIA GeForce RTX 3080");
+
+    let gpus = GpuRepository::new(app_state.db.clone()).find_all().await.unwrap();
+    assert_eq!(gpus.len(), 2);
+    assert!(gpus.iter().all(|gpu| gpu.parser_version == Some(2)));
+
+    // Re-running the same version changes nothing
+    let (status, body) = post_uri(&app, "/api/admin/backfill?stage=gpu&parser_version=2").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["runs_changed"], 0);
+    assert_eq!(body["diffs"].as_array().unwrap().len(), 0);
+}
+
+#[tokio::test]
+async fn test_backfill_can_return_to_the_legacy_parser() {
+    let app_state = create_test_app_state().await;
+    create_run(&app_state, "device:NVIDIA GeForce RTX 3060 driver:531.41").await;
+    let app = create_app(app_state.clone());
+
+    let (status, _) = post_uri(&app, "/api/admin/backfill?stage=gpu&parser_version=2").await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, body) = post_uri(&app, "/api/admin/backfill?stage=gpu&parser_version=1").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["runs_changed"], 0);
+
+    let gpus = GpuRepository::new(app_state.db.clone()).find_all().await.unwrap();
+    assert_eq!(gpus.len(), 1);
+    assert_eq!(gpus[0].parser_version, Some(1));
+}
+
+#[tokio::test]
+async fn test_backfill_rejects_unknown_stage_or_version() {
+    let app = create_app(create_test_app_state().await);
+
+    for uri in [
+        "/api/admin/backfill?stage=libraries&parser_version=1",
+        "/api/admin/backfill?stage=gpu&parser_version=7",
+    ] {
+        let (status, _) = post_uri(&app, uri).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{}", uri);
+    }
+}
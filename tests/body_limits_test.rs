@@ -0,0 +1,103 @@
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    Router,
+};
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}, settings::BodyLimitsConfig},
+    error::i18n::ErrorCatalog,
+    router::create_router,
+};
+
+async fn create_app() -> Router {
+    create_app_with_settings(Settings::default()).await
+}
+
+async fn create_app_with_settings(settings: Settings) -> Router {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    let settings = Settings {
+        body_limits: BodyLimitsConfig {
+            upload_max_mb: 1,
+            json_max_kb: 1,
+        },
+        ..settings
+    };
+
+    create_router(AppState { db: db_pool, settings }, Arc::new(ErrorCatalog::bundled().unwrap()))
+}
+
+async fn send(app: &Router, method: &str, uri: &str, body: Vec<u8>) -> StatusCode {
+    send_as(app, method, uri, "application/octet-stream", body).await
+}
+
+async fn send_as(app: &Router, method: &str, uri: &str, content_type: &str, body: Vec<u8>) -> StatusCode {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("content-type", content_type)
+        .body(Body::from(body))
+        .unwrap();
+    app.clone().oneshot(request).await.unwrap().status()
+}
+
+#[tokio::test]
+async fn test_json_routes_reject_bodies_over_json_limit() {
+    let app = create_app().await;
+
+    let small_csv = b"model_name,base_model\nsd_xl_base_1.0,SDXL\n".to_vec();
+    assert_eq!(send(&app, "POST", "/api/model-map/import", small_csv).await, StatusCode::OK);
+
+    let large_csv = format!("model_name,base_model\n{}", "sd_xl_base_1.0,SDXL\n".repeat(100)).into_bytes();
+    assert!(large_csv.len() > 1024);
+    assert_eq!(send(&app, "POST", "/api/model-map/import", large_csv).await, StatusCode::PAYLOAD_TOO_LARGE);
+}
+
+#[tokio::test]
+async fn test_upload_routes_use_upload_limit() {
+    let app = create_app().await;
+
+    // Over the JSON limit but under the upload limit: reaches the handler, which
+    // rejects the unknown upload id rather than the body size
+    let part = vec![b'x'; 64 * 1024];
+    assert_eq!(send(&app, "PUT", "/api/uploads/missing/part/1", part).await, StatusCode::NOT_FOUND);
+
+    let oversized_part = vec![b'x'; 2 * 1024 * 1024];
+    assert_eq!(
+        send(&app, "PUT", "/api/uploads/missing/part/1", oversized_part).await,
+        StatusCode::PAYLOAD_TOO_LARGE
+    );
+}
+
+#[tokio::test]
+async fn test_request_logging_keeps_the_json_limit() {
+    let mut settings = Settings::default();
+    settings.logging.request_logging.enabled = true;
+    settings.logging.request_logging.max_body_bytes = 16;
+    let app = create_app_with_settings(settings).await;
+
+    let tags = format!(r#"{{"tags":[{}]}}"#, vec![r#""fast""#; 300].join(","));
+    assert!(tags.len() > 1024);
+    assert_eq!(
+        send_as(&app, "POST", "/api/runs/1/tags", "application/json", tags.into_bytes()).await,
+        StatusCode::PAYLOAD_TOO_LARGE
+    );
+
+    // Only the sample is held back: the rest of a body under the limit still reaches the handler
+    let tags = r#"{"tags":["fast","sdxl","rtx-4090"]}"#;
+    assert_eq!(
+        send_as(&app, "POST", "/api/runs/1/tags", "application/json", tags.as_bytes().to_vec()).await,
+        StatusCode::NOT_FOUND
+    );
+}
@@ -2,6 +2,7 @@ use sqlx::SqlitePool;
 use std::sync::Once;
 
 use sd_its_benchmark::{
+    config::database::initialize_database,
     models::{
         runs::Run,
         performance_result::PerformanceResult,
@@ -35,144 +36,8 @@ async fn setup_test_db() -> SqlitePool {
     // Create in-memory database for testing
     let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
     
-    // Create all tables
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS runs (
-            id INTEGER PRIMARY KEY,
-            timestamp TEXT,
-            vram_usage TEXT,
-            info TEXT,
-            system_info TEXT,
-            model_info TEXT,
-            device_info TEXT,
-            xformers TEXT,
-            model_name TEXT,
-            user TEXT,
-            notes TEXT
-        )
-        "#
-    )
-    .execute(&pool)
-    .await
-    .unwrap();
-
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS performanceResult (
-            id INTEGER PRIMARY KEY,
-            run_id INTEGER,
-            its TEXT,
-            avg_its REAL,
-            FOREIGN KEY (run_id) REFERENCES runs(id)
-        )
-        "#
-    )
-    .execute(&pool)
-    .await
-    .unwrap();
-
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS AppDetails (
-            id INTEGER PRIMARY KEY,
-            run_id INTEGER,
-            app_name TEXT,
-            updated TEXT,
-            hash TEXT,
-            url TEXT,
-            FOREIGN KEY (run_id) REFERENCES runs(id)
-        )
-        "#
-    )
-    .execute(&pool)
-    .await
-    .unwrap();
-
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS SystemInfo (
-            id INTEGER PRIMARY KEY,
-            run_id INTEGER,
-            arch TEXT,
-            cpu TEXT,
-            system TEXT,
-            release TEXT,
-            python TEXT,
-            FOREIGN KEY (run_id) REFERENCES runs(id)
-        )
-        "#
-    )
-    .execute(&pool)
-    .await
-    .unwrap();
-
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS Libraries (
-            id INTEGER PRIMARY KEY,
-            run_id INTEGER,
-            torch TEXT,
-            xformers TEXT,
-            xformers1 TEXT,
-            diffusers TEXT,
-            transformers TEXT,
-            FOREIGN KEY (run_id) REFERENCES runs(id)
-        )
-        "#
-    )
-    .execute(&pool)
-    .await
-    .unwrap();
-
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS GPU (
-            id INTEGER PRIMARY KEY,
-            run_id INTEGER,
-            device TEXT,
-            driver TEXT,
-            gpu_chip TEXT,
-            brand TEXT,
-            isLaptop BOOLEAN,
-            FOREIGN KEY (run_id) REFERENCES runs(id)
-        )
-        "#
-    )
-    .execute(&pool)
-    .await
-    .unwrap();
-
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS RunMoreDetails (
-            id INTEGER PRIMARY KEY,
-            run_id INTEGER,
-            timestamp TEXT,
-            model_name TEXT,
-            user TEXT,
-            notes TEXT,
-            ModelMapId INTEGER,
-            FOREIGN KEY (run_id) REFERENCES runs(id)
-        )
-        "#
-    )
-    .execute(&pool)
-    .await
-    .unwrap();
-
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS ModelMap (
-            id INTEGER PRIMARY KEY,
-            model_name TEXT,
-            base_model TEXT
-        )
-        "#
-    )
-    .execute(&pool)
-    .await
-    .unwrap();
+    // Create all tables, with the same indexes as the application database
+    initialize_database(&pool).await.unwrap();
 
     pool
 }
@@ -190,6 +55,8 @@ fn create_test_run(id: Option<i64>) -> Run {
         model_name: Some("test-model".to_string()),
         user: Some("test-user".to_string()),
         notes: Some("Test notes".to_string()),
+        created_at: None,
+        updated_at: None,
     }
 }
 
@@ -199,6 +66,9 @@ fn create_test_performance_result(run_id: i64) -> PerformanceResult {
         run_id: Some(run_id),
         its: Some("10.5/11.2/9.8".to_string()),
         avg_its: Some(10.5),
+        vram_usage_kind: None,
+        created_at: None,
+        updated_at: None,
     }
 }
 
@@ -210,6 +80,9 @@ fn create_test_app_details(run_id: i64) -> AppDetails {
         updated: Some("2024-01-01".to_string()),
         hash: Some("abc123".to_string()),
         url: Some("https://test.com".to_string()),
+        parser_version: None,
+        created_at: None,
+        updated_at: None,
     }
 }
 
@@ -222,6 +95,12 @@ fn create_test_system_info(run_id: i64) -> SystemInfo {
         system: Some("Linux".to_string()),
         release: Some("Ubuntu 22.04".to_string()),
         python: Some("3.9.0".to_string()),
+        ram_gb: None,
+        swap_gb: None,
+        os_bits: None,
+        parser_version: None,
+        created_at: None,
+        updated_at: None,
     }
 }
 
@@ -234,6 +113,9 @@ fn create_test_libraries(run_id: i64) -> Libraries {
         xformers1: Some("0.0.20".to_string()),
         diffusers: Some("0.18.0".to_string()),
         transformers: Some("4.30.0".to_string()),
+        parser_version: None,
+        created_at: None,
+        updated_at: None,
     }
 }
 
@@ -246,6 +128,12 @@ fn create_test_gpu(run_id: i64) -> Gpu {
         gpu_chip: Some("AD102".to_string()),
         brand: Some("nvidia".to_string()),
         is_laptop: Some(false),
+        is_integrated: None,
+        is_cloud: None,
+        gpu_index: 0,
+        parser_version: None,
+        created_at: None,
+        updated_at: None,
     }
 }
 
@@ -258,6 +146,8 @@ fn create_test_run_more_details(run_id: i64) -> RunMoreDetails {
         user: Some("test-user".to_string()),
         notes: Some("Test notes".to_string()),
         model_map_id: None,
+        created_at: None,
+        updated_at: None,
     }
 }
 
@@ -266,6 +156,9 @@ fn create_test_model_map() -> ModelMap {
         id: None,
         model_name: Some("test-model".to_string()),
         base_model: Some("stable-diffusion-v1-5".to_string()),
+        version: 1,
+        created_at: None,
+        updated_at: None,
     }
 }
 
@@ -445,12 +338,18 @@ async fn test_transaction_rollback_on_error() {
             run_id: Some(99999), // Invalid run_id
             its: Some("10.5".to_string()),
             avg_its: Some(10.5),
+            vram_usage_kind: None,
+            created_at: None,
+            updated_at: None,
         },
         PerformanceResult {
             id: None,
             run_id: Some(created_run.id.unwrap()),
             its: Some("11.2".to_string()),
             avg_its: Some(11.2),
+            vram_usage_kind: None,
+            created_at: None,
+            updated_at: None,
         },
     ];
 
@@ -480,6 +379,8 @@ async fn test_large_bulk_operations() {
             model_name: Some(format!("test-model-{}", i)),
             user: Some("test-user".to_string()),
             notes: Some(format!("Test notes {}", i)),
+            created_at: None,
+            updated_at: None,
         })
         .collect();
 
@@ -498,6 +399,12 @@ async fn test_large_bulk_operations() {
     let count = runs_repo.count().await.unwrap();
     assert_eq!(count, 100);
 
+    // The runs span two INSERT statements; every returned id names its own row
+    for (i, run) in created_runs.iter().enumerate() {
+        let stored = runs_repo.find_by_id(run.id.unwrap()).await.unwrap().unwrap();
+        assert_eq!(stored.model_name, Some(format!("test-model-{}", i)));
+    }
+
     // Test bulk update
     let mut updated_runs = created_runs.clone();
     for run in &mut updated_runs {
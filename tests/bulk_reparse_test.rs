@@ -0,0 +1,190 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    routing::post,
+    Router,
+};
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}},
+    handlers::reparse::reparse,
+    middleware::request_transaction::transaction_per_request,
+    models::{gpu::Gpu, runs::Run},
+    repositories::{gpu_repository::GpuRepository, runs_repository::RunsRepository, traits::Repository},
+};
+
+async fn create_test_app_state() -> AppState {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    AppState {
+        db: db_pool,
+        settings: Settings::default(),
+    }
+}
+
+async fn send(app_state: &AppState, uri: &str, body: Value) -> (StatusCode, Value) {
+    let app = Router::new()
+        .route("/api/admin/reparse", post(reparse))
+        .route_layer(axum::middleware::from_fn_with_state(app_state.clone(), transaction_per_request))
+        .with_state(app_state.clone());
+    let request = Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+/// Insert a run on `device_info` with a GPU row parsed as `parsed_device` by an older parser
+async fn create_run(app_state: &AppState, device_info: &str, parsed_device: &str) -> i64 {
+    let run_id = RunsRepository::new(app_state.db.clone())
+        .create(Run {
+            id: None,
+            timestamp: Some("2024-01-01T00:00:00Z".to_string()),
+            vram_usage: None,
+            info: None,
+            system_info: None,
+            model_info: None,
+            device_info: Some(device_info.to_string()),
+            xformers: None,
+            model_name: None,
+            user: Some("alice".to_string()),
+            notes: None,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap()
+        .id
+        .unwrap();
+    GpuRepository::new(app_state.db.clone())
+        .create(Gpu {
+            id: None,
+            run_id: Some(run_id),
+            device: Some(parsed_device.to_string()),
+            driver: None,
+            gpu_chip: None,
+            brand: None,
+            is_laptop: None,
+            is_integrated: None,
+            is_cloud: None,
+            gpu_index: 0,
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap();
+
+    run_id
+}
+
+async fn parsed_device(app_state: &AppState, run_id: i64) -> Option<String> {
+    let gpus = GpuRepository::new(app_state.db.clone()).find_by_run_ids(&[run_id]).await.unwrap();
+    gpus[&run_id][0].device.clone()
+}
+
+#[tokio::test]
+async fn test_reparse_rebuilds_only_the_matching_runs() {
+    let app_state = create_test_app_state().await;
+    let radeon = create_run(&app_state, "device:AMD Radeon(TM) RX 6800 XT driver:23.5.2", "AMD").await;
+    let nvidia = create_run(&app_state, "device:NVIDIA GeForce RTX 3080 driver:535.86", "NVIDIA").await;
+
+    let (status, body) = send(
+        &app_state,
+        "/api/admin/reparse",
+        json!({ "stage": "gpu", "where_like": "%Radeon(TM)%" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    let result = &body["data"];
+    assert_eq!(result["stage"], "gpu");
+    assert_eq!(result["dry_run"], false);
+    assert_eq!(result["matched_runs"], 1);
+    assert_eq!(result["rows_deleted"], 1);
+    assert_eq!(result["rows_inserted"], 1);
+    assert_eq!(result["skipped_runs"], 0);
+    assert_eq!(result["sample_run_ids"], json!([radeon]));
+
+    assert_eq!(parsed_device(&app_state, radeon).await.as_deref(), Some("AMD Radeon(TM) RX 6800 XT"));
+    // Not matched, so still as the older parser left it
+    assert_eq!(parsed_device(&app_state, nvidia).await.as_deref(), Some("NVIDIA"));
+}
+
+#[tokio::test]
+async fn test_reparse_by_regex() {
+    let app_state = create_test_app_state().await;
+    let rx_6800 = create_run(&app_state, "device:AMD Radeon(TM) RX 6800 XT driver:23.5.2", "AMD").await;
+    let vega = create_run(&app_state, "device:AMD Radeon(TM) Vega 8 Graphics driver:23.5.2", "AMD").await;
+
+    let (status, body) = send(
+        &app_state,
+        "/api/admin/reparse",
+        json!({ "stage": "gpu", "where_regex": r"Radeon\(TM\) RX \d{4}" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["data"]["where_regex"], r"Radeon\(TM\) RX \d{4}");
+    assert!(body["data"].get("where_like").is_none());
+    assert_eq!(body["data"]["sample_run_ids"], json!([rx_6800]));
+
+    assert_eq!(parsed_device(&app_state, rx_6800).await.as_deref(), Some("AMD Radeon(TM) RX 6800 XT"));
+    assert_eq!(parsed_device(&app_state, vega).await.as_deref(), Some("AMD"));
+}
+
+#[tokio::test]
+async fn test_dry_run_reparse_writes_nothing() {
+    let app_state = create_test_app_state().await;
+    let radeon = create_run(&app_state, "device:AMD Radeon(TM) RX 6800 XT driver:23.5.2", "AMD").await;
+
+    let (status, body) = send(
+        &app_state,
+        "/api/admin/reparse?dry_run=true",
+        json!({ "stage": "gpu", "where_like": "%Radeon(TM)%" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["data"]["dry_run"], true);
+    assert_eq!(body["data"]["rows_inserted"], 1);
+
+    assert_eq!(parsed_device(&app_state, radeon).await.as_deref(), Some("AMD"));
+}
+
+#[tokio::test]
+async fn test_reparse_rejects_patterns_matching_everything_or_nothing() {
+    let app_state = create_test_app_state().await;
+
+    for pattern in ["", "  ", "%", "%%"] {
+        let (status, _) = send(&app_state, "/api/admin/reparse", json!({ "stage": "gpu", "where_like": pattern })).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{:?}", pattern);
+    }
+
+    for pattern in ["", ".*", "x?", "(unclosed"] {
+        let (status, _) = send(&app_state, "/api/admin/reparse", json!({ "stage": "gpu", "where_regex": pattern })).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{:?}", pattern);
+    }
+
+    for body in [
+        json!({ "stage": "gpu" }),
+        json!({ "stage": "gpu", "where_like": "%x%", "where_regex": "x" }),
+    ] {
+        let (status, _) = send(&app_state, "/api/admin/reparse", body.clone()).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{}", body);
+    }
+
+    let (status, _) = send(&app_state, "/api/admin/reparse", json!({ "stage": "everything", "where_like": "%x%" })).await;
+    assert!(status.is_client_error());
+}
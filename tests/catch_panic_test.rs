@@ -0,0 +1,53 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    routing::get,
+    Router,
+};
+use serde_json::Value;
+use tower::ServiceExt;
+
+use sd_its_benchmark::middleware::catch_panic::{catch_panic_layer, install_panic_hook, REQUEST_ID_HEADER};
+
+async fn panicking_handler() -> &'static str {
+    panic!("row decoder hit an impossible state")
+}
+
+#[tokio::test]
+async fn test_panicking_handler_returns_structured_500() {
+    install_panic_hook();
+    let app = Router::new()
+        .route("/boom", get(panicking_handler))
+        .route("/ok", get(|| async { "OK" }))
+        .layer(catch_panic_layer());
+
+    let response = app
+        .clone()
+        .oneshot(Request::builder().uri("/boom").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    let request_id = response
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .unwrap()
+        .to_string();
+
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body: Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(body["error"]["code"], "INTERNAL_ERROR");
+    assert_eq!(body["error"]["status"], 500);
+    assert_eq!(body["error"]["request_id"], request_id.as_str());
+    assert!(
+        !body["error"]["message"].as_str().unwrap().contains("impossible"),
+        "panic details stay in the logs"
+    );
+
+    // The server keeps serving after a panic
+    let response = app
+        .oneshot(Request::builder().uri("/ok").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
@@ -0,0 +1,178 @@
+use axum::{
+    body::Body,
+    http::{Method, Request, StatusCode},
+    routing::{get, post},
+    Router,
+};
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}},
+    handlers::{
+        admin::fix_app_names,
+        change_sets::{list_change_sets, revert_change_set},
+    },
+    middleware::request_transaction::transaction_per_request,
+    models::{app_details::AppDetails, runs::Run},
+    repositories::{app_details_repository::AppDetailsRepository, runs_repository::RunsRepository, traits::Repository},
+};
+
+async fn create_test_app_state() -> AppState {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    AppState {
+        db: db_pool,
+        settings: Settings::default(),
+    }
+}
+
+fn create_app(app_state: AppState) -> Router {
+    Router::new()
+        .route("/api/fix-app-names", post(fix_app_names))
+        .route("/api/admin/change-sets", get(list_change_sets))
+        .route("/api/admin/change-sets/{id}/revert", post(revert_change_set))
+        .route_layer(axum::middleware::from_fn_with_state(app_state.clone(), transaction_per_request))
+        .with_state(app_state)
+}
+
+async fn send(app: &Router, method: Method, uri: &str, body: Option<Value>) -> (StatusCode, Value) {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("content-type", "application/json")
+        .body(body.map_or_else(Body::empty, |body| Body::from(body.to_string())))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+async fn create_app_details(app_state: &AppState, app_name: Option<&str>, url: Option<&str>) -> i64 {
+    let run = RunsRepository::new(app_state.db.clone())
+        .create(Run {
+            id: None,
+            timestamp: Some("2024-01-01T00:00:00Z".to_string()),
+            vram_usage: None,
+            info: None,
+            system_info: None,
+            model_info: None,
+            device_info: None,
+            xformers: None,
+            model_name: None,
+            user: Some("tester".to_string()),
+            notes: None,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap();
+
+    AppDetailsRepository::new(app_state.db.clone())
+        .create(AppDetails {
+            id: None,
+            run_id: run.id,
+            app_name: app_name.map(str::to_string),
+            updated: None,
+            hash: None,
+            url: url.map(str::to_string),
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap()
+        .id
+        .unwrap()
+}
+
+async fn app_name(app_state: &AppState, id: i64) -> Option<String> {
+    AppDetailsRepository::new(app_state.db.clone())
+        .find_by_id(id)
+        .await
+        .unwrap()
+        .unwrap()
+        .app_name
+}
+
+fn fix_request() -> Value {
+    json!({
+        "automatic1111": "AUTOMATIC1111",
+        "vladmandic": "Vladmandic",
+        "stable_diffusion": "StableDiffusion",
+        "null_app_name_null_url": "Unknown"
+    })
+}
+
+#[tokio::test]
+async fn test_revert_restores_app_names_replaced_by_fix() {
+    let app_state = create_test_app_state().await;
+    let renamed = create_app_details(&app_state, Some("a1111"), Some("https://github.com/AUTOMATIC1111/stable-diffusion-webui")).await;
+    let named = create_app_details(&app_state, None, Some("https://github.com/vladmandic/automatic")).await;
+    let edited = create_app_details(&app_state, None, None).await;
+    let untouched = create_app_details(&app_state, Some("existing-app"), Some("https://github.com/some-other/app")).await;
+    let app = create_app(app_state.clone());
+
+    let (status, body) = send(&app, Method::POST, "/api/fix-app-names", Some(fix_request())).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    let change_set_id = body["data"]["change_set_id"].as_i64().unwrap();
+    assert_eq!(app_name(&app_state, renamed).await.as_deref(), Some("AUTOMATIC1111"));
+    assert_eq!(app_name(&app_state, named).await.as_deref(), Some("Vladmandic"));
+
+    // A row changed after the fix keeps its newer value
+    sqlx::query("UPDATE AppDetails SET app_name = 'hand-fixed' WHERE id = ?")
+        .bind(edited)
+        .execute(&app_state.db)
+        .await
+        .unwrap();
+
+    let (status, body) = send(&app, Method::POST, &format!("/api/admin/change-sets/{}/revert", change_set_id), None).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["data"]["restored"], 2);
+    assert_eq!(body["data"]["skipped"], 1);
+
+    assert_eq!(app_name(&app_state, renamed).await.as_deref(), Some("a1111"));
+    assert_eq!(app_name(&app_state, named).await, None);
+    assert_eq!(app_name(&app_state, edited).await.as_deref(), Some("hand-fixed"));
+    assert_eq!(app_name(&app_state, untouched).await.as_deref(), Some("existing-app"));
+
+    let (status, body) = send(&app, Method::GET, "/api/admin/change-sets", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"][0]["id"], change_set_id);
+    assert_eq!(body["data"][0]["action"], "fix-app-names");
+    assert!(body["data"][0]["reverted_at"].is_string());
+
+    // A change set is only reverted once
+    let (status, _) = send(&app, Method::POST, &format!("/api/admin/change-sets/{}/revert", change_set_id), None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_dry_run_records_no_change_set() {
+    let app_state = create_test_app_state().await;
+    create_app_details(&app_state, None, None).await;
+    let app = create_app(app_state.clone());
+
+    let (status, body) = send(&app, Method::POST, "/api/fix-app-names?dry_run=true", Some(fix_request())).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["data"].get("change_set_id").is_none());
+
+    let (_, body) = send(&app, Method::GET, "/api/admin/change-sets", None).await;
+    assert_eq!(body["data"].as_array().unwrap().len(), 0);
+}
+
+#[tokio::test]
+async fn test_revert_unknown_change_set_is_not_found() {
+    let app = create_app(create_test_app_state().await);
+
+    let (status, _) = send(&app, Method::POST, "/api/admin/change-sets/42/revert", None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,164 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    routing::{get, post},
+    Router,
+};
+use serde_json::Value;
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}},
+    handlers::{admin::update_gpu_cloud_info, stats::leaderboard, tags::get_run_tags},
+    models::{gpu::Gpu, performance_result::PerformanceResult, runs::Run, system_info::SystemInfo},
+    repositories::{
+        gpu_repository::GpuRepository,
+        performance_result_repository::PerformanceResultRepository,
+        runs_repository::RunsRepository,
+        system_info_repository::SystemInfoRepository,
+        traits::Repository,
+    },
+};
+
+async fn create_test_app_state() -> AppState {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    AppState {
+        db: db_pool,
+        settings: Settings::default(),
+    }
+}
+
+fn create_app(app_state: AppState) -> Router {
+    Router::new()
+        .route("/api/update-gpu-cloud-info", post(update_gpu_cloud_info))
+        .route("/api/leaderboard", get(leaderboard))
+        .route("/api/runs/{id}/tags", get(get_run_tags))
+        .with_state(app_state)
+}
+
+async fn send(app: &Router, method: &str, uri: &str) -> (StatusCode, Value) {
+    let request = Request::builder().method(method).uri(uri).body(Body::empty()).unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+/// A run on one GPU with the given avg_its and kernel release
+async fn create_run(app_state: &AppState, device: &str, release: &str, avg_its: f64) -> i64 {
+    let run_id = RunsRepository::new(app_state.db.clone())
+        .create(Run {
+            id: None,
+            timestamp: Some("2024-01-01T00:00:00Z".to_string()),
+            vram_usage: None,
+            info: None,
+            system_info: None,
+            model_info: None,
+            device_info: None,
+            xformers: None,
+            model_name: None,
+            user: Some("tester".to_string()),
+            notes: None,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap()
+        .id;
+
+    GpuRepository::new(app_state.db.clone())
+        .create(Gpu {
+            id: None,
+            run_id,
+            device: Some(device.to_string()),
+            driver: None,
+            gpu_chip: None,
+            brand: None,
+            is_laptop: None,
+            is_integrated: None,
+            is_cloud: None,
+            gpu_index: 0,
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap();
+
+    SystemInfoRepository::new(app_state.db.clone())
+        .create(SystemInfo {
+            id: None,
+            run_id,
+            arch: Some("x86_64".to_string()),
+            cpu: None,
+            system: Some("Linux".to_string()),
+            release: Some(release.to_string()),
+            python: None,
+            ram_gb: None,
+            swap_gb: None,
+            os_bits: None,
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap();
+
+    PerformanceResultRepository::new(app_state.db.clone())
+        .create(PerformanceResult { id: None, run_id, its: None, avg_its: Some(avg_its), vram_usage_kind: None, created_at: None, updated_at: None })
+        .await
+        .unwrap();
+
+    run_id.unwrap()
+}
+
+#[tokio::test]
+async fn test_cloud_runs_are_tagged_and_can_be_excluded() {
+    let app_state = create_test_app_state().await;
+    let datacenter = create_run(&app_state, "Tesla T4", "5.15.0-91-generic", 6.0).await;
+    let cloud_kernel = create_run(&app_state, "NVIDIA GeForce RTX 3080", "5.15.0-1040-aws", 15.0).await;
+    let consumer = create_run(&app_state, "NVIDIA GeForce RTX 4090", "6.5.0-35-generic", 30.0).await;
+    let app = create_app(app_state.clone());
+
+    let (status, body) = send(&app, "POST", "/api/update-gpu-cloud-info").await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["total_rows"], 3);
+    assert_eq!(body["cloud_rows"], 2);
+    assert_eq!(body["datacenter_rows"], 1);
+    assert_eq!(body["auto_tags"], 2);
+
+    let gpus = GpuRepository::new(app_state.db.clone()).find_by_run_id(consumer).await.unwrap();
+    assert_eq!(gpus[0].is_cloud, Some(false));
+    let (_, body) = send(&app, "GET", &format!("/api/runs/{}/tags", cloud_kernel)).await;
+    assert_eq!(body["data"][0]["name"], "cloud");
+    assert_eq!(body["data"][0]["source"], "auto");
+
+    let (status, body) = send(&app, "GET", "/api/leaderboard?metric=avg_its&exclude_tag=cloud").await;
+    assert_eq!(status, StatusCode::OK);
+    let rows = body["data"].as_array().unwrap();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0]["run_id"], consumer);
+
+    let (status, body) = send(&app, "GET", "/api/leaderboard?metric=avg_its&tag=cloud").await;
+    assert_eq!(status, StatusCode::OK);
+    let rows = body["data"].as_array().unwrap();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0]["run_id"], cloud_kernel);
+    assert_eq!(rows[1]["run_id"], datacenter);
+}
+
+#[tokio::test]
+async fn test_leaderboard_rejects_invalid_exclude_tag() {
+    let app = create_app(create_test_app_state().await);
+
+    let (status, _) = send(&app, "GET", "/api/leaderboard?exclude_tag=not%20a%20tag").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
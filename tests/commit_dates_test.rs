@@ -0,0 +1,217 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use axum::{
+    body::Body,
+    extract::{Path, State},
+    http::{Request, StatusCode},
+    response::IntoResponse,
+    routing::{get, post},
+    Json, Router,
+};
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}},
+    handlers::admin::{canonicalize_app_urls, enrich_commit_dates},
+    models::{app_details::AppDetails, runs::Run},
+    repositories::{app_details_repository::AppDetailsRepository, runs_repository::RunsRepository, traits::Repository},
+};
+
+/// Answer commit lookups like the GitHub API, counting the requests
+///
+/// `abc1234` is known, repositories of `limited` answer 403 as over the rate
+/// limit, and everything else is unknown.
+async fn start_github(requests: Arc<AtomicUsize>) -> String {
+    let app = Router::new()
+        .route(
+            "/repos/{owner}/{repo}/commits/{hash}",
+            get(|State(requests): State<Arc<AtomicUsize>>, Path((owner, _repo, hash)): Path<(String, String, String)>| async move {
+                requests.fetch_add(1, Ordering::SeqCst);
+                match (owner.as_str(), hash.as_str()) {
+                    ("limited", _) => StatusCode::FORBIDDEN.into_response(),
+                    (_, "abc1234") => Json(json!({
+                        "sha": "abc1234",
+                        "commit": {
+                            "author": { "date": "2023-03-01T09:00:00Z" },
+                            "committer": { "date": "2023-03-02T10:00:00Z" }
+                        }
+                    }))
+                    .into_response(),
+                    _ => StatusCode::NOT_FOUND.into_response(),
+                }
+            }),
+        )
+        .with_state(requests);
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    url
+}
+
+async fn create_test_app_state(api_url: String) -> AppState {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    let mut settings = Settings::default();
+    settings.commit_dates.enabled = true;
+    settings.commit_dates.api_url = api_url;
+    settings.commit_dates.requests_per_minute = 60_000;
+
+    AppState { db: db_pool, settings }
+}
+
+fn create_app(app_state: AppState) -> Router {
+    Router::new()
+        .route("/api/canonicalize-app-urls", post(canonicalize_app_urls))
+        .route("/api/admin/enrich-commit-dates", post(enrich_commit_dates))
+        .with_state(app_state)
+}
+
+async fn send(app: &Router, uri: &str) -> (StatusCode, Value) {
+    let request = Request::builder().method("POST").uri(uri).body(Body::empty()).unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+/// Insert a run with app details pointing at `url` and `hash`, returning the app details id
+async fn create_app_details(app_state: &AppState, url: &str, hash: &str) -> i64 {
+    let run = RunsRepository::new(app_state.db.clone())
+        .create(Run {
+            id: None,
+            timestamp: Some("2024-01-01T00:00:00Z".to_string()),
+            vram_usage: None,
+            info: None,
+            system_info: None,
+            model_info: None,
+            device_info: None,
+            xformers: None,
+            model_name: None,
+            user: None,
+            notes: None,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap();
+
+    AppDetailsRepository::new(app_state.db.clone())
+        .create(AppDetails {
+            id: None,
+            run_id: run.id,
+            app_name: Some("stable-diffusion-webui".to_string()),
+            updated: Some("2023-03-02".to_string()),
+            hash: Some(hash.to_string()),
+            url: Some(url.to_string()),
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap()
+        .id
+        .unwrap()
+}
+
+async fn commit_date(app_state: &AppState, id: i64) -> Option<String> {
+    sqlx::query_scalar("SELECT commit_date FROM AppDetails WHERE id = ?")
+        .bind(id)
+        .fetch_one(&app_state.db)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_commit_dates_are_looked_up_once_and_restored_from_the_cache() {
+    let requests = Arc::new(AtomicUsize::new(0));
+    let app_state = create_test_app_state(start_github(requests.clone()).await).await;
+    let webui = "https://github.com/AUTOMATIC1111/stable-diffusion-webui.git";
+    let first = create_app_details(&app_state, webui, "ABC1234").await;
+    let second = create_app_details(&app_state, webui, "abc1234").await;
+    let unknown = create_app_details(&app_state, webui, "deadbee").await;
+    // Neither is looked up: not a hash, and not on GitHub
+    let not_a_hash = create_app_details(&app_state, webui, "master").await;
+    let gitlab = create_app_details(&app_state, "https://gitlab.com/someone/webui", "abc1234").await;
+    let app = create_app(app_state.clone());
+
+    let (status, body) = send(&app, "/api/canonicalize-app-urls").await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+
+    let (status, body) = send(&app, "/api/admin/enrich-commit-dates").await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["pending_commits"], 2);
+    assert_eq!(body["looked_up"], 2);
+    assert_eq!(body["resolved"], 1);
+    assert_eq!(body["not_found"], 1);
+    assert_eq!(body["remaining"], 0);
+    assert_eq!(body["rows_updated"], 2);
+    assert!(body["stopped"].is_null());
+    assert_eq!(requests.load(Ordering::SeqCst), 2);
+
+    // The committer date, on every row of the commit however its hash is spelled
+    assert_eq!(commit_date(&app_state, first).await.as_deref(), Some("2023-03-02T10:00:00Z"));
+    assert_eq!(commit_date(&app_state, second).await.as_deref(), Some("2023-03-02T10:00:00Z"));
+    assert_eq!(commit_date(&app_state, unknown).await, None);
+    assert_eq!(commit_date(&app_state, not_a_hash).await, None);
+    assert_eq!(commit_date(&app_state, gitlab).await, None);
+
+    // Cleared dates come back from the cache, with lookups disabled
+    sqlx::query("UPDATE AppDetails SET commit_date = NULL").execute(&app_state.db).await.unwrap();
+    let mut offline = app_state.clone();
+    offline.settings.commit_dates.enabled = false;
+    let (status, body) = send(&create_app(offline), "/api/admin/enrich-commit-dates").await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["lookups_enabled"], false);
+    assert_eq!(body["pending_commits"], 0);
+    assert_eq!(body["rows_updated"], 2);
+    assert_eq!(commit_date(&app_state, first).await.as_deref(), Some("2023-03-02T10:00:00Z"));
+
+    // Nothing is asked twice, not even the unknown commit
+    let (status, body) = send(&app, "/api/admin/enrich-commit-dates").await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["looked_up"], 0);
+    assert_eq!(body["rows_updated"], 0);
+    assert_eq!(requests.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_lookups_stop_when_github_refuses_and_keep_earlier_answers() {
+    let requests = Arc::new(AtomicUsize::new(0));
+    let mut app_state = create_test_app_state(start_github(requests.clone()).await).await;
+    app_state.settings.commit_dates.max_lookups = 1;
+    let resolved = create_app_details(&app_state, "https://github.com/vladmandic/automatic", "abc1234").await;
+    create_app_details(&app_state, "https://github.com/vladmandic/automatic", "abc1234").await;
+    create_app_details(&app_state, "https://github.com/limited/webui", "abc1234").await;
+    create_app_details(&app_state, "https://github.com/someone/webui", "abc1234").await;
+    let app = create_app(app_state.clone());
+    send(&app, "/api/canonicalize-app-urls").await;
+
+    // The commit used by the most runs goes first; max_lookups leaves the rest
+    let (status, body) = send(&app, "/api/admin/enrich-commit-dates").await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["pending_commits"], 3);
+    assert_eq!(body["looked_up"], 1);
+    assert_eq!(body["remaining"], 2);
+    assert_eq!(commit_date(&app_state, resolved).await.as_deref(), Some("2023-03-02T10:00:00Z"));
+
+    // `limited` sorts before `someone`, so the refusal ends the call before it
+    let (status, body) = send(&app, "/api/admin/enrich-commit-dates").await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["pending_commits"], 2);
+    assert_eq!(body["looked_up"], 0);
+    assert_eq!(body["remaining"], 2);
+    assert!(body["stopped"].as_str().unwrap().contains("403"));
+    assert_eq!(requests.load(Ordering::SeqCst), 2);
+}
@@ -0,0 +1,68 @@
+//! Fixtures shared by the integration tests; each test crate uses only some of them
+#![allow(dead_code)]
+
+use sd_its_benchmark::{
+    AppState,
+    models::{gpu::Gpu, performance_result::PerformanceResult, runs::Run},
+    repositories::{
+        gpu_repository::GpuRepository,
+        performance_result_repository::PerformanceResultRepository,
+        runs_repository::RunsRepository,
+        traits::Repository,
+    },
+};
+
+/// A run with a timestamp and nothing else, to fill in with struct update syntax
+pub fn test_run() -> Run {
+    Run {
+        id: None,
+        timestamp: Some("2024-01-01T00:00:00Z".to_string()),
+        vram_usage: None,
+        info: None,
+        system_info: None,
+        model_info: None,
+        device_info: None,
+        xformers: None,
+        model_name: None,
+        user: None,
+        notes: None,
+        created_at: None,
+        updated_at: None,
+    }
+}
+
+/// Insert a run on the given device with the given avg_its, returning the run id
+pub async fn create_run_on_device(app_state: &AppState, device: &str, avg_its: f64) -> i64 {
+    create_run_on_device_with(app_state, test_run(), device, avg_its).await
+}
+
+/// `create_run_on_device` for a run with more fields set than `test_run`
+pub async fn create_run_on_device_with(app_state: &AppState, run: Run, device: &str, avg_its: f64) -> i64 {
+    let run = RunsRepository::new(app_state.db.clone()).create(run).await.unwrap();
+
+    GpuRepository::new(app_state.db.clone())
+        .create(Gpu {
+            id: None,
+            run_id: run.id,
+            device: Some(device.to_string()),
+            driver: None,
+            gpu_chip: None,
+            brand: None,
+            is_laptop: None,
+            is_integrated: None,
+            is_cloud: None,
+            gpu_index: 0,
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap();
+
+    PerformanceResultRepository::new(app_state.db.clone())
+        .create(PerformanceResult { id: None, run_id: run.id, its: None, avg_its: Some(avg_its), vram_usage_kind: None, created_at: None, updated_at: None })
+        .await
+        .unwrap();
+
+    run.id.unwrap()
+}
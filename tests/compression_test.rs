@@ -0,0 +1,111 @@
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{header, Request, Response, StatusCode},
+    Router,
+};
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}, settings::CompressionConfig},
+    error::i18n::ErrorCatalog,
+    models::model_map::ModelMap,
+    repositories::{model_map_repository::ModelMapRepository, traits::Repository},
+    router::create_router,
+};
+
+async fn create_app(compression: CompressionConfig) -> Router {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+    ModelMapRepository::new(db_pool.clone())
+        .create(ModelMap {
+            id: None,
+            model_name: Some("v1-5-pruned-emaonly".to_string()),
+            base_model: Some("SD 1.5".to_string()),
+            version: 1,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap();
+
+    let settings = Settings {
+        compression,
+        ..Settings::default()
+    };
+
+    create_router(AppState { db: db_pool, settings }, Arc::new(ErrorCatalog::bundled().unwrap()))
+}
+
+/// Compress anything, so the one-row export qualifies
+fn compress_everything(encodings: &[&str]) -> CompressionConfig {
+    CompressionConfig {
+        enabled: true,
+        encodings: encodings.iter().map(|encoding| encoding.to_string()).collect(),
+        min_size_bytes: 0,
+    }
+}
+
+async fn export(app: &Router, accept_encoding: Option<&str>) -> Response<Body> {
+    let mut request = Request::get("/api/model-map/export");
+    if let Some(accept_encoding) = accept_encoding {
+        request = request.header(header::ACCEPT_ENCODING, accept_encoding);
+    }
+    app.clone().oneshot(request.body(Body::empty()).unwrap()).await.unwrap()
+}
+
+fn content_encoding(response: &Response<Body>) -> Option<&str> {
+    response.headers().get(header::CONTENT_ENCODING).and_then(|value| value.to_str().ok())
+}
+
+#[tokio::test]
+async fn test_export_honors_accept_encoding() {
+    let app = create_app(compress_everything(&["gzip", "br"])).await;
+
+    let response = export(&app, Some("gzip")).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(content_encoding(&response), Some("gzip"));
+
+    let response = export(&app, Some("br")).await;
+    assert_eq!(content_encoding(&response), Some("br"));
+
+    let response = export(&app, None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(content_encoding(&response), None);
+}
+
+#[tokio::test]
+async fn test_only_configured_encodings_are_used() {
+    let app = create_app(compress_everything(&["gzip"])).await;
+
+    assert_eq!(content_encoding(&export(&app, Some("br")).await), None);
+    assert_eq!(content_encoding(&export(&app, Some("br, gzip")).await), Some("gzip"));
+}
+
+#[tokio::test]
+async fn test_compression_can_be_disabled() {
+    let app = create_app(CompressionConfig {
+        enabled: false,
+        ..compress_everything(&["gzip", "br"])
+    })
+    .await;
+
+    assert_eq!(content_encoding(&export(&app, Some("gzip")).await), None);
+}
+
+#[tokio::test]
+async fn test_small_responses_are_not_compressed() {
+    let app = create_app(CompressionConfig::default()).await;
+
+    // The one-row export is far below the default 1 KiB minimum
+    let response = export(&app, Some("gzip")).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(content_encoding(&response), None);
+}
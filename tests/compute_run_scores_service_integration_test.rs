@@ -0,0 +1,187 @@
+use sqlx::SqlitePool;
+
+use sd_its_benchmark::{
+    config::settings::ScoringConfig,
+    models::{
+        gpu::Gpu, model_map::ModelMap, performance_result::PerformanceResult,
+        run_more_details::RunMoreDetails, runs::Run,
+    },
+    repositories::{
+        gpu_repository::GpuRepository,
+        model_map_repository::ModelMapRepository,
+        performance_result_repository::PerformanceResultRepository,
+        run_more_details_repository::RunMoreDetailsRepository,
+        run_score_repository::RunScoreRepository,
+        runs_repository::RunsRepository,
+        traits::Repository,
+    },
+    services::data_processing::compute_run_scores_service::ComputeRunScoresService,
+};
+
+async fn setup_test_database() -> SqlitePool {
+    let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+
+    sqlx::migrate!("./migrations")
+        .run(&pool)
+        .await
+        .expect("Failed to run migrations");
+
+    pool
+}
+
+/// Insert a run with its derived rows and return the run id
+async fn insert_scored_run(
+    pool: &SqlitePool,
+    avg_its: Option<f64>,
+    device: &str,
+    model_map_id: Option<i64>,
+) -> i64 {
+    let run = RunsRepository::new(pool.clone())
+        .create(Run {
+            id: None,
+            timestamp: Some("2024-01-01T00:00:00Z".to_string()),
+            vram_usage: Some("1.0/2.0".to_string()),
+            info: None,
+            system_info: None,
+            model_info: None,
+            device_info: None,
+            xformers: None,
+            model_name: Some("test-model".to_string()),
+            user: Some("test-user".to_string()),
+            notes: None,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap();
+    let run_id = run.id.unwrap();
+
+    PerformanceResultRepository::new(pool.clone())
+        .create(PerformanceResult {
+            id: None,
+            run_id: Some(run_id),
+            its: Some("1.0/2.0".to_string()),
+            avg_its,
+            vram_usage_kind: None,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap();
+
+    GpuRepository::new(pool.clone())
+        .create(Gpu {
+            id: None,
+            run_id: Some(run_id),
+            device: Some(device.to_string()),
+            driver: None,
+            gpu_chip: None,
+            brand: None,
+            is_laptop: None,
+            is_integrated: None,
+            is_cloud: None,
+            gpu_index: 0,
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap();
+
+    RunMoreDetailsRepository::new(pool.clone())
+        .create(RunMoreDetails {
+            id: None,
+            run_id: Some(run_id),
+            timestamp: None,
+            model_name: Some("test-model".to_string()),
+            user: None,
+            notes: None,
+            model_map_id,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap();
+
+    run_id
+}
+
+#[tokio::test]
+async fn test_compute_run_scores_service_integration() {
+    let pool = setup_test_database().await;
+
+    let sdxl = ModelMapRepository::new(pool.clone())
+        .create(ModelMap {
+            id: None,
+            model_name: Some("sd_xl_base_1.0".to_string()),
+            base_model: Some("SDXL".to_string()),
+            version: 1,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap();
+
+    let fast_sd15 = insert_scored_run(&pool, Some(20.0), "NVIDIA GeForce RTX 4090 24GB", None).await;
+    let slow_sdxl = insert_scored_run(&pool, Some(6.0), "NVIDIA GeForce RTX 3060 12GB", sdxl.id).await;
+    let _unparsed = insert_scored_run(&pool, None, "NVIDIA GeForce RTX 3060", None).await;
+
+    let service = ComputeRunScoresService::new(
+        RunScoreRepository::new(pool.clone()),
+        pool.clone(),
+        ScoringConfig::default(),
+    );
+    let result = service.compute_scores().await.unwrap();
+
+    assert!(result.success);
+    assert_eq!(result.total_runs, 3);
+    assert_eq!(result.scored_rows, 2);
+    assert_eq!(result.skipped_rows, 1);
+
+    let repository = RunScoreRepository::new(pool.clone());
+    let sdxl_score = repository.find_by_run_id(slow_sdxl).await.unwrap();
+    assert_eq!(sdxl_score.len(), 1);
+    assert_eq!(sdxl_score[0].model_factor, Some(2.5));
+    assert_eq!(sdxl_score[0].vram_gb, Some(12.0));
+
+    let leaderboard = repository.find_leaderboard("default", "score", None, None, false, 10).await.unwrap();
+    assert_eq!(leaderboard.len(), 2);
+    assert!(leaderboard[0].score >= leaderboard[1].score);
+
+    let by_its = repository.find_leaderboard("default", "avg_its", None, None, false, 10).await.unwrap();
+    assert_eq!(by_its[0].run_id, fast_sd15);
+    assert_eq!(by_its[0].avg_its, Some(20.0));
+}
+
+#[tokio::test]
+async fn test_compute_run_scores_replaces_previous_scores() {
+    let pool = setup_test_database().await;
+    insert_scored_run(&pool, Some(10.0), "NVIDIA GeForce RTX 3080 10GB", None).await;
+
+    let service = ComputeRunScoresService::new(
+        RunScoreRepository::new(pool.clone()),
+        pool.clone(),
+        ScoringConfig::default(),
+    );
+    service.compute_scores().await.unwrap();
+    service.compute_scores().await.unwrap();
+
+    let count = RunScoreRepository::new(pool.clone()).count().await.unwrap();
+    assert_eq!(count, 1, "Recomputing should not duplicate scores");
+}
+
+#[tokio::test]
+async fn test_compute_run_scores_empty_database() {
+    let pool = setup_test_database().await;
+
+    let service = ComputeRunScoresService::new(
+        RunScoreRepository::new(pool.clone()),
+        pool.clone(),
+        ScoringConfig::default(),
+    );
+    let result = service.compute_scores().await.unwrap();
+
+    assert!(result.success);
+    assert_eq!(result.total_runs, 0);
+    assert_eq!(result.scored_rows, 0);
+}
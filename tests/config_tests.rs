@@ -19,11 +19,31 @@ fn test_validate_config_invalid_port() {
     assert!(errors.iter().any(|e| e.contains("port")));
 }
 
+#[test]
+fn test_default_secrets_are_rejected_outside_development_and_demo() {
+    for environment in [Environment::Staging, Environment::Production] {
+        let mut settings = Settings::default();
+        settings.application.environment = environment;
+        let errors = validate_config(&settings).unwrap_err();
+        assert!(errors.iter().any(|e| e.contains("Sharing secret must be changed")), "{:?}", errors);
+        assert!(errors.iter().any(|e| e.contains("Anonymization salt must be changed")), "{:?}", errors);
+
+        settings.sharing.secret = "staging-share-secret-from-env".to_string();
+        settings.anonymization.salt = "staging-anonymization-salt-from-env".to_string();
+        assert!(validate_config(&settings).is_ok());
+    }
+
+    let mut settings = Settings::default();
+    settings.application.environment = Environment::Demo;
+    assert!(validate_config(&settings).is_ok());
+}
+
 #[test]
 fn test_get_database_url() {
     assert_eq!(get_database_url("development"), "sqlite:./dev-database.db");
     assert_eq!(get_database_url("staging"), "sqlite:./staging-database.db");
     assert_eq!(get_database_url("production"), "sqlite:./production-database.db");
+    assert_eq!(get_database_url("demo"), "sqlite::memory:");
     assert_eq!(get_database_url("unknown"), "sqlite:./my-database.db");
 }
 
@@ -44,6 +64,16 @@ fn test_get_config_summary() {
     assert!(summary.contains("Server: 127.0.0.1:4022")); // Updated to match current default port
 }
 
+#[test]
+fn test_demo_profile_runs_in_memory() {
+    let settings = Settings::for_environment("demo").unwrap();
+    assert!(settings.is_demo());
+    assert_eq!(settings.database.url, "sqlite::memory:");
+    assert!(settings.features.enable_export);
+    assert!(settings.demo.sample_rows > 0);
+    assert!(validate_config(&settings).is_ok());
+}
+
 #[test]
 fn test_environment_display() {
     assert_eq!(Environment::Development.to_string(), "development");
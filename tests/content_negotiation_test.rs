@@ -0,0 +1,106 @@
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    routing::get,
+    Router,
+};
+use serde_json::Value;
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}},
+    handlers::gpu_prices::list_gpu_prices,
+    middleware::content_negotiation::negotiate_content_type,
+    models::{gpu_base::GpuBase, gpu_price::GpuPrice},
+    repositories::{gpu_base_repository::GpuBaseRepository, gpu_price_repository::GpuPriceRepository, traits::Repository},
+};
+
+async fn create_app() -> Router {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    let base_gpu = GpuBaseRepository::new(db_pool.clone())
+        .create(GpuBase { id: None, name: "RTX 4090".to_string(), brand: Some("nvidia".to_string()), architecture: None, generation: None, version: 1, created_at: None, updated_at: None })
+        .await
+        .unwrap();
+    GpuPriceRepository::new(db_pool.clone())
+        .create(GpuPrice {
+            id: None,
+            base_gpu_id: base_gpu.id,
+            price_usd: Some(1599.0),
+            source: Some("MSRP".to_string()),
+            recorded_at: None,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap();
+
+    let app_state = AppState {
+        db: db_pool,
+        settings: Settings::default(),
+    };
+
+    Router::new()
+        .route("/api/gpu-prices", get(list_gpu_prices))
+        .route_layer(axum::middleware::from_fn(negotiate_content_type))
+        .with_state(app_state)
+}
+
+async fn get_with_accept(app: Router, accept: Option<&str>) -> (StatusCode, String, Vec<u8>) {
+    let mut builder = Request::builder().uri("/api/gpu-prices");
+    if let Some(accept) = accept {
+        builder = builder.header(header::ACCEPT, accept);
+    }
+
+    let response = app.oneshot(builder.body(Body::empty()).unwrap()).await.unwrap();
+    let status = response.status();
+    let content_type = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, content_type, body.to_vec())
+}
+
+#[tokio::test]
+async fn test_default_is_json() {
+    let (status, content_type, body) = get_with_accept(create_app().await, None).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert!(content_type.starts_with("application/json"));
+    let json: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["data"][0]["price_usd"], 1599.0);
+}
+
+#[tokio::test]
+async fn test_csv_response() {
+    let (status, content_type, body) = get_with_accept(create_app().await, Some("text/csv")).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert!(content_type.starts_with("text/csv"));
+    let csv = String::from_utf8(body).unwrap();
+    let mut lines = csv.lines();
+    assert_eq!(lines.next(), Some("base_gpu_id,created_at,id,price_usd,recorded_at,source,updated_at"));
+    let row: Vec<&str> = lines.next().unwrap().split(',').collect();
+    assert_eq!(row[0], "1");
+    assert_eq!(&row[2..6], ["1", "1599.0", "", "MSRP"]);
+    assert!(!row[1].is_empty() && row[1] == row[6], "audit timestamps should be set on create");
+}
+
+#[tokio::test]
+async fn test_msgpack_response() {
+    let (status, content_type, body) = get_with_accept(create_app().await, Some("application/msgpack")).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content_type, "application/msgpack");
+    let decoded: Value = rmp_serde::from_slice(&body).unwrap();
+    assert_eq!(decoded["data"][0]["source"], "MSRP");
+}
@@ -0,0 +1,171 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    routing::{get, post},
+    Router,
+};
+use serde_json::Value;
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}},
+    handlers::{admin::normalize_cpu_info, stats::cpu_impact},
+    models::{gpu::Gpu, performance_result::PerformanceResult, runs::Run, system_info::SystemInfo},
+    repositories::{
+        gpu_repository::GpuRepository,
+        performance_result_repository::PerformanceResultRepository,
+        runs_repository::RunsRepository,
+        system_info_repository::SystemInfoRepository,
+        traits::Repository,
+    },
+};
+
+async fn create_test_app_state() -> AppState {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    AppState {
+        db: db_pool,
+        settings: Settings::default(),
+    }
+}
+
+fn create_app(app_state: AppState) -> Router {
+    Router::new()
+        .route("/api/normalize-cpu-info", post(normalize_cpu_info))
+        .route("/api/stats/cpu-impact", get(cpu_impact))
+        .with_state(app_state)
+}
+
+async fn send(app: &Router, method: &str, uri: &str) -> (StatusCode, Value) {
+    let request = Request::builder().method(method).uri(uri).body(Body::empty()).unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+/// Insert a run on the given GPUs with a CPU string and avg_its
+async fn create_run(app_state: &AppState, devices: &[&str], cpu: &str, avg_its: f64) {
+    let run_id = RunsRepository::new(app_state.db.clone())
+        .create(Run {
+            id: None,
+            timestamp: Some("2024-01-01T00:00:00Z".to_string()),
+            vram_usage: None,
+            info: None,
+            system_info: None,
+            model_info: None,
+            device_info: None,
+            xformers: None,
+            model_name: None,
+            user: None,
+            notes: None,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap()
+        .id;
+
+    SystemInfoRepository::new(app_state.db.clone())
+        .create(SystemInfo {
+            id: None,
+            run_id,
+            arch: None,
+            cpu: Some(cpu.to_string()),
+            system: None,
+            release: None,
+            python: None,
+            ram_gb: None,
+            swap_gb: None,
+            os_bits: None,
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap();
+
+    for (gpu_index, device) in devices.iter().enumerate() {
+        GpuRepository::new(app_state.db.clone())
+            .create(Gpu {
+                id: None,
+                run_id,
+                device: Some(device.to_string()),
+                driver: None,
+                gpu_chip: None,
+                brand: None,
+                is_laptop: None,
+                is_integrated: None,
+                is_cloud: None,
+                gpu_index: gpu_index as i64,
+                parser_version: None,
+                created_at: None,
+                updated_at: None,
+            })
+            .await
+            .unwrap();
+    }
+
+    PerformanceResultRepository::new(app_state.db.clone())
+        .create(PerformanceResult { id: None, run_id, its: None, avg_its: Some(avg_its), vram_usage_kind: None, created_at: None, updated_at: None })
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_cpu_impact_after_normalization() {
+    let app_state = create_test_app_state().await;
+    let gpu = "NVIDIA GeForce RTX 3080";
+    for avg_its in [20.0, 20.5, 19.5] {
+        create_run(&app_state, &[gpu], "Intel(R) Core(TM) i9-13900K", avg_its).await;
+    }
+    for avg_its in [15.0, 15.5, 14.5] {
+        create_run(&app_state, &[gpu], "Intel(R) Core(TM) i5-8400 CPU @ 2.80GHz", avg_its).await;
+    }
+    create_run(&app_state, &[gpu], "x86_64", 10.0).await;
+    // Multi-GPU runs are left out of the comparison
+    create_run(&app_state, &[gpu, gpu], "AMD Ryzen 9 5950X 16-Core Processor", 40.0).await;
+    let app = create_app(app_state);
+
+    let (status, body) = send(&app, "POST", "/api/normalize-cpu-info").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total_rows"], 8);
+    assert_eq!(body["normalized_rows"], 7);
+
+    let (status, body) = send(&app, "GET", "/api/stats/cpu-impact?gpu=nvidia%20geforce%20rtx%203080").await;
+    assert_eq!(status, StatusCode::OK);
+    let impact = &body["data"];
+    assert_eq!(impact["run_count"], 7);
+    assert_eq!(impact["significant"], true);
+
+    let groups = impact["groups"].as_array().unwrap();
+    assert_eq!(groups.len(), 3);
+    assert_eq!(groups[0]["cpu_vendor"], "Intel");
+    assert_eq!(groups[0]["cpu_family"], "i9");
+    assert_eq!(groups[0]["cpu_generation"], 13);
+    assert_eq!(groups[1]["cpu_family"], "i5");
+    assert_eq!(groups[1]["cpu_generation"], 8);
+    assert_eq!(groups[2]["cpu_vendor"], Value::Null);
+}
+
+#[tokio::test]
+async fn test_cpu_impact_rejects_missing_or_unknown_gpu() {
+    let app_state = create_test_app_state().await;
+    let app = create_app(app_state);
+
+    let (status, _) = send(&app, "GET", "/api/stats/cpu-impact").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = send(&app, "GET", "/api/stats/cpu-impact?gpu=%20").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = send(&app, "GET", "/api/stats/cpu-impact?gpu=RTX%209999").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,98 @@
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    Router,
+};
+use tempfile::TempDir;
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}},
+    error::i18n::ErrorCatalog,
+    router::create_router,
+};
+
+const INDEX_HTML: &str = "<!doctype html><title>Benchmark dashboard</title>";
+
+/// A built SPA with an index page and one hashed asset
+fn create_dist_dir() -> TempDir {
+    let dist_dir = TempDir::new().unwrap();
+    std::fs::write(dist_dir.path().join("index.html"), INDEX_HTML).unwrap();
+    std::fs::create_dir(dist_dir.path().join("assets")).unwrap();
+    std::fs::write(dist_dir.path().join("assets").join("app.js"), "console.log('dashboard');").unwrap();
+    dist_dir
+}
+
+async fn create_app(dist_dir: Option<&TempDir>) -> Router {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    let mut settings = Settings::default();
+    settings.application.dashboard_dir = dist_dir.map(|dir| dir.path().to_path_buf());
+
+    let app_state = AppState { db: db_pool, settings };
+    create_router(app_state, Arc::new(ErrorCatalog::bundled().unwrap()))
+}
+
+async fn get(app: &Router, uri: &str) -> (StatusCode, Option<String>, String) {
+    let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let content_type = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .map(|value| value.to_str().unwrap().to_string());
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, content_type, String::from_utf8_lossy(&bytes).into_owned())
+}
+
+#[tokio::test]
+async fn test_dashboard_serves_files_and_falls_back_to_index() {
+    let dist_dir = create_dist_dir();
+    let app = create_app(Some(&dist_dir)).await;
+
+    let (status, content_type, body) = get(&app, "/").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(content_type.unwrap().starts_with("text/html"));
+    assert_eq!(body, INDEX_HTML);
+
+    let (status, _, body) = get(&app, "/assets/app.js").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, "console.log('dashboard');");
+
+    // Client-side routes get the SPA
+    let (status, _, body) = get(&app, "/leaderboard/rtx-4090").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, INDEX_HTML);
+}
+
+#[tokio::test]
+async fn test_dashboard_leaves_api_routes_alone() {
+    let dist_dir = create_dist_dir();
+    let app = create_app(Some(&dist_dir)).await;
+
+    let (status, content_type, _) = get(&app, "/api/leaderboard").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(content_type.unwrap().starts_with("application/json"));
+
+    let (status, content_type, body) = get(&app, "/api/no-such-endpoint").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert!(content_type.unwrap().starts_with("application/json"));
+    assert_ne!(body, INDEX_HTML);
+}
+
+#[tokio::test]
+async fn test_dashboard_not_served_unless_configured() {
+    let app = create_app(None).await;
+
+    let (status, _, _) = get(&app, "/").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,193 @@
+use std::{str::FromStr, time::Duration};
+
+use axum::{
+    http::{header, StatusCode},
+    response::IntoResponse,
+};
+use serde_json::Value;
+use sqlx::{
+    sqlite::{SqliteConnectOptions, SqlitePoolOptions},
+    SqlitePool,
+};
+
+use sd_its_benchmark::{
+    config::database::{DatabaseConfig, create_pool, initialize_database},
+    error::{handlers::{handle_anyhow_error, reference_error}, AppError, DatabaseErrorKind},
+    models::{gpu::Gpu, gpu_base::GpuBase},
+    repositories::{traits::Repository, GpuBaseRepository, GpuRepository, TagRepository},
+};
+
+fn gpu_base(name: &str) -> GpuBase {
+    GpuBase {
+        id: None,
+        name: name.to_string(),
+        brand: Some("NVIDIA".to_string()),
+        architecture: None,
+        generation: None,
+        version: 1,
+        created_at: None,
+        updated_at: None,
+    }
+}
+
+/// A file-backed pool that fails immediately instead of waiting on SQLite's busy timeout
+async fn create_file_pool(path: &std::path::Path) -> SqlitePool {
+    let options = SqliteConnectOptions::from_str(&format!("sqlite:{}", path.display()))
+        .unwrap()
+        .create_if_missing(true)
+        .busy_timeout(Duration::ZERO);
+    SqlitePoolOptions::new().max_connections(1).connect_with(options).await.unwrap()
+}
+
+async fn error_body(error: AppError) -> (StatusCode, Option<String>, Value) {
+    let response = error.into_response();
+    let status = response.status();
+    let retry_after = response
+        .headers()
+        .get(header::RETRY_AFTER)
+        .map(|value| value.to_str().unwrap().to_string());
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, retry_after, serde_json::from_slice(&bytes).unwrap())
+}
+
+#[tokio::test]
+async fn test_unique_violation_maps_to_conflict() {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let pool = create_pool(&db_config).await.unwrap();
+    initialize_database(&pool).await.unwrap();
+
+    let repository = GpuBaseRepository::new(pool);
+    repository.create(gpu_base("RTX 4090")).await.unwrap();
+    let err = repository.create(gpu_base("RTX 4090")).await.unwrap_err();
+    assert_eq!(DatabaseErrorKind::of(&err), DatabaseErrorKind::ConstraintViolation);
+
+    let (status, retry_after, body) = error_body(AppError::Database(err)).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["error"]["code"], "CONSTRAINT_VIOLATION");
+    assert!(retry_after.is_none());
+}
+
+#[tokio::test]
+async fn test_foreign_key_violation_maps_to_missing_reference() {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let pool = create_pool(&db_config).await.unwrap();
+    initialize_database(&pool).await.unwrap();
+
+    let err = GpuRepository::new(pool)
+        .create(Gpu {
+            id: None,
+            run_id: Some(404),
+            device: Some("NVIDIA GeForce RTX 4090".to_string()),
+            driver: None,
+            gpu_chip: None,
+            brand: None,
+            is_laptop: None,
+            is_integrated: None,
+            is_cloud: None,
+            gpu_index: 0,
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(DatabaseErrorKind::of(&err), DatabaseErrorKind::MissingReference);
+
+    let (status, _, body) = error_body(AppError::Database(err)).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["error"]["code"], "MISSING_REFERENCE");
+
+    let (status, _, body) = error_body(AppError::missing_reference("run_id", "run not found")).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["error"]["field"], "run_id");
+    assert_eq!(body["error"]["reason"], "run not found");
+}
+
+#[tokio::test]
+async fn test_child_write_for_a_missing_run_names_run_id() {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let pool = create_pool(&db_config).await.unwrap();
+    initialize_database(&pool).await.unwrap();
+
+    let err = TagRepository::new(pool)
+        .add_to_run(9999, &["fast".to_string()])
+        .await
+        .unwrap_err();
+
+    let (status, _, body) = error_body(reference_error(err, "run_id", "run not found")).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["error"]["code"], "MISSING_REFERENCE");
+    assert_eq!(body["error"]["field"], "run_id");
+    assert_eq!(body["error"]["reason"], "run not found");
+
+    let (status, _, body) = error_body(reference_error(sqlx::Error::RowNotFound, "run_id", "run not found")).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["error"]["code"], "NOT_FOUND");
+}
+
+#[tokio::test]
+async fn test_row_not_found_and_anyhow_wrapped_errors_keep_their_status() {
+    let (status, _, body) = error_body(AppError::Database(sqlx::Error::RowNotFound)).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["error"]["code"], "NOT_FOUND");
+
+    let wrapped = handle_anyhow_error(anyhow::Error::new(sqlx::Error::PoolTimedOut), "test");
+    assert_eq!(wrapped.status_code(), StatusCode::SERVICE_UNAVAILABLE);
+
+    let other = handle_anyhow_error(anyhow::anyhow!("parser gave up"), "test");
+    assert_eq!(other.status_code(), StatusCode::INTERNAL_SERVER_ERROR);
+}
+
+#[tokio::test]
+async fn test_locked_database_maps_to_503_with_retry_after() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("busy.db");
+    let writer = create_file_pool(&path).await;
+    initialize_database(&writer).await.unwrap();
+    let reader = create_file_pool(&path).await;
+
+    let mut lock = writer.acquire().await.unwrap();
+    sqlx::query("BEGIN IMMEDIATE").execute(&mut *lock).await.unwrap();
+
+    let err = GpuBaseRepository::new(reader).create(gpu_base("RTX 3060")).await.unwrap_err();
+    assert_eq!(DatabaseErrorKind::of(&err), DatabaseErrorKind::Busy);
+
+    let (status, retry_after, body) = error_body(AppError::Database(err)).await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(body["error"]["code"], "DATABASE_BUSY");
+    assert_eq!(retry_after.as_deref(), Some("1"));
+
+    sqlx::query("ROLLBACK").execute(&mut *lock).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_repository_write_retries_until_lock_is_released() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("retry.db");
+    let writer = create_file_pool(&path).await;
+    initialize_database(&writer).await.unwrap();
+    let reader = create_file_pool(&path).await;
+
+    let mut lock = writer.acquire().await.unwrap();
+    sqlx::query("BEGIN IMMEDIATE").execute(&mut *lock).await.unwrap();
+    let release = tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        sqlx::query("COMMIT").execute(&mut *lock).await.unwrap();
+    });
+
+    let created = GpuBaseRepository::new(reader).create(gpu_base("RTX 3060")).await.unwrap();
+    assert!(created.id.is_some());
+    release.await.unwrap();
+}
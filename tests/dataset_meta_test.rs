@@ -0,0 +1,187 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    middleware::from_fn_with_state,
+    routing::{get, post},
+    Router,
+};
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}},
+    handlers::{admin::{process_its, save_data}, meta::{dataset_metadata, wait_for_update}},
+    middleware::pipeline_tracking::track_pipeline_run,
+};
+
+/// Pipeline steps read through the pool while holding a transaction,
+/// so the test database must be a file shared by several connections
+async fn create_test_app_state(name: &str) -> AppState {
+    let path = std::env::temp_dir().join(format!("sd_its_{}_{}.db", name, std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let db_config = DatabaseConfig {
+        url: format!("sqlite:{}?mode=rwc", path.display()),
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    AppState {
+        db: db_pool,
+        settings: Settings::default(),
+    }
+}
+
+fn create_app(app_state: AppState) -> Router {
+    let pipeline_routes = Router::new()
+        .route("/api/process-its", post(process_its))
+        .route_layer(from_fn_with_state(app_state.clone(), track_pipeline_run));
+
+    Router::new()
+        .route("/api/save-data", post(save_data))
+        .route("/api/meta/dataset", get(dataset_metadata))
+        .route("/api/meta/wait-for-update", get(wait_for_update))
+        .merge(pipeline_routes)
+        .with_state(app_state)
+}
+
+async fn send(app: &Router, request: Request<Body>) -> (StatusCode, Value) {
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+fn get_request(uri: &str) -> Request<Body> {
+    Request::builder().method("GET").uri(uri).body(Body::empty()).unwrap()
+}
+
+fn upload_request(file_name: &str, json_data: &str) -> Request<Body> {
+    let boundary = "----WebKitFormBoundary7MA4YWxkTrZu0gW";
+    let body = format!(
+        "--{boundary}\r\n\
+        Content-Disposition: form-data; name=\"file\"; filename=\"{file_name}\"\r\n\
+        Content-Type: application/json\r\n\
+        \r\n\
+        {json_data}\r\n\
+        --{boundary}--\r\n"
+    );
+
+    Request::builder()
+        .method("POST")
+        .uri("/api/save-data")
+        .header("content-type", format!("multipart/form-data; boundary={}", boundary))
+        .body(Body::from(body))
+        .unwrap()
+}
+
+fn sample_runs() -> String {
+    json!([
+        {
+            "timestamp": "2024-01-01T10:00:00Z",
+            "vram_usage": "8GB",
+            "info": "app:stable-diffusion-webui updated:2023-05-18",
+            "system_info": "Windows 11",
+            "model_info": "SDXL",
+            "device_info": "RTX 4090",
+            "xformers": "true",
+            "model_name": "stable-diffusion-xl",
+            "user": "testuser1",
+            "notes": "10.5/11.0/12.5"
+        }
+    ])
+    .to_string()
+}
+
+#[tokio::test]
+async fn test_dataset_metadata_empty_dataset() {
+    let app = create_app(create_test_app_state("dataset_meta_empty").await);
+
+    let (status, body) = send(&app, get_request("/api/meta/dataset")).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let data = &body["data"];
+    assert_eq!(data["dataset_version"], 0);
+    assert_eq!(data["table_counts"]["runs"], 0);
+    assert_eq!(data["table_counts"]["GPUBase"], 0);
+    assert!(data["last_pipeline_step"].is_null());
+    assert!(data["last_pipeline_stage_timings"].as_array().unwrap().is_empty());
+    assert!(data["source_upload"].is_null());
+}
+
+#[tokio::test]
+async fn test_dataset_metadata_tracks_uploads_and_pipeline_runs() {
+    let app = create_app(create_test_app_state("dataset_meta_tracking").await);
+
+    let (status, _) = send(&app, upload_request("first.json", &sample_runs())).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = send(&app, upload_request("second.json", &sample_runs())).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (_, body) = send(&app, get_request("/api/meta/dataset")).await;
+    let data = &body["data"];
+    assert_eq!(data["dataset_version"], 2);
+    assert_eq!(data["table_counts"]["runs"], 1);
+    assert_eq!(data["source_upload"]["file_name"], "second.json");
+    assert_eq!(data["source_upload"]["format"], "webui");
+    assert!(data["source_upload"]["file_size"].as_i64().unwrap() > 0);
+    assert!(data["last_pipeline_step"].is_null());
+
+    let request = Request::builder().method("POST").uri("/api/process-its").body(Body::empty()).unwrap();
+    let (status, body) = send(&app, request).await;
+    assert_eq!(status, StatusCode::OK);
+    let stages: Vec<&str> = body["stage_timings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|timing| timing["stage"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        stages,
+        ["create_shadow_table", "fetch_runs", "insert_performance_results", "swap_shadow_table", "commit"]
+    );
+    assert_eq!(body["stage_timings"][2]["rows"], 1);
+    assert!(body["stage_timings"][2]["db_time_ms"].as_f64().unwrap() > 0.0);
+
+    let (_, body) = send(&app, get_request("/api/meta/dataset")).await;
+    let data = &body["data"];
+    assert_eq!(data["last_pipeline_step"], "process-its");
+    assert!(data["last_pipeline_run_at"].is_string());
+    assert!(data["last_pipeline_duration_ms"].as_f64().unwrap() > 0.0);
+    assert_eq!(data["last_pipeline_stage_timings"].as_array().unwrap().len(), 5);
+    assert_eq!(data["table_counts"]["performanceResult"], 1);
+}
+
+#[tokio::test]
+async fn test_wait_for_update_returns_once_the_version_passes() {
+    let app = create_app(create_test_app_state("dataset_meta_wait").await);
+
+    // Nothing uploaded yet, so the wait runs out
+    let (status, body) = send(&app, get_request("/api/meta/wait-for-update?since_version=0&timeout_seconds=1")).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["data"]["dataset_version"], 0);
+    assert_eq!(body["data"]["updated"], false);
+
+    let waiting = tokio::spawn({
+        let app = app.clone();
+        async move { send(&app, get_request("/api/meta/wait-for-update?since_version=0&timeout_seconds=30")).await }
+    });
+    tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+    let (status, _) = send(&app, upload_request("runs.json", &sample_runs())).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = tokio::time::timeout(std::time::Duration::from_secs(5), waiting).await.unwrap().unwrap();
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["dataset_version"], 1);
+    assert_eq!(body["data"]["updated"], true);
+
+    // A client already behind gets its answer at once
+    let (_, body) = send(&app, get_request("/api/meta/wait-for-update?since_version=0&timeout_seconds=1")).await;
+    assert_eq!(body["data"]["updated"], true);
+
+    let (status, body) = send(&app, get_request("/api/meta/wait-for-update")).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"]["params"][0]["field"], "since_version");
+}
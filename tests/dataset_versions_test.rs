@@ -0,0 +1,110 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    middleware::from_fn_with_state,
+    routing::{get, post},
+    Router,
+};
+use serde_json::Value;
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}},
+    handlers::meta::list_dataset_versions,
+    middleware::pipeline_tracking::track_pipeline_run,
+    models::{dataset_version::REFERENCE_MODEL_MAP, model_map::ModelMap},
+    repositories::{model_map_repository::ModelMapRepository, traits::Repository},
+};
+
+async fn create_test_app_state() -> AppState {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    AppState {
+        db: db_pool,
+        settings: Settings::default(),
+    }
+}
+
+/// A pipeline step that does nothing, so only the tracking middleware touches the database
+fn create_app(app_state: AppState) -> Router {
+    let pipeline_routes = Router::new()
+        .route("/api/process-noop", post(|| async { StatusCode::OK }))
+        .route("/api/process-failing", post(|| async { StatusCode::INTERNAL_SERVER_ERROR }))
+        .route_layer(from_fn_with_state(app_state.clone(), track_pipeline_run));
+
+    Router::new()
+        .route("/api/admin/dataset-versions", get(list_dataset_versions))
+        .merge(pipeline_routes)
+        .with_state(app_state)
+}
+
+async fn send(app: &Router, method: &str, uri: &str) -> (StatusCode, Value) {
+    let request = Request::builder().method(method).uri(uri).body(Body::empty()).unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+async fn add_model_map(app_state: &AppState, model_name: &str) {
+    ModelMapRepository::new(app_state.db.clone())
+        .create(ModelMap {
+            id: None,
+            model_name: Some(model_name.to_string()),
+            base_model: Some("SD 1.5".to_string()),
+            version: 1,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_pipeline_steps_pin_reference_revisions() {
+    let app_state = create_test_app_state().await;
+    let app = create_app(app_state.clone());
+
+    assert_eq!(send(&app, "POST", "/api/process-noop").await.0, StatusCode::OK);
+    add_model_map(&app_state, "v1-5-pruned").await;
+    assert_eq!(send(&app, "POST", "/api/process-noop").await.0, StatusCode::OK);
+    assert_eq!(send(&app, "POST", "/api/process-noop").await.0, StatusCode::OK);
+    // Failed steps produce no dataset version
+    assert_eq!(send(&app, "POST", "/api/process-failing").await.0, StatusCode::INTERNAL_SERVER_ERROR);
+
+    let (status, body) = send(&app, "GET", "/api/admin/dataset-versions").await;
+    assert_eq!(status, StatusCode::OK);
+    let versions = body["data"].as_array().unwrap();
+    assert_eq!(versions.len(), 3);
+    assert!(versions.iter().all(|version| version["pipeline_step"] == "process-noop"));
+
+    // Newest first: unchanged, then the ModelMap edit, then the first run
+    assert_eq!(versions[0]["changed_since_previous"].as_array().unwrap().len(), 0);
+    assert_eq!(versions[1]["changed_since_previous"], serde_json::json!([REFERENCE_MODEL_MAP]));
+    assert_ne!(versions[1]["model_map_hash"], versions[2]["model_map_hash"]);
+    assert_eq!(versions[1]["gpu_map_hash"], versions[2]["gpu_map_hash"]);
+    assert_eq!(versions[1]["rules_hash"], versions[2]["rules_hash"]);
+    assert_eq!(versions[2]["changed_since_previous"].as_array().unwrap().len(), 0);
+
+    // A limited page still compares its oldest version with the one before it
+    let (status, body) = send(&app, "GET", "/api/admin/dataset-versions?limit=2").await;
+    assert_eq!(status, StatusCode::OK);
+    let versions = body["data"].as_array().unwrap();
+    assert_eq!(versions.len(), 2);
+    assert_eq!(versions[1]["changed_since_previous"], serde_json::json!([REFERENCE_MODEL_MAP]));
+}
+
+#[tokio::test]
+async fn test_list_dataset_versions_rejects_non_positive_limit() {
+    let app = create_app(create_test_app_state().await);
+
+    let (status, _) = send(&app, "GET", "/api/admin/dataset-versions?limit=0").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,154 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    routing::get,
+    Router,
+};
+use chrono::{Duration, Utc};
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}},
+    handlers::digest::digest,
+    models::{gpu::Gpu, performance_result::PerformanceResult, runs::Run},
+    repositories::{
+        gpu_repository::GpuRepository,
+        performance_result_repository::PerformanceResultRepository,
+        runs_repository::RunsRepository,
+        traits::Repository,
+    },
+};
+
+async fn create_test_app_state() -> AppState {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    AppState {
+        db: db_pool,
+        settings: Settings::default(),
+    }
+}
+
+/// Insert a run by `user` on `device` with `avg_its`, ingested `days_ago`
+async fn create_run(app_state: &AppState, user: &str, device: &str, avg_its: f64, days_ago: i64) {
+    let run = RunsRepository::new(app_state.db.clone())
+        .create(Run {
+            id: None,
+            timestamp: Some("2024-01-01T00:00:00Z".to_string()),
+            vram_usage: None,
+            info: None,
+            system_info: None,
+            model_info: None,
+            device_info: None,
+            xformers: None,
+            model_name: Some("sd-v1-5".to_string()),
+            user: Some(user.to_string()),
+            notes: None,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap();
+
+    GpuRepository::new(app_state.db.clone())
+        .create(Gpu {
+            id: None,
+            run_id: run.id,
+            device: Some(device.to_string()),
+            driver: None,
+            gpu_chip: None,
+            brand: None,
+            is_laptop: None,
+            is_integrated: None,
+            is_cloud: None,
+            gpu_index: 0,
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap();
+
+    PerformanceResultRepository::new(app_state.db.clone())
+        .create(PerformanceResult { id: None, run_id: run.id, its: None, avg_its: Some(avg_its), vram_usage_kind: None, created_at: None, updated_at: None })
+        .await
+        .unwrap();
+
+    let created_at = (Utc::now() - Duration::days(days_ago)).to_rfc3339();
+    sqlx::query("UPDATE runs SET created_at = ? WHERE id = ?")
+        .bind(created_at)
+        .bind(run.id)
+        .execute(&app_state.db)
+        .await
+        .unwrap();
+}
+
+async fn fetch(app_state: &AppState, uri: &str) -> (StatusCode, Value) {
+    let app = Router::new().route("/api/digest", get(digest)).with_state(app_state.clone());
+    let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+#[tokio::test]
+async fn test_weekly_digest_summarizes_the_last_week() {
+    let app_state = create_test_app_state().await;
+    create_run(&app_state, "alice", "NVIDIA GeForce RTX 4090", 20.0, 30).await;
+    create_run(&app_state, "alice", "NVIDIA GeForce RTX 3060", 8.0, 30).await;
+    create_run(&app_state, "bob", "NVIDIA GeForce RTX 4090", 25.0, 2).await;
+    create_run(&app_state, "bob", "NVIDIA GeForce RTX 3060", 10.0, 1).await;
+    create_run(&app_state, "carol", "NVIDIA GeForce RTX 5090", 30.0, 1).await;
+
+    let (status, body) = fetch(&app_state, "/api/digest?period=week").await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    let digest = &body["data"];
+    assert_eq!(digest["period"], "week");
+    assert_eq!(digest["new_runs"], 3);
+    assert_eq!(digest["new_gpus"], json!([{ "device": "NVIDIA GeForce RTX 5090", "runs": 1 }]));
+    // 3060: +25%, 4090: +25% too, so by device name
+    let improvements: Vec<&str> = digest["its_improvements"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|improvement| improvement["device"].as_str().unwrap())
+        .collect();
+    assert_eq!(improvements, ["NVIDIA GeForce RTX 3060", "NVIDIA GeForce RTX 4090"]);
+    assert_eq!(
+        digest["active_users"],
+        json!([{ "user": "bob", "runs": 2 }, { "user": "carol", "runs": 1 }])
+    );
+
+    let summary = digest["summary"].as_str().unwrap();
+    assert!(summary.starts_with("SD-ITS-Benchmark this week: 3 new benchmark runs."), "{}", summary);
+    assert!(summary.contains("- NVIDIA GeForce RTX 4090: 20.00 -> 25.00 it/s (+25.0%)"), "{}", summary);
+}
+
+#[tokio::test]
+async fn test_digest_periods_and_anonymization() {
+    let app_state = create_test_app_state().await;
+    create_run(&app_state, "alice", "NVIDIA GeForce RTX 4090", 20.0, 3).await;
+
+    let (_, body) = fetch(&app_state, "/api/digest?period=day").await;
+    assert_eq!(body["data"]["new_runs"], 0);
+    assert_eq!(body["data"]["active_users"], json!([]));
+
+    // A week by default
+    let (_, body) = fetch(&app_state, "/api/digest?anonymize=true").await;
+    assert_eq!(body["data"]["period"], "week");
+    assert_eq!(body["data"]["new_runs"], 1);
+    let user = body["data"]["active_users"][0]["user"].as_str().unwrap();
+    assert!(user.starts_with("user-"), "{}", user);
+
+    let (status, _) = fetch(&app_state, "/api/digest?period=year").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,102 @@
+use std::{io::Write, sync::Arc};
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    routing::get,
+    Router,
+};
+use serde_json::Value;
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}, settings::ErrorMessagesConfig},
+    error::i18n::ErrorCatalog,
+    handlers::model_map,
+    middleware::error_localization::localize_errors,
+};
+
+async fn create_app(catalog: ErrorCatalog) -> Router {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    let app_state = AppState {
+        db: db_pool,
+        settings: Settings::default(),
+    };
+
+    Router::new()
+        .route("/api/model-map/{id}", get(model_map::get_model_map))
+        .route("/health", get(|| async { "OK" }))
+        .layer(axum::middleware::from_fn_with_state(Arc::new(catalog), localize_errors))
+        .with_state(app_state)
+}
+
+async fn get_with_language(app: &Router, uri: &str, accept_language: Option<&str>) -> (StatusCode, Option<String>, Vec<u8>) {
+    let mut builder = Request::builder().uri(uri);
+    if let Some(accept_language) = accept_language {
+        builder = builder.header(header::ACCEPT_LANGUAGE, accept_language);
+    }
+
+    let response = app.clone().oneshot(builder.body(Body::empty()).unwrap()).await.unwrap();
+    let status = response.status();
+    let content_language = response
+        .headers()
+        .get(header::CONTENT_LANGUAGE)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, content_language, body.to_vec())
+}
+
+#[tokio::test]
+async fn test_error_responses_are_translated_from_accept_language() {
+    let app = create_app(ErrorCatalog::bundled().unwrap()).await;
+
+    let (status, content_language, body) = get_with_language(&app, "/api/model-map/42", Some("de-DE,de;q=0.9,en;q=0.5")).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(content_language.as_deref(), Some("de"));
+    let body: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["error"]["code"], "NOT_FOUND", "codes stay stable across locales");
+    assert_eq!(body["error"]["locale"], "de");
+    assert_eq!(body["error"]["localized_message"], "Der angeforderte Eintrag wurde nicht gefunden.");
+    assert!(body["error"]["message"].as_str().unwrap().contains("ModelMap 42"));
+
+    let (_, _, body) = get_with_language(&app, "/api/model-map/42", Some("ja")).await;
+    let body: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["error"]["locale"], "en");
+    assert_eq!(body["error"]["localized_message"], "The requested item could not be found.");
+
+    let (status, content_language, body) = get_with_language(&app, "/health", Some("fr")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(content_language.is_none());
+    assert_eq!(body, b"OK");
+}
+
+#[tokio::test]
+async fn test_configured_catalog_overrides_and_default_locale() {
+    let mut overrides = tempfile::NamedTempFile::new().unwrap();
+    write!(overrides, r#"{{"fr": {{"NOT_FOUND": "Rien ici."}}}}"#).unwrap();
+
+    let catalog = ErrorCatalog::load(&ErrorMessagesConfig {
+        default_locale: "fr".to_string(),
+        catalog_path: Some(overrides.path().to_path_buf()),
+    })
+    .unwrap();
+    let app = create_app(catalog).await;
+
+    let (_, content_language, body) = get_with_language(&app, "/api/model-map/7", None).await;
+    assert_eq!(content_language.as_deref(), Some("fr"));
+    let body: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["error"]["localized_message"], "Rien ici.");
+
+    let (_, _, body) = get_with_language(&app, "/api/model-map/7", Some("es")).await;
+    let body: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["error"]["localized_message"], "No se encontró el elemento solicitado.");
+}
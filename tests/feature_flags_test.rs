@@ -0,0 +1,85 @@
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    routing::get,
+    Router,
+};
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}, settings::FeatureFlags},
+    error::i18n::ErrorCatalog,
+    handlers::model_map::export_model_map,
+    router::create_router,
+};
+
+async fn create_test_app_state(features: FeatureFlags) -> AppState {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    AppState {
+        db: db_pool,
+        settings: Settings {
+            features,
+            ..Settings::default()
+        },
+    }
+}
+
+async fn send(app: &Router, method: &str, uri: &str, body: &str) -> StatusCode {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    app.clone().oneshot(request).await.unwrap().status()
+}
+
+fn all_disabled() -> FeatureFlags {
+    FeatureFlags {
+        enable_public_submissions: false,
+        enable_export: false,
+    }
+}
+
+#[tokio::test]
+async fn test_features_are_enabled_by_default() {
+    let app_state = create_test_app_state(FeatureFlags::default()).await;
+    let app = create_router(app_state, Arc::new(ErrorCatalog::bundled().unwrap()));
+
+    assert_eq!(send(&app, "GET", "/api/model-map/export", "").await, StatusCode::OK);
+    // Mounted: the handler rejects the empty file name rather than the route being missing
+    assert_eq!(send(&app, "POST", "/api/uploads/init", r#"{"file_name":""}"#).await, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_disabled_features_are_not_mounted() {
+    let app_state = create_test_app_state(all_disabled()).await;
+    let app = create_router(app_state, Arc::new(ErrorCatalog::bundled().unwrap()));
+
+    assert_eq!(send(&app, "GET", "/api/model-map/export", "").await, StatusCode::NOT_FOUND);
+    assert_eq!(send(&app, "GET", "/api/gpu-map/export", "").await, StatusCode::NOT_FOUND);
+    assert_eq!(send(&app, "POST", "/api/uploads/init", r#"{"file_name":""}"#).await, StatusCode::NOT_FOUND);
+
+    // Routes outside the switched-off features stay available
+    assert_eq!(send(&app, "POST", "/api/model-map/import", "model_name,base_model\nsd_xl_base_1.0,SDXL\n").await, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_handlers_check_flags_when_mounted_directly() {
+    let app_state = create_test_app_state(all_disabled()).await;
+    let app = Router::new()
+        .route("/api/model-map/export", get(export_model_map))
+        .with_state(app_state);
+
+    assert_eq!(send(&app, "GET", "/api/model-map/export", "").await, StatusCode::NOT_FOUND);
+}
@@ -212,6 +212,8 @@ async fn create_required_runs(pool: &SqlitePool) -> Result<(), Box<dyn std::erro
             model_name: Some("test-model".to_string()),
             user: Some("test-user".to_string()),
             notes: Some(format!("Test run {}", i)),
+            created_at: None,
+            updated_at: None,
         };
         runs_repository.create(run).await?;
     }
@@ -230,6 +232,9 @@ fn create_test_app_details() -> Vec<AppDetails> {
             url: Some("https://github.com/AUTOMATIC1111/stable-diffusion-webui".to_string()),
             hash: Some("abc123".to_string()),
             updated: Some("2024-01-01".to_string()),
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
         },
         // Vladmandic URL - should be updated (NULL app_name)
         AppDetails {
@@ -239,6 +244,9 @@ fn create_test_app_details() -> Vec<AppDetails> {
             url: Some("https://github.com/vladmandic/automatic".to_string()),
             hash: Some("def456".to_string()),
             updated: Some("2024-01-02".to_string()),
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
         },
         // Stable Diffusion URL - should be updated (NULL app_name)
         AppDetails {
@@ -248,6 +256,9 @@ fn create_test_app_details() -> Vec<AppDetails> {
             url: Some("https://github.com/CompVis/stable-diffusion-webui".to_string()),
             hash: Some("ghi789".to_string()),
             updated: Some("2024-01-03".to_string()),
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
         },
         // Both app_name and URL are null - should be updated
         AppDetails {
@@ -257,6 +268,9 @@ fn create_test_app_details() -> Vec<AppDetails> {
             url: None,
             hash: Some("jkl012".to_string()),
             updated: Some("2024-01-04".to_string()),
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
         },
         // Existing app name - should not be updated
         AppDetails {
@@ -266,6 +280,9 @@ fn create_test_app_details() -> Vec<AppDetails> {
             url: Some("https://github.com/some-other/app".to_string()),
             hash: Some("mno345".to_string()),
             updated: Some("2024-01-05".to_string()),
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
         },
     ]
 }
@@ -281,6 +298,9 @@ fn create_non_matching_app_details() -> Vec<AppDetails> {
             url: Some("https://github.com/some-other/app".to_string()),
             hash: Some("abc123".to_string()),
             updated: Some("2024-01-01".to_string()),
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
         },
         // Non-matching URL with NULL app name
         AppDetails {
@@ -290,6 +310,9 @@ fn create_non_matching_app_details() -> Vec<AppDetails> {
             url: Some("https://github.com/another-app".to_string()),
             hash: Some("def456".to_string()),
             updated: Some("2024-01-02".to_string()),
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
         },
     ]
 }
@@ -305,6 +328,9 @@ fn create_edge_case_app_details() -> Vec<AppDetails> {
             url: Some("https://github.com/vladmandic/automatic".to_string()),
             hash: Some("hash1".to_string()),
             updated: Some("2024-01-01".to_string()),
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
         },
         // Existing app name with stable-diffusion-webui URL - should not be updated
         AppDetails {
@@ -314,6 +340,9 @@ fn create_edge_case_app_details() -> Vec<AppDetails> {
             url: Some("https://github.com/CompVis/stable-diffusion-webui".to_string()),
             hash: Some("hash2".to_string()),
             updated: Some("2024-01-02".to_string()),
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
         },
     ]
 } 
\ No newline at end of file
@@ -10,6 +10,7 @@ use sd_its_benchmark::{
     AppState,
     handlers::admin::fix_app_names,
     handlers::validation::FixAppNamesRequest,
+    middleware::request_transaction::transaction_per_request,
     models::{app_details::AppDetails, runs::Run},
     repositories::{
         app_details_repository::AppDetailsRepository,
@@ -33,6 +34,7 @@ async fn create_test_pool() -> SqlitePool {
 fn create_test_app(app_state: AppState) -> Router {
     Router::new()
         .route("/api/fix-app-names", axum::routing::post(fix_app_names))
+        .route_layer(axum::middleware::from_fn_with_state(app_state.clone(), transaction_per_request))
         .with_state(app_state)
 }
 
@@ -54,6 +56,8 @@ async fn setup_test_app_details_data(pool: &SqlitePool) -> Vec<AppDetails> {
             model_name: Some("test-model-1".to_string()),
             user: Some("test-user-1".to_string()),
             notes: Some("test-notes-1".to_string()),
+            created_at: None,
+            updated_at: None,
         },
         Run {
             id: None,
@@ -67,6 +71,8 @@ async fn setup_test_app_details_data(pool: &SqlitePool) -> Vec<AppDetails> {
             model_name: Some("test-model-2".to_string()),
             user: Some("test-user-2".to_string()),
             notes: Some("test-notes-2".to_string()),
+            created_at: None,
+            updated_at: None,
         },
         Run {
             id: None,
@@ -80,6 +86,8 @@ async fn setup_test_app_details_data(pool: &SqlitePool) -> Vec<AppDetails> {
             model_name: Some("test-model-3".to_string()),
             user: Some("test-user-3".to_string()),
             notes: Some("test-notes-3".to_string()),
+            created_at: None,
+            updated_at: None,
         },
         Run {
             id: None,
@@ -93,6 +101,8 @@ async fn setup_test_app_details_data(pool: &SqlitePool) -> Vec<AppDetails> {
             model_name: Some("test-model-4".to_string()),
             user: Some("test-user-4".to_string()),
             notes: Some("test-notes-4".to_string()),
+            created_at: None,
+            updated_at: None,
         },
         Run {
             id: None,
@@ -106,6 +116,8 @@ async fn setup_test_app_details_data(pool: &SqlitePool) -> Vec<AppDetails> {
             model_name: Some("test-model-5".to_string()),
             user: Some("test-user-5".to_string()),
             notes: Some("test-notes-5".to_string()),
+            created_at: None,
+            updated_at: None,
         },
     ];
 
@@ -124,6 +136,9 @@ async fn setup_test_app_details_data(pool: &SqlitePool) -> Vec<AppDetails> {
             url: Some("https://github.com/AUTOMATIC1111/stable-diffusion-webui".to_string()),
             hash: Some("abc123".to_string()),
             updated: Some("2024-01-01".to_string()),
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
         },
         AppDetails {
             id: None,
@@ -132,6 +147,9 @@ async fn setup_test_app_details_data(pool: &SqlitePool) -> Vec<AppDetails> {
             url: Some("https://github.com/vladmandic/automatic".to_string()),
             hash: Some("def456".to_string()),
             updated: Some("2024-01-02".to_string()),
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
         },
         AppDetails {
             id: None,
@@ -140,6 +158,9 @@ async fn setup_test_app_details_data(pool: &SqlitePool) -> Vec<AppDetails> {
             url: Some("https://github.com/CompVis/stable-diffusion-webui".to_string()),
             hash: Some("ghi789".to_string()),
             updated: Some("2024-01-03".to_string()),
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
         },
         AppDetails {
             id: None,
@@ -148,6 +169,9 @@ async fn setup_test_app_details_data(pool: &SqlitePool) -> Vec<AppDetails> {
             url: None,
             hash: Some("jkl012".to_string()),
             updated: Some("2024-01-04".to_string()),
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
         },
         AppDetails {
             id: None,
@@ -156,6 +180,9 @@ async fn setup_test_app_details_data(pool: &SqlitePool) -> Vec<AppDetails> {
             url: Some("https://github.com/some-other/app".to_string()),
             hash: Some("mno345".to_string()),
             updated: Some("2024-01-05".to_string()),
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
         },
     ];
 
@@ -251,6 +278,8 @@ async fn test_fix_app_names_no_matches() {
         model_name: Some("test-model".to_string()),
         user: Some("test-user".to_string()),
         notes: Some("test-notes".to_string()),
+        created_at: None,
+        updated_at: None,
     };
 
     let created_run = runs_repo.create(run).await.unwrap();
@@ -263,6 +292,9 @@ async fn test_fix_app_names_no_matches() {
         url: Some("https://github.com/some-other/app".to_string()),
         hash: Some("abc123".to_string()),
         updated: Some("2024-01-01".to_string()),
+        parser_version: None,
+        created_at: None,
+        updated_at: None,
     };
 
     app_details_repo.create(app_detail).await.unwrap();
@@ -392,6 +424,8 @@ async fn test_fix_app_names_edge_cases() {
             model_name: Some("test-model-1".to_string()),
             user: Some("test-user-1".to_string()),
             notes: Some("test-notes-1".to_string()),
+            created_at: None,
+            updated_at: None,
         },
         Run {
             id: None,
@@ -405,6 +439,8 @@ async fn test_fix_app_names_edge_cases() {
             model_name: Some("test-model-2".to_string()),
             user: Some("test-user-2".to_string()),
             notes: Some("test-notes-2".to_string()),
+            created_at: None,
+            updated_at: None,
         },
     ];
 
@@ -423,6 +459,9 @@ async fn test_fix_app_names_edge_cases() {
             url: Some("https://github.com/vladmandic/automatic".to_string()),
             hash: Some("hash1".to_string()),
             updated: Some("2024-01-01".to_string()),
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
         },
         AppDetails {
             id: None,
@@ -431,6 +470,9 @@ async fn test_fix_app_names_edge_cases() {
             url: Some("https://github.com/CompVis/stable-diffusion-webui".to_string()),
             hash: Some("hash2".to_string()),
             updated: Some("2024-01-02".to_string()),
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
         },
     ];
 
@@ -479,4 +521,147 @@ async fn test_fix_app_names_edge_cases() {
     assert_eq!(updated_counts["vladmandic"], 1); // Empty string app_name with vladmandic URL
     assert_eq!(updated_counts["stable_diffusion"], 0); // Existing app_name should not be updated
     assert_eq!(updated_counts["null_app_name_null_url"], 0);
-} 
\ No newline at end of file
+} 
+// Test that a dry run reports the changes without writing them
+#[tokio::test]
+async fn test_fix_app_names_dry_run() {
+    let pool = create_test_pool().await;
+    let test_app_details = setup_test_app_details_data(&pool).await;
+
+    let app_state = AppState {
+        db: pool.clone(),
+        settings: sd_its_benchmark::config::settings::Settings::new().unwrap(),
+    };
+    let app = create_test_app(app_state);
+
+    let request_body = FixAppNamesRequest {
+        automatic1111: "AUTOMATIC1111".to_string(),
+        vladmandic: "Vladmandic".to_string(),
+        stable_diffusion: "StableDiffusion".to_string(),
+        null_app_name_null_url: "Unknown".to_string(),
+    };
+
+    let request = Request::builder()
+        .method(Method::POST)
+        .uri("/api/fix-app-names?dry_run=true")
+        .header("Content-Type", "application/json")
+        .body(axum::body::Body::from(serde_json::to_string(&request_body).unwrap()))
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let response_json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+    let data = &response_json["data"];
+    assert_eq!(data["dry_run"], true);
+    assert_eq!(data["updated_counts"]["automatic1111"], 1);
+    assert_eq!(data["updated_counts"]["null_app_name_null_url"], 1);
+
+    let samples = &data["samples"]["automatic1111"];
+    assert_eq!(samples.as_array().unwrap().len(), 1);
+    assert_eq!(samples[0]["app_name"], serde_json::Value::Null);
+    assert_eq!(samples[0]["new_app_name"], "AUTOMATIC1111");
+
+    // Nothing was written
+    let app_details_repo = AppDetailsRepository::new(pool);
+    let all_app_details = app_details_repo.find_all().await.unwrap();
+    assert_eq!(all_app_details.len(), test_app_details.len());
+    let unchanged = all_app_details.iter().filter(|details| details.app_name.is_none()).count();
+    assert_eq!(unchanged, 4);
+}
+
+// Test that the rules match the normalized URL and report the renames it gained
+#[tokio::test]
+async fn test_fix_app_names_matches_normalized_urls() {
+    let pool = create_test_pool().await;
+    let runs_repo = RunsRepository::new(pool.clone());
+    let app_details_repo = AppDetailsRepository::new(pool.clone());
+
+    let urls = [Some("  HTTPS://GitHub.com/VLADMANDIC/automatic//  "), Some("   "), None];
+    for (index, url) in urls.into_iter().enumerate() {
+        let run = runs_repo
+            .create(Run {
+                id: None,
+                timestamp: Some(format!("2024-01-0{}T00:00:00Z", index + 1)),
+                vram_usage: Some("8GB".to_string()),
+                info: Some(format!("test-info-{}", index)),
+                system_info: None,
+                model_info: None,
+                device_info: None,
+                xformers: None,
+                model_name: None,
+                user: None,
+                notes: None,
+                created_at: None,
+                updated_at: None,
+            })
+            .await
+            .unwrap();
+        app_details_repo
+            .create(AppDetails {
+                id: None,
+                run_id: run.id,
+                app_name: None,
+                url: url.map(str::to_string),
+                hash: None,
+                updated: None,
+                parser_version: None,
+                created_at: None,
+                updated_at: None,
+            })
+            .await
+            .unwrap();
+    }
+
+    let normalized: Vec<Option<String>> = sqlx::query_scalar("SELECT url_normalized FROM AppDetails ORDER BY id")
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+    assert_eq!(normalized, [Some("https://github.com/vladmandic/automatic".to_string()), None, None]);
+
+    let app_state = AppState {
+        db: pool.clone(),
+        settings: sd_its_benchmark::config::settings::Settings::new().unwrap(),
+    };
+    let app = create_test_app(app_state);
+
+    let request_body = FixAppNamesRequest {
+        automatic1111: "AUTOMATIC1111".to_string(),
+        vladmandic: "Vladmandic".to_string(),
+        stable_diffusion: "StableDiffusion".to_string(),
+        null_app_name_null_url: "Unknown".to_string(),
+    };
+
+    let request = Request::builder()
+        .method(Method::POST)
+        .uri("/api/fix-app-names")
+        .header("Content-Type", "application/json")
+        .body(axum::body::Body::from(serde_json::to_string(&request_body).unwrap()))
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let response_json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+    let data = &response_json["data"];
+    assert_eq!(data["updated_counts"]["vladmandic"], 1);
+    // The whitespace-only URL is treated as missing
+    assert_eq!(data["updated_counts"]["null_app_name_null_url"], 2);
+    assert_eq!(data["additional_matches"]["null_app_name_null_url"], 1);
+    // LIKE already ignores ASCII case and the padding sits outside the pattern
+    assert_eq!(data["additional_matches"]["vladmandic"], 0);
+    assert_eq!(data["additional_matches"]["automatic1111"], 0);
+
+    let app_names: Vec<Option<String>> = sqlx::query_scalar("SELECT app_name FROM AppDetails ORDER BY id")
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+    assert_eq!(
+        app_names,
+        [Some("Vladmandic".to_string()), Some("Unknown".to_string()), Some("Unknown".to_string())]
+    );
+}
@@ -0,0 +1,176 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    routing::post,
+    Router,
+};
+use serde_json::Value;
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}},
+    handlers::users::forget_user,
+    middleware::request_transaction::transaction_per_request,
+    models::{
+        audit_log::AUDIT_ACTION_FORGET_USER, gpu::Gpu, performance_result::PerformanceResult,
+        run_more_details::RunMoreDetails, runs::Run,
+    },
+    repositories::{
+        audit_log_repository::AuditLogRepository,
+        gpu_repository::GpuRepository,
+        performance_result_repository::PerformanceResultRepository,
+        run_more_details_repository::RunMoreDetailsRepository,
+        runs_repository::RunsRepository,
+        traits::Repository,
+    },
+};
+
+async fn create_test_app_state() -> AppState {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    AppState {
+        db: db_pool,
+        settings: Settings::default(),
+    }
+}
+
+fn create_app(app_state: AppState) -> Router {
+    Router::new()
+        .route("/api/admin/users/{user}/forget", post(forget_user))
+        .route_layer(axum::middleware::from_fn_with_state(app_state.clone(), transaction_per_request))
+        .with_state(app_state)
+}
+
+async fn send(app: &Router, uri: &str) -> (StatusCode, Value) {
+    let request = Request::builder().method("POST").uri(uri).body(Body::empty()).unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+/// Insert a run with a GPU and performance row; `details_user` is the processed RunMoreDetails user
+async fn create_run(app_state: &AppState, user: Option<&str>, details_user: Option<&str>) -> i64 {
+    let run_id = RunsRepository::new(app_state.db.clone())
+        .create(Run {
+            id: None,
+            timestamp: Some("2024-01-01T00:00:00Z".to_string()),
+            vram_usage: None,
+            info: None,
+            system_info: None,
+            model_info: None,
+            device_info: None,
+            xformers: None,
+            model_name: None,
+            user: user.map(str::to_string),
+            notes: Some("my notes".to_string()),
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap()
+        .id
+        .unwrap();
+
+    PerformanceResultRepository::new(app_state.db.clone())
+        .create(PerformanceResult { id: None, run_id: Some(run_id), its: None, avg_its: Some(10.0), vram_usage_kind: None, created_at: None, updated_at: None })
+        .await
+        .unwrap();
+    GpuRepository::new(app_state.db.clone())
+        .create(Gpu {
+            id: None,
+            run_id: Some(run_id),
+            device: Some("NVIDIA GeForce RTX 4090".to_string()),
+            driver: None,
+            gpu_chip: None,
+            brand: None,
+            is_laptop: None,
+            is_integrated: None,
+            is_cloud: None,
+            gpu_index: 0,
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap();
+    RunMoreDetailsRepository::new(app_state.db.clone())
+        .create(RunMoreDetails {
+            id: None,
+            run_id: Some(run_id),
+            timestamp: None,
+            model_name: None,
+            user: details_user.map(str::to_string),
+            notes: None,
+            model_map_id: None,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap();
+
+    run_id
+}
+
+async fn count(app_state: &AppState, table: &str) -> i64 {
+    sqlx::query_scalar(&format!("SELECT COUNT(*) FROM {}", table))
+        .fetch_one(&app_state.db)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_forget_user_deletes_runs_and_derived_rows() {
+    let app_state = create_test_app_state().await;
+    create_run(&app_state, Some("alice"), Some("alice")).await;
+    // Only the processed details name the user
+    create_run(&app_state, None, Some("alice")).await;
+    let kept_run = create_run(&app_state, Some("bob"), Some("bob")).await;
+    let app = create_app(app_state.clone());
+
+    let (status, body) = send(&app, "/api/admin/users/alice/forget").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["runs_deleted"], 2);
+    assert_eq!(body["data"]["deleted"]["runs"], 2);
+    assert_eq!(body["data"]["deleted"]["GPU"], 2);
+    assert_eq!(body["data"]["deleted"]["performanceResult"], 2);
+    assert_eq!(body["data"]["deleted"]["RunMoreDetails"], 2);
+    assert_eq!(body["data"]["deleted"]["RunScore"], 0);
+
+    for table in ["runs", "GPU", "performanceResult", "RunMoreDetails"] {
+        assert_eq!(count(&app_state, table).await, 1, "{}", table);
+    }
+    assert!(RunsRepository::new(app_state.db.clone()).find_by_id(kept_run).await.unwrap().is_some());
+
+    // The audit entry records the deletion without the raw username
+    let entries = AuditLogRepository::new(app_state.db.clone())
+        .find_by_action(AUDIT_ACTION_FORGET_USER)
+        .await
+        .unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(body["data"]["audit_id"], entries[0].id);
+    let details = entries[0].details.as_deref().unwrap();
+    assert!(!details.contains("alice"));
+    assert!(details.contains("user-"));
+
+    // Forgetting again finds nothing and records nothing
+    let (status, body) = send(&app, "/api/admin/users/alice/forget").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["runs_deleted"], 0);
+    assert!(body["data"]["audit_id"].is_null());
+}
+
+#[tokio::test]
+async fn test_forget_user_rejects_blank_user() {
+    let app = create_app(create_test_app_state().await);
+
+    let (status, _) = send(&app, "/api/admin/users/%20/forget").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
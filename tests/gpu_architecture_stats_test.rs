@@ -0,0 +1,124 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    routing::{get, post},
+    Router,
+};
+use serde_json::Value;
+use tower::ServiceExt;
+
+mod common;
+
+use common::create_run_on_device;
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}},
+    handlers::{admin::seed_gpu_base, stats::its_by_architecture},
+    models::gpu_map::GpuMap,
+    repositories::{
+        gpu_base_repository::GpuBaseRepository,
+        gpu_map_repository::GpuMapRepository,
+        traits::Repository,
+    },
+};
+
+async fn create_test_app_state() -> AppState {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    AppState {
+        db: db_pool,
+        settings: Settings::default(),
+    }
+}
+
+fn create_app(app_state: AppState) -> Router {
+    Router::new()
+        .route("/api/admin/seed-gpu-base", post(seed_gpu_base))
+        .route("/api/stats/its-by-architecture", get(its_by_architecture))
+        .with_state(app_state)
+}
+
+async fn send(app: &Router, method: &str, uri: &str) -> (StatusCode, Value) {
+    let request = Request::builder().method(method).uri(uri).body(Body::empty()).unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+/// Map a reported device name onto the seeded base GPU with the given name
+async fn map_device(app_state: &AppState, device: &str, base_gpu_name: &str) {
+    let base_gpu = GpuBaseRepository::new(app_state.db.clone())
+        .find_by_name(base_gpu_name)
+        .await
+        .unwrap()
+        .remove(0);
+
+    GpuMapRepository::new(app_state.db.clone())
+        .create(GpuMap { id: None, gpu_name: Some(device.to_string()), base_gpu_id: base_gpu.id, version: 1, created_at: None, updated_at: None })
+        .await
+        .unwrap();
+}
+
+/// Find the stats row for an architecture / generation pair
+fn find_row<'a>(rows: &'a [Value], architecture: &str, generation: &str) -> &'a Value {
+    rows.iter()
+        .find(|row| row["architecture"] == architecture && row["generation"] == generation)
+        .unwrap_or_else(|| panic!("missing row for {} / {}", architecture, generation))
+}
+
+#[tokio::test]
+async fn test_its_by_architecture_groups_runs_by_generation() {
+    let app_state = create_test_app_state().await;
+    let app = create_app(app_state.clone());
+
+    let (status, _) = send(&app, "POST", "/api/admin/seed-gpu-base").await;
+    assert_eq!(status, StatusCode::OK);
+
+    map_device(&app_state, "NVIDIA GeForce RTX 4090", "GeForce RTX 4090").await;
+    map_device(&app_state, "NVIDIA GeForce RTX 4080", "GeForce RTX 4080").await;
+    map_device(&app_state, "NVIDIA GeForce RTX 3090", "GeForce RTX 3090").await;
+    map_device(&app_state, "AMD Radeon RX 7900 XTX", "Radeon RX 7900 XTX").await;
+
+    create_run_on_device(&app_state, "NVIDIA GeForce RTX 4090", 30.0).await;
+    create_run_on_device(&app_state, "NVIDIA GeForce RTX 4080", 20.0).await;
+    create_run_on_device(&app_state, "NVIDIA GeForce RTX 3090", 15.0).await;
+    create_run_on_device(&app_state, "AMD Radeon RX 7900 XTX", 12.0).await;
+
+    let (status, stats) = send(&app, "GET", "/api/stats/its-by-architecture").await;
+    assert_eq!(status, StatusCode::OK);
+    let rows = stats["data"].as_array().unwrap();
+
+    let ada = find_row(rows, "Ada Lovelace", "GeForce 40");
+    assert_eq!(ada["brand"], "nvidia");
+    assert_eq!(ada["run_count"], 2);
+    assert_eq!(ada["avg_its"], 25.0);
+    assert_eq!(ada["first_launch_year"], 2022);
+
+    let ampere = find_row(rows, "Ampere", "GeForce 30");
+    assert_eq!(ampere["run_count"], 1);
+    assert_eq!(ampere["avg_its"], 15.0);
+
+    let unbenchmarked = find_row(rows, "Turing", "GeForce 20");
+    assert_eq!(unbenchmarked["run_count"], 0);
+    assert!(unbenchmarked["avg_its"].is_null());
+
+    let nvidia_years: Vec<i64> = rows
+        .iter()
+        .filter(|row| row["brand"] == "nvidia")
+        .map(|row| row["first_launch_year"].as_i64().unwrap())
+        .collect();
+    assert!(nvidia_years.windows(2).all(|pair| pair[0] <= pair[1]), "generations should be oldest first");
+
+    let (status, amd) = send(&app, "GET", "/api/stats/its-by-architecture?brand=amd").await;
+    assert_eq!(status, StatusCode::OK);
+    let amd_rows = amd["data"].as_array().unwrap();
+    assert!(amd_rows.iter().all(|row| row["brand"] == "amd"));
+    assert_eq!(find_row(amd_rows, "RDNA 3", "Radeon RX 7000")["avg_its"], 12.0);
+}
@@ -0,0 +1,166 @@
+use std::{net::SocketAddr, sync::Arc};
+
+use axum::{
+    body::Body,
+    extract::ConnectInfo,
+    http::{header, Request, StatusCode},
+    response::Response,
+    Router,
+};
+use tower::ServiceExt;
+
+mod common;
+
+use common::create_run_on_device;
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}, settings::BadgesConfig},
+    error::i18n::ErrorCatalog,
+    router::create_router,
+};
+
+const BADGE_URI: &str = "/api/badge/gpu/NVIDIA%20GeForce%20RTX%204090.svg";
+
+async fn create_test_app_state(badges: BadgesConfig) -> AppState {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    AppState {
+        db: db_pool,
+        settings: Settings { badges, ..Settings::default() },
+    }
+}
+
+fn create_app(app_state: AppState) -> Router {
+    create_router(app_state, Arc::new(ErrorCatalog::bundled().unwrap()))
+}
+
+struct BadgeResponse {
+    status: StatusCode,
+    content_type: Option<String>,
+    retry_after: Option<String>,
+    body: String,
+}
+
+async fn get_badge(app: &Router, uri: &str, client: &str) -> BadgeResponse {
+    get_badge_via(app, uri, client, None).await
+}
+
+/// Request a badge from the `peer` address, optionally claiming to forward for `forwarded_for`
+async fn get_badge_via(app: &Router, uri: &str, peer: &str, forwarded_for: Option<&str>) -> BadgeResponse {
+    let mut request = Request::builder()
+        .uri(uri)
+        .extension(ConnectInfo(SocketAddr::new(peer.parse().unwrap(), 40000)));
+    if let Some(forwarded_for) = forwarded_for {
+        request = request.header("x-forwarded-for", forwarded_for);
+    }
+    let request = request.body(Body::empty()).unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let content_type = header_value(&response, header::CONTENT_TYPE);
+    let retry_after = header_value(&response, header::RETRY_AFTER);
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+
+    BadgeResponse {
+        status,
+        content_type,
+        retry_after,
+        body: String::from_utf8(bytes.to_vec()).unwrap(),
+    }
+}
+
+fn header_value(response: &Response, name: header::HeaderName) -> Option<String> {
+    response.headers().get(name).map(|value| value.to_str().unwrap().to_string())
+}
+
+#[tokio::test]
+async fn test_badge_shows_median_its() {
+    let app_state = create_test_app_state(BadgesConfig::default()).await;
+    for avg_its in [10.0, 20.5, 30.0] {
+        create_run_on_device(&app_state, "NVIDIA GeForce RTX 4090", avg_its).await;
+    }
+    create_run_on_device(&app_state, "NVIDIA GeForce RTX 3060", 5.0).await;
+    let app = create_app(app_state);
+
+    let badge = get_badge(&app, BADGE_URI, "10.0.0.1").await;
+    assert_eq!(badge.status, StatusCode::OK);
+    assert_eq!(badge.content_type.as_deref(), Some("image/svg+xml; charset=utf-8"));
+    assert!(badge.body.starts_with("<svg"));
+    assert!(badge.body.contains("NVIDIA GeForce RTX 4090"));
+    assert!(badge.body.contains("20.50 it/s"));
+}
+
+#[tokio::test]
+async fn test_unknown_gpu_gets_no_data_badge() {
+    let app = create_app(create_test_app_state(BadgesConfig::default()).await);
+
+    let badge = get_badge(&app, "/api/badge/gpu/Imaginary%20GPU.svg", "10.0.0.1").await;
+    assert_eq!(badge.status, StatusCode::OK);
+    assert!(badge.body.contains("no data"));
+
+    let badge = get_badge(&app, "/api/badge/gpu/Imaginary%20GPU.png", "10.0.0.1").await;
+    assert_eq!(badge.status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_badge_is_served_from_cache() {
+    let app_state = create_test_app_state(BadgesConfig::default()).await;
+    create_run_on_device(&app_state, "NVIDIA GeForce RTX 4090", 10.0).await;
+    let app = create_app(app_state.clone());
+
+    let first = get_badge(&app, BADGE_URI, "10.0.0.1").await;
+    assert!(first.body.contains("10.00 it/s"));
+
+    create_run_on_device(&app_state, "NVIDIA GeForce RTX 4090", 30.0).await;
+    let second = get_badge(&app, BADGE_URI, "10.0.0.1").await;
+    assert_eq!(second.body, first.body);
+}
+
+#[tokio::test]
+async fn test_badge_requests_are_rate_limited_per_client() {
+    let badges = BadgesConfig {
+        rate_limit_per_minute: 2,
+        ..BadgesConfig::default()
+    };
+    let app = create_app(create_test_app_state(badges).await);
+
+    assert_eq!(get_badge(&app, BADGE_URI, "10.0.0.1").await.status, StatusCode::OK);
+    assert_eq!(get_badge(&app, BADGE_URI, "10.0.0.1").await.status, StatusCode::OK);
+
+    let limited = get_badge(&app, BADGE_URI, "10.0.0.1").await;
+    assert_eq!(limited.status, StatusCode::TOO_MANY_REQUESTS);
+    assert!(limited.retry_after.is_some());
+    assert!(limited.body.contains("RATE_LIMITED"));
+
+    assert_eq!(get_badge(&app, BADGE_URI, "10.0.0.2").await.status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_forwarded_for_is_only_believed_from_trusted_proxies() {
+    let badges = BadgesConfig {
+        rate_limit_per_minute: 1,
+        trusted_proxies: vec!["10.0.0.100".parse().unwrap()],
+        ..BadgesConfig::default()
+    };
+    let app = create_app(create_test_app_state(badges).await);
+
+    // A direct client cannot dodge the limit by making up a new address each time
+    assert_eq!(get_badge_via(&app, BADGE_URI, "10.0.0.1", Some("1.1.1.1")).await.status, StatusCode::OK);
+    assert_eq!(
+        get_badge_via(&app, BADGE_URI, "10.0.0.1", Some("2.2.2.2")).await.status,
+        StatusCode::TOO_MANY_REQUESTS
+    );
+
+    // Behind the proxy, clients are told apart by the address it forwarded, not one they prepended
+    assert_eq!(get_badge_via(&app, BADGE_URI, "10.0.0.100", Some("10.0.0.2")).await.status, StatusCode::OK);
+    assert_eq!(
+        get_badge_via(&app, BADGE_URI, "10.0.0.100", Some("3.3.3.3, 10.0.0.2")).await.status,
+        StatusCode::TOO_MANY_REQUESTS
+    );
+    assert_eq!(get_badge_via(&app, BADGE_URI, "10.0.0.100", Some("10.0.0.3")).await.status, StatusCode::OK);
+}
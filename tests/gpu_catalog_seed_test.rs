@@ -0,0 +1,114 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    routing::post,
+    Router,
+};
+use serde_json::Value;
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}},
+    handlers::admin::seed_gpu_base,
+    models::gpu_base::GpuBase,
+    repositories::{
+        gpu_base_repository::GpuBaseRepository,
+        gpu_spec_repository::GpuSpecRepository,
+        traits::Repository,
+    },
+    services::data_processing::seed_gpu_base_service::gpu_catalog,
+};
+
+async fn create_test_app_state() -> AppState {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    AppState {
+        db: db_pool,
+        settings: Settings::default(),
+    }
+}
+
+fn create_app(app_state: AppState) -> Router {
+    Router::new()
+        .route("/api/admin/seed-gpu-base", post(seed_gpu_base))
+        .with_state(app_state)
+}
+
+async fn seed(app: &Router) -> (StatusCode, Value) {
+    let request = Request::builder().method("POST").uri("/api/admin/seed-gpu-base").body(Body::empty()).unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+#[tokio::test]
+async fn test_seed_gpu_base_inserts_catalog() {
+    let app_state = create_test_app_state().await;
+    let app = create_app(app_state.clone());
+    let catalog_size = gpu_catalog().unwrap().len();
+
+    let (status, body) = seed(&app).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["success"], true);
+    assert_eq!(body["rows_processed"], catalog_size);
+    assert_eq!(body["rows_inserted"], catalog_size);
+    assert_eq!(body["rows_updated"], 0);
+
+    let gpu_base_repository = GpuBaseRepository::new(app_state.db.clone());
+    assert_eq!(gpu_base_repository.count().await.unwrap(), catalog_size as i64);
+
+    let rtx_4090 = gpu_base_repository.find_by_name("GeForce RTX 4090").await.unwrap().remove(0);
+    assert_eq!(rtx_4090.brand.as_deref(), Some("nvidia"));
+    assert_eq!(rtx_4090.architecture.as_deref(), Some("Ada Lovelace"));
+    assert_eq!(rtx_4090.generation.as_deref(), Some("GeForce 40"));
+
+    let spec = GpuSpecRepository::new(app_state.db.clone())
+        .find_by_base_gpu_id(rtx_4090.id.unwrap())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(spec.vram_gb, Some(24.0));
+    assert_eq!(spec.launch_year, Some(2022));
+}
+
+#[tokio::test]
+async fn test_seed_gpu_base_is_idempotent_and_keeps_existing_ids() {
+    let app_state = create_test_app_state().await;
+    let app = create_app(app_state.clone());
+    let catalog_size = gpu_catalog().unwrap().len();
+
+    let gpu_base_repository = GpuBaseRepository::new(app_state.db.clone());
+    let existing = gpu_base_repository
+        .create(GpuBase { id: None, name: "GeForce RTX 3090".to_string(), brand: None, architecture: None, generation: None, version: 1, created_at: None, updated_at: None })
+        .await
+        .unwrap();
+    let custom = gpu_base_repository
+        .create(GpuBase { id: None, name: "Custom GPU".to_string(), brand: Some("other".to_string()), architecture: None, generation: None, version: 1, created_at: None, updated_at: None })
+        .await
+        .unwrap();
+
+    let (status, body) = seed(&app).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["rows_inserted"], catalog_size - 1);
+    assert_eq!(body["rows_updated"], 1);
+
+    let (_, body) = seed(&app).await;
+    assert_eq!(body["rows_inserted"], 0);
+    assert_eq!(body["rows_updated"], catalog_size);
+
+    assert_eq!(gpu_base_repository.count().await.unwrap(), catalog_size as i64 + 1);
+    let rtx_3090 = gpu_base_repository.find_by_id(existing.id.unwrap()).await.unwrap().unwrap();
+    assert_eq!(rtx_3090.name, "GeForce RTX 3090");
+    assert_eq!(rtx_3090.brand.as_deref(), Some("nvidia"));
+    assert_eq!(rtx_3090.architecture.as_deref(), Some("Ampere"));
+    assert!(gpu_base_repository.find_by_id(custom.id.unwrap()).await.unwrap().is_some());
+    assert_eq!(GpuSpecRepository::new(app_state.db.clone()).count().await.unwrap(), catalog_size as i64);
+}
@@ -0,0 +1,143 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    routing::{get, post},
+    Router,
+};
+use serde_json::Value;
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}},
+    handlers::gpu_dedup::{apply_gpu_dedup, preview_gpu_dedup},
+    models::{audit_log::AUDIT_ACTION_GPU_DEDUP, gpu::Gpu, gpu_map::GpuMap},
+    repositories::{
+        audit_log_repository::AuditLogRepository,
+        gpu_map_repository::GpuMapRepository,
+        gpu_repository::GpuRepository,
+        traits::Repository,
+    },
+};
+
+async fn create_test_app_state() -> AppState {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    AppState {
+        db: db_pool,
+        settings: Settings::default(),
+    }
+}
+
+fn create_app(app_state: AppState) -> Router {
+    Router::new()
+        .route("/api/admin/gpu-dedup/preview", get(preview_gpu_dedup))
+        .route("/api/admin/gpu-dedup/apply", post(apply_gpu_dedup))
+        .with_state(app_state)
+}
+
+async fn send(app: &Router, method: &str, uri: &str) -> (StatusCode, Value) {
+    let request = Request::builder().method(method).uri(uri).body(Body::empty()).unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+async fn create_gpu(app_state: &AppState, device: &str) {
+    GpuRepository::new(app_state.db.clone())
+        .create(Gpu {
+            id: None,
+            run_id: None,
+            device: Some(device.to_string()),
+            driver: None,
+            gpu_chip: None,
+            brand: None,
+            is_laptop: None,
+            is_integrated: None,
+            is_cloud: None,
+            gpu_index: 0,
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap();
+}
+
+async fn create_gpu_map(app_state: &AppState, gpu_name: &str) {
+    GpuMapRepository::new(app_state.db.clone())
+        .create(GpuMap {
+            id: None,
+            gpu_name: Some(gpu_name.to_string()),
+            base_gpu_id: None,
+            version: 1,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_gpu_dedup_preview_and_apply() {
+    let app_state = create_test_app_state().await;
+    for _ in 0..3 {
+        create_gpu(&app_state, "NVIDIA GeForce RTX 3080").await;
+    }
+    create_gpu(&app_state, "NVIDIA GeForce RTX 3080 ").await;
+    create_gpu(&app_state, "nvidia geforce rtx 3080").await;
+    create_gpu(&app_state, "NVIDIA GeForce RTX 4090").await;
+    create_gpu(&app_state, "Intel(R) Arc(TM) A770 Graphics").await;
+    create_gpu_map(&app_state, "Intel(R) Arc(TM) A770 Graphics").await;
+    let app = create_app(app_state.clone());
+
+    let (status, body) = send(&app, "GET", "/api/admin/gpu-dedup/preview").await;
+    assert_eq!(status, StatusCode::OK);
+    let clusters = body["data"].as_array().unwrap();
+    assert_eq!(clusters.len(), 2);
+    assert_eq!(clusters[0]["canonical"], "Intel Arc A770 Graphics");
+    assert_eq!(clusters[1]["canonical"], "NVIDIA GeForce RTX 3080");
+    assert_eq!(clusters[1]["rows_to_update"], 2);
+
+    let (status, body) = send(&app, "POST", "/api/admin/gpu-dedup/apply").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["clusters"], 2);
+    assert_eq!(body["data"]["gpu_rows_updated"], 3);
+    assert_eq!(body["data"]["gpu_map_rows_updated"], 1);
+
+    let devices = GpuRepository::new(app_state.db.clone()).find_device_counts().await.unwrap();
+    let devices: Vec<(&str, i64)> = devices.iter().map(|device| (device.device.as_str(), device.row_count)).collect();
+    assert_eq!(
+        devices,
+        vec![("Intel Arc A770 Graphics", 1), ("NVIDIA GeForce RTX 3080", 5), ("NVIDIA GeForce RTX 4090", 1)]
+    );
+    let gpu_map = GpuMapRepository::new(app_state.db.clone())
+        .find_by_gpu_name("Intel Arc A770 Graphics")
+        .await
+        .unwrap();
+    assert_eq!(gpu_map.len(), 1);
+    assert_eq!(gpu_map[0].version, 2);
+
+    let audit = AuditLogRepository::new(app_state.db.clone())
+        .find_by_action(AUDIT_ACTION_GPU_DEDUP)
+        .await
+        .unwrap();
+    assert_eq!(audit.len(), 1);
+    assert_eq!(Some(audit[0].id), body["data"]["audit_id"].as_i64());
+    let details: Value = serde_json::from_str(audit[0].details.as_deref().unwrap()).unwrap();
+    assert_eq!(details.as_array().unwrap().len(), 2);
+
+    // Nothing is left to merge, and no further audit entry is written
+    let (_, body) = send(&app, "GET", "/api/admin/gpu-dedup/preview").await;
+    assert!(body["data"].as_array().unwrap().is_empty());
+    let (status, body) = send(&app, "POST", "/api/admin/gpu-dedup/apply").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["audit_id"], Value::Null);
+}
@@ -0,0 +1,152 @@
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use chrono::{Duration, Utc};
+use serde_json::Value;
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}},
+    error::i18n::ErrorCatalog,
+    models::{dataset_meta::META_SOURCE_UPLOADED_AT, gpu::Gpu, performance_result::PerformanceResult, runs::Run},
+    repositories::{
+        dataset_meta_repository::DatasetMetaRepository,
+        gpu_repository::GpuRepository,
+        performance_result_repository::PerformanceResultRepository,
+        runs_repository::RunsRepository,
+        traits::Repository,
+    },
+    router::create_router,
+};
+
+async fn create_test_app_state() -> AppState {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    AppState {
+        db: db_pool,
+        settings: Settings::default(),
+    }
+}
+
+/// Insert a processed single-GPU run on a device with the given avg_its
+async fn create_run(app_state: &AppState, device: &str, avg_its: f64) {
+    let run_id = RunsRepository::new(app_state.db.clone())
+        .create(Run {
+            id: None,
+            timestamp: Some("2024-01-01T00:00:00Z".to_string()),
+            vram_usage: None,
+            info: None,
+            system_info: None,
+            model_info: None,
+            device_info: None,
+            xformers: None,
+            model_name: None,
+            user: None,
+            notes: None,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap()
+        .id;
+
+    GpuRepository::new(app_state.db.clone())
+        .create(Gpu {
+            id: None,
+            run_id,
+            device: Some(device.to_string()),
+            driver: None,
+            gpu_chip: None,
+            brand: None,
+            is_laptop: None,
+            is_integrated: None,
+            is_cloud: None,
+            gpu_index: 0,
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap();
+
+    PerformanceResultRepository::new(app_state.db.clone())
+        .create(PerformanceResult { id: None, run_id, its: None, avg_its: Some(avg_its), vram_usage_kind: None, created_at: None, updated_at: None })
+        .await
+        .unwrap();
+}
+
+async fn send(app_state: &AppState, method: &str, uri: &str) -> (StatusCode, Value) {
+    let app = create_router(app_state.clone(), Arc::new(ErrorCatalog::bundled().unwrap()));
+    let request = Request::builder().method(method).uri(uri).body(Body::empty()).unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+#[tokio::test]
+async fn test_gpu_percentiles_computed_from_runs_until_refreshed() {
+    let app_state = create_test_app_state().await;
+    for avg_its in [10.0, 20.0, 30.0] {
+        create_run(&app_state, "NVIDIA GeForce RTX 4090", avg_its).await;
+    }
+    create_run(&app_state, "NVIDIA GeForce RTX 3060", 8.0).await;
+
+    let (status, body) = send(&app_state, "GET", "/api/stats/gpu-percentiles").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["cached"], false);
+    assert_eq!(body["data"]["stale"], false);
+    let gpus = body["data"]["gpus"].as_array().unwrap();
+    assert_eq!(gpus.len(), 2);
+    assert_eq!(gpus[0]["gpu"], "NVIDIA GeForce RTX 4090");
+    assert_eq!(gpus[0]["run_count"], 3);
+    assert_eq!(gpus[0]["median_its"], 20.0);
+    assert_eq!(gpus[0]["p90_its"], 28.0);
+}
+
+#[tokio::test]
+async fn test_refresh_percentiles_serves_the_cache_until_data_changes() {
+    let app_state = create_test_app_state().await;
+    create_run(&app_state, "NVIDIA GeForce RTX 4090", 20.0).await;
+
+    let (status, body) = send(&app_state, "POST", "/api/admin/refresh-percentiles").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["rows_processed"], 1);
+    assert_eq!(body["rows_inserted"], 1);
+
+    // Runs added after the refresh are not in the cache
+    create_run(&app_state, "NVIDIA GeForce RTX 3060", 8.0).await;
+    let (status, body) = send(&app_state, "GET", "/api/stats/gpu-percentiles?gpu=nvidia%20geforce%20rtx%204090").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["cached"], true);
+    assert_eq!(body["data"]["stale"], false);
+    assert!(body["data"]["computed_at"].is_string());
+    assert_eq!(body["data"]["gpus"].as_array().unwrap().len(), 1);
+
+    let (status, _) = send(&app_state, "GET", "/api/stats/gpu-percentiles?gpu=NVIDIA%20GeForce%20RTX%203060").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // An upload after the refresh marks the cache stale
+    let uploaded_at = (Utc::now() + Duration::seconds(1)).to_rfc3339();
+    DatasetMetaRepository::new(app_state.db.clone())
+        .set(META_SOURCE_UPLOADED_AT, Some(&uploaded_at), &uploaded_at)
+        .await
+        .unwrap();
+    let (_, body) = send(&app_state, "GET", "/api/stats/gpu-percentiles").await;
+    assert_eq!(body["data"]["stale"], true);
+
+    let (status, _) = send(&app_state, "POST", "/api/admin/refresh-percentiles").await;
+    assert_eq!(status, StatusCode::OK);
+    let (_, body) = send(&app_state, "GET", "/api/stats/gpu-percentiles").await;
+    assert_eq!(body["data"]["gpus"].as_array().unwrap().len(), 2);
+}
@@ -0,0 +1,163 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    routing::get,
+    Router,
+};
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+mod common;
+
+use common::create_run_on_device;
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}},
+    handlers::{gpu_prices, stats},
+    models::{gpu_base::GpuBase, gpu_map::GpuMap},
+    repositories::{
+        gpu_base_repository::GpuBaseRepository,
+        gpu_map_repository::GpuMapRepository,
+        traits::Repository,
+    },
+};
+
+async fn create_test_app_state() -> AppState {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    AppState {
+        db: db_pool,
+        settings: Settings::default(),
+    }
+}
+
+fn create_app(app_state: AppState) -> Router {
+    Router::new()
+        .route("/api/gpu-prices", get(gpu_prices::list_gpu_prices).post(gpu_prices::create_gpu_price))
+        .route("/api/gpu-prices/{id}", get(gpu_prices::get_gpu_price).put(gpu_prices::update_gpu_price).delete(gpu_prices::delete_gpu_price))
+        .route("/api/stats/its-per-dollar", get(stats::its_per_dollar))
+        .with_state(app_state)
+}
+
+async fn send(app: &Router, method: &str, uri: &str, body: Option<Value>) -> (StatusCode, Value) {
+    let mut builder = Request::builder().method(method).uri(uri);
+    let body = match body {
+        Some(json) => {
+            builder = builder.header("content-type", "application/json");
+            Body::from(json.to_string())
+        }
+        None => Body::empty(),
+    };
+
+    let response = app.clone().oneshot(builder.body(body).unwrap()).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let json = serde_json::from_slice(&bytes).unwrap_or(Value::Null);
+    (status, json)
+}
+
+async fn create_base_gpu(app_state: &AppState, name: &str) -> i64 {
+    GpuBaseRepository::new(app_state.db.clone())
+        .create(GpuBase { id: None, name: name.to_string(), brand: Some("nvidia".to_string()), architecture: None, generation: None, version: 1, created_at: None, updated_at: None })
+        .await
+        .unwrap()
+        .id
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_gpu_price_crud() {
+    let app_state = create_test_app_state().await;
+    let base_gpu_id = create_base_gpu(&app_state, "RTX 4090").await;
+    let app = create_app(app_state);
+
+    let (status, created) = send(&app, "POST", "/api/gpu-prices", Some(json!({
+        "base_gpu_id": base_gpu_id,
+        "price_usd": 1599.0,
+        "source": "MSRP"
+    }))).await;
+    assert_eq!(status, StatusCode::OK);
+    let id = created["data"]["id"].as_i64().unwrap();
+
+    let (status, duplicate) = send(&app, "POST", "/api/gpu-prices", Some(json!({
+        "base_gpu_id": base_gpu_id,
+        "price_usd": 1499.0
+    }))).await;
+    assert_eq!(status, StatusCode::CONFLICT, "Duplicate price for a base GPU should be rejected");
+    // The same answer a create racing past the check gets from the unique constraint
+    assert_eq!(duplicate["error"]["code"], "CONSTRAINT_VIOLATION");
+
+    let (status, updated) = send(&app, "PUT", &format!("/api/gpu-prices/{}", id), Some(json!({
+        "base_gpu_id": base_gpu_id,
+        "price_usd": 1799.0
+    }))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(updated["data"]["price_usd"], 1799.0);
+
+    let (status, list) = send(&app, "GET", "/api/gpu-prices", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(list["data"].as_array().unwrap().len(), 1);
+
+    let (status, _) = send(&app, "DELETE", &format!("/api/gpu-prices/{}", id), None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _) = send(&app, "GET", &format!("/api/gpu-prices/{}", id), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_gpu_price_validation() {
+    let app_state = create_test_app_state().await;
+    let base_gpu_id = create_base_gpu(&app_state, "RTX 3060").await;
+    let app = create_app(app_state);
+
+    let (status, _) = send(&app, "POST", "/api/gpu-prices", Some(json!({
+        "base_gpu_id": base_gpu_id,
+        "price_usd": -5.0
+    }))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, body) = send(&app, "POST", "/api/gpu-prices", Some(json!({
+        "base_gpu_id": 9999,
+        "price_usd": 300.0
+    }))).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["error"]["code"], "MISSING_REFERENCE");
+    assert_eq!(body["error"]["field"], "base_gpu_id");
+    assert_eq!(body["error"]["reason"], "base GPU not found");
+}
+
+#[tokio::test]
+async fn test_its_per_dollar_stats() {
+    let app_state = create_test_app_state().await;
+    let fast_id = create_base_gpu(&app_state, "RTX 4090").await;
+    let cheap_id = create_base_gpu(&app_state, "RTX 3060").await;
+
+    let gpu_map_repo = GpuMapRepository::new(app_state.db.clone());
+    gpu_map_repo.create(GpuMap { id: None, gpu_name: Some("NVIDIA GeForce RTX 4090".to_string()), base_gpu_id: Some(fast_id), version: 1, created_at: None, updated_at: None }).await.unwrap();
+    gpu_map_repo.create(GpuMap { id: None, gpu_name: Some("NVIDIA GeForce RTX 3060".to_string()), base_gpu_id: Some(cheap_id), version: 1, created_at: None, updated_at: None }).await.unwrap();
+
+    create_run_on_device(&app_state, "NVIDIA GeForce RTX 4090", 30.0).await;
+    create_run_on_device(&app_state, "NVIDIA GeForce RTX 4090", 34.0).await;
+    create_run_on_device(&app_state, "NVIDIA GeForce RTX 3060", 10.0).await;
+
+    let app = create_app(app_state);
+    send(&app, "POST", "/api/gpu-prices", Some(json!({ "base_gpu_id": fast_id, "price_usd": 1600.0 }))).await;
+    send(&app, "POST", "/api/gpu-prices", Some(json!({ "base_gpu_id": cheap_id, "price_usd": 300.0 }))).await;
+
+    let (status, stats) = send(&app, "GET", "/api/stats/its-per-dollar", None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let rows = stats["data"].as_array().unwrap();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0]["gpu_name"], "RTX 3060", "Cheaper GPU should offer better value");
+    assert_eq!(rows[1]["sample_count"], 2);
+    assert_eq!(rows[1]["avg_its"], 32.0);
+    assert_eq!(rows[1]["its_per_dollar"], 0.02);
+}
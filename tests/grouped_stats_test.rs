@@ -0,0 +1,216 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    routing::get,
+    Router,
+};
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}},
+    handlers::{meta::facets, stats::{grouped_averages, grouped_counts}},
+    models::{gpu::Gpu, performance_result::PerformanceResult, runs::Run, system_info::SystemInfo},
+    repositories::{
+        gpu_repository::GpuRepository,
+        performance_result_repository::PerformanceResultRepository,
+        runs_repository::RunsRepository,
+        system_info_repository::SystemInfoRepository,
+        traits::Repository,
+    },
+};
+
+async fn create_test_app_state() -> AppState {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    AppState {
+        db: db_pool,
+        settings: Settings::default(),
+    }
+}
+
+fn create_app(app_state: AppState) -> Router {
+    Router::new()
+        .route("/api/stats/counts", get(grouped_counts))
+        .route("/api/stats/averages", get(grouped_averages))
+        .route("/api/meta/facets", get(facets))
+        .with_state(app_state)
+}
+
+async fn send(app: &Router, uri: &str) -> (StatusCode, Value) {
+    let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+/// Insert a run on the given GPU brands with an OS and, optionally, avg_its
+async fn create_run(app_state: &AppState, brands: &[&str], system: &str, avg_its: Option<f64>) {
+    let run_id = RunsRepository::new(app_state.db.clone())
+        .create(Run {
+            id: None,
+            timestamp: Some("2024-01-01T00:00:00Z".to_string()),
+            vram_usage: None,
+            info: None,
+            system_info: None,
+            model_info: None,
+            device_info: None,
+            xformers: None,
+            model_name: None,
+            user: None,
+            notes: None,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap()
+        .id;
+
+    SystemInfoRepository::new(app_state.db.clone())
+        .create(SystemInfo {
+            id: None,
+            run_id,
+            arch: None,
+            cpu: None,
+            system: Some(system.to_string()),
+            release: None,
+            python: None,
+            ram_gb: None,
+            swap_gb: None,
+            os_bits: None,
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap();
+
+    for (gpu_index, brand) in brands.iter().enumerate() {
+        GpuRepository::new(app_state.db.clone())
+            .create(Gpu {
+                id: None,
+                run_id,
+                device: None,
+                driver: None,
+                gpu_chip: None,
+                brand: Some(brand.to_string()),
+                is_laptop: None,
+                is_integrated: None,
+                is_cloud: None,
+                gpu_index: gpu_index as i64,
+                parser_version: None,
+                created_at: None,
+                updated_at: None,
+            })
+            .await
+            .unwrap();
+    }
+
+    if let Some(avg_its) = avg_its {
+        PerformanceResultRepository::new(app_state.db.clone())
+            .create(PerformanceResult { id: None, run_id, its: None, avg_its: Some(avg_its), vram_usage_kind: None, created_at: None, updated_at: None })
+            .await
+            .unwrap();
+    }
+}
+
+#[tokio::test]
+async fn test_grouped_counts_and_averages() {
+    let app_state = create_test_app_state().await;
+    // A multi-GPU run counts once, under its first GPU
+    create_run(&app_state, &["nvidia", "amd"], "Linux", Some(10.0)).await;
+    create_run(&app_state, &["nvidia"], "Windows", Some(20.0)).await;
+    create_run(&app_state, &["amd"], "Linux", Some(6.0)).await;
+    create_run(&app_state, &["amd"], "Linux", None).await;
+    let app = create_app(app_state);
+
+    let (status, body) = send(&app, "/api/stats/counts?by=gpu_brand").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        body["data"],
+        json!([{"value": "amd", "run_count": 2}, {"value": "nvidia", "run_count": 2}])
+    );
+
+    let (_, body) = send(&app, "/api/stats/counts?by=gpu_brand&filter_by=os&filter_value=Linux").await;
+    assert_eq!(
+        body["data"],
+        json!([{"value": "amd", "run_count": 2}, {"value": "nvidia", "run_count": 1}])
+    );
+
+    // Runs without avg_its are left out of the averages
+    let (status, body) = send(&app, "/api/stats/averages?by=gpu_brand").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        body["data"],
+        json!([
+            {"value": "nvidia", "run_count": 2, "average": 15.0},
+            {"value": "amd", "run_count": 1, "average": 6.0}
+        ])
+    );
+}
+
+#[tokio::test]
+async fn test_grouped_stats_reject_unknown_columns() {
+    let app = create_app(create_test_app_state().await);
+
+    for uri in [
+        "/api/stats/counts",
+        "/api/stats/counts?by=user",
+        "/api/stats/counts?by=gpu_brand%3BDROP%20TABLE%20runs",
+        "/api/stats/counts?by=os&filter_by=notes&filter_value=x",
+        "/api/stats/counts?by=os&filter_by=gpu_brand",
+        "/api/stats/averages?by=os&metric=vram_usage",
+    ] {
+        let (status, _) = send(&app, uri).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{}", uri);
+    }
+}
+
+#[tokio::test]
+async fn test_facets_cap_values_and_bucket_the_rest() {
+    let app_state = create_test_app_state().await;
+    create_run(&app_state, &["nvidia"], "Linux", Some(10.0)).await;
+    create_run(&app_state, &["nvidia"], "Windows", Some(20.0)).await;
+    create_run(&app_state, &["amd"], "Linux", Some(6.0)).await;
+    create_run(&app_state, &["intel"], "Linux", None).await;
+    let app = create_app(app_state);
+
+    let (status, body) = send(&app, "/api/meta/facets?fields=gpu.brand,system_info.system&limit=1").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        body["data"],
+        json!([
+            {
+                "field": "gpu.brand",
+                "distinct_values": 3,
+                "values": [{"value": "nvidia", "run_count": 2}],
+                "other_values": 2,
+                "other_run_count": 2
+            },
+            {
+                "field": "system_info.system",
+                "distinct_values": 2,
+                "values": [{"value": "Linux", "run_count": 3}],
+                "other_values": 1,
+                "other_run_count": 1
+            }
+        ])
+    );
+
+    for uri in [
+        "/api/meta/facets",
+        "/api/meta/facets?fields=runs.user",
+        "/api/meta/facets?fields=gpu.brand&limit=0",
+    ] {
+        let (status, _) = send(&app, uri).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{}", uri);
+    }
+}
@@ -0,0 +1,98 @@
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    Router,
+};
+use serde_json::Value;
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}},
+    error::i18n::ErrorCatalog,
+    repositories::health_sample_repository::HealthSampleRepository,
+    router::create_router,
+    services::health_history::HealthSampler,
+};
+
+async fn create_test_app_state() -> AppState {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    AppState {
+        db: db_pool,
+        settings: Settings::default(),
+    }
+}
+
+async fn fetch(app: &Router, uri: &str) -> (StatusCode, Value) {
+    let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+#[tokio::test]
+async fn test_health_samples_are_served_oldest_first() {
+    let app_state = create_test_app_state().await;
+    let app = create_router(app_state.clone(), Arc::new(ErrorCatalog::bundled().unwrap()));
+
+    let mut sampler = HealthSampler::new(app_state.db.clone());
+    let first = sampler.record().await.unwrap();
+    // Requests answered between samples are counted in the next one
+    fetch(&app, "/health").await;
+    fetch(&app, "/health").await;
+    let second = sampler.record().await.unwrap();
+
+    assert!(first.db_size_bytes > 0);
+    assert_eq!(first.row_counts["runs"], 0);
+    assert_eq!(first.pool_max_connections, 1);
+    // Other tests in this binary may answer requests concurrently
+    assert!(second.requests >= 2, "{:?}", second);
+    assert!(second.requests_per_minute > 0.0);
+
+    let (status, body) = fetch(&app, "/api/admin/health-history").await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    let samples = body["data"].as_array().unwrap();
+    assert_eq!(samples.len(), 2);
+    assert_eq!(samples[0]["id"], first.id.unwrap());
+    assert_eq!(samples[1]["requests"], second.requests);
+    assert_eq!(samples[1]["row_counts"]["GPU"], 0);
+
+    let (_, body) = fetch(&app, "/api/admin/health-history?limit=1").await;
+    assert_eq!(body["data"][0]["id"], second.id.unwrap());
+
+    let uri = format!("/api/admin/health-history?since={}", urlencoding(&second.sampled_at));
+    let (_, body) = fetch(&app, &uri).await;
+    assert_eq!(body["data"].as_array().unwrap().len(), 1);
+
+    let (status, body) = fetch(&app, "/api/admin/health-history?since=yesterday").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"]["params"][0]["field"], "since");
+}
+
+#[tokio::test]
+async fn test_old_health_samples_are_pruned() {
+    let app_state = create_test_app_state().await;
+    let mut sampler = HealthSampler::new(app_state.db.clone());
+    let mut old = sampler.record().await.unwrap();
+    old.sampled_at = "2020-01-01T00:00:00+00:00".to_string();
+    let repository = HealthSampleRepository::new(app_state.db.clone());
+    repository.create(&old).await.unwrap();
+
+    assert_eq!(sampler.prune(30).await.unwrap(), 1);
+    assert_eq!(repository.find_recent(None, 10).await.unwrap().len(), 1);
+}
+
+/// RFC 3339 offsets start with `+`, which means a space in a query string
+fn urlencoding(value: &str) -> String {
+    value.replace('+', "%2B").replace(':', "%3A")
+}
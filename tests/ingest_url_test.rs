@@ -0,0 +1,155 @@
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    response::IntoResponse,
+    routing::get,
+    Router,
+};
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}},
+    error::i18n::ErrorCatalog,
+    repositories::{runs_repository::RunsRepository, traits::Repository},
+    router::create_router,
+};
+
+async fn create_test_app_state() -> AppState {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    let mut settings = Settings::default();
+    settings.remote_ingest.allowed_hosts = vec!["127.0.0.1".to_string()];
+
+    AppState {
+        db: db_pool,
+        settings,
+    }
+}
+
+fn benchmark_export() -> String {
+    json!([
+        {
+            "timestamp": "2024-01-01T10:00:00Z",
+            "vram_usage": "10.0/11.0",
+            "info": "app:test",
+            "system_info": "system:Linux",
+            "model_info": "torch:2.1",
+            "device_info": "device:NVIDIA GeForce RTX 4090",
+            "xformers": "True",
+            "model_name": "sdxl",
+            "user": "tester",
+            "notes": ""
+        }
+    ])
+    .to_string()
+}
+
+/// A file server on a local port, returning its port
+///
+/// `/redirect` points at the same server under the host name `localhost`.
+async fn start_file_server() -> u16 {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let app = Router::new()
+        .route("/data/benchmark.json", get(|| async { benchmark_export() }))
+        .route(
+            "/redirect",
+            get(move || async move {
+                (
+                    StatusCode::FOUND,
+                    [(header::LOCATION, format!("http://localhost:{}/data/benchmark.json", port))],
+                )
+                    .into_response()
+            }),
+        );
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    port
+}
+
+async fn ingest_url(app_state: &AppState, body: Value) -> (StatusCode, Value) {
+    let app = create_router(app_state.clone(), Arc::new(ErrorCatalog::bundled().unwrap()));
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/ingest-url")
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+#[tokio::test]
+async fn test_ingest_url_downloads_and_ingests() {
+    let port = start_file_server().await;
+    let app_state = create_test_app_state().await;
+    let url = format!("http://127.0.0.1:{}/data/benchmark.json", port);
+
+    let (status, body) = ingest_url(&app_state, json!({ "url": url })).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["file_name"], "benchmark.json");
+    assert_eq!(body["rows_inserted"], 1);
+
+    // Appending keeps the runs already there
+    let (status, _) = ingest_url(&app_state, json!({ "url": url, "append": true })).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(RunsRepository::new(app_state.db.clone()).count().await.unwrap(), 2);
+}
+
+#[tokio::test]
+async fn test_ingest_url_refuses_hosts_off_the_allowlist() {
+    let port = start_file_server().await;
+    let app_state = create_test_app_state().await;
+
+    let (status, _) = ingest_url(&app_state, json!({ "url": format!("http://localhost:{}/data/benchmark.json", port) })).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = ingest_url(&app_state, json!({ "url": "file:///etc/passwd" })).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // Redirects to other hosts are not followed
+    let (status, _) = ingest_url(&app_state, json!({ "url": format!("http://127.0.0.1:{}/redirect", port) })).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    assert_eq!(RunsRepository::new(app_state.db.clone()).count().await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn test_ingest_url_appends_under_the_tenant_and_never_replaces_while_tenancy_is_enabled() {
+    let port = start_file_server().await;
+    let mut app_state = create_test_app_state().await;
+    app_state.settings.tenancy.enabled = true;
+    app_state.settings.tenancy.api_keys.insert("key-a".to_string(), "community-a".to_string());
+    let app = create_router(app_state.clone(), Arc::new(ErrorCatalog::bundled().unwrap()));
+    let url = format!("http://127.0.0.1:{}/data/benchmark.json", port);
+    let send = |body: Value| {
+        let request = Request::builder()
+            .method("POST")
+            .uri("/api/ingest-url")
+            .header("content-type", "application/json")
+            .header("x-api-key", "key-a")
+            .body(Body::from(body.to_string()))
+            .unwrap();
+        app.clone().oneshot(request)
+    };
+
+    let response = send(json!({ "url": url })).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = send(json!({ "url": url, "append": true })).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let tenants: Vec<String> = sqlx::query_scalar("SELECT tenant_id FROM runs").fetch_all(&app_state.db).await.unwrap();
+    assert_eq!(tenants, ["community-a"]);
+}
@@ -0,0 +1,131 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    routing::{get, post},
+    Router,
+};
+use serde_json::Value;
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}},
+    handlers::{admin::update_gpu_integrated_info, stats::leaderboard},
+    models::{gpu::Gpu, performance_result::PerformanceResult, runs::Run},
+    repositories::{
+        gpu_repository::GpuRepository,
+        performance_result_repository::PerformanceResultRepository,
+        runs_repository::RunsRepository,
+        traits::Repository,
+    },
+};
+
+async fn create_test_app_state() -> AppState {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    AppState {
+        db: db_pool,
+        settings: Settings::default(),
+    }
+}
+
+fn create_app(app_state: AppState) -> Router {
+    Router::new()
+        .route("/api/update-gpu-integrated-info", post(update_gpu_integrated_info))
+        .route("/api/leaderboard", get(leaderboard))
+        .with_state(app_state)
+}
+
+async fn send(app: &Router, method: &str, uri: &str) -> (StatusCode, Value) {
+    let request = Request::builder().method(method).uri(uri).body(Body::empty()).unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+/// A run on one GPU with the given avg_its
+async fn create_run(app_state: &AppState, device: &str, avg_its: f64) -> i64 {
+    let run_id = RunsRepository::new(app_state.db.clone())
+        .create(Run {
+            id: None,
+            timestamp: Some("2024-01-01T00:00:00Z".to_string()),
+            vram_usage: None,
+            info: None,
+            system_info: None,
+            model_info: None,
+            device_info: None,
+            xformers: None,
+            model_name: None,
+            user: Some("tester".to_string()),
+            notes: None,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap()
+        .id;
+
+    GpuRepository::new(app_state.db.clone())
+        .create(Gpu {
+            id: None,
+            run_id,
+            device: Some(device.to_string()),
+            driver: None,
+            gpu_chip: None,
+            brand: None,
+            is_laptop: None,
+            is_integrated: None,
+            is_cloud: None,
+            gpu_index: 0,
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap();
+
+    PerformanceResultRepository::new(app_state.db.clone())
+        .create(PerformanceResult { id: None, run_id, its: None, avg_its: Some(avg_its), vram_usage_kind: None, created_at: None, updated_at: None })
+        .await
+        .unwrap();
+
+    run_id.unwrap()
+}
+
+#[tokio::test]
+async fn test_integrated_gpus_are_flagged_and_left_out_of_the_leaderboard() {
+    let app_state = create_test_app_state().await;
+    let discrete = create_run(&app_state, "NVIDIA GeForce RTX 3080", 20.0).await;
+    let integrated = create_run(&app_state, "Intel(R) UHD Graphics 620", 0.5).await;
+    create_run(&app_state, "AMD Radeon RX Vega 64", 8.0).await;
+    let app = create_app(app_state.clone());
+
+    let (status, body) = send(&app, "POST", "/api/update-gpu-integrated-info").await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["total_rows"], 3);
+    assert_eq!(body["integrated_rows"], 1);
+
+    let gpus = GpuRepository::new(app_state.db.clone()).find_by_run_id(integrated).await.unwrap();
+    assert_eq!(gpus[0].is_integrated, Some(true));
+    let gpus = GpuRepository::new(app_state.db.clone()).find_by_run_id(discrete).await.unwrap();
+    assert_eq!(gpus[0].is_integrated, Some(false));
+
+    let (status, body) = send(&app, "GET", "/api/leaderboard?metric=avg_its").await;
+    assert_eq!(status, StatusCode::OK);
+    let rows = body["data"].as_array().unwrap();
+    assert_eq!(rows.len(), 2);
+    assert!(rows.iter().all(|row| row["run_id"] != integrated));
+
+    let (status, body) = send(&app, "GET", "/api/leaderboard?metric=avg_its&include_integrated=true").await;
+    assert_eq!(status, StatusCode::OK);
+    let rows = body["data"].as_array().unwrap();
+    assert_eq!(rows.len(), 3);
+    assert_eq!(rows[2]["run_id"], integrated);
+}
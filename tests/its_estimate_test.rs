@@ -0,0 +1,242 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    routing::post,
+    Router,
+};
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}},
+    handlers::estimate::estimate_its,
+    models::{
+        gpu::Gpu, gpu_base::GpuBase, gpu_map::GpuMap, libraries::Libraries, model_map::ModelMap,
+        performance_result::PerformanceResult, run_more_details::RunMoreDetails, runs::Run,
+    },
+    repositories::{
+        gpu_base_repository::GpuBaseRepository,
+        gpu_map_repository::GpuMapRepository,
+        gpu_repository::GpuRepository,
+        libraries_repository::LibrariesRepository,
+        model_map_repository::ModelMapRepository,
+        performance_result_repository::PerformanceResultRepository,
+        run_more_details_repository::RunMoreDetailsRepository,
+        runs_repository::RunsRepository,
+        traits::Repository,
+    },
+};
+
+async fn create_test_app_state() -> AppState {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    AppState {
+        db: db_pool,
+        settings: Settings::default(),
+    }
+}
+
+fn create_app(app_state: AppState) -> Router {
+    Router::new()
+        .route("/api/estimate", post(estimate_its))
+        .with_state(app_state)
+}
+
+async fn send(app: &Router, body: Value) -> (StatusCode, Value) {
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/estimate")
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+/// Create a base GPU of the given architecture and map the reported device names onto it
+async fn create_base_gpu(app_state: &AppState, name: &str, architecture: &str, devices: &[&str]) {
+    let base_gpu = GpuBaseRepository::new(app_state.db.clone())
+        .create(GpuBase {
+            id: None,
+            name: name.to_string(),
+            brand: Some("nvidia".to_string()),
+            architecture: Some(architecture.to_string()),
+            generation: None,
+            version: 1,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap();
+
+    for device in devices {
+        GpuMapRepository::new(app_state.db.clone())
+            .create(GpuMap { id: None, gpu_name: Some(device.to_string()), base_gpu_id: base_gpu.id, version: 1, created_at: None, updated_at: None })
+            .await
+            .unwrap();
+    }
+}
+
+/// Insert a processed run on a device with the given torch version, model map and avg_its
+async fn create_run(app_state: &AppState, device: &str, torch: &str, model_map_id: i64, avg_its: f64) {
+    let run_id = RunsRepository::new(app_state.db.clone())
+        .create(Run {
+            id: None,
+            timestamp: Some("2024-01-01T00:00:00Z".to_string()),
+            vram_usage: None,
+            info: None,
+            system_info: None,
+            model_info: None,
+            device_info: None,
+            xformers: None,
+            model_name: None,
+            user: None,
+            notes: None,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap()
+        .id;
+
+    GpuRepository::new(app_state.db.clone())
+        .create(Gpu {
+            id: None,
+            run_id,
+            device: Some(device.to_string()),
+            driver: None,
+            gpu_chip: None,
+            brand: None,
+            is_laptop: None,
+            is_integrated: None,
+            is_cloud: None,
+            gpu_index: 0,
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap();
+
+    LibrariesRepository::new(app_state.db.clone())
+        .create(Libraries {
+            id: None,
+            run_id,
+            torch: Some(torch.to_string()),
+            xformers: None,
+            xformers1: None,
+            diffusers: None,
+            transformers: None,
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap();
+
+    RunMoreDetailsRepository::new(app_state.db.clone())
+        .create(RunMoreDetails {
+            id: None,
+            run_id,
+            timestamp: None,
+            model_name: None,
+            user: None,
+            notes: None,
+            model_map_id: Some(model_map_id),
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap();
+
+    PerformanceResultRepository::new(app_state.db.clone())
+        .create(PerformanceResult { id: None, run_id, its: None, avg_its: Some(avg_its), vram_usage_kind: None, created_at: None, updated_at: None })
+        .await
+        .unwrap();
+}
+
+async fn seed(app_state: &AppState) {
+    let sdxl = ModelMapRepository::new(app_state.db.clone())
+        .create(ModelMap {
+            id: None,
+            model_name: Some("sd_xl_base_1.0".to_string()),
+            base_model: Some("sdxl".to_string()),
+            version: 1,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap()
+        .id
+        .unwrap();
+
+    create_base_gpu(app_state, "RTX 3060", "Ampere", &["NVIDIA GeForce RTX 3060", "NVIDIA GeForce RTX 3060 12GB"]).await;
+    create_base_gpu(app_state, "RTX 3070", "Ampere", &["NVIDIA GeForce RTX 3070"]).await;
+
+    create_run(app_state, "NVIDIA GeForce RTX 3060", "2.1.0+cu118", sdxl, 4.0).await;
+    create_run(app_state, "NVIDIA GeForce RTX 3060", "2.1.0+cu121", sdxl, 5.0).await;
+    create_run(app_state, "NVIDIA GeForce RTX 3060", "2.0.1", sdxl, 3.0).await;
+}
+
+#[tokio::test]
+async fn test_estimate_uses_exact_gpu_and_torch() {
+    let app_state = create_test_app_state().await;
+    seed(&app_state).await;
+    let app = create_app(app_state);
+
+    let (status, body) = send(&app, json!({ "gpu": "NVIDIA GeForce RTX 3060", "torch": "2.1.0", "model": "sd_xl_base_1.0" })).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let estimate = &body["data"];
+    assert_eq!(estimate["match_level"], "exact_gpu");
+    assert_eq!(estimate["torch_matched"], true);
+    assert_eq!(estimate["base_model"], "sdxl");
+    assert_eq!(estimate["sample_count"], 2);
+    assert_eq!(estimate["min_its"], 4.0);
+    assert_eq!(estimate["median_its"], 4.5);
+    assert_eq!(estimate["max_its"], 5.0);
+}
+
+#[tokio::test]
+async fn test_estimate_falls_back_to_base_gpu_then_architecture() {
+    let app_state = create_test_app_state().await;
+    seed(&app_state).await;
+    let app = create_app(app_state);
+
+    let (status, body) = send(&app, json!({ "gpu": "NVIDIA GeForce RTX 3060 12GB", "model": "sdxl" })).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["match_level"], "gpu_base");
+    assert_eq!(body["data"]["matched_on"], "RTX 3060");
+    assert_eq!(body["data"]["sample_count"], 3);
+
+    let (status, body) = send(&app, json!({ "gpu": "NVIDIA GeForce RTX 3070" })).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["match_level"], "architecture");
+    assert_eq!(body["data"]["matched_on"], "Ampere");
+    assert_eq!(body["data"]["torch_matched"], false);
+}
+
+#[tokio::test]
+async fn test_estimate_without_comparable_runs() {
+    let app_state = create_test_app_state().await;
+    seed(&app_state).await;
+    let app = create_app(app_state);
+
+    let (status, _) = send(&app, json!({ "gpu": "Imaginary GPU" })).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, _) = send(&app, json!({ "gpu": "NVIDIA GeForce RTX 3060", "model": "sd15" })).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, _) = send(&app, json!({ "gpu": "  " })).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,113 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    routing::get,
+    Router,
+};
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}},
+    handlers::stats::leaderboard,
+    models::{performance_result::PerformanceResult, runs::Run},
+    repositories::{
+        dataset_meta_repository::DatasetMetaRepository,
+        performance_result_repository::PerformanceResultRepository,
+        runs_repository::RunsRepository,
+        traits::Repository,
+    },
+};
+
+async fn create_test_app_state() -> AppState {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    AppState {
+        db: db_pool,
+        settings: Settings::default(),
+    }
+}
+
+fn create_app(app_state: AppState) -> Router {
+    Router::new()
+        .route("/api/leaderboard", get(leaderboard))
+        .with_state(app_state)
+}
+
+async fn fetch(app: &Router, uri: &str) -> (StatusCode, Value) {
+    let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+/// Insert a run, with an avg_its when given
+async fn insert_run(app_state: &AppState, avg_its: Option<f64>) {
+    let run_id = RunsRepository::new(app_state.db.clone())
+        .create(Run {
+            id: None,
+            timestamp: Some("2024-01-01T00:00:00Z".to_string()),
+            vram_usage: None,
+            info: None,
+            system_info: None,
+            model_info: None,
+            device_info: None,
+            xformers: None,
+            model_name: Some("sd-v1-5".to_string()),
+            user: Some("tester".to_string()),
+            notes: None,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap()
+        .id
+        .unwrap();
+
+    if let Some(avg_its) = avg_its {
+        PerformanceResultRepository::new(app_state.db.clone())
+            .create(PerformanceResult { id: None, run_id: Some(run_id), its: None, avg_its: Some(avg_its), vram_usage_kind: None, created_at: None, updated_at: None })
+            .await
+            .unwrap();
+    }
+}
+
+#[tokio::test]
+async fn test_leaderboard_meta_counts_matching_runs() {
+    let app_state = create_test_app_state().await;
+    for avg_its in [Some(10.0), Some(12.0), Some(14.0), None] {
+        insert_run(&app_state, avg_its).await;
+    }
+    let app = create_app(app_state.clone());
+
+    let (status, body) = fetch(&app, "/api/leaderboard?metric=avg_its&limit=2").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"].as_array().unwrap().len(), 2);
+    // The run without an avg_its is not ranked
+    assert_eq!(body["meta"], json!({ "count_mode": "exact", "total": 3, "limit": 2 }));
+
+    let (_, body) = fetch(&app, "/api/leaderboard?metric=avg_its&limit=2&count_mode=none").await;
+    assert_eq!(body["meta"], json!({ "count_mode": "none", "total": null, "limit": 2 }));
+
+    // Approximate counts fall back to exact ones until the tables are analyzed
+    let (_, body) = fetch(&app, "/api/leaderboard?metric=avg_its&count_mode=approximate").await;
+    assert_eq!(body["meta"]["count_mode"], "exact");
+    assert_eq!(body["meta"]["total"], 3);
+
+    // Then they are the runs table's row count, filters aside
+    DatasetMetaRepository::new(app_state.db.clone()).analyze().await.unwrap();
+    let (_, body) = fetch(&app, "/api/leaderboard?metric=avg_its&count_mode=approximate").await;
+    assert_eq!(body["meta"]["count_mode"], "approximate");
+    assert_eq!(body["meta"]["total"], 4);
+
+    let (status, _) = fetch(&app, "/api/leaderboard?count_mode=roughly").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,114 @@
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    Router,
+};
+use serde_json::Value;
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}, settings::Environment},
+    error::i18n::ErrorCatalog,
+    handlers::load_data::seed_demo_data,
+    repositories::{performance_result_repository::PerformanceResultRepository, runs_repository::RunsRepository, traits::Repository},
+    router::create_router,
+};
+
+async fn create_test_app_state() -> AppState {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    AppState {
+        db: db_pool,
+        settings: Settings::default(),
+    }
+}
+
+fn create_app(app_state: AppState) -> Router {
+    create_router(app_state, Arc::new(ErrorCatalog::bundled().unwrap()))
+}
+
+async fn post(app: &Router, uri: &str) -> (StatusCode, Option<String>, Vec<u8>) {
+    let request = Request::builder().method("POST").uri(uri).body(Body::empty()).unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let disposition = response
+        .headers()
+        .get(header::CONTENT_DISPOSITION)
+        .map(|value| value.to_str().unwrap().to_string());
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, disposition, bytes.to_vec())
+}
+
+#[tokio::test]
+async fn test_generate_data_downloads_a_reproducible_export() {
+    let app = create_app(create_test_app_state().await);
+
+    let (status, disposition, body) = post(&app, "/api/admin/generate-data?rows=30&seed=3").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(disposition.as_deref(), Some("attachment; filename=\"synthetic_30_3.json\""));
+    let runs: Vec<Value> = serde_json::from_slice(&body).unwrap();
+    assert_eq!(runs.len(), 30);
+    assert!(runs[0]["device_info"].as_str().unwrap().starts_with("device:"));
+
+    let (_, _, again) = post(&app, "/api/admin/generate-data?rows=30&seed=3").await;
+    assert_eq!(body, again);
+
+    let (status, _, _) = post(&app, "/api/admin/generate-data").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _, _) = post(&app, "/api/admin/generate-data?rows=0").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_generate_data_ingests_the_requested_rows() {
+    let app_state = create_test_app_state().await;
+    let app = create_app(app_state.clone());
+
+    let (status, disposition, body) = post(&app, "/api/admin/generate-data?rows=120&ingest=true").await;
+    let body: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert!(disposition.is_none());
+    assert_eq!(body["rows_inserted"], 120);
+    assert_eq!(body["rows_failed"], 0);
+    assert_eq!(RunsRepository::new(app_state.db.clone()).count().await.unwrap(), 120);
+}
+
+#[tokio::test]
+async fn test_generate_data_is_not_served_outside_development() {
+    let mut app_state = create_test_app_state().await;
+    app_state.settings.application.environment = Environment::Production;
+    let app = create_app(app_state);
+
+    let (status, _, _) = post(&app, "/api/admin/generate-data?rows=10").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_seed_demo_data_ingests_and_processes_sample_runs() {
+    // The demo profile's database: one in-memory connection for everything
+    let db_pool = create_pool(&DatabaseConfig::in_memory()).await.unwrap();
+    initialize_database(&db_pool).await.unwrap();
+    let mut app_state = AppState {
+        db: db_pool,
+        settings: Settings::default(),
+    };
+    app_state.settings.application.environment = Environment::Demo;
+    app_state.settings.demo.sample_rows = 40;
+
+    seed_demo_data(&app_state).await.unwrap();
+
+    assert_eq!(RunsRepository::new(app_state.db.clone()).count().await.unwrap(), 40);
+    assert_eq!(PerformanceResultRepository::new(app_state.db.clone()).count().await.unwrap(), 40);
+    // The demo profile is not development, so the generator is not mounted
+    let (status, _, _) = post(&create_app(app_state), "/api/admin/generate-data?rows=10").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
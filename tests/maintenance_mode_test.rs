@@ -0,0 +1,115 @@
+use axum::{
+    body::Body,
+    extract::State,
+    http::{Method, Request, StatusCode},
+    routing::get,
+    Router,
+};
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}},
+    error::types::AppError,
+    handlers::{
+        admin::enrich_commit_dates,
+        gpu_prices::{create_gpu_price, list_gpu_prices},
+        maintenance::{get_maintenance_mode, set_maintenance_mode},
+        upstream_sync::sync_upstream,
+    },
+    middleware::maintenance_mode::{ensure_not_in_maintenance, reject_writes_during_maintenance, MAINTENANCE_MODE_PATH},
+    models::audit_log::AUDIT_ACTION_MAINTENANCE_MODE,
+    repositories::{audit_log_repository::AuditLogRepository, dataset_meta_repository::DatasetMetaRepository},
+};
+
+async fn create_test_app_state() -> AppState {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    AppState {
+        db: db_pool,
+        settings: Settings::default(),
+    }
+}
+
+fn create_app(app_state: AppState) -> Router {
+    Router::new()
+        .route(MAINTENANCE_MODE_PATH, get(get_maintenance_mode).post(set_maintenance_mode))
+        .route("/api/gpu-prices", get(list_gpu_prices).post(create_gpu_price))
+        .layer(axum::middleware::from_fn_with_state(app_state.clone(), reject_writes_during_maintenance))
+        .with_state(app_state)
+}
+
+async fn send(app: &Router, method: Method, uri: &str, body: Option<Value>) -> (StatusCode, Value) {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("content-type", "application/json")
+        .body(body.map_or_else(Body::empty, |body| Body::from(body.to_string())))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+fn gpu_price() -> Option<Value> {
+    Some(json!({ "base_gpu_id": 1, "price_usd": 1599.0 }))
+}
+
+#[tokio::test]
+async fn test_maintenance_mode_blocks_writes_but_not_reads() {
+    let app_state = create_test_app_state().await;
+    let app = create_app(app_state.clone());
+
+    let (status, body) = send(&app, Method::GET, MAINTENANCE_MODE_PATH, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["enabled"], false);
+
+    let (status, body) = send(&app, Method::POST, MAINTENANCE_MODE_PATH, Some(json!({ "enabled": true }))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["enabled"], true);
+
+    let (status, body) = send(&app, Method::POST, "/api/gpu-prices", gpu_price()).await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(body["error"]["code"], "MAINTENANCE_MODE");
+
+    let (status, _) = send(&app, Method::GET, "/api/gpu-prices", None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // The flag is persisted, so a fresh router over the same database still sees it
+    let (_, body) = send(&create_app(app_state.clone()), Method::GET, MAINTENANCE_MODE_PATH, None).await;
+    assert_eq!(body["data"]["enabled"], true);
+
+    let (status, _) = send(&app, Method::POST, MAINTENANCE_MODE_PATH, Some(json!({ "enabled": false }))).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = send(&app, Method::POST, "/api/gpu-prices", gpu_price()).await;
+    assert_ne!(status, StatusCode::SERVICE_UNAVAILABLE);
+
+    let entries = AuditLogRepository::new(app_state.db.clone())
+        .find_by_action(AUDIT_ACTION_MAINTENANCE_MODE)
+        .await
+        .unwrap();
+    assert_eq!(entries.len(), 2);
+}
+
+#[tokio::test]
+async fn test_jobs_outside_requests_are_refused_during_maintenance() {
+    let mut app_state = create_test_app_state().await;
+    app_state.settings.upstream_sync.url = Some("https://example.com/benchmark.json".to_string());
+    let app = create_app(app_state.clone());
+    assert!(ensure_not_in_maintenance(&app_state.db).await.is_ok());
+
+    send(&app, Method::POST, MAINTENANCE_MODE_PATH, Some(json!({ "enabled": true }))).await;
+    assert!(DatasetMetaRepository::new(app_state.db.clone()).maintenance_mode().await.unwrap());
+
+    // Refused before anything is downloaded or looked up
+    assert!(matches!(sync_upstream(&app_state).await, Err(AppError::Maintenance(_))));
+    assert!(matches!(enrich_commit_dates(State(app_state.clone())).await, Err(AppError::Maintenance(_))));
+}
@@ -0,0 +1,193 @@
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    routing::{get, post},
+    Router,
+};
+use serde_json::Value;
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}},
+    handlers::{gpu_map, model_map},
+    models::{
+        gpu::Gpu, gpu_base::GpuBase, gpu_map::GpuMap, model_map::ModelMap,
+        run_more_details::RunMoreDetails,
+    },
+    repositories::{
+        gpu_base_repository::GpuBaseRepository,
+        gpu_map_repository::GpuMapRepository,
+        gpu_repository::GpuRepository,
+        model_map_repository::ModelMapRepository,
+        run_more_details_repository::RunMoreDetailsRepository,
+        traits::Repository,
+    },
+};
+
+async fn create_test_app_state() -> AppState {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    AppState {
+        db: db_pool,
+        settings: Settings::default(),
+    }
+}
+
+fn create_app(app_state: AppState) -> Router {
+    Router::new()
+        .route("/api/model-map/export", get(model_map::export_model_map))
+        .route("/api/model-map/import", post(model_map::import_model_map))
+        .route("/api/gpu-map/export", get(gpu_map::export_gpu_map))
+        .with_state(app_state)
+}
+
+async fn send(app: &Router, method: &str, uri: &str, body: String) -> (StatusCode, String, String) {
+    let request = Request::builder().method(method).uri(uri).body(Body::from(body)).unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let content_type = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, content_type, String::from_utf8(bytes.to_vec()).unwrap())
+}
+
+async fn create_model_map(app_state: &AppState, model_name: &str, base_model: &str) -> i64 {
+    ModelMapRepository::new(app_state.db.clone())
+        .create(ModelMap {
+            id: None,
+            model_name: Some(model_name.to_string()),
+            base_model: Some(base_model.to_string()),
+            version: 1,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap()
+        .id
+        .unwrap()
+}
+
+async fn create_run_details(app_state: &AppState, model_map_id: i64) {
+    RunMoreDetailsRepository::new(app_state.db.clone())
+        .create(RunMoreDetails {
+            id: None,
+            run_id: None,
+            timestamp: None,
+            model_name: None,
+            user: None,
+            notes: None,
+            model_map_id: Some(model_map_id),
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap();
+}
+
+async fn create_gpu(app_state: &AppState, device: &str) {
+    GpuRepository::new(app_state.db.clone())
+        .create(Gpu {
+            id: None,
+            run_id: None,
+            device: Some(device.to_string()),
+            driver: None,
+            gpu_chip: None,
+            brand: None,
+            is_laptop: None,
+            is_integrated: None,
+            is_cloud: None,
+            gpu_index: 0,
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_model_map_export_round_trips_through_import() {
+    let app_state = create_test_app_state().await;
+    let sdxl = create_model_map(&app_state, "sd_xl_base_1.0", "SDXL").await;
+    create_model_map(&app_state, "v1-5-pruned-emaonly", "SD 1.5").await;
+    create_run_details(&app_state, sdxl).await;
+    create_run_details(&app_state, sdxl).await;
+    let app = create_app(app_state);
+
+    let (status, content_type, csv) = send(&app, "GET", "/api/model-map/export", String::new()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(content_type.starts_with("text/csv"));
+
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines[0], "model_name,base_model,usage_count,id,version");
+    assert_eq!(lines[1], format!("sd_xl_base_1.0,SDXL,2,{},1", sdxl));
+    assert!(lines[2].starts_with("v1-5-pruned-emaonly,SD 1.5,0,"));
+
+    // A curator edits the spreadsheet and sends it back
+    let edited = csv.replace("sd_xl_base_1.0,SDXL,", "sd_xl_base_1.0,SDXL 1.0,");
+    let (status, _, report) = send(&app, "POST", "/api/model-map/import", edited).await;
+    assert_eq!(status, StatusCode::OK);
+    let report: Value = serde_json::from_str(&report).unwrap();
+    assert_eq!(report["data"]["updated_rows"], 1);
+    assert_eq!(report["data"]["unchanged_rows"], 1);
+    assert!(report["data"]["errors"].as_array().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_gpu_map_export_includes_base_gpu_and_usage() {
+    let app_state = create_test_app_state().await;
+    let base_gpu_id = GpuBaseRepository::new(app_state.db.clone())
+        .create(GpuBase {
+            id: None,
+            name: "RTX 4090".to_string(),
+            brand: Some("nvidia".to_string()),
+            architecture: None,
+            generation: None,
+            version: 1,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap()
+        .id;
+    let gpu_map_id = GpuMapRepository::new(app_state.db.clone())
+        .create(GpuMap {
+            id: None,
+            gpu_name: Some("NVIDIA GeForce RTX 4090".to_string()),
+            base_gpu_id,
+            version: 1,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap()
+        .id
+        .unwrap();
+    create_gpu(&app_state, "NVIDIA GeForce RTX 4090").await;
+    create_gpu(&app_state, "NVIDIA GeForce RTX 4090").await;
+    create_gpu(&app_state, "NVIDIA GeForce RTX 3090").await;
+    let app = create_app(app_state);
+
+    let (status, content_type, csv) = send(&app, "GET", "/api/gpu-map/export", String::new()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(content_type.starts_with("text/csv"));
+
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines[0], "gpu_name,base_gpu_id,base_gpu_name,usage_count,id,version");
+    assert_eq!(
+        lines[1],
+        format!("NVIDIA GeForce RTX 4090,{},RTX 4090,2,{},1", base_gpu_id.unwrap(), gpu_map_id)
+    );
+    assert_eq!(lines.len(), 2);
+}
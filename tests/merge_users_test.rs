@@ -0,0 +1,148 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    routing::post,
+    Router,
+};
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}},
+    handlers::users::merge_users,
+    middleware::request_transaction::transaction_per_request,
+    models::{audit_log::AUDIT_ACTION_MERGE_USERS, run_more_details::RunMoreDetails, runs::Run},
+    repositories::{
+        audit_log_repository::AuditLogRepository,
+        run_more_details_repository::RunMoreDetailsRepository,
+        runs_repository::RunsRepository,
+        traits::Repository,
+    },
+};
+
+async fn create_test_app_state() -> AppState {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    AppState {
+        db: db_pool,
+        settings: Settings::default(),
+    }
+}
+
+fn create_app(app_state: AppState) -> Router {
+    Router::new()
+        .route("/api/admin/users/merge", post(merge_users))
+        .route_layer(axum::middleware::from_fn_with_state(app_state.clone(), transaction_per_request))
+        .with_state(app_state)
+}
+
+async fn merge(app: &Router, body: Value) -> (StatusCode, Value) {
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/admin/users/merge")
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+/// Insert a run by `user` with its processed details
+async fn create_run(app_state: &AppState, user: &str) -> i64 {
+    let run_id = RunsRepository::new(app_state.db.clone())
+        .create(Run {
+            id: None,
+            timestamp: Some("2024-01-01T00:00:00Z".to_string()),
+            vram_usage: None,
+            info: None,
+            system_info: None,
+            model_info: None,
+            device_info: None,
+            xformers: None,
+            model_name: None,
+            user: Some(user.to_string()),
+            notes: None,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap()
+        .id
+        .unwrap();
+
+    RunMoreDetailsRepository::new(app_state.db.clone())
+        .create(RunMoreDetails {
+            id: None,
+            run_id: Some(run_id),
+            timestamp: None,
+            model_name: None,
+            user: Some(user.to_string()),
+            notes: None,
+            model_map_id: None,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap();
+
+    run_id
+}
+
+#[tokio::test]
+async fn test_merge_users_rewrites_runs_and_details() {
+    let app_state = create_test_app_state().await;
+    create_run(&app_state, "Alice_SD").await;
+    create_run(&app_state, "Alice_SD").await;
+    create_run(&app_state, "alice").await;
+    create_run(&app_state, "bob").await;
+    let app = create_app(app_state.clone());
+
+    let (status, body) = merge(&app, json!({ "from": "Alice_SD", "to": "alice" })).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["runs_updated"], 2);
+    assert_eq!(body["data"]["run_details_updated"], 2);
+    assert_eq!(body["data"]["dataset_version"], 1);
+
+    let runs = RunsRepository::new(app_state.db.clone()).find_all().await.unwrap();
+    assert_eq!(runs.iter().filter(|run| run.user.as_deref() == Some("alice")).count(), 3);
+    assert!(runs.iter().all(|run| run.user.as_deref() != Some("Alice_SD")));
+    assert_eq!(RunMoreDetailsRepository::new(app_state.db.clone()).find_by_user("alice").await.unwrap().len(), 3);
+    assert_eq!(RunMoreDetailsRepository::new(app_state.db.clone()).find_by_user("bob").await.unwrap().len(), 1);
+
+    let entries = AuditLogRepository::new(app_state.db.clone())
+        .find_by_action(AUDIT_ACTION_MERGE_USERS)
+        .await
+        .unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(body["data"]["audit_id"], entries[0].id);
+    assert!(entries[0].details.as_deref().unwrap().contains("Alice_SD"));
+
+    // Nothing is left under the old spelling, so a repeat changes and records nothing
+    let (status, body) = merge(&app, json!({ "from": "Alice_SD", "to": "alice" })).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["runs_updated"], 0);
+    assert!(body["data"]["audit_id"].is_null());
+}
+
+#[tokio::test]
+async fn test_merge_users_rejects_blank_or_identical_users() {
+    let app = create_app(create_test_app_state().await);
+
+    for body in [
+        json!({ "from": " ", "to": "alice" }),
+        json!({ "from": "alice", "to": "" }),
+        json!({ "from": "alice", "to": "alice" }),
+    ] {
+        let (status, _) = merge(&app, body.clone()).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{}", body);
+    }
+}
@@ -0,0 +1,116 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    routing::post,
+    Router,
+};
+use serde_json::Value;
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}},
+    handlers::model_map::import_model_map,
+    models::model_map::ModelMap,
+    repositories::{model_map_repository::ModelMapRepository, traits::Repository},
+};
+
+async fn create_test_app_state() -> AppState {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    AppState {
+        db: db_pool,
+        settings: Settings::default(),
+    }
+}
+
+fn create_app(app_state: AppState) -> Router {
+    Router::new()
+        .route("/api/model-map/import", post(import_model_map))
+        .with_state(app_state)
+}
+
+async fn post_csv(app: &Router, uri: &str, csv: &str) -> (StatusCode, Value) {
+    let request = Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header("content-type", "text/csv")
+        .body(Body::from(csv.to_string()))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+async fn seed_model_map(app_state: &AppState, model_name: &str, base_model: &str) {
+    ModelMapRepository::new(app_state.db.clone())
+        .create(ModelMap {
+            id: None,
+            model_name: Some(model_name.to_string()),
+            base_model: Some(base_model.to_string()),
+            version: 1,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap();
+}
+
+const IMPORT_CSV: &str = "model_name,base_model\nsd_xl_base_1.0,SDXL 1.0\nv1-5-pruned-emaonly,SD 1.5\nsd_xl_turbo_1.0_fp16,SDXL Turbo\n";
+
+#[tokio::test]
+async fn test_import_previews_then_applies_diff() {
+    let app_state = create_test_app_state().await;
+    seed_model_map(&app_state, "sd_xl_base_1.0", "SDXL").await;
+    seed_model_map(&app_state, "v1-5-pruned-emaonly", "SD 1.5").await;
+    let app = create_app(app_state.clone());
+
+    let (status, preview) = post_csv(&app, "/api/model-map/import", IMPORT_CSV).await;
+    assert_eq!(status, StatusCode::OK);
+    let report = &preview["data"];
+    assert_eq!(report["applied"], false);
+    assert_eq!(report["new_rows"], 1);
+    assert_eq!(report["updated_rows"], 1);
+    assert_eq!(report["unchanged_rows"], 1);
+    assert_eq!(report["changes"][0]["action"], "updated");
+    assert_eq!(report["changes"][0]["previous_base_model"], "SDXL");
+
+    let repository = ModelMapRepository::new(app_state.db.clone());
+    assert_eq!(repository.find_all().await.unwrap().len(), 2, "a preview must not write");
+
+    let (status, applied) = post_csv(&app, "/api/model-map/import?apply=true", IMPORT_CSV).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(applied["data"]["applied"], true);
+
+    let sdxl = repository.find_by_model_name("sd_xl_base_1.0").await.unwrap().remove(0);
+    assert_eq!(sdxl.base_model.as_deref(), Some("SDXL 1.0"));
+    assert_eq!(sdxl.version, 2);
+    assert_eq!(repository.find_by_model_name("sd_xl_turbo_1.0_fp16").await.unwrap().len(), 1);
+
+    let (_, again) = post_csv(&app, "/api/model-map/import", IMPORT_CSV).await;
+    assert_eq!(again["data"]["unchanged_rows"], 3);
+}
+
+#[tokio::test]
+async fn test_import_with_invalid_rows_is_not_applied() {
+    let app_state = create_test_app_state().await;
+    let app = create_app(app_state.clone());
+
+    let csv = "model_name,base_model\nsd_xl_base_1.0,SDXL\nsd_xl_base_1.0,SDXL 1.0\nanything-v3,\n";
+    let (status, body) = post_csv(&app, "/api/model-map/import?apply=true", csv).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["applied"], false);
+    assert_eq!(body["data"]["total_rows"], 3);
+    assert_eq!(body["data"]["errors"].as_array().unwrap().len(), 2);
+    assert!(ModelMapRepository::new(app_state.db.clone()).find_all().await.unwrap().is_empty());
+
+    let (status, _) = post_csv(&app, "/api/model-map/import", "name,model\nfoo,bar\n").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,130 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    routing::post,
+    Router,
+};
+use serde_json::Value;
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}},
+    handlers::admin::{seed_model_map, update_run_more_details_with_modelmapid},
+    models::run_more_details::RunMoreDetails,
+    repositories::{
+        model_map_repository::ModelMapRepository,
+        run_more_details_repository::RunMoreDetailsRepository,
+        traits::Repository,
+    },
+    services::data_processing::seed_model_map_service::model_catalog,
+};
+
+async fn create_test_app_state() -> AppState {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    AppState {
+        db: db_pool,
+        settings: Settings::default(),
+    }
+}
+
+fn create_app(app_state: AppState) -> Router {
+    Router::new()
+        .route("/api/admin/seed-model-map", post(seed_model_map))
+        .route("/api/update-run-more-details-with-modelmapid", post(update_run_more_details_with_modelmapid))
+        .with_state(app_state)
+}
+
+async fn post_empty(app: &Router, uri: &str) -> (StatusCode, Value) {
+    let request = Request::builder().method("POST").uri(uri).body(Body::empty()).unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+async fn create_run_details(app_state: &AppState, model_name: &str) -> i64 {
+    RunMoreDetailsRepository::new(app_state.db.clone())
+        .create(RunMoreDetails {
+            id: None,
+            run_id: None,
+            timestamp: None,
+            model_name: Some(model_name.to_string()),
+            user: None,
+            notes: None,
+            model_map_id: None,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap()
+        .id
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_seed_model_map_is_idempotent() {
+    let app_state = create_test_app_state().await;
+    let app = create_app(app_state.clone());
+    let catalog_size = model_catalog().unwrap().len();
+
+    let (status, body) = post_empty(&app, "/api/admin/seed-model-map").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["rows_inserted"], catalog_size);
+    assert_eq!(body["rows_updated"], 0);
+
+    let (_, body) = post_empty(&app, "/api/admin/seed-model-map").await;
+    assert_eq!(body["rows_inserted"], 0);
+    assert_eq!(body["rows_updated"], catalog_size);
+
+    let model_map_repository = ModelMapRepository::new(app_state.db.clone());
+    assert_eq!(model_map_repository.count().await.unwrap(), catalog_size as i64);
+
+    let sdxl = model_map_repository.find_by_hash("31e35c80fc").await.unwrap().unwrap();
+    assert_eq!(sdxl.model_name.as_deref(), Some("sd_xl_base_1.0"));
+    assert_eq!(sdxl.base_model.as_deref(), Some("SDXL"));
+}
+
+#[tokio::test]
+async fn test_update_run_more_details_matches_checkpoint_hash() {
+    let app_state = create_test_app_state().await;
+    let app = create_app(app_state.clone());
+    post_empty(&app, "/api/admin/seed-model-map").await;
+
+    let by_name = create_run_details(&app_state, "sd_xl_base_1.0").await;
+    let by_short_hash = create_run_details(&app_state, "my-renamed-sd15.safetensors [6CE0161689]").await;
+    let by_full_hash = create_run_details(
+        &app_state,
+        "v1-5 (6ce0161689b3853acaa03779ec93eafe75a02f4ced659bee03f50797806fa2fa)",
+    )
+    .await;
+    let unknown = create_run_details(&app_state, "custom-model [0123456789]").await;
+
+    let (status, body) = post_empty(&app, "/api/update-run-more-details-with-modelmapid").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        body["message"],
+        "RunMoreDetails updated with ModelMapId successfully. Updated: 3 (2 by checkpoint hash), Not found: 1"
+    );
+
+    let model_map_repository = ModelMapRepository::new(app_state.db.clone());
+    let sd15 = model_map_repository.find_by_hash("6ce0161689").await.unwrap().unwrap();
+    let sdxl = model_map_repository.find_by_hash("31e35c80fc").await.unwrap().unwrap();
+
+    let run_more_details_repository = RunMoreDetailsRepository::new(app_state.db.clone());
+    let model_map_id = |id: i64| {
+        let repository = &run_more_details_repository;
+        async move { repository.find_by_id(id).await.unwrap().unwrap().model_map_id }
+    };
+    assert_eq!(model_map_id(by_name).await, sdxl.id);
+    assert_eq!(model_map_id(by_short_hash).await, sd15.id);
+    assert_eq!(model_map_id(by_full_hash).await, sd15.id);
+    assert_eq!(model_map_id(unknown).await, None);
+}
@@ -0,0 +1,191 @@
+use axum::{
+    body::Body,
+    http::{Method, Request, StatusCode},
+    routing::{get, post},
+    Router,
+};
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{
+        Settings,
+        database::{DatabaseConfig, create_pool, initialize_database},
+        settings::{NotesFilterAction, NotesFilterConfig},
+    },
+    handlers::{
+        admin::save_data,
+        moderation::{list_moderation_queue, review_moderation_entry},
+        share::{create_share_link, get_shared_run},
+    },
+    middleware::request_transaction::transaction_per_request,
+    models::audit_log::AUDIT_ACTION_MODERATION_REVIEW,
+    repositories::{audit_log_repository::AuditLogRepository, runs_repository::RunsRepository, traits::Repository},
+};
+
+const BOUNDARY: &str = "----WebKitFormBoundary7MA4YWxkTrZu0gW";
+
+async fn create_test_app_state(links: NotesFilterAction) -> AppState {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    AppState {
+        db: db_pool,
+        settings: Settings {
+            notes_filter: NotesFilterConfig {
+                links,
+                markup: NotesFilterAction::Flag,
+                ..NotesFilterConfig::default()
+            },
+            ..Settings::default()
+        },
+    }
+}
+
+fn create_app(app_state: AppState) -> Router {
+    let moderation_routes = Router::new()
+        .route("/api/admin/moderation", get(list_moderation_queue))
+        .route("/api/admin/moderation/{id}", post(review_moderation_entry))
+        .route_layer(axum::middleware::from_fn_with_state(app_state.clone(), transaction_per_request));
+
+    Router::new()
+        .route("/api/save-data", post(save_data))
+        .route("/api/runs/{id}/share", post(create_share_link))
+        .route("/api/share/{token}", get(get_shared_run))
+        .merge(moderation_routes)
+        .with_state(app_state)
+}
+
+fn run(user: &str, notes: &str) -> Value {
+    json!({
+        "timestamp": "2024-01-01T10:00:00Z",
+        "vram_usage": "8GB",
+        "info": "app:automatic",
+        "system_info": "Windows 11",
+        "model_info": "SDXL",
+        "device_info": "RTX 4090",
+        "xformers": "true",
+        "model_name": "stable-diffusion-xl",
+        "user": user,
+        "notes": notes
+    })
+}
+
+async fn send(app: &Router, method: Method, uri: &str, body: Option<Value>) -> (StatusCode, Value) {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("content-type", "application/json")
+        .body(body.map_or_else(Body::empty, |body| Body::from(body.to_string())))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+async fn upload(app: &Router, rows: Value) -> (StatusCode, Value) {
+    let body = format!(
+        "--{boundary}\r\n\
+        Content-Disposition: form-data; name=\"file\"; filename=\"test.json\"\r\n\
+        Content-Type: application/json\r\n\
+        \r\n\
+        {json_data}\r\n\
+        --{boundary}--\r\n",
+        boundary = BOUNDARY,
+        json_data = rows
+    );
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/save-data")
+        .header("content-type", format!("multipart/form-data; boundary={}", BOUNDARY))
+        .body(Body::from(body))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+/// Status of fetching the run through a fresh share link
+async fn shared_status(app: &Router, run_id: i64) -> StatusCode {
+    let (_, link) = send(app, Method::POST, &format!("/api/runs/{}/share", run_id), None).await;
+    let url = link["data"]["url"].as_str().unwrap().to_string();
+    send(app, Method::GET, &url, None).await.0
+}
+
+#[tokio::test]
+async fn test_flagged_runs_are_held_until_reviewed() {
+    let app_state = create_test_app_state(NotesFilterAction::Flag).await;
+    let app = create_app(app_state.clone());
+
+    let (status, body) = upload(
+        &app,
+        json!([
+            run("alice", "details at https://example.com"),
+            run("bob", "<b>fast</b> card"),
+            run("carol", "clean run"),
+        ]),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["rows_inserted"], 3);
+    assert_eq!(body["notes_flagged"], 2);
+
+    let (status, body) = send(&app, Method::GET, "/api/admin/moderation", None).await;
+    assert_eq!(status, StatusCode::OK);
+    let queue = body["data"].as_array().unwrap().clone();
+    assert_eq!(queue.len(), 2);
+    assert_eq!(queue[0]["reasons"], "links");
+    assert_eq!(queue[0]["notes"], "details at https://example.com");
+    assert_eq!(queue[1]["reasons"], "markup");
+
+    let runs = RunsRepository::new(app_state.db.clone()).find_all().await.unwrap();
+    let run_id = |user: &str| runs.iter().find(|run| run.user.as_deref() == Some(user)).unwrap().id.unwrap();
+    assert_eq!(shared_status(&app, run_id("alice")).await, StatusCode::NOT_FOUND);
+    assert_eq!(shared_status(&app, run_id("carol")).await, StatusCode::OK);
+
+    // Approval publishes the run as submitted
+    let uri = format!("/api/admin/moderation/{}", queue[0]["id"]);
+    let (status, body) = send(&app, Method::POST, &uri, Some(json!({ "decision": "approve" }))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["status"], "approved");
+    assert_eq!(shared_status(&app, run_id("alice")).await, StatusCode::OK);
+
+    // Rejection publishes it with the notes cleared
+    let uri = format!("/api/admin/moderation/{}", queue[1]["id"]);
+    let (status, body) = send(&app, Method::POST, &uri, Some(json!({ "decision": "reject" }))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["status"], "rejected");
+    assert!(body["data"]["notes"].is_null());
+    assert_eq!(shared_status(&app, run_id("bob")).await, StatusCode::OK);
+
+    // A reviewed entry cannot be reviewed again
+    let (status, _) = send(&app, Method::POST, &uri, Some(json!({ "decision": "approve" }))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (_, body) = send(&app, Method::GET, "/api/admin/moderation", None).await;
+    assert!(body["data"].as_array().unwrap().is_empty());
+    let entries = AuditLogRepository::new(app_state.db.clone())
+        .find_by_action(AUDIT_ACTION_MODERATION_REVIEW)
+        .await
+        .unwrap();
+    assert_eq!(entries.len(), 2);
+}
+
+#[tokio::test]
+async fn test_blocked_notes_refuse_the_upload() {
+    let app_state = create_test_app_state(NotesFilterAction::Block).await;
+    let app = create_app(app_state.clone());
+
+    let (status, body) = upload(&app, json!([run("carol", "clean run"), run("alice", "see www.example.com")])).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(!body.to_string().contains("example.com"));
+    assert!(RunsRepository::new(app_state.db.clone()).find_all().await.unwrap().is_empty());
+}
@@ -0,0 +1,161 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    routing::get,
+    Router,
+};
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}},
+    handlers::{gpu_base, gpu_map, model_map},
+    models::{gpu_base::GpuBase, gpu_map::GpuMap, model_map::ModelMap},
+    repositories::{
+        gpu_base_repository::GpuBaseRepository,
+        gpu_map_repository::GpuMapRepository,
+        model_map_repository::ModelMapRepository,
+        traits::Repository,
+    },
+};
+
+async fn create_test_app_state() -> AppState {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    AppState {
+        db: db_pool,
+        settings: Settings::default(),
+    }
+}
+
+fn create_app(app_state: AppState) -> Router {
+    Router::new()
+        .route("/api/model-map/{id}", get(model_map::get_model_map).put(model_map::update_model_map))
+        .route("/api/gpu-map/{id}", get(gpu_map::get_gpu_map).put(gpu_map::update_gpu_map))
+        .route("/api/gpu-base/{id}", get(gpu_base::get_gpu_base).put(gpu_base::update_gpu_base))
+        .with_state(app_state)
+}
+
+async fn send(app: &Router, method: &str, uri: &str, body: Option<Value>) -> (StatusCode, Value) {
+    let mut builder = Request::builder().method(method).uri(uri);
+    let body = match body {
+        Some(json) => {
+            builder = builder.header("content-type", "application/json");
+            Body::from(json.to_string())
+        }
+        None => Body::empty(),
+    };
+
+    let response = app.clone().oneshot(builder.body(body).unwrap()).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+#[tokio::test]
+async fn test_model_map_update_rejects_stale_version() {
+    let app_state = create_test_app_state().await;
+    let created = ModelMapRepository::new(app_state.db.clone())
+        .create(ModelMap {
+            id: None,
+            model_name: Some("sd_xl_base_1.0".to_string()),
+            base_model: Some("SDXL".to_string()),
+            version: 1,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap();
+    let uri = format!("/api/model-map/{}", created.id.unwrap());
+    let app = create_app(app_state);
+
+    let (status, read) = send(&app, "GET", &uri, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(read["data"]["version"], 1);
+
+    // Two admins edit the row they both read at version 1
+    let (status, first) = send(&app, "PUT", &uri, Some(json!({
+        "model_name": "sd_xl_base_1.0",
+        "base_model": "SDXL 1.0",
+        "version": 1
+    }))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(first["data"]["version"], 2);
+
+    let (status, second) = send(&app, "PUT", &uri, Some(json!({
+        "model_name": "sd_xl_base_1.0",
+        "base_model": "SD XL",
+        "version": 1
+    }))).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(second["error"]["code"], "VERSION_CONFLICT");
+    assert_eq!(second["error"]["current_version"], 2);
+
+    let (_, current) = send(&app, "GET", &uri, None).await;
+    assert_eq!(current["data"]["base_model"], "SDXL 1.0", "The losing edit must not overwrite the winner");
+
+    let (status, _) = send(&app, "PUT", "/api/model-map/9999", Some(json!({
+        "model_name": "missing",
+        "base_model": "SD 1.5",
+        "version": 1
+    }))).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_gpu_base_and_gpu_map_updates_are_versioned() {
+    let app_state = create_test_app_state().await;
+    let base = GpuBaseRepository::new(app_state.db.clone())
+        .create(GpuBase { id: None, name: "RTX 4090".to_string(), brand: Some("nvidia".to_string()), architecture: None, generation: None, version: 1, created_at: None, updated_at: None })
+        .await
+        .unwrap();
+    let base_id = base.id.unwrap();
+    let map = GpuMapRepository::new(app_state.db.clone())
+        .create(GpuMap { id: None, gpu_name: Some("NVIDIA GeForce RTX 4090".to_string()), base_gpu_id: Some(base_id), version: 1, created_at: None, updated_at: None })
+        .await
+        .unwrap();
+    let app = create_app(app_state);
+
+    let base_uri = format!("/api/gpu-base/{}", base_id);
+    let (status, updated) = send(&app, "PUT", &base_uri, Some(json!({
+        "name": "RTX 4090",
+        "brand": "nvidia",
+        "architecture": "Ada Lovelace",
+        "generation": "GeForce 40",
+        "version": 1
+    }))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(updated["data"]["version"], 2);
+
+    let (status, conflict) = send(&app, "PUT", &base_uri, Some(json!({
+        "name": "RTX 4090",
+        "brand": "NVIDIA",
+        "version": 1
+    }))).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(conflict["error"]["current_version"], 2);
+
+    let map_uri = format!("/api/gpu-map/{}", map.id.unwrap());
+    let (status, updated) = send(&app, "PUT", &map_uri, Some(json!({
+        "gpu_name": "NVIDIA GeForce RTX 4090 D",
+        "base_gpu_id": base_id,
+        "version": 1
+    }))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(updated["data"]["version"], 2);
+
+    let (status, conflict) = send(&app, "PUT", &map_uri, Some(json!({
+        "gpu_name": "NVIDIA GeForce RTX 4090",
+        "base_gpu_id": base_id,
+        "version": 1
+    }))).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(conflict["error"]["current_version"], 2);
+}
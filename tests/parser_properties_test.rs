@@ -0,0 +1,170 @@
+//! Property tests for the submission string parsers: they must never panic on
+//! hostile input, and rendering a parsed value with `get_summary` must parse
+//! back to the same value.
+use proptest::prelude::*;
+
+use sd_its_benchmark::services::parsers::{
+    tokenize, AppDetailsParser, GpuInfoParser, LibrariesParser, ParsedAppDetails, ParsedLibraries,
+    ParsedSystemInfo, PerformanceParser, SystemInfoParser, Token,
+};
+
+/// A single-word value: no spaces and no colons
+fn word() -> impl Strategy<Value = String> {
+    "[A-Za-z0-9._+/()-]{0,12}"
+}
+
+/// A value that may span several words, like `cpu:AMD Ryzen 9 5900X`
+fn multi_word() -> impl Strategy<Value = String> {
+    prop::collection::vec("[A-Za-z0-9._+()-]{1,8}", 1..4).prop_map(|words| words.join(" "))
+}
+
+/// Tokens real submissions don't contain: unknown keys, stray colons, empty words
+fn noise() -> impl Strategy<Value = String> {
+    prop_oneof![
+        "[a-z]{1,6}:[A-Za-z0-9.]{0,6}",
+        "[A-Za-z0-9:._-]{0,8}",
+        Just(String::new()),
+        Just(":".to_string()),
+    ]
+}
+
+fn info_string() -> impl Strategy<Value = String> {
+    let token = prop_oneof![
+        word().prop_map(|v| format!("app:{}", v)),
+        "20[0-9]{2}-[01][0-9]-[0-3][0-9]".prop_map(|v| format!("updated:{}", v)),
+        "[0-9a-f]{7,40}".prop_map(|v| format!("hash:{}", v)),
+        (word(), word()).prop_map(|(repo, branch)| format!("url:https://github.com/{}/tree/{}", repo, branch)),
+        noise(),
+    ];
+    prop::collection::vec(token, 0..8).prop_map(|tokens| tokens.join(" "))
+}
+
+fn system_info_string() -> impl Strategy<Value = String> {
+    let key = prop::sample::select(vec!["arch", "cpu", "system", "release", "python"]);
+    let token = prop_oneof![
+        3 => (key, multi_word()).prop_map(|(key, value)| format!("{}:{}", key, value)),
+        1 => noise(),
+    ];
+    prop::collection::vec(token, 0..8).prop_map(|tokens| tokens.join(" "))
+}
+
+fn model_info_string() -> impl Strategy<Value = String> {
+    let token = prop_oneof![
+        (word(), prop::collection::vec(prop::sample::select(vec!["autocast", "half", "fp32"]), 0..3))
+            .prop_map(|(version, flags)| format!("torch:{} {}", version, flags.join(" "))),
+        word().prop_map(|v| format!("xformers:{}", v)),
+        word().prop_map(|v| format!("diffusers:{}", v)),
+        word().prop_map(|v| format!("transformers:{}", v)),
+        noise(),
+    ];
+    prop::collection::vec(token, 0..8).prop_map(|tokens| tokens.join(" "))
+}
+
+fn device_info_string() -> impl Strategy<Value = String> {
+    let token = prop_oneof![
+        word().prop_map(|v| format!("device:{}", v)),
+        word().prop_map(|v| format!("driver:{}", v)),
+        "[0-9]{1,2}GB",
+        multi_word(),
+        Just("Laptop GPU".to_string()),
+        noise(),
+    ];
+    prop::collection::vec(token, 0..8).prop_map(|tokens| tokens.join(" "))
+}
+
+fn its_string() -> impl Strategy<Value = String> {
+    let value = prop_oneof![
+        any::<f64>().prop_map(|v| v.to_string()),
+        (0.0f64..100.0).prop_map(|v| format!("{:.2}", v)),
+        Just("NaN".to_string()),
+        "[ a-z0-9.]{0,4}",
+    ];
+    prop::collection::vec(value, 0..10).prop_map(|values| values.join("/"))
+}
+
+proptest! {
+    #[test]
+    fn parsers_never_panic_on_arbitrary_input(input in any::<String>()) {
+        AppDetailsParser::parse(&input);
+        AppDetailsParser::parse_release_channel(&input);
+        AppDetailsParser::parse_release_month(&input);
+        SystemInfoParser::parse(&input);
+        LibrariesParser::parse(&input);
+        GpuInfoParser::parse(&input);
+        GpuInfoParser::parse_vram_gb(&input);
+        GpuInfoParser::get_brand_name(&input);
+        PerformanceParser::parse(&input);
+    }
+
+    #[test]
+    fn tokens_rejoin_to_the_input(input in any::<String>()) {
+        let tokens: Vec<&str> = tokenize(&input).map(Token::as_str).collect();
+        prop_assert_eq!(tokens.join(" "), input);
+    }
+
+    #[test]
+    fn app_details_summary_round_trips(input in info_string()) {
+        let parsed = AppDetailsParser::parse(&input);
+        prop_assert_eq!(AppDetailsParser::parse(&AppDetailsParser::get_summary(&parsed)), parsed);
+    }
+
+    #[test]
+    fn app_details_fields_round_trip(
+        app_name in prop::option::of(word()),
+        updated in prop::option::of(word()),
+        hash in prop::option::of(word()),
+        url in prop::option::of(word().prop_map(|v| format!("https://{}", v))),
+    ) {
+        let details = ParsedAppDetails { app_name, updated, hash, url };
+        prop_assert_eq!(AppDetailsParser::parse(&AppDetailsParser::get_summary(&details)), details);
+    }
+
+    #[test]
+    fn system_info_summary_round_trips(input in system_info_string()) {
+        let parsed = SystemInfoParser::parse(&input);
+        prop_assert_eq!(SystemInfoParser::parse(&SystemInfoParser::get_summary(&parsed)), parsed);
+    }
+
+    #[test]
+    fn system_info_fields_round_trip(
+        arch in prop::option::of(word()),
+        cpu in prop::option::of(multi_word()),
+        system in prop::option::of(word()),
+        release in prop::option::of(word()),
+        python in prop::option::of(word()),
+    ) {
+        let system_info = ParsedSystemInfo { arch, cpu, system, release, python, ram_gb: None, swap_gb: None, os_bits: None };
+        prop_assert_eq!(SystemInfoParser::parse(&SystemInfoParser::get_summary(&system_info)), system_info);
+    }
+
+    #[test]
+    fn libraries_summary_round_trips(input in model_info_string()) {
+        let parsed = LibrariesParser::parse(&input);
+        prop_assert_eq!(LibrariesParser::parse(&LibrariesParser::get_summary(&parsed)), parsed);
+    }
+
+    #[test]
+    fn libraries_fields_round_trip(
+        torch in prop::option::of(multi_word()),
+        xformers in prop::option::of(word()),
+        diffusers in prop::option::of(word()),
+        transformers in prop::option::of(word()),
+    ) {
+        let libraries = ParsedLibraries { torch, xformers, diffusers, transformers };
+        prop_assert_eq!(LibrariesParser::parse(&LibrariesParser::get_summary(&libraries)), libraries);
+    }
+
+    #[test]
+    fn gpu_info_summary_round_trips(input in device_info_string()) {
+        let parsed = GpuInfoParser::parse(&input);
+        prop_assert_eq!(GpuInfoParser::parse(&GpuInfoParser::get_summary(&parsed)), parsed);
+    }
+
+    #[test]
+    fn its_average_matches_parsed_values(input in its_string()) {
+        let parsed = PerformanceParser::parse(&input);
+        prop_assert!(parsed.its_values.len() <= input.split('/').count());
+        prop_assert_eq!(parsed.avg_its.is_some(), !parsed.its_values.is_empty());
+        prop_assert_eq!(parsed.raw_vram_usage, input);
+    }
+}
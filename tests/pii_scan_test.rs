@@ -0,0 +1,137 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    Router,
+    routing::post,
+};
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{
+        Settings,
+        database::{DatabaseConfig, create_pool, initialize_database},
+        settings::{PiiScanConfig, PiiScanMode},
+    },
+    handlers::admin::save_data,
+    repositories::{runs_repository::RunsRepository, traits::Repository},
+    services::ingest::pii_scan::{REDACTED_EMAIL, REDACTED_IP, REDACTED_PATH},
+};
+
+const BOUNDARY: &str = "----WebKitFormBoundary7MA4YWxkTrZu0gW";
+
+async fn create_test_app_state(mode: PiiScanMode) -> AppState {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    AppState {
+        db: db_pool,
+        settings: Settings {
+            pii_scan: PiiScanConfig { mode },
+            ..Settings::default()
+        },
+    }
+}
+
+async fn upload(app_state: &AppState) -> (StatusCode, Value) {
+    let test_data = json!([
+        {
+            "timestamp": "2024-01-01T10:00:00Z",
+            "vram_usage": "8GB",
+            "info": "app:automatic path:C:\\Users\\alice\\stable-diffusion-webui",
+            "system_info": "Windows 11",
+            "model_info": "SDXL",
+            "device_info": "RTX 4090",
+            "xformers": "true",
+            "model_name": "stable-diffusion-xl",
+            "user": "alice",
+            "notes": "questions to alice@example.com, box at 192.168.1.20"
+        },
+        {
+            "timestamp": "2024-01-01T11:00:00Z",
+            "vram_usage": "6GB",
+            "info": "app:vladmandic",
+            "system_info": "Ubuntu 22.04",
+            "model_info": "SD1.5",
+            "device_info": "RTX 3080",
+            "xformers": "false",
+            "model_name": "stable-diffusion-1-5",
+            "user": "bob",
+            "notes": "clean run"
+        }
+    ]);
+    let body = format!(
+        "--{boundary}\r\n\
+        Content-Disposition: form-data; name=\"file\"; filename=\"test.json\"\r\n\
+        Content-Type: application/json\r\n\
+        \r\n\
+        {json_data}\r\n\
+        --{boundary}--\r\n",
+        boundary = BOUNDARY,
+        json_data = test_data
+    );
+
+    let app = Router::new()
+        .route("/api/save-data", post(save_data))
+        .with_state(app_state.clone());
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/save-data")
+        .header("content-type", format!("multipart/form-data; boundary={}", BOUNDARY))
+        .body(Body::from(body))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+#[tokio::test]
+async fn test_redact_mode_stores_placeholders_and_reports_counts() {
+    let app_state = create_test_app_state(PiiScanMode::Redact).await;
+
+    let (status, body) = upload(&app_state).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["rows_inserted"], 2);
+    assert_eq!(body["pii_redactions"], json!({"emails": 1, "ip_addresses": 1, "home_paths": 1}));
+
+    let runs = RunsRepository::new(app_state.db.clone()).find_all().await.unwrap();
+    let alice = runs.iter().find(|run| run.user.as_deref() == Some("alice")).unwrap();
+    assert_eq!(
+        alice.notes.as_deref(),
+        Some(format!("questions to {}, box at {}", REDACTED_EMAIL, REDACTED_IP).as_str())
+    );
+    assert_eq!(alice.info.as_deref(), Some(format!("app:automatic path:{}", REDACTED_PATH).as_str()));
+    let bob = runs.iter().find(|run| run.user.as_deref() == Some("bob")).unwrap();
+    assert_eq!(bob.notes.as_deref(), Some("clean run"));
+}
+
+#[tokio::test]
+async fn test_reject_mode_refuses_upload_without_echoing_data() {
+    let app_state = create_test_app_state(PiiScanMode::Reject).await;
+
+    let (status, body) = upload(&app_state).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(!body.to_string().contains("alice@example.com"));
+
+    let runs = RunsRepository::new(app_state.db.clone()).find_all().await.unwrap();
+    assert!(runs.is_empty());
+}
+
+#[tokio::test]
+async fn test_off_mode_stores_uploads_as_submitted() {
+    let app_state = create_test_app_state(PiiScanMode::Off).await;
+
+    let (status, body) = upload(&app_state).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.get("pii_redactions").is_none());
+
+    let runs = RunsRepository::new(app_state.db.clone()).find_all().await.unwrap();
+    assert!(runs.iter().any(|run| run.notes.as_deref().is_some_and(|notes| notes.contains("alice@example.com"))));
+}
@@ -0,0 +1,111 @@
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use axum::{
+    body::Body,
+    extract::State,
+    http::{Request, StatusCode},
+    routing::post,
+    Json, Router,
+};
+use serde_json::Value;
+use tokio::sync::mpsc;
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}},
+    error::i18n::ErrorCatalog,
+    router::create_router,
+};
+
+async fn create_test_app_state() -> AppState {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    AppState {
+        db: db_pool,
+        settings: Settings::default(),
+    }
+}
+
+/// A webhook receiver on a local port, handing each delivered body to the returned channel
+async fn start_webhook_receiver() -> (String, mpsc::UnboundedReceiver<Value>) {
+    let (sender, receiver) = mpsc::unbounded_channel();
+    let app = Router::new()
+        .route(
+            "/hook",
+            post(|State(sender): State<mpsc::UnboundedSender<Value>>, Json(body): Json<Value>| async move {
+                sender.send(body).unwrap();
+                StatusCode::NO_CONTENT
+            }),
+        )
+        .with_state(sender);
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/hook", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    (url, receiver)
+}
+
+async fn run_pipeline(app_state: AppState, uri: &str) -> (StatusCode, Value) {
+    let app = create_router(app_state, Arc::new(ErrorCatalog::bundled().unwrap()));
+    let request = Request::builder().method("POST").uri(uri).body(Body::empty()).unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+#[tokio::test]
+async fn test_pipeline_alerts_are_reported_and_sent_to_the_webhook() {
+    let (webhook_url, mut deliveries) = start_webhook_receiver().await;
+    let mut app_state = create_test_app_state().await;
+    app_state.settings.alerts.webhook_url = Some(webhook_url);
+    // No runs imported, so process-its writes nothing
+    app_state.settings.alerts.min_rows_out = HashMap::from([("process-its".to_string(), 1)]);
+
+    let (status, body) = run_pipeline(app_state, "/api/run-pipeline?stages=process-its,process-gpu").await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    let alerts = body["alerts"].as_array().unwrap();
+    assert_eq!(alerts.len(), 1);
+    assert_eq!(alerts[0]["rule"], "min_rows_out");
+    assert_eq!(alerts[0]["stage"], "process-its");
+    assert_eq!(alerts[0]["value"], 0.0);
+
+    let delivered = tokio::time::timeout(Duration::from_secs(5), deliveries.recv()).await.unwrap().unwrap();
+    assert_eq!(delivered["source"], "SD-ITS-Benchmark");
+    assert_eq!(delivered["alerts"], body["alerts"]);
+}
+
+#[tokio::test]
+async fn test_failed_stage_trips_the_error_rate() {
+    let (webhook_url, mut deliveries) = start_webhook_receiver().await;
+    let mut app_state = create_test_app_state().await;
+    app_state.settings.alerts.webhook_url = Some(webhook_url);
+    app_state.settings.alerts.max_error_rate_percent = Some(10.0);
+    // process-its is rolled back with the failing stage, so its rows do not count
+    app_state.settings.alerts.min_rows_out = HashMap::from([("process-its".to_string(), 1)]);
+    sqlx::query("DROP TABLE GPU").execute(&app_state.db).await.unwrap();
+
+    let (status, _) = run_pipeline(app_state, "/api/run-pipeline?stages=process-its,process-gpu").await;
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+
+    let delivered = tokio::time::timeout(Duration::from_secs(5), deliveries.recv()).await.unwrap().unwrap();
+    assert_eq!(delivered["alerts"].as_array().unwrap().len(), 1);
+    assert_eq!(delivered["alerts"][0]["rule"], "error_rate");
+    // One of the two executions failed
+    assert_eq!(delivered["alerts"][0]["value"], 50.0);
+}
+
+#[tokio::test]
+async fn test_no_alerts_without_thresholds() {
+    let (status, body) = run_pipeline(create_test_app_state().await, "/api/run-pipeline?stages=process-its").await;
+
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["alerts"].as_array().unwrap().len(), 0);
+}
@@ -0,0 +1,192 @@
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    Router,
+};
+use serde_json::Value;
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}},
+    error::i18n::ErrorCatalog,
+    handlers::pipeline::pipeline_registry,
+    models::runs::Run,
+    repositories::{gpu_repository::GpuRepository, runs_repository::RunsRepository, traits::Repository},
+    router::create_router,
+};
+
+async fn create_test_app_state() -> AppState {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    AppState {
+        db: db_pool,
+        settings: Settings::default(),
+    }
+}
+
+fn create_app(app_state: AppState) -> Router {
+    create_router(app_state, Arc::new(ErrorCatalog::bundled().unwrap()))
+}
+
+async fn send(app: &Router, method: &str, uri: &str) -> (StatusCode, Value) {
+    let request = Request::builder().method(method).uri(uri).body(Body::empty()).unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+#[test]
+fn test_pipeline_registry_resolves_every_stage() {
+    let registry = pipeline_registry();
+    let order: Vec<&str> = registry.execution_order().unwrap().iter().map(|stage| stage.name()).collect();
+
+    assert_eq!(order.len(), registry.names().len());
+    let position = |name: &str| order.iter().position(|stage| *stage == name).unwrap();
+    assert!(position("process-gpu") < position("update-gpu-brands"));
+    assert!(position("process-run-details") < position("update-run-more-details-with-modelmapid"));
+    assert!(position("update-run-more-details-with-modelmapid") < position("compute-run-scores"));
+}
+
+#[tokio::test]
+async fn test_run_pipeline_runs_all_stages_in_order() {
+    let app_state = create_test_app_state().await;
+    RunsRepository::new(app_state.db.clone())
+        .create(Run {
+            id: None,
+            timestamp: Some("2024-01-01T00:00:00Z".to_string()),
+            vram_usage: Some("10.5/11.0/12.0".to_string()),
+            info: Some("app:test-app updated:2024-01-01 hash:abc123 url:https://example.com".to_string()),
+            system_info: Some("arch:x86_64 cpu:Intel i5 system:Linux release:5.15.0 python:3.10".to_string()),
+            model_info: Some("torch:2.0.1 xformers:0.0.22 diffusers:0.21.4 transformers:4.30.2".to_string()),
+            device_info: Some("device:NVIDIA GeForce RTX 3060 driver:531.41".to_string()),
+            xformers: Some("enabled".to_string()),
+            model_name: Some("test-model".to_string()),
+            user: Some("tester".to_string()),
+            notes: Some("test notes".to_string()),
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap();
+    let app = create_app(app_state.clone());
+
+    let (status, body) = send(&app, "POST", "/api/run-pipeline").await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    let stages: Vec<&str> = body["stages"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|stage| stage["stage"].as_str().unwrap())
+        .collect();
+    let expected: Vec<&str> = pipeline_registry()
+        .execution_order()
+        .unwrap()
+        .iter()
+        .map(|stage| stage.name())
+        .collect();
+    assert_eq!(stages, expected);
+    assert_eq!(body["stages"][0]["summary"]["success"], true);
+    assert_eq!(body["skipped"].as_array().unwrap().len(), 0);
+
+    let gpus = GpuRepository::new(app_state.db.clone()).find_all().await.unwrap();
+    assert_eq!(gpus.len(), 1);
+
+    // Recorded as one pipeline step
+    let (_, body) = send(&app, "GET", "/api/meta/dataset").await;
+    assert_eq!(body["data"]["last_pipeline_step"], "run-pipeline");
+}
+
+#[tokio::test]
+async fn test_run_pipeline_runs_requested_stages_only() {
+    let app = create_app(create_test_app_state().await);
+
+    let (status, body) = send(&app, "POST", "/api/run-pipeline?stages=update-gpu-brands,process-gpu").await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    let stages: Vec<&str> = body["stages"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|stage| stage["stage"].as_str().unwrap())
+        .collect();
+    // Dependency order, whatever order they were asked for in
+    assert_eq!(stages, vec!["process-gpu", "update-gpu-brands"]);
+    let skipped = body["skipped"].as_array().unwrap();
+    assert_eq!(skipped.len(), pipeline_registry().names().len() - 2);
+    assert!(skipped.contains(&Value::from("update-gpu-laptop-info")));
+}
+
+#[tokio::test]
+async fn test_run_pipeline_reads_enabled_stages_from_settings() {
+    let mut app_state = create_test_app_state().await;
+    let mut enabled_stages: Vec<String> = pipeline_registry().names().iter().map(|name| name.to_string()).collect();
+    enabled_stages.retain(|stage| stage != "update-gpu-laptop-info");
+    app_state.settings.pipeline.enabled_stages = Some(enabled_stages);
+    let app = create_app(app_state);
+
+    let (status, body) = send(&app, "POST", "/api/run-pipeline").await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["skipped"], serde_json::json!(["update-gpu-laptop-info"]));
+
+    // The request's own list takes precedence
+    let (status, body) = send(&app, "POST", "/api/run-pipeline?stages=update-gpu-laptop-info").await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["stages"][0]["stage"], "update-gpu-laptop-info");
+}
+
+#[tokio::test]
+async fn test_run_pipeline_rejects_unknown_or_empty_stage_lists() {
+    let app = create_app(create_test_app_state().await);
+
+    for uri in ["/api/run-pipeline?stages=process-gpu,process-vram", "/api/run-pipeline?stages="] {
+        let (status, _) = send(&app, "POST", uri).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{}", uri);
+    }
+}
+
+#[tokio::test]
+async fn test_pipeline_history_logs_each_stage_execution() {
+    let app_state = create_test_app_state().await;
+    let app = create_app(app_state.clone());
+
+    let (status, _) = send(&app, "POST", "/api/run-pipeline?stages=process-its,process-gpu").await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = send(&app, "GET", "/api/admin/pipeline-history").await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    let events = body["data"].as_array().unwrap();
+    assert_eq!(events.len(), 2);
+    // Newest first
+    assert_eq!(events[0]["stage"], "process-gpu");
+    assert_eq!(events[1]["stage"], "process-its");
+    assert!(events.iter().all(|event| event["status"] == "succeeded" && event["error"].is_null()));
+    assert_eq!(events[1]["rows_in"], 0);
+    assert_eq!(events[1]["rows_out"], 0);
+
+    // A failing stage is logged with its error, after the stages before it
+    sqlx::query("DROP TABLE GPU").execute(&app_state.db).await.unwrap();
+    let (status, _) = send(&app, "POST", "/api/run-pipeline?stages=process-its,process-gpu").await;
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+
+    let (_, body) = send(&app, "GET", "/api/admin/pipeline-history?stage=process-gpu").await;
+    let events = body["data"].as_array().unwrap();
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0]["status"], "failed");
+    assert!(events[0]["error"].as_str().is_some_and(|error| !error.is_empty()));
+
+    // The stage before it shared its transaction, so it was rolled back too
+    let (_, body) = send(&app, "GET", "/api/admin/pipeline-history?stage=process-its").await;
+    assert_eq!(body["data"][0]["status"], "rolled_back");
+
+    let (status, _) = send(&app, "GET", "/api/admin/pipeline-history?limit=0").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
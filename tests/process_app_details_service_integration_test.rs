@@ -7,7 +7,7 @@ use sd_its_benchmark::{
         runs_repository::RunsRepository,
         traits::Repository,
     },
-    services::data_processing::process_app_details_service::ProcessAppDetailsService,
+    services::{data_processing::process_app_details_service::ProcessAppDetailsService, parsers::APP_DETAILS_PARSER_VERSION},
 };
 
 /// Integration test for Process App Details Service
@@ -53,6 +53,10 @@ async fn test_process_app_details_service_integration() -> Result<(), Box<dyn st
     let app_details_repo_for_check = AppDetailsRepository::new(pool.clone());
     let app_details = app_details_repo_for_check.find_all().await?;
     assert_eq!(app_details.len(), 3, "Should have 3 app details");
+    assert!(
+        app_details.iter().all(|record| record.parser_version == Some(APP_DETAILS_PARSER_VERSION)),
+        "All app details should record the parser version"
+    );
     
     // Verify specific app details
     verify_app_details(&app_details).await?;
@@ -148,6 +152,8 @@ fn create_test_runs() -> Vec<Run> {
             model_name: Some("test-model".to_string()),
             user: Some("test-user".to_string()),
             notes: Some("Test run 1".to_string()),
+            created_at: None,
+            updated_at: None,
         },
         Run {
             id: None,
@@ -161,6 +167,8 @@ fn create_test_runs() -> Vec<Run> {
             model_name: Some("test-model".to_string()),
             user: Some("test-user".to_string()),
             notes: Some("Test run 2".to_string()),
+            created_at: None,
+            updated_at: None,
         },
         Run {
             id: None,
@@ -174,6 +182,8 @@ fn create_test_runs() -> Vec<Run> {
             model_name: Some("test-model".to_string()),
             user: Some("test-user".to_string()),
             notes: Some("Test run 3".to_string()),
+            created_at: None,
+            updated_at: None,
         },
     ]
 }
@@ -194,6 +204,8 @@ fn create_test_runs_with_errors() -> Vec<Run> {
             model_name: Some("test-model".to_string()),
             user: Some("test-user".to_string()),
             notes: Some("Valid test run".to_string()),
+            created_at: None,
+            updated_at: None,
         },
         // Run with missing info (should cause error)
         Run {
@@ -208,6 +220,8 @@ fn create_test_runs_with_errors() -> Vec<Run> {
             model_name: Some("test-model".to_string()),
             user: Some("test-user".to_string()),
             notes: Some("Invalid test run - no info".to_string()),
+            created_at: None,
+            updated_at: None,
         },
         // Run with empty info string
         Run {
@@ -222,6 +236,8 @@ fn create_test_runs_with_errors() -> Vec<Run> {
             model_name: Some("test-model".to_string()),
             user: Some("test-user".to_string()),
             notes: Some("Invalid test run - empty info".to_string()),
+            created_at: None,
+            updated_at: None,
         },
         // Valid run
         Run {
@@ -236,6 +252,8 @@ fn create_test_runs_with_errors() -> Vec<Run> {
             model_name: Some("test-model".to_string()),
             user: Some("test-user".to_string()),
             notes: Some("Another valid test run".to_string()),
+            created_at: None,
+            updated_at: None,
         },
     ]
 }
@@ -8,6 +8,7 @@ use tower::ServiceExt;
 
 use sd_its_benchmark::{
     AppState,
+    config::settings::{ParsingConfig, Settings},
     handlers::admin::process_gpu,
     models::{gpu::Gpu, runs::Run},
     repositories::{
@@ -51,6 +52,8 @@ async fn setup_test_data(pool: &SqlitePool) -> Vec<Run> {
             model_name: Some("test-model".to_string()),
             user: Some("testuser".to_string()),
             notes: Some("test notes".to_string()),
+            created_at: None,
+            updated_at: None,
         },
         Run {
             id: None,
@@ -64,6 +67,8 @@ async fn setup_test_data(pool: &SqlitePool) -> Vec<Run> {
             model_name: Some("another-model".to_string()),
             user: Some("anotheruser".to_string()),
             notes: Some("another test".to_string()),
+            created_at: None,
+            updated_at: None,
         },
         Run {
             id: None,
@@ -77,6 +82,8 @@ async fn setup_test_data(pool: &SqlitePool) -> Vec<Run> {
             model_name: Some("simple-model".to_string()),
             user: Some("simpleuser".to_string()),
             notes: Some("simple test".to_string()),
+            created_at: None,
+            updated_at: None,
         },
     ];
 
@@ -151,6 +158,8 @@ async fn test_process_gpu_clears_existing_data() {
         model_name: Some("test-model".to_string()),
         user: Some("testuser".to_string()),
         notes: Some("test notes".to_string()),
+        created_at: None,
+        updated_at: None,
     };
 
     let created_run = runs_repo.create(test_run).await.unwrap();
@@ -166,6 +175,12 @@ async fn test_process_gpu_clears_existing_data() {
         gpu_chip: Some("old-gpu-chip".to_string()),
         brand: Some("old-brand".to_string()),
         is_laptop: Some(false),
+        is_integrated: None,
+        is_cloud: None,
+        gpu_index: 0,
+        parser_version: None,
+        created_at: None,
+        updated_at: None,
     };
 
     gpu_repo.create(existing_gpu).await.unwrap();
@@ -207,6 +222,129 @@ async fn test_process_gpu_clears_existing_data() {
     assert_eq!(updated_gpu.is_laptop, None); // Cleared by this process
 }
 
+// Test that runs reporting several GPUs get one row per GPU
+#[tokio::test]
+async fn test_process_gpu_splits_multi_gpu_runs() {
+    let pool = create_test_pool().await;
+
+    let runs_repo = RunsRepository::new(pool.clone());
+    let device_infos = [
+        "device:NVIDIA GeForce RTX 3090 (2) (sm_86) driver:537.13",
+        "device:NVIDIA GeForce RTX 4090 driver:545.23.08 device:NVIDIA GeForce RTX 3060",
+        "device:NVIDIA GeForce RTX 4080 (1) (sm_89) driver:545.23.08",
+    ];
+    let mut run_ids = Vec::new();
+    for device_info in device_infos {
+        let run = runs_repo
+            .create(Run {
+                id: None,
+                timestamp: Some("2024-01-01T10:00:00Z".to_string()),
+                vram_usage: None,
+                info: None,
+                system_info: None,
+                model_info: None,
+                device_info: Some(device_info.to_string()),
+                xformers: None,
+                model_name: None,
+                user: None,
+                notes: None,
+                created_at: None,
+                updated_at: None,
+            })
+            .await
+            .unwrap();
+        run_ids.push(run.id.unwrap());
+    }
+
+    let app_state = AppState {
+        db: pool.clone(),
+        settings: sd_its_benchmark::config::settings::Settings::new().unwrap(),
+    };
+    let app = create_test_app(app_state);
+
+    let request = Request::builder()
+        .method(Method::POST)
+        .uri("/api/process-gpu")
+        .body(axum::body::Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let response_json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(response_json["rows_inserted"], 5);
+    assert_eq!(response_json["multi_gpu_runs"], 2);
+
+    let gpu_repo = GpuRepository::new(pool.clone());
+
+    let mut counted = gpu_repo.find_by_run_id(run_ids[0]).await.unwrap();
+    counted.sort_by_key(|gpu| gpu.gpu_index);
+    assert_eq!(counted.iter().map(|gpu| gpu.gpu_index).collect::<Vec<_>>(), vec![0, 1]);
+    assert!(counted.iter().all(|gpu| gpu.device == Some("NVIDIA GeForce RTX 3090 (2) (sm_86)".to_string())));
+
+    let mut listed = gpu_repo.find_by_run_id(run_ids[1]).await.unwrap();
+    listed.sort_by_key(|gpu| gpu.gpu_index);
+    assert_eq!(listed[0].device, Some("NVIDIA GeForce RTX 4090".to_string()));
+    assert_eq!(listed[1].device, Some("NVIDIA GeForce RTX 3060".to_string()));
+    // The driver is reported once and applies to both GPUs
+    assert_eq!(listed[1].driver, Some("545.23.08".to_string()));
+
+    let single = gpu_repo.find_by_run_id(run_ids[2]).await.unwrap();
+    assert_eq!(single.len(), 1);
+    assert_eq!(single[0].gpu_index, 0);
+
+    // Multi-GPU runs are flagged with the automatic tag
+    let tagged: Vec<i64> = sqlx::query_scalar(
+        "SELECT rt.run_id FROM run_tags rt JOIN tags t ON t.id = rt.tag_id WHERE t.name = 'multi-gpu' ORDER BY rt.run_id",
+    )
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+    assert_eq!(tagged, vec![run_ids[0], run_ids[1]]);
+}
+
+// Test that runs parsed in separate worker chunks are all written
+#[tokio::test]
+async fn test_process_gpu_across_parsing_chunks() {
+    let pool = create_test_pool().await;
+    let test_runs = setup_test_data(&pool).await;
+
+    let app_state = AppState {
+        db: pool.clone(),
+        settings: Settings {
+            parsing: ParsingConfig {
+                workers: 2,
+                chunk_size: 1,
+                queue_depth: 1,
+            },
+            ..Settings::new().unwrap()
+        },
+    };
+    let app = create_test_app(app_state);
+
+    let request = Request::builder()
+        .method(Method::POST)
+        .uri("/api/process-gpu")
+        .body(axum::body::Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let response_json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(response_json["rows_inserted"], test_runs.len());
+
+    let gpu_repo = GpuRepository::new(pool);
+    let all_gpus = gpu_repo.find_all().await.unwrap();
+    let mut run_ids: Vec<Option<i64>> = all_gpus.iter().map(|gpu| gpu.run_id).collect();
+    run_ids.sort();
+    let mut expected: Vec<Option<i64>> = test_runs.iter().map(|run| run.id).collect();
+    expected.sort();
+    assert_eq!(run_ids, expected);
+}
+
 // Test with no runs data
 #[tokio::test]
 async fn test_process_gpu_with_no_runs() {
@@ -260,6 +398,8 @@ async fn test_gpu_repository_clear_methods() {
         model_name: Some("test-model".to_string()),
         user: Some("testuser".to_string()),
         notes: Some("test notes".to_string()),
+        created_at: None,
+        updated_at: None,
     };
 
     let created_run = runs_repo.create(test_run).await.unwrap();
@@ -274,6 +414,12 @@ async fn test_gpu_repository_clear_methods() {
         gpu_chip: Some("gpu:RTX 4090".to_string()),
         brand: Some("nvidia".to_string()),
         is_laptop: Some(false),
+        is_integrated: None,
+        is_cloud: None,
+        gpu_index: 0,
+        parser_version: None,
+        created_at: None,
+        updated_at: None,
     };
 
     let created_gpu = gpu_repo.create(test_gpu).await.unwrap();
@@ -294,6 +440,12 @@ async fn test_gpu_repository_clear_methods() {
         gpu_chip: Some("gpu:RTX 4080".to_string()),
         brand: Some("nvidia".to_string()),
         is_laptop: Some(true),
+        is_integrated: None,
+        is_cloud: None,
+        gpu_index: 0,
+        parser_version: None,
+        created_at: None,
+        updated_at: None,
     };
 
     gpu_repo.create_tx(test_gpu_2, &mut tx).await.unwrap();
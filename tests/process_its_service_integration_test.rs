@@ -148,6 +148,8 @@ fn create_test_runs() -> Vec<Run> {
             model_name: Some("test-model".to_string()),
             user: Some("test-user".to_string()),
             notes: Some("Test run 1".to_string()),
+            created_at: None,
+            updated_at: None,
         },
         Run {
             id: None,
@@ -161,6 +163,8 @@ fn create_test_runs() -> Vec<Run> {
             model_name: Some("test-model".to_string()),
             user: Some("test-user".to_string()),
             notes: Some("Test run 2".to_string()),
+            created_at: None,
+            updated_at: None,
         },
         Run {
             id: None,
@@ -174,6 +178,8 @@ fn create_test_runs() -> Vec<Run> {
             model_name: Some("test-model".to_string()),
             user: Some("test-user".to_string()),
             notes: Some("Test run 3".to_string()),
+            created_at: None,
+            updated_at: None,
         },
     ]
 }
@@ -194,6 +200,8 @@ fn create_test_runs_with_errors() -> Vec<Run> {
             model_name: Some("test-model".to_string()),
             user: Some("test-user".to_string()),
             notes: Some("Valid test run".to_string()),
+            created_at: None,
+            updated_at: None,
         },
         // Run with missing vram_usage (should cause error)
         Run {
@@ -208,6 +216,8 @@ fn create_test_runs_with_errors() -> Vec<Run> {
             model_name: Some("test-model".to_string()),
             user: Some("test-user".to_string()),
             notes: Some("Invalid test run - no vram_usage".to_string()),
+            created_at: None,
+            updated_at: None,
         },
         // Run with invalid ITS values
         Run {
@@ -222,6 +232,8 @@ fn create_test_runs_with_errors() -> Vec<Run> {
             model_name: Some("test-model".to_string()),
             user: Some("test-user".to_string()),
             notes: Some("Invalid test run - bad ITS values".to_string()),
+            created_at: None,
+            updated_at: None,
         },
         // Valid run
         Run {
@@ -236,6 +248,8 @@ fn create_test_runs_with_errors() -> Vec<Run> {
             model_name: Some("test-model".to_string()),
             user: Some("test-user".to_string()),
             notes: Some("Another valid test run".to_string()),
+            created_at: None,
+            updated_at: None,
         },
     ]
 }
@@ -50,6 +50,8 @@ async fn setup_test_data(pool: &SqlitePool) -> Vec<Run> {
             model_name: Some("test-model-1".to_string()),
             user: Some("test-user".to_string()),
             notes: Some("Test notes".to_string()),
+            created_at: None,
+            updated_at: None,
         },
         Run {
             id: None,
@@ -63,6 +65,8 @@ async fn setup_test_data(pool: &SqlitePool) -> Vec<Run> {
             model_name: Some("test-model-2".to_string()),
             user: Some("test-user".to_string()),
             notes: Some("Test notes".to_string()),
+            created_at: None,
+            updated_at: None,
         },
         Run {
             id: None,
@@ -76,6 +80,8 @@ async fn setup_test_data(pool: &SqlitePool) -> Vec<Run> {
             model_name: Some("test-model-3".to_string()),
             user: Some("test-user".to_string()),
             notes: Some("Test notes".to_string()),
+            created_at: None,
+            updated_at: None,
         },
         Run {
             id: None,
@@ -89,6 +95,8 @@ async fn setup_test_data(pool: &SqlitePool) -> Vec<Run> {
             model_name: Some("test-model-4".to_string()),
             user: Some("test-user".to_string()),
             notes: Some("Test notes".to_string()),
+            created_at: None,
+            updated_at: None,
         },
     ];
 
@@ -208,6 +216,9 @@ async fn test_process_its_clears_existing_data() {
         run_id: test_runs[0].id, // Use a valid run_id from the test data
         its: Some("old_data".to_string()),
         avg_its: Some(5.0),
+        vram_usage_kind: None,
+        created_at: None,
+        updated_at: None,
     };
     perf_repo.create(existing_result).await.expect("Failed to create existing result");
 
@@ -318,6 +329,8 @@ async fn test_process_its_its_calculation_edge_cases() {
             model_name: Some("test-model-1".to_string()),
             user: Some("test-user".to_string()),
             notes: Some("Test notes".to_string()),
+            created_at: None,
+            updated_at: None,
         },
         Run {
             id: None,
@@ -331,6 +344,8 @@ async fn test_process_its_its_calculation_edge_cases() {
             model_name: Some("test-model-2".to_string()),
             user: Some("test-user".to_string()),
             notes: Some("Test notes".to_string()),
+            created_at: None,
+            updated_at: None,
         },
         Run {
             id: None,
@@ -344,6 +359,8 @@ async fn test_process_its_its_calculation_edge_cases() {
             model_name: Some("test-model-3".to_string()),
             user: Some("test-user".to_string()),
             notes: Some("Test notes".to_string()),
+            created_at: None,
+            updated_at: None,
         },
     ];
 
@@ -389,6 +406,7 @@ async fn test_process_its_its_calculation_edge_cases() {
 
     // Check that all results have valid avg_its values
     for result in results {
+        assert_eq!(result.vram_usage_kind.as_deref(), Some("its"));
         assert!(result.avg_its.is_some());
         assert!(result.avg_its.unwrap() >= 0.0); // Should be non-negative
     }
@@ -418,6 +436,8 @@ async fn test_performance_result_repository_clear_methods() {
         model_name: Some("test-model".to_string()),
         user: Some("test-user".to_string()),
         notes: Some("Test notes".to_string()),
+        created_at: None,
+        updated_at: None,
     };
     let created_run = runs_repo.create(test_run).await.expect("Failed to create test run");
     let run_id = created_run.id.unwrap();
@@ -430,6 +450,9 @@ async fn test_performance_result_repository_clear_methods() {
         run_id: Some(run_id),
         its: Some("10.5".to_string()),
         avg_its: Some(10.5),
+        vram_usage_kind: None,
+        created_at: None,
+        updated_at: None,
     };
 
     repo.create(test_result).await.expect("Failed to create test result");
@@ -454,6 +477,9 @@ async fn test_performance_result_repository_clear_methods() {
         run_id: Some(run_id),
         its: Some("15.0".to_string()),
         avg_its: Some(15.0),
+        vram_usage_kind: None,
+        created_at: None,
+        updated_at: None,
     };
 
     repo.create_tx(test_result2, &mut tx).await.expect("Failed to create test result in transaction");
@@ -555,6 +581,8 @@ async fn setup_test_runs_with_app_details(pool: &SqlitePool) -> Vec<Run> {
             model_name: Some("test-model-1".to_string()),
             user: Some("test-user".to_string()),
             notes: Some("Test notes".to_string()),
+            created_at: None,
+            updated_at: None,
         },
         Run {
             id: None,
@@ -568,6 +596,8 @@ async fn setup_test_runs_with_app_details(pool: &SqlitePool) -> Vec<Run> {
             model_name: Some("test-model-2".to_string()),
             user: Some("test-user".to_string()),
             notes: Some("Test notes".to_string()),
+            created_at: None,
+            updated_at: None,
         },
         Run {
             id: None,
@@ -581,6 +611,8 @@ async fn setup_test_runs_with_app_details(pool: &SqlitePool) -> Vec<Run> {
             model_name: Some("test-model-3".to_string()),
             user: Some("test-user".to_string()),
             notes: Some("Test notes".to_string()),
+            created_at: None,
+            updated_at: None,
         },
         Run {
             id: None,
@@ -594,6 +626,8 @@ async fn setup_test_runs_with_app_details(pool: &SqlitePool) -> Vec<Run> {
             model_name: Some("test-model-4".to_string()),
             user: Some("test-user".to_string()),
             notes: Some("Test notes".to_string()),
+            created_at: None,
+            updated_at: None,
         },
     ];
 
@@ -687,6 +721,9 @@ async fn test_process_app_details_clears_existing_data() {
         updated: Some("2023-01-01".to_string()),
         hash: Some("old-hash".to_string()),
         url: Some("https://old-url.com".to_string()),
+        parser_version: None,
+        created_at: None,
+        updated_at: None,
     };
     app_details_repo.create(existing_details).await.expect("Failed to create existing app details");
 
@@ -795,6 +832,8 @@ async fn test_app_details_repository_clear_methods() {
         model_name: Some("test-model".to_string()),
         user: Some("test-user".to_string()),
         notes: Some("Test notes".to_string()),
+        created_at: None,
+        updated_at: None,
     };
     let created_run = runs_repo.create(test_run).await.expect("Failed to create test run");
     let run_id = created_run.id.unwrap();
@@ -809,6 +848,9 @@ async fn test_app_details_repository_clear_methods() {
         updated: Some("2024-01-01".to_string()),
         hash: Some("abc123".to_string()),
         url: Some("https://example.com".to_string()),
+        parser_version: None,
+        created_at: None,
+        updated_at: None,
     };
 
     repo.create(test_details).await.expect("Failed to create test app details");
@@ -835,6 +877,9 @@ async fn test_app_details_repository_clear_methods() {
         updated: Some("2024-01-02".to_string()),
         hash: Some("def456".to_string()),
         url: Some("https://example2.com".to_string()),
+        parser_version: None,
+        created_at: None,
+        updated_at: None,
     };
 
     repo.create_tx(test_details2, &mut tx).await.expect("Failed to create test app details in transaction");
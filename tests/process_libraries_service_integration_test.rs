@@ -7,7 +7,7 @@ use sd_its_benchmark::{
         runs_repository::RunsRepository,
         traits::Repository,
     },
-    services::data_processing::process_libraries_service::ProcessLibrariesService,
+    services::{data_processing::process_libraries_service::ProcessLibrariesService, parsers::LIBRARIES_PARSER_VERSION},
 };
 
 /// Integration test for Process Libraries Service
@@ -53,6 +53,10 @@ async fn test_process_libraries_service_integration() -> Result<(), Box<dyn std:
     let libraries_repo_for_check = LibrariesRepository::new(pool.clone());
     let libraries_records = libraries_repo_for_check.find_all().await?;
     assert_eq!(libraries_records.len(), 3, "Should have 3 library records");
+    assert!(
+        libraries_records.iter().all(|record| record.parser_version == Some(LIBRARIES_PARSER_VERSION)),
+        "All library records should record the parser version"
+    );
     
     // Verify specific libraries
     verify_libraries(&libraries_records).await?;
@@ -148,6 +152,8 @@ fn create_test_runs() -> Vec<Run> {
             model_name: Some("test-model".to_string()),
             user: Some("test-user".to_string()),
             notes: Some("Test run 1".to_string()),
+            created_at: None,
+            updated_at: None,
         },
         Run {
             id: None,
@@ -161,6 +167,8 @@ fn create_test_runs() -> Vec<Run> {
             model_name: Some("test-model".to_string()),
             user: Some("test-user".to_string()),
             notes: Some("Test run 2".to_string()),
+            created_at: None,
+            updated_at: None,
         },
         Run {
             id: None,
@@ -174,6 +182,8 @@ fn create_test_runs() -> Vec<Run> {
             model_name: Some("test-model".to_string()),
             user: Some("test-user".to_string()),
             notes: Some("Test run 3".to_string()),
+            created_at: None,
+            updated_at: None,
         },
     ]
 }
@@ -194,6 +204,8 @@ fn create_test_runs_with_errors() -> Vec<Run> {
             model_name: Some("test-model".to_string()),
             user: Some("test-user".to_string()),
             notes: Some("Valid test run".to_string()),
+            created_at: None,
+            updated_at: None,
         },
         // Run with missing model_info (should cause error)
         Run {
@@ -208,6 +220,8 @@ fn create_test_runs_with_errors() -> Vec<Run> {
             model_name: Some("test-model".to_string()),
             user: Some("test-user".to_string()),
             notes: Some("Invalid test run - no model_info".to_string()),
+            created_at: None,
+            updated_at: None,
         },
         // Run with missing xformers (should cause error)
         Run {
@@ -222,6 +236,8 @@ fn create_test_runs_with_errors() -> Vec<Run> {
             model_name: Some("test-model".to_string()),
             user: Some("test-user".to_string()),
             notes: Some("Invalid test run - no xformers".to_string()),
+            created_at: None,
+            updated_at: None,
         },
         // Valid run
         Run {
@@ -236,6 +252,8 @@ fn create_test_runs_with_errors() -> Vec<Run> {
             model_name: Some("test-model".to_string()),
             user: Some("test-user".to_string()),
             notes: Some("Another valid test run".to_string()),
+            created_at: None,
+            updated_at: None,
         },
     ]
 }
@@ -51,6 +51,8 @@ async fn setup_test_data(pool: &SqlitePool) -> Vec<Run> {
             model_name: Some("test-model".to_string()),
             user: Some("testuser".to_string()),
             notes: Some("test notes".to_string()),
+            created_at: None,
+            updated_at: None,
         },
         Run {
             id: None,
@@ -64,6 +66,8 @@ async fn setup_test_data(pool: &SqlitePool) -> Vec<Run> {
             model_name: Some("another-model".to_string()),
             user: Some("anotheruser".to_string()),
             notes: Some("another test".to_string()),
+            created_at: None,
+            updated_at: None,
         },
         Run {
             id: None,
@@ -77,6 +81,8 @@ async fn setup_test_data(pool: &SqlitePool) -> Vec<Run> {
             model_name: Some("simple-model".to_string()),
             user: Some("simpleuser".to_string()),
             notes: Some("simple test".to_string()),
+            created_at: None,
+            updated_at: None,
         },
     ];
 
@@ -151,6 +157,8 @@ async fn test_process_libraries_clears_existing_data() {
         model_name: Some("test-model".to_string()),
         user: Some("testuser".to_string()),
         notes: Some("test notes".to_string()),
+        created_at: None,
+        updated_at: None,
     };
 
     let created_run = runs_repo.create(test_run).await.unwrap();
@@ -166,6 +174,9 @@ async fn test_process_libraries_clears_existing_data() {
         xformers1: Some("old-xformers1".to_string()),
         diffusers: Some("old-diffusers".to_string()),
         transformers: Some("old-transformers".to_string()),
+        parser_version: None,
+        created_at: None,
+        updated_at: None,
     };
 
     libraries_repo.create(existing_library).await.unwrap();
@@ -260,6 +271,8 @@ async fn test_libraries_repository_clear_methods() {
         model_name: Some("test-model".to_string()),
         user: Some("testuser".to_string()),
         notes: Some("test notes".to_string()),
+        created_at: None,
+        updated_at: None,
     };
 
     let created_run = runs_repo.create(test_run).await.unwrap();
@@ -274,6 +287,9 @@ async fn test_libraries_repository_clear_methods() {
         xformers1: Some("enabled".to_string()),
         diffusers: Some("0.21.4".to_string()),
         transformers: Some("4.30.2".to_string()),
+        parser_version: None,
+        created_at: None,
+        updated_at: None,
     };
 
     let created_library = libraries_repo.create(test_library).await.unwrap();
@@ -294,6 +310,9 @@ async fn test_libraries_repository_clear_methods() {
         xformers1: Some("disabled".to_string()),
         diffusers: Some("0.22.0".to_string()),
         transformers: Some("4.31.0".to_string()),
+        parser_version: None,
+        created_at: None,
+        updated_at: None,
     };
 
     libraries_repo.create_tx(test_library_2, &mut tx).await.unwrap();
@@ -115,6 +115,8 @@ async fn test_process_run_details_service_clears_existing_data() -> Result<(), B
         model_name: Some("dummy-model".to_string()),
         user: Some("dummy-user".to_string()),
         notes: Some("Dummy run for testing".to_string()),
+        created_at: None,
+        updated_at: None,
     };
     let dummy_run = runs_repo_for_insert.create(dummy_run).await?;
     let dummy_run_id = dummy_run.id.unwrap();
@@ -127,6 +129,8 @@ async fn test_process_run_details_service_clears_existing_data() -> Result<(), B
         user: Some("old-user".to_string()),
         notes: Some("old notes".to_string()),
         model_map_id: None,
+        created_at: None,
+        updated_at: None,
     };
     run_more_details_repo.create(existing_detail).await?;
     
@@ -192,6 +196,8 @@ fn create_test_runs() -> Vec<Run> {
             model_name: Some("test-model-1".to_string()),
             user: Some("test-user-1".to_string()),
             notes: Some("Test run 1 notes".to_string()),
+            created_at: None,
+            updated_at: None,
         },
         Run {
             id: None,
@@ -205,6 +211,8 @@ fn create_test_runs() -> Vec<Run> {
             model_name: Some("test-model-2".to_string()),
             user: Some("test-user-2".to_string()),
             notes: Some("Test run 2 notes".to_string()),
+            created_at: None,
+            updated_at: None,
         },
         Run {
             id: None,
@@ -218,6 +226,8 @@ fn create_test_runs() -> Vec<Run> {
             model_name: Some("test-model-3".to_string()),
             user: Some("test-user-3".to_string()),
             notes: Some("Test run 3 notes".to_string()),
+            created_at: None,
+            updated_at: None,
         },
     ]
 }
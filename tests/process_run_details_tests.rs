@@ -51,6 +51,8 @@ async fn setup_test_runs_data(pool: &SqlitePool) -> Vec<Run> {
             model_name: Some("test-model-1".to_string()),
             user: Some("testuser1".to_string()),
             notes: Some("test notes 1".to_string()),
+            created_at: None,
+            updated_at: None,
         },
         Run {
             id: None,
@@ -64,6 +66,8 @@ async fn setup_test_runs_data(pool: &SqlitePool) -> Vec<Run> {
             model_name: Some("test-model-2".to_string()),
             user: Some("testuser2".to_string()),
             notes: Some("test notes 2".to_string()),
+            created_at: None,
+            updated_at: None,
         },
         Run {
             id: None,
@@ -77,6 +81,8 @@ async fn setup_test_runs_data(pool: &SqlitePool) -> Vec<Run> {
             model_name: Some("test-model-3".to_string()),
             user: Some("testuser3".to_string()),
             notes: Some("test notes 3".to_string()),
+            created_at: None,
+            updated_at: None,
         },
     ];
 
@@ -150,6 +156,8 @@ async fn test_process_run_details_clears_existing_data() {
         user: Some("old-user".to_string()),
         notes: Some("old-notes".to_string()),
         model_map_id: None,
+        created_at: None,
+        updated_at: None,
     };
     run_more_details_repo.create(existing_detail).await.unwrap();
 
@@ -277,6 +285,8 @@ async fn test_run_more_details_repository_clear_methods() {
         model_name: Some("test-model".to_string()),
         user: Some("testuser".to_string()),
         notes: Some("test notes".to_string()),
+        created_at: None,
+        updated_at: None,
     };
     let created_run = runs_repo.create(test_run).await.unwrap();
     let run_id = created_run.id.unwrap();
@@ -290,6 +300,8 @@ async fn test_run_more_details_repository_clear_methods() {
         user: Some("testuser".to_string()),
         notes: Some("test notes".to_string()),
         model_map_id: None,
+        created_at: None,
+        updated_at: None,
     };
     run_more_details_repo.create(test_detail).await.unwrap();
 
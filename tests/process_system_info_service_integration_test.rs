@@ -7,7 +7,7 @@ use sd_its_benchmark::{
         system_info_repository::SystemInfoRepository,
         traits::Repository,
     },
-    services::data_processing::process_system_info_service::ProcessSystemInfoService,
+    services::{data_processing::process_system_info_service::ProcessSystemInfoService, parsers::SYSTEM_INFO_PARSER_VERSION},
 };
 
 /// Integration test for Process System Info Service
@@ -53,6 +53,10 @@ async fn test_process_system_info_service_integration() -> Result<(), Box<dyn st
     let system_info_repo_for_check = SystemInfoRepository::new(pool.clone());
     let system_info_records = system_info_repo_for_check.find_all().await?;
     assert_eq!(system_info_records.len(), 3, "Should have 3 system info records");
+    assert!(
+        system_info_records.iter().all(|record| record.parser_version == Some(SYSTEM_INFO_PARSER_VERSION)),
+        "All system info records should record the parser version"
+    );
     
     // Verify specific system info
     verify_system_info(&system_info_records).await?;
@@ -148,6 +152,8 @@ fn create_test_runs() -> Vec<Run> {
             model_name: Some("test-model".to_string()),
             user: Some("test-user".to_string()),
             notes: Some("Test run 1".to_string()),
+            created_at: None,
+            updated_at: None,
         },
         Run {
             id: None,
@@ -161,6 +167,8 @@ fn create_test_runs() -> Vec<Run> {
             model_name: Some("test-model".to_string()),
             user: Some("test-user".to_string()),
             notes: Some("Test run 2".to_string()),
+            created_at: None,
+            updated_at: None,
         },
         Run {
             id: None,
@@ -174,6 +182,8 @@ fn create_test_runs() -> Vec<Run> {
             model_name: Some("test-model".to_string()),
             user: Some("test-user".to_string()),
             notes: Some("Test run 3".to_string()),
+            created_at: None,
+            updated_at: None,
         },
     ]
 }
@@ -194,6 +204,8 @@ fn create_test_runs_with_errors() -> Vec<Run> {
             model_name: Some("test-model".to_string()),
             user: Some("test-user".to_string()),
             notes: Some("Valid test run".to_string()),
+            created_at: None,
+            updated_at: None,
         },
         // Run with missing system_info (should cause error)
         Run {
@@ -208,6 +220,8 @@ fn create_test_runs_with_errors() -> Vec<Run> {
             model_name: Some("test-model".to_string()),
             user: Some("test-user".to_string()),
             notes: Some("Invalid test run - no system_info".to_string()),
+            created_at: None,
+            updated_at: None,
         },
         // Run with incomplete system_info (should be skipped)
         Run {
@@ -222,6 +236,8 @@ fn create_test_runs_with_errors() -> Vec<Run> {
             model_name: Some("test-model".to_string()),
             user: Some("test-user".to_string()),
             notes: Some("Invalid test run - incomplete system_info".to_string()),
+            created_at: None,
+            updated_at: None,
         },
         // Valid run
         Run {
@@ -236,6 +252,8 @@ fn create_test_runs_with_errors() -> Vec<Run> {
             model_name: Some("test-model".to_string()),
             user: Some("test-user".to_string()),
             notes: Some("Another valid test run".to_string()),
+            created_at: None,
+            updated_at: None,
         },
     ]
 }
@@ -53,6 +53,8 @@ async fn setup_test_data(pool: &SqlitePool) -> Vec<Run> {
             model_name: Some("stable-diffusion-v1-5".to_string()),
             user: Some("testuser".to_string()),
             notes: Some("Test run 1".to_string()),
+            created_at: None,
+            updated_at: None,
         },
         Run {
             id: None,
@@ -66,6 +68,8 @@ async fn setup_test_data(pool: &SqlitePool) -> Vec<Run> {
             model_name: Some("stable-diffusion-xl".to_string()),
             user: Some("testuser2".to_string()),
             notes: Some("Test run 2".to_string()),
+            created_at: None,
+            updated_at: None,
         },
         Run {
             id: None,
@@ -79,6 +83,8 @@ async fn setup_test_data(pool: &SqlitePool) -> Vec<Run> {
             model_name: Some("stable-diffusion-v2-1".to_string()),
             user: Some("testuser3".to_string()),
             notes: Some("Test run 3".to_string()),
+            created_at: None,
+            updated_at: None,
         },
     ];
 
@@ -195,6 +201,56 @@ async fn test_process_system_info_success() {
     }
 }
 
+// Memory and bitness keys from v2 uploads are stored alongside the basic fields
+#[tokio::test]
+async fn test_process_system_info_stores_memory_and_bits() {
+    let pool = create_test_pool().await;
+    sqlx::migrate!("./migrations")
+        .run(&pool)
+        .await
+        .expect("Failed to run migrations");
+
+    let run = RunsRepository::new(pool.clone())
+        .create(Run {
+            id: None,
+            timestamp: Some("2024-01-01T10:00:00Z".to_string()),
+            vram_usage: Some("8.5/16.0".to_string()),
+            info: None,
+            system_info: Some("arch:x86_64 cpu:AMD Ryzen 9 5950X system:Linux release:6.5.0 python:3.10.12 ram:63.9 swap:8 bits:64".to_string()),
+            model_info: None,
+            device_info: None,
+            xformers: None,
+            model_name: None,
+            user: None,
+            notes: None,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap();
+
+    let app_state = AppState {
+        db: pool.clone(),
+        settings: sd_its_benchmark::config::settings::Settings::new().unwrap(),
+    };
+    let app = create_test_app(app_state);
+
+    let request = Request::builder()
+        .method(Method::POST)
+        .uri("/api/process-system-info")
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let stored = SystemInfoRepository::new(pool).find_by_run_id(run.id.unwrap()).await.unwrap();
+    assert_eq!(stored.len(), 1);
+    assert_eq!(stored[0].cpu.as_deref(), Some("AMD Ryzen 9 5950X"));
+    assert_eq!(stored[0].ram_gb, Some(63.9));
+    assert_eq!(stored[0].swap_gb, Some(8.0));
+    assert_eq!(stored[0].os_bits, Some(64));
+}
+
 // Test that existing data is cleared before processing
 #[tokio::test]
 async fn test_process_system_info_clears_existing_data() {
@@ -306,6 +362,8 @@ async fn test_system_info_repository_clear_methods() {
         model_name: Some("test-model".to_string()),
         user: Some("testuser".to_string()),
         notes: Some("test notes".to_string()),
+        created_at: None,
+        updated_at: None,
     };
 
     let created_run = runs_repo.create(test_run).await.unwrap();
@@ -320,6 +378,12 @@ async fn test_system_info_repository_clear_methods() {
         system: Some("Linux".to_string()),
         release: Some("5.15.0".to_string()),
         python: Some("3.10".to_string()),
+        ram_gb: None,
+        swap_gb: None,
+        os_bits: None,
+        parser_version: None,
+        created_at: None,
+        updated_at: None,
     };
 
     let created_system_info = system_info_repo.create(test_system_info).await.unwrap();
@@ -340,6 +404,12 @@ async fn test_system_info_repository_clear_methods() {
         system: Some("Windows".to_string()),
         release: Some("10.0".to_string()),
         python: Some("3.11".to_string()),
+        ram_gb: None,
+        swap_gb: None,
+        os_bits: None,
+        parser_version: None,
+        created_at: None,
+        updated_at: None,
     };
 
     system_info_repo.create_tx(test_system_info_2, &mut tx).await.unwrap();
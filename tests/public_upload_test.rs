@@ -0,0 +1,121 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    routing::post,
+    Router,
+};
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}},
+    handlers::upload::upload_file_compat,
+    repositories::{runs_repository::RunsRepository, traits::Repository},
+};
+
+const BOUNDARY: &str = "----WebKitFormBoundary7MA4YWxkTrZu0gW";
+
+async fn create_test_app_state() -> AppState {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    AppState {
+        db: db_pool,
+        settings: Settings::default(),
+    }
+}
+
+/// A multipart body with one part per `(field, file name, content)`
+fn multipart_body(files: &[(&str, &str, &str)]) -> String {
+    let mut body = String::new();
+    for (field, file_name, content) in files {
+        body.push_str(&format!(
+            "--{BOUNDARY}\r\nContent-Disposition: form-data; name=\"{field}\"; filename=\"{file_name}\"\r\nContent-Type: application/json\r\n\r\n{content}\r\n"
+        ));
+    }
+    body.push_str(&format!("--{BOUNDARY}--\r\n"));
+
+    body
+}
+
+fn runs(user: &str) -> String {
+    json!([{
+        "timestamp": "2024-01-01T10:00:00Z",
+        "vram_usage": "8.5/12.0/15.2",
+        "info": "app:stable-diffusion-webui updated:2024-01-01 hash:abc123 url:https://github.com/AUTOMATIC1111/stable-diffusion-webui",
+        "system_info": "arch:x86_64 system:Linux",
+        "model_info": "torch:2.0.1",
+        "device_info": "device:NVIDIA GeForce RTX 3080 driver:535.86",
+        "xformers": "True",
+        "model_name": "v1-5-pruned-emaonly.safetensors",
+        "user": user,
+        "notes": ""
+    }])
+    .to_string()
+}
+
+async fn upload(app_state: &AppState, files: &[(&str, &str, &str)]) -> (StatusCode, Value) {
+    let app = Router::new()
+        .route("/api/upload", post(upload_file_compat))
+        .with_state(app_state.clone());
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/upload")
+        .header("content-type", format!("multipart/form-data; boundary={}", BOUNDARY))
+        .body(Body::from(multipart_body(files)))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+#[tokio::test]
+async fn test_upload_appends_runs_and_answers_the_standard_response() {
+    let app_state = create_test_app_state().await;
+
+    let (status, body) = upload(&app_state, &[("benchmark", "alice.json", &runs("alice"))]).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["success"], true);
+    assert_eq!(body["file_name"], "alice.json");
+    assert_eq!(body["rows_inserted"], 1);
+    assert!(body["upload_id"].is_string());
+    assert!(body.get("error").is_none());
+
+    // Appended, not replacing the runs already there
+    let (status, _) = upload(&app_state, &[("file", "bob.json", &runs("bob"))]).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(RunsRepository::new(app_state.db.clone()).count().await.unwrap(), 2);
+}
+
+#[tokio::test]
+async fn test_failed_files_keep_the_legacy_error_code() {
+    let app_state = create_test_app_state().await;
+
+    let (status, body) = upload(
+        &app_state,
+        &[("file", "good.json", &runs("alice")), ("file", "bad.json", "not json"), ("file", "runs.txt", &runs("bob"))],
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["success"], false);
+    assert_eq!(body["error"], "FILE_UPLOAD_ERROR");
+    let files = body["files"].as_array().unwrap();
+    assert_eq!(files.len(), 3);
+    assert_eq!(files[0]["success"], true);
+    assert_eq!(files[1]["success"], false);
+    // Rejected by the same extension check as save-data
+    assert_eq!(files[2]["success"], false);
+    assert_eq!(RunsRepository::new(app_state.db.clone()).count().await.unwrap(), 1);
+
+    let (status, body) = upload(&app_state, &[]).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "NO_FILES_UPLOADED");
+}
@@ -0,0 +1,124 @@
+use axum::{
+    body::Body,
+    http::{Method, Request, StatusCode},
+    routing::{get, post},
+    Router,
+};
+use serde_json::Value;
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}},
+    handlers::{admin::process_its, quality::quality_report},
+    models::{
+        performance_result::{VRAM_USAGE_KIND_ITS, VRAM_USAGE_KIND_MEMORY},
+        quality_report::{QUALITY_CHECK_VRAM_USAGE_MEMORY, QUALITY_CHECK_VRAM_USAGE_UNPARSEABLE},
+        runs::Run,
+    },
+    repositories::{
+        performance_result_repository::PerformanceResultRepository,
+        runs_repository::RunsRepository,
+        traits::Repository,
+    },
+};
+
+async fn create_test_app_state() -> AppState {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    AppState {
+        db: db_pool,
+        settings: Settings::default(),
+    }
+}
+
+fn create_app(app_state: AppState) -> Router {
+    Router::new()
+        .route("/api/process-its", post(process_its))
+        .route("/api/admin/quality-report", get(quality_report))
+        .with_state(app_state)
+}
+
+async fn send(app: &Router, method: Method, uri: &str) -> (StatusCode, Value) {
+    let request = Request::builder().method(method).uri(uri).body(Body::empty()).unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+async fn create_run(app_state: &AppState, vram_usage: &str) -> i64 {
+    RunsRepository::new(app_state.db.clone())
+        .create(Run {
+            id: None,
+            timestamp: Some("2024-01-01T00:00:00Z".to_string()),
+            vram_usage: Some(vram_usage.to_string()),
+            info: None,
+            system_info: None,
+            model_info: None,
+            device_info: None,
+            xformers: None,
+            model_name: None,
+            user: None,
+            notes: None,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap()
+        .id
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_memory_values_are_classified_and_reported() {
+    let app_state = create_test_app_state().await;
+    let its_run = create_run(&app_state, "10.5/11.2").await;
+    let gb_run = create_run(&app_state, "7.5GB/24GB").await;
+    let mb_run = create_run(&app_state, "8123/24564").await;
+    let empty_run = create_run(&app_state, "n/a").await;
+    let app = create_app(app_state.clone());
+
+    // process-its rebuilds through its transaction, so it runs on the single test connection
+    let (status, body) = send(&app, Method::POST, "/api/process-its").await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+
+    let results = PerformanceResultRepository::new(app_state.db.clone()).find_all().await.unwrap();
+    let result = |run_id: i64| results.iter().find(|result| result.run_id == Some(run_id)).unwrap();
+    assert_eq!(result(its_run).vram_usage_kind.as_deref(), Some(VRAM_USAGE_KIND_ITS));
+    assert!(result(its_run).avg_its.is_some());
+    for run_id in [gb_run, mb_run] {
+        assert_eq!(result(run_id).vram_usage_kind.as_deref(), Some(VRAM_USAGE_KIND_MEMORY));
+        // Without an average the run drops out of every ITS statistic
+        assert!(result(run_id).avg_its.is_none());
+    }
+
+    let (status, body) = send(&app, Method::GET, "/api/admin/quality-report").await;
+    assert_eq!(status, StatusCode::OK);
+    let entries = body["data"]["entries"].as_array().unwrap();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0]["check"], QUALITY_CHECK_VRAM_USAGE_MEMORY);
+    assert_eq!(entries[0]["affected_rows"], 2);
+    assert_eq!(entries[0]["sample_run_ids"], serde_json::json!([gb_run, mb_run]));
+    assert_eq!(entries[1]["check"], QUALITY_CHECK_VRAM_USAGE_UNPARSEABLE);
+    assert_eq!(entries[1]["sample_run_ids"], serde_json::json!([empty_run]));
+}
+
+#[tokio::test]
+async fn test_clean_dataset_has_empty_report() {
+    let app_state = create_test_app_state().await;
+    create_run(&app_state, "1.5/2.1/1.8").await;
+    let app = create_app(app_state);
+
+    send(&app, Method::POST, "/api/process-its").await;
+
+    let (status, body) = send(&app, Method::GET, "/api/admin/quality-report").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["data"]["entries"].as_array().unwrap().is_empty());
+}
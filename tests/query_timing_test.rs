@@ -0,0 +1,81 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    response::IntoResponse,
+    routing::get,
+    Router,
+};
+use serde_json::Value;
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    config::{
+        database::{DatabaseConfig, create_pool, initialize_database},
+        settings::DatabaseSettings,
+    },
+    error::{AppError, DatabaseErrorKind},
+    handlers::metrics::metrics,
+    repositories::{
+        connection::{configure_query_limits, query_metrics, TimedQuery},
+        traits::Repository,
+        RunsRepository,
+    },
+};
+
+/// Time out after a second and treat every finished query as slow
+fn configure_test_limits() {
+    configure_query_limits(&DatabaseSettings {
+        query_timeout_secs: 1,
+        slow_query_ms: 0,
+        ..DatabaseSettings::default()
+    });
+}
+
+async fn fetch_metrics() -> Value {
+    let app = Router::new().route("/metrics", get(metrics));
+    let request = Request::builder().uri("/metrics").body(Body::empty()).unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+#[tokio::test]
+async fn test_slow_queries_are_counted_in_metrics() {
+    configure_test_limits();
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let pool = create_pool(&db_config).await.unwrap();
+    initialize_database(&pool).await.unwrap();
+    let before = query_metrics();
+
+    assert_eq!(RunsRepository::new(pool).count().await.unwrap(), 0);
+
+    // Other tests in this binary may run queries concurrently
+    let body = fetch_metrics().await;
+    assert!(body["data"]["database"]["queries"].as_u64().unwrap() > before.queries);
+    assert!(body["data"]["database"]["slow_queries"].as_u64().unwrap() > before.slow_queries);
+}
+
+#[tokio::test]
+async fn test_query_past_the_timeout_is_abandoned() {
+    configure_test_limits();
+    let before = query_metrics();
+
+    let err = std::future::pending::<Result<(), sqlx::Error>>()
+        .timed("test.pending", 2)
+        .await
+        .unwrap_err();
+
+    assert_eq!(DatabaseErrorKind::of(&err), DatabaseErrorKind::Timeout);
+    assert!(query_metrics().timed_out_queries > before.timed_out_queries);
+
+    let response = AppError::Database(err).into_response();
+    assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body: Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(body["error"]["code"], "QUERY_TIMEOUT");
+}
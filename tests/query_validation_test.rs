@@ -0,0 +1,87 @@
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    Router,
+};
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}},
+    error::i18n::ErrorCatalog,
+    router::create_router,
+};
+
+async fn create_test_app() -> Router {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    let app_state = AppState {
+        db: db_pool,
+        settings: Settings::default(),
+    };
+    create_router(app_state, Arc::new(ErrorCatalog::bundled().unwrap()))
+}
+
+async fn fetch(app: &Router, uri: &str) -> (StatusCode, Value) {
+    let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+#[tokio::test]
+async fn test_bad_query_parameters_are_listed_with_allowed_values() {
+    let app = create_test_app().await;
+
+    let (status, body) = fetch(&app, "/api/leaderboard?metric=speed&limit=lots&count_mode=exact").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"]["code"], "INVALID_QUERY");
+    let params = body["error"]["params"].as_array().unwrap();
+    assert_eq!(params.len(), 2, "{}", body);
+    assert_eq!(params[0]["field"], "metric");
+    assert_eq!(params[0]["allowed"], json!(["score", "avg_its"]));
+    assert_eq!(params[1]["field"], "limit");
+    assert!(params[1].get("allowed").is_none());
+
+    // Values that parse but fail validation are listed the same way
+    let (status, body) = fetch(&app, "/api/leaderboard?limit=0&tag=not%20a%20tag").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let fields: Vec<&str> = body["error"]["params"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|param| param["field"].as_str().unwrap())
+        .collect();
+    assert_eq!(fields, ["limit", "tag"]);
+
+    let (status, body) = fetch(&app, "/api/stats/counts?by=os&filter_by=user&filter_value=x").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"]["params"][0]["field"], "filter_by");
+    assert!(body["error"]["params"][0]["allowed"].as_array().unwrap().contains(&json!("gpu_brand")));
+
+    let (status, body) = fetch(&app, "/api/stats/counts?by=os&filter_value=x").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"]["params"][0]["field"], "filter_by");
+}
+
+#[tokio::test]
+async fn test_blank_enumerated_parameters_use_their_default() {
+    let app = create_test_app().await;
+
+    let (status, body) = fetch(&app, "/api/leaderboard?count_mode=").await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["meta"]["count_mode"], "exact");
+
+    let (status, _) = fetch(&app, "/api/stats/averages?by=os&metric=%20").await;
+    assert_eq!(status, StatusCode::OK);
+}
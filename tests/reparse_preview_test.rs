@@ -0,0 +1,126 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    routing::post,
+    Router,
+};
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}},
+    handlers::reparse::reparse_preview,
+    models::{gpu::Gpu, runs::Run},
+    repositories::{gpu_repository::GpuRepository, runs_repository::RunsRepository, traits::Repository},
+};
+
+async fn create_test_app_state() -> AppState {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    AppState {
+        db: db_pool,
+        settings: Settings::default(),
+    }
+}
+
+async fn preview(app_state: &AppState, run_id: i64) -> (StatusCode, Value) {
+    let app = Router::new()
+        .route("/api/admin/reparse-preview", post(reparse_preview))
+        .with_state(app_state.clone());
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/admin/reparse-preview")
+        .header("content-type", "application/json")
+        .body(Body::from(json!({ "run_id": run_id }).to_string()))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+async fn create_run(app_state: &AppState, system_info: Option<&str>) -> i64 {
+    RunsRepository::new(app_state.db.clone())
+        .create(Run {
+            id: None,
+            timestamp: Some("2024-01-01T00:00:00Z".to_string()),
+            vram_usage: Some("8.0/12.0/16.0".to_string()),
+            info: Some("app:stable-diffusion-webui updated:2024-01-01 hash:abc123".to_string()),
+            system_info: system_info.map(str::to_string),
+            model_info: Some("torch:2.0.1".to_string()),
+            device_info: Some("device:NVIDIA GeForce RTX 3080 driver:535.86".to_string()),
+            xformers: Some("True".to_string()),
+            model_name: Some("v1-5-pruned-emaonly.safetensors".to_string()),
+            user: Some("alice".to_string()),
+            notes: None,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap()
+        .id
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_preview_shows_reparsed_rows_beside_current_ones_without_writing() {
+    let app_state = create_test_app_state().await;
+    let run_id = create_run(&app_state, None).await;
+    // Parsed by an older parser, with a device name since corrected
+    GpuRepository::new(app_state.db.clone())
+        .create(Gpu {
+            id: None,
+            run_id: Some(run_id),
+            device: Some("NVIDIA GeForce RTX".to_string()),
+            driver: None,
+            gpu_chip: None,
+            brand: None,
+            is_laptop: None,
+            is_integrated: None,
+            is_cloud: None,
+            gpu_index: 0,
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap();
+
+    let (status, body) = preview(&app_state, run_id).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    let preview = &body["data"];
+    assert_eq!(preview["run_id"], run_id);
+
+    assert_eq!(preview["gpus"]["current"][0]["device"], "NVIDIA GeForce RTX");
+    assert_eq!(preview["gpus"]["reparsed"][0]["device"], "NVIDIA GeForce RTX 3080");
+    assert!(preview["performance"]["current"].is_null());
+    assert_eq!(preview["performance"]["reparsed"]["avg_its"], 12.0);
+    assert_eq!(preview["app_details"]["reparsed"]["app_name"], "stable-diffusion-webui");
+    assert!(preview["app_release"]["reparsed"].is_object());
+    assert_eq!(preview["libraries"]["reparsed"]["torch"], "2.0.1");
+    assert_eq!(preview["run_details"]["reparsed"]["model_name"], "v1-5-pruned-emaonly.safetensors");
+    // No system_info string, so that step would write nothing
+    assert!(preview["system_info"]["reparsed"].is_null());
+    assert!(preview["system_info"]["skipped"].is_string());
+    assert!(preview["gpus"]["skipped"].is_null());
+
+    // Nothing was written
+    let gpus = GpuRepository::new(app_state.db.clone()).find_by_run_ids(&[run_id]).await.unwrap();
+    assert_eq!(gpus[&run_id].len(), 1);
+    assert_eq!(gpus[&run_id][0].device.as_deref(), Some("NVIDIA GeForce RTX"));
+}
+
+#[tokio::test]
+async fn test_preview_of_a_missing_run_is_not_found() {
+    let app_state = create_test_app_state().await;
+
+    let (status, _) = preview(&app_state, 404).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
@@ -1,6 +1,6 @@
 use sqlx::SqlitePool;
 use sd_its_benchmark::models::{runs::Run, performance_result::PerformanceResult, app_details::AppDetails, system_info::SystemInfo, libraries::Libraries, gpu::Gpu, run_more_details::RunMoreDetails, model_map::ModelMap, gpu_map::GpuMap, gpu_base::GpuBase};
-use sd_its_benchmark::repositories::{RunsRepository, PerformanceResultRepository, AppDetailsRepository, SystemInfoRepository, LibrariesRepository, GpuRepository, RunMoreDetailsRepository, ModelMapRepository, GpuMapRepository, GpuBaseRepository, traits::Repository};
+use sd_its_benchmark::repositories::{query_builder::Filter, RunsRepository, PerformanceResultRepository, AppDetailsRepository, SystemInfoRepository, LibrariesRepository, GpuRepository, RunMoreDetailsRepository, ModelMapRepository, GpuMapRepository, GpuBaseRepository, traits::Repository};
 
 async fn create_test_pool() -> SqlitePool {
     SqlitePool::connect("sqlite::memory:").await.unwrap()
@@ -31,6 +31,8 @@ async fn test_runs_repository_basic_operations() {
         model_name: Some("test-model".to_string()),
         user: Some("test-user".to_string()),
         notes: Some("Test notes".to_string()),
+        created_at: None,
+        updated_at: None,
     };
 
     let created_run = repo.create(new_run).await.expect("Failed to create run");
@@ -59,6 +61,67 @@ async fn test_runs_repository_basic_operations() {
     assert_eq!(count_after_delete, 0);
 }
 
+#[tokio::test]
+async fn test_exists_and_count_where() {
+    let pool = create_test_pool().await;
+
+    // Run migrations to create tables
+    sqlx::migrate!("./migrations")
+        .run(&pool)
+        .await
+        .expect("Failed to run migrations");
+
+    let runs_repo = RunsRepository::new(pool.clone());
+    let mut run_ids = Vec::new();
+    for user in ["alice", "alice", "bob"] {
+        let run = Run {
+            id: None,
+            timestamp: Some("2024-01-01T00:00:00Z".to_string()),
+            vram_usage: None,
+            info: None,
+            system_info: None,
+            model_info: None,
+            device_info: None,
+            xformers: None,
+            model_name: None,
+            user: Some(user.to_string()),
+            notes: None,
+            created_at: None,
+            updated_at: None,
+        };
+        run_ids.push(runs_repo.create(run).await.expect("Failed to create run").id.unwrap());
+    }
+
+    assert!(runs_repo.exists(run_ids[0]).await.expect("Failed to check run"));
+    assert!(!runs_repo.exists(run_ids[2] + 1).await.expect("Failed to check missing run"));
+
+    assert_eq!(runs_repo.count_where(&Filter::new("user", "alice")).await.expect("Failed to count runs"), 2);
+    assert_eq!(runs_repo.count_where(&Filter::new("user", "carol")).await.expect("Failed to count runs"), 0);
+
+    // Integer columns match a filter value given as a number
+    let perf_repo = PerformanceResultRepository::new(pool);
+    for run_id in &run_ids[..2] {
+        let result = PerformanceResult {
+            id: None,
+            run_id: Some(*run_id),
+            its: None,
+            avg_its: Some(10.0),
+            vram_usage_kind: None,
+            created_at: None,
+            updated_at: None,
+        };
+        perf_repo.create(result).await.expect("Failed to create performance result");
+    }
+    assert_eq!(
+        perf_repo.count_where(&Filter::new("run_id", run_ids[0])).await.expect("Failed to count results"),
+        1
+    );
+
+    // The field is written into the SQL, so only plain column names are accepted
+    let error = runs_repo.count_where(&Filter::new("user = user OR 1", "x")).await.unwrap_err();
+    assert!(matches!(error, sqlx::Error::ColumnNotFound(_)));
+}
+
 #[tokio::test]
 async fn test_performance_result_repository_basic_operations() {
     let pool = create_test_pool().await;
@@ -83,6 +146,8 @@ async fn test_performance_result_repository_basic_operations() {
         model_name: Some("test-model".to_string()),
         user: Some("test-user".to_string()),
         notes: Some("Test notes".to_string()),
+        created_at: None,
+        updated_at: None,
     };
     let created_run = runs_repo.create(test_run).await.expect("Failed to create test run");
     let run_id = created_run.id.unwrap();
@@ -95,6 +160,9 @@ async fn test_performance_result_repository_basic_operations() {
         run_id: Some(run_id),
         its: Some("10.5".to_string()),
         avg_its: Some(10.5),
+        vram_usage_kind: None,
+        created_at: None,
+        updated_at: None,
     };
 
     let created_result = repo.create(new_result).await.expect("Failed to create performance result");
@@ -129,6 +197,84 @@ async fn test_performance_result_repository_basic_operations() {
     assert_eq!(count_after_delete, 0);
 }
 
+#[tokio::test]
+async fn test_child_repositories_replace_rows_of_the_same_run() {
+    let pool = create_test_pool().await;
+
+    // Run migrations to create tables
+    sqlx::migrate!("./migrations")
+        .run(&pool)
+        .await
+        .expect("Failed to run migrations");
+
+    let runs_repo = RunsRepository::new(pool.clone());
+    let created_run = runs_repo
+        .create(Run {
+            id: None,
+            timestamp: Some("2024-01-01T00:00:00Z".to_string()),
+            vram_usage: Some("10.5".to_string()),
+            info: None,
+            system_info: None,
+            model_info: None,
+            device_info: None,
+            xformers: None,
+            model_name: None,
+            user: None,
+            notes: None,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .expect("Failed to create test run");
+    let run_id = created_run.id.unwrap();
+
+    // Creating a second performance result for the run replaces the first
+    let repo = PerformanceResultRepository::new(pool.clone());
+    let performance_result = |avg_its: f64| PerformanceResult {
+        id: None,
+        run_id: Some(run_id),
+        its: Some(avg_its.to_string()),
+        avg_its: Some(avg_its),
+        vram_usage_kind: None,
+        created_at: None,
+        updated_at: None,
+    };
+    let first = repo.create(performance_result(10.5)).await.expect("Failed to create performance result");
+    let second = repo.create(performance_result(12.0)).await.expect("Failed to replace performance result");
+    assert_eq!(second.id, first.id);
+    assert_eq!(second.created_at, first.created_at);
+
+    let results = repo.find_by_run_id(run_id).await.unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].avg_its, Some(12.0));
+
+    // GPU rows are unique per run and GPU position
+    let gpu_repo = GpuRepository::new(pool);
+    let gpu = |gpu_index: i64, device: &str| Gpu {
+        id: None,
+        run_id: Some(run_id),
+        device: Some(device.to_string()),
+        driver: None,
+        gpu_chip: None,
+        brand: None,
+        is_laptop: None,
+        is_integrated: None,
+        is_cloud: None,
+        gpu_index,
+        parser_version: None,
+        created_at: None,
+        updated_at: None,
+    };
+    gpu_repo.create(gpu(0, "RTX 3090")).await.unwrap();
+    gpu_repo.create(gpu(1, "RTX 3090")).await.unwrap();
+    gpu_repo.create(gpu(1, "RTX 3060")).await.unwrap();
+
+    let mut gpus = gpu_repo.find_by_run_id(run_id).await.unwrap();
+    gpus.sort_by_key(|gpu| gpu.gpu_index);
+    let devices: Vec<Option<&str>> = gpus.iter().map(|gpu| gpu.device.as_deref()).collect();
+    assert_eq!(devices, vec![Some("RTX 3090"), Some("RTX 3060")]);
+}
+
 #[tokio::test]
 async fn test_app_details_repository_basic_operations() {
     let pool = create_test_pool().await;
@@ -153,6 +299,8 @@ async fn test_app_details_repository_basic_operations() {
         model_name: Some("test-model".to_string()),
         user: Some("test-user".to_string()),
         notes: Some("Test notes".to_string()),
+        created_at: None,
+        updated_at: None,
     };
     let created_run = runs_repo.create(test_run).await.expect("Failed to create test run");
     let run_id = created_run.id.unwrap();
@@ -167,6 +315,9 @@ async fn test_app_details_repository_basic_operations() {
         updated: Some("2024-01-01T00:00:00Z".to_string()),
         hash: Some("abc123".to_string()),
         url: Some("https://example.com".to_string()),
+        parser_version: None,
+        created_at: None,
+        updated_at: None,
     };
 
     let created_app_details = repo.create(new_app_details).await.expect("Failed to create app details");
@@ -230,6 +381,8 @@ async fn test_system_info_repository_basic_operations() {
         model_name: Some("test-model".to_string()),
         user: Some("test-user".to_string()),
         notes: Some("Test notes".to_string()),
+        created_at: None,
+        updated_at: None,
     };
     let created_run = runs_repo.create(test_run).await.expect("Failed to create test run");
     let run_id = created_run.id.unwrap();
@@ -245,6 +398,12 @@ async fn test_system_info_repository_basic_operations() {
         system: Some("Windows".to_string()),
         release: Some("11.0.0".to_string()),
         python: Some("3.9.0".to_string()),
+        ram_gb: None,
+        swap_gb: None,
+        os_bits: None,
+        parser_version: None,
+        created_at: None,
+        updated_at: None,
     };
 
     let created_system_info = repo.create(new_system_info).await.expect("Failed to create system info");
@@ -313,6 +472,8 @@ async fn test_libraries_repository_basic_operations() {
         model_name: Some("test-model".to_string()),
         user: Some("test-user".to_string()),
         notes: Some("Test notes".to_string()),
+        created_at: None,
+        updated_at: None,
     };
     let created_run = runs_repo.create(test_run).await.expect("Failed to create test run");
     let run_id = created_run.id.unwrap();
@@ -328,6 +489,9 @@ async fn test_libraries_repository_basic_operations() {
         xformers1: Some("0.0.17".to_string()),
         diffusers: Some("0.7.2".to_string()),
         transformers: Some("4.19.2".to_string()),
+        parser_version: None,
+        created_at: None,
+        updated_at: None,
     };
 
     let created_libraries = repo.create(new_libraries).await.expect("Failed to create libraries");
@@ -386,6 +550,8 @@ async fn test_gpu_repository_basic_operations() {
         model_name: Some("test-model".to_string()),
         user: Some("test-user".to_string()),
         notes: Some("Test notes".to_string()),
+        created_at: None,
+        updated_at: None,
     };
     let created_run = runs_repo.create(test_run).await.expect("Failed to create test run");
     let run_id = created_run.id.unwrap();
@@ -401,6 +567,12 @@ async fn test_gpu_repository_basic_operations() {
         gpu_chip: Some("AD102".to_string()),
         brand: Some("NVIDIA".to_string()),
         is_laptop: Some(false),
+        is_integrated: None,
+        is_cloud: None,
+        gpu_index: 0,
+        parser_version: None,
+        created_at: None,
+        updated_at: None,
     };
 
     let created_gpu = repo.create(new_gpu).await.expect("Failed to create GPU");
@@ -469,6 +641,8 @@ async fn test_run_more_details_repository_basic_operations() {
         model_name: Some("test-model".to_string()),
         user: Some("test-user".to_string()),
         notes: Some("Test notes".to_string()),
+        created_at: None,
+        updated_at: None,
     };
     let created_run = runs_repo.create(test_run).await.expect("Failed to create test run");
     let run_id = created_run.id.unwrap();
@@ -484,6 +658,8 @@ async fn test_run_more_details_repository_basic_operations() {
         user: Some("test-user".to_string()),
         notes: Some("Additional test details".to_string()),
         model_map_id: Some(1),
+        created_at: None,
+        updated_at: None,
     };
 
     let created_details = repo.create(new_details).await.expect("Failed to create run more details");
@@ -545,6 +721,9 @@ async fn test_model_map_repository_basic_operations() {
         id: None,
         model_name: Some("stable-diffusion-v1-5".to_string()),
         base_model: Some("stable-diffusion-v1-5".to_string()),
+        version: 1,
+        created_at: None,
+        updated_at: None,
     };
 
     let created_model_map = repo.create(new_model_map).await.expect("Failed to create model map");
@@ -573,6 +752,14 @@ async fn test_model_map_repository_basic_operations() {
     updated_model_map.base_model = Some("stable-diffusion-v2-1".to_string());
     let updated_model_map = repo.update(updated_model_map).await.expect("Failed to update model map");
     assert_eq!(updated_model_map.base_model, Some("stable-diffusion-v2-1".to_string()));
+    assert_eq!(updated_model_map.version, 2);
+
+    // Test update with a stale version
+    let stale_update = repo.update(found_model_map.clone()).await;
+    assert!(matches!(stale_update, Err(sqlx::Error::RowNotFound)));
+    let current = repo.find_by_id(created_model_map.id.unwrap()).await.unwrap().unwrap();
+    assert_eq!(current.base_model, Some("stable-diffusion-v2-1".to_string()));
+    assert_eq!(current.version, 2);
 
     // Test count
     let count = repo.count().await.expect("Failed to count model maps");
@@ -615,6 +802,9 @@ async fn test_gpu_map_repository_basic_operations() {
         id: None,
         gpu_name: Some("RTX 4090".to_string()),
         base_gpu_id: Some(gpu_base_id),
+        version: 1,
+        created_at: None,
+        updated_at: None,
     };
 
     let created_gpu_map = repo.create(new_gpu_map).await.expect("Failed to create GPU map");
@@ -671,6 +861,11 @@ async fn test_gpu_base_repository_basic_operations() {
         id: None,
         name: "RTX 4090 Base".to_string(),
         brand: Some("NVIDIA".to_string()),
+        architecture: Some("Ada Lovelace".to_string()),
+        generation: Some("GeForce 40".to_string()),
+        version: 1,
+        created_at: None,
+        updated_at: None,
     };
 
     let created_gpu_base = repo.create(new_gpu_base).await.expect("Failed to create GPU base");
@@ -699,6 +894,11 @@ async fn test_gpu_base_repository_basic_operations() {
     updated_gpu_base.brand = Some("NVIDIA Corporation".to_string());
     let updated_gpu_base = repo.update(updated_gpu_base).await.expect("Failed to update GPU base");
     assert_eq!(updated_gpu_base.brand, Some("NVIDIA Corporation".to_string()));
+    assert_eq!(updated_gpu_base.version, 2);
+
+    // Test update with a stale version
+    let stale_update = repo.update(found_gpu_base.clone()).await;
+    assert!(matches!(stale_update, Err(sqlx::Error::RowNotFound)));
 
     // Test count
     let count = repo.count().await.expect("Failed to count GPU bases");
@@ -0,0 +1,60 @@
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    routing::post,
+    Json, Router,
+};
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    config::settings::RequestLoggingConfig,
+    middleware::logging::log_requests,
+};
+
+fn create_app() -> Router {
+    let config = RequestLoggingConfig {
+        enabled: true,
+        max_body_bytes: 8,
+        ..RequestLoggingConfig::default()
+    };
+
+    Router::new()
+        .route("/echo-json", post(|Json(body): Json<Value>| async move { Json(body) }))
+        .route("/echo-text", post(|body: String| async move { body }))
+        .layer(axum::middleware::from_fn_with_state(Arc::new(config), log_requests))
+}
+
+#[tokio::test]
+async fn test_sampled_json_body_still_reaches_the_handler() {
+    let payload = json!({ "file_name": "benchmark-export.json", "parts": 12 });
+    let request = Request::builder()
+        .method("POST")
+        .uri("/echo-json")
+        .header(header::CONTENT_TYPE, "application/json")
+        .header(header::AUTHORIZATION, "Bearer secret")
+        .body(Body::from(payload.to_string()))
+        .unwrap();
+
+    let response = create_app().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    assert_eq!(serde_json::from_slice::<Value>(&body).unwrap(), payload);
+}
+
+#[tokio::test]
+async fn test_non_json_body_is_streamed_through() {
+    let request = Request::builder()
+        .method("POST")
+        .uri("/echo-text")
+        .header(header::CONTENT_TYPE, "text/plain")
+        .body(Body::from("raw upload bytes"))
+        .unwrap();
+
+    let response = create_app().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    assert_eq!(&body[..], b"raw upload bytes");
+}
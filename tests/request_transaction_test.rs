@@ -0,0 +1,104 @@
+use axum::{
+    body::Body,
+    extract::Path,
+    http::{Request, StatusCode},
+    routing::post,
+    Router,
+};
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}},
+    error::types::AppError,
+    middleware::request_transaction::{transaction_per_request, RequestTransaction},
+};
+
+async fn create_test_app_state() -> AppState {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    AppState {
+        db: db_pool,
+        settings: Settings::default(),
+    }
+}
+
+/// Insert two tags through the request transaction, then fail if asked to
+async fn create_tags(
+    request_tx: RequestTransaction,
+    Path(outcome): Path<String>,
+) -> Result<&'static str, AppError> {
+    let mut tx = request_tx.begin().await?;
+    for name in ["first", "second"] {
+        sqlx::query("INSERT INTO tags (name) VALUES (?)")
+            .bind(name)
+            .execute(&mut **tx)
+            .await
+            .map_err(AppError::Database)?;
+    }
+
+    match outcome.as_str() {
+        "ok" => Ok("created"),
+        _ => Err(AppError::validation("failing after the writes")),
+    }
+}
+
+fn create_app(app_state: AppState) -> Router {
+    Router::new()
+        .route("/tags/{outcome}", post(create_tags))
+        .route_layer(axum::middleware::from_fn_with_state(app_state.clone(), transaction_per_request))
+        .with_state(app_state)
+}
+
+async fn post_status(app: Router, uri: &str) -> StatusCode {
+    let request = Request::builder().method("POST").uri(uri).body(Body::empty()).unwrap();
+    app.oneshot(request).await.unwrap().status()
+}
+
+async fn tag_count(app_state: &AppState) -> i64 {
+    sqlx::query_scalar("SELECT COUNT(*) FROM tags")
+        .fetch_one(&app_state.db)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_request_transaction_commits_on_success() {
+    let app_state = create_test_app_state().await;
+
+    let status = post_status(create_app(app_state.clone()), "/tags/ok").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(tag_count(&app_state).await, 2);
+}
+
+#[tokio::test]
+async fn test_request_transaction_rolls_back_on_error() {
+    let app_state = create_test_app_state().await;
+
+    let status = post_status(create_app(app_state.clone()), "/tags/fail").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(tag_count(&app_state).await, 0);
+
+    // The connection is released, so the next request still works
+    let status = post_status(create_app(app_state.clone()), "/tags/ok").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(tag_count(&app_state).await, 2);
+}
+
+#[tokio::test]
+async fn test_request_transaction_requires_middleware() {
+    let app_state = create_test_app_state().await;
+    let app = Router::new()
+        .route("/tags/{outcome}", post(create_tags))
+        .with_state(app_state.clone());
+
+    let status = post_status(app, "/tags/ok").await;
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(tag_count(&app_state).await, 0);
+}
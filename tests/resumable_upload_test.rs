@@ -0,0 +1,326 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    routing::{get, post, put},
+    Router,
+};
+use chrono::{Duration, Utc};
+use serde_json::{json, Value};
+use tempfile::TempDir;
+use tower::ServiceExt;
+
+mod common;
+
+use common::test_run;
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}},
+    handlers::uploads::{complete_upload, get_upload, init_upload, upload_part},
+    middleware::tenant::DEFAULT_TENANT,
+    repositories::{runs_repository::RunsRepository, traits::Repository, upload_session_repository::UploadSessionRepository},
+    services::{
+        retention::{StorageArea, StorageRetention},
+        uploads::{upload_staging_dir, ResumableUploadService},
+    },
+};
+
+async fn create_test_app_state(temp_dir: &TempDir) -> AppState {
+    let mut settings = Settings::default();
+    settings.file_upload.temp_dir = temp_dir.path().to_path_buf();
+
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    AppState {
+        db: db_pool,
+        settings,
+    }
+}
+
+fn create_app(app_state: AppState) -> Router {
+    Router::new()
+        .route("/api/uploads/init", post(init_upload))
+        .route("/api/uploads/{id}", get(get_upload))
+        .route("/api/uploads/{id}/part/{n}", put(upload_part))
+        .route("/api/uploads/{id}/complete", post(complete_upload))
+        .with_state(app_state)
+}
+
+async fn send(app: &Router, method: &str, uri: &str, content_type: &str, body: Vec<u8>) -> (StatusCode, Value) {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("content-type", content_type)
+        .body(Body::from(body))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+fn benchmark_file() -> Vec<u8> {
+    json!([
+        {
+            "timestamp": "2024-01-01T10:00:00Z",
+            "vram_usage": "10.0/11.0",
+            "info": "app:test",
+            "system_info": "system:Linux",
+            "model_info": "torch:2.1",
+            "device_info": "device:NVIDIA GeForce RTX 4090",
+            "xformers": "True",
+            "model_name": "sdxl",
+            "user": "tester",
+            "notes": ""
+        },
+        {
+            "timestamp": "2024-01-01T11:00:00Z",
+            "vram_usage": "20.0",
+            "info": "app:test",
+            "system_info": "system:Linux",
+            "model_info": "torch:2.1",
+            "device_info": "device:NVIDIA GeForce RTX 3060",
+            "xformers": "False",
+            "model_name": "sd15",
+            "user": "tester",
+            "notes": ""
+        }
+    ])
+    .to_string()
+    .into_bytes()
+}
+
+#[tokio::test]
+async fn test_resumable_upload_flow() {
+    let temp_dir = TempDir::new().unwrap();
+    let app_state = create_test_app_state(&temp_dir).await;
+    let app = create_app(app_state.clone());
+
+    let file = benchmark_file();
+    let (first, second) = file.split_at(file.len() / 2);
+
+    let (status, init) = send(
+        &app,
+        "POST",
+        "/api/uploads/init",
+        "application/json",
+        json!({ "file_name": "runs.json", "total_parts": 2, "total_size": file.len() }).to_string().into_bytes(),
+    ).await;
+    assert_eq!(status, StatusCode::OK);
+    let id = init["data"]["id"].as_str().unwrap().to_string();
+
+    // Parts may arrive out of order
+    let (status, progress) = send(&app, "PUT", &format!("/api/uploads/{}/part/2", id), "application/octet-stream", second.to_vec()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(progress["data"]["received_parts"], 1);
+
+    // Completing with a missing part is rejected
+    let (status, _) = send(&app, "POST", &format!("/api/uploads/{}/complete", id), "application/json", vec![]).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    send(&app, "PUT", &format!("/api/uploads/{}/part/1", id), "application/octet-stream", first.to_vec()).await;
+
+    let (status, progress) = send(&app, "GET", &format!("/api/uploads/{}", id), "application/json", vec![]).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(progress["data"]["bytes_received"], file.len());
+    assert_eq!(progress["data"]["percent_complete"], 100.0);
+    assert_eq!(progress["data"]["status"], "pending");
+
+    // Completing appends; the runs already there are kept
+    let runs_repo = RunsRepository::new(app_state.db.clone());
+    runs_repo.create(test_run()).await.unwrap();
+
+    let (status, completed) = send(&app, "POST", &format!("/api/uploads/{}/complete", id), "application/json", vec![]).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(completed["rows_inserted"], 2);
+
+    let (_, progress) = send(&app, "GET", &format!("/api/uploads/{}", id), "application/json", vec![]).await;
+    assert_eq!(progress["data"]["status"], "completed");
+
+    assert_eq!(runs_repo.count().await.unwrap(), 3);
+    assert!(!temp_dir.path().join("uploads").join(&id).exists(), "Staged parts should be removed");
+}
+
+#[tokio::test]
+async fn test_resumable_upload_rejects_invalid_parts() {
+    let temp_dir = TempDir::new().unwrap();
+    let app = create_app(create_test_app_state(&temp_dir).await);
+
+    let (status, _) = send(&app, "PUT", "/api/uploads/unknown/part/1", "application/octet-stream", b"data".to_vec()).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (_, init) = send(
+        &app,
+        "POST",
+        "/api/uploads/init",
+        "application/json",
+        json!({ "file_name": "runs.json", "total_parts": 1 }).to_string().into_bytes(),
+    ).await;
+    let id = init["data"]["id"].as_str().unwrap().to_string();
+
+    let (status, _) = send(&app, "PUT", &format!("/api/uploads/{}/part/2", id), "application/octet-stream", b"data".to_vec()).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_abandoned_upload_is_pruned_and_expired() {
+    let temp_dir = TempDir::new().unwrap();
+    let app_state = create_test_app_state(&temp_dir).await;
+    let app = create_app(app_state.clone());
+
+    let (_, init) = send(
+        &app,
+        "POST",
+        "/api/uploads/init",
+        "application/json",
+        json!({ "file_name": "runs.json", "total_parts": 2 }).to_string().into_bytes(),
+    ).await;
+    let id = init["data"]["id"].as_str().unwrap().to_string();
+    send(&app, "PUT", &format!("/api/uploads/{}/part/1", id), "application/octet-stream", b"data".to_vec()).await;
+
+    let settings = &app_state.settings;
+    let retention = StorageRetention::from_settings(settings);
+    let later = Utc::now() + Duration::days(settings.retention.max_age_days as i64 + 1);
+    let report = retention.run(later, false).unwrap();
+    assert_eq!(report.files.len(), 1);
+    assert_eq!(report.files[0].area, StorageArea::UploadStaging);
+    assert_eq!(report.files[0].size_bytes, 4);
+    assert!(!temp_dir.path().join("uploads").join(&id).exists());
+
+    ResumableUploadService::new(
+        UploadSessionRepository::new(app_state.db.clone()),
+        upload_staging_dir(&settings.file_upload),
+        settings.file_upload.max_resumable_size_mb * 1024 * 1024,
+    )
+    .expire(&id)
+    .await
+    .unwrap();
+
+    let (_, progress) = send(&app, "GET", &format!("/api/uploads/{}", id), "application/json", vec![]).await;
+    assert_eq!(progress["data"]["status"], "failed");
+    let (status, _) = send(&app, "PUT", &format!("/api/uploads/{}/part/2", id), "application/octet-stream", b"data".to_vec()).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_quota_pressure_keeps_an_active_upload() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut app_state = create_test_app_state(&temp_dir).await;
+    app_state.settings.retention.max_total_bytes = Some(1);
+    app_state.settings.retention.backup_dir = temp_dir.path().join("backups");
+    app_state.settings.logging.file_path = None;
+    let app = create_app(app_state.clone());
+
+    let file = benchmark_file();
+    let (first, second) = file.split_at(file.len() / 2);
+    let (_, init) = send(
+        &app,
+        "POST",
+        "/api/uploads/init",
+        "application/json",
+        json!({ "file_name": "runs.json", "total_parts": 2 }).to_string().into_bytes(),
+    ).await;
+    let id = init["data"]["id"].as_str().unwrap().to_string();
+    send(&app, "PUT", &format!("/api/uploads/{}/part/1", id), "application/octet-stream", first.to_vec()).await;
+
+    let report = StorageRetention::from_settings(&app_state.settings).run(Utc::now(), false).unwrap();
+    assert!(report.total_bytes > 1);
+    assert!(report.files.is_empty());
+    assert!(temp_dir.path().join("uploads").join(&id).join("part_000001").exists());
+
+    let (status, _) = send(&app, "PUT", &format!("/api/uploads/{}/part/2", id), "application/octet-stream", second.to_vec()).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, completed) = send(&app, "POST", &format!("/api/uploads/{}/complete", id), "application/json", vec![]).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(completed["rows_inserted"], 2);
+}
+
+#[tokio::test]
+async fn test_interrupted_complete_releases_the_upload() {
+    let temp_dir = TempDir::new().unwrap();
+    let app_state = create_test_app_state(&temp_dir).await;
+    let app = create_app(app_state.clone());
+    let service = ResumableUploadService::new(
+        UploadSessionRepository::new(app_state.db.clone()),
+        upload_staging_dir(&app_state.settings.file_upload),
+        app_state.settings.file_upload.max_resumable_size_mb * 1024 * 1024,
+    );
+
+    let mut ids = Vec::new();
+    for _ in 0..2 {
+        let (_, init) = send(
+            &app,
+            "POST",
+            "/api/uploads/init",
+            "application/json",
+            json!({ "file_name": "runs.json", "total_parts": 1 }).to_string().into_bytes(),
+        ).await;
+        let id = init["data"]["id"].as_str().unwrap().to_string();
+        send(&app, "PUT", &format!("/api/uploads/{}/part/1", id), "application/octet-stream", benchmark_file()).await;
+        ids.push(id);
+    }
+
+    // The request is dropped between claiming the upload and recording the outcome
+    let (_, _, claim) = service.assemble(DEFAULT_TENANT, &ids[0]).await.unwrap();
+    drop(claim);
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    let (_, progress) = send(&app, "GET", &format!("/api/uploads/{}", ids[0]), "application/json", vec![]).await;
+    assert_eq!(progress["data"]["status"], "failed");
+    assert!(!temp_dir.path().join("uploads").join(&ids[0]).exists());
+
+    // The process dies instead, so nothing releases the claim until the cleanup
+    let (_, _, claim) = service.assemble(DEFAULT_TENANT, &ids[1]).await.unwrap();
+    std::mem::forget(claim);
+    assert_eq!(service.expire_stalled(Utc::now()).await.unwrap(), 0);
+    assert_eq!(service.expire_stalled(Utc::now() + Duration::hours(2)).await.unwrap(), 1);
+    let (_, progress) = send(&app, "GET", &format!("/api/uploads/{}", ids[1]), "application/json", vec![]).await;
+    assert_eq!(progress["data"]["status"], "failed");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_concurrent_completes_ingest_the_upload_once() {
+    // A file database, so the completes really run side by side on separate connections
+    let temp_dir = TempDir::new().unwrap();
+    let mut settings = Settings::default();
+    settings.file_upload.temp_dir = temp_dir.path().to_path_buf();
+    let db_config = DatabaseConfig {
+        url: format!("sqlite:{}?mode=rwc", temp_dir.path().join("uploads.db").display()),
+        max_connections: 8,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.unwrap();
+    initialize_database(&db_pool).await.unwrap();
+    let app_state = AppState { db: db_pool, settings };
+    let app = create_app(app_state.clone());
+
+    let (_, init) = send(
+        &app,
+        "POST",
+        "/api/uploads/init",
+        "application/json",
+        json!({ "file_name": "runs.json", "total_parts": 1 }).to_string().into_bytes(),
+    ).await;
+    let id = init["data"]["id"].as_str().unwrap().to_string();
+    send(&app, "PUT", &format!("/api/uploads/{}/part/1", id), "application/octet-stream", benchmark_file()).await;
+
+    let completes: Vec<_> = (0..4)
+        .map(|_| {
+            let (app, uri) = (app.clone(), format!("/api/uploads/{}/complete", id));
+            tokio::spawn(async move { send(&app, "POST", &uri, "application/json", vec![]).await.0 })
+        })
+        .collect();
+    let mut statuses = Vec::new();
+    for complete in completes {
+        statuses.push(complete.await.unwrap());
+    }
+    statuses.sort();
+    assert_eq!(statuses, [StatusCode::OK, StatusCode::CONFLICT, StatusCode::CONFLICT, StatusCode::CONFLICT]);
+
+    assert_eq!(RunsRepository::new(app_state.db.clone()).count().await.unwrap(), 2);
+    let (_, progress) = send(&app, "GET", &format!("/api/uploads/{}", id), "application/json", vec![]).await;
+    assert_eq!(progress["data"]["status"], "completed");
+}
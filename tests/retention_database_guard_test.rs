@@ -0,0 +1,58 @@
+use std::{
+    fs,
+    path::Path,
+    time::{Duration, SystemTime},
+};
+
+use chrono::Utc;
+use tempfile::TempDir;
+
+use sd_its_benchmark::{
+    config::{Settings, validate_config},
+    services::retention::StorageRetention,
+};
+
+const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Write `size` bytes to `path`, last modified `days_ago`
+fn write_file(path: &Path, size: usize, days_ago: u32) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, vec![b'x'; size]).unwrap();
+    let file = fs::File::options().write(true).open(path).unwrap();
+    file.set_modified(SystemTime::now() - DAY * days_ago).unwrap();
+}
+
+// The only test in this binary, since it sets DATABASE_URL for the whole process
+#[test]
+fn test_an_area_holding_the_live_database_is_rejected() {
+    let dir = TempDir::new().unwrap();
+    let database = dir.path().join("backups/sd_its.db");
+    // SAFETY: no other thread of this test binary reads or writes the environment
+    unsafe { std::env::set_var("DATABASE_URL", format!("sqlite:{}", database.display())) };
+
+    let mut settings = Settings::default();
+    settings.file_upload.temp_dir = dir.path().join("temp");
+    settings.retention.backup_dir = dir.path().join("backups");
+    settings.logging.file_path = None;
+    // Not the file the server opens, so it does not protect anything
+    settings.database.url = format!("sqlite:{}", dir.path().join("temp/uploads/other.db").display());
+    for file in ["backups/sd_its.db", "backups/sd_its.db-wal", "backups/sd_its.db-shm"] {
+        write_file(&dir.path().join(file), 500, 90);
+    }
+    write_file(&dir.path().join("temp/uploads/abandoned/part_000001"), 300, 60);
+
+    let retention = StorageRetention::from_settings(&settings);
+    assert_eq!(retention.rejected().len(), 1);
+    assert!(retention.rejected()[0].contains("backups"));
+    let report = retention.run(Utc::now(), false).unwrap();
+    assert_eq!(report.scanned_files, 1);
+    for kept in ["backups/sd_its.db", "backups/sd_its.db-wal", "backups/sd_its.db-shm"] {
+        assert!(dir.path().join(kept).exists(), "{} was deleted", kept);
+    }
+    assert!(!dir.path().join("temp/uploads/abandoned").exists());
+
+    // Refused at startup when the cleanup is enabled
+    settings.retention.enabled = true;
+    let errors = validate_config(&settings).unwrap_err();
+    assert!(errors.iter().any(|error| error.contains("backups")));
+}
@@ -0,0 +1,126 @@
+use std::{
+    fs,
+    path::Path,
+    time::{Duration, SystemTime},
+};
+
+use chrono::Utc;
+use tempfile::TempDir;
+
+use sd_its_benchmark::{
+    config::Settings,
+    services::retention::{PruneReason, StorageArea, StorageRetention},
+};
+
+const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Write `size` bytes to `path`, last modified `days_ago`
+fn write_file(path: &Path, size: usize, days_ago: u32) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, vec![b'x'; size]).unwrap();
+    let file = fs::File::options().write(true).open(path).unwrap();
+    file.set_modified(SystemTime::now() - DAY * days_ago).unwrap();
+}
+
+fn settings_in(dir: &Path) -> Settings {
+    let mut settings = Settings::default();
+    settings.application.upload_dir = dir.join("uploads");
+    settings.file_upload.temp_dir = dir.join("temp");
+    settings.retention.backup_dir = dir.join("backups");
+    settings.logging.file_path = Some(dir.join("logs/app.log"));
+    settings.retention.max_age_days = 30;
+    settings
+}
+
+#[test]
+fn test_dry_run_reports_old_files_without_deleting_them() {
+    let dir = TempDir::new().unwrap();
+    write_file(&dir.path().join("temp/uploads/abandoned/part_000001"), 50, 46);
+    write_file(&dir.path().join("temp/uploads/abandoned/part_000002"), 50, 45);
+    // Aged by its newest part, so an old first part does not expire a live session
+    write_file(&dir.path().join("temp/uploads/active/part_000001"), 50, 60);
+    write_file(&dir.path().join("temp/uploads/active/part_000002"), 50, 1);
+    // Nothing stores uploads in upload_dir, so it is not cleaned
+    write_file(&dir.path().join("uploads/old.json"), 100, 45);
+    write_file(&dir.path().join("backups/db-old.sqlite"), 300, 60);
+    write_file(&dir.path().join("logs/app.log.1"), 50, 40);
+    // The log being written is kept however old
+    write_file(&dir.path().join("logs/app.log"), 50, 90);
+
+    let report = StorageRetention::from_settings(&settings_in(dir.path())).run(Utc::now(), true).unwrap();
+    assert!(report.dry_run);
+    assert_eq!(report.scanned_files, 4);
+    assert_eq!(report.total_bytes, 550);
+    assert_eq!(report.freed_bytes, 450);
+    let pruned: Vec<_> = report.files.iter().map(|file| (file.area, file.reason)).collect();
+    assert_eq!(
+        pruned,
+        [
+            (StorageArea::Backups, PruneReason::Age),
+            (StorageArea::UploadStaging, PruneReason::Age),
+            (StorageArea::Logs, PruneReason::Age),
+        ]
+    );
+    assert_eq!(report.files[1].path, dir.path().join("temp/uploads/abandoned").display().to_string());
+    assert!(dir.path().join("backups/db-old.sqlite").exists());
+}
+
+#[test]
+fn test_cleanup_deletes_the_oldest_files_over_the_quota() {
+    let dir = TempDir::new().unwrap();
+    write_file(&dir.path().join("temp/uploads/a/part_000001"), 100, 10);
+    write_file(&dir.path().join("backups/b.sqlite"), 100, 5);
+    write_file(&dir.path().join("logs/app.log.1"), 100, 3);
+    write_file(&dir.path().join("backups/d.sqlite"), 100, 1);
+    let mut settings = settings_in(dir.path());
+    settings.retention.max_total_bytes = Some(250);
+
+    let report = StorageRetention::from_settings(&settings).run(Utc::now(), false).unwrap();
+    assert!(!report.dry_run);
+    assert_eq!(report.freed_bytes, 200);
+    assert!(report.files.iter().all(|file| file.reason == PruneReason::Quota));
+    assert!(report.failures.is_empty());
+    // Staged sessions count towards the quota, but only their age deletes them
+    assert!(dir.path().join("temp/uploads/a/part_000001").exists());
+    assert!(!dir.path().join("backups/b.sqlite").exists());
+    assert!(!dir.path().join("logs/app.log.1").exists());
+    assert!(dir.path().join("backups/d.sqlite").exists());
+
+    // Within both limits now
+    let report = StorageRetention::from_settings(&settings).run(Utc::now(), false).unwrap();
+    assert!(report.files.is_empty());
+}
+
+#[test]
+fn test_missing_directories_are_skipped() {
+    let dir = TempDir::new().unwrap();
+
+    let report = StorageRetention::from_settings(&settings_in(dir.path())).run(Utc::now(), false).unwrap();
+    assert_eq!(report.scanned_files, 0);
+    assert!(report.files.is_empty());
+}
+
+#[test]
+fn test_only_rotated_copies_of_the_log_are_pruned_next_to_it() {
+    let dir = TempDir::new().unwrap();
+    let mut settings = settings_in(dir.path());
+    // Logging into the directory that also holds the database and everything else
+    settings.logging.file_path = Some(dir.path().join("app.log"));
+    write_file(&dir.path().join("app.log"), 50, 90);
+    write_file(&dir.path().join("app.log.1"), 50, 40);
+    write_file(&dir.path().join("app.log.2024-01-01"), 50, 40);
+    write_file(&dir.path().join("sd_its.db"), 500, 90);
+    write_file(&dir.path().join("other.log.1"), 50, 90);
+    write_file(&dir.path().join("nested/app.log.1"), 50, 90);
+
+    let retention = StorageRetention::from_settings(&settings);
+    assert!(retention.rejected().is_empty());
+    let report = retention.run(Utc::now(), false).unwrap();
+    assert_eq!(report.scanned_files, 2);
+    assert!(report.files.iter().all(|file| file.area == StorageArea::Logs));
+    assert!(!dir.path().join("app.log.1").exists());
+    assert!(!dir.path().join("app.log.2024-01-01").exists());
+    for kept in ["app.log", "sd_its.db", "other.log.1", "nested/app.log.1"] {
+        assert!(dir.path().join(kept).exists(), "{} was deleted", kept);
+    }
+}
@@ -0,0 +1,62 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    Router,
+};
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}},
+    routes,
+};
+
+async fn create_test_app_state() -> AppState {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    AppState {
+        db: db_pool,
+        settings: Settings::default(),
+    }
+}
+
+async fn status(app: &Router, method: &str, uri: &str) -> StatusCode {
+    let request = Request::builder().method(method).uri(uri).body(Body::empty()).unwrap();
+    app.clone().oneshot(request).await.unwrap().status()
+}
+
+#[tokio::test]
+async fn test_query_group_serves_stats_only() {
+    let app_state = create_test_app_state().await;
+    let app = routes::query::routes(&app_state).with_state(app_state);
+
+    assert_eq!(status(&app, "GET", "/api/leaderboard").await, StatusCode::OK);
+    assert_eq!(status(&app, "GET", "/api/tags").await, StatusCode::OK);
+    assert_eq!(status(&app, "GET", "/api/admin/change-sets").await, StatusCode::NOT_FOUND);
+    assert_eq!(status(&app, "GET", "/api/feed.atom").await, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_admin_group_serves_pipeline_and_curation() {
+    let app_state = create_test_app_state().await;
+    let app = routes::admin::routes(&app_state).with_state(app_state);
+
+    assert_eq!(status(&app, "GET", "/api/admin/change-sets").await, StatusCode::OK);
+    assert_eq!(status(&app, "POST", "/api/update-gpu-integrated-info").await, StatusCode::OK);
+    assert_eq!(status(&app, "GET", "/api/leaderboard").await, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_public_group_serves_feed_only() {
+    let app_state = create_test_app_state().await;
+    let app = routes::public::routes(&app_state).with_state(app_state);
+
+    assert_eq!(status(&app, "GET", "/api/feed.atom").await, StatusCode::OK);
+    assert_eq!(status(&app, "POST", "/api/process-gpu").await, StatusCode::NOT_FOUND);
+}
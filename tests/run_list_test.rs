@@ -0,0 +1,173 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    routing::get,
+    Router,
+};
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}},
+    handlers::runs::list_runs,
+    models::{gpu::Gpu, performance_result::PerformanceResult, runs::Run},
+    repositories::{
+        gpu_repository::GpuRepository,
+        performance_result_repository::PerformanceResultRepository,
+        runs_repository::RunsRepository,
+        traits::Repository,
+    },
+};
+
+async fn create_test_app_state() -> AppState {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    AppState {
+        db: db_pool,
+        settings: Settings::default(),
+    }
+}
+
+fn create_app(app_state: AppState) -> Router {
+    Router::new().route("/api/runs", get(list_runs)).with_state(app_state)
+}
+
+async fn send(app: &Router, uri: &str) -> (StatusCode, Value) {
+    let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+/// Insert a run by `user` on the given GPU devices, with an avg_its when given
+async fn create_run(app_state: &AppState, user: &str, devices: &[&str], avg_its: Option<f64>) -> i64 {
+    let run_id = RunsRepository::new(app_state.db.clone())
+        .create(Run {
+            id: None,
+            timestamp: Some("2024-01-01T00:00:00Z".to_string()),
+            vram_usage: None,
+            info: Some("app:test".to_string()),
+            system_info: None,
+            model_info: None,
+            device_info: None,
+            xformers: None,
+            model_name: Some("sdxl".to_string()),
+            user: Some(user.to_string()),
+            notes: Some("fast".to_string()),
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap()
+        .id;
+
+    for (gpu_index, device) in devices.iter().enumerate() {
+        GpuRepository::new(app_state.db.clone())
+            .create(Gpu {
+                id: None,
+                run_id,
+                device: Some(device.to_string()),
+                driver: None,
+                gpu_chip: None,
+                brand: None,
+                is_laptop: None,
+                is_integrated: None,
+                is_cloud: None,
+                gpu_index: gpu_index as i64,
+                parser_version: None,
+                created_at: None,
+                updated_at: None,
+            })
+            .await
+            .unwrap();
+    }
+
+    if let Some(avg_its) = avg_its {
+        PerformanceResultRepository::new(app_state.db.clone())
+            .create(PerformanceResult { id: None, run_id, its: None, avg_its: Some(avg_its), vram_usage_kind: None, created_at: None, updated_at: None })
+            .await
+            .unwrap();
+    }
+
+    run_id.unwrap()
+}
+
+#[tokio::test]
+async fn test_list_runs_embeds_only_included_children() {
+    let app_state = create_test_app_state().await;
+    let first = create_run(&app_state, "alice", &["RTX 4090", "RTX 3060"], Some(20.0)).await;
+    let second = create_run(&app_state, "bob", &[], None).await;
+    let app = create_app(app_state);
+
+    let (status, body) = send(&app, "/api/runs").await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    let runs = body["data"].as_array().unwrap();
+    assert_eq!(runs.len(), 2);
+    assert_eq!(runs[0]["run_id"], second);
+    assert_eq!(runs[1]["user"], "alice");
+    // Lightweight rows leave out the raw strings and the children
+    assert!(runs[1].get("info").is_none());
+    assert!(runs[1].get("gpus").is_none());
+    assert!(runs[1].get("performance").is_none());
+
+    let (status, body) = send(&app, "/api/runs?include=gpu,performance,gpu").await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    let runs = body["data"].as_array().unwrap();
+    assert_eq!(runs[0]["gpus"], json!([]));
+    // Included but missing single-row children are null rather than left out
+    assert_eq!(runs[0].get("performance"), Some(&Value::Null));
+    assert_eq!(runs[1]["run_id"], first);
+    let devices: Vec<&str> = runs[1]["gpus"].as_array().unwrap().iter().map(|gpu| gpu["device"].as_str().unwrap()).collect();
+    assert_eq!(devices, ["RTX 4090", "RTX 3060"]);
+    assert_eq!(runs[1]["performance"]["avg_its"], 20.0);
+
+    let (_, body) = send(&app, "/api/runs?include=performance&limit=1&offset=1&anonymize=true").await;
+    let runs = body["data"].as_array().unwrap();
+    assert_eq!(runs.len(), 1);
+    assert!(runs[0].get("gpus").is_none());
+    assert_ne!(runs[0]["user"], "alice");
+    assert!(runs[0]["notes"].is_null());
+}
+
+#[tokio::test]
+async fn test_list_runs_rejects_unknown_includes() {
+    let app = create_app(create_test_app_state().await);
+
+    let (status, body) = send(&app, "/api/runs?include=gpu,os").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"]["params"][0]["field"], "include");
+    assert_eq!(body["error"]["params"][0]["allowed"], json!(["gpu", "performance", "system_info", "libraries", "app"]));
+
+    let (status, _) = send(&app, "/api/runs?limit=0").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_find_by_run_ids_keys_the_newest_row_by_run() {
+    let app_state = create_test_app_state().await;
+    let first = create_run(&app_state, "alice", &["RTX 4090"], Some(10.0)).await;
+    let second = create_run(&app_state, "bob", &[], Some(5.0)).await;
+    let performance = PerformanceResultRepository::new(app_state.db.clone());
+    performance
+        .create(PerformanceResult { id: None, run_id: Some(first), its: None, avg_its: Some(12.0), vram_usage_kind: None, created_at: None, updated_at: None })
+        .await
+        .unwrap();
+
+    let results = performance.find_by_run_ids(&[first, second, first + second + 1]).await.unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[&first].avg_its, Some(12.0));
+    assert_eq!(results[&second].avg_its, Some(5.0));
+
+    let gpus = GpuRepository::new(app_state.db.clone()).find_by_run_ids(&[first, second]).await.unwrap();
+    assert_eq!(gpus[&first].len(), 1);
+    assert!(!gpus.contains_key(&second));
+    assert!(performance.find_by_run_ids(&[]).await.unwrap().is_empty());
+}
@@ -0,0 +1,105 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    routing::get,
+    Router,
+};
+use serde_json::Value;
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}},
+    handlers::runs::get_run_raw_sources,
+    models::{gpu::Gpu, runs::Run},
+    repositories::{gpu_repository::GpuRepository, runs_repository::RunsRepository, traits::Repository},
+};
+
+async fn create_test_app_state() -> AppState {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    AppState {
+        db: db_pool,
+        settings: Settings::default(),
+    }
+}
+
+async fn send(app_state: &AppState, uri: &str) -> (StatusCode, Value) {
+    let app = Router::new()
+        .route("/api/runs/{id}/raw", get(get_run_raw_sources))
+        .with_state(app_state.clone());
+    let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+#[tokio::test]
+async fn test_raw_sources_are_paired_with_their_parsed_rows() {
+    let app_state = create_test_app_state().await;
+    let run_id = RunsRepository::new(app_state.db.clone())
+        .create(Run {
+            id: None,
+            timestamp: Some("2024-01-01T00:00:00Z".to_string()),
+            vram_usage: Some("8.5/12.0/15.2".to_string()),
+            info: Some("app:stable-diffusion-webui updated:2024-01-01 hash:abc123".to_string()),
+            system_info: Some("arch:x86_64 system:Linux".to_string()),
+            model_info: Some("torch:2.0.1".to_string()),
+            device_info: Some("device:NVIDIA GeForce RTX 3080 driver:535.86".to_string()),
+            xformers: None,
+            model_name: Some("v1-5-pruned-emaonly.safetensors".to_string()),
+            user: Some("alice".to_string()),
+            notes: None,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap()
+        .id
+        .unwrap();
+    GpuRepository::new(app_state.db.clone())
+        .create(Gpu {
+            id: None,
+            run_id: Some(run_id),
+            device: Some("NVIDIA GeForce RTX 3080".to_string()),
+            driver: Some("535.86".to_string()),
+            gpu_chip: None,
+            brand: None,
+            is_laptop: None,
+            is_integrated: None,
+            is_cloud: None,
+            gpu_index: 0,
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap();
+
+    let (status, body) = send(&app_state, &format!("/api/runs/{}/raw", run_id)).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    let sources = &body["data"];
+    assert_eq!(sources["run_id"], run_id);
+    assert_eq!(sources["device_info"]["raw"], "device:NVIDIA GeForce RTX 3080 driver:535.86");
+    assert_eq!(sources["device_info"]["parsed"][0]["device"], "NVIDIA GeForce RTX 3080");
+    assert_eq!(sources["info"]["raw"], "app:stable-diffusion-webui updated:2024-01-01 hash:abc123");
+    // The app details step has not run yet
+    assert!(sources["info"]["parsed"].is_null());
+    assert_eq!(sources["vram_usage"]["raw"], "8.5/12.0/15.2");
+    assert!(sources.get("user").is_none());
+}
+
+#[tokio::test]
+async fn test_raw_sources_of_a_missing_run_are_not_found() {
+    let app_state = create_test_app_state().await;
+
+    let (status, _) = send(&app_state, "/api/runs/404/raw").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,208 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    routing::{delete, get, post},
+    Router,
+};
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}},
+    handlers::{
+        admin::update_gpu_laptop_info,
+        stats::leaderboard,
+        tags::{add_run_tags, get_run_tags, list_tags, remove_run_tag},
+    },
+    models::{gpu::Gpu, performance_result::PerformanceResult, runs::Run},
+    repositories::{
+        gpu_repository::GpuRepository,
+        performance_result_repository::PerformanceResultRepository,
+        runs_repository::RunsRepository,
+        traits::Repository,
+    },
+};
+
+async fn create_test_app_state() -> AppState {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    AppState {
+        db: db_pool,
+        settings: Settings::default(),
+    }
+}
+
+fn create_app(app_state: AppState) -> Router {
+    Router::new()
+        .route("/api/tags", get(list_tags))
+        .route("/api/runs/{id}/tags", get(get_run_tags).post(add_run_tags))
+        .route("/api/runs/{id}/tags/{tag}", delete(remove_run_tag))
+        .route("/api/leaderboard", get(leaderboard))
+        .route("/api/update-gpu-laptop-info", post(update_gpu_laptop_info))
+        .with_state(app_state)
+}
+
+async fn send(app: &Router, method: &str, uri: &str, body: Option<Value>) -> (StatusCode, Value) {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("content-type", "application/json")
+        .body(body.map_or_else(Body::empty, |body| Body::from(body.to_string())))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+/// Insert a run with one GPU row per device, in order, and the given avg_its
+async fn create_run(app_state: &AppState, devices: &[&str], avg_its: f64) -> i64 {
+    let run_id = RunsRepository::new(app_state.db.clone())
+        .create(Run {
+            id: None,
+            timestamp: Some("2024-01-01T00:00:00Z".to_string()),
+            vram_usage: None,
+            info: None,
+            system_info: None,
+            model_info: None,
+            device_info: None,
+            xformers: None,
+            model_name: None,
+            user: None,
+            notes: None,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap()
+        .id;
+
+    for (gpu_index, device) in devices.iter().enumerate() {
+        GpuRepository::new(app_state.db.clone())
+            .create(Gpu {
+                id: None,
+                run_id,
+                device: Some(device.to_string()),
+                driver: None,
+                gpu_chip: None,
+                brand: None,
+                is_laptop: None,
+                is_integrated: None,
+                is_cloud: None,
+                gpu_index: gpu_index as i64,
+                parser_version: None,
+                created_at: None,
+                updated_at: None,
+            })
+            .await
+            .unwrap();
+    }
+
+    PerformanceResultRepository::new(app_state.db.clone())
+        .create(PerformanceResult { id: None, run_id, its: None, avg_its: Some(avg_its), vram_usage_kind: None, created_at: None, updated_at: None })
+        .await
+        .unwrap();
+
+    run_id.unwrap()
+}
+
+fn tag_names(body: &Value) -> Vec<String> {
+    body["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|tag| tag["name"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_tag_run_and_filter_leaderboard() {
+    let app_state = create_test_app_state().await;
+    let tagged = create_run(&app_state, &["NVIDIA GeForce RTX 3060"], 5.0).await;
+    create_run(&app_state, &["NVIDIA GeForce RTX 4090"], 20.0).await;
+    let app = create_app(app_state);
+
+    let uri = format!("/api/runs/{}/tags", tagged);
+    let (status, body) = send(&app, "POST", &uri, Some(json!({ "tags": [" Overclocked ", "docker", "docker"] }))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(tag_names(&body), vec!["docker", "overclocked"]);
+    assert_eq!(body["data"][0]["source"], "manual");
+
+    let (_, body) = send(&app, "GET", "/api/tags", None).await;
+    assert_eq!(tag_names(&body), vec!["docker", "overclocked"]);
+    assert_eq!(body["data"][0]["run_count"], 1);
+
+    let (status, body) = send(&app, "GET", "/api/leaderboard?metric=avg_its&tag=overclocked", None).await;
+    assert_eq!(status, StatusCode::OK);
+    let rows = body["data"].as_array().unwrap();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0]["run_id"], tagged);
+
+    let (_, body) = send(&app, "GET", "/api/leaderboard?metric=avg_its", None).await;
+    assert_eq!(body["data"].as_array().unwrap().len(), 2);
+
+    let (status, _) = send(&app, "DELETE", &format!("{}/docker", uri), None).await;
+    assert_eq!(status, StatusCode::OK);
+    let (_, body) = send(&app, "GET", &uri, None).await;
+    assert_eq!(tag_names(&body), vec!["overclocked"]);
+
+    let (status, _) = send(&app, "DELETE", &format!("{}/docker", uri), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_tagging_rejects_invalid_requests() {
+    let app_state = create_test_app_state().await;
+    let run_id = create_run(&app_state, &["NVIDIA GeForce RTX 3060"], 5.0).await;
+    let app = create_app(app_state);
+
+    let uri = format!("/api/runs/{}/tags", run_id);
+    let (status, _) = send(&app, "POST", &uri, Some(json!({ "tags": ["two words"] }))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = send(&app, "POST", &uri, Some(json!({ "tags": [] }))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = send(&app, "POST", "/api/runs/9999/tags", Some(json!({ "tags": ["docker"] }))).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, _) = send(&app, "GET", "/api/leaderboard?tag=not%20a%20tag", None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_automatic_tags_derived_from_gpu_data() {
+    let app_state = create_test_app_state().await;
+    let laptop = create_run(&app_state, &["NVIDIA GeForce RTX 3060 Laptop GPU"], 4.0).await;
+    let multi_gpu = create_run(&app_state, &["NVIDIA GeForce RTX 3090", "NVIDIA GeForce RTX 3090"], 15.0).await;
+    let desktop = create_run(&app_state, &["NVIDIA GeForce RTX 4090"], 20.0).await;
+    let app = create_app(app_state);
+
+    // A manual tag survives the refresh of automatic tags
+    send(&app, "POST", &format!("/api/runs/{}/tags", desktop), Some(json!({ "tags": ["undervolted"] }))).await;
+
+    let (status, body) = send(&app, "POST", "/api/update-gpu-laptop-info", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["auto_tags"], 2);
+
+    // Refreshing again does not duplicate the automatic tags
+    let (_, body) = send(&app, "POST", "/api/update-gpu-laptop-info", None).await;
+    assert_eq!(body["auto_tags"], 2);
+
+    let (_, body) = send(&app, "GET", &format!("/api/runs/{}/tags", laptop), None).await;
+    assert_eq!(tag_names(&body), vec!["laptop"]);
+    assert_eq!(body["data"][0]["source"], "auto");
+
+    let (_, body) = send(&app, "GET", &format!("/api/runs/{}/tags", multi_gpu), None).await;
+    assert_eq!(tag_names(&body), vec!["multi-gpu"]);
+
+    let (_, body) = send(&app, "GET", &format!("/api/runs/{}/tags", desktop), None).await;
+    assert_eq!(tag_names(&body), vec!["undervolted"]);
+}
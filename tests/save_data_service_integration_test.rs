@@ -0,0 +1,94 @@
+use sqlx::SqlitePool;
+
+use sd_its_benchmark::{
+    models::runs::Run,
+    repositories::{runs_repository::RunsRepository, traits::Repository},
+    services::data_processing::save_data_service::SaveDataService,
+};
+
+async fn setup_test_database() -> SqlitePool {
+    let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+
+    sqlx::migrate!("./migrations")
+        .run(&pool)
+        .await
+        .expect("Failed to run migrations");
+
+    pool
+}
+
+fn create_run(user: &str) -> Run {
+    Run {
+        id: None,
+        timestamp: Some("2024-01-01T00:00:00Z".to_string()),
+        vram_usage: Some("10.0/11.0".to_string()),
+        info: Some("app:test".to_string()),
+        system_info: None,
+        model_info: None,
+        device_info: None,
+        xformers: None,
+        model_name: None,
+        user: Some(user.to_string()),
+        notes: None,
+        created_at: None,
+        updated_at: None,
+    }
+}
+
+#[tokio::test]
+async fn test_insert_runs_in_chunks_all_valid() {
+    let pool = setup_test_database().await;
+    let service = SaveDataService::new(RunsRepository::new(pool.clone()), pool.clone());
+
+    let runs = (0..5).map(|i| create_run(&format!("user{}", i))).collect();
+
+    let mut tx = pool.begin().await.unwrap();
+    let result = service.insert_runs_in_chunks_tx(runs, 2, &mut tx).await.unwrap();
+    tx.commit().await.unwrap();
+
+    assert_eq!(result.inserted_runs.len(), 5);
+    assert!(result.error_data.is_empty());
+    assert_eq!(RunsRepository::new(pool.clone()).count().await.unwrap(), 5);
+}
+
+#[tokio::test]
+async fn test_insert_runs_in_chunks_partitions_failed_chunk() {
+    let pool = setup_test_database().await;
+
+    // Reject one specific row so its chunk has to fall back to per-row inserts
+    sqlx::query(
+        "CREATE TRIGGER reject_bad_run BEFORE INSERT ON runs WHEN NEW.user = 'bad' \
+         BEGIN SELECT RAISE(ABORT, 'rejected run'); END",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let service = SaveDataService::new(RunsRepository::new(pool.clone()), pool.clone());
+    let runs = vec![
+        create_run("user1"),
+        create_run("user2"),
+        create_run("user3"),
+        create_run("bad"),
+        create_run("user5"),
+    ];
+
+    let mut tx = pool.begin().await.unwrap();
+    let result = service.insert_runs_in_chunks_tx(runs, 2, &mut tx).await.unwrap();
+    tx.commit().await.unwrap();
+
+    assert_eq!(result.inserted_runs.len(), 4);
+    assert_eq!(result.error_data.len(), 1);
+    assert!(result.error_data[0].starts_with("Row 4:"), "unexpected error: {}", result.error_data[0]);
+
+    // The good row from the failed chunk must be inserted exactly once
+    let users: Vec<Option<String>> = RunsRepository::new(pool.clone())
+        .find_all()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|run| run.user)
+        .collect();
+    assert_eq!(users.len(), 4);
+    assert_eq!(users.iter().filter(|user| user.as_deref() == Some("user3")).count(), 1);
+}
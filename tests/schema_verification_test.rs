@@ -0,0 +1,58 @@
+use sd_its_benchmark::config::{
+    database::{DatabaseConfig, create_pool, initialize_database},
+    schema::verify_schema,
+};
+use sqlx::SqlitePool;
+
+async fn create_test_pool() -> SqlitePool {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    create_pool(&db_config).await.expect("Failed to create test pool")
+}
+
+#[tokio::test]
+async fn test_initialized_database_matches_the_migrations() {
+    let pool = create_test_pool().await;
+    initialize_database(&pool).await.expect("Failed to initialize test database");
+
+    let report = verify_schema(&pool).await.unwrap();
+    assert!(report.is_complete(), "{:?}", report.problems());
+}
+
+#[tokio::test]
+async fn test_missing_tables_and_columns_are_reported() {
+    let pool = create_test_pool().await;
+    initialize_database(&pool).await.expect("Failed to initialize test database");
+    sqlx::query("DROP TABLE commit_dates").execute(&pool).await.unwrap();
+    sqlx::query("ALTER TABLE AppDetails DROP COLUMN commit_date").execute(&pool).await.unwrap();
+
+    let report = verify_schema(&pool).await.unwrap();
+    assert_eq!(report.missing_tables, ["commit_dates"]);
+    assert_eq!(report.missing_columns, ["AppDetails.commit_date"]);
+    assert_eq!(
+        report.problems(),
+        ["Missing table commit_dates", "Missing column AppDetails.commit_date"]
+    );
+}
+
+#[tokio::test]
+async fn test_duplicate_child_rows_are_reported_not_deleted() {
+    let pool = create_test_pool().await;
+    initialize_database(&pool).await.expect("Failed to initialize test database");
+    // A database from before the unique index, holding a duplicate
+    sqlx::query("DROP INDEX uq_AppDetails_run_id").execute(&pool).await.unwrap();
+    sqlx::query("INSERT INTO runs (timestamp) VALUES ('2024-01-01T00:00:00Z')").execute(&pool).await.unwrap();
+    sqlx::query("INSERT INTO AppDetails (run_id, app_name) VALUES (1, 'first'), (1, 'second')")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let error = initialize_database(&pool).await.unwrap_err().to_string();
+    assert!(error.contains("AppDetails has 1 row(s) duplicating"), "{}", error);
+
+    let rows: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM AppDetails").fetch_one(&pool).await.unwrap();
+    assert_eq!(rows, 2);
+}
@@ -0,0 +1,63 @@
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use serde_json::Value;
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}},
+    error::i18n::ErrorCatalog,
+    handlers::{pipeline::pipeline_registry, self_test::{run_self_test, SelfTestStatus}},
+    repositories::{runs_repository::RunsRepository, traits::Repository},
+    router::create_router,
+};
+
+async fn create_test_app_state() -> AppState {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    AppState {
+        db: db_pool,
+        settings: Settings::default(),
+    }
+}
+
+#[tokio::test]
+async fn test_self_test_passes_every_stage_on_the_fixture() {
+    let response = run_self_test(&Settings::default()).await;
+
+    let failed: Vec<_> = response.steps.iter().filter(|step| step.status != SelfTestStatus::Passed).collect();
+    assert!(failed.is_empty(), "{:?}", failed);
+    assert!(response.success);
+    assert_eq!(response.steps.len(), pipeline_registry().names().len() + 2);
+    assert_eq!(response.steps[0].step, "migrate");
+    assert_eq!(response.steps[1].step, "ingest");
+    assert_eq!(response.steps[1].rows_out, Some(20));
+    let process_its = response.steps.iter().find(|step| step.step == "process-its").unwrap();
+    assert_eq!(process_its.rows_out, Some(20));
+}
+
+#[tokio::test]
+async fn test_self_test_endpoint_leaves_the_dataset_untouched() {
+    let app_state = create_test_app_state().await;
+    let app = create_router(app_state.clone(), Arc::new(ErrorCatalog::bundled().unwrap()));
+
+    let request = Request::builder().method("POST").uri("/api/admin/self-test").body(Body::empty()).unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body: Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(body["success"], true, "{}", body);
+    assert!(body["steps"].as_array().unwrap().iter().all(|step| step["status"] == "passed"));
+
+    assert!(RunsRepository::new(app_state.db.clone()).find_all().await.unwrap().is_empty());
+}
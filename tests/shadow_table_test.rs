@@ -0,0 +1,125 @@
+use sqlx::SqlitePool;
+
+use sd_its_benchmark::{
+    config::database::{DatabaseConfig, create_pool, initialize_database},
+    models::{gpu::Gpu, runs::Run},
+    repositories::{
+        gpu_repository::GpuRepository,
+        runs_repository::RunsRepository,
+        shadow_table::ShadowTable,
+        traits::Repository,
+    },
+};
+
+async fn create_test_pool() -> SqlitePool {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&pool).await.expect("Failed to initialize test database");
+    pool
+}
+
+fn gpu(run_id: i64, device: &str) -> Gpu {
+    Gpu {
+        id: None,
+        run_id: Some(run_id),
+        device: Some(device.to_string()),
+        driver: None,
+        gpu_chip: None,
+        brand: None,
+        is_laptop: None,
+        is_integrated: None,
+        is_cloud: None,
+        gpu_index: 0,
+        parser_version: None,
+        created_at: None,
+        updated_at: None,
+    }
+}
+
+async fn create_run(pool: &SqlitePool) -> i64 {
+    RunsRepository::new(pool.clone())
+        .create(Run {
+            id: None,
+            timestamp: Some("2024-01-01T00:00:00Z".to_string()),
+            vram_usage: None,
+            info: None,
+            system_info: None,
+            model_info: None,
+            device_info: None,
+            xformers: None,
+            model_name: None,
+            user: None,
+            notes: None,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap()
+        .id
+        .unwrap()
+}
+
+async fn live_devices(tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>) -> Vec<String> {
+    sqlx::query_scalar("SELECT device FROM GPU ORDER BY id")
+        .fetch_all(&mut **tx)
+        .await
+        .unwrap()
+}
+
+async fn schema_names(pool: &SqlitePool, table: &str) -> Vec<String> {
+    sqlx::query_scalar("SELECT name FROM sqlite_master WHERE tbl_name = ? ORDER BY name")
+        .bind(table)
+        .fetch_all(pool)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_swap_replaces_live_table_and_keeps_its_indexes() {
+    let pool = create_test_pool().await;
+    let run_id = create_run(&pool).await;
+    GpuRepository::new(pool.clone()).create(gpu(run_id, "old device")).await.unwrap();
+    let live_schema = schema_names(&pool, "GPU").await;
+
+    let mut tx = pool.begin().await.unwrap();
+    let shadow = ShadowTable::<Gpu>::create_tx(&mut tx).await.unwrap();
+    shadow.insert_tx(gpu(run_id, "rebuilt device"), &mut tx).await.unwrap();
+    // A duplicate from the rebuild replaces the earlier row, like the upsert it stands in for
+    shadow.insert_tx(gpu(run_id, "NVIDIA GeForce RTX 4090"), &mut tx).await.unwrap();
+
+    // The live table is untouched until the swap
+    assert_eq!(live_devices(&mut tx).await, ["old device"]);
+
+    shadow.swap_tx(&mut tx).await.unwrap();
+    assert_eq!(live_devices(&mut tx).await, ["NVIDIA GeForce RTX 4090"]);
+    tx.commit().await.unwrap();
+
+    assert_eq!(schema_names(&pool, "GPU").await, live_schema);
+    assert!(schema_names(&pool, "GPU_new").await.is_empty());
+    let gpus = GpuRepository::new(pool.clone()).find_all().await.unwrap();
+    assert_eq!(gpus.len(), 1);
+    assert!(gpus[0].created_at.is_some());
+}
+
+#[tokio::test]
+async fn test_abandoned_rebuild_leaves_live_table_alone() {
+    let pool = create_test_pool().await;
+    let run_id = create_run(&pool).await;
+    GpuRepository::new(pool.clone()).create(gpu(run_id, "old device")).await.unwrap();
+
+    {
+        let mut tx = pool.begin().await.unwrap();
+        let shadow = ShadowTable::<Gpu>::create_tx(&mut tx).await.unwrap();
+        shadow.insert_tx(gpu(run_id, "rebuilt device"), &mut tx).await.unwrap();
+        // Dropped without a swap or commit, as when a processing step fails
+    }
+
+    assert!(schema_names(&pool, "GPU_new").await.is_empty());
+    let gpus = GpuRepository::new(pool.clone()).find_all().await.unwrap();
+    assert_eq!(gpus.len(), 1);
+    assert_eq!(gpus[0].device.as_deref(), Some("old device"));
+}
@@ -0,0 +1,111 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    routing::{get, post},
+    Router,
+};
+use serde_json::Value;
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}},
+    handlers::share::{create_share_link, get_shared_run},
+    models::runs::Run,
+    repositories::{runs_repository::RunsRepository, traits::Repository},
+};
+
+async fn create_test_app_state() -> AppState {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    AppState {
+        db: db_pool,
+        settings: Settings::default(),
+    }
+}
+
+fn create_app(app_state: AppState) -> Router {
+    Router::new()
+        .route("/api/runs/{id}/share", post(create_share_link))
+        .route("/api/share/{token}", get(get_shared_run))
+        .with_state(app_state)
+}
+
+async fn send(app: &Router, method: &str, uri: &str) -> (StatusCode, Value) {
+    let request = Request::builder().method(method).uri(uri).body(Body::empty()).unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+async fn insert_run(app_state: &AppState) -> i64 {
+    let run = Run {
+        id: None,
+        timestamp: Some("2024-01-01T00:00:00Z".to_string()),
+        vram_usage: Some("10.5/11.2".to_string()),
+        info: Some("app:automatic updated:2024-01-01".to_string()),
+        system_info: Some("arch:x86_64 cpu:AMD Ryzen system:Linux".to_string()),
+        model_info: Some("torch:2.0.1 xformers:0.0.20".to_string()),
+        device_info: Some("device:NVIDIA GeForce RTX 4090 driver:535.54".to_string()),
+        xformers: Some("true".to_string()),
+        model_name: Some("sd-v1-5".to_string()),
+        user: Some("share-user".to_string()),
+        notes: Some("Shared run".to_string()),
+        created_at: None,
+        updated_at: None,
+    };
+    RunsRepository::new(app_state.db.clone()).create(run).await.unwrap().id.unwrap()
+}
+
+#[tokio::test]
+async fn test_share_link_resolves_to_run_detail() {
+    let app_state = create_test_app_state().await;
+    let run_id = insert_run(&app_state).await;
+    let app = create_app(app_state);
+
+    let (status, body) = send(&app, "POST", &format!("/api/runs/{}/share", run_id)).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["data"]["run_id"], run_id);
+    assert!(body["data"]["expires_at"].is_null());
+
+    let url = body["data"]["url"].as_str().unwrap().to_string();
+    let (status, body) = send(&app, "GET", &url).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["run_id"], run_id);
+    assert_eq!(body["data"]["model_name"], "sd-v1-5");
+    assert_ne!(body["data"]["user"], "share-user");
+    assert!(body["data"]["user"].as_str().unwrap().starts_with("user-"));
+    assert!(body["data"]["notes"].is_null());
+}
+
+#[tokio::test]
+async fn test_tampered_share_token_is_not_found() {
+    let app_state = create_test_app_state().await;
+    let run_id = insert_run(&app_state).await;
+    let app = create_app(app_state);
+
+    let (_, body) = send(&app, "POST", &format!("/api/runs/{}/share", run_id)).await;
+    let token = body["data"]["token"].as_str().unwrap();
+    let (payload, _) = token.split_once('.').unwrap();
+
+    let (status, _) = send(&app, "GET", &format!("/api/share/{}.AAAA", payload)).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, _) = send(&app, "GET", "/api/share/garbage").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_sharing_missing_run_is_not_found() {
+    let app = create_app(create_test_app_state().await);
+
+    let (status, _) = send(&app, "POST", "/api/runs/999/share").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
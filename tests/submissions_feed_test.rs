@@ -0,0 +1,115 @@
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    routing::get,
+    Router,
+};
+use serde_json::Value;
+use tower::ServiceExt;
+
+mod common;
+
+use common::{create_run_on_device_with, test_run};
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}, settings::FeedConfig},
+    handlers::{feed::submissions_feed, share::get_shared_run},
+    models::runs::Run,
+};
+
+async fn create_test_app_state(max_entries: i64) -> AppState {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    AppState {
+        db: db_pool,
+        settings: Settings {
+            feed: FeedConfig {
+                base_url: "https://bench.example.com".to_string(),
+                max_entries,
+            },
+            ..Settings::default()
+        },
+    }
+}
+
+fn create_app(app_state: AppState) -> Router {
+    Router::new()
+        .route("/api/feed.atom", get(submissions_feed))
+        .route("/api/share/{token}", get(get_shared_run))
+        .with_state(app_state)
+}
+
+async fn fetch(app: &Router, uri: &str) -> (StatusCode, Option<String>, String) {
+    let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let content_type = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .map(|value| value.to_str().unwrap().to_string());
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, content_type, String::from_utf8(bytes.to_vec()).unwrap())
+}
+
+/// A run of `model_name` by the user the feed credits
+fn feed_run(model_name: &str) -> Run {
+    Run {
+        model_name: Some(model_name.to_string()),
+        user: Some("feed-user".to_string()),
+        ..test_run()
+    }
+}
+
+#[tokio::test]
+async fn test_feed_lists_newest_runs_first() {
+    let app_state = create_test_app_state(50).await;
+    create_run_on_device_with(&app_state, feed_run("sd-v1-5"), "NVIDIA GeForce RTX 3060", 7.25).await;
+    create_run_on_device_with(&app_state, feed_run("sdxl-base"), "NVIDIA GeForce RTX 4090", 21.5).await;
+    let app = create_app(app_state);
+
+    let (status, content_type, xml) = fetch(&app, "/api/feed.atom").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content_type.as_deref(), Some("application/atom+xml; charset=utf-8"));
+    assert!(xml.contains("<id>https://bench.example.com/api/feed.atom</id>"));
+    assert_eq!(xml.matches("<entry>").count(), 2);
+
+    let newest = xml.find("NVIDIA GeForce RTX 4090 on sdxl-base: 21.50 it/s").unwrap();
+    let oldest = xml.find("NVIDIA GeForce RTX 3060 on sd-v1-5: 7.25 it/s").unwrap();
+    assert!(newest < oldest);
+    assert!(xml.contains("Submitted by: feed-user"));
+}
+
+#[tokio::test]
+async fn test_feed_entries_link_to_share_permalinks() {
+    let app_state = create_test_app_state(50).await;
+    let run_id = create_run_on_device_with(&app_state, feed_run("sd-v1-5"), "NVIDIA GeForce RTX 3060", 7.25).await;
+    let app = create_app(app_state);
+
+    let (_, _, xml) = fetch(&app, "/api/feed.atom").await;
+    let link_start = xml.find("<link href=\"https://bench.example.com").unwrap() + "<link href=\"https://bench.example.com".len();
+    let link = &xml[link_start..link_start + xml[link_start..].find('"').unwrap()];
+
+    let (status, _, body) = fetch(&app, link).await;
+    assert_eq!(status, StatusCode::OK);
+    let body: Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(body["data"]["run_id"], run_id);
+}
+
+#[tokio::test]
+async fn test_feed_is_capped_at_max_entries() {
+    let app_state = create_test_app_state(2).await;
+    for avg_its in [1.0, 2.0, 3.0] {
+        create_run_on_device_with(&app_state, feed_run("sd-v1-5"), "NVIDIA GeForce RTX 3060", avg_its).await;
+    }
+    let app = create_app(app_state);
+
+    let (_, _, xml) = fetch(&app, "/api/feed.atom").await;
+    assert_eq!(xml.matches("<entry>").count(), 2);
+    assert!(!xml.contains("1.00 it/s"));
+}
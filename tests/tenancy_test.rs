@@ -0,0 +1,214 @@
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    Router,
+};
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}},
+    error::i18n::ErrorCatalog,
+    router::create_router,
+};
+
+const BOUNDARY: &str = "----WebKitFormBoundary7MA4YWxkTrZu0gW";
+
+async fn create_test_app_state() -> AppState {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    let mut settings = Settings::default();
+    settings.tenancy.enabled = true;
+    settings.tenancy.api_keys.insert("key-a".to_string(), "community-a".to_string());
+
+    AppState { db: db_pool, settings }
+}
+
+fn create_app(app_state: AppState) -> Router {
+    create_router(app_state, Arc::new(ErrorCatalog::bundled().unwrap()))
+}
+
+/// A benchmark file with one run by `user`
+fn runs_file(user: &str) -> String {
+    json!([{
+        "timestamp": "2024-01-01T10:00:00Z",
+        "vram_usage": "8.5/12.0/15.2",
+        "info": "app:stable-diffusion-webui updated:2024-01-01 hash:abc123",
+        "system_info": "arch:x86_64 system:Linux",
+        "model_info": "torch:2.0.1",
+        "device_info": "device:NVIDIA GeForce RTX 3080 driver:535.86",
+        "xformers": "True",
+        "model_name": "v1-5-pruned-emaonly.safetensors",
+        "user": user,
+        "notes": ""
+    }])
+    .to_string()
+}
+
+fn upload_body(user: &str) -> String {
+    let runs = runs_file(user);
+    format!(
+        "--{BOUNDARY}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"runs.json\"\r\nContent-Type: application/json\r\n\r\n{runs}\r\n--{BOUNDARY}--\r\n"
+    )
+}
+
+async fn send(app: &Router, method: &str, uri: &str, api_key: Option<&str>, body: Option<String>) -> (StatusCode, Value) {
+    let mut request = Request::builder().method(method).uri(uri);
+    if let Some(api_key) = api_key {
+        request = request.header("x-api-key", api_key);
+    }
+    if body.is_some() {
+        request = request.header("content-type", format!("multipart/form-data; boundary={}", BOUNDARY));
+    }
+    let request = request.body(body.map(Body::from).unwrap_or_else(Body::empty)).unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+/// `send` for a body other than a multipart upload
+async fn send_bytes(app: &Router, method: &str, uri: &str, api_key: Option<&str>, content_type: &str, body: Vec<u8>) -> (StatusCode, Value) {
+    let mut request = Request::builder().method(method).uri(uri).header("content-type", content_type);
+    if let Some(api_key) = api_key {
+        request = request.header("x-api-key", api_key);
+    }
+    let response = app.clone().oneshot(request.body(Body::from(body)).unwrap()).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+fn users(body: &Value) -> Vec<&str> {
+    body["data"].as_array().unwrap().iter().map(|run| run["user"].as_str().unwrap()).collect()
+}
+
+#[tokio::test]
+async fn test_uploads_and_the_runs_list_are_scoped_by_api_key() {
+    let app = create_app(create_test_app_state().await);
+
+    let (status, body) = send(&app, "POST", "/api/upload", Some("key-a"), Some(upload_body("alice"))).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    let (status, _) = send(&app, "POST", "/api/upload", None, Some(upload_body("bob"))).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = send(&app, "GET", "/api/runs", Some("key-a"), None).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(users(&body), ["alice"]);
+    let (_, body) = send(&app, "GET", "/api/runs", None, None).await;
+    assert_eq!(users(&body), ["bob"]);
+}
+
+#[tokio::test]
+async fn test_unknown_api_keys_are_refused() {
+    let app = create_app(create_test_app_state().await);
+
+    let (status, _) = send(&app, "GET", "/api/runs", Some("key-b"), None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _) = send(&app, "POST", "/api/upload", Some("key-b"), Some(upload_body("mallory"))).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_replacing_the_runs_is_refused_while_tenancy_is_enabled() {
+    let app = create_app(create_test_app_state().await);
+
+    let (status, _) = send(&app, "POST", "/api/save-data", Some("key-a"), Some(upload_body("alice"))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, body) = send(&app, "POST", "/api/save-data?append=true", Some("key-a"), Some(upload_body("alice"))).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+}
+
+#[tokio::test]
+async fn test_api_keys_are_ignored_while_tenancy_is_off() {
+    let mut app_state = create_test_app_state().await;
+    app_state.settings.tenancy.enabled = false;
+    let app = create_app(app_state);
+
+    let (status, _) = send(&app, "POST", "/api/upload", Some("key-a"), Some(upload_body("alice"))).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, body) = send(&app, "GET", "/api/runs", Some("unknown"), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(users(&body), ["alice"]);
+}
+
+#[tokio::test]
+async fn test_reads_are_scoped_to_the_tenant() {
+    let app = create_app(create_test_app_state().await);
+
+    let (status, _) = send(&app, "POST", "/api/upload", Some("key-a"), Some(upload_body("alice"))).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = send(&app, "POST", "/api/upload", None, Some(upload_body("bob"))).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, body) = send(&app, "POST", "/api/run-pipeline", None, None).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+
+    let (status, body) = send(&app, "GET", "/api/leaderboard?metric=avg_its", Some("key-a"), None).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(users(&body), ["alice"]);
+    assert_eq!(body["meta"]["total"], 1);
+
+    let (_, body) = send(&app, "GET", "/api/stats/counts?by=gpu_device", Some("key-a"), None).await;
+    assert_eq!(body["data"][0]["run_count"], 1, "{}", body);
+    let (_, body) = send(&app, "GET", "/api/meta/dataset", None, None).await;
+    assert_eq!(body["data"]["table_counts"]["runs"], 1, "{}", body);
+
+    // Bob's run is the second one uploaded; to community-a it does not exist
+    let (_, body) = send(&app, "GET", "/api/runs", None, None).await;
+    let bob_run_id = body["data"][0]["run_id"].as_i64().unwrap();
+    for (method, uri) in [
+        ("POST", format!("/api/runs/{}/share", bob_run_id)),
+        ("GET", format!("/api/runs/{}/tags", bob_run_id)),
+        ("GET", format!("/api/runs/{}/raw", bob_run_id)),
+    ] {
+        let (status, _) = send(&app, method, &uri, Some("key-a"), None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND, "{} {}", method, uri);
+        let (status, _) = send(&app, method, &uri, None, None).await;
+        assert!(status.is_success(), "{} {}", method, uri);
+    }
+}
+
+#[tokio::test]
+async fn test_uploads_of_another_tenant_are_not_found() {
+    let app = create_app(create_test_app_state().await);
+
+    let init = json!({ "file_name": "runs.json", "total_parts": 1 }).to_string().into_bytes();
+    let (status, body) = send_bytes(&app, "POST", "/api/uploads/init", Some("key-a"), "application/json", init).await;
+    assert!(status.is_success(), "{}", body);
+    let id = body["data"]["id"].as_str().unwrap().to_string();
+
+    let part = runs_file("alice").into_bytes();
+    let part_uri = format!("/api/uploads/{}/part/1", id);
+    let (status, _) = send_bytes(&app, "PUT", &part_uri, None, "application/octet-stream", part.clone()).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, body) = send_bytes(&app, "PUT", &part_uri, Some("key-a"), "application/octet-stream", part).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+
+    let complete_uri = format!("/api/uploads/{}/complete", id);
+    for (method, uri) in [("GET", format!("/api/uploads/{}", id)), ("POST", complete_uri.clone())] {
+        let (status, _) = send_bytes(&app, method, &uri, None, "application/json", vec![]).await;
+        assert_eq!(status, StatusCode::NOT_FOUND, "{} {}", method, uri);
+    }
+    let (status, body) = send_bytes(&app, "POST", &complete_uri, Some("key-a"), "application/json", vec![]).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+
+    let report_uri = format!("/api/uploads/{}/schema-report", id);
+    let (status, _) = send(&app, "GET", &report_uri, None, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, body) = send(&app, "GET", &report_uri, Some("key-a"), None).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["data"]["format"], "webui");
+    let (_, body) = send(&app, "GET", "/api/runs", Some("key-a"), None).await;
+    assert_eq!(users(&body), ["alice"]);
+}
@@ -0,0 +1,237 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    routing::get,
+    Router,
+};
+use serde_json::Value;
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}},
+    handlers::stats::top_configurations,
+    models::{
+        gpu::Gpu, libraries::Libraries, model_map::ModelMap, performance_result::PerformanceResult,
+        run_more_details::RunMoreDetails, run_score::RunScore, runs::Run,
+    },
+    repositories::{
+        gpu_repository::GpuRepository,
+        libraries_repository::LibrariesRepository,
+        model_map_repository::ModelMapRepository,
+        performance_result_repository::PerformanceResultRepository,
+        run_more_details_repository::RunMoreDetailsRepository,
+        run_score_repository::RunScoreRepository,
+        runs_repository::RunsRepository,
+        traits::Repository,
+    },
+};
+
+async fn create_test_app_state() -> AppState {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    AppState {
+        db: db_pool,
+        settings: Settings::default(),
+    }
+}
+
+fn create_app(app_state: AppState) -> Router {
+    Router::new()
+        .route("/api/top", get(top_configurations))
+        .with_state(app_state)
+}
+
+async fn send(app: &Router, uri: &str) -> (StatusCode, Value) {
+    let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+async fn create_model_map(app_state: &AppState, model_name: &str, base_model: &str) -> i64 {
+    ModelMapRepository::new(app_state.db.clone())
+        .create(ModelMap {
+            id: None,
+            model_name: Some(model_name.to_string()),
+            base_model: Some(base_model.to_string()),
+            version: 1,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap()
+        .id
+        .unwrap()
+}
+
+/// A processed run on a device with the given libraries, base model, VRAM and avg_its
+struct TestRun<'a> {
+    device: &'a str,
+    torch: &'a str,
+    xformers: &'a str,
+    model_map_id: i64,
+    vram_gb: Option<f64>,
+    avg_its: f64,
+}
+
+async fn create_run(app_state: &AppState, test_run: TestRun<'_>) {
+    let run_id = RunsRepository::new(app_state.db.clone())
+        .create(Run {
+            id: None,
+            timestamp: Some("2024-01-01T00:00:00Z".to_string()),
+            vram_usage: None,
+            info: None,
+            system_info: None,
+            model_info: None,
+            device_info: None,
+            xformers: None,
+            model_name: None,
+            user: None,
+            notes: None,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap()
+        .id;
+
+    GpuRepository::new(app_state.db.clone())
+        .create(Gpu {
+            id: None,
+            run_id,
+            device: Some(test_run.device.to_string()),
+            driver: None,
+            gpu_chip: None,
+            brand: None,
+            is_laptop: None,
+            is_integrated: None,
+            is_cloud: None,
+            gpu_index: 0,
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap();
+
+    LibrariesRepository::new(app_state.db.clone())
+        .create(Libraries {
+            id: None,
+            run_id,
+            torch: Some(test_run.torch.to_string()),
+            xformers: Some(test_run.xformers.to_string()),
+            xformers1: None,
+            diffusers: None,
+            transformers: None,
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap();
+
+    RunMoreDetailsRepository::new(app_state.db.clone())
+        .create(RunMoreDetails {
+            id: None,
+            run_id,
+            timestamp: None,
+            model_name: None,
+            user: None,
+            notes: None,
+            model_map_id: Some(test_run.model_map_id),
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap();
+
+    RunScoreRepository::new(app_state.db.clone())
+        .create(RunScore {
+            id: None,
+            run_id,
+            score: Some(50.0),
+            normalized_its: None,
+            model_factor: None,
+            vram_gb: test_run.vram_gb,
+            created_at: None,
+            updated_at: None,
+        })
+        .await
+        .unwrap();
+
+    PerformanceResultRepository::new(app_state.db.clone())
+        .create(PerformanceResult { id: None, run_id, its: None, avg_its: Some(test_run.avg_its), vram_usage_kind: None, created_at: None, updated_at: None })
+        .await
+        .unwrap();
+}
+
+async fn seed_runs(app_state: &AppState) {
+    let sdxl = create_model_map(app_state, "sdxl-base", "sdxl").await;
+    let sd15 = create_model_map(app_state, "v1-5-pruned", "sd15").await;
+
+    let runs = [
+        TestRun { device: "RTX 4090", torch: "2.1.0", xformers: "0.0.22", model_map_id: sdxl, vram_gb: Some(24.0), avg_its: 12.0 },
+        TestRun { device: "RTX 3060", torch: "2.1.0", xformers: "0.0.22", model_map_id: sdxl, vram_gb: Some(12.0), avg_its: 4.0 },
+        TestRun { device: "RTX 3060", torch: "2.1.0", xformers: "0.0.22", model_map_id: sdxl, vram_gb: Some(12.0), avg_its: 5.0 },
+        TestRun { device: "RTX 3060", torch: "2.0.1", xformers: "none", model_map_id: sdxl, vram_gb: Some(12.0), avg_its: 3.0 },
+        TestRun { device: "RTX 4060", torch: "2.1.0", xformers: "0.0.22", model_map_id: sdxl, vram_gb: None, avg_its: 6.0 },
+        TestRun { device: "RTX 3060", torch: "2.1.0", xformers: "0.0.22", model_map_id: sd15, vram_gb: Some(12.0), avg_its: 12.0 },
+    ];
+    for run in runs {
+        create_run(app_state, run).await;
+    }
+}
+
+#[tokio::test]
+async fn test_top_configurations_meet_constraints() {
+    let app_state = create_test_app_state().await;
+    seed_runs(&app_state).await;
+    let app = create_app(app_state);
+
+    let (status, body) = send(&app, "/api/top?model_base=sdxl&max_vram=12").await;
+    assert_eq!(status, StatusCode::OK);
+
+    let rows = body["data"].as_array().unwrap();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0]["device"], "RTX 3060");
+    assert_eq!(rows[0]["torch"], "2.1.0");
+    assert_eq!(rows[0]["avg_its"], 4.5);
+    assert_eq!(rows[0]["max_its"], 5.0);
+    assert_eq!(rows[0]["sample_count"], 2);
+    assert_eq!(rows[1]["torch"], "2.0.1");
+}
+
+#[tokio::test]
+async fn test_top_configurations_without_constraints() {
+    let app_state = create_test_app_state().await;
+    seed_runs(&app_state).await;
+    let app = create_app(app_state);
+
+    let (status, body) = send(&app, "/api/top?limit=2").await;
+    assert_eq!(status, StatusCode::OK);
+
+    let rows = body["data"].as_array().unwrap();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0]["device"], "RTX 4090");
+    assert_eq!(rows[1]["device"], "RTX 3060");
+    assert_eq!(rows[1]["avg_its"], 7.0);
+}
+
+#[tokio::test]
+async fn test_top_configurations_rejects_invalid_parameters() {
+    let app = create_app(create_test_app_state().await);
+
+    let (status, _) = send(&app, "/api/top?limit=0").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = send(&app, "/api/top?max_vram=-4").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
@@ -173,6 +173,8 @@ async fn create_required_runs(pool: &SqlitePool) -> Result<(), Box<dyn std::erro
             model_name: Some("test-model".to_string()),
             user: Some("test-user".to_string()),
             notes: Some(format!("Test run {}", i)),
+            created_at: None,
+            updated_at: None,
         };
         runs_repository.create(run).await?;
     }
@@ -191,6 +193,12 @@ fn create_test_gpus() -> Vec<Gpu> {
             gpu_chip: Some("RTX 4090".to_string()),
             brand: None, // Will be populated by the service
             is_laptop: None,
+            is_integrated: None,
+            is_cloud: None,
+            gpu_index: 0,
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
         },
         Gpu {
             id: None,
@@ -200,6 +208,12 @@ fn create_test_gpus() -> Vec<Gpu> {
             gpu_chip: Some("RTX 4080".to_string()),
             brand: None, // Will be populated by the service
             is_laptop: None,
+            is_integrated: None,
+            is_cloud: None,
+            gpu_index: 0,
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
         },
         Gpu {
             id: None,
@@ -209,6 +223,12 @@ fn create_test_gpus() -> Vec<Gpu> {
             gpu_chip: Some("RTX 5000".to_string()),
             brand: None, // Will be populated by the service
             is_laptop: None,
+            is_integrated: None,
+            is_cloud: None,
+            gpu_index: 0,
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
         },
         Gpu {
             id: None,
@@ -218,6 +238,12 @@ fn create_test_gpus() -> Vec<Gpu> {
             gpu_chip: Some("RX 7900 XTX".to_string()),
             brand: None, // Will be populated by the service
             is_laptop: None,
+            is_integrated: None,
+            is_cloud: None,
+            gpu_index: 0,
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
         },
     ]
 }
@@ -234,6 +260,12 @@ fn create_test_gpus_with_errors() -> Vec<Gpu> {
             gpu_chip: Some("RTX 4090".to_string()),
             brand: None,
             is_laptop: None,
+            is_integrated: None,
+            is_cloud: None,
+            gpu_index: 0,
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
         },
         // GPU with missing device (should cause error)
         Gpu {
@@ -244,6 +276,12 @@ fn create_test_gpus_with_errors() -> Vec<Gpu> {
             gpu_chip: Some("RTX 4080".to_string()),
             brand: None,
             is_laptop: None,
+            is_integrated: None,
+            is_cloud: None,
+            gpu_index: 0,
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
         },
         // Unknown GPU
         Gpu {
@@ -254,6 +292,12 @@ fn create_test_gpus_with_errors() -> Vec<Gpu> {
             gpu_chip: Some("Unknown".to_string()),
             brand: None,
             is_laptop: None,
+            is_integrated: None,
+            is_cloud: None,
+            gpu_index: 0,
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
         },
         // Valid NVIDIA GPU
         Gpu {
@@ -264,6 +308,12 @@ fn create_test_gpus_with_errors() -> Vec<Gpu> {
             gpu_chip: Some("Tesla V100".to_string()),
             brand: None,
             is_laptop: None,
+            is_integrated: None,
+            is_cloud: None,
+            gpu_index: 0,
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
         },
     ]
 }
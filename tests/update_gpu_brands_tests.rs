@@ -53,6 +53,8 @@ async fn setup_test_gpu_data(pool: &SqlitePool) -> Vec<Gpu> {
             model_name: Some("test-model".to_string()),
             user: Some("testuser".to_string()),
             notes: Some("test notes".to_string()),
+            created_at: None,
+            updated_at: None,
         },
         Run {
             id: None,
@@ -66,6 +68,8 @@ async fn setup_test_gpu_data(pool: &SqlitePool) -> Vec<Gpu> {
             model_name: Some("another-model".to_string()),
             user: Some("anotheruser".to_string()),
             notes: Some("another test".to_string()),
+            created_at: None,
+            updated_at: None,
         },
         Run {
             id: None,
@@ -79,6 +83,8 @@ async fn setup_test_gpu_data(pool: &SqlitePool) -> Vec<Gpu> {
             model_name: Some("simple-model".to_string()),
             user: Some("simpleuser".to_string()),
             notes: Some("simple test".to_string()),
+            created_at: None,
+            updated_at: None,
         },
     ];
 
@@ -96,6 +102,12 @@ async fn setup_test_gpu_data(pool: &SqlitePool) -> Vec<Gpu> {
             gpu_chip: Some("gpu:RTX 4090".to_string()),
             brand: None, // Will be populated by the update process
             is_laptop: None,
+            is_integrated: None,
+            is_cloud: None,
+            gpu_index: 0,
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
         };
 
         let created_gpu = gpu_repo.create(gpu).await.unwrap();
@@ -181,6 +193,8 @@ async fn test_brand_detection_logic() {
             model_name: Some("test-model".to_string()),
             user: Some("testuser".to_string()),
             notes: Some("test notes".to_string()),
+            created_at: None,
+            updated_at: None,
         };
 
         let created_run = runs_repo.create(test_run).await.unwrap();
@@ -194,6 +208,12 @@ async fn test_brand_detection_logic() {
             gpu_chip: Some("gpu:Test".to_string()),
             brand: None,
             is_laptop: None,
+            is_integrated: None,
+            is_cloud: None,
+            gpu_index: 0,
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
         };
 
         let created_gpu = gpu_repo.create(gpu).await.unwrap();
@@ -309,4 +329,39 @@ async fn test_update_gpu_brands_response_format() {
     assert!(brand_names.contains(&"Amd".to_string()));
     assert!(brand_names.contains(&"Intel".to_string()));
     assert!(brand_names.contains(&"Unknown".to_string()));
-} 
\ No newline at end of file
+} 
+// Test that a dry run reports the brand changes without writing them
+#[tokio::test]
+async fn test_update_gpu_brands_dry_run() {
+    let pool = create_test_pool().await;
+    let test_gpus = setup_test_gpu_data(&pool).await;
+
+    let app_state = AppState {
+        db: pool.clone(),
+        settings: sd_its_benchmark::config::settings::Settings::new().unwrap(),
+    };
+    let app = create_test_app(app_state);
+
+    let request = Request::builder()
+        .method(Method::POST)
+        .uri("/api/update-gpu-brands?dry_run=true")
+        .body(axum::body::Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let response_json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(response_json["dry_run"], true);
+    assert_eq!(response_json["total_updates"], test_gpus.len());
+    let samples = response_json["samples"].as_array().unwrap();
+    assert!(!samples.is_empty());
+    assert_eq!(samples[0]["new_brand"], "nvidia");
+
+    // Nothing was written
+    let gpu_repo = GpuRepository::new(pool);
+    let all_gpus = gpu_repo.find_all().await.unwrap();
+    assert!(all_gpus.iter().all(|gpu| gpu.brand.is_none()));
+}
@@ -148,6 +148,8 @@ async fn create_required_runs(pool: &SqlitePool) -> Result<(), Box<dyn std::erro
             model_name: Some("test-model".to_string()),
             user: Some("test-user".to_string()),
             notes: Some(format!("Test run {}", i)),
+            created_at: None,
+            updated_at: None,
         };
         runs_repository.create(run).await?;
     }
@@ -165,7 +167,13 @@ fn create_test_gpus() -> Vec<Gpu> {
             driver: Some("470.82.01".to_string()),
             gpu_chip: Some("RTX 4090".to_string()),
             brand: Some("nvidia".to_string()),
-            is_laptop: None, // Will be populated by the service
+            is_laptop: None, // Will be populated by the service,
+            is_integrated: None,
+            is_cloud: None,
+            gpu_index: 0,
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
         },
         Gpu {
             id: None,
@@ -174,7 +182,13 @@ fn create_test_gpus() -> Vec<Gpu> {
             driver: Some("535.98.01".to_string()),
             gpu_chip: Some("RTX 4090".to_string()),
             brand: Some("nvidia".to_string()),
-            is_laptop: None, // Will be populated by the service
+            is_laptop: None, // Will be populated by the service,
+            is_integrated: None,
+            is_cloud: None,
+            gpu_index: 0,
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
         },
         Gpu {
             id: None,
@@ -183,7 +197,13 @@ fn create_test_gpus() -> Vec<Gpu> {
             driver: Some("23.12.1".to_string()),
             gpu_chip: Some("RX 6800".to_string()),
             brand: Some("amd".to_string()),
-            is_laptop: None, // Will be populated by the service
+            is_laptop: None, // Will be populated by the service,
+            is_integrated: None,
+            is_cloud: None,
+            gpu_index: 0,
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
         },
         Gpu {
             id: None,
@@ -192,7 +212,13 @@ fn create_test_gpus() -> Vec<Gpu> {
             driver: Some("23.12.1".to_string()),
             gpu_chip: Some("RX 6800M".to_string()),
             brand: Some("amd".to_string()),
-            is_laptop: None, // Will be populated by the service
+            is_laptop: None, // Will be populated by the service,
+            is_integrated: None,
+            is_cloud: None,
+            gpu_index: 0,
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
         },
     ]
 }
@@ -209,6 +235,12 @@ fn create_test_gpus_with_errors() -> Vec<Gpu> {
             gpu_chip: Some("RTX 4090".to_string()),
             brand: Some("nvidia".to_string()),
             is_laptop: None,
+            is_integrated: None,
+            is_cloud: None,
+            gpu_index: 0,
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
         },
         // GPU with missing device (should cause error)
         Gpu {
@@ -219,6 +251,12 @@ fn create_test_gpus_with_errors() -> Vec<Gpu> {
             gpu_chip: Some("RTX 4080".to_string()),
             brand: Some("nvidia".to_string()),
             is_laptop: None,
+            is_integrated: None,
+            is_cloud: None,
+            gpu_index: 0,
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
         },
         // Valid laptop GPU
         Gpu {
@@ -229,6 +267,12 @@ fn create_test_gpus_with_errors() -> Vec<Gpu> {
             gpu_chip: Some("RTX 4090".to_string()),
             brand: Some("nvidia".to_string()),
             is_laptop: None,
+            is_integrated: None,
+            is_cloud: None,
+            gpu_index: 0,
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
         },
         // Valid mobile GPU
         Gpu {
@@ -239,6 +283,12 @@ fn create_test_gpus_with_errors() -> Vec<Gpu> {
             gpu_chip: Some("RX 6800M".to_string()),
             brand: Some("amd".to_string()),
             is_laptop: None,
+            is_integrated: None,
+            is_cloud: None,
+            gpu_index: 0,
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
         },
     ]
 }
@@ -8,6 +8,7 @@ use tower::ServiceExt;
 
 use sd_its_benchmark::{
     AppState,
+    config::settings::LaptopDetectionConfig,
     handlers::admin::update_gpu_laptop_info,
     models::{gpu::Gpu, runs::Run},
     repositories::{
@@ -53,6 +54,8 @@ async fn setup_test_gpu_data(pool: &SqlitePool) -> Vec<Gpu> {
             model_name: Some("test-model".to_string()),
             user: Some("testuser".to_string()),
             notes: Some("test notes".to_string()),
+            created_at: None,
+            updated_at: None,
         },
         Run {
             id: None,
@@ -66,6 +69,8 @@ async fn setup_test_gpu_data(pool: &SqlitePool) -> Vec<Gpu> {
             model_name: Some("another-model".to_string()),
             user: Some("anotheruser".to_string()),
             notes: Some("another test".to_string()),
+            created_at: None,
+            updated_at: None,
         },
         Run {
             id: None,
@@ -79,6 +84,8 @@ async fn setup_test_gpu_data(pool: &SqlitePool) -> Vec<Gpu> {
             model_name: Some("simple-model".to_string()),
             user: Some("simpleuser".to_string()),
             notes: Some("simple test".to_string()),
+            created_at: None,
+            updated_at: None,
         },
     ];
 
@@ -95,7 +102,13 @@ async fn setup_test_gpu_data(pool: &SqlitePool) -> Vec<Gpu> {
             driver: Some("535.86.10".to_string()),
             gpu_chip: Some("gpu:RTX 4090".to_string()),
             brand: Some("nvidia".to_string()),
-            is_laptop: None, // Will be populated by the update process
+            is_laptop: None, // Will be populated by the update process,
+            is_integrated: None,
+            is_cloud: None,
+            gpu_index: 0,
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
         };
 
         let created_gpu = gpu_repo.create(gpu).await.unwrap();
@@ -179,6 +192,8 @@ async fn test_laptop_detection_logic() {
             model_name: Some("test-model".to_string()),
             user: Some("testuser".to_string()),
             notes: Some("test notes".to_string()),
+            created_at: None,
+            updated_at: None,
         };
 
         let created_run = runs_repo.create(test_run).await.unwrap();
@@ -192,6 +207,12 @@ async fn test_laptop_detection_logic() {
             gpu_chip: Some("gpu:Test".to_string()),
             brand: Some("nvidia".to_string()),
             is_laptop: None,
+            is_integrated: None,
+            is_cloud: None,
+            gpu_index: 0,
+            parser_version: None,
+            created_at: None,
+            updated_at: None,
         };
 
         let created_gpu = gpu_repo.create(gpu).await.unwrap();
@@ -290,4 +311,99 @@ async fn test_update_gpu_laptop_info_response_format() {
     
     assert!(total_updates > 0);
     assert!(laptop_updates <= total_updates);
-} 
\ No newline at end of file
+} 
+// Test configured laptop patterns, matched ignoring case and counted per pattern
+#[tokio::test]
+async fn test_update_gpu_laptop_info_with_configured_patterns() {
+    let pool = create_test_pool().await;
+    let runs_repo = RunsRepository::new(pool.clone());
+    let gpu_repo = GpuRepository::new(pool.clone());
+
+    let test_devices = vec![
+        ("NVIDIA GeForce RTX 2080 with max-q design", true),
+        ("NVIDIA GeForce RTX 3070 LAPTOP GPU", true),
+        ("NVIDIA GeForce RTX 3080 Laptop GPU", true),
+        ("AMD Radeon RX 6800 Mobile", false), // "Mobile" is not configured here
+        ("NVIDIA GeForce RTX 4090", false),
+    ];
+
+    let mut created_gpus = Vec::new();
+    for (device, expected_laptop) in test_devices {
+        let created_run = runs_repo
+            .create(Run {
+                id: None,
+                timestamp: Some("2024-01-01T10:00:00Z".to_string()),
+                vram_usage: None,
+                info: None,
+                system_info: None,
+                model_info: None,
+                device_info: Some(format!("device:{} driver:535.86.10", device)),
+                xformers: None,
+                model_name: None,
+                user: Some("testuser".to_string()),
+                notes: None,
+                created_at: None,
+                updated_at: None,
+            })
+            .await
+            .unwrap();
+
+        let created_gpu = gpu_repo
+            .create(Gpu {
+                id: None,
+                run_id: created_run.id,
+                device: Some(device.to_string()),
+                driver: Some("535.86.10".to_string()),
+                gpu_chip: None,
+                brand: None,
+                is_laptop: None,
+                is_integrated: None,
+                is_cloud: None,
+                gpu_index: 0,
+                parser_version: None,
+                created_at: None,
+                updated_at: None,
+            })
+            .await
+            .unwrap();
+        created_gpus.push((created_gpu, expected_laptop));
+    }
+
+    let app_state = AppState {
+        db: pool.clone(),
+        settings: sd_its_benchmark::config::settings::Settings {
+            laptop_detection: LaptopDetectionConfig {
+                patterns: vec!["Max-Q".to_string(), "Laptop".to_string()],
+            },
+            ..sd_its_benchmark::config::settings::Settings::new().unwrap()
+        },
+    };
+    let app = create_test_app(app_state);
+
+    let request = Request::builder()
+        .method(Method::POST)
+        .uri("/api/update-gpu-laptop-info")
+        .body(axum::body::Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let response_json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(response_json["laptop_only_updates"], 3);
+    assert_eq!(
+        response_json["pattern_matches"],
+        serde_json::json!([
+            { "pattern": "Max-Q", "count": 1 },
+            { "pattern": "Laptop", "count": 2 },
+        ])
+    );
+
+    let all_gpus = GpuRepository::new(pool).find_all().await.unwrap();
+    for (original_gpu, expected_laptop) in created_gpus {
+        let updated_gpu = all_gpus.iter().find(|g| g.id == original_gpu.id).unwrap();
+        assert_eq!(updated_gpu.is_laptop, Some(expected_laptop), "{:?}", updated_gpu.device);
+    }
+}
@@ -39,6 +39,8 @@ async fn create_required_runs(pool: &SqlitePool) -> Vec<i64> {
             model_name: Some("model-1".to_string()),
             user: Some("test-user-1".to_string()),
             notes: Some("test-notes-1".to_string()),
+            created_at: None,
+            updated_at: None,
         },
         Run {
             id: None,
@@ -52,6 +54,8 @@ async fn create_required_runs(pool: &SqlitePool) -> Vec<i64> {
             model_name: Some("model-2".to_string()),
             user: Some("test-user-2".to_string()),
             notes: Some("test-notes-2".to_string()),
+            created_at: None,
+            updated_at: None,
         },
         Run {
             id: None,
@@ -65,6 +69,8 @@ async fn create_required_runs(pool: &SqlitePool) -> Vec<i64> {
             model_name: Some("model-3".to_string()),
             user: Some("test-user-3".to_string()),
             notes: Some("test-notes-3".to_string()),
+            created_at: None,
+            updated_at: None,
         },
     ];
 
@@ -92,11 +98,17 @@ async fn test_update_run_more_details_service_integration() {
             id: None,
             model_name: Some("model-1".to_string()),
             base_model: Some("base-model-1".to_string()),
+            version: 1,
+            created_at: None,
+            updated_at: None,
         },
         ModelMap {
             id: None,
             model_name: Some("model-2".to_string()),
             base_model: Some("base-model-2".to_string()),
+            version: 1,
+            created_at: None,
+            updated_at: None,
         },
     ];
 
@@ -115,7 +127,9 @@ async fn test_update_run_more_details_service_integration() {
             model_name: Some("model-1".to_string()),
             user: Some("test-user-1".to_string()),
             notes: Some("test-notes-1".to_string()),
-            model_map_id: None, // Will be updated
+            model_map_id: None, // Will be updated,
+            created_at: None,
+            updated_at: None,
         },
         RunMoreDetails {
             id: None,
@@ -124,7 +138,9 @@ async fn test_update_run_more_details_service_integration() {
             model_name: Some("model-2".to_string()),
             user: Some("test-user-2".to_string()),
             notes: Some("test-notes-2".to_string()),
-            model_map_id: None, // Will be updated
+            model_map_id: None, // Will be updated,
+            created_at: None,
+            updated_at: None,
         },
         RunMoreDetails {
             id: None,
@@ -133,7 +149,9 @@ async fn test_update_run_more_details_service_integration() {
             model_name: Some("model-3".to_string()),
             user: Some("test-user-3".to_string()),
             notes: Some("test-notes-3".to_string()),
-            model_map_id: None, // Will not be updated (no matching ModelMap)
+            model_map_id: None, // Will not be updated (no matching ModelMap),
+            created_at: None,
+            updated_at: None,
         },
     ];
 
@@ -186,6 +204,9 @@ async fn test_update_run_more_details_service_no_updates_needed() {
         id: None,
         model_name: Some("model-1".to_string()),
         base_model: Some("base-model-1".to_string()),
+        version: 1,
+        created_at: None,
+        updated_at: None,
     };
 
     let created_model_map = model_map_repo.create(model_map).await.unwrap();
@@ -198,7 +219,9 @@ async fn test_update_run_more_details_service_no_updates_needed() {
         model_name: Some("model-1".to_string()),
         user: Some("test-user".to_string()),
         notes: Some("test-notes".to_string()),
-        model_map_id: Some(model_map_id), // Already has ModelMapId
+        model_map_id: Some(model_map_id), // Already has ModelMapId,
+        created_at: None,
+        updated_at: None,
     };
 
     run_more_details_repo.create(run_more_detail).await.unwrap();
@@ -230,6 +253,8 @@ async fn test_update_run_more_details_service_null_model_name() {
         user: Some("test-user".to_string()),
         notes: Some("test-notes".to_string()),
         model_map_id: None,
+        created_at: None,
+        updated_at: None,
     };
 
     run_more_details_repo.create(run_more_detail).await.unwrap();
@@ -55,6 +55,8 @@ async fn setup_test_data(pool: &SqlitePool) -> (Vec<RunMoreDetails>, Vec<ModelMa
             model_name: Some("model-1".to_string()),
             user: Some("test-user-1".to_string()),
             notes: Some("test-notes-1".to_string()),
+            created_at: None,
+            updated_at: None,
         },
         Run {
             id: None,
@@ -68,6 +70,8 @@ async fn setup_test_data(pool: &SqlitePool) -> (Vec<RunMoreDetails>, Vec<ModelMa
             model_name: Some("model-2".to_string()),
             user: Some("test-user-2".to_string()),
             notes: Some("test-notes-2".to_string()),
+            created_at: None,
+            updated_at: None,
         },
         Run {
             id: None,
@@ -81,6 +85,8 @@ async fn setup_test_data(pool: &SqlitePool) -> (Vec<RunMoreDetails>, Vec<ModelMa
             model_name: Some("model-3".to_string()),
             user: Some("test-user-3".to_string()),
             notes: Some("test-notes-3".to_string()),
+            created_at: None,
+            updated_at: None,
         },
     ];
 
@@ -97,11 +103,17 @@ async fn setup_test_data(pool: &SqlitePool) -> (Vec<RunMoreDetails>, Vec<ModelMa
             id: None,
             model_name: Some("model-1".to_string()),
             base_model: Some("base-model-1".to_string()),
+            version: 1,
+            created_at: None,
+            updated_at: None,
         },
         ModelMap {
             id: None,
             model_name: Some("model-2".to_string()),
             base_model: Some("base-model-2".to_string()),
+            version: 1,
+            created_at: None,
+            updated_at: None,
         },
     ];
 
@@ -120,7 +132,9 @@ async fn setup_test_data(pool: &SqlitePool) -> (Vec<RunMoreDetails>, Vec<ModelMa
             model_name: Some("model-1".to_string()),
             user: Some("test-user-1".to_string()),
             notes: Some("test-notes-1".to_string()),
-            model_map_id: None, // Will be updated
+            model_map_id: None, // Will be updated,
+            created_at: None,
+            updated_at: None,
         },
         RunMoreDetails {
             id: None,
@@ -129,7 +143,9 @@ async fn setup_test_data(pool: &SqlitePool) -> (Vec<RunMoreDetails>, Vec<ModelMa
             model_name: Some("model-2".to_string()),
             user: Some("test-user-2".to_string()),
             notes: Some("test-notes-2".to_string()),
-            model_map_id: None, // Will be updated
+            model_map_id: None, // Will be updated,
+            created_at: None,
+            updated_at: None,
         },
         RunMoreDetails {
             id: None,
@@ -138,7 +154,9 @@ async fn setup_test_data(pool: &SqlitePool) -> (Vec<RunMoreDetails>, Vec<ModelMa
             model_name: Some("model-3".to_string()),
             user: Some("test-user-3".to_string()),
             notes: Some("test-notes-3".to_string()),
-            model_map_id: None, // Will not be updated (no matching ModelMap)
+            model_map_id: None, // Will not be updated (no matching ModelMap),
+            created_at: None,
+            updated_at: None,
         },
     ];
 
@@ -207,6 +225,8 @@ async fn test_update_run_more_details_with_modelmapid_no_updates_needed() {
         model_name: Some("model-1".to_string()),
         user: Some("test-user".to_string()),
         notes: Some("test-notes".to_string()),
+        created_at: None,
+        updated_at: None,
     };
 
     let created_run = runs_repo.create(run).await.unwrap();
@@ -216,6 +236,9 @@ async fn test_update_run_more_details_with_modelmapid_no_updates_needed() {
         id: None,
         model_name: Some("model-1".to_string()),
         base_model: Some("base-model-1".to_string()),
+        version: 1,
+        created_at: None,
+        updated_at: None,
     };
 
     let created_model_map = model_map_repo.create(model_map).await.unwrap();
@@ -228,7 +251,9 @@ async fn test_update_run_more_details_with_modelmapid_no_updates_needed() {
         model_name: Some("model-1".to_string()),
         user: Some("test-user".to_string()),
         notes: Some("test-notes".to_string()),
-        model_map_id: Some(model_map_id), // Already has ModelMapId
+        model_map_id: Some(model_map_id), // Already has ModelMapId,
+        created_at: None,
+        updated_at: None,
     };
 
     run_more_details_repo.create(run_more_detail).await.unwrap();
@@ -311,6 +336,8 @@ async fn test_update_run_more_details_with_modelmapid_null_model_name() {
         model_name: Some("model-1".to_string()),
         user: Some("test-user".to_string()),
         notes: Some("test-notes".to_string()),
+        created_at: None,
+        updated_at: None,
     };
 
     let created_run = runs_repo.create(run).await.unwrap();
@@ -325,6 +352,8 @@ async fn test_update_run_more_details_with_modelmapid_null_model_name() {
         user: Some("test-user".to_string()),
         notes: Some("test-notes".to_string()),
         model_map_id: None,
+        created_at: None,
+        updated_at: None,
     };
 
     run_more_details_repo.create(run_more_detail).await.unwrap();
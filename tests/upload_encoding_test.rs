@@ -0,0 +1,133 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    routing::post,
+    Router,
+};
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}},
+    handlers::upload::upload_file_compat,
+    repositories::{runs_repository::RunsRepository, traits::Repository},
+};
+
+const BOUNDARY: &str = "----WebKitFormBoundary7MA4YWxkTrZu0gW";
+
+async fn create_test_app_state() -> AppState {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    AppState {
+        db: db_pool,
+        settings: Settings::default(),
+    }
+}
+
+/// A benchmark file of one row per `notes`, each placed as raw bytes into the JSON
+fn benchmark_file(notes: &[&[u8]]) -> Vec<u8> {
+    let mut file = b"[".to_vec();
+    for (index, note) in notes.iter().enumerate() {
+        if index > 0 {
+            file.push(b',');
+        }
+        let mut row = json!({
+            "timestamp": "2024-01-01T10:00:00Z",
+            "vram_usage": "8.5/12.0/15.2",
+            "info": "app:stable-diffusion-webui updated:2024-01-01 hash:abc123",
+            "system_info": "arch:x86_64 system:Linux",
+            "model_info": "torch:2.0.1",
+            "device_info": "device:NVIDIA GeForce RTX 3080 driver:535.86",
+            "xformers": "True",
+            "model_name": "v1-5-pruned-emaonly.safetensors",
+            "user": "alice",
+            "notes": "NOTES"
+        })
+        .to_string()
+        .into_bytes();
+        let at = row.windows(5).position(|window| window == b"NOTES").unwrap();
+        row.splice(at..at + 5, note.iter().copied());
+        file.extend(row);
+    }
+    file.push(b']');
+
+    file
+}
+
+async fn upload(app_state: &AppState, content: &[u8]) -> (StatusCode, Value) {
+    let mut body = format!(
+        "--{BOUNDARY}\r\nContent-Disposition: form-data; name=\"benchmark\"; filename=\"runs.json\"\r\nContent-Type: application/json\r\n\r\n"
+    )
+    .into_bytes();
+    body.extend_from_slice(content);
+    body.extend(format!("\r\n--{BOUNDARY}--\r\n").into_bytes());
+
+    let app = Router::new()
+        .route("/api/upload", post(upload_file_compat))
+        .with_state(app_state.clone());
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/upload")
+        .header("content-type", format!("multipart/form-data; boundary={}", BOUNDARY))
+        .body(Body::from(body))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+#[tokio::test]
+async fn test_windows_1252_bytes_are_repaired_and_reported_per_row() {
+    let app_state = create_test_app_state().await;
+    // Curly quotes and an accent as a Windows editor saves them
+    let file = benchmark_file(&[b"plain", b"\x93fast\x94 on my caf\xE9 PC"]);
+
+    let (status, body) = upload(&app_state, &file).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["rows_inserted"], 2);
+    assert_eq!(
+        body["encoding_repairs"],
+        json!({
+            "encoding": "utf-8",
+            "byte_order_mark": false,
+            "repaired_characters": 3,
+            "rows": [{ "row": 1, "fields": ["notes"], "characters": "“”é" }]
+        })
+    );
+
+    let runs = RunsRepository::new(app_state.db.clone()).find_all().await.unwrap();
+    let notes: Vec<_> = runs.iter().filter_map(|run| run.notes.as_deref()).collect();
+    assert!(notes.contains(&"“fast” on my café PC"), "{:?}", notes);
+}
+
+#[tokio::test]
+async fn test_byte_order_mark_is_accepted() {
+    let app_state = create_test_app_state().await;
+    let mut file = b"\xEF\xBB\xBF".to_vec();
+    file.extend(benchmark_file(&[b"plain"]));
+
+    let (status, body) = upload(&app_state, &file).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["rows_inserted"], 1);
+    assert_eq!(body["encoding_repairs"]["byte_order_mark"], true);
+    assert_eq!(body["encoding_repairs"]["repaired_characters"], 0);
+}
+
+#[tokio::test]
+async fn test_clean_utf8_has_no_encoding_report() {
+    let app_state = create_test_app_state().await;
+    let file = benchmark_file(&["“already” UTF-8".as_bytes()]);
+
+    let (status, body) = upload(&app_state, &file).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert!(body.get("encoding_repairs").is_none(), "{}", body);
+}
@@ -0,0 +1,70 @@
+use axum::{body::Body, http::Request, routing::get, Router};
+use serde_json::Value;
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    handlers::upload_formats::list_upload_formats,
+    services::{
+        ingest::{detect_benchmark_format, detect_schema_drift, parse_benchmark_document, registered_formats, WebUiRow},
+        load_data::SyntheticDataGenerator,
+    },
+};
+
+/// Example exports shipped with the repository, each with its name
+fn bundled_examples() -> Vec<(&'static str, Vec<u8>)> {
+    vec![
+        ("data/self_test_runs.json", include_bytes!("../data/self_test_runs.json").to_vec()),
+        ("test_data/test_runs_data.json", include_bytes!("../test_data/test_runs_data.json").to_vec()),
+        ("synthetic export", SyntheticDataGenerator::new(1).export(25)),
+    ]
+}
+
+#[tokio::test]
+async fn test_upload_formats_endpoint_publishes_the_parser_schemas() {
+    let app = Router::new().route("/api/upload-formats", get(list_upload_formats));
+    let request = Request::builder().uri("/api/upload-formats").body(Body::empty()).unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body: Value = serde_json::from_slice(&bytes).unwrap();
+
+    let published = body["data"].as_array().unwrap();
+    let formats = registered_formats();
+    assert_eq!(published.len(), formats.len());
+    for (published, format) in published.iter().zip(&formats) {
+        assert_eq!(published["name"], format.name());
+        assert_eq!(published["schema"], format.schema(), "{}", format.name());
+    }
+}
+
+#[test]
+fn test_bundled_examples_conform_to_their_published_schema() {
+    for (name, bytes) in bundled_examples() {
+        let document = parse_benchmark_document(&bytes).unwrap();
+        let format = detect_benchmark_format(&document).unwrap();
+        let schema = format.schema();
+
+        let drift = detect_schema_drift(&schema, &document);
+        assert!(drift.is_empty(), "{} drifts from the {} schema: {:?}", name, format.name(), drift);
+        let rows = format.to_run_data(&document).unwrap();
+        assert_eq!(rows.len(), document.as_array().unwrap().len(), "{}", name);
+    }
+}
+
+#[test]
+fn test_webui_rows_round_trip_through_the_schema_types() {
+    for (name, bytes) in bundled_examples() {
+        let document = parse_benchmark_document(&bytes).unwrap();
+        let rows: Vec<WebUiRow> = serde_json::from_value(document.clone()).unwrap();
+
+        // What the serde models write back must still be a document the schema describes
+        let written = serde_json::to_value(&rows).unwrap();
+        let schema = detect_benchmark_format(&written).unwrap().schema();
+        assert!(detect_schema_drift(&schema, &written).is_empty(), "{}", name);
+        let reread: Vec<WebUiRow> = serde_json::from_value(written).unwrap();
+        assert_eq!(reread.len(), rows.len(), "{}", name);
+        for (original, reread) in rows.iter().zip(&reread) {
+            assert_eq!(original.run.vram_usage, reread.run.vram_usage, "{}", name);
+            assert_eq!(original.run.timestamp, reread.run.timestamp, "{}", name);
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    routing::post,
+    Router,
+};
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}},
+    handlers::upload::upload_file_compat,
+    repositories::{runs_repository::RunsRepository, traits::Repository},
+};
+
+const BOUNDARY: &str = "----WebKitFormBoundary7MA4YWxkTrZu0gW";
+
+async fn create_test_app_state(max_rows: usize, max_field_bytes: usize) -> AppState {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    let mut settings = Settings::default();
+    settings.file_upload.max_rows = max_rows;
+    settings.file_upload.max_field_bytes = max_field_bytes;
+
+    AppState { db: db_pool, settings }
+}
+
+fn runs(notes: &[&str]) -> String {
+    let rows: Vec<Value> = notes
+        .iter()
+        .map(|notes| {
+            json!({
+                "timestamp": "2024-01-01T10:00:00Z",
+                "vram_usage": "8.5/12.0/15.2",
+                "info": "app:stable-diffusion-webui updated:2024-01-01 hash:abc123",
+                "system_info": "arch:x86_64 system:Linux",
+                "model_info": "torch:2.0.1",
+                "device_info": "device:NVIDIA GeForce RTX 3080 driver:535.86",
+                "xformers": "True",
+                "model_name": "v1-5-pruned-emaonly.safetensors",
+                "user": "alice",
+                "notes": notes
+            })
+        })
+        .collect();
+
+    Value::Array(rows).to_string()
+}
+
+async fn upload(app_state: &AppState, content: &str) -> (StatusCode, Value) {
+    let body = format!(
+        "--{BOUNDARY}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"runs.json\"\r\nContent-Type: application/json\r\n\r\n{content}\r\n--{BOUNDARY}--\r\n"
+    );
+    let app = Router::new()
+        .route("/api/upload", post(upload_file_compat))
+        .with_state(app_state.clone());
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/upload")
+        .header("content-type", format!("multipart/form-data; boundary={}", BOUNDARY))
+        .body(Body::from(body))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+#[tokio::test]
+async fn test_upload_with_too_many_rows_is_refused() {
+    let app_state = create_test_app_state(2, 1024).await;
+
+    let (status, body) = upload(&app_state, &runs(&["", "", ""])).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{}", body);
+    let message = body["message"].as_str().unwrap();
+    assert!(message.contains("Upload has 3 rows, more than the 2 allowed"), "{}", message);
+    assert_eq!(RunsRepository::new(app_state.db.clone()).count().await.unwrap(), 0);
+
+    let (status, body) = upload(&app_state, &runs(&["", ""])).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+}
+
+#[tokio::test]
+async fn test_oversized_field_is_refused_naming_its_row() {
+    let app_state = create_test_app_state(10, 100).await;
+    let long_notes = "x".repeat(101);
+
+    let (status, body) = upload(&app_state, &runs(&["fine", &long_notes])).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{}", body);
+    let message = body["message"].as_str().unwrap();
+    assert!(message.contains("Field notes at index 1 is 101 bytes, more than the 100 allowed"), "{}", message);
+    assert_eq!(RunsRepository::new(app_state.db.clone()).count().await.unwrap(), 0);
+}
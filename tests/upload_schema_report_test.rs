@@ -0,0 +1,151 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    routing::{get, post, put},
+    Router,
+};
+use serde_json::{json, Value};
+use tempfile::TempDir;
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}},
+    handlers::{
+        admin::save_data,
+        uploads::{complete_upload, get_schema_report, init_upload, upload_part},
+    },
+};
+
+const BOUNDARY: &str = "----WebKitFormBoundary7MA4YWxkTrZu0gW";
+
+async fn create_test_app_state(temp_dir: &TempDir) -> AppState {
+    let mut settings = Settings::default();
+    settings.file_upload.temp_dir = temp_dir.path().to_path_buf();
+
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    AppState {
+        db: db_pool,
+        settings,
+    }
+}
+
+fn create_app(app_state: AppState) -> Router {
+    Router::new()
+        .route("/api/save-data", post(save_data))
+        .route("/api/uploads/init", post(init_upload))
+        .route("/api/uploads/{id}/part/{n}", put(upload_part))
+        .route("/api/uploads/{id}/complete", post(complete_upload))
+        .route("/api/uploads/{id}/schema-report", get(get_schema_report))
+        .with_state(app_state)
+}
+
+async fn send(app: &Router, method: &str, uri: &str, content_type: &str, body: Vec<u8>) -> (StatusCode, Value) {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("content-type", content_type)
+        .body(Body::from(body))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+fn run_row(model_name: &str) -> Value {
+    json!({
+        "timestamp": "2024-01-01T10:00:00Z",
+        "vram_usage": "10.0/11.0",
+        "info": "app:test",
+        "system_info": "system:Linux",
+        "model_info": "torch:2.1",
+        "device_info": "device:NVIDIA GeForce RTX 4090",
+        "xformers": "True",
+        "model_name": model_name,
+        "user": "tester",
+        "notes": ""
+    })
+}
+
+#[tokio::test]
+async fn test_save_data_records_unknown_keys() {
+    let temp_dir = TempDir::new().unwrap();
+    let app = create_app(create_test_app_state(&temp_dir).await);
+
+    let mut first = run_row("sdxl");
+    first["gpu_temp"] = json!(70);
+    first["ram_gb"] = json!(32);
+    let mut second = run_row("sd15");
+    second["gpu_temp"] = json!(65);
+    let body = format!(
+        "--{boundary}\r\n\
+        Content-Disposition: form-data; name=\"file\"; filename=\"runs.json\"\r\n\
+        Content-Type: application/json\r\n\
+        \r\n\
+        {json_data}\r\n\
+        --{boundary}--\r\n",
+        boundary = BOUNDARY,
+        json_data = json!([first, second])
+    );
+
+    let (status, uploaded) = send(
+        &app,
+        "POST",
+        "/api/save-data",
+        &format!("multipart/form-data; boundary={}", BOUNDARY),
+        body.into_bytes(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(uploaded["rows_inserted"], 2);
+    let upload_id = uploaded["upload_id"].as_str().unwrap();
+
+    let (status, report) = send(&app, "GET", &format!("/api/uploads/{}/schema-report", upload_id), "application/json", vec![]).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(report["data"]["file_name"], "runs.json");
+    assert_eq!(report["data"]["format"], "webui");
+    // Declared optional keys such as ram_gb are neither unknown nor missing when left out
+    assert_eq!(report["data"]["unknown_keys"], json!([{ "path": "[].gpu_temp", "occurrences": 2 }]));
+    assert_eq!(report["data"]["missing_keys"], json!([]));
+
+    let (status, _) = send(&app, "GET", "/api/uploads/no-such-upload/schema-report", "application/json", vec![]).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_rejected_upload_keeps_its_schema_report() {
+    let temp_dir = TempDir::new().unwrap();
+    let app = create_app(create_test_app_state(&temp_dir).await);
+
+    let mut row = run_row("sdxl");
+    row.as_object_mut().unwrap().remove("notes");
+    let file = json!([row]).to_string().into_bytes();
+
+    let (status, init) = send(
+        &app,
+        "POST",
+        "/api/uploads/init",
+        "application/json",
+        json!({ "file_name": "runs.json", "total_parts": 1 }).to_string().into_bytes(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let id = init["data"]["id"].as_str().unwrap().to_string();
+    send(&app, "PUT", &format!("/api/uploads/{}/part/1", id), "application/octet-stream", file).await;
+
+    let (status, _) = send(&app, "POST", &format!("/api/uploads/{}/complete", id), "application/json", vec![]).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, report) = send(&app, "GET", &format!("/api/uploads/{}/schema-report", id), "application/json", vec![]).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(report["data"]["missing_keys"], json!(["[].notes"]));
+    assert_eq!(report["data"]["unknown_keys"], json!([]));
+}
@@ -0,0 +1,156 @@
+use std::sync::{Arc, Mutex};
+
+use axum::{
+    body::Body,
+    extract::State,
+    http::{Request, StatusCode},
+    routing::get,
+    Router,
+};
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+use sd_its_benchmark::{
+    AppState,
+    config::{Settings, database::{DatabaseConfig, create_pool, initialize_database}},
+    error::i18n::ErrorCatalog,
+    handlers::{upstream_sync::sync_upstream, validation::RunData},
+    repositories::{runs_repository::RunsRepository, traits::Repository},
+    router::create_router,
+    services::data_processing::save_data_service::runs_from_data,
+};
+
+type Upstream = Arc<Mutex<Vec<Value>>>;
+
+fn row(user: &str) -> Value {
+    json!({
+        "timestamp": "2024-01-01T10:00:00Z",
+        "vram_usage": "10.0/11.0",
+        "info": "app:test",
+        "system_info": "system:Linux",
+        "model_info": "torch:2.1",
+        "device_info": "device:NVIDIA GeForce RTX 4090",
+        "xformers": "True",
+        "model_name": "sdxl",
+        "user": user,
+        "notes": ""
+    })
+}
+
+/// Serve the upstream rows as a benchmark JSON file on a local port, returning its URL
+async fn start_upstream(rows: Upstream) -> String {
+    let app = Router::new()
+        .route(
+            "/benchmark.json",
+            get(|State(rows): State<Upstream>| async move { Value::Array(rows.lock().unwrap().clone()).to_string() }),
+        )
+        .with_state(rows);
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/benchmark.json", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    url
+}
+
+async fn create_test_app_state(url: String) -> AppState {
+    let db_config = DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        ..DatabaseConfig::default()
+    };
+    let db_pool = create_pool(&db_config).await.expect("Failed to create test pool");
+    initialize_database(&db_pool).await.expect("Failed to initialize test database");
+
+    let mut settings = Settings::default();
+    settings.remote_ingest.allowed_hosts = vec!["127.0.0.1".to_string()];
+    settings.upstream_sync.url = Some(url);
+    settings.upstream_sync.run_pipeline = false;
+
+    AppState {
+        db: db_pool,
+        settings,
+    }
+}
+
+#[tokio::test]
+async fn test_sync_appends_only_new_rows() {
+    let upstream: Upstream = Arc::new(Mutex::new(vec![row("a"), row("b"), row("a")]));
+    let mut app_state = create_test_app_state(start_upstream(upstream.clone()).await).await;
+    let runs_repo = RunsRepository::new(app_state.db.clone());
+
+    let first = sync_upstream(&app_state).await.unwrap();
+    assert_eq!(first.rows_downloaded, 3);
+    // The repeated row is only imported once
+    assert_eq!(first.rows_already_ingested, 1);
+    assert_eq!(first.upload.unwrap().rows_inserted, 2);
+    assert_eq!(runs_repo.count().await.unwrap(), 2);
+
+    let unchanged = sync_upstream(&app_state).await.unwrap();
+    assert_eq!(unchanged.rows_already_ingested, 3);
+    assert!(unchanged.upload.is_none());
+    assert_eq!(runs_repo.count().await.unwrap(), 2);
+
+    upstream.lock().unwrap().push(row("c"));
+    app_state.settings.upstream_sync.run_pipeline = true;
+    let grown = sync_upstream(&app_state).await.unwrap();
+    assert_eq!(grown.upload.unwrap().rows_inserted, 1);
+    let pipeline = grown.pipeline.unwrap();
+    assert!(pipeline.success);
+    assert!(!pipeline.stages.is_empty());
+    assert_eq!(runs_repo.count().await.unwrap(), 3);
+    // The stages see every run, the synced ones included
+    let process_its = pipeline.stages.iter().find(|stage| stage.stage == "process-its").unwrap();
+    assert_eq!(process_its.rows_in, Some(3));
+}
+
+#[tokio::test]
+async fn test_sync_skips_runs_ingested_before_hashes_were_kept() {
+    let upstream: Upstream = Arc::new(Mutex::new(vec![row("a"), row("b")]));
+    let app_state = create_test_app_state(start_upstream(upstream.clone()).await).await;
+    let runs_repo = RunsRepository::new(app_state.db.clone());
+
+    let existing: Vec<RunData> = serde_json::from_value(json!([row("a")])).unwrap();
+    for run in runs_from_data(existing) {
+        runs_repo.create(run).await.unwrap();
+    }
+
+    let outcome = sync_upstream(&app_state).await.unwrap();
+    assert_eq!(outcome.rows_already_ingested, 1);
+    assert_eq!(outcome.upload.unwrap().rows_inserted, 1);
+    assert_eq!(runs_repo.count().await.unwrap(), 2);
+}
+
+#[tokio::test]
+async fn test_upstream_sync_endpoint() {
+    let upstream: Upstream = Arc::new(Mutex::new(vec![row("a")]));
+    let app_state = create_test_app_state(start_upstream(upstream).await).await;
+    let app = create_router(app_state, Arc::new(ErrorCatalog::bundled().unwrap()));
+
+    let request = Request::builder().method("POST").uri("/api/admin/upstream-sync").body(Body::empty()).unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body: Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(body["rows_downloaded"], 1);
+    assert_eq!(body["upload"]["rows_inserted"], 1);
+}
+
+#[tokio::test]
+async fn test_synced_rows_are_filed_under_the_default_tenant() {
+    let upstream: Upstream = Arc::new(Mutex::new(vec![row("a")]));
+    let mut app_state = create_test_app_state(start_upstream(upstream).await).await;
+    app_state.settings.tenancy.enabled = true;
+    app_state.settings.tenancy.api_keys.insert("key-a".to_string(), "community-a".to_string());
+    let app = create_router(app_state.clone(), Arc::new(ErrorCatalog::bundled().unwrap()));
+
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/admin/upstream-sync")
+        .header("x-api-key", "key-a")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let tenants: Vec<String> = sqlx::query_scalar("SELECT tenant_id FROM runs").fetch_all(&app_state.db).await.unwrap();
+    assert_eq!(tenants, ["default"]);
+}